@@ -1,5 +1,6 @@
 const DEV_API_EDGES = {
   auth: "api/auth",
+  graphQuery: "api/graph_query",
 };
 
 export default DEV_API_EDGES;
@@ -0,0 +1,41 @@
+import DEV_API_EDGES from "../constants";
+import { apiPostRequestWithBody } from "../fetch";
+
+export type MatchedNode = {
+  uid: number;
+  node_type: string;
+  properties: Record<string, string>;
+};
+
+export type MatchedEdge = {
+  source_uid: number;
+  edge_name: string;
+  destination_uid: number;
+};
+
+export type QueryResult =
+  | {
+      result: "matched";
+      root_uid: number;
+      nodes: MatchedNode[];
+      edges: MatchedEdge[];
+    }
+  | { result: "missed" };
+
+// `span` holds the byte offsets of the part of the query that failed to parse
+export type QueryError = {
+  message: string;
+  span?: { start: number; end: number };
+};
+
+export const queryGraphWithText = async (
+  nodeUid: number,
+  query: string,
+): Promise<QueryResult | QueryError> => {
+  const body = JSON.stringify({
+    node_uid: nodeUid,
+    query: query,
+  });
+
+  return apiPostRequestWithBody(`${DEV_API_EDGES.graphQuery}/query`, body);
+};
@@ -43,7 +43,12 @@ class PropertyUpdate(Protocol):
     a protocol that captures
     StringPropertyUpdate,
     Int64PropertyUpdate,
-    UInt64PropertyUpdate
+    UInt64PropertyUpdate,
+    BoolPropertyUpdate,
+    DoublePropertyUpdate,
+    BytesPropertyUpdate,
+    TimestampPropertyUpdate,
+    StringSetPropertyUpdate
 
     and, thanks to `@runtime_checkable`, lets us do isinstance checks!
     """
@@ -4,6 +4,7 @@ import dataclasses
 from typing import Mapping, Sequence, cast
 
 from graplinc.grapl.api.graph.v1beta1 import types_pb2 as proto
+from python_proto.common import Timestamp
 from python_proto.grapl.common.v1beta1.messages import Uid
 from python_proto.serde import SerDe
 
@@ -244,6 +245,153 @@ class ImmutableStrProp(SerDe[proto.ImmutableStrProp]):
         return proto_immutable_str_prop
 
 
+@dataclasses.dataclass(
+    frozen=True,
+    slots=True,
+)
+class ImmutableBoolProp(SerDe[proto.ImmutableBoolProp]):
+    prop: bool
+    _proto_cls = proto.ImmutableBoolProp
+
+    @classmethod
+    def from_proto(
+        cls,
+        proto_immutable_bool_prop: proto.ImmutableBoolProp,
+    ) -> ImmutableBoolProp:
+        return ImmutableBoolProp(prop=proto_immutable_bool_prop.prop)
+
+    def into_proto(self) -> proto.ImmutableBoolProp:
+        proto_immutable_bool_prop = proto.ImmutableBoolProp()
+        proto_immutable_bool_prop.prop = self.prop
+        return proto_immutable_bool_prop
+
+
+@dataclasses.dataclass(
+    frozen=True,
+    slots=True,
+)
+class ImmutableDoubleProp(SerDe[proto.ImmutableDoubleProp]):
+    prop: float
+    _proto_cls = proto.ImmutableDoubleProp
+
+    @classmethod
+    def from_proto(
+        cls,
+        proto_immutable_double_prop: proto.ImmutableDoubleProp,
+    ) -> ImmutableDoubleProp:
+        return ImmutableDoubleProp(prop=proto_immutable_double_prop.prop)
+
+    def into_proto(self) -> proto.ImmutableDoubleProp:
+        proto_immutable_double_prop = proto.ImmutableDoubleProp()
+        proto_immutable_double_prop.prop = self.prop
+        return proto_immutable_double_prop
+
+
+@dataclasses.dataclass(
+    frozen=True,
+    slots=True,
+)
+class ImmutableBytesProp(SerDe[proto.ImmutableBytesProp]):
+    prop: bytes
+    _proto_cls = proto.ImmutableBytesProp
+
+    @classmethod
+    def from_proto(
+        cls,
+        proto_immutable_bytes_prop: proto.ImmutableBytesProp,
+    ) -> ImmutableBytesProp:
+        return ImmutableBytesProp(prop=proto_immutable_bytes_prop.prop)
+
+    def into_proto(self) -> proto.ImmutableBytesProp:
+        proto_immutable_bytes_prop = proto.ImmutableBytesProp()
+        proto_immutable_bytes_prop.prop = self.prop
+        return proto_immutable_bytes_prop
+
+
+@dataclasses.dataclass(
+    frozen=True,
+    slots=True,
+)
+class ImmutableTimestampProp(SerDe[proto.ImmutableTimestampProp]):
+    prop: Timestamp
+    _proto_cls = proto.ImmutableTimestampProp
+
+    @classmethod
+    def from_proto(
+        cls,
+        proto_immutable_timestamp_prop: proto.ImmutableTimestampProp,
+    ) -> ImmutableTimestampProp:
+        return ImmutableTimestampProp(prop=Timestamp.from_proto(proto_immutable_timestamp_prop.prop))
+
+    def into_proto(self) -> proto.ImmutableTimestampProp:
+        proto_immutable_timestamp_prop = proto.ImmutableTimestampProp()
+        proto_immutable_timestamp_prop.prop.CopyFrom(self.prop.into_proto())
+        return proto_immutable_timestamp_prop
+
+
+@dataclasses.dataclass(
+    frozen=True,
+    slots=True,
+)
+class IncrementOnlyTimestampProp(SerDe[proto.IncrementOnlyTimestampProp]):
+    prop: Timestamp
+    _proto_cls = proto.IncrementOnlyTimestampProp
+
+    @classmethod
+    def from_proto(
+        cls,
+        proto_increment_only_timestamp_prop: proto.IncrementOnlyTimestampProp,
+    ) -> IncrementOnlyTimestampProp:
+        return IncrementOnlyTimestampProp(prop=Timestamp.from_proto(proto_increment_only_timestamp_prop.prop))
+
+    def into_proto(self) -> proto.IncrementOnlyTimestampProp:
+        proto_increment_only_timestamp_prop = proto.IncrementOnlyTimestampProp()
+        proto_increment_only_timestamp_prop.prop.CopyFrom(self.prop.into_proto())
+        return proto_increment_only_timestamp_prop
+
+
+@dataclasses.dataclass(
+    frozen=True,
+    slots=True,
+)
+class DecrementOnlyTimestampProp(SerDe[proto.DecrementOnlyTimestampProp]):
+    prop: Timestamp
+    _proto_cls = proto.DecrementOnlyTimestampProp
+
+    @classmethod
+    def from_proto(
+        cls,
+        proto_decrement_only_timestamp_prop: proto.DecrementOnlyTimestampProp,
+    ) -> DecrementOnlyTimestampProp:
+        return DecrementOnlyTimestampProp(prop=Timestamp.from_proto(proto_decrement_only_timestamp_prop.prop))
+
+    def into_proto(self) -> proto.DecrementOnlyTimestampProp:
+        proto_decrement_only_timestamp_prop = proto.DecrementOnlyTimestampProp()
+        proto_decrement_only_timestamp_prop.prop.CopyFrom(self.prop.into_proto())
+        return proto_decrement_only_timestamp_prop
+
+
+@dataclasses.dataclass(
+    frozen=True,
+    slots=True,
+)
+class StringSetProp(SerDe[proto.StringSetProp]):
+    prop: frozenset[str]
+    _proto_cls = proto.StringSetProp
+
+    @classmethod
+    def from_proto(
+        cls,
+        proto_string_set_prop: proto.StringSetProp,
+    ) -> StringSetProp:
+        return StringSetProp(prop=frozenset(proto_string_set_prop.prop))
+
+    def into_proto(self) -> proto.StringSetProp:
+        proto_string_set_prop = proto.StringSetProp()
+        proto_string_set_prop.prop.extend(sorted(self.prop))
+        return proto_string_set_prop
+
+
 @dataclasses.dataclass(
     frozen=True,
     slots=True,
@@ -257,6 +405,13 @@ class NodeProperty(SerDe[proto.NodeProperty]):
         | DecrementOnlyIntProp
         | ImmutableIntProp
         | ImmutableStrProp
+        | ImmutableBoolProp
+        | ImmutableDoubleProp
+        | ImmutableBytesProp
+        | ImmutableTimestampProp
+        | IncrementOnlyTimestampProp
+        | DecrementOnlyTimestampProp
+        | StringSetProp
     )
     _proto_cls = proto.NodeProperty
 
@@ -300,6 +455,34 @@ class NodeProperty(SerDe[proto.NodeProperty]):
             return NodeProperty(
                 property_=ImmutableStrProp.from_proto(proto_node_property.immutable_str)
             )
+        elif proto_node_property.HasField("immutable_bool"):
+            return NodeProperty(
+                property_=ImmutableBoolProp.from_proto(proto_node_property.immutable_bool)
+            )
+        elif proto_node_property.HasField("immutable_double"):
+            return NodeProperty(
+                property_=ImmutableDoubleProp.from_proto(proto_node_property.immutable_double)
+            )
+        elif proto_node_property.HasField("immutable_bytes"):
+            return NodeProperty(
+                property_=ImmutableBytesProp.from_proto(proto_node_property.immutable_bytes)
+            )
+        elif proto_node_property.HasField("immutable_timestamp"):
+            return NodeProperty(
+                property_=ImmutableTimestampProp.from_proto(proto_node_property.immutable_timestamp)
+            )
+        elif proto_node_property.HasField("increment_only_timestamp"):
+            return NodeProperty(
+                property_=IncrementOnlyTimestampProp.from_proto(proto_node_property.increment_only_timestamp)
+            )
+        elif proto_node_property.HasField("decrement_only_timestamp"):
+            return NodeProperty(
+                property_=DecrementOnlyTimestampProp.from_proto(proto_node_property.decrement_only_timestamp)
+            )
+        elif proto_node_property.HasField("string_set"):
+            return NodeProperty(
+                property_=StringSetProp.from_proto(proto_node_property.string_set)
+            )
         else:
             raise Exception("Encountered unknown type")
 
@@ -333,6 +516,34 @@ class NodeProperty(SerDe[proto.NodeProperty]):
             proto_node_property.immutable_str.CopyFrom(
                 cast(proto.ImmutableStrProp, self.property_.into_proto())
             )
+        elif type(self.property_) is ImmutableBoolProp:
+            proto_node_property.immutable_bool.CopyFrom(
+                cast(proto.ImmutableBoolProp, self.property_.into_proto())
+            )
+        elif type(self.property_) is ImmutableDoubleProp:
+            proto_node_property.immutable_double.CopyFrom(
+                cast(proto.ImmutableDoubleProp, self.property_.into_proto())
+            )
+        elif type(self.property_) is ImmutableBytesProp:
+            proto_node_property.immutable_bytes.CopyFrom(
+                cast(proto.ImmutableBytesProp, self.property_.into_proto())
+            )
+        elif type(self.property_) is ImmutableTimestampProp:
+            proto_node_property.immutable_timestamp.CopyFrom(
+                cast(proto.ImmutableTimestampProp, self.property_.into_proto())
+            )
+        elif type(self.property_) is IncrementOnlyTimestampProp:
+            proto_node_property.increment_only_timestamp.CopyFrom(
+                cast(proto.IncrementOnlyTimestampProp, self.property_.into_proto())
+            )
+        elif type(self.property_) is DecrementOnlyTimestampProp:
+            proto_node_property.decrement_only_timestamp.CopyFrom(
+                cast(proto.DecrementOnlyTimestampProp, self.property_.into_proto())
+            )
+        elif type(self.property_) is StringSetProp:
+            proto_node_property.string_set.CopyFrom(
+                cast(proto.StringSetProp, self.property_.into_proto())
+            )
         else:
             raise Exception("Encountered unknown type")
         return proto_node_property
@@ -108,6 +108,136 @@ class Int64PropertyUpdate(SerDe[proto.Int64PropertyUpdate]):
         return proto_value
 
 
+@dataclasses.dataclass(frozen=True, slots=True)
+class BoolPropertyUpdate(SerDe[proto.BoolPropertyUpdate]):
+    uid: grapl_common_msgs.Uid
+    property_name: grapl_common_msgs.PropertyName
+
+    _proto_cls = proto.BoolPropertyUpdate
+
+    @classmethod
+    def from_proto(
+        cls,
+        proto_value: proto.BoolPropertyUpdate,
+    ) -> BoolPropertyUpdate:
+        return cls(
+            uid=grapl_common_msgs.Uid.from_proto(proto_value.uid),
+            property_name=grapl_common_msgs.PropertyName.from_proto(
+                proto_value.property_name
+            ),
+        )
+
+    def into_proto(self) -> proto.BoolPropertyUpdate:
+        proto_value = self.new_proto()
+        proto_value.uid.CopyFrom(self.uid.into_proto())
+        proto_value.property_name.CopyFrom(self.property_name.into_proto())
+        return proto_value
+
+
+@dataclasses.dataclass(frozen=True, slots=True)
+class DoublePropertyUpdate(SerDe[proto.DoublePropertyUpdate]):
+    uid: grapl_common_msgs.Uid
+    property_name: grapl_common_msgs.PropertyName
+
+    _proto_cls = proto.DoublePropertyUpdate
+
+    @classmethod
+    def from_proto(
+        cls,
+        proto_value: proto.DoublePropertyUpdate,
+    ) -> DoublePropertyUpdate:
+        return cls(
+            uid=grapl_common_msgs.Uid.from_proto(proto_value.uid),
+            property_name=grapl_common_msgs.PropertyName.from_proto(
+                proto_value.property_name
+            ),
+        )
+
+    def into_proto(self) -> proto.DoublePropertyUpdate:
+        proto_value = self.new_proto()
+        proto_value.uid.CopyFrom(self.uid.into_proto())
+        proto_value.property_name.CopyFrom(self.property_name.into_proto())
+        return proto_value
+
+
+@dataclasses.dataclass(frozen=True, slots=True)
+class BytesPropertyUpdate(SerDe[proto.BytesPropertyUpdate]):
+    uid: grapl_common_msgs.Uid
+    property_name: grapl_common_msgs.PropertyName
+
+    _proto_cls = proto.BytesPropertyUpdate
+
+    @classmethod
+    def from_proto(
+        cls,
+        proto_value: proto.BytesPropertyUpdate,
+    ) -> BytesPropertyUpdate:
+        return cls(
+            uid=grapl_common_msgs.Uid.from_proto(proto_value.uid),
+            property_name=grapl_common_msgs.PropertyName.from_proto(
+                proto_value.property_name
+            ),
+        )
+
+    def into_proto(self) -> proto.BytesPropertyUpdate:
+        proto_value = self.new_proto()
+        proto_value.uid.CopyFrom(self.uid.into_proto())
+        proto_value.property_name.CopyFrom(self.property_name.into_proto())
+        return proto_value
+
+
+@dataclasses.dataclass(frozen=True, slots=True)
+class TimestampPropertyUpdate(SerDe[proto.TimestampPropertyUpdate]):
+    uid: grapl_common_msgs.Uid
+    property_name: grapl_common_msgs.PropertyName
+
+    _proto_cls = proto.TimestampPropertyUpdate
+
+    @classmethod
+    def from_proto(
+        cls,
+        proto_value: proto.TimestampPropertyUpdate,
+    ) -> TimestampPropertyUpdate:
+        return cls(
+            uid=grapl_common_msgs.Uid.from_proto(proto_value.uid),
+            property_name=grapl_common_msgs.PropertyName.from_proto(
+                proto_value.property_name
+            ),
+        )
+
+    def into_proto(self) -> proto.TimestampPropertyUpdate:
+        proto_value = self.new_proto()
+        proto_value.uid.CopyFrom(self.uid.into_proto())
+        proto_value.property_name.CopyFrom(self.property_name.into_proto())
+        return proto_value
+
+
+@dataclasses.dataclass(frozen=True, slots=True)
+class StringSetPropertyUpdate(SerDe[proto.StringSetPropertyUpdate]):
+    uid: grapl_common_msgs.Uid
+    property_name: grapl_common_msgs.PropertyName
+
+    _proto_cls = proto.StringSetPropertyUpdate
+
+    @classmethod
+    def from_proto(
+        cls,
+        proto_value: proto.StringSetPropertyUpdate,
+    ) -> StringSetPropertyUpdate:
+        return cls(
+            uid=grapl_common_msgs.Uid.from_proto(proto_value.uid),
+            property_name=grapl_common_msgs.PropertyName.from_proto(
+                proto_value.property_name
+            ),
+        )
+
+    def into_proto(self) -> proto.StringSetPropertyUpdate:
+        proto_value = self.new_proto()
+        proto_value.uid.CopyFrom(self.uid.into_proto())
+        proto_value.property_name.CopyFrom(self.property_name.into_proto())
+        return proto_value
+
+
 @dataclasses.dataclass(frozen=True, slots=True)
 class EdgeUpdate(SerDe[proto.EdgeUpdate]):
     src_uid: grapl_common_msgs.Uid
@@ -143,7 +273,15 @@ class EdgeUpdate(SerDe[proto.EdgeUpdate]):
 
 
 UpdateInner = (
-    StringPropertyUpdate | UInt64PropertyUpdate | Int64PropertyUpdate | EdgeUpdate
+    StringPropertyUpdate
+    | UInt64PropertyUpdate
+    | Int64PropertyUpdate
+    | EdgeUpdate
+    | BoolPropertyUpdate
+    | DoublePropertyUpdate
+    | BytesPropertyUpdate
+    | TimestampPropertyUpdate
+    | StringSetPropertyUpdate
 )
 
 
@@ -173,6 +311,16 @@ class Update(SerDe[proto.Update]):
                 )
             case "edge":
                 return cls(inner=EdgeUpdate.from_proto(proto_value.edge))
+            case "bool_property":
+                return cls(inner=BoolPropertyUpdate.from_proto(proto_value.bool_property))
+            case "double_property":
+                return cls(inner=DoublePropertyUpdate.from_proto(proto_value.double_property))
+            case "bytes_property":
+                return cls(inner=BytesPropertyUpdate.from_proto(proto_value.bytes_property))
+            case "timestamp_property":
+                return cls(inner=TimestampPropertyUpdate.from_proto(proto_value.timestamp_property))
+            case "string_set_property":
+                return cls(inner=StringSetPropertyUpdate.from_proto(proto_value.string_set_property))
 
         raise Exception(f"Unknown variant: {field_name}")
 
@@ -187,6 +335,16 @@ class Update(SerDe[proto.Update]):
                 msg.int64_property.CopyFrom(inner.into_proto())
             case EdgeUpdate() as inner:
                 msg.edge.CopyFrom(inner.into_proto())
+            case BoolPropertyUpdate() as inner:
+                msg.bool_property.CopyFrom(inner.into_proto())
+            case DoublePropertyUpdate() as inner:
+                msg.double_property.CopyFrom(inner.into_proto())
+            case BytesPropertyUpdate() as inner:
+                msg.bytes_property.CopyFrom(inner.into_proto())
+            case TimestampPropertyUpdate() as inner:
+                msg.timestamp_property.CopyFrom(inner.into_proto())
+            case StringSetPropertyUpdate() as inner:
+                msg.string_set_property.CopyFrom(inner.into_proto())
             case _:
                 raise Exception(f"Unknown variant: {self.inner}")
 
@@ -5,6 +5,7 @@ from typing import Mapping, Sequence
 import hypothesis.strategies as st
 from python_proto.api.graph.v1beta1.messages import (
     DecrementOnlyIntProp,
+    DecrementOnlyTimestampProp,
     DecrementOnlyUintProp,
     Edge,
     EdgeList,
@@ -14,16 +15,22 @@ from python_proto.api.graph.v1beta1.messages import (
     IdentifiedGraph,
     IdentifiedNode,
     IdStrategy,
+    ImmutableBoolProp,
+    ImmutableBytesProp,
+    ImmutableDoubleProp,
     ImmutableIntProp,
     ImmutableStrProp,
+    ImmutableTimestampProp,
     ImmutableUintProp,
     IncrementOnlyIntProp,
+    IncrementOnlyTimestampProp,
     IncrementOnlyUintProp,
     Lens,
     NodeDescription,
     NodeProperty,
     Session,
     Static,
+    StringSetProp,
 )
 from python_proto.common import Duration, Timestamp, Uuid
 from python_proto.grapl.common.v1beta1 import messages as grapl_common_messages
@@ -248,6 +255,48 @@ def immutable_str_props(
     return st.builds(ImmutableStrProp, prop=props)
 
 
+def immutable_bool_props(
+    props: st.SearchStrategy[bool] = st.booleans(),
+) -> st.SearchStrategy[ImmutableBoolProp]:
+    return st.builds(ImmutableBoolProp, prop=props)
+
+
+def immutable_double_props(
+    props: st.SearchStrategy[float] = st.floats(allow_nan=False),
+) -> st.SearchStrategy[ImmutableDoubleProp]:
+    return st.builds(ImmutableDoubleProp, prop=props)
+
+
+def immutable_bytes_props(
+    props: st.SearchStrategy[bytes] = st.binary(),
+) -> st.SearchStrategy[ImmutableBytesProp]:
+    return st.builds(ImmutableBytesProp, prop=props)
+
+
+def immutable_timestamp_props(
+    props: st.SearchStrategy[Timestamp] = timestamps(),
+) -> st.SearchStrategy[ImmutableTimestampProp]:
+    return st.builds(ImmutableTimestampProp, prop=props)
+
+
+def increment_only_timestamp_props(
+    props: st.SearchStrategy[Timestamp] = timestamps(),
+) -> st.SearchStrategy[IncrementOnlyTimestampProp]:
+    return st.builds(IncrementOnlyTimestampProp, prop=props)
+
+
+def decrement_only_timestamp_props(
+    props: st.SearchStrategy[Timestamp] = timestamps(),
+) -> st.SearchStrategy[DecrementOnlyTimestampProp]:
+    return st.builds(DecrementOnlyTimestampProp, prop=props)
+
+
+def string_set_props(
+    props: st.SearchStrategy[frozenset[str]] = st.frozensets(st.text()),
+) -> st.SearchStrategy[StringSetProp]:
+    return st.builds(StringSetProp, prop=props)
+
+
 def node_properties(
     properties: st.SearchStrategy[
         (
@@ -258,6 +307,13 @@ def node_properties(
             | DecrementOnlyIntProp
             | ImmutableIntProp
             | ImmutableStrProp
+            | ImmutableBoolProp
+            | ImmutableDoubleProp
+            | ImmutableBytesProp
+            | ImmutableTimestampProp
+            | IncrementOnlyTimestampProp
+            | DecrementOnlyTimestampProp
+            | StringSetProp
         )
     ] = st.one_of(
         increment_only_uint_props(),
@@ -267,6 +323,13 @@ def node_properties(
         decrement_only_int_props(),
         immutable_int_props(),
         immutable_str_props(),
+        immutable_bool_props(),
+        immutable_double_props(),
+        immutable_bytes_props(),
+        immutable_timestamp_props(),
+        increment_only_timestamp_props(),
+        decrement_only_timestamp_props(),
+        string_set_props(),
     )
 ) -> st.SearchStrategy[NodeProperty]:
     return st.builds(NodeProperty, property_=properties)
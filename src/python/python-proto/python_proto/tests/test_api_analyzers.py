@@ -44,6 +44,51 @@ def int64_property_updates(
     )
 
 
+def bool_property_updates(
+    uid: st.SearchStrategy[grapl_common_msgs.Uid] = strategies.uids(),
+    property_name: st.SearchStrategy[grapl_common_msgs.PropertyName] = property_names(),
+) -> st.SearchStrategy[analyzer_msgs.BoolPropertyUpdate]:
+    return st.builds(
+        analyzer_msgs.BoolPropertyUpdate, uid=uid, property_name=property_name
+    )
+
+
+def double_property_updates(
+    uid: st.SearchStrategy[grapl_common_msgs.Uid] = strategies.uids(),
+    property_name: st.SearchStrategy[grapl_common_msgs.PropertyName] = property_names(),
+) -> st.SearchStrategy[analyzer_msgs.DoublePropertyUpdate]:
+    return st.builds(
+        analyzer_msgs.DoublePropertyUpdate, uid=uid, property_name=property_name
+    )
+
+
+def bytes_property_updates(
+    uid: st.SearchStrategy[grapl_common_msgs.Uid] = strategies.uids(),
+    property_name: st.SearchStrategy[grapl_common_msgs.PropertyName] = property_names(),
+) -> st.SearchStrategy[analyzer_msgs.BytesPropertyUpdate]:
+    return st.builds(
+        analyzer_msgs.BytesPropertyUpdate, uid=uid, property_name=property_name
+    )
+
+
+def timestamp_property_updates(
+    uid: st.SearchStrategy[grapl_common_msgs.Uid] = strategies.uids(),
+    property_name: st.SearchStrategy[grapl_common_msgs.PropertyName] = property_names(),
+) -> st.SearchStrategy[analyzer_msgs.TimestampPropertyUpdate]:
+    return st.builds(
+        analyzer_msgs.TimestampPropertyUpdate, uid=uid, property_name=property_name
+    )
+
+
+def string_set_property_updates(
+    uid: st.SearchStrategy[grapl_common_msgs.Uid] = strategies.uids(),
+    property_name: st.SearchStrategy[grapl_common_msgs.PropertyName] = property_names(),
+) -> st.SearchStrategy[analyzer_msgs.StringSetPropertyUpdate]:
+    return st.builds(
+        analyzer_msgs.StringSetPropertyUpdate, uid=uid, property_name=property_name
+    )
+
+
 def edge_updates(
     src_uid: st.SearchStrategy[grapl_common_msgs.Uid] = strategies.uids(),
     dst_uid: st.SearchStrategy[grapl_common_msgs.Uid] = strategies.uids(),
@@ -65,6 +110,11 @@ def updates(
         uint64_property_updates(),
         int64_property_updates(),
         edge_updates(),
+        bool_property_updates(),
+        double_property_updates(),
+        bytes_property_updates(),
+        timestamp_property_updates(),
+        string_set_property_updates(),
     )
 ) -> st.SearchStrategy[analyzer_msgs.Update]:
     return st.builds(analyzer_msgs.Update, inner=inner)
@@ -5,6 +5,7 @@ pytest.register_assert_rewrite("python_proto.tests.helpers")
 from python_proto.tests.helpers import check_encode_decode_invariant
 from python_proto.tests.strategies import (
     decrement_only_int_props,
+    decrement_only_timestamp_props,
     decrement_only_uint_props,
     edge_lists,
     edges,
@@ -12,16 +13,22 @@ from python_proto.tests.strategies import (
     id_strategies,
     identified_graphs,
     identified_nodes,
+    immutable_bool_props,
+    immutable_bytes_props,
+    immutable_double_props,
     immutable_int_props,
     immutable_str_props,
+    immutable_timestamp_props,
     immutable_uint_props,
     increment_only_int_props,
+    increment_only_timestamp_props,
     increment_only_uint_props,
     lenses,
     node_descriptions,
     node_properties,
     sessions,
     statics,
+    string_set_props,
 )
 
 
@@ -95,3 +102,31 @@ def test_session_encode_decode() -> None:
 
 def test_static_encode_decode() -> None:
     check_encode_decode_invariant(statics())
+
+
+def test_immutable_bool_prop_encode_decode() -> None:
+    check_encode_decode_invariant(immutable_bool_props())
+
+
+def test_immutable_double_prop_encode_decode() -> None:
+    check_encode_decode_invariant(immutable_double_props())
+
+
+def test_immutable_bytes_prop_encode_decode() -> None:
+    check_encode_decode_invariant(immutable_bytes_props())
+
+
+def test_immutable_timestamp_prop_encode_decode() -> None:
+    check_encode_decode_invariant(immutable_timestamp_props())
+
+
+def test_increment_only_timestamp_prop_encode_decode() -> None:
+    check_encode_decode_invariant(increment_only_timestamp_props())
+
+
+def test_decrement_only_timestamp_prop_encode_decode() -> None:
+    check_encode_decode_invariant(decrement_only_timestamp_props())
+
+
+def test_string_set_prop_encode_decode() -> None:
+    check_encode_decode_invariant(string_set_props())
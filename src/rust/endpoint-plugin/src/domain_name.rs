@@ -0,0 +1,30 @@
+use derive_dynamic_node::{
+    GraplStaticId,
+    NodeDescription,
+};
+use rust_proto::graplinc::grapl::api::graph::v1beta1::{
+    IdStrategy,
+    NodeDescription,
+    NodeProperty,
+    Static,
+};
+
+#[derive(NodeDescription, GraplStaticId)]
+pub struct DomainName {
+    #[grapl(static_id, immutable)]
+    domain_name: String,
+    #[grapl(decrement)]
+    first_seen_timestamp: u64,
+    #[grapl(increment)]
+    last_seen_timestamp: u64,
+}
+
+impl IDomainNameNode for DomainNameNode {
+    fn get_mut_dynamic_node(&mut self) -> &mut NodeDescription {
+        &mut self.dynamic_node
+    }
+
+    fn get_dynamic_node(&self) -> &NodeDescription {
+        &self.dynamic_node
+    }
+}
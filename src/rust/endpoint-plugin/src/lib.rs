@@ -1,4 +1,5 @@
 pub mod asset;
+pub mod domain_name;
 pub mod error;
 pub mod file;
 pub mod ip_address;
@@ -15,6 +16,11 @@ pub use crate::{
         AssetNode,
         IAssetNode,
     },
+    domain_name::{
+        DomainName,
+        DomainNameNode,
+        IDomainNameNode,
+    },
     error::Error,
     file::{
         File,
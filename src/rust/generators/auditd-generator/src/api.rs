@@ -0,0 +1,55 @@
+use rust_proto::graplinc::grapl::api::{
+    graph::v1beta1::GraphDescription,
+    plugin_sdk::generators::v1beta1::{
+        server::GeneratorApi,
+        GeneratedGraph,
+        RunGeneratorRequest,
+        RunGeneratorResponse,
+    },
+};
+
+use crate::{
+    error::AuditdGeneratorError,
+    models,
+    parser,
+};
+
+pub struct AuditdGenerator {}
+
+#[async_trait::async_trait]
+impl GeneratorApi for AuditdGenerator {
+    type Error = AuditdGeneratorError;
+
+    /// A single audit event is spread across several records (SYSCALL,
+    /// EXECVE, CWD, PATH, ...), so a request may carry any number of lines.
+    /// Records are reassembled by serial number within the request; an event
+    /// split across requests can't be reassembled.
+    #[tracing::instrument(skip(self, request), err)]
+    async fn run_generator(
+        &self,
+        request: RunGeneratorRequest,
+    ) -> Result<RunGeneratorResponse, Self::Error> {
+        let input_utf8 = std::str::from_utf8(&request.data)?;
+
+        let mut graph_description = GraphDescription::new();
+        for event in parser::parse_events(input_utf8)? {
+            // One event we can't build a graph from (e.g. from a host without
+            // `name_format` configured) shouldn't drop the rest of the batch.
+            match models::generate_graph_from_event(&event) {
+                Ok(Some(graph)) => graph_description.merge(&graph),
+                Ok(None) => {}
+                Err(e) => {
+                    tracing::warn!(
+                        message = "skipping audit event",
+                        serial = event.serial,
+                        error = %e,
+                    );
+                }
+            }
+        }
+
+        Ok(RunGeneratorResponse {
+            generated_graph: GeneratedGraph { graph_description },
+        })
+    }
+}
@@ -0,0 +1,26 @@
+use rust_proto::graplinc::grapl::api::protocol::status::Status;
+use thiserror::Error;
+
+/// This represents all possible errors that can occur in this generator.
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum AuditdGeneratorError {
+    #[error("error converting bytes to utf-8 {0}")]
+    Utf8Error(#[from] std::str::Utf8Error),
+
+    #[error("malformed audit record: {0}")]
+    MalformedRecord(String),
+
+    /// auditd only records the host when `name_format` is configured
+    #[error("audit event {0} is missing the `node` field")]
+    MissingNode(u64),
+
+    #[error("error configuring tracing {0}")]
+    SetupTracingError(#[from] grapl_tracing::SetupTracingError),
+}
+
+impl From<AuditdGeneratorError> for Status {
+    fn from(e: AuditdGeneratorError) -> Self {
+        Status::unknown(e.to_string())
+    }
+}
@@ -0,0 +1,69 @@
+use rust_proto::graplinc::grapl::api::graph::v1beta1::GraphDescription;
+
+use crate::{
+    error::AuditdGeneratorError,
+    parser::AuditEvent,
+};
+
+mod file;
+mod process;
+
+#[tracing::instrument(err, skip(event))]
+pub(crate) fn generate_graph_from_event(
+    event: &AuditEvent,
+) -> Result<Option<GraphDescription>, AuditdGeneratorError> {
+    // We only handle syscall events, and only those which succeeded
+    let syscall = match event.record("SYSCALL") {
+        Some(syscall) if syscall.raw("success") != Some("no") => syscall,
+        _ => return Ok(None),
+    };
+
+    let has_execve = event.record("EXECVE").is_some();
+    let file_changes: Vec<_> = event
+        .records_of_type("PATH")
+        .filter(|path| matches!(path.raw("nametype"), Some("CREATE" | "DELETE")))
+        .collect();
+
+    if !has_execve && file_changes.is_empty() {
+        return Ok(None);
+    }
+
+    let asset_id = event
+        .node
+        .as_deref()
+        .ok_or(AuditdGeneratorError::MissingNode(event.serial))?;
+
+    let mut graph = GraphDescription::new();
+
+    if has_execve {
+        graph.merge(&process::generate_process_create_subgraph(
+            asset_id, event, syscall,
+        ));
+    }
+
+    let cwd = event.record("CWD").and_then(|cwd| cwd.string("cwd"));
+    for path in file_changes {
+        if let Some(file_graph) =
+            file::generate_file_change_subgraph(asset_id, event, syscall, path, cwd.as_deref())
+        {
+            graph.merge(&file_graph);
+        }
+    }
+
+    tracing::debug!(
+        message = "completed graph generation",
+        node_count = graph.nodes.len(),
+        edge_count = graph.edges.len(),
+    );
+
+    Ok(Some(graph))
+}
+
+/// Gets the name of the process given a path to the executable.
+fn get_image_name(image_path: &str) -> String {
+    image_path
+        .rsplit('/')
+        .next()
+        .unwrap_or(image_path)
+        .to_string()
+}
@@ -0,0 +1,102 @@
+use endpoint_plugin::{
+    AssetNode,
+    FileNode,
+    IAssetNode,
+    IFileNode,
+    IProcessNode,
+    ProcessNode,
+};
+use rust_proto::graplinc::grapl::api::graph::v1beta1::GraphDescription;
+
+use crate::{
+    models::get_image_name,
+    parser::{
+        AuditEvent,
+        AuditRecord,
+    },
+};
+
+/// Creates a subgraph describing a file created or deleted by a syscall.
+///
+/// The subgraph generation for a `PATH` record includes the following:
+/// * A `Process` node - denotes the process that made the syscall
+/// * A subject `File` node - the file that was created or deleted
+///
+/// Returns `None` for `PATH` records which neither create nor delete a file.
+#[tracing::instrument(skip(event, syscall, path))]
+pub(crate) fn generate_file_change_subgraph(
+    asset_id: &str,
+    event: &AuditEvent,
+    syscall: &AuditRecord,
+    path: &AuditRecord,
+    cwd: Option<&str>,
+) -> Option<GraphDescription> {
+    tracing::trace!("generating graph from event");
+
+    let timestamp = event.timestamp;
+    let name = path.string("name")?;
+
+    // Paths are recorded as the process passed them, so relative paths are
+    // resolved against the process' working directory.
+    let file_path = match cwd {
+        Some(cwd) if !name.starts_with('/') => format!("{}/{}", cwd.trim_end_matches('/'), name),
+        _ => name,
+    };
+
+    let mut graph = GraphDescription::new();
+
+    let mut asset = AssetNode::new(AssetNode::static_strategy());
+    asset.with_asset_id(asset_id).with_hostname(asset_id);
+
+    let mut process = ProcessNode::new(ProcessNode::session_strategy());
+    process
+        .with_asset_id(asset_id)
+        .with_last_seen_timestamp(timestamp);
+
+    if let Some(pid) = syscall.number::<u64>("pid") {
+        process.with_process_id(pid);
+    }
+
+    if let Some(exe) = syscall.string("exe") {
+        process.with_process_name(get_image_name(&exe));
+    }
+
+    let mut file = FileNode::new(FileNode::session_strategy());
+    file.with_asset_id(asset_id).with_file_path(file_path);
+
+    let edge_name = match path.raw("nametype") {
+        Some("CREATE") => {
+            file.with_created_timestamp(timestamp);
+            "created_files"
+        }
+        Some("DELETE") => {
+            file.with_deleted_timestamp(timestamp);
+            "deleted_files"
+        }
+        _ => return None,
+    };
+
+    if let Some(inode) = path.number::<u64>("inode") {
+        file.with_file_inode(inode);
+    }
+
+    graph.add_edge(
+        "process_asset",
+        process.clone_node_key(),
+        asset.clone_node_key(),
+    );
+
+    graph.add_edge(edge_name, process.clone_node_key(), file.clone_node_key());
+
+    graph.add_edge(
+        "files_on_asset",
+        asset.clone_node_key(),
+        file.clone_node_key(),
+    );
+
+    graph.add_node(asset);
+    graph.add_node(process);
+    graph.add_node(file);
+
+    Some(graph)
+}
@@ -0,0 +1,118 @@
+use endpoint_plugin::{
+    AssetNode,
+    FileNode,
+    IAssetNode,
+    IFileNode,
+    IProcessNode,
+    ProcessNode,
+};
+use rust_proto::graplinc::grapl::api::graph::v1beta1::GraphDescription;
+
+use crate::{
+    models::get_image_name,
+    parser::{
+        AuditEvent,
+        AuditRecord,
+    },
+};
+
+/// Creates a graph describing an `execve` audit event.
+///
+/// Graph generation for an `execve` event includes the following:
+/// * An `Asset` node - indicating the asset in which the process was created
+/// * A parent `Process` node - indicating the process that created the subject process
+/// * A subject `Process` node - indicating the process created by the `execve`
+/// * A process `File` node - indicating the file executed in creating the new process
+///
+/// Processes are identified the same way sysmon-generator identifies them:
+/// by asset, process id and creation time.
+#[tracing::instrument(skip(event, syscall))]
+pub(crate) fn generate_process_create_subgraph(
+    asset_id: &str,
+    event: &AuditEvent,
+    syscall: &AuditRecord,
+) -> GraphDescription {
+    tracing::trace!("generating graph from event");
+
+    let timestamp = event.timestamp;
+    let mut graph = GraphDescription::new();
+
+    let mut asset = AssetNode::new(AssetNode::static_strategy());
+    asset.with_asset_id(asset_id).with_hostname(asset_id);
+
+    let exe = syscall.string("exe");
+
+    let command_line = event
+        .execve_arguments()
+        .map(|arguments| arguments.join(" "))
+        .unwrap_or_default();
+
+    let process_name = exe
+        .as_deref()
+        .map(get_image_name)
+        .or_else(|| syscall.string("comm"))
+        .unwrap_or_default();
+
+    let mut child = ProcessNode::new(ProcessNode::session_strategy());
+    child
+        .with_asset_id(asset_id)
+        .with_process_name(process_name)
+        .with_process_command_line(command_line)
+        .with_operating_system("Linux")
+        .with_created_timestamp(timestamp);
+
+    if let Some(pid) = syscall.number::<u64>("pid") {
+        child.with_process_id(pid);
+    }
+
+    if let Some(ppid) = syscall.number::<u64>("ppid") {
+        let mut parent = ProcessNode::new(ProcessNode::session_strategy());
+        parent
+            .with_asset_id(asset_id)
+            .with_process_id(ppid)
+            .with_last_seen_timestamp(timestamp);
+
+        graph.add_edge(
+            "process_asset",
+            parent.clone_node_key(),
+            asset.clone_node_key(),
+        );
+
+        graph.add_edge("children", parent.clone_node_key(), child.clone_node_key());
+
+        graph.add_node(parent);
+    }
+
+    if let Some(exe) = exe {
+        let mut child_exe = FileNode::new(FileNode::session_strategy());
+        child_exe
+            .with_asset_id(asset_id)
+            .with_last_seen_timestamp(timestamp)
+            .with_file_path(exe);
+
+        graph.add_edge(
+            "binary_file",
+            child.clone_node_key(),
+            child_exe.clone_node_key(),
+        );
+
+        graph.add_edge(
+            "files_on_asset",
+            asset.clone_node_key(),
+            child_exe.clone_node_key(),
+        );
+
+        graph.add_node(child_exe);
+    }
+
+    graph.add_edge(
+        "process_asset",
+        child.clone_node_key(),
+        asset.clone_node_key(),
+    );
+
+    graph.add_node(asset);
+    graph.add_node(child);
+
+    graph
+}
@@ -0,0 +1,337 @@
+use std::{
+    collections::HashMap,
+    str::FromStr,
+};
+
+use crate::error::AuditdGeneratorError;
+
+/// A single line of an audit log, e.g.
+///
+/// ```text
+/// node=web-1 type=CWD msg=audit(1603400719.250:1234): cwd="/home/grapl"
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditRecord {
+    pub node: Option<String>,
+    pub record_type: String,
+    /// Milliseconds since the UNIX epoch
+    pub timestamp: u64,
+    pub serial: u64,
+    fields: HashMap<String, String>,
+}
+
+impl AuditRecord {
+    pub fn parse(line: &str) -> Result<Self, AuditdGeneratorError> {
+        // With `log_format = ENRICHED`, auditd appends interpreted fields
+        // (e.g. `UID="root"`) after a group separator. We only need the raw
+        // fields.
+        let line = line.split('\u{1d}').next().unwrap_or_default();
+
+        let mut node = None;
+        let mut record_type = None;
+        let mut timestamp_and_serial = None;
+        let mut fields = HashMap::new();
+
+        for token in tokenize(line) {
+            let (key, value) = match token.split_once('=') {
+                Some(pair) => pair,
+                None => continue,
+            };
+
+            match key {
+                "node" if record_type.is_none() => node = Some(value.to_string()),
+                "type" if record_type.is_none() => record_type = Some(value.to_string()),
+                "msg" if timestamp_and_serial.is_none() => {
+                    timestamp_and_serial = Some(parse_audit_header(value)?)
+                }
+                _ => {
+                    fields.insert(key.to_string(), value.to_string());
+                }
+            }
+        }
+
+        let record_type = record_type.ok_or_else(|| {
+            AuditdGeneratorError::MalformedRecord(format!("missing type: {line}"))
+        })?;
+        let (timestamp, serial) = timestamp_and_serial
+            .ok_or_else(|| AuditdGeneratorError::MalformedRecord(format!("missing msg: {line}")))?;
+
+        Ok(Self {
+            node,
+            record_type,
+            timestamp,
+            serial,
+            fields,
+        })
+    }
+
+    /// Returns the field exactly as auditd wrote it.
+    pub fn raw(&self, name: &str) -> Option<&str> {
+        self.fields.get(name).map(String::as_str)
+    }
+
+    /// Returns a numeric field, such as `pid` or `inode`.
+    pub fn number<T: FromStr>(&self, name: &str) -> Option<T> {
+        self.raw(name).and_then(|value| value.parse().ok())
+    }
+
+    /// Returns an "untrusted string" field, such as `exe`, `cwd` or `name`.
+    ///
+    /// auditd quotes these when they are plain printable text, and otherwise
+    /// hex-encodes them (e.g. when they contain spaces, quotes or control
+    /// characters). `(null)` marks a missing value.
+    pub fn string(&self, name: &str) -> Option<String> {
+        self.raw(name).and_then(decode_untrusted)
+    }
+}
+
+/// Parses the `audit(1603400719.250:1234):` event header into milliseconds
+/// since the UNIX epoch and the event's serial number.
+fn parse_audit_header(value: &str) -> Result<(u64, u64), AuditdGeneratorError> {
+    let malformed = || AuditdGeneratorError::MalformedRecord(format!("bad msg header: {value}"));
+
+    let inner = value
+        .strip_prefix("audit(")
+        .and_then(|rest| rest.trim_end_matches(':').strip_suffix(')'))
+        .ok_or_else(malformed)?;
+
+    let (timestamp, serial) = inner.split_once(':').ok_or_else(malformed)?;
+    let (seconds, millis) = timestamp.split_once('.').unwrap_or((timestamp, "0"));
+
+    let seconds: u64 = seconds.parse().map_err(|_| malformed())?;
+    let millis: u64 = millis.parse().map_err(|_| malformed())?;
+    let serial: u64 = serial.parse().map_err(|_| malformed())?;
+
+    Ok((seconds * 1000 + millis, serial))
+}
+
+/// Splits a record on spaces, keeping quoted values (which may contain spaces
+/// in some record types, e.g. `msg='op=login acct="root"'`) intact.
+fn tokenize(line: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut quote = None;
+    let mut start = 0;
+
+    for (index, c) in line.char_indices() {
+        match (c, quote) {
+            ('"' | '\'', None) => quote = Some(c),
+            (c, Some(q)) if c == q => quote = None,
+            (' ', None) => {
+                if index > start {
+                    tokens.push(&line[start..index]);
+                }
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+
+    if line.len() > start {
+        tokens.push(&line[start..]);
+    }
+
+    tokens
+}
+
+fn decode_untrusted(raw: &str) -> Option<String> {
+    if raw == "(null)" || raw == "(none)" {
+        return None;
+    }
+
+    if let Some(quoted) = raw
+        .strip_prefix('"')
+        .and_then(|rest| rest.strip_suffix('"'))
+    {
+        return Some(quoted.to_string());
+    }
+
+    Some(decode_hex(raw).unwrap_or_else(|| raw.to_string()))
+}
+
+/// Decodes a hex-encoded value. `PROCTITLE` separates arguments with NUL
+/// bytes, which are replaced by spaces.
+fn decode_hex(raw: &str) -> Option<String> {
+    if raw.is_empty() || raw.len() % 2 != 0 || !raw.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    let bytes: Vec<u8> = (0..raw.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(&raw[index..index + 2], 16))
+        .collect::<Result<_, _>>()
+        .ok()?;
+
+    let decoded = String::from_utf8_lossy(&bytes);
+    Some(decoded.trim_end_matches('\0').replace('\0', " "))
+}
+
+/// All of the records auditd wrote for one event, i.e. sharing one serial
+/// number.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditEvent {
+    pub node: Option<String>,
+    pub timestamp: u64,
+    pub serial: u64,
+    pub records: Vec<AuditRecord>,
+}
+
+impl AuditEvent {
+    /// Returns the first record of the given type, e.g. `SYSCALL`.
+    pub fn record(&self, record_type: &str) -> Option<&AuditRecord> {
+        self.records
+            .iter()
+            .find(|record| record.record_type == record_type)
+    }
+
+    pub fn records_of_type<'a>(
+        &'a self,
+        record_type: &'a str,
+    ) -> impl Iterator<Item = &'a AuditRecord> + 'a {
+        self.records
+            .iter()
+            .filter(move |record| record.record_type == record_type)
+    }
+
+    /// Returns the arguments of an `EXECVE` record.
+    ///
+    /// Arguments longer than auditd's record size limit are split into
+    /// `a1[0]`, `a1[1]`, ... chunks (announced by `a1_len`), which are joined
+    /// back together here.
+    pub fn execve_arguments(&self) -> Option<Vec<String>> {
+        let execve = self.record("EXECVE")?;
+        let argc: usize = execve.number("argc")?;
+
+        let arguments = (0..argc)
+            .map(|index| {
+                let name = format!("a{index}");
+                if execve.raw(&format!("{name}_len")).is_some() {
+                    (0..)
+                        .map(|chunk| execve.string(&format!("{name}[{chunk}]")))
+                        .take_while(Option::is_some)
+                        .flatten()
+                        .collect()
+                } else {
+                    execve.string(&name).unwrap_or_default()
+                }
+            })
+            .collect();
+
+        Some(arguments)
+    }
+}
+
+/// Parses a chunk of an audit log, reassembling records into events by their
+/// serial number.
+///
+/// Events are returned in the order their first record appeared. `EOE`
+/// ("end of event") records carry no data and are dropped, as are lines that
+/// aren't valid audit records, so that one bad line doesn't drop the rest of
+/// the chunk.
+pub fn parse_events(input: &str) -> Result<Vec<AuditEvent>, AuditdGeneratorError> {
+    let mut events: Vec<AuditEvent> = Vec::new();
+    let mut index_by_serial: HashMap<(Option<String>, u64), usize> = HashMap::new();
+
+    for line in input.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        let record = match AuditRecord::parse(line) {
+            Ok(record) => record,
+            Err(e) => {
+                tracing::warn!(message = "skipping malformed audit record", error = %e);
+                continue;
+            }
+        };
+        if record.record_type == "EOE" {
+            continue;
+        }
+
+        let key = (record.node.clone(), record.serial);
+        match index_by_serial.get(&key) {
+            Some(index) => events[*index].records.push(record),
+            None => {
+                index_by_serial.insert(key, events.len());
+                events.push(AuditEvent {
+                    node: record.node.clone(),
+                    timestamp: record.timestamp,
+                    serial: record.serial,
+                    records: vec![record],
+                });
+            }
+        }
+    }
+
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXECVE_EVENT: &str = r#"node=web-1 type=SYSCALL msg=audit(1603400719.250:1234): arch=c000003e syscall=59 success=yes exit=0 a0=55d0 a1=55d1 a2=55d2 a3=0 items=2 ppid=8753 pid=9355 auid=1000 uid=1000 gid=1000 euid=1000 suid=1000 fsuid=1000 egid=1000 sgid=1000 fsgid=1000 tty=pts0 ses=3 comm="ls" exe="/usr/bin/ls" key=(null)
+node=web-1 type=EXECVE msg=audit(1603400719.250:1234): argc=3 a0="ls" a1="-la" a2=2F746D702F6D7920646972
+node=web-1 type=CWD msg=audit(1603400719.250:1234): cwd="/home/grapl"
+node=web-1 type=PATH msg=audit(1603400719.250:1234): item=0 name="/usr/bin/ls" inode=1835 dev=ca:01 mode=0100755 ouid=0 ogid=0 rdev=00:00 nametype=NORMAL cap_fp=0 cap_fi=0 cap_fe=0 cap_fver=0
+node=web-1 type=PROCTITLE msg=audit(1603400719.250:1234): proctitle=6C73002D6C61002F746D702F6D7920646972
+node=web-1 type=EOE msg=audit(1603400719.250:1234):
+"#;
+
+    #[test]
+    fn reassembles_records_by_serial() {
+        let events = parse_events(EXECVE_EVENT).expect("failed to parse audit log");
+
+        assert_eq!(events.len(), 1);
+        let event = &events[0];
+        assert_eq!(event.node.as_deref(), Some("web-1"));
+        assert_eq!(event.timestamp, 1603400719250);
+        assert_eq!(event.serial, 1234);
+        assert_eq!(event.records.len(), 5);
+
+        let syscall = event.record("SYSCALL").expect("missing SYSCALL record");
+        assert_eq!(syscall.number::<u64>("pid"), Some(9355));
+        assert_eq!(syscall.string("exe").as_deref(), Some("/usr/bin/ls"));
+        assert_eq!(syscall.string("key"), None);
+    }
+
+    #[test]
+    fn decodes_hex_encoded_arguments() {
+        let events = parse_events(EXECVE_EVENT).expect("failed to parse audit log");
+
+        assert_eq!(
+            events[0].execve_arguments(),
+            Some(vec![
+                "ls".to_string(),
+                "-la".to_string(),
+                "/tmp/my dir".to_string(),
+            ])
+        );
+        assert_eq!(
+            events[0]
+                .record("PROCTITLE")
+                .and_then(|record| record.string("proctitle"))
+                .as_deref(),
+            Some("ls -la /tmp/my dir")
+        );
+    }
+
+    #[test]
+    fn joins_split_arguments() {
+        let input = r#"type=EXECVE msg=audit(1603400719.250:1235): argc=2 a0="echo" a1_len=10 a1[0]=68656C6C6F a1[1]="world""#;
+
+        let events = parse_events(input).expect("failed to parse audit log");
+
+        assert_eq!(
+            events[0].execve_arguments(),
+            Some(vec!["echo".to_string(), "helloworld".to_string()])
+        );
+    }
+
+    #[test]
+    fn malformed_records_are_skipped() {
+        let input =
+            format!("type=SYSCALL msg=audit(garbage): pid=1\n{EXECVE_EVENT}not an audit record\n");
+
+        let events = parse_events(&input).expect("failed to parse audit log");
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].serial, 1234);
+        assert_eq!(events[0].records.len(), 5);
+    }
+}
@@ -0,0 +1,133 @@
+use auditd_generator::api::AuditdGenerator;
+use bytes::Bytes;
+use generator_sdk::test_utils::test_ctx::GeneratorTestContext;
+use rust_proto::graplinc::grapl::api::{
+    graph::v1beta1::{
+        GraphDescription,
+        ImmutableStrProp,
+        ImmutableUintProp,
+        NodeDescription,
+        Property,
+    },
+    plugin_sdk::generators::v1beta1::RunGeneratorRequest,
+};
+use test_context::test_context;
+
+fn find_node<'a>(
+    graph: &'a GraphDescription,
+    o_p_name: &str,
+    o_p_value: Property,
+) -> Option<&'a NodeDescription> {
+    graph.nodes.values().find(|n| {
+        n.properties.iter().any(|(p_name, p_value)| {
+            p_name.as_str() == o_p_name && p_value.property.clone() == o_p_value
+        })
+    })
+}
+
+fn log_bytes() -> Bytes {
+    let log_lines: Bytes = r#"
+node=web-1 type=SYSCALL msg=audit(1603400719.250:1234): arch=c000003e syscall=59 success=yes exit=0 a0=55d0 a1=55d1 a2=55d2 a3=0 items=2 ppid=8753 pid=9355 auid=1000 uid=1000 gid=1000 euid=1000 suid=1000 fsuid=1000 egid=1000 sgid=1000 fsgid=1000 tty=pts0 ses=3 comm="touch" exe="/usr/bin/touch" key=(null)
+node=web-1 type=EXECVE msg=audit(1603400719.250:1234): argc=2 a0="touch" a1=6D792066696C65
+node=web-1 type=CWD msg=audit(1603400719.250:1234): cwd="/home/grapl"
+node=web-1 type=PATH msg=audit(1603400719.250:1234): item=0 name="/usr/bin/touch" inode=1835 dev=ca:01 mode=0100755 ouid=0 ogid=0 rdev=00:00 nametype=NORMAL cap_fp=0 cap_fi=0 cap_fe=0 cap_fver=0
+node=web-1 type=EOE msg=audit(1603400719.250:1234):
+node=web-1 type=SYSCALL msg=audit(1603400719.260:1235): arch=c000003e syscall=257 success=yes exit=3 a0=ffffff9c a1=7ffd a2=941 a3=1b6 items=2 ppid=8753 pid=9355 auid=1000 uid=1000 gid=1000 euid=1000 suid=1000 fsuid=1000 egid=1000 sgid=1000 fsgid=1000 tty=pts0 ses=3 comm="touch" exe="/usr/bin/touch" key=(null)
+node=web-1 type=CWD msg=audit(1603400719.260:1235): cwd="/home/grapl"
+node=web-1 type=PATH msg=audit(1603400719.260:1235): item=0 name="/home/grapl" inode=2049 dev=ca:01 mode=040755 ouid=1000 ogid=1000 rdev=00:00 nametype=PARENT cap_fp=0 cap_fi=0 cap_fe=0 cap_fver=0
+node=web-1 type=PATH msg=audit(1603400719.260:1235): item=1 name=6D792066696C65 inode=2051 dev=ca:01 mode=0100644 ouid=1000 ogid=1000 rdev=00:00 nametype=CREATE cap_fp=0 cap_fi=0 cap_fe=0 cap_fver=0
+node=web-1 type=EOE msg=audit(1603400719.260:1235):
+"#
+    .into();
+    log_lines
+}
+
+#[test_context(GeneratorTestContext)]
+#[tokio::test]
+async fn test_audit_events_produce_expected_graph(
+    ctx: &mut GeneratorTestContext,
+) -> eyre::Result<()> {
+    let mut client = ctx.get_client(AuditdGenerator {}).await;
+
+    let result = client
+        .run_generator(RunGeneratorRequest { data: log_bytes() })
+        .await?;
+    let generated_graph = result.generated_graph.graph_description;
+
+    let parent_process = find_node(
+        &generated_graph,
+        "process_id",
+        ImmutableUintProp { prop: 8753 }.into(),
+    )
+    .expect("parent process missing");
+
+    let child_process = find_node(
+        &generated_graph,
+        "process_command_line",
+        ImmutableStrProp::from("touch my file").into(),
+    )
+    .expect("child process missing");
+
+    let parent_to_child_edge = generated_graph
+        .edges
+        .get(parent_process.get_node_key())
+        .iter()
+        .flat_map(|edge_list| edge_list.edges.iter())
+        .find(|edge| edge.to_node_key == child_process.get_node_key())
+        .expect("missing edge from parent to child");
+
+    assert_eq!(parent_to_child_edge.edge_name, "children");
+
+    find_node(
+        &generated_graph,
+        "file_path",
+        ImmutableStrProp::from("/home/grapl/my file").into(),
+    )
+    .expect("created file missing");
+
+    Ok(())
+}
+
+#[test_context(GeneratorTestContext)]
+#[tokio::test]
+async fn test_audit_event_without_node_is_skipped(
+    ctx: &mut GeneratorTestContext,
+) -> eyre::Result<()> {
+    let mut client = ctx.get_client(AuditdGenerator {}).await;
+
+    let node_less_event = r#"
+type=SYSCALL msg=audit(1603400719.240:1233): arch=c000003e syscall=59 success=yes exit=0 a0=55d0 a1=55d1 a2=55d2 a3=0 items=1 ppid=7001 pid=7002 auid=1000 uid=1000 gid=1000 euid=1000 suid=1000 fsuid=1000 egid=1000 sgid=1000 fsgid=1000 tty=pts0 ses=3 comm="id" exe="/usr/bin/id" key=(null)
+type=EXECVE msg=audit(1603400719.240:1233): argc=1 a0="id"
+type=EOE msg=audit(1603400719.240:1233):
+"#;
+    let mut data = node_less_event.as_bytes().to_vec();
+    data.extend_from_slice(&log_bytes());
+
+    let result = client
+        .run_generator(RunGeneratorRequest { data: data.into() })
+        .await?;
+    let generated_graph = result.generated_graph.graph_description;
+
+    assert!(find_node(
+        &generated_graph,
+        "process_id",
+        ImmutableUintProp { prop: 7002 }.into(),
+    )
+    .is_none());
+
+    find_node(
+        &generated_graph,
+        "process_command_line",
+        ImmutableStrProp::from("touch my file").into(),
+    )
+    .expect("child process missing");
+
+    find_node(
+        &generated_graph,
+        "file_path",
+        ImmutableStrProp::from("/home/grapl/my file").into(),
+    )
+    .expect("created file missing");
+
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use rust_proto::graplinc::grapl::api::{
+    graph::v1beta1::GraphDescription,
+    plugin_sdk::generators::v1beta1::{
+        server::GeneratorApi,
+        GeneratedGraph,
+        RunGeneratorRequest,
+        RunGeneratorResponse,
+    },
+};
+
+use crate::{
+    error::CloudTrailGeneratorError,
+    models,
+    parser,
+};
+
+pub struct CloudTrailGenerator {}
+
+#[async_trait::async_trait]
+impl GeneratorApi for CloudTrailGenerator {
+    type Error = CloudTrailGeneratorError;
+
+    /// Accepts a CloudTrail log file as delivered to S3 (`{"Records": [...]}`),
+    /// CloudTrail events forwarded by EventBridge, or bare records; see
+    /// `parser::parse_records`.
+    #[tracing::instrument(skip(self, request), err)]
+    async fn run_generator(
+        &self,
+        request: RunGeneratorRequest,
+    ) -> Result<RunGeneratorResponse, Self::Error> {
+        let mut graph_description = GraphDescription::new();
+        for record in parser::parse_records(&request.data)? {
+            graph_description.merge(&models::generate_graph_from_record(&record));
+        }
+
+        Ok(RunGeneratorResponse {
+            generated_graph: GeneratedGraph { graph_description },
+        })
+    }
+}
@@ -0,0 +1,19 @@
+use rust_proto::graplinc::grapl::api::protocol::status::Status;
+use thiserror::Error;
+
+/// This represents all possible errors that can occur in this generator.
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum CloudTrailGeneratorError {
+    #[error("error parsing CloudTrail record {0}")]
+    JsonError(#[from] serde_json::Error),
+
+    #[error("error configuring tracing {0}")]
+    SetupTracingError(#[from] grapl_tracing::SetupTracingError),
+}
+
+impl From<CloudTrailGeneratorError> for Status {
+    fn from(e: CloudTrailGeneratorError) -> Self {
+        Status::unknown(e.to_string())
+    }
+}
@@ -0,0 +1,17 @@
+use cloudtrail_generator::api;
+use generator_sdk::server::{
+    self,
+    GeneratorServiceConfig,
+};
+use grapl_tracing::setup_tracing;
+
+const SERVICE_NAME: &'static str = "cloudtrail-generator";
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let _guard = setup_tracing(SERVICE_NAME)?;
+
+    let config = GeneratorServiceConfig::from_env_vars();
+    let generator = api::CloudTrailGenerator {};
+    server::exec_service(generator, config).await
+}
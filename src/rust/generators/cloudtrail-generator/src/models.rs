@@ -0,0 +1,398 @@
+use rust_proto::graplinc::grapl::api::graph::v1beta1::GraphDescription;
+
+use crate::{
+    nodes::{
+        AwsApiCallNode,
+        AwsResourceNode,
+        IAwsApiCallNode,
+        IAwsResourceNode,
+    },
+    parser::CloudTrailRecord,
+};
+
+mod ec2;
+mod identity;
+
+/// Creates a subgraph describing a CloudTrail record.
+///
+/// Every record produces an `AwsApiCall` node, which is linked to:
+/// * the `IamPrincipal` that made the call (and the `IamRole` behind it, for
+///   assumed role sessions)
+/// * an `AwsResource` for each of the record's `resources`
+/// * the `IamRole` targeted by STS `AssumeRole*` calls
+/// * the `AwsEc2Instance`s launched, started, stopped, rebooted or terminated
+///   by EC2 instance lifecycle calls
+#[tracing::instrument(skip(record), fields(event_id = %record.event_id, event_name = %record.event_name))]
+pub(crate) fn generate_graph_from_record(record: &CloudTrailRecord) -> GraphDescription {
+    tracing::trace!("generating graph from record");
+
+    let mut graph = GraphDescription::new();
+
+    let api_call = api_call_node(record);
+    let api_call_key = api_call.clone_node_key();
+    graph.add_node(api_call);
+
+    identity::add_caller(&mut graph, record, &api_call_key);
+    add_resources(&mut graph, record, &api_call_key);
+
+    match (record.event_source.as_str(), record.event_name.as_str()) {
+        (
+            "sts.amazonaws.com",
+            "AssumeRole" | "AssumeRoleWithSAML" | "AssumeRoleWithWebIdentity",
+        ) => identity::add_assumed_role(&mut graph, record, &api_call_key),
+        (
+            "ec2.amazonaws.com",
+            "RunInstances" | "StartInstances" | "StopInstances" | "RebootInstances"
+            | "TerminateInstances",
+        ) => ec2::add_instances(&mut graph, record, &api_call_key),
+        _ => {}
+    }
+
+    tracing::debug!(
+        message = "completed graph generation",
+        node_count = graph.nodes.len(),
+        edge_count = graph.edges.len(),
+    );
+
+    graph
+}
+
+fn api_call_node(record: &CloudTrailRecord) -> AwsApiCallNode {
+    let mut api_call = AwsApiCallNode::new(AwsApiCallNode::static_strategy());
+    api_call
+        .with_event_id(&record.event_id)
+        .with_event_name(&record.event_name)
+        .with_event_source(&record.event_source)
+        .with_event_timestamp(record.event_time)
+        .with_aws_region(&record.aws_region);
+
+    if let Some(account_id) = record.account_id() {
+        api_call.with_account_id(account_id);
+    }
+
+    if let Some(source_ip_address) = &record.source_ip_address {
+        api_call.with_source_ip_address(source_ip_address);
+    }
+
+    if let Some(user_agent) = &record.user_agent {
+        api_call.with_user_agent(user_agent);
+    }
+
+    if let Some(invoked_by) = &record.user_identity.invoked_by {
+        api_call.with_invoked_by(invoked_by);
+    }
+
+    if let Some(error_code) = &record.error_code {
+        api_call.with_error_code(error_code);
+    }
+
+    if let Some(request_id) = &record.request_id {
+        api_call.with_request_id(request_id);
+    }
+
+    api_call
+}
+
+fn add_resources(graph: &mut GraphDescription, record: &CloudTrailRecord, api_call_key: &str) {
+    for resource in &record.resources {
+        // Some services list resources by type alone
+        let arn = match &resource.arn {
+            Some(arn) => arn,
+            None => continue,
+        };
+
+        let mut resource_node = AwsResourceNode::new(AwsResourceNode::static_strategy());
+        resource_node
+            .with_arn(arn)
+            .with_first_seen_timestamp(record.event_time)
+            .with_last_seen_timestamp(record.event_time);
+
+        if let Some(resource_type) = &resource.resource_type {
+            resource_node.with_resource_type(resource_type);
+        }
+
+        if let Some(account_id) = resource
+            .account_id
+            .as_deref()
+            .or_else(|| arn_account_id(arn))
+        {
+            resource_node.with_account_id(account_id);
+        }
+
+        graph.add_edge(
+            "accessed_resources",
+            api_call_key,
+            resource_node.clone_node_key(),
+        );
+
+        graph.add_node(resource_node);
+    }
+}
+
+/// Returns the account ID component of an ARN
+/// (`arn:partition:service:region:account-id:resource`), if it has one; S3
+/// ARNs, for example, do not.
+fn arn_account_id(arn: &str) -> Option<&str> {
+    arn.split(':')
+        .nth(4)
+        .filter(|account_id| !account_id.is_empty())
+}
+
+/// Returns the ARN partition a region belongs to.
+fn arn_partition(region: &str) -> &'static str {
+    if region.starts_with("cn-") {
+        "aws-cn"
+    } else if region.starts_with("us-gov-") {
+        "aws-us-gov"
+    } else {
+        "aws"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_proto::graplinc::grapl::api::graph::v1beta1::{
+        GraphDescription,
+        IdStrategy,
+        NodeDescription,
+        Strategy,
+    };
+
+    use super::*;
+    use crate::parser::parse_records;
+
+    fn read_sample(name: &str) -> GraphDescription {
+        let input =
+            std::fs::read(format!("sample_data/unit/{name}")).expect("unable to read test file.");
+
+        let mut graph = GraphDescription::new();
+        for record in parse_records(&input).expect("failed to parse sample") {
+            graph.merge(&generate_graph_from_record(&record));
+        }
+        graph
+    }
+
+    fn str_property(node: &NodeDescription, name: &str) -> Option<String> {
+        node.get_property(name)
+            .and_then(|p| p.as_immutable_str())
+            .map(|p| p.as_inner().to_string())
+    }
+
+    fn uint_property(node: &NodeDescription, name: &str) -> Option<u64> {
+        node.get_property(name)
+            .and_then(|p| p.as_immutable_uint())
+            .map(|p| p.as_inner())
+    }
+
+    fn find_nodes<'a>(
+        graph: &'a GraphDescription,
+        node_type: &str,
+    ) -> impl Iterator<Item = &'a NodeDescription> + 'a {
+        let node_type = node_type.to_string();
+        graph
+            .nodes
+            .values()
+            .filter(move |node| node.node_type == node_type)
+    }
+
+    fn find_node<'a>(
+        graph: &'a GraphDescription,
+        node_type: &str,
+        property: &str,
+        value: &str,
+    ) -> Option<&'a NodeDescription> {
+        find_nodes(graph, node_type)
+            .find(|node| str_property(node, property).as_deref() == Some(value))
+    }
+
+    /// Returns the nodes `node` has an edge named `edge_name` to.
+    fn neighbors<'a>(
+        graph: &'a GraphDescription,
+        node: &NodeDescription,
+        edge_name: &str,
+    ) -> Vec<&'a NodeDescription> {
+        graph
+            .edges
+            .get(node.get_node_key())
+            .iter()
+            .flat_map(|edge_list| edge_list.edges.iter())
+            .filter(|edge| edge.edge_name == edge_name)
+            .filter_map(|edge| graph.nodes.get(&edge.to_node_key))
+            .collect()
+    }
+
+    #[test]
+    fn every_node_uses_a_static_strategy() {
+        for sample in ["ec2_instance_lifecycle.json", "sts_assume_role.json"] {
+            let graph = read_sample(sample);
+
+            assert!(!graph.is_empty());
+            for node in graph.nodes.values() {
+                assert!(
+                    matches!(
+                        node.id_strategy.as_slice(),
+                        [IdStrategy {
+                            strategy: Strategy::Static(_)
+                        }]
+                    ),
+                    "{} is not statically identified",
+                    node.node_type
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn ec2_instance_lifecycle() {
+        let graph = read_sample("ec2_instance_lifecycle.json");
+        let instance_arn = "arn:aws:ec2:us-west-2:123456789012:instance/i-0a1b2c3d4e5f67890";
+
+        let run_instances = find_node(&graph, "AwsApiCall", "event_name", "RunInstances")
+            .expect("RunInstances call missing");
+        let launched = neighbors(&graph, run_instances, "launched_instances");
+        assert_eq!(launched.len(), 1);
+        assert_eq!(
+            str_property(launched[0], "arn").as_deref(),
+            Some(instance_arn)
+        );
+        assert_eq!(
+            str_property(launched[0], "instance_type").as_deref(),
+            Some("t3.micro")
+        );
+        assert_eq!(
+            str_property(launched[0], "availability_zone").as_deref(),
+            Some("us-west-2a")
+        );
+        assert_eq!(
+            uint_property(launched[0], "launch_timestamp"),
+            Some(1654084800000)
+        );
+
+        let terminate_instances =
+            find_node(&graph, "AwsApiCall", "event_name", "TerminateInstances")
+                .expect("TerminateInstances call missing");
+        let terminated = neighbors(&graph, terminate_instances, "terminated_instances");
+        assert_eq!(terminated.len(), 1);
+        assert_eq!(
+            str_property(terminated[0], "arn").as_deref(),
+            Some(instance_arn)
+        );
+        assert_eq!(
+            uint_property(terminated[0], "terminated_timestamp"),
+            Some(1654088400000)
+        );
+
+        // Both calls were made by a session of the same role
+        let sessions: Vec<_> = find_nodes(&graph, "IamPrincipal").collect();
+        assert_eq!(sessions.len(), 2);
+        for session in sessions {
+            assert_eq!(
+                str_property(session, "principal_type").as_deref(),
+                Some("AssumedRole")
+            );
+
+            let roles = neighbors(&graph, session, "assumed_role");
+            assert_eq!(roles.len(), 1);
+            assert_eq!(
+                str_property(roles[0], "arn").as_deref(),
+                Some("arn:aws:iam::123456789012:role/Deployer")
+            );
+            assert_eq!(
+                str_property(roles[0], "role_name").as_deref(),
+                Some("Deployer")
+            );
+        }
+    }
+
+    #[test]
+    fn sts_assume_role() {
+        let graph = read_sample("sts_assume_role.json");
+
+        let alice = find_node(
+            &graph,
+            "IamPrincipal",
+            "arn",
+            "arn:aws:iam::123456789012:user/alice",
+        )
+        .expect("caller missing");
+        let calls = neighbors(&graph, alice, "api_calls");
+        assert_eq!(calls.len(), 1);
+        let assume_role = calls[0];
+        assert_eq!(
+            str_property(assume_role, "event_name").as_deref(),
+            Some("AssumeRole")
+        );
+
+        let roles = neighbors(&graph, assume_role, "target_role");
+        assert_eq!(roles.len(), 1);
+        assert_eq!(
+            str_property(roles[0], "arn").as_deref(),
+            Some("arn:aws:iam::123456789012:role/Deployer")
+        );
+
+        let sessions = neighbors(&graph, roles[0], "role_sessions");
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(
+            str_property(sessions[0], "arn").as_deref(),
+            Some("arn:aws:sts::123456789012:assumed-role/Deployer/alice-deploy")
+        );
+
+        let get_object = find_node(&graph, "AwsApiCall", "event_name", "GetObject")
+            .expect("GetObject call missing");
+        let resources = neighbors(&graph, get_object, "accessed_resources");
+        assert_eq!(resources.len(), 2);
+        let object = resources
+            .iter()
+            .find(|resource| {
+                str_property(resource, "arn").as_deref()
+                    == Some("arn:aws:s3:::deploy-artifacts/releases/app.tar.gz")
+            })
+            .expect("S3 object missing");
+        assert_eq!(
+            str_property(object, "resource_type").as_deref(),
+            Some("AWS::S3::Object")
+        );
+        // S3 object ARNs don't name an account, and CloudTrail only reports
+        // the bucket owner
+        assert_eq!(str_property(object, "account_id"), None);
+
+        let bucket = resources
+            .iter()
+            .find(|resource| {
+                str_property(resource, "arn").as_deref() == Some("arn:aws:s3:::deploy-artifacts")
+            })
+            .expect("S3 bucket missing");
+        assert_eq!(
+            str_property(bucket, "account_id").as_deref(),
+            Some("123456789012")
+        );
+    }
+
+    #[test]
+    fn aws_service_calls_have_no_principal() {
+        let graph = read_sample("sts_assume_role.json");
+
+        let service_call = find_node(&graph, "AwsApiCall", "invoked_by", "ec2.amazonaws.com")
+            .expect("service call missing");
+
+        let callers = graph
+            .edges
+            .values()
+            .flat_map(|edge_list| edge_list.edges.iter())
+            .filter(|edge| edge.to_node_key == service_call.get_node_key())
+            .count();
+        assert_eq!(callers, 0);
+    }
+
+    #[test]
+    fn arn_parsing() {
+        assert_eq!(
+            arn_account_id("arn:aws:iam::123456789012:role/Deployer"),
+            Some("123456789012")
+        );
+        assert_eq!(arn_account_id("arn:aws:s3:::deploy-artifacts"), None);
+        assert_eq!(arn_partition("us-west-2"), "aws");
+        assert_eq!(arn_partition("cn-north-1"), "aws-cn");
+        assert_eq!(arn_partition("us-gov-west-1"), "aws-us-gov");
+    }
+}
@@ -0,0 +1,120 @@
+use rust_proto::graplinc::grapl::api::graph::v1beta1::GraphDescription;
+use serde::Deserialize;
+
+use crate::{
+    models::arn_partition,
+    nodes::{
+        AwsEc2InstanceNode,
+        IAwsEc2InstanceNode,
+    },
+    parser::CloudTrailRecord,
+};
+
+/// The `instancesSet` EC2 instance lifecycle calls carry in their request
+/// parameters and response elements.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Instances {
+    instances_set: InstancesSet,
+}
+
+#[derive(Debug, Deserialize)]
+struct InstancesSet {
+    #[serde(default)]
+    items: Vec<InstanceItem>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct InstanceItem {
+    instance_id: String,
+    image_id: Option<String>,
+    instance_type: Option<String>,
+    placement: Option<Placement>,
+    /// Milliseconds since the UNIX epoch
+    launch_time: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Placement {
+    availability_zone: Option<String>,
+}
+
+/// Adds the `AwsEc2Instance`s affected by an EC2 instance lifecycle call.
+///
+/// Instances are read from the response where there is one; `RebootInstances`
+/// only lists them in its request.
+pub(super) fn add_instances(
+    graph: &mut GraphDescription,
+    record: &CloudTrailRecord,
+    api_call_key: &str,
+) {
+    let account_id = match record.account_id() {
+        Some(account_id) => account_id,
+        None => {
+            tracing::debug!(message = "EC2 call without an account ID");
+            return;
+        }
+    };
+
+    let instances = match record
+        .response_elements::<Instances>()
+        .or_else(|| record.request_parameters::<Instances>())
+    {
+        Some(instances) => instances.instances_set.items,
+        None => return,
+    };
+
+    let edge_name = match record.event_name.as_str() {
+        "RunInstances" => "launched_instances",
+        "TerminateInstances" => "terminated_instances",
+        _ => "affected_instances",
+    };
+
+    for item in instances {
+        let arn = format!(
+            "arn:{}:ec2:{}:{}:instance/{}",
+            arn_partition(&record.aws_region),
+            record.aws_region,
+            account_id,
+            item.instance_id
+        );
+
+        let mut instance = AwsEc2InstanceNode::new(AwsEc2InstanceNode::static_strategy());
+        instance
+            .with_arn(arn)
+            .with_instance_id(&item.instance_id)
+            .with_aws_region(&record.aws_region)
+            .with_account_id(account_id)
+            .with_last_seen_timestamp(record.event_time);
+
+        if let Some(image_id) = &item.image_id {
+            instance.with_image_id(image_id);
+        }
+
+        if let Some(instance_type) = &item.instance_type {
+            instance.with_instance_type(instance_type);
+        }
+
+        if let Some(availability_zone) = item
+            .placement
+            .as_ref()
+            .and_then(|placement| placement.availability_zone.as_ref())
+        {
+            instance.with_availability_zone(availability_zone);
+        }
+
+        if let Some(launch_time) = item.launch_time {
+            instance.with_launch_timestamp(launch_time);
+        }
+
+        if record.event_name == "TerminateInstances" {
+            instance.with_terminated_timestamp(record.event_time);
+        }
+
+        graph.add_edge(edge_name, api_call_key, instance.clone_node_key());
+
+        graph.add_node(instance);
+    }
+}
@@ -0,0 +1,167 @@
+use rust_proto::graplinc::grapl::api::graph::v1beta1::GraphDescription;
+use serde::Deserialize;
+
+use crate::{
+    models::arn_account_id,
+    nodes::{
+        IIamPrincipalNode,
+        IIamRoleNode,
+        IamPrincipalNode,
+        IamRoleNode,
+    },
+    parser::CloudTrailRecord,
+};
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AssumeRoleRequest {
+    role_arn: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AssumeRoleResponse {
+    assumed_role_user: AssumedRoleUser,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AssumedRoleUser {
+    assumed_role_id: Option<String>,
+    arn: String,
+}
+
+fn role_node(arn: &str, account_id: Option<&str>, timestamp: u64) -> IamRoleNode {
+    let mut role = IamRoleNode::new(IamRoleNode::static_strategy());
+    role.with_arn(arn)
+        .with_first_seen_timestamp(timestamp)
+        .with_last_seen_timestamp(timestamp);
+
+    // Role ARNs may include a path, e.g. `role/service-role/Name`
+    if let Some(role_name) = arn.rsplit('/').next() {
+        role.with_role_name(role_name);
+    }
+
+    if let Some(account_id) = account_id.or_else(|| arn_account_id(arn)) {
+        role.with_account_id(account_id);
+    }
+
+    role
+}
+
+/// Adds the `IamPrincipal` that made the call, and the `IamRole` it assumed.
+///
+/// Identities without an ARN (e.g. `AWSService`, for calls AWS services make
+/// on your behalf) are not added: there is nothing to identify them by.
+pub(super) fn add_caller(
+    graph: &mut GraphDescription,
+    record: &CloudTrailRecord,
+    api_call_key: &str,
+) {
+    let identity = &record.user_identity;
+    let arn = match &identity.arn {
+        Some(arn) => arn,
+        None => return,
+    };
+
+    let mut principal = IamPrincipalNode::new(IamPrincipalNode::static_strategy());
+    principal
+        .with_arn(arn)
+        .with_first_seen_timestamp(record.event_time)
+        .with_last_seen_timestamp(record.event_time);
+
+    if let Some(principal_type) = &identity.identity_type {
+        principal.with_principal_type(principal_type);
+    }
+
+    if let Some(principal_id) = &identity.principal_id {
+        principal.with_principal_id(principal_id);
+    }
+
+    if let Some(account_id) = identity
+        .account_id
+        .as_deref()
+        .or_else(|| arn_account_id(arn))
+    {
+        principal.with_account_id(account_id);
+    }
+
+    if let Some(user_name) = &identity.user_name {
+        principal.with_user_name(user_name);
+    }
+
+    graph.add_edge("api_calls", principal.clone_node_key(), api_call_key);
+
+    let issuer = identity
+        .session_context
+        .as_ref()
+        .and_then(|session_context| session_context.session_issuer.as_ref())
+        .filter(|issuer| issuer.issuer_type.as_deref() == Some("Role"));
+
+    if let Some(issuer_arn) = issuer.and_then(|issuer| issuer.arn.as_deref()) {
+        let role = role_node(
+            issuer_arn,
+            issuer.and_then(|issuer| issuer.account_id.as_deref()),
+            record.event_time,
+        );
+
+        graph.add_edge(
+            "assumed_role",
+            principal.clone_node_key(),
+            role.clone_node_key(),
+        );
+
+        graph.add_node(role);
+    }
+
+    graph.add_node(principal);
+}
+
+/// Adds the `IamRole` targeted by an STS `AssumeRole*` call and, if the call
+/// succeeded, the session principal it created.
+pub(super) fn add_assumed_role(
+    graph: &mut GraphDescription,
+    record: &CloudTrailRecord,
+    api_call_key: &str,
+) {
+    let request: AssumeRoleRequest = match record.request_parameters() {
+        Some(request) => request,
+        None => {
+            tracing::debug!(message = "AssumeRole call without a role ARN");
+            return;
+        }
+    };
+
+    let role = role_node(&request.role_arn, None, record.event_time);
+
+    graph.add_edge("target_role", api_call_key, role.clone_node_key());
+
+    if let Some(response) = record.response_elements::<AssumeRoleResponse>() {
+        let assumed_role_user = response.assumed_role_user;
+
+        let mut session = IamPrincipalNode::new(IamPrincipalNode::static_strategy());
+        session
+            .with_arn(&assumed_role_user.arn)
+            .with_principal_type("AssumedRole")
+            .with_first_seen_timestamp(record.event_time)
+            .with_last_seen_timestamp(record.event_time);
+
+        if let Some(assumed_role_id) = &assumed_role_user.assumed_role_id {
+            session.with_principal_id(assumed_role_id);
+        }
+
+        if let Some(account_id) = arn_account_id(&assumed_role_user.arn) {
+            session.with_account_id(account_id);
+        }
+
+        graph.add_edge(
+            "role_sessions",
+            role.clone_node_key(),
+            session.clone_node_key(),
+        );
+
+        graph.add_node(session);
+    }
+
+    graph.add_node(role);
+}
@@ -0,0 +1,37 @@
+//! The node types this generator emits. Every node uses a static identity
+//! strategy: CloudTrail identifies principals and resources by their ARN, and
+//! each event by its `eventID`.
+
+mod aws_api_call;
+mod aws_ec2_instance;
+mod aws_resource;
+mod iam_principal;
+mod iam_role;
+
+pub use self::{
+    aws_api_call::{
+        AwsApiCall,
+        AwsApiCallNode,
+        IAwsApiCallNode,
+    },
+    aws_ec2_instance::{
+        AwsEc2Instance,
+        AwsEc2InstanceNode,
+        IAwsEc2InstanceNode,
+    },
+    aws_resource::{
+        AwsResource,
+        AwsResourceNode,
+        IAwsResourceNode,
+    },
+    iam_principal::{
+        IIamPrincipalNode,
+        IamPrincipal,
+        IamPrincipalNode,
+    },
+    iam_role::{
+        IIamRoleNode,
+        IamRole,
+        IamRoleNode,
+    },
+};
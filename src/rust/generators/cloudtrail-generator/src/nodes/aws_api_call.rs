@@ -0,0 +1,48 @@
+use derive_dynamic_node::{
+    GraplStaticId,
+    NodeDescription,
+};
+use rust_proto::graplinc::grapl::api::graph::v1beta1::{
+    IdStrategy,
+    NodeDescription,
+    NodeProperty,
+    Static,
+};
+
+/// A single CloudTrail event. CloudTrail assigns every event a unique
+/// `eventID`, so API calls are never merged with one another.
+#[derive(NodeDescription, GraplStaticId)]
+pub struct AwsApiCall {
+    #[grapl(static_id, immutable)]
+    event_id: String,
+    #[grapl(immutable)]
+    event_name: String,
+    #[grapl(immutable)]
+    event_source: String,
+    #[grapl(immutable)]
+    event_timestamp: u64,
+    #[grapl(immutable)]
+    aws_region: String,
+    #[grapl(immutable)]
+    account_id: String,
+    #[grapl(immutable)]
+    source_ip_address: String,
+    #[grapl(immutable)]
+    user_agent: String,
+    #[grapl(immutable)]
+    invoked_by: String,
+    #[grapl(immutable)]
+    error_code: String,
+    #[grapl(immutable)]
+    request_id: String,
+}
+
+impl IAwsApiCallNode for AwsApiCallNode {
+    fn get_mut_dynamic_node(&mut self) -> &mut NodeDescription {
+        &mut self.dynamic_node
+    }
+
+    fn get_dynamic_node(&self) -> &NodeDescription {
+        &self.dynamic_node
+    }
+}
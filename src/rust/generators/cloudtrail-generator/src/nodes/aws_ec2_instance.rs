@@ -0,0 +1,44 @@
+use derive_dynamic_node::{
+    GraplStaticId,
+    NodeDescription,
+};
+use rust_proto::graplinc::grapl::api::graph::v1beta1::{
+    IdStrategy,
+    NodeDescription,
+    NodeProperty,
+    Static,
+};
+
+#[derive(NodeDescription, GraplStaticId)]
+pub struct AwsEc2Instance {
+    #[grapl(static_id, immutable)]
+    arn: String,
+    #[grapl(immutable)]
+    instance_id: String,
+    #[grapl(immutable)]
+    image_id: String,
+    #[grapl(immutable)]
+    instance_type: String,
+    #[grapl(immutable)]
+    availability_zone: String,
+    #[grapl(immutable)]
+    aws_region: String,
+    #[grapl(immutable)]
+    account_id: String,
+    #[grapl(immutable)]
+    launch_timestamp: u64,
+    #[grapl(immutable)]
+    terminated_timestamp: u64,
+    #[grapl(increment)]
+    last_seen_timestamp: u64,
+}
+
+impl IAwsEc2InstanceNode for AwsEc2InstanceNode {
+    fn get_mut_dynamic_node(&mut self) -> &mut NodeDescription {
+        &mut self.dynamic_node
+    }
+
+    fn get_dynamic_node(&self) -> &NodeDescription {
+        &self.dynamic_node
+    }
+}
@@ -0,0 +1,36 @@
+use derive_dynamic_node::{
+    GraplStaticId,
+    NodeDescription,
+};
+use rust_proto::graplinc::grapl::api::graph::v1beta1::{
+    IdStrategy,
+    NodeDescription,
+    NodeProperty,
+    Static,
+};
+
+/// Any resource CloudTrail lists in a record's `resources` field, e.g. an S3
+/// object or a KMS key.
+#[derive(NodeDescription, GraplStaticId)]
+pub struct AwsResource {
+    #[grapl(static_id, immutable)]
+    arn: String,
+    #[grapl(immutable)]
+    resource_type: String,
+    #[grapl(immutable)]
+    account_id: String,
+    #[grapl(decrement)]
+    first_seen_timestamp: u64,
+    #[grapl(increment)]
+    last_seen_timestamp: u64,
+}
+
+impl IAwsResourceNode for AwsResourceNode {
+    fn get_mut_dynamic_node(&mut self) -> &mut NodeDescription {
+        &mut self.dynamic_node
+    }
+
+    fn get_dynamic_node(&self) -> &NodeDescription {
+        &self.dynamic_node
+    }
+}
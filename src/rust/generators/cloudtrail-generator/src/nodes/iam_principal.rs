@@ -0,0 +1,40 @@
+use derive_dynamic_node::{
+    GraplStaticId,
+    NodeDescription,
+};
+use rust_proto::graplinc::grapl::api::graph::v1beta1::{
+    IdStrategy,
+    NodeDescription,
+    NodeProperty,
+    Static,
+};
+
+/// The identity that made an AWS API call: an IAM user, the account root
+/// user, or a session of an assumed role or federated user.
+#[derive(NodeDescription, GraplStaticId)]
+pub struct IamPrincipal {
+    #[grapl(static_id, immutable)]
+    arn: String,
+    #[grapl(immutable)]
+    principal_type: String,
+    #[grapl(immutable)]
+    principal_id: String,
+    #[grapl(immutable)]
+    account_id: String,
+    #[grapl(immutable)]
+    user_name: String,
+    #[grapl(decrement)]
+    first_seen_timestamp: u64,
+    #[grapl(increment)]
+    last_seen_timestamp: u64,
+}
+
+impl IIamPrincipalNode for IamPrincipalNode {
+    fn get_mut_dynamic_node(&mut self) -> &mut NodeDescription {
+        &mut self.dynamic_node
+    }
+
+    fn get_dynamic_node(&self) -> &NodeDescription {
+        &self.dynamic_node
+    }
+}
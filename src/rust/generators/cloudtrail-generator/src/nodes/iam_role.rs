@@ -0,0 +1,34 @@
+use derive_dynamic_node::{
+    GraplStaticId,
+    NodeDescription,
+};
+use rust_proto::graplinc::grapl::api::graph::v1beta1::{
+    IdStrategy,
+    NodeDescription,
+    NodeProperty,
+    Static,
+};
+
+#[derive(NodeDescription, GraplStaticId)]
+pub struct IamRole {
+    #[grapl(static_id, immutable)]
+    arn: String,
+    #[grapl(immutable)]
+    role_name: String,
+    #[grapl(immutable)]
+    account_id: String,
+    #[grapl(decrement)]
+    first_seen_timestamp: u64,
+    #[grapl(increment)]
+    last_seen_timestamp: u64,
+}
+
+impl IIamRoleNode for IamRoleNode {
+    fn get_mut_dynamic_node(&mut self) -> &mut NodeDescription {
+        &mut self.dynamic_node
+    }
+
+    fn get_dynamic_node(&self) -> &NodeDescription {
+        &self.dynamic_node
+    }
+}
@@ -0,0 +1,215 @@
+use serde::{
+    Deserialize,
+    Deserializer,
+};
+
+use crate::error::CloudTrailGeneratorError;
+
+/// A single CloudTrail record. Only the fields this generator uses are
+/// deserialized; see the [CloudTrail record contents] for the rest.
+///
+/// [CloudTrail record contents]: https://docs.aws.amazon.com/awscloudtrail/latest/userguide/cloudtrail-event-reference-record-contents.html
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CloudTrailRecord {
+    #[serde(rename = "eventID")]
+    pub event_id: String,
+    /// Milliseconds since the UNIX epoch
+    #[serde(deserialize_with = "deserialize_event_time")]
+    pub event_time: u64,
+    pub event_source: String,
+    pub event_name: String,
+    pub aws_region: String,
+    #[serde(rename = "sourceIPAddress")]
+    pub source_ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub user_identity: UserIdentity,
+    pub error_code: Option<String>,
+    #[serde(rename = "requestID")]
+    pub request_id: Option<String>,
+    pub recipient_account_id: Option<String>,
+    #[serde(default)]
+    pub request_parameters: Option<serde_json::Value>,
+    #[serde(default)]
+    pub response_elements: Option<serde_json::Value>,
+    #[serde(default)]
+    pub resources: Vec<Resource>,
+}
+
+impl CloudTrailRecord {
+    /// The account the event was delivered to, falling back to the caller's
+    /// account for records which predate `recipientAccountId`.
+    pub fn account_id(&self) -> Option<&str> {
+        self.recipient_account_id
+            .as_deref()
+            .or(self.user_identity.account_id.as_deref())
+    }
+
+    /// Deserializes `requestParameters` into an API-specific type, returning
+    /// `None` if it is absent or has an unexpected shape.
+    pub fn request_parameters<T: for<'de> Deserialize<'de>>(&self) -> Option<T> {
+        self.request_parameters
+            .clone()
+            .and_then(|value| serde_json::from_value(value).ok())
+    }
+
+    /// Deserializes `responseElements` into an API-specific type, returning
+    /// `None` if it is absent or has an unexpected shape.
+    pub fn response_elements<T: for<'de> Deserialize<'de>>(&self) -> Option<T> {
+        self.response_elements
+            .clone()
+            .and_then(|value| serde_json::from_value(value).ok())
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserIdentity {
+    /// e.g. `IAMUser`, `AssumedRole`, `Root` or `AWSService`
+    #[serde(rename = "type")]
+    pub identity_type: Option<String>,
+    pub principal_id: Option<String>,
+    pub arn: Option<String>,
+    pub account_id: Option<String>,
+    pub user_name: Option<String>,
+    /// The service that made the call, for `AWSService` identities.
+    pub invoked_by: Option<String>,
+    pub session_context: Option<SessionContext>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionContext {
+    pub session_issuer: Option<SessionIssuer>,
+}
+
+/// The identity which issued temporary credentials, e.g. the role behind an
+/// `AssumedRole` session.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionIssuer {
+    #[serde(rename = "type")]
+    pub issuer_type: Option<String>,
+    pub arn: Option<String>,
+    pub account_id: Option<String>,
+    pub user_name: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Resource {
+    #[serde(rename = "ARN")]
+    pub arn: Option<String>,
+    pub account_id: Option<String>,
+    #[serde(rename = "type")]
+    pub resource_type: Option<String>,
+}
+
+fn deserialize_event_time<'de, D>(deserializer: D) -> Result<u64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let event_time = String::deserialize(deserializer)?;
+    let timestamp = chrono::DateTime::parse_from_rfc3339(&event_time)
+        .map_err(serde::de::Error::custom)?
+        .timestamp_millis();
+
+    u64::try_from(timestamp).map_err(serde::de::Error::custom)
+}
+
+/// Parses any number of CloudTrail logs, which may be concatenated or
+/// newline-delimited. Each log is one of:
+///
+/// - a log file as CloudTrail delivers it to S3 (`{"Records": [...]}`),
+/// - an "AWS API Call via CloudTrail" event delivered by EventBridge, with the
+///   record in `detail`,
+/// - a bare record.
+///
+/// Records are deserialized one at a time, so that a record we can't parse
+/// is skipped with a warning rather than dropping the rest of its file. Input
+/// which isn't valid JSON can't be split into records, and is an error.
+pub fn parse_records(input: &[u8]) -> Result<Vec<CloudTrailRecord>, CloudTrailGeneratorError> {
+    let mut records = Vec::new();
+
+    for log in serde_json::Deserializer::from_slice(input).into_iter::<serde_json::Value>() {
+        let mut log = log?;
+
+        let raw_records = match log.get_mut("Records").map(serde_json::Value::take) {
+            Some(serde_json::Value::Array(file_records)) => file_records,
+            Some(_) => {
+                tracing::warn!(message = "skipping cloudtrail log whose Records isn't a list");
+                continue;
+            }
+            None => match log.get_mut("detail").map(serde_json::Value::take) {
+                Some(detail) => vec![detail],
+                None => vec![log],
+            },
+        };
+
+        for raw_record in raw_records {
+            match serde_json::from_value(raw_record) {
+                Ok(record) => records.push(record),
+                Err(e) => {
+                    tracing::warn!(message = "skipping malformed cloudtrail record", error = %e);
+                }
+            }
+        }
+    }
+
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RECORD: &str = r#"{"eventVersion":"1.08","userIdentity":{"type":"IAMUser","principalId":"AIDAEXAMPLE","arn":"arn:aws:iam::123456789012:user/alice","accountId":"123456789012","userName":"alice"},"eventTime":"2022-06-01T12:00:00Z","eventSource":"iam.amazonaws.com","eventName":"ListRoles","awsRegion":"us-east-1","sourceIPAddress":"203.0.113.10","userAgent":"aws-cli/2.7.0","requestParameters":null,"responseElements":null,"requestID":"1f0e2a9c","eventID":"8b1b1e4e-0000-4000-8000-000000000001","recipientAccountId":"123456789012"}"#;
+
+    #[test]
+    fn parses_a_bare_record() {
+        let records = parse_records(RECORD.as_bytes()).expect("failed to parse record");
+
+        assert_eq!(records.len(), 1);
+        let record = &records[0];
+        assert_eq!(record.event_name, "ListRoles");
+        assert_eq!(record.event_time, 1654084800000);
+        assert_eq!(
+            record.user_identity.arn.as_deref(),
+            Some("arn:aws:iam::123456789012:user/alice")
+        );
+        assert_eq!(record.account_id(), Some("123456789012"));
+        assert!(record.resources.is_empty());
+    }
+
+    #[test]
+    fn parses_log_files_and_eventbridge_events() {
+        let input = format!(
+            "{{\"Records\":[{RECORD},{RECORD}]}}\n{{\"detail-type\":\"AWS API Call via CloudTrail\",\"source\":\"aws.iam\",\"detail\":{RECORD}}}\n"
+        );
+
+        let records = parse_records(input.as_bytes()).expect("failed to parse records");
+
+        assert_eq!(records.len(), 3);
+    }
+
+    #[test]
+    fn skips_malformed_records() {
+        let bad_time = RECORD.replace("2022-06-01T12:00:00Z", "yesterday");
+        let missing_name = RECORD.replace("\"eventName\":\"ListRoles\",", "");
+        let input = format!(
+            "{{\"Records\":[{RECORD},{bad_time},{missing_name},{RECORD}]}}\n{{\"detail\":{bad_time}}}\n{bad_time}\n{RECORD}"
+        );
+
+        let records = parse_records(input.as_bytes()).expect("failed to parse records");
+
+        assert_eq!(records.len(), 3);
+        assert!(records
+            .iter()
+            .all(|record| record.event_name == "ListRoles"));
+    }
+
+    #[test]
+    fn rejects_invalid_json() {
+        assert!(parse_records(b"{\"Records\":[").is_err());
+    }
+}
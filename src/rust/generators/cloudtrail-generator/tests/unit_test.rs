@@ -0,0 +1,66 @@
+use cloudtrail_generator::api::CloudTrailGenerator;
+use generator_sdk::test_utils::test_ctx::GeneratorTestContext;
+use rust_proto::graplinc::grapl::api::{
+    graph::v1beta1::{
+        GraphDescription,
+        ImmutableStrProp,
+        NodeDescription,
+        Property,
+    },
+    plugin_sdk::generators::v1beta1::RunGeneratorRequest,
+};
+use test_context::test_context;
+
+fn find_node<'a>(
+    graph: &'a GraphDescription,
+    o_p_name: &str,
+    o_p_value: Property,
+) -> Option<&'a NodeDescription> {
+    graph.nodes.values().find(|n| {
+        n.properties.iter().any(|(p_name, p_value)| {
+            p_name.as_str() == o_p_name && p_value.property.clone() == o_p_value
+        })
+    })
+}
+
+#[test_context(GeneratorTestContext)]
+#[tokio::test]
+async fn test_cloudtrail_log_file_produces_expected_graph(
+    ctx: &mut GeneratorTestContext,
+) -> eyre::Result<()> {
+    let mut client = ctx.get_client(CloudTrailGenerator {}).await;
+
+    let log_file = std::fs::read("sample_data/unit/sts_assume_role.json")?;
+    let result = client
+        .run_generator(RunGeneratorRequest {
+            data: log_file.into(),
+        })
+        .await?;
+    let generated_graph = result.generated_graph.graph_description;
+
+    let caller = find_node(
+        &generated_graph,
+        "arn",
+        ImmutableStrProp::from("arn:aws:iam::123456789012:user/alice").into(),
+    )
+    .expect("caller missing");
+
+    let api_call = find_node(
+        &generated_graph,
+        "event_name",
+        ImmutableStrProp::from("AssumeRole").into(),
+    )
+    .expect("api call missing");
+
+    let caller_to_api_call_edge = generated_graph
+        .edges
+        .get(caller.get_node_key())
+        .iter()
+        .flat_map(|edge_list| edge_list.edges.iter())
+        .find(|edge| edge.to_node_key == api_call.get_node_key())
+        .expect("missing edge from caller to api call");
+
+    assert_eq!(caller_to_api_call_edge.edge_name, "api_calls");
+
+    Ok(())
+}
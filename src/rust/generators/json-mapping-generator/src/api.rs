@@ -0,0 +1,79 @@
+use rust_proto::graplinc::grapl::api::{
+    graph::v1beta1::GraphDescription,
+    plugin_sdk::generators::v1beta1::{
+        server::GeneratorApi,
+        GeneratedGraph,
+        RunGeneratorRequest,
+        RunGeneratorResponse,
+    },
+};
+use serde_json::Value;
+
+use crate::{
+    error::JsonMappingGeneratorError,
+    mapping::Mapping,
+    models,
+};
+
+pub struct JsonMappingGenerator {
+    mapping: Mapping,
+}
+
+impl JsonMappingGenerator {
+    pub fn new(mapping: Mapping) -> Self {
+        Self { mapping }
+    }
+}
+
+#[async_trait::async_trait]
+impl GeneratorApi for JsonMappingGenerator {
+    type Error = JsonMappingGeneratorError;
+
+    /// Accepts any number of JSON values, which may be concatenated or
+    /// newline-delimited; each is mapped to nodes and edges independently.
+    /// A malformed line is skipped rather than failing the whole batch.
+    #[tracing::instrument(skip(self, request), err)]
+    async fn run_generator(
+        &self,
+        request: RunGeneratorRequest,
+    ) -> Result<RunGeneratorResponse, Self::Error> {
+        let mut graph_description = GraphDescription::new();
+        for record in parse_records(&request.data) {
+            graph_description.merge(&models::generate_graph(&self.mapping, &record));
+        }
+
+        Ok(RunGeneratorResponse {
+            generated_graph: GeneratedGraph { graph_description },
+        })
+    }
+}
+
+/// Reads every JSON value in `data`. When a value fails to parse, the rest of
+/// the line it starts on is skipped and reading resumes on the next line.
+fn parse_records(mut data: &[u8]) -> Vec<Value> {
+    let mut records = Vec::new();
+
+    loop {
+        let mut stream = serde_json::Deserializer::from_slice(data).into_iter::<Value>();
+        loop {
+            match stream.next() {
+                Some(Ok(record)) => records.push(record),
+                Some(Err(e)) => {
+                    tracing::warn!(message = "skipping malformed JSON log line", error = %e);
+                    break;
+                }
+                None => return records,
+            }
+        }
+
+        let rest = &data[stream.byte_offset()..];
+        let rest = match rest.iter().position(|b| !b.is_ascii_whitespace()) {
+            Some(start) => &rest[start..],
+            None => return records,
+        };
+        data = match rest.iter().position(|&b| b == b'\n') {
+            Some(end) => &rest[end + 1..],
+            None => return records,
+        };
+    }
+}
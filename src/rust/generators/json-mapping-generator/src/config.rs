@@ -0,0 +1,11 @@
+#[derive(clap::Parser, Clone, Debug)]
+pub struct JsonMappingGeneratorConfig {
+    /// The mapping document this generator applies to every log line
+    #[clap(long, env = "JSON_MAPPING_DOCUMENT_PATH")]
+    pub mapping_document_path: std::path::PathBuf,
+
+    /// If set, the mapping document is validated against this tenant's
+    /// deployed schema before the generator starts serving.
+    #[clap(long, env = "JSON_MAPPING_TENANT_ID")]
+    pub tenant_id: Option<uuid::Uuid>,
+}
@@ -0,0 +1,76 @@
+use rust_proto::graplinc::grapl::api::{
+    client::ClientError,
+    protocol::status::Status,
+};
+use thiserror::Error;
+
+use crate::validation::SchemaMismatch;
+
+/// This represents all possible errors that can occur in this generator.
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum JsonMappingGeneratorError {
+    #[error("error parsing JSON log line {0}")]
+    JsonError(#[from] serde_json::Error),
+
+    #[error("invalid mapping document {0}")]
+    MappingError(#[from] MappingError),
+
+    #[error("error reading mapping document {0}")]
+    ReadMappingError(#[from] std::io::Error),
+
+    #[error("error fetching schema from graph-schema-manager {0}")]
+    GraphSchemaManagerError(#[from] ClientError),
+
+    #[error("mapping document does not match the deployed schema: {}", display_mismatches(.0))]
+    SchemaMismatch(Vec<SchemaMismatch>),
+
+    #[error("error configuring tracing {0}")]
+    SetupTracingError(#[from] grapl_tracing::SetupTracingError),
+}
+
+impl From<JsonMappingGeneratorError> for Status {
+    fn from(e: JsonMappingGeneratorError) -> Self {
+        Status::unknown(e.to_string())
+    }
+}
+
+/// Errors in the mapping document itself, found before any log is processed.
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum MappingError {
+    #[error("{0}")]
+    JsonError(#[from] serde_json::Error),
+
+    #[error("the mapping document defines no nodes")]
+    NoNodes,
+
+    #[error("node name {0} is used more than once")]
+    DuplicateNodeName(String),
+
+    #[error("edge refers to unknown node {0}")]
+    UnknownNode(String),
+
+    #[error("{kind} {name} must be between 1 and 32 characters")]
+    InvalidName { kind: &'static str, name: String },
+
+    #[error("invalid JSONPath {path}: {reason}")]
+    InvalidPath { path: String, reason: String },
+
+    #[error("property {0} must set exactly one of path, record_path or constant")]
+    AmbiguousPropertySource(String),
+
+    #[error("node {node} is identified by unmapped property {property_name}")]
+    UnmappedIdentityProperty { node: String, property_name: String },
+
+    #[error("timestamp property {property_name} of node {node} must be a u64 property")]
+    InvalidTimestampProperty { node: String, property_name: String },
+}
+
+fn display_mismatches(mismatches: &[SchemaMismatch]) -> String {
+    mismatches
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
@@ -0,0 +1,6 @@
+pub mod api;
+pub mod config;
+pub mod error;
+pub mod mapping;
+pub mod models;
+pub mod validation;
@@ -0,0 +1,48 @@
+use clap::Parser;
+use figment::{
+    providers::Env,
+    Figment,
+};
+use generator_sdk::server::{
+    self,
+    GeneratorServiceConfig,
+};
+use grapl_tracing::setup_tracing;
+use json_mapping_generator::{
+    api,
+    config::JsonMappingGeneratorConfig,
+    mapping::Mapping,
+    validation,
+};
+use rust_proto::graplinc::grapl::api::{
+    client::Connect,
+    graph_schema_manager::v1beta1::client::GraphSchemaManagerClient,
+};
+
+const SERVICE_NAME: &'static str = "json-mapping-generator";
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let _guard = setup_tracing(SERVICE_NAME)?;
+
+    let mapping_config = JsonMappingGeneratorConfig::parse();
+    let mapping = Mapping::from_slice(&std::fs::read(&mapping_config.mapping_document_path)?)?;
+
+    if let Some(tenant_id) = mapping_config.tenant_id {
+        let client_config = Figment::new()
+            .merge(Env::prefixed("GRAPH_SCHEMA_MANAGER_CLIENT_"))
+            .extract()?;
+        let mut client = GraphSchemaManagerClient::connect(client_config).await?;
+
+        validation::validate_mapping(&mut client, tenant_id, &mapping).await?;
+        tracing::info!(message = "mapping document matches the deployed schema", %tenant_id);
+    } else {
+        tracing::warn!(
+            message = "JSON_MAPPING_TENANT_ID is not set, so the mapping document is not validated against the deployed schema",
+        );
+    }
+
+    let config = GeneratorServiceConfig::from_env_vars();
+    let generator = api::JsonMappingGenerator::new(mapping);
+    server::exec_service(generator, config).await
+}
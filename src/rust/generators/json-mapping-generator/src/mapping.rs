@@ -0,0 +1,526 @@
+//! The mapping document which drives this generator.
+//!
+//! A mapping document is JSON. Each entry in `nodes` selects zero or more
+//! values from a log line with a JSONPath, and builds a node from each of them;
+//! each entry in `edges` connects every node built by one entry to every node
+//! built by another:
+//!
+//! ```json
+//! {
+//!   "nodes": [
+//!     {
+//!       "name": "process",
+//!       "node_type": "Process",
+//!       "identity": {
+//!         "strategy": "session",
+//!         "pseudo_key_properties": ["process_id"],
+//!         "creation_timestamp_property": "created_time",
+//!         "last_seen_timestamp_property": "last_seen_time",
+//!         "termination_timestamp_property": "terminated_time"
+//!       },
+//!       "properties": {
+//!         "process_id": { "path": "$.pid", "type": "immutable_u64" },
+//!         "created_time": {
+//!           "path": "$.timestamp",
+//!           "type": "immutable_u64",
+//!           "transform": "rfc3339_to_millis"
+//!         }
+//!       }
+//!     }
+//!   ],
+//!   "edges": [{ "from": "process", "to": "file", "edge_name": "created_files" }]
+//! }
+//! ```
+//!
+//! Property paths are evaluated against the value a node entry selected (the
+//! whole line, by default); `record_path` is always evaluated against the whole
+//! line.
+use std::collections::{
+    BTreeMap,
+    HashSet,
+};
+
+use jsonpath_lib::Compiled;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::error::MappingError;
+
+/// Node types, edge names and property names share the graph schema's limit.
+const MAX_NAME_LENGTH: usize = 32;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MappingDocument {
+    pub nodes: Vec<NodeDefinition>,
+    #[serde(default = "Vec::new")]
+    pub edges: Vec<EdgeDefinition>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NodeDefinition {
+    /// Identifies this entry within the mapping document, for `edges`.
+    pub name: String,
+    pub node_type: String,
+    /// Selects the values nodes are built from. Defaults to the whole line.
+    #[serde(default = "root_path")]
+    pub select: String,
+    pub identity: IdentityDefinition,
+    pub properties: BTreeMap<String, PropertyDefinition>,
+}
+
+fn root_path() -> String {
+    "$".to_owned()
+}
+
+/// Mirrors the identity algorithms the graph schema supports.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(tag = "strategy", rename_all = "snake_case")]
+pub enum IdentityDefinition {
+    Static {
+        static_key_properties: Vec<String>,
+    },
+    Session {
+        pseudo_key_properties: Vec<String>,
+        creation_timestamp_property: String,
+        last_seen_timestamp_property: String,
+        termination_timestamp_property: String,
+    },
+}
+
+impl IdentityDefinition {
+    /// The properties a node can't be identified without. Session identities
+    /// also need their creation or last seen timestamp.
+    pub fn key_properties(&self) -> &[String] {
+        match self {
+            IdentityDefinition::Static {
+                static_key_properties,
+            } => static_key_properties,
+            IdentityDefinition::Session {
+                pseudo_key_properties,
+                ..
+            } => pseudo_key_properties,
+        }
+    }
+}
+
+/// Exactly one of `path`, `record_path` and `constant` must be set.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PropertyDefinition {
+    pub path: Option<String>,
+    pub record_path: Option<String>,
+    pub constant: Option<Value>,
+    #[serde(rename = "type")]
+    pub property_type: PropertyType,
+    pub transform: Option<Transform>,
+}
+
+/// Mirrors the graph schema's property types, other than bytes, which JSON
+/// has no representation for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PropertyType {
+    ImmutableString,
+    ImmutableI64,
+    MaxI64,
+    MinI64,
+    ImmutableU64,
+    MaxU64,
+    MinU64,
+    ImmutableBool,
+    ImmutableF64,
+    ImmutableTimestamp,
+    MaxTimestamp,
+    MinTimestamp,
+    StringSet,
+}
+
+/// Applied to a selected value before it is converted to its property type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Transform {
+    /// An RFC 3339 timestamp, e.g. `2022-06-01T12:00:00Z`, to milliseconds
+    /// since the UNIX epoch
+    Rfc3339ToMillis,
+    /// Seconds since the UNIX epoch, which may be fractional, to milliseconds
+    SecondsToMillis,
+    Lowercase,
+}
+
+impl Transform {
+    pub fn apply(self, value: &Value) -> Option<Value> {
+        match self {
+            Transform::Rfc3339ToMillis => {
+                let timestamp = chrono::DateTime::parse_from_rfc3339(value.as_str()?).ok()?;
+                Some(Value::from(timestamp.timestamp_millis()))
+            }
+            Transform::SecondsToMillis => {
+                let seconds = match value {
+                    Value::String(seconds) => seconds.parse().ok()?,
+                    value => value.as_f64()?,
+                };
+                Some(Value::from((seconds * 1000.0).round() as i64))
+            }
+            Transform::Lowercase => Some(Value::from(value.as_str()?.to_lowercase())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EdgeDefinition {
+    pub from: String,
+    pub to: String,
+    pub edge_name: String,
+}
+
+/// A mapping document whose paths have been compiled and whose references
+/// have been resolved.
+#[derive(Debug, Clone)]
+pub struct Mapping {
+    pub nodes: Vec<NodeMapping>,
+    pub edges: Vec<EdgeMapping>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NodeMapping {
+    pub name: String,
+    pub node_type: String,
+    pub select: Compiled,
+    pub identity: IdentityDefinition,
+    pub properties: Vec<PropertyMapping>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PropertyMapping {
+    pub name: String,
+    pub source: PropertySource,
+    pub property_type: PropertyType,
+    pub transform: Option<Transform>,
+}
+
+#[derive(Debug, Clone)]
+pub enum PropertySource {
+    /// Evaluated against the value the node was built from
+    Path(Compiled),
+    /// Evaluated against the whole line
+    RecordPath(Compiled),
+    Constant(Value),
+}
+
+#[derive(Debug, Clone)]
+pub struct EdgeMapping {
+    /// Index into `Mapping::nodes`
+    pub from: usize,
+    /// Index into `Mapping::nodes`
+    pub to: usize,
+    pub edge_name: String,
+}
+
+impl Mapping {
+    pub fn from_slice(document: &[u8]) -> Result<Self, MappingError> {
+        let document: MappingDocument = serde_json::from_slice(document)?;
+        Self::compile(document)
+    }
+
+    pub fn compile(document: MappingDocument) -> Result<Self, MappingError> {
+        if document.nodes.is_empty() {
+            return Err(MappingError::NoNodes);
+        }
+
+        let mut node_names = HashSet::new();
+        let mut nodes = Vec::with_capacity(document.nodes.len());
+        for node in document.nodes {
+            if !node_names.insert(node.name.clone()) {
+                return Err(MappingError::DuplicateNodeName(node.name));
+            }
+
+            nodes.push(NodeMapping::compile(node)?);
+        }
+
+        let edges = document
+            .edges
+            .into_iter()
+            .map(|edge| EdgeMapping::compile(edge, &nodes))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Mapping { nodes, edges })
+    }
+}
+
+impl NodeMapping {
+    fn compile(node: NodeDefinition) -> Result<Self, MappingError> {
+        check_name_length("node_type", &node.node_type)?;
+
+        let select = compile_path(&node.select)?;
+
+        let properties = node
+            .properties
+            .into_iter()
+            .map(|(name, property)| PropertyMapping::compile(name, property))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for key in node.identity.key_properties() {
+            if !properties.iter().any(|property| &property.name == key) {
+                return Err(MappingError::UnmappedIdentityProperty {
+                    node: node.name,
+                    property_name: key.clone(),
+                });
+            }
+        }
+
+        if let IdentityDefinition::Session {
+            creation_timestamp_property,
+            last_seen_timestamp_property,
+            termination_timestamp_property,
+            ..
+        } = &node.identity
+        {
+            let timestamp_type = |name: &str| {
+                properties
+                    .iter()
+                    .find(|property| property.name == name)
+                    .map(|property| property.property_type)
+            };
+
+            if timestamp_type(creation_timestamp_property).is_none()
+                && timestamp_type(last_seen_timestamp_property).is_none()
+            {
+                return Err(MappingError::UnmappedIdentityProperty {
+                    node: node.name,
+                    property_name: creation_timestamp_property.clone(),
+                });
+            }
+
+            for timestamp in [
+                creation_timestamp_property,
+                last_seen_timestamp_property,
+                termination_timestamp_property,
+            ] {
+                match timestamp_type(timestamp) {
+                    None
+                    | Some(
+                        PropertyType::ImmutableU64 | PropertyType::MaxU64 | PropertyType::MinU64,
+                    ) => {}
+                    Some(_) => {
+                        return Err(MappingError::InvalidTimestampProperty {
+                            node: node.name,
+                            property_name: timestamp.clone(),
+                        })
+                    }
+                }
+            }
+        }
+
+        Ok(NodeMapping {
+            name: node.name,
+            node_type: node.node_type,
+            select,
+            identity: node.identity,
+            properties,
+        })
+    }
+
+    /// The values this entry builds nodes from. A path which can't be
+    /// evaluated against `record` selects nothing.
+    pub fn select<'a>(&self, record: &'a Value) -> Vec<&'a Value> {
+        self.select.select(record).unwrap_or_default()
+    }
+}
+
+impl PropertyMapping {
+    fn compile(name: String, property: PropertyDefinition) -> Result<Self, MappingError> {
+        check_name_length("property name", &name)?;
+
+        let source = match (property.path, property.record_path, property.constant) {
+            (Some(path), None, None) => PropertySource::Path(compile_path(&path)?),
+            (None, Some(path), None) => PropertySource::RecordPath(compile_path(&path)?),
+            (None, None, Some(constant)) => PropertySource::Constant(constant),
+            _ => return Err(MappingError::AmbiguousPropertySource(name)),
+        };
+
+        Ok(PropertyMapping {
+            name,
+            source,
+            property_type: property.property_type,
+            transform: property.transform,
+        })
+    }
+
+    /// The first value this property selects, after its transform, if any.
+    pub fn value(&self, record: &Value, selected: &Value) -> Option<Value> {
+        let value = match &self.source {
+            PropertySource::Path(path) => first_value(path, selected)?,
+            PropertySource::RecordPath(path) => first_value(path, record)?,
+            PropertySource::Constant(constant) => constant.clone(),
+        };
+
+        match self.transform {
+            Some(transform) => transform.apply(&value),
+            None => Some(value),
+        }
+    }
+}
+
+impl EdgeMapping {
+    fn compile(edge: EdgeDefinition, nodes: &[NodeMapping]) -> Result<Self, MappingError> {
+        check_name_length("edge_name", &edge.edge_name)?;
+
+        let index_of = |name: &str| {
+            nodes
+                .iter()
+                .position(|node| node.name == name)
+                .ok_or_else(|| MappingError::UnknownNode(name.to_owned()))
+        };
+
+        Ok(EdgeMapping {
+            from: index_of(&edge.from)?,
+            to: index_of(&edge.to)?,
+            edge_name: edge.edge_name,
+        })
+    }
+}
+
+fn compile_path(path: &str) -> Result<Compiled, MappingError> {
+    Compiled::compile(path).map_err(|reason| MappingError::InvalidPath {
+        path: path.to_owned(),
+        reason,
+    })
+}
+
+fn first_value(path: &Compiled, value: &Value) -> Option<Value> {
+    path.select(value)
+        .ok()?
+        .into_iter()
+        .find(|value| !value.is_null())
+        .cloned()
+}
+
+fn check_name_length(kind: &'static str, name: &str) -> Result<(), MappingError> {
+    if name.is_empty() || name.len() > MAX_NAME_LENGTH {
+        return Err(MappingError::InvalidName {
+            kind,
+            name: name.to_owned(),
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn document(value: Value) -> MappingDocument {
+        serde_json::from_value(value).expect("invalid mapping document")
+    }
+
+    fn asset_node() -> Value {
+        json!({
+            "name": "asset",
+            "node_type": "Asset",
+            "identity": {"strategy": "static", "static_key_properties": ["hostname"]},
+            "properties": {
+                "hostname": {"path": "$.host.name", "type": "immutable_string"}
+            }
+        })
+    }
+
+    #[test]
+    fn compiles_a_mapping() {
+        let mapping = Mapping::compile(document(json!({
+            "nodes": [
+                asset_node(),
+                {
+                    "name": "process",
+                    "node_type": "Process",
+                    "select": "$.processes[*]",
+                    "identity": {
+                        "strategy": "session",
+                        "pseudo_key_properties": ["process_id"],
+                        "creation_timestamp_property": "created_time",
+                        "last_seen_timestamp_property": "last_seen_time",
+                        "termination_timestamp_property": "terminated_time"
+                    },
+                    "properties": {
+                        "process_id": {"path": "$.pid", "type": "immutable_u64"},
+                        "created_time": {"record_path": "$.ts", "type": "immutable_u64"},
+                        "last_seen_time": {"record_path": "$.ts", "type": "max_u64"},
+                        "terminated_time": {"constant": 0, "type": "min_u64"}
+                    }
+                }
+            ],
+            "edges": [{"from": "asset", "to": "process", "edge_name": "asset_processes"}]
+        })))
+        .expect("failed to compile mapping");
+
+        assert_eq!(mapping.nodes.len(), 2);
+        assert_eq!(mapping.nodes[1].properties.len(), 4);
+        assert_eq!(mapping.edges[0].from, 0);
+        assert_eq!(mapping.edges[0].to, 1);
+    }
+
+    #[test]
+    fn rejects_invalid_mappings() {
+        let duplicate = document(json!({"nodes": [asset_node(), asset_node()]}));
+        assert!(matches!(
+            Mapping::compile(duplicate),
+            Err(MappingError::DuplicateNodeName(_))
+        ));
+
+        let unknown_edge = document(json!({
+            "nodes": [asset_node()],
+            "edges": [{"from": "asset", "to": "process", "edge_name": "asset_processes"}]
+        }));
+        assert!(matches!(
+            Mapping::compile(unknown_edge),
+            Err(MappingError::UnknownNode(_))
+        ));
+
+        let mut unmapped_key = asset_node();
+        unmapped_key["identity"]["static_key_properties"] = json!(["hostname", "asset_id"]);
+        assert!(matches!(
+            Mapping::compile(document(json!({ "nodes": [unmapped_key] }))),
+            Err(MappingError::UnmappedIdentityProperty { .. })
+        ));
+
+        let mut ambiguous = asset_node();
+        ambiguous["properties"]["hostname"]["constant"] = json!("localhost");
+        assert!(matches!(
+            Mapping::compile(document(json!({ "nodes": [ambiguous] }))),
+            Err(MappingError::AmbiguousPropertySource(_))
+        ));
+
+        let mut invalid_path = asset_node();
+        invalid_path["select"] = json!("$.[");
+        assert!(matches!(
+            Mapping::compile(document(json!({ "nodes": [invalid_path] }))),
+            Err(MappingError::InvalidPath { .. })
+        ));
+    }
+
+    #[test]
+    fn applies_transforms() {
+        assert_eq!(
+            Transform::Rfc3339ToMillis.apply(&json!("2022-06-01T12:00:00.5Z")),
+            Some(json!(1654084800500i64))
+        );
+        assert_eq!(
+            Transform::SecondsToMillis.apply(&json!(1654084800.25)),
+            Some(json!(1654084800250i64))
+        );
+        assert_eq!(
+            Transform::SecondsToMillis.apply(&json!("1654084800")),
+            Some(json!(1654084800000i64))
+        );
+        assert_eq!(
+            Transform::Lowercase.apply(&json!("CMD.EXE")),
+            Some(json!("cmd.exe"))
+        );
+        assert_eq!(Transform::Rfc3339ToMillis.apply(&json!("yesterday")), None);
+    }
+}
@@ -0,0 +1,444 @@
+use std::{
+    collections::{
+        BTreeSet,
+        HashMap,
+    },
+    time::{
+        Duration,
+        SystemTime,
+        UNIX_EPOCH,
+    },
+};
+
+use rust_proto::graplinc::grapl::api::graph::v1beta1::{
+    DecrementOnlyIntProp,
+    DecrementOnlyTimestampProp,
+    DecrementOnlyUintProp,
+    GraphDescription,
+    IdStrategy,
+    ImmutableBoolProp,
+    ImmutableDoubleProp,
+    ImmutableIntProp,
+    ImmutableStrProp,
+    ImmutableTimestampProp,
+    ImmutableUintProp,
+    IncrementOnlyIntProp,
+    IncrementOnlyTimestampProp,
+    IncrementOnlyUintProp,
+    NodeDescription,
+    NodeProperty,
+    Property,
+    Session,
+    Static,
+    StringSetProp,
+};
+use serde_json::Value;
+
+use crate::mapping::{
+    IdentityDefinition,
+    Mapping,
+    NodeMapping,
+    PropertyType,
+};
+
+/// Builds the nodes and edges `mapping` describes from a single log line.
+pub fn generate_graph(mapping: &Mapping, record: &Value) -> GraphDescription {
+    let mut graph = GraphDescription::new();
+
+    // The keys of the nodes built by each entry in `mapping.nodes`
+    let node_keys: Vec<Vec<String>> = mapping
+        .nodes
+        .iter()
+        .map(|node_mapping| {
+            node_mapping
+                .select(record)
+                .into_iter()
+                .filter_map(|selected| build_node(node_mapping, record, selected))
+                .map(|node| {
+                    let node_key = node.clone_node_key();
+                    graph.add_node(node);
+                    node_key
+                })
+                .collect()
+        })
+        .collect();
+
+    for edge in &mapping.edges {
+        for from_node_key in &node_keys[edge.from] {
+            for to_node_key in &node_keys[edge.to] {
+                // An entry with an edge to itself doesn't connect a node to itself
+                if from_node_key != to_node_key {
+                    graph.add_edge(&edge.edge_name, from_node_key, to_node_key);
+                }
+            }
+        }
+    }
+
+    tracing::debug!(
+        message = "completed graph generation",
+        node_count = graph.nodes.len(),
+        edge_count = graph.edges.len(),
+    );
+
+    graph
+}
+
+/// Builds a node from a value `node_mapping` selected, or returns `None` if
+/// the value is missing any of the properties the node is identified by.
+fn build_node(
+    node_mapping: &NodeMapping,
+    record: &Value,
+    selected: &Value,
+) -> Option<NodeDescription> {
+    let mut properties = HashMap::with_capacity(node_mapping.properties.len());
+    for property_mapping in &node_mapping.properties {
+        let value = match property_mapping.value(record, selected) {
+            Some(value) => value,
+            None => continue,
+        };
+
+        match node_property(property_mapping.property_type, &value) {
+            Some(property) => {
+                properties.insert(property_mapping.name.clone(), property);
+            }
+            None => tracing::debug!(
+                message = "property value does not match its type",
+                node = %node_mapping.name,
+                property_name = %property_mapping.name,
+            ),
+        }
+    }
+
+    if let Some(missing) = node_mapping
+        .identity
+        .key_properties()
+        .iter()
+        .find(|key| !properties.contains_key(key.as_str()))
+    {
+        tracing::debug!(
+            message = "skipping node without identity property",
+            node = %node_mapping.name,
+            property_name = %missing,
+        );
+        return None;
+    }
+
+    let id_strategy = match id_strategy(&node_mapping.identity, &properties) {
+        Some(id_strategy) => id_strategy,
+        None => {
+            tracing::debug!(
+                message = "skipping session node without a creation or last seen timestamp",
+                node = %node_mapping.name,
+            );
+            return None;
+        }
+    };
+
+    Some(NodeDescription {
+        properties,
+        node_key: uuid::Uuid::new_v4().to_string(),
+        node_type: node_mapping.node_type.clone(),
+        id_strategy: vec![id_strategy],
+    })
+}
+
+fn id_strategy(
+    identity: &IdentityDefinition,
+    properties: &HashMap<String, NodeProperty>,
+) -> Option<IdStrategy> {
+    match identity {
+        IdentityDefinition::Static {
+            static_key_properties,
+        } => Some(
+            Static {
+                primary_key_properties: static_key_properties.clone(),
+                primary_key_requires_asset_id: false,
+            }
+            .into(),
+        ),
+        IdentityDefinition::Session {
+            pseudo_key_properties,
+            creation_timestamp_property,
+            last_seen_timestamp_property,
+            termination_timestamp_property,
+        } => {
+            let timestamp = |name: &String| properties.get(name).and_then(uint_value).unwrap_or(0);
+
+            let session = Session {
+                primary_key_properties: pseudo_key_properties.clone(),
+                primary_key_requires_asset_id: false,
+                create_time: timestamp(creation_timestamp_property),
+                last_seen_time: timestamp(last_seen_timestamp_property),
+                terminate_time: timestamp(termination_timestamp_property),
+            };
+
+            // node-identifier can't attribute a session without either
+            if session.create_time == 0 && session.last_seen_time == 0 {
+                return None;
+            }
+
+            Some(session.into())
+        }
+    }
+}
+
+fn uint_value(property: &NodeProperty) -> Option<u64> {
+    match &property.property {
+        Property::ImmutableUintProp(prop) => Some(prop.prop),
+        Property::IncrementOnlyUintProp(prop) => Some(prop.prop),
+        Property::DecrementOnlyUintProp(prop) => Some(prop.prop),
+        _ => None,
+    }
+}
+
+/// Converts a selected value to `property_type`, returning `None` if it can't
+/// be represented. Numbers may be given as strings and vice versa.
+fn node_property(property_type: PropertyType, value: &Value) -> Option<NodeProperty> {
+    let property: Property = match property_type {
+        PropertyType::ImmutableString => ImmutableStrProp {
+            prop: string_value(value)?,
+        }
+        .into(),
+        PropertyType::ImmutableI64 => ImmutableIntProp {
+            prop: i64_value(value)?,
+        }
+        .into(),
+        PropertyType::MaxI64 => IncrementOnlyIntProp {
+            prop: i64_value(value)?,
+        }
+        .into(),
+        PropertyType::MinI64 => DecrementOnlyIntProp {
+            prop: i64_value(value)?,
+        }
+        .into(),
+        PropertyType::ImmutableU64 => ImmutableUintProp {
+            prop: u64_value(value)?,
+        }
+        .into(),
+        PropertyType::MaxU64 => IncrementOnlyUintProp {
+            prop: u64_value(value)?,
+        }
+        .into(),
+        PropertyType::MinU64 => DecrementOnlyUintProp {
+            prop: u64_value(value)?,
+        }
+        .into(),
+        PropertyType::ImmutableBool => ImmutableBoolProp {
+            prop: bool_value(value)?,
+        }
+        .into(),
+        PropertyType::ImmutableF64 => ImmutableDoubleProp {
+            prop: f64_value(value)?,
+        }
+        .into(),
+        PropertyType::ImmutableTimestamp => ImmutableTimestampProp {
+            prop: timestamp_value(value)?,
+        }
+        .into(),
+        PropertyType::MaxTimestamp => IncrementOnlyTimestampProp {
+            prop: timestamp_value(value)?,
+        }
+        .into(),
+        PropertyType::MinTimestamp => DecrementOnlyTimestampProp {
+            prop: timestamp_value(value)?,
+        }
+        .into(),
+        PropertyType::StringSet => StringSetProp {
+            prop: string_set_value(value)?,
+        }
+        .into(),
+    };
+
+    Some(property.into())
+}
+
+fn string_value(value: &Value) -> Option<String> {
+    match value {
+        Value::String(value) => Some(value.clone()),
+        Value::Number(value) => Some(value.to_string()),
+        Value::Bool(value) => Some(value.to_string()),
+        _ => None,
+    }
+}
+
+fn i64_value(value: &Value) -> Option<i64> {
+    match value {
+        Value::String(value) => value.parse().ok(),
+        value => value.as_i64(),
+    }
+}
+
+fn u64_value(value: &Value) -> Option<u64> {
+    match value {
+        Value::String(value) => value.parse().ok(),
+        value => value.as_u64(),
+    }
+}
+
+fn bool_value(value: &Value) -> Option<bool> {
+    match value {
+        Value::String(value) => value.parse().ok(),
+        value => value.as_bool(),
+    }
+}
+
+fn f64_value(value: &Value) -> Option<f64> {
+    match value {
+        Value::String(value) => value.parse().ok(),
+        value => value.as_f64(),
+    }
+}
+
+/// Timestamps are given as milliseconds since the unix epoch; use the
+/// `seconds_to_millis` transform for seconds.
+fn timestamp_value(value: &Value) -> Option<SystemTime> {
+    UNIX_EPOCH.checked_add(Duration::from_millis(u64_value(value)?))
+}
+
+/// An array is converted member by member, and any other value becomes a set
+/// of one.
+fn string_set_value(value: &Value) -> Option<BTreeSet<String>> {
+    match value {
+        Value::Array(values) => values.iter().map(string_value).collect(),
+        value => Some(BTreeSet::from([string_value(value)?])),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn mapping() -> Mapping {
+        let document = json!({
+            "nodes": [
+                {
+                    "name": "asset",
+                    "node_type": "Asset",
+                    "identity": {"strategy": "static", "static_key_properties": ["hostname"]},
+                    "properties": {
+                        "hostname": {"path": "$.host", "type": "immutable_string"}
+                    }
+                },
+                {
+                    "name": "process",
+                    "node_type": "Process",
+                    "select": "$.processes[*]",
+                    "identity": {
+                        "strategy": "session",
+                        "pseudo_key_properties": ["process_id"],
+                        "creation_timestamp_property": "created_time",
+                        "last_seen_timestamp_property": "last_seen_time",
+                        "termination_timestamp_property": "terminated_time"
+                    },
+                    "properties": {
+                        "process_id": {"path": "$.pid", "type": "immutable_u64"},
+                        "process_name": {"path": "$.name", "type": "immutable_string"},
+                        "last_seen_time": {
+                            "record_path": "$.time",
+                            "type": "max_u64",
+                            "transform": "seconds_to_millis"
+                        }
+                    }
+                }
+            ],
+            "edges": [{"from": "asset", "to": "process", "edge_name": "asset_processes"}]
+        });
+
+        Mapping::compile(serde_json::from_value(document).expect("invalid mapping document"))
+            .expect("failed to compile mapping")
+    }
+
+    #[test]
+    fn builds_nodes_and_edges() {
+        let record = json!({
+            "host": "web-1",
+            "time": 1654084800,
+            "processes": [{"pid": 1, "name": "init"}, {"pid": "42", "name": "sshd"}]
+        });
+
+        let graph = generate_graph(&mapping(), &record);
+
+        assert_eq!(graph.nodes.len(), 3);
+        assert_eq!(
+            graph
+                .edges
+                .values()
+                .map(|edges| edges.edges.len())
+                .sum::<usize>(),
+            2
+        );
+
+        let process = graph
+            .nodes
+            .values()
+            .find(|node| {
+                node.get_property("process_name")
+                    .and_then(|name| name.as_immutable_str())
+                    .map(|name| name.as_inner() == "sshd")
+                    .unwrap_or(false)
+            })
+            .expect("missing sshd process");
+
+        assert_eq!(
+            process
+                .get_property("process_id")
+                .and_then(|pid| pid.as_immutable_uint())
+                .map(|pid| pid.as_inner()),
+            Some(42)
+        );
+
+        match &process.id_strategy[0].strategy {
+            rust_proto::graplinc::grapl::api::graph::v1beta1::Strategy::Session(session) => {
+                assert_eq!(session.primary_key_properties, vec!["process_id"]);
+                assert_eq!(session.last_seen_time, 1654084800000);
+                assert_eq!(session.create_time, 0);
+            }
+            strategy => panic!("unexpected strategy {strategy:?}"),
+        }
+    }
+
+    #[test]
+    fn skips_nodes_without_identity() {
+        let record = json!({
+            "time": 1654084800,
+            "processes": [{"name": "no-pid"}, {"pid": 7, "name": "cron"}]
+        });
+
+        let graph = generate_graph(&mapping(), &record);
+
+        // Neither the asset, which has no hostname, nor the process without
+        // a PID can be identified
+        assert_eq!(graph.nodes.len(), 1);
+        assert!(graph.edges.is_empty());
+    }
+
+    #[test]
+    fn skips_session_nodes_without_timestamps() {
+        let record = json!({"host": "web-1", "processes": [{"pid": 1}]});
+
+        let graph = generate_graph(&mapping(), &record);
+
+        assert_eq!(graph.nodes.len(), 1);
+    }
+
+    #[test]
+    fn converts_string_sets_and_timestamps() {
+        let string_set =
+            node_property(PropertyType::StringSet, &json!(["-c", 42, "-c"])).expect("string set");
+        assert_eq!(
+            string_set.as_string_set().map(StringSetProp::as_inner),
+            Some(&BTreeSet::from(["-c".to_owned(), "42".to_owned()]))
+        );
+
+        let timestamp =
+            node_property(PropertyType::MaxTimestamp, &json!("1654084800000")).expect("timestamp");
+        assert_eq!(
+            timestamp.as_increment_only_timestamp().map(|p| p.prop),
+            Some(UNIX_EPOCH + Duration::from_secs(1654084800))
+        );
+
+        assert!(node_property(PropertyType::StringSet, &json!([{"nested": true}])).is_none());
+    }
+}
@@ -0,0 +1,328 @@
+//! Checks a mapping against the schema a tenant has deployed, so that a
+//! mistake in the mapping document is reported when the generator starts
+//! rather than as graph-mutation errors for every log line.
+use std::collections::HashSet;
+
+use rust_proto::graplinc::grapl::{
+    api::{
+        client::ClientError,
+        graph_schema_manager::v1beta1::{
+            client::GraphSchemaManagerClient,
+            messages::{
+                GetEdgeSchemaRequest,
+                GetNodeSchemaRequest,
+                GetNodeSchemaResponse,
+                NodeIdentity,
+                PropertyType as SchemaPropertyType,
+            },
+        },
+        protocol::status::Code,
+    },
+    common::v1beta1::types::{
+        EdgeName,
+        NodeType,
+        PropertyName,
+    },
+};
+use thiserror::Error;
+
+use crate::{
+    error::JsonMappingGeneratorError,
+    mapping::{
+        IdentityDefinition,
+        Mapping,
+        NodeMapping,
+        PropertyType,
+    },
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum SchemaMismatch {
+    #[error("node type {node_type} is not deployed")]
+    NodeTypeNotDeployed { node_type: String },
+
+    #[error("node type {node_type} is identified differently by the deployed schema")]
+    IdentityMismatch { node_type: String },
+
+    #[error("node type {node_type} has no property {property_name}")]
+    UnknownProperty {
+        node_type: String,
+        property_name: String,
+    },
+
+    #[error("property {property_name} of node type {node_type} is mapped as {mapped:?} but deployed as {deployed:?}")]
+    PropertyTypeMismatch {
+        node_type: String,
+        property_name: String,
+        mapped: SchemaPropertyType,
+        deployed: SchemaPropertyType,
+    },
+
+    #[error("node type {node_type} has no edge {edge_name}")]
+    EdgeNotDeployed {
+        node_type: String,
+        edge_name: String,
+    },
+}
+
+impl From<PropertyType> for SchemaPropertyType {
+    fn from(property_type: PropertyType) -> Self {
+        match property_type {
+            PropertyType::ImmutableString => SchemaPropertyType::ImmutableString,
+            PropertyType::ImmutableI64 => SchemaPropertyType::ImmutableI64,
+            PropertyType::MaxI64 => SchemaPropertyType::MaxI64,
+            PropertyType::MinI64 => SchemaPropertyType::MinI64,
+            PropertyType::ImmutableU64 => SchemaPropertyType::ImmutableU64,
+            PropertyType::MaxU64 => SchemaPropertyType::MaxU64,
+            PropertyType::MinU64 => SchemaPropertyType::MinU64,
+            PropertyType::ImmutableBool => SchemaPropertyType::ImmutableBool,
+            PropertyType::ImmutableF64 => SchemaPropertyType::ImmutableF64,
+            PropertyType::ImmutableTimestamp => SchemaPropertyType::ImmutableTimestamp,
+            PropertyType::MaxTimestamp => SchemaPropertyType::MaxTimestamp,
+            PropertyType::MinTimestamp => SchemaPropertyType::MinTimestamp,
+            PropertyType::StringSet => SchemaPropertyType::StringSet,
+        }
+    }
+}
+
+/// Compares a node mapping with the deployed schema for its node type.
+pub fn check_node_schema(
+    node_mapping: &NodeMapping,
+    schema: &GetNodeSchemaResponse,
+) -> Vec<SchemaMismatch> {
+    let mut mismatches = Vec::new();
+
+    if !identity_matches(&node_mapping.identity, &schema.identity) {
+        mismatches.push(SchemaMismatch::IdentityMismatch {
+            node_type: node_mapping.node_type.clone(),
+        });
+    }
+
+    for property_mapping in &node_mapping.properties {
+        let deployed = schema
+            .properties
+            .iter()
+            .find(|property| property.property_name.value == property_mapping.name);
+
+        let mapped = SchemaPropertyType::from(property_mapping.property_type);
+        match deployed {
+            None => mismatches.push(SchemaMismatch::UnknownProperty {
+                node_type: node_mapping.node_type.clone(),
+                property_name: property_mapping.name.clone(),
+            }),
+            Some(deployed) if deployed.property_type != mapped => {
+                mismatches.push(SchemaMismatch::PropertyTypeMismatch {
+                    node_type: node_mapping.node_type.clone(),
+                    property_name: property_mapping.name.clone(),
+                    mapped,
+                    deployed: deployed.property_type,
+                })
+            }
+            Some(_) => {}
+        }
+    }
+
+    mismatches
+}
+
+fn identity_matches(identity: &IdentityDefinition, deployed: &NodeIdentity) -> bool {
+    match (identity, deployed) {
+        (
+            IdentityDefinition::Static {
+                static_key_properties,
+            },
+            NodeIdentity::Static(deployed),
+        ) => same_names(static_key_properties, &deployed.static_key_properties),
+        (
+            IdentityDefinition::Session {
+                pseudo_key_properties,
+                creation_timestamp_property,
+                last_seen_timestamp_property,
+                termination_timestamp_property,
+            },
+            NodeIdentity::Session(deployed),
+        ) => {
+            same_names(pseudo_key_properties, &deployed.pseudo_key_properties)
+                && *creation_timestamp_property == deployed.creation_timestamp_property.value
+                && *last_seen_timestamp_property == deployed.last_seen_timestamp_property.value
+                && *termination_timestamp_property == deployed.termination_timestamp_property.value
+        }
+        _ => false,
+    }
+}
+
+/// Key properties are compared irrespective of order.
+fn same_names(mapped: &[String], deployed: &[PropertyName]) -> bool {
+    let mapped: HashSet<&str> = mapped.iter().map(String::as_str).collect();
+    let deployed: HashSet<&str> = deployed.iter().map(|name| name.value.as_str()).collect();
+    mapped == deployed
+}
+
+/// Fetches the tenant's schema for every node type and edge in `mapping`,
+/// returning `JsonMappingGeneratorError::SchemaMismatch` if any of them
+/// differ.
+#[tracing::instrument(skip(client, mapping), err)]
+pub async fn validate_mapping(
+    client: &mut GraphSchemaManagerClient,
+    tenant_id: uuid::Uuid,
+    mapping: &Mapping,
+) -> Result<(), JsonMappingGeneratorError> {
+    let mut mismatches = Vec::new();
+
+    for node_mapping in &mapping.nodes {
+        let request = GetNodeSchemaRequest {
+            tenant_id,
+            node_type: NodeType {
+                value: node_mapping.node_type.clone(),
+            },
+        };
+
+        match client.get_node_schema(request).await {
+            Ok(schema) => mismatches.extend(check_node_schema(node_mapping, &schema)),
+            Err(ClientError::Status(status)) if status.code() == Code::NotFound => {
+                mismatches.push(SchemaMismatch::NodeTypeNotDeployed {
+                    node_type: node_mapping.node_type.clone(),
+                })
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    for edge in &mapping.edges {
+        let node_type = &mapping.nodes[edge.from].node_type;
+        let request = GetEdgeSchemaRequest {
+            tenant_id,
+            node_type: NodeType {
+                value: node_type.clone(),
+            },
+            edge_name: EdgeName {
+                value: edge.edge_name.clone(),
+            },
+        };
+
+        match client.get_edge_schema(request).await {
+            Ok(_) => {}
+            Err(ClientError::Status(status)) if status.code() == Code::NotFound => {
+                mismatches.push(SchemaMismatch::EdgeNotDeployed {
+                    node_type: node_type.clone(),
+                    edge_name: edge.edge_name.clone(),
+                })
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    if mismatches.is_empty() {
+        Ok(())
+    } else {
+        Err(JsonMappingGeneratorError::SchemaMismatch(mismatches))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_proto::graplinc::grapl::api::graph_schema_manager::v1beta1::messages::{
+        PropertySchema,
+        SessionIdentity,
+        StaticIdentity,
+    };
+    use serde_json::json;
+
+    use super::*;
+
+    fn property_name(name: &str) -> PropertyName {
+        PropertyName::new_unchecked(name.to_owned())
+    }
+
+    fn property_schema(name: &str, property_type: SchemaPropertyType) -> PropertySchema {
+        PropertySchema {
+            property_name: property_name(name),
+            property_type,
+            identity_only: false,
+        }
+    }
+
+    fn process_mapping() -> NodeMapping {
+        let document = json!({
+            "nodes": [{
+                "name": "process",
+                "node_type": "Process",
+                "identity": {
+                    "strategy": "session",
+                    "pseudo_key_properties": ["process_id"],
+                    "creation_timestamp_property": "created_time",
+                    "last_seen_timestamp_property": "last_seen_time",
+                    "termination_timestamp_property": "terminated_time"
+                },
+                "properties": {
+                    "process_id": {"path": "$.pid", "type": "immutable_u64"},
+                    "process_name": {"path": "$.name", "type": "immutable_string"},
+                    "created_time": {"path": "$.time", "type": "immutable_u64"}
+                }
+            }]
+        });
+
+        Mapping::compile(serde_json::from_value(document).expect("invalid mapping document"))
+            .expect("failed to compile mapping")
+            .nodes
+            .remove(0)
+    }
+
+    fn process_schema() -> GetNodeSchemaResponse {
+        GetNodeSchemaResponse {
+            schema_version: 1,
+            identity: NodeIdentity::Session(SessionIdentity {
+                pseudo_key_properties: vec![property_name("process_id")],
+                creation_timestamp_property: property_name("created_time"),
+                last_seen_timestamp_property: property_name("last_seen_time"),
+                termination_timestamp_property: property_name("terminated_time"),
+            }),
+            properties: vec![
+                property_schema("process_id", SchemaPropertyType::ImmutableU64),
+                property_schema("process_name", SchemaPropertyType::ImmutableString),
+                property_schema("created_time", SchemaPropertyType::ImmutableU64),
+                property_schema("last_seen_time", SchemaPropertyType::MaxU64),
+                property_schema("terminated_time", SchemaPropertyType::ImmutableU64),
+            ],
+        }
+    }
+
+    #[test]
+    fn accepts_a_matching_schema() {
+        assert_eq!(
+            check_node_schema(&process_mapping(), &process_schema()),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn reports_mismatches() {
+        let mut schema = process_schema();
+        schema.identity = NodeIdentity::Static(StaticIdentity {
+            static_key_properties: vec![property_name("process_id")],
+        });
+        schema.properties.remove(1);
+        schema.properties[1].property_type = SchemaPropertyType::MaxU64;
+
+        let mismatches = check_node_schema(&process_mapping(), &schema);
+
+        assert_eq!(
+            mismatches,
+            vec![
+                SchemaMismatch::IdentityMismatch {
+                    node_type: "Process".to_owned(),
+                },
+                SchemaMismatch::PropertyTypeMismatch {
+                    node_type: "Process".to_owned(),
+                    property_name: "created_time".to_owned(),
+                    mapped: SchemaPropertyType::ImmutableU64,
+                    deployed: SchemaPropertyType::MaxU64,
+                },
+                SchemaMismatch::UnknownProperty {
+                    node_type: "Process".to_owned(),
+                    property_name: "process_name".to_owned(),
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,151 @@
+use generator_sdk::test_utils::test_ctx::GeneratorTestContext;
+use json_mapping_generator::{
+    api::JsonMappingGenerator,
+    mapping::Mapping,
+};
+use rust_proto::graplinc::grapl::api::{
+    graph::v1beta1::{
+        GraphDescription,
+        ImmutableStrProp,
+        ImmutableUintProp,
+        NodeDescription,
+        Property,
+    },
+    plugin_sdk::generators::v1beta1::RunGeneratorRequest,
+};
+use test_context::test_context;
+
+fn find_nodes<'a>(
+    graph: &'a GraphDescription,
+    o_p_name: &str,
+    o_p_value: Property,
+) -> Vec<&'a NodeDescription> {
+    graph
+        .nodes
+        .values()
+        .filter(|n| {
+            n.properties.iter().any(|(p_name, p_value)| {
+                p_name.as_str() == o_p_name && p_value.property.clone() == o_p_value
+            })
+        })
+        .collect()
+}
+
+fn has_edge(
+    graph: &GraphDescription,
+    from: &NodeDescription,
+    edge_name: &str,
+    to: &NodeDescription,
+) -> bool {
+    graph
+        .edges
+        .get(from.get_node_key())
+        .iter()
+        .flat_map(|edge_list| edge_list.edges.iter())
+        .any(|edge| edge.edge_name == edge_name && edge.to_node_key == to.get_node_key())
+}
+
+#[test_context(GeneratorTestContext)]
+#[tokio::test]
+async fn test_edr_process_events_produce_expected_graph(
+    ctx: &mut GeneratorTestContext,
+) -> eyre::Result<()> {
+    let mapping = Mapping::from_slice(&std::fs::read("sample_data/edr_process_mapping.json")?)?;
+    let mut client = ctx.get_client(JsonMappingGenerator::new(mapping)).await;
+
+    let events = std::fs::read("sample_data/unit/edr_process_events.jsonl")?;
+    let result = client
+        .run_generator(RunGeneratorRequest {
+            data: events.into(),
+        })
+        .await?;
+    let generated_graph = result.generated_graph.graph_description;
+
+    // An asset, a process and its parent from each event, and the two files
+    // written by the first
+    assert_eq!(generated_graph.nodes.len(), 8);
+
+    let assets = find_nodes(
+        &generated_graph,
+        "hostname",
+        ImmutableStrProp::from("ws-0042").into(),
+    );
+    assert_eq!(assets.len(), 2);
+
+    let explorer = find_nodes(
+        &generated_graph,
+        "process_name",
+        ImmutableStrProp::from("explorer.exe").into(),
+    );
+    let powershell = find_nodes(
+        &generated_graph,
+        "process_name",
+        ImmutableStrProp::from("powershell.exe").into(),
+    );
+    assert_eq!(explorer.len(), 1);
+    assert_eq!(powershell.len(), 2);
+
+    // The powershell process the first event describes, rather than the
+    // parent of the second
+    let started_powershell = find_nodes(
+        &generated_graph,
+        "created_time",
+        ImmutableUintProp {
+            prop: 1654084799500,
+        }
+        .into(),
+    );
+    assert_eq!(started_powershell.len(), 1);
+    let started_powershell = started_powershell[0];
+
+    assert!(has_edge(
+        &generated_graph,
+        explorer[0],
+        "children",
+        started_powershell
+    ));
+    assert!(assets.iter().any(|asset| has_edge(
+        &generated_graph,
+        asset,
+        "asset_processes",
+        started_powershell
+    )));
+
+    let stage1 = find_nodes(
+        &generated_graph,
+        "file_path",
+        ImmutableStrProp::from("C:\\Users\\alice\\AppData\\Local\\Temp\\stage1.ps1").into(),
+    );
+    assert_eq!(stage1.len(), 1);
+    assert!(has_edge(
+        &generated_graph,
+        started_powershell,
+        "created_files",
+        stage1[0]
+    ));
+
+    Ok(())
+}
+
+#[test_context(GeneratorTestContext)]
+#[tokio::test]
+async fn test_malformed_lines_are_skipped(ctx: &mut GeneratorTestContext) -> eyre::Result<()> {
+    let mapping = Mapping::from_slice(&std::fs::read("sample_data/edr_process_mapping.json")?)?;
+    let mut client = ctx.get_client(JsonMappingGenerator::new(mapping)).await;
+
+    let events = std::fs::read_to_string("sample_data/unit/edr_process_events.jsonl")?;
+    let mut lines = events.lines();
+    let (first, second) = (lines.next().unwrap(), lines.next().unwrap());
+    let data = format!("{first}\n{{\"process\": \n{second}\n{{\"process\": ");
+
+    let result = client
+        .run_generator(RunGeneratorRequest { data: data.into() })
+        .await?;
+    let generated_graph = result.generated_graph.graph_description;
+
+    // Both well-formed events are mapped as if the malformed lines weren't
+    // there
+    assert_eq!(generated_graph.nodes.len(), 8);
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use rust_proto::graplinc::grapl::api::{
+    graph::v1beta1::GraphDescription,
+    plugin_sdk::generators::v1beta1::{
+        server::GeneratorApi,
+        GeneratedGraph,
+        RunGeneratorRequest,
+        RunGeneratorResponse,
+    },
+};
+
+use crate::{
+    error::K8sAuditGeneratorError,
+    models,
+    parser,
+};
+
+pub struct K8sAuditGenerator {}
+
+#[async_trait::async_trait]
+impl GeneratorApi for K8sAuditGenerator {
+    type Error = K8sAuditGeneratorError;
+
+    /// Accepts audit events as the API server's log backend writes them (one
+    /// per line), or `EventList`s as its webhook backend sends them; see
+    /// `parser::parse_events`.
+    #[tracing::instrument(skip(self, request), err)]
+    async fn run_generator(
+        &self,
+        request: RunGeneratorRequest,
+    ) -> Result<RunGeneratorResponse, Self::Error> {
+        let mut graph_description = GraphDescription::new();
+        for event in parser::parse_events(&request.data)? {
+            if let Some(graph) = models::generate_graph_from_event(&event) {
+                graph_description.merge(&graph);
+            }
+        }
+
+        Ok(RunGeneratorResponse {
+            generated_graph: GeneratedGraph { graph_description },
+        })
+    }
+}
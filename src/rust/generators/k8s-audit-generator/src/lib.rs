@@ -0,0 +1,5 @@
+pub mod api;
+pub mod error;
+pub mod models;
+pub mod nodes;
+pub mod parser;
@@ -0,0 +1,259 @@
+use rust_proto::graplinc::grapl::api::graph::v1beta1::GraphDescription;
+
+use crate::{
+    nodes::{
+        IK8sApiRequestNode,
+        IK8sNamespaceNode,
+        IK8sPrincipalNode,
+        K8sApiRequestNode,
+        K8sNamespaceNode,
+        K8sPrincipalNode,
+    },
+    parser::{
+        AuditEvent,
+        Stage,
+    },
+};
+
+mod pod;
+mod session;
+
+/// Creates a subgraph describing an audit event.
+///
+/// `exec` and `attach` requests produce a `K8sExecSession` node; every other
+/// request to a resource produces a `K8sApiRequest` node. Either is linked to:
+/// * the `K8sPrincipal` that made the request (and the one it impersonated,
+///   if any)
+/// * the `K8sNamespace` the request targeted
+/// * the `K8sPod` the request targeted, which is in turn linked to its
+///   namespace, its service account and, once it has been scheduled, the
+///   `Asset` of the node it runs on
+/// * for sessions, the `K8sContainer` the request URI names, which is linked
+///   to its pod
+///
+/// Kubernetes node names are the hostnames auditd-generator and
+/// sysmon-generator identify assets by, so a session's pod shares an `Asset`
+/// with the processes it started.
+///
+/// Returns `None` for events which add nothing to the graph:
+/// * `RequestReceived` events, which are always followed by another event for
+///   the same request
+/// * `watch` requests, which are made continuously by controllers
+/// * requests to non-resource URLs, e.g. `/healthz`
+#[tracing::instrument(skip(event), fields(audit_id = %event.audit_id, verb = %event.verb))]
+pub(crate) fn generate_graph_from_event(event: &AuditEvent) -> Option<GraphDescription> {
+    tracing::trace!("generating graph from event");
+
+    let object_ref = event.object_ref.as_ref()?;
+    let username = event.user.username.as_deref()?;
+    let session_type = session::session_type(event);
+
+    match (event.stage, session_type) {
+        (Stage::RequestReceived, _) => return None,
+        // Sessions are recorded as they start and again when they end. Other
+        // long-running requests are recorded only once they complete.
+        (Stage::ResponseStarted, None) => return None,
+        (_, None) if event.verb == "watch" => return None,
+        _ => {}
+    }
+
+    let timestamp = event.request_received_timestamp;
+    let mut graph = GraphDescription::new();
+
+    let principal_key = add_principal(&mut graph, username, Some(timestamp));
+
+    if let Some(impersonated) = event
+        .impersonated_user
+        .as_ref()
+        .and_then(|impersonated_user| impersonated_user.username.as_deref())
+    {
+        let impersonated_key = add_principal(&mut graph, impersonated, Some(timestamp));
+        graph.add_edge("impersonated_principals", &principal_key, impersonated_key);
+    }
+
+    let subject_key = match session_type {
+        Some(session_type) => {
+            let session = session::session_node(event, session_type);
+            let session_key = session.clone_node_key();
+            graph.add_edge("exec_sessions", &principal_key, &session_key);
+            graph.add_node(session);
+            session_key
+        }
+        None => {
+            let request = request_node(event);
+            let request_key = request.clone_node_key();
+            graph.add_edge("api_requests", &principal_key, &request_key);
+            graph.add_node(request);
+            request_key
+        }
+    };
+
+    if let Some(namespace) = object_ref.namespace.as_deref() {
+        let namespace = namespace_node(namespace, timestamp);
+        let edge_name = match session_type {
+            Some(_) => "session_namespace",
+            None => "request_namespace",
+        };
+        graph.add_edge(edge_name, &subject_key, namespace.clone_node_key());
+        graph.add_node(namespace);
+    }
+
+    match session_type.and_then(|_| event.session_target()) {
+        Some(target) => session::add_session_target(&mut graph, &subject_key, &target, timestamp),
+        None => {
+            if let Some(pod_key) = pod::add_pod(&mut graph, event) {
+                let edge_name = match session_type {
+                    Some(_) => "session_pod",
+                    None => "request_pod",
+                };
+                graph.add_edge(edge_name, &subject_key, pod_key);
+            }
+        }
+    }
+
+    tracing::debug!(
+        message = "completed graph generation",
+        node_count = graph.nodes.len(),
+        edge_count = graph.edges.len(),
+    );
+
+    Some(graph)
+}
+
+fn request_node(event: &AuditEvent) -> K8sApiRequestNode {
+    let mut request = K8sApiRequestNode::new(K8sApiRequestNode::static_strategy());
+    request
+        .with_audit_id(&event.audit_id)
+        .with_verb(&event.verb)
+        .with_request_uri(&event.request_uri)
+        .with_request_timestamp(event.request_received_timestamp);
+
+    if let Some(object_ref) = &event.object_ref {
+        if let Some(resource) = &object_ref.resource {
+            request.with_resource(resource);
+        }
+
+        if let Some(subresource) = &object_ref.subresource {
+            request.with_subresource(subresource);
+        }
+
+        if let Some(api_group) = &object_ref.api_group {
+            request.with_api_group(api_group);
+        }
+
+        if let Some(namespace) = &object_ref.namespace {
+            request.with_namespace(namespace);
+        }
+
+        if let Some(name) = &object_ref.name {
+            request.with_object_name(name);
+        }
+    }
+
+    if let Some(source_ip_address) = event.source_ips.first() {
+        request.with_source_ip_address(source_ip_address);
+    }
+
+    if let Some(user_agent) = &event.user_agent {
+        request.with_user_agent(user_agent);
+    }
+
+    if let Some(response_code) = event.response_code() {
+        request.with_response_code(response_code);
+    }
+
+    request
+}
+
+/// Adds the principal with `username`, linked to its namespace if it is a
+/// service account, and returns its node key.
+pub(super) fn add_principal(
+    graph: &mut GraphDescription,
+    username: &str,
+    timestamp: Option<u64>,
+) -> String {
+    let mut principal = K8sPrincipalNode::new(K8sPrincipalNode::static_strategy());
+    principal
+        .with_username(username)
+        .with_principal_type(principal_type(username));
+
+    if let Some(timestamp) = timestamp {
+        principal
+            .with_first_seen_timestamp(timestamp)
+            .with_last_seen_timestamp(timestamp);
+    }
+
+    if let Some(namespace_name) = service_account_namespace(username) {
+        principal.with_namespace(namespace_name);
+
+        let mut namespace = K8sNamespaceNode::new(K8sNamespaceNode::static_strategy());
+        namespace.with_namespace_name(namespace_name);
+
+        graph.add_edge(
+            "principal_namespace",
+            principal.clone_node_key(),
+            namespace.clone_node_key(),
+        );
+        graph.add_node(namespace);
+    }
+
+    let principal_key = principal.clone_node_key();
+    graph.add_node(principal);
+
+    principal_key
+}
+
+/// Classifies a principal by its username; see [Kubernetes authentication].
+///
+/// [Kubernetes authentication]: https://kubernetes.io/docs/reference/access-authn-authz/authentication/
+fn principal_type(username: &str) -> &'static str {
+    if username.starts_with("system:serviceaccount:") {
+        "service_account"
+    } else if username.starts_with("system:node:") {
+        "node"
+    } else if username.starts_with("system:") {
+        "system"
+    } else {
+        "user"
+    }
+}
+
+/// Service accounts' usernames are `system:serviceaccount:<namespace>:<name>`.
+fn service_account_namespace(username: &str) -> Option<&str> {
+    username
+        .strip_prefix("system:serviceaccount:")
+        .and_then(|service_account| service_account.split_once(':'))
+        .map(|(namespace, _)| namespace)
+}
+
+pub(super) fn namespace_node(namespace_name: &str, timestamp: u64) -> K8sNamespaceNode {
+    let mut namespace = K8sNamespaceNode::new(K8sNamespaceNode::static_strategy());
+    namespace
+        .with_namespace_name(namespace_name)
+        .with_first_seen_timestamp(timestamp)
+        .with_last_seen_timestamp(timestamp);
+
+    namespace
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_principals() {
+        assert_eq!(principal_type("alice@example.com"), "user");
+        assert_eq!(
+            principal_type("system:serviceaccount:payments:api"),
+            "service_account"
+        );
+        assert_eq!(principal_type("system:node:ip-10-0-1-23"), "node");
+        assert_eq!(principal_type("system:kube-scheduler"), "system");
+
+        assert_eq!(
+            service_account_namespace("system:serviceaccount:payments:api"),
+            Some("payments")
+        );
+        assert_eq!(service_account_namespace("alice@example.com"), None);
+    }
+}
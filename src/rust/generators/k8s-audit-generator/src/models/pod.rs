@@ -0,0 +1,148 @@
+use endpoint_plugin::{
+    AssetNode,
+    IAssetNode,
+};
+use rust_proto::graplinc::grapl::api::graph::v1beta1::GraphDescription;
+use serde::Deserialize;
+
+use crate::{
+    models::{
+        add_principal,
+        namespace_node,
+    },
+    nodes::{
+        IK8sPodNode,
+        K8sPodNode,
+    },
+    parser::AuditEvent,
+};
+
+/// The parts of a `Pod` object this generator reads.
+#[derive(Debug, Deserialize)]
+struct Pod {
+    metadata: Option<ObjectMeta>,
+    spec: Option<PodSpec>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ObjectMeta {
+    name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PodSpec {
+    service_account_name: Option<String>,
+    node_name: Option<String>,
+}
+
+/// The object the scheduler creates (`pods/binding`) to assign a pod to a
+/// node.
+#[derive(Debug, Deserialize)]
+struct Binding {
+    target: BindingTarget,
+}
+
+#[derive(Debug, Deserialize)]
+struct BindingTarget {
+    name: String,
+}
+
+/// Adds the `K8sPod` an event's request targeted, if it targeted one, and
+/// returns its node key.
+///
+/// Successful requests to create, delete or bind the pod also record when it
+/// was created or deleted, its service account and the node it was scheduled
+/// to.
+pub(super) fn add_pod(graph: &mut GraphDescription, event: &AuditEvent) -> Option<String> {
+    let object_ref = event.object_ref.as_ref()?;
+    if object_ref.resource.as_deref() != Some("pods") {
+        return None;
+    }
+
+    let namespace_name = object_ref.namespace.as_deref()?;
+    let subresource = object_ref.subresource.as_deref();
+    let succeeded = matches!(event.response_code(), Some(200..=299));
+    let timestamp = event.request_received_timestamp;
+
+    // Pods created with `generateName` are only named in the response
+    let pod_object = match (event.verb.as_str(), subresource) {
+        ("create", None) => event.object::<Pod>(),
+        _ => None,
+    };
+
+    let pod_name = object_ref.name.clone().or_else(|| {
+        pod_object
+            .as_ref()
+            .and_then(|pod| pod.metadata.as_ref())
+            .and_then(|metadata| metadata.name.clone())
+    })?;
+
+    let mut pod = K8sPodNode::new(K8sPodNode::static_strategy());
+    pod.with_namespace(namespace_name)
+        .with_pod_name(&pod_name)
+        .with_last_seen_timestamp(timestamp);
+
+    let mut node_name = None;
+    let mut service_account_name = None;
+
+    if succeeded {
+        match (event.verb.as_str(), subresource) {
+            ("create", None) => {
+                pod.with_created_timestamp(timestamp);
+
+                if let Some(spec) = pod_object.and_then(|pod| pod.spec) {
+                    node_name = spec.node_name;
+                    service_account_name = spec.service_account_name;
+                }
+            }
+            ("delete", None) => {
+                pod.with_deleted_timestamp(timestamp);
+            }
+            ("create", Some("binding")) => {
+                node_name = event.object::<Binding>().map(|binding| binding.target.name);
+            }
+            _ => {}
+        }
+    }
+
+    let namespace = namespace_node(namespace_name, timestamp);
+    graph.add_edge(
+        "pod_namespace",
+        pod.clone_node_key(),
+        namespace.clone_node_key(),
+    );
+    graph.add_node(namespace);
+
+    if let Some(node_name) = node_name {
+        pod.with_node_name(&node_name);
+
+        // Kubernetes node names are the hostnames of the machines which run
+        // them, which is what endpoint generators identify assets by.
+        let mut asset = AssetNode::new(AssetNode::static_strategy());
+        asset.with_asset_id(&node_name).with_hostname(&node_name);
+
+        graph.add_edge("pod_asset", pod.clone_node_key(), asset.clone_node_key());
+        graph.add_node(asset);
+    }
+
+    if let Some(service_account_name) = service_account_name {
+        pod.with_service_account_name(&service_account_name);
+
+        let service_account_key = add_principal(
+            graph,
+            &format!("system:serviceaccount:{namespace_name}:{service_account_name}"),
+            None,
+        );
+        graph.add_edge(
+            "pod_service_account",
+            pod.clone_node_key(),
+            service_account_key,
+        );
+    }
+
+    let pod_key = pod.clone_node_key();
+    graph.add_node(pod);
+
+    Some(pod_key)
+}
@@ -0,0 +1,114 @@
+use rust_proto::graplinc::grapl::api::graph::v1beta1::GraphDescription;
+
+use crate::{
+    models::namespace_node,
+    nodes::{
+        IK8sContainerNode,
+        IK8sExecSessionNode,
+        IK8sPodNode,
+        K8sContainerNode,
+        K8sExecSessionNode,
+        K8sPodNode,
+    },
+    parser::{
+        AuditEvent,
+        SessionTarget,
+        Stage,
+    },
+};
+
+/// Returns `exec` or `attach` if the event is for a request which opens a
+/// session in a container.
+pub(super) fn session_type(event: &AuditEvent) -> Option<&'static str> {
+    let object_ref = event.object_ref.as_ref()?;
+    if object_ref.resource.as_deref() != Some("pods") {
+        return None;
+    }
+
+    match object_ref.subresource.as_deref() {
+        Some("exec") => Some("exec"),
+        Some("attach") => Some("attach"),
+        _ => None,
+    }
+}
+
+/// Creates the `K8sExecSession` for an `exec` or `attach` request. The event
+/// for each stage of the request describes the same node, and the
+/// `ResponseComplete` event records when the session ended.
+pub(super) fn session_node(event: &AuditEvent, session_type: &str) -> K8sExecSessionNode {
+    let mut session = K8sExecSessionNode::new(K8sExecSessionNode::static_strategy());
+    session
+        .with_audit_id(&event.audit_id)
+        .with_session_type(session_type)
+        .with_started_timestamp(event.request_received_timestamp);
+
+    if let Some(container_name) = event.query_values("container").first() {
+        session.with_container_name(container_name);
+    }
+
+    let command = event.query_values("command");
+    if !command.is_empty() {
+        session.with_command(command.join(" "));
+    }
+
+    if let Some(source_ip_address) = event.source_ips.first() {
+        session.with_source_ip_address(source_ip_address);
+    }
+
+    if let Some(user_agent) = &event.user_agent {
+        session.with_user_agent(user_agent);
+    }
+
+    if let Some(response_code) = event.response_code() {
+        session.with_response_code(response_code);
+    }
+
+    if matches!(event.stage, Stage::ResponseComplete | Stage::Panic) {
+        session.with_ended_timestamp(event.stage_timestamp);
+    }
+
+    session
+}
+
+/// Links a session to the pod it runs in, and to the container when the
+/// request named one, as identified by the request URI. Processes the
+/// session starts run in that container, on the `Asset` the pod is bound to.
+pub(super) fn add_session_target(
+    graph: &mut GraphDescription,
+    session_key: &str,
+    target: &SessionTarget,
+    timestamp: u64,
+) {
+    let mut pod = K8sPodNode::new(K8sPodNode::static_strategy());
+    pod.with_namespace(&target.namespace)
+        .with_pod_name(&target.pod_name)
+        .with_last_seen_timestamp(timestamp);
+
+    let namespace = namespace_node(&target.namespace, timestamp);
+    graph.add_edge(
+        "pod_namespace",
+        pod.clone_node_key(),
+        namespace.clone_node_key(),
+    );
+    graph.add_edge("session_pod", session_key, pod.clone_node_key());
+    graph.add_node(namespace);
+
+    if let Some(container_name) = &target.container_name {
+        let mut container = K8sContainerNode::new(K8sContainerNode::static_strategy());
+        container
+            .with_namespace(&target.namespace)
+            .with_pod_name(&target.pod_name)
+            .with_container_name(container_name)
+            .with_last_seen_timestamp(timestamp);
+
+        graph.add_edge(
+            "container_pod",
+            container.clone_node_key(),
+            pod.clone_node_key(),
+        );
+        graph.add_edge("session_container", session_key, container.clone_node_key());
+        graph.add_node(container);
+    }
+
+    graph.add_node(pod);
+}
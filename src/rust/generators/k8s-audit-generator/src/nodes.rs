@@ -0,0 +1,43 @@
+//! The node types this generator emits. Every node uses a static identity
+//! strategy: the API server identifies principals by username and objects by
+//! namespace and name, and each request by its audit ID.
+
+mod k8s_api_request;
+mod k8s_container;
+mod k8s_exec_session;
+mod k8s_namespace;
+mod k8s_pod;
+mod k8s_principal;
+
+pub use self::{
+    k8s_api_request::{
+        IK8sApiRequestNode,
+        K8sApiRequest,
+        K8sApiRequestNode,
+    },
+    k8s_container::{
+        IK8sContainerNode,
+        K8sContainer,
+        K8sContainerNode,
+    },
+    k8s_exec_session::{
+        IK8sExecSessionNode,
+        K8sExecSession,
+        K8sExecSessionNode,
+    },
+    k8s_namespace::{
+        IK8sNamespaceNode,
+        K8sNamespace,
+        K8sNamespaceNode,
+    },
+    k8s_pod::{
+        IK8sPodNode,
+        K8sPod,
+        K8sPodNode,
+    },
+    k8s_principal::{
+        IK8sPrincipalNode,
+        K8sPrincipal,
+        K8sPrincipalNode,
+    },
+};
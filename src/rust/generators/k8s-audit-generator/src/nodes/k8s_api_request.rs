@@ -0,0 +1,50 @@
+use derive_dynamic_node::{
+    GraplStaticId,
+    NodeDescription,
+};
+use rust_proto::graplinc::grapl::api::graph::v1beta1::{
+    IdStrategy,
+    NodeDescription,
+    NodeProperty,
+    Static,
+};
+
+/// A single request to the API server. The API server assigns every request a
+/// unique audit ID, so requests are never merged with one another.
+#[derive(NodeDescription, GraplStaticId)]
+pub struct K8sApiRequest {
+    #[grapl(static_id, immutable)]
+    audit_id: String,
+    #[grapl(immutable)]
+    verb: String,
+    #[grapl(immutable)]
+    resource: String,
+    #[grapl(immutable)]
+    subresource: String,
+    #[grapl(immutable)]
+    api_group: String,
+    #[grapl(immutable)]
+    namespace: String,
+    #[grapl(immutable)]
+    object_name: String,
+    #[grapl(immutable)]
+    request_uri: String,
+    #[grapl(immutable)]
+    source_ip_address: String,
+    #[grapl(immutable)]
+    user_agent: String,
+    #[grapl(immutable)]
+    response_code: u64,
+    #[grapl(immutable)]
+    request_timestamp: u64,
+}
+
+impl IK8sApiRequestNode for K8sApiRequestNode {
+    fn get_mut_dynamic_node(&mut self) -> &mut NodeDescription {
+        &mut self.dynamic_node
+    }
+
+    fn get_dynamic_node(&self) -> &NodeDescription {
+        &self.dynamic_node
+    }
+}
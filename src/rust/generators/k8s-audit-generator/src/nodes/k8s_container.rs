@@ -0,0 +1,35 @@
+use derive_dynamic_node::{
+    GraplStaticId,
+    NodeDescription,
+};
+use rust_proto::graplinc::grapl::api::graph::v1beta1::{
+    IdStrategy,
+    NodeDescription,
+    NodeProperty,
+    Static,
+};
+
+/// A container in a pod, identified by the pod's namespace and name and its
+/// own name. Like `K8sPod`, a container which is recreated under the same
+/// pod and name is the same node.
+#[derive(NodeDescription, GraplStaticId)]
+pub struct K8sContainer {
+    #[grapl(static_id, immutable)]
+    namespace: String,
+    #[grapl(static_id, immutable)]
+    pod_name: String,
+    #[grapl(static_id, immutable)]
+    container_name: String,
+    #[grapl(increment)]
+    last_seen_timestamp: u64,
+}
+
+impl IK8sContainerNode for K8sContainerNode {
+    fn get_mut_dynamic_node(&mut self) -> &mut NodeDescription {
+        &mut self.dynamic_node
+    }
+
+    fn get_dynamic_node(&self) -> &NodeDescription {
+        &self.dynamic_node
+    }
+}
@@ -0,0 +1,47 @@
+use derive_dynamic_node::{
+    GraplStaticId,
+    NodeDescription,
+};
+use rust_proto::graplinc::grapl::api::graph::v1beta1::{
+    IdStrategy,
+    NodeDescription,
+    NodeProperty,
+    Static,
+};
+
+/// An interactive session in a container started by `kubectl exec` or
+/// `kubectl attach`, identified by the audit ID of the request which started
+/// it.
+#[derive(NodeDescription, GraplStaticId)]
+pub struct K8sExecSession {
+    #[grapl(static_id, immutable)]
+    audit_id: String,
+    /// `exec` or `attach`
+    #[grapl(immutable)]
+    session_type: String,
+    #[grapl(immutable)]
+    container_name: String,
+    /// The command an `exec` session ran, with its arguments joined by spaces
+    #[grapl(immutable)]
+    command: String,
+    #[grapl(immutable)]
+    source_ip_address: String,
+    #[grapl(immutable)]
+    user_agent: String,
+    #[grapl(immutable)]
+    response_code: u64,
+    #[grapl(immutable)]
+    started_timestamp: u64,
+    #[grapl(immutable)]
+    ended_timestamp: u64,
+}
+
+impl IK8sExecSessionNode for K8sExecSessionNode {
+    fn get_mut_dynamic_node(&mut self) -> &mut NodeDescription {
+        &mut self.dynamic_node
+    }
+
+    fn get_dynamic_node(&self) -> &NodeDescription {
+        &self.dynamic_node
+    }
+}
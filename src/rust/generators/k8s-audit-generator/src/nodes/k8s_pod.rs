@@ -0,0 +1,43 @@
+use derive_dynamic_node::{
+    GraplStaticId,
+    NodeDescription,
+};
+use rust_proto::graplinc::grapl::api::graph::v1beta1::{
+    IdStrategy,
+    NodeDescription,
+    NodeProperty,
+    Static,
+};
+
+/// A pod, identified by its namespace and name. Pod names are only unique
+/// among the pods that exist at the same time, so a pod which is deleted and
+/// recreated with the same name is the same node; its timestamps are those of
+/// the latest creation and deletion.
+#[derive(NodeDescription, GraplStaticId)]
+pub struct K8sPod {
+    #[grapl(static_id, immutable)]
+    namespace: String,
+    #[grapl(static_id, immutable)]
+    pod_name: String,
+    /// The node the pod was scheduled to
+    #[grapl(immutable)]
+    node_name: String,
+    #[grapl(immutable)]
+    service_account_name: String,
+    #[grapl(increment)]
+    created_timestamp: u64,
+    #[grapl(increment)]
+    deleted_timestamp: u64,
+    #[grapl(increment)]
+    last_seen_timestamp: u64,
+}
+
+impl IK8sPodNode for K8sPodNode {
+    fn get_mut_dynamic_node(&mut self) -> &mut NodeDescription {
+        &mut self.dynamic_node
+    }
+
+    fn get_dynamic_node(&self) -> &NodeDescription {
+        &self.dynamic_node
+    }
+}
@@ -0,0 +1,39 @@
+use derive_dynamic_node::{
+    GraplStaticId,
+    NodeDescription,
+};
+use rust_proto::graplinc::grapl::api::graph::v1beta1::{
+    IdStrategy,
+    NodeDescription,
+    NodeProperty,
+    Static,
+};
+
+/// A user, service account or node which made requests to the API server,
+/// identified by its username, e.g. `alice@example.com` or
+/// `system:serviceaccount:payments:api`.
+#[derive(NodeDescription, GraplStaticId)]
+pub struct K8sPrincipal {
+    #[grapl(static_id, immutable)]
+    username: String,
+    /// `user`, `service_account`, `node` or `system`
+    #[grapl(immutable)]
+    principal_type: String,
+    /// The namespace a service account belongs to
+    #[grapl(immutable)]
+    namespace: String,
+    #[grapl(decrement)]
+    first_seen_timestamp: u64,
+    #[grapl(increment)]
+    last_seen_timestamp: u64,
+}
+
+impl IK8sPrincipalNode for K8sPrincipalNode {
+    fn get_mut_dynamic_node(&mut self) -> &mut NodeDescription {
+        &mut self.dynamic_node
+    }
+
+    fn get_dynamic_node(&self) -> &NodeDescription {
+        &self.dynamic_node
+    }
+}
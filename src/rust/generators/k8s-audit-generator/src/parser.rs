@@ -0,0 +1,274 @@
+use serde::{
+    Deserialize,
+    Deserializer,
+};
+
+use crate::error::K8sAuditGeneratorError;
+
+/// A single Kubernetes API server audit event (`audit.k8s.io/v1`). Only the
+/// fields this generator uses are deserialized; see the [audit Event
+/// reference] for the rest.
+///
+/// [audit Event reference]: https://kubernetes.io/docs/reference/config-api/apiserver-audit.v1/#audit-k8s-io-v1-Event
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditEvent {
+    #[serde(rename = "auditID")]
+    pub audit_id: String,
+    pub stage: Stage,
+    #[serde(rename = "requestURI")]
+    pub request_uri: String,
+    pub verb: String,
+    pub user: UserInfo,
+    pub impersonated_user: Option<UserInfo>,
+    #[serde(rename = "sourceIPs", default)]
+    pub source_ips: Vec<String>,
+    pub user_agent: Option<String>,
+    pub object_ref: Option<ObjectReference>,
+    pub response_status: Option<ResponseStatus>,
+    /// Only present at the `Request` and `RequestResponse` audit levels
+    #[serde(default)]
+    pub request_object: Option<serde_json::Value>,
+    /// Only present at the `RequestResponse` audit level
+    #[serde(default)]
+    pub response_object: Option<serde_json::Value>,
+    /// Milliseconds since the UNIX epoch
+    #[serde(deserialize_with = "deserialize_timestamp")]
+    pub request_received_timestamp: u64,
+    /// Milliseconds since the UNIX epoch
+    #[serde(deserialize_with = "deserialize_timestamp")]
+    pub stage_timestamp: u64,
+}
+
+impl AuditEvent {
+    /// The subresource the request targeted, e.g. `exec` for `pods/exec`.
+    pub fn subresource(&self) -> Option<&str> {
+        self.object_ref
+            .as_ref()
+            .and_then(|object_ref| object_ref.subresource.as_deref())
+    }
+
+    /// The values of a query parameter of the request URI, in order. `exec`
+    /// requests, for example, pass each argument of the command as a separate
+    /// `command` parameter.
+    pub fn query_values(&self, key: &str) -> Vec<String> {
+        let query = match self.request_uri.split_once('?') {
+            Some((_, query)) => query,
+            None => return Vec::new(),
+        };
+
+        url::form_urlencoded::parse(query.as_bytes())
+            .filter(|(name, _)| name == key)
+            .map(|(_, value)| value.into_owned())
+            .collect()
+    }
+
+    /// The pod and container an `exec` or `attach` request opened a session
+    /// in, read from its request URI
+    /// (`/api/v1/namespaces/{namespace}/pods/{pod}/exec?container={container}`).
+    /// The container is only named when the pod has more than one, or when
+    /// the caller picked one explicitly.
+    pub fn session_target(&self) -> Option<SessionTarget> {
+        let path = self
+            .request_uri
+            .split_once('?')
+            .map_or(self.request_uri.as_str(), |(path, _)| path);
+
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        let (namespace, pod_name) = match segments.as_slice() {
+            ["api", "v1", "namespaces", namespace, "pods", pod_name, "exec" | "attach"] => {
+                (*namespace, *pod_name)
+            }
+            _ => return None,
+        };
+
+        Some(SessionTarget {
+            namespace: namespace.to_string(),
+            pod_name: pod_name.to_string(),
+            container_name: self.query_values("container").into_iter().next(),
+        })
+    }
+
+    pub fn response_code(&self) -> Option<u64> {
+        self.response_status
+            .as_ref()
+            .and_then(|response_status| response_status.code)
+    }
+
+    /// Deserializes the response object, falling back to the request object,
+    /// into a resource-specific type. Returns `None` if neither was logged or
+    /// neither has the expected shape.
+    pub fn object<T: for<'de> Deserialize<'de>>(&self) -> Option<T> {
+        self.response_object
+            .iter()
+            .chain(self.request_object.iter())
+            .find_map(|value| serde_json::from_value(value.clone()).ok())
+    }
+}
+
+/// Where an `exec` or `attach` session runs; see [`AuditEvent::session_target`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionTarget {
+    pub namespace: String,
+    pub pod_name: String,
+    pub container_name: Option<String>,
+}
+
+/// The stage of request handling an event was generated at. Long-running
+/// requests, like `exec`, produce an event at `ResponseStarted` and another
+/// at `ResponseComplete`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum Stage {
+    RequestReceived,
+    ResponseStarted,
+    ResponseComplete,
+    Panic,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct UserInfo {
+    pub username: Option<String>,
+    pub uid: Option<String>,
+    #[serde(default)]
+    pub groups: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ObjectReference {
+    pub resource: Option<String>,
+    pub namespace: Option<String>,
+    pub name: Option<String>,
+    pub subresource: Option<String>,
+    pub api_group: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ResponseStatus {
+    pub code: Option<u64>,
+}
+
+fn deserialize_timestamp<'de, D>(deserializer: D) -> Result<u64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let timestamp = String::deserialize(deserializer)?;
+    let timestamp = chrono::DateTime::parse_from_rfc3339(&timestamp)
+        .map_err(serde::de::Error::custom)?
+        .timestamp_millis();
+
+    u64::try_from(timestamp).map_err(serde::de::Error::custom)
+}
+
+/// Parses any number of audit events or event lists, which may be
+/// concatenated or newline-delimited. The webhook backend sends batches of
+/// events as an `EventList`; the log backend writes single events.
+///
+/// Events are deserialized one at a time, so that an event we can't parse is
+/// skipped with a warning rather than dropping the rest of its batch. Input
+/// which isn't valid JSON can't be split into events, and is an error.
+pub fn parse_events(input: &[u8]) -> Result<Vec<AuditEvent>, K8sAuditGeneratorError> {
+    let mut events = Vec::new();
+
+    for log in serde_json::Deserializer::from_slice(input).into_iter::<serde_json::Value>() {
+        let mut log = log?;
+
+        let raw_events = match log.get_mut("items").map(serde_json::Value::take) {
+            Some(serde_json::Value::Array(items)) => items,
+            Some(_) => {
+                tracing::warn!(message = "skipping audit event list whose items isn't a list");
+                continue;
+            }
+            None => vec![log],
+        };
+
+        for raw_event in raw_events {
+            match serde_json::from_value(raw_event) {
+                Ok(event) => events.push(event),
+                Err(e) => {
+                    tracing::warn!(message = "skipping malformed audit event", error = %e);
+                }
+            }
+        }
+    }
+
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXEC_EVENT: &str = r#"{"kind":"Event","apiVersion":"audit.k8s.io/v1","level":"Metadata","auditID":"4f7c9b8e-0000-4000-8000-000000000001","stage":"ResponseStarted","requestURI":"/api/v1/namespaces/payments/pods/api-7d9f/exec?command=sh&command=-c&command=cat%20%2Fetc%2Fshadow&container=api&stdin=true&stdout=true&tty=true","verb":"create","user":{"username":"alice@example.com","groups":["developers","system:authenticated"]},"sourceIPs":["203.0.113.10"],"userAgent":"kubectl/v1.24.1 (linux/amd64) kubernetes/3ddd0f4","objectRef":{"resource":"pods","namespace":"payments","name":"api-7d9f","apiVersion":"v1","subresource":"exec"},"responseStatus":{"metadata":{},"code":101},"requestReceivedTimestamp":"2022-06-01T12:00:00.000000Z","stageTimestamp":"2022-06-01T12:00:00.120000Z"}"#;
+
+    #[test]
+    fn parses_an_exec_event() {
+        let events = parse_events(EXEC_EVENT.as_bytes()).expect("failed to parse event");
+
+        assert_eq!(events.len(), 1);
+        let event = &events[0];
+        assert_eq!(event.stage, Stage::ResponseStarted);
+        assert_eq!(event.subresource(), Some("exec"));
+        assert_eq!(event.request_received_timestamp, 1654084800000);
+        assert_eq!(event.stage_timestamp, 1654084800120);
+        assert_eq!(event.response_code(), Some(101));
+        assert_eq!(
+            event.query_values("command"),
+            vec!["sh", "-c", "cat /etc/shadow"]
+        );
+        assert_eq!(event.query_values("container"), vec!["api"]);
+        assert!(event.query_values("missing").is_empty());
+    }
+
+    #[test]
+    fn parses_event_lists() {
+        let input = format!(
+            "{{\"kind\":\"EventList\",\"apiVersion\":\"audit.k8s.io/v1\",\"items\":[{EXEC_EVENT},{EXEC_EVENT}]}}\n{EXEC_EVENT}\n"
+        );
+
+        let events = parse_events(input.as_bytes()).expect("failed to parse events");
+
+        assert_eq!(events.len(), 3);
+    }
+
+    #[test]
+    fn skips_malformed_events() {
+        let bad_timestamp = EXEC_EVENT.replace("2022-06-01T12:00:00.000000Z", "yesterday");
+        let missing_verb = EXEC_EVENT.replace("\"verb\":\"create\",", "");
+        let input = format!(
+            "{{\"kind\":\"EventList\",\"items\":[{EXEC_EVENT},{bad_timestamp},{missing_verb}]}}\n{bad_timestamp}\n{EXEC_EVENT}\n"
+        );
+
+        let events = parse_events(input.as_bytes()).expect("failed to parse events");
+
+        assert_eq!(events.len(), 2);
+        assert!(events.iter().all(|event| event.verb == "create"));
+    }
+
+    #[test]
+    fn rejects_invalid_json() {
+        assert!(parse_events(b"{\"items\":[").is_err());
+    }
+
+    #[test]
+    fn reads_the_session_target_from_the_request_uri() {
+        let events = parse_events(EXEC_EVENT.as_bytes()).expect("failed to parse event");
+
+        let target = events[0].session_target().expect("missing session target");
+        assert_eq!(target.namespace, "payments");
+        assert_eq!(target.pod_name, "api-7d9f");
+        assert_eq!(target.container_name.as_deref(), Some("api"));
+
+        let attach = EXEC_EVENT
+            .replace(
+                "/exec?command=sh&command=-c&command=cat%20%2Fetc%2Fshadow&container=api&",
+                "/attach?",
+            )
+            .replace("\"subresource\":\"exec\"", "\"subresource\":\"attach\"");
+        let events = parse_events(attach.as_bytes()).expect("failed to parse event");
+
+        let target = events[0].session_target().expect("missing session target");
+        assert_eq!(target.pod_name, "api-7d9f");
+        assert_eq!(target.container_name, None);
+    }
+}
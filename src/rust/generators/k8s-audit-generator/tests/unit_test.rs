@@ -0,0 +1,231 @@
+use generator_sdk::test_utils::test_ctx::GeneratorTestContext;
+use k8s_audit_generator::api::K8sAuditGenerator;
+use rust_proto::graplinc::grapl::api::{
+    graph::v1beta1::{
+        GraphDescription,
+        ImmutableStrProp,
+        IncrementOnlyUintProp,
+        NodeDescription,
+        Property,
+    },
+    plugin_sdk::generators::v1beta1::RunGeneratorRequest,
+};
+use test_context::test_context;
+
+fn find_nodes<'a>(
+    graph: &'a GraphDescription,
+    o_p_name: &str,
+    o_p_value: Property,
+) -> Vec<&'a NodeDescription> {
+    graph
+        .nodes
+        .values()
+        .filter(|n| {
+            n.properties.iter().any(|(p_name, p_value)| {
+                p_name.as_str() == o_p_name && p_value.property.clone() == o_p_value
+            })
+        })
+        .collect()
+}
+
+/// The nodes `node` has an edge named `edge_name` to.
+fn neighbors<'a>(
+    graph: &'a GraphDescription,
+    node: &NodeDescription,
+    edge_name: &str,
+) -> Vec<&'a NodeDescription> {
+    graph
+        .edges
+        .get(node.get_node_key())
+        .iter()
+        .flat_map(|edge_list| edge_list.edges.iter())
+        .filter(|edge| edge.edge_name == edge_name)
+        .filter_map(|edge| graph.nodes.get(&edge.to_node_key))
+        .collect()
+}
+
+fn string_property<'a>(node: &'a NodeDescription, name: &str) -> Option<&'a str> {
+    node.get_property(name)
+        .and_then(|property| property.as_immutable_str())
+        .map(|property| property.as_inner())
+}
+
+async fn generate(ctx: &mut GeneratorTestContext) -> eyre::Result<GraphDescription> {
+    let mut client = ctx.get_client(K8sAuditGenerator {}).await;
+
+    let audit_log = std::fs::read("sample_data/unit/kube_apiserver_audit.log")?;
+    let result = client
+        .run_generator(RunGeneratorRequest {
+            data: audit_log.into(),
+        })
+        .await?;
+
+    Ok(result.generated_graph.graph_description)
+}
+
+#[test_context(GeneratorTestContext)]
+#[tokio::test]
+async fn test_exec_session_links_to_pod_and_asset(
+    ctx: &mut GeneratorTestContext,
+) -> eyre::Result<()> {
+    let generated_graph = generate(ctx).await?;
+
+    // The session is described by both its ResponseStarted and
+    // ResponseComplete events, but not its RequestReceived event
+    let sessions = find_nodes(
+        &generated_graph,
+        "audit_id",
+        ImmutableStrProp::from("0b3e1c5a-1111-4a4e-9c2e-000000000003").into(),
+    );
+    assert_eq!(sessions.len(), 2);
+
+    for session in &sessions {
+        assert_eq!(string_property(session, "session_type"), Some("exec"));
+        assert_eq!(string_property(session, "container_name"), Some("api"));
+        assert_eq!(
+            string_property(session, "command"),
+            Some("sh -c cat /etc/shadow")
+        );
+
+        let pods = neighbors(&generated_graph, session, "session_pod");
+        assert_eq!(pods.len(), 1);
+        assert_eq!(
+            string_property(pods[0], "pod_name"),
+            Some("api-7d9f4c-x2kq8")
+        );
+
+        // The container named by the exec request URI, in the same pod
+        let containers = neighbors(&generated_graph, session, "session_container");
+        assert_eq!(containers.len(), 1);
+        assert_eq!(
+            string_property(containers[0], "container_name"),
+            Some("api")
+        );
+        assert_eq!(
+            neighbors(&generated_graph, containers[0], "container_pod"),
+            pods
+        );
+    }
+
+    assert!(sessions
+        .iter()
+        .any(|session| session.get_property("ended_timestamp").is_some()));
+
+    let alice = find_nodes(
+        &generated_graph,
+        "username",
+        ImmutableStrProp::from("alice@example.com").into(),
+    );
+    assert!(alice.iter().any(|principal| {
+        neighbors(&generated_graph, principal, "exec_sessions")
+            .iter()
+            .any(|session| sessions.contains(session))
+    }));
+
+    // The scheduler's binding places the pod on a node, whose Asset is shared
+    // with the process activity endpoint generators emit for it
+    let bound_pod = find_nodes(
+        &generated_graph,
+        "node_name",
+        ImmutableStrProp::from("ip-10-0-1-23").into(),
+    );
+    assert_eq!(bound_pod.len(), 1);
+
+    let assets = neighbors(&generated_graph, bound_pod[0], "pod_asset");
+    assert_eq!(assets.len(), 1);
+    assert_eq!(string_property(assets[0], "asset_id"), Some("ip-10-0-1-23"));
+    assert_eq!(string_property(assets[0], "hostname"), Some("ip-10-0-1-23"));
+
+    Ok(())
+}
+
+#[test_context(GeneratorTestContext)]
+#[tokio::test]
+async fn test_api_requests_produce_expected_graph(
+    ctx: &mut GeneratorTestContext,
+) -> eyre::Result<()> {
+    let generated_graph = generate(ctx).await?;
+
+    // The pod creation, its binding and the secret read; the watch and the
+    // request to /healthz are skipped
+    let requests = find_nodes(
+        &generated_graph,
+        "request_uri",
+        ImmutableStrProp::from("/api/v1/namespaces/payments/secrets/stripe-api-key").into(),
+    );
+    assert_eq!(requests.len(), 1);
+    assert!(find_nodes(
+        &generated_graph,
+        "verb",
+        ImmutableStrProp::from("watch").into()
+    )
+    .is_empty());
+    assert!(find_nodes(
+        &generated_graph,
+        "username",
+        ImmutableStrProp::from("system:anonymous").into()
+    )
+    .is_empty());
+
+    let secret_read = requests[0];
+    assert_eq!(string_property(secret_read, "resource"), Some("secrets"));
+    assert_eq!(
+        string_property(secret_read, "object_name"),
+        Some("stripe-api-key")
+    );
+
+    let namespaces = neighbors(&generated_graph, secret_read, "request_namespace");
+    assert_eq!(namespaces.len(), 1);
+    assert_eq!(
+        string_property(namespaces[0], "namespace_name"),
+        Some("payments")
+    );
+
+    let alice = find_nodes(
+        &generated_graph,
+        "username",
+        ImmutableStrProp::from("alice@example.com").into(),
+    );
+    let caller = alice
+        .iter()
+        .find(|principal| {
+            neighbors(&generated_graph, principal, "api_requests").contains(&secret_read)
+        })
+        .expect("caller missing");
+
+    let impersonated = neighbors(&generated_graph, caller, "impersonated_principals");
+    assert_eq!(impersonated.len(), 1);
+    assert_eq!(
+        string_property(impersonated[0], "username"),
+        Some("system:serviceaccount:payments:deployer")
+    );
+    assert_eq!(
+        string_property(impersonated[0], "principal_type"),
+        Some("service_account")
+    );
+
+    // The pod created with generateName is named by the response, and linked
+    // to the service account it runs as
+    let created_pod = find_nodes(
+        &generated_graph,
+        "created_timestamp",
+        IncrementOnlyUintProp {
+            prop: 1654084680000,
+        }
+        .into(),
+    );
+    assert_eq!(created_pod.len(), 1);
+    assert_eq!(
+        string_property(created_pod[0], "pod_name"),
+        Some("api-7d9f4c-x2kq8")
+    );
+
+    let service_accounts = neighbors(&generated_graph, created_pod[0], "pod_service_account");
+    assert_eq!(service_accounts.len(), 1);
+    assert_eq!(
+        string_property(service_accounts[0], "username"),
+        Some("system:serviceaccount:payments:api")
+    );
+
+    Ok(())
+}
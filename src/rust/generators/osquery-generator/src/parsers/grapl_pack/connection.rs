@@ -0,0 +1,377 @@
+use std::net::IpAddr;
+
+use endpoint_plugin::{
+    AssetNode,
+    IAssetNode,
+    IIpAddressNode,
+    IIpConnectionNode,
+    IIpPortNode,
+    INetworkConnectionNode,
+    IProcessInboundConnectionNode,
+    IProcessNode,
+    IProcessOutboundConnectionNode,
+    IpAddressNode,
+    IpConnectionNode,
+    IpPortNode,
+    NetworkConnectionNode,
+    ProcessInboundConnectionNode,
+    ProcessNode,
+    ProcessOutboundConnectionNode,
+};
+use rust_proto::graplinc::grapl::api::graph::v1beta1::GraphDescription;
+
+/// One side of a socket, as reported by osquery's socket tables.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Endpoint<'a> {
+    pub(super) address: &'a str,
+    pub(super) port: u64,
+}
+
+/// The socket address families we build network nodes for (`AF_INET` and
+/// `AF_INET6`). Unix domain sockets show up in the same tables.
+pub(super) fn is_inet_family(family: u64) -> bool {
+    matches!(family, 2 | 10)
+}
+
+/// Converts an IANA protocol number into the name sysmon-generator uses.
+pub(super) fn protocol_name(protocol: u64) -> String {
+    match protocol {
+        6 => "tcp".to_string(),
+        17 => "udp".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// `IpAddress` and `IpPort` nodes are identified by their address alone, so
+/// wildcard and loopback addresses (e.g. `0.0.0.0`, `::1`) would merge every
+/// host's sockets into the same node. Those endpoints only get the process
+/// connection node, which is keyed on the asset.
+fn identifies_host(endpoint: &Endpoint<'_>) -> bool {
+    match endpoint.address.parse::<IpAddr>() {
+        Ok(ip) => !(ip.is_unspecified() || ip.is_loopback()),
+        Err(_) => false,
+    }
+}
+
+fn ip_address_node(endpoint: &Endpoint<'_>, timestamp: u64) -> IpAddressNode {
+    let mut ip_address = IpAddressNode::new(IpAddressNode::identity_strategy());
+    ip_address
+        .with_ip_address(endpoint.address)
+        .with_first_seen_timestamp(timestamp)
+        .with_last_seen_timestamp(timestamp);
+
+    ip_address
+}
+
+fn ip_port_node(endpoint: &Endpoint<'_>, protocol: &str, timestamp: u64) -> IpPortNode {
+    let mut ip_port = IpPortNode::new(IpPortNode::identity_strategy());
+    ip_port
+        .with_ip_address(endpoint.address)
+        .with_port(endpoint.port)
+        .with_protocol(protocol)
+        .with_first_seen_timestamp(timestamp)
+        .with_last_seen_timestamp(timestamp);
+
+    ip_port
+}
+
+/// The node keys of the address and port nodes added by `add_network_connection`.
+struct ConnectionKeys {
+    src_ip: String,
+    dst_ip: String,
+    src_port: String,
+    dst_port: String,
+}
+
+/// Adds the address, port and connection nodes shared by both directions of a
+/// connection.
+fn add_network_connection(
+    graph: &mut GraphDescription,
+    protocol: &str,
+    timestamp: u64,
+    src: &Endpoint<'_>,
+    dst: &Endpoint<'_>,
+) -> ConnectionKeys {
+    let src_ip = ip_address_node(src, timestamp);
+    let dst_ip = ip_address_node(dst, timestamp);
+    let src_port = ip_port_node(src, protocol, timestamp);
+    let dst_port = ip_port_node(dst, protocol, timestamp);
+
+    let mut network_connection =
+        NetworkConnectionNode::new(NetworkConnectionNode::identity_strategy());
+    network_connection
+        .with_src_ip_address(src.address)
+        .with_src_port(src.port)
+        .with_dst_ip_address(dst.address)
+        .with_dst_port(dst.port)
+        .with_protocol(protocol)
+        .with_created_timestamp(timestamp);
+
+    let mut ip_connection = IpConnectionNode::new(IpConnectionNode::identity_strategy());
+    ip_connection
+        .with_src_ip_address(src.address)
+        .with_dst_ip_address(dst.address)
+        .with_protocol(protocol)
+        .with_created_timestamp(timestamp);
+
+    graph.add_edge(
+        "ip_connections",
+        src_ip.clone_node_key(),
+        ip_connection.clone_node_key(),
+    );
+
+    graph.add_edge(
+        "ip_connections",
+        dst_ip.clone_node_key(),
+        ip_connection.clone_node_key(),
+    );
+
+    graph.add_edge(
+        "network_connections",
+        src_port.clone_node_key(),
+        network_connection.clone_node_key(),
+    );
+
+    graph.add_edge(
+        "network_connections",
+        dst_port.clone_node_key(),
+        network_connection.clone_node_key(),
+    );
+
+    let keys = ConnectionKeys {
+        src_ip: src_ip.clone_node_key(),
+        dst_ip: dst_ip.clone_node_key(),
+        src_port: src_port.clone_node_key(),
+        dst_port: dst_port.clone_node_key(),
+    };
+
+    graph.add_node(src_ip);
+    graph.add_node(dst_ip);
+    graph.add_node(src_port);
+    graph.add_node(dst_port);
+    graph.add_node(network_connection);
+    graph.add_node(ip_connection);
+
+    keys
+}
+
+/// Creates a subgraph describing a connection the process opened to `remote`.
+///
+/// This mirrors sysmon-generator's outbound connection subgraph, so that Linux
+/// and Windows hosts produce the same network graph.
+pub(super) fn outbound_connection_graph(
+    host_identifier: &str,
+    pid: u64,
+    timestamp: u64,
+    protocol: &str,
+    local: Endpoint<'_>,
+    remote: Endpoint<'_>,
+) -> GraphDescription {
+    let mut graph = GraphDescription::new();
+
+    let mut asset = AssetNode::new(AssetNode::static_strategy());
+    asset
+        .with_asset_id(host_identifier)
+        .with_hostname(host_identifier);
+
+    let mut process = ProcessNode::new(ProcessNode::session_strategy());
+    process
+        .with_asset_id(host_identifier)
+        .with_process_id(pid)
+        .with_last_seen_timestamp(timestamp);
+
+    let mut outbound =
+        ProcessOutboundConnectionNode::new(ProcessOutboundConnectionNode::identity_strategy());
+    outbound
+        .with_asset_id(host_identifier)
+        .with_hostname(host_identifier)
+        .with_ip_address(local.address)
+        .with_protocol(protocol)
+        .with_port(local.port)
+        .with_created_timestamp(timestamp);
+
+    graph.add_edge(
+        "asset_processes",
+        asset.clone_node_key(),
+        process.clone_node_key(),
+    );
+
+    graph.add_edge(
+        "created_connections",
+        process.clone_node_key(),
+        outbound.clone_node_key(),
+    );
+
+    if identifies_host(&local) && identifies_host(&remote) {
+        let keys = add_network_connection(&mut graph, protocol, timestamp, &local, &remote);
+
+        graph.add_edge("asset_ip", asset.clone_node_key(), keys.src_ip);
+        graph.add_edge("connected_over", outbound.clone_node_key(), keys.src_port);
+        graph.add_edge("connected_to", outbound.clone_node_key(), keys.dst_port);
+    } else if identifies_host(&remote) {
+        // Without a local address there's no `IpConnection` to link the
+        // remote `IpAddress` to, so only its port is described.
+        let dst_port = ip_port_node(&remote, protocol, timestamp);
+
+        graph.add_edge(
+            "connected_to",
+            outbound.clone_node_key(),
+            dst_port.clone_node_key(),
+        );
+
+        graph.add_node(dst_port);
+    }
+
+    graph.add_node(asset);
+    graph.add_node(process);
+    graph.add_node(outbound);
+
+    graph
+}
+
+/// Creates a subgraph describing a socket the process bound to `local`.
+///
+/// `remote` is the peer of an accepted connection; it is `None` for sockets
+/// which are only listening.
+pub(super) fn inbound_connection_graph(
+    host_identifier: &str,
+    pid: u64,
+    timestamp: u64,
+    protocol: &str,
+    local: Endpoint<'_>,
+    remote: Option<Endpoint<'_>>,
+) -> GraphDescription {
+    let mut graph = GraphDescription::new();
+
+    let mut asset = AssetNode::new(AssetNode::static_strategy());
+    asset
+        .with_asset_id(host_identifier)
+        .with_hostname(host_identifier);
+
+    let mut process = ProcessNode::new(ProcessNode::session_strategy());
+    process
+        .with_asset_id(host_identifier)
+        .with_process_id(pid)
+        .with_last_seen_timestamp(timestamp);
+
+    let mut inbound =
+        ProcessInboundConnectionNode::new(ProcessInboundConnectionNode::identity_strategy());
+    inbound
+        .with_asset_id(host_identifier)
+        .with_ip_address(local.address)
+        .with_port(local.port)
+        .with_protocol(protocol)
+        .with_created_timestamp(timestamp);
+
+    graph.add_edge(
+        "asset_processes",
+        asset.clone_node_key(),
+        process.clone_node_key(),
+    );
+
+    graph.add_edge(
+        "inbound_connections",
+        process.clone_node_key(),
+        inbound.clone_node_key(),
+    );
+
+    let remote = remote.filter(identifies_host);
+
+    match (identifies_host(&local), remote) {
+        (true, Some(remote)) => {
+            let keys = add_network_connection(&mut graph, protocol, timestamp, &remote, &local);
+
+            graph.add_edge("asset_ip", asset.clone_node_key(), keys.dst_ip);
+            graph.add_edge("bound_port", inbound.clone_node_key(), keys.dst_port);
+            graph.add_edge("connected_from", inbound.clone_node_key(), keys.src_port);
+        }
+        (true, None) => {
+            let local_ip = ip_address_node(&local, timestamp);
+            let local_port = ip_port_node(&local, protocol, timestamp);
+
+            graph.add_edge(
+                "asset_ip",
+                asset.clone_node_key(),
+                local_ip.clone_node_key(),
+            );
+
+            graph.add_edge(
+                "bound_port",
+                inbound.clone_node_key(),
+                local_port.clone_node_key(),
+            );
+
+            graph.add_node(local_ip);
+            graph.add_node(local_port);
+        }
+        (false, Some(remote)) => {
+            // As in `outbound_connection_graph`, the remote `IpAddress` would
+            // have nothing to link it, so only its port is described.
+            let remote_port = ip_port_node(&remote, protocol, timestamp);
+
+            graph.add_edge(
+                "connected_from",
+                inbound.clone_node_key(),
+                remote_port.clone_node_key(),
+            );
+
+            graph.add_node(remote_port);
+        }
+        (false, None) => {}
+    }
+
+    graph.add_node(asset);
+    graph.add_node(process);
+    graph.add_node(inbound);
+
+    graph
+}
+
+/// Records that an outbound connection was closed, without describing a new
+/// connection.
+pub(super) fn terminated_outbound_connection_graph(
+    host_identifier: &str,
+    timestamp: u64,
+    protocol: &str,
+    local: Endpoint<'_>,
+) -> GraphDescription {
+    let mut graph = GraphDescription::new();
+
+    let mut outbound =
+        ProcessOutboundConnectionNode::new(ProcessOutboundConnectionNode::identity_strategy());
+    outbound
+        .with_asset_id(host_identifier)
+        .with_hostname(host_identifier)
+        .with_ip_address(local.address)
+        .with_protocol(protocol)
+        .with_port(local.port)
+        .with_terminated_timestamp(timestamp);
+
+    graph.add_node(outbound);
+
+    graph
+}
+
+/// Records that a bound socket was closed, without describing a new
+/// connection.
+pub(super) fn terminated_inbound_connection_graph(
+    host_identifier: &str,
+    timestamp: u64,
+    protocol: &str,
+    local: Endpoint<'_>,
+) -> GraphDescription {
+    let mut graph = GraphDescription::new();
+
+    let mut inbound =
+        ProcessInboundConnectionNode::new(ProcessInboundConnectionNode::identity_strategy());
+    inbound
+        .with_asset_id(host_identifier)
+        .with_ip_address(local.address)
+        .with_port(local.port)
+        .with_protocol(protocol)
+        .with_terminated_timestamp(timestamp);
+
+    graph.add_node(inbound);
+
+    graph
+}
@@ -8,7 +8,10 @@ use rust_proto::graplinc::grapl::api::graph::v1beta1::GraphDescription;
 use serde::Deserialize;
 
 use super::from_str;
-use crate::parsers::OSQueryAction;
+use crate::parsers::{
+    OSQueryAction,
+    OSQueryRows,
+};
 
 #[derive(Deserialize, Debug, Clone, Hash)]
 #[serde(rename_all = "camelCase")]
@@ -16,8 +19,8 @@ pub struct FileEvent {
     host_identifier: String,
     calendar_time: String,
     unix_time: u64,
-    action: OSQueryAction,
-    columns: FileEventColumns,
+    #[serde(flatten)]
+    rows: OSQueryRows<FileEventColumns>,
 }
 
 /// See https://osquery.io/schema/4.5.0/#processes
@@ -54,44 +57,60 @@ impl From<FileEvent> for GraphDescription {
 
         let mut graph = GraphDescription::new();
 
-        let mut asset = AssetNode::new(AssetNode::static_strategy());
-        asset
-            .with_asset_id(file_event.host_identifier.clone())
-            .with_hostname(file_event.host_identifier.clone());
-
-        let mut subject_file = FileNode::new(FileNode::session_strategy());
-        subject_file
-            .with_asset_id(file_event.host_identifier.clone())
-            .with_file_path(file_event.columns.target_path.clone())
-            .with_last_seen_timestamp(file_event.columns.time);
-
-        /*
-           Technically this might not be 100% correct but the moved_to and moved_from events
-           seem like they could easily be represented by using create/deletes.
-        */
-        match &file_event.columns.action {
-            OSQueryFileAction::Created | OSQueryFileAction::MovedFrom => {
-                subject_file.with_created_timestamp(file_event.columns.time)
-            }
-            OSQueryFileAction::Deleted | OSQueryFileAction::MovedTo => {
-                subject_file.with_deleted_timestamp(file_event.columns.time)
+        for (action, columns) in file_event.rows {
+            match action {
+                // `file_events` is an evented table, so a removed row only
+                // means the event aged out of the query's window. The file
+                // action itself was already recorded when the row was added.
+                OSQueryAction::Removed => continue,
+                _ => graph.merge(&file_graph(&file_event.host_identifier, &columns)),
             }
-            _ => subject_file.with_last_seen_timestamp(file_event.columns.time),
-        };
-
-        graph.add_edge(
-            "files_on_asset",
-            asset.clone_node_key(),
-            subject_file.clone_node_key(),
-        );
-
-        graph.add_node(asset);
-        graph.add_node(subject_file);
+        }
 
         graph
     }
 }
 
+fn file_graph(host_identifier: &str, columns: &FileEventColumns) -> GraphDescription {
+    let mut graph = GraphDescription::new();
+
+    let mut asset = AssetNode::new(AssetNode::static_strategy());
+    asset
+        .with_asset_id(host_identifier)
+        .with_hostname(host_identifier);
+
+    let mut subject_file = FileNode::new(FileNode::session_strategy());
+    subject_file
+        .with_asset_id(host_identifier)
+        .with_file_path(columns.target_path.clone())
+        .with_last_seen_timestamp(columns.time);
+
+    /*
+       Technically this might not be 100% correct but the moved_to and moved_from events
+       seem like they could easily be represented by using create/deletes.
+    */
+    match &columns.action {
+        OSQueryFileAction::Created | OSQueryFileAction::MovedFrom => {
+            subject_file.with_created_timestamp(columns.time)
+        }
+        OSQueryFileAction::Deleted | OSQueryFileAction::MovedTo => {
+            subject_file.with_deleted_timestamp(columns.time)
+        }
+        _ => subject_file.with_last_seen_timestamp(columns.time),
+    };
+
+    graph.add_edge(
+        "files_on_asset",
+        asset.clone_node_key(),
+        subject_file.clone_node_key(),
+    );
+
+    graph.add_node(asset);
+    graph.add_node(subject_file);
+
+    graph
+}
+
 #[cfg(test)]
 mod tests {
     use crate::parsers::OSQueryEvent;
@@ -0,0 +1,99 @@
+use rust_proto::graplinc::grapl::api::graph::v1beta1::GraphDescription;
+use serde::Deserialize;
+
+use super::{
+    connection::{
+        self,
+        Endpoint,
+    },
+    from_str,
+};
+use crate::parsers::{
+    OSQueryAction,
+    OSQueryRows,
+};
+
+#[derive(Deserialize, Debug, Clone, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct ListeningPortEvent {
+    host_identifier: String,
+    calendar_time: String,
+    unix_time: u64,
+    #[serde(flatten)]
+    rows: OSQueryRows<ListeningPortEventColumns>,
+}
+
+/// See https://osquery.io/schema/4.5.0/#listening_ports
+#[derive(Deserialize, Debug, Clone, Hash)]
+pub struct ListeningPortEventColumns {
+    #[serde(deserialize_with = "from_str")]
+    pid: i64,
+    #[serde(deserialize_with = "from_str")]
+    port: u64,
+    #[serde(deserialize_with = "from_str")]
+    protocol: u64,
+    #[serde(deserialize_with = "from_str")]
+    family: u64,
+    address: String,
+}
+
+impl From<ListeningPortEvent> for GraphDescription {
+    #[tracing::instrument]
+    fn from(listening_port_event: ListeningPortEvent) -> Self {
+        tracing::trace!(message = "Building Graph from ListeningPortEvent.");
+
+        let mut graph = GraphDescription::new();
+
+        for (action, columns) in listening_port_event.rows {
+            // osquery records -1 for sockets it can't attribute to a process
+            if columns.pid < 0 || !connection::is_inet_family(columns.family) {
+                continue;
+            }
+
+            let protocol = connection::protocol_name(columns.protocol);
+            let local = Endpoint {
+                address: &columns.address,
+                port: columns.port,
+            };
+
+            let row_graph = match action {
+                OSQueryAction::Removed => connection::terminated_inbound_connection_graph(
+                    &listening_port_event.host_identifier,
+                    listening_port_event.unix_time,
+                    &protocol,
+                    local,
+                ),
+                _ => connection::inbound_connection_graph(
+                    &listening_port_event.host_identifier,
+                    columns.pid as u64,
+                    listening_port_event.unix_time,
+                    &protocol,
+                    local,
+                    None,
+                ),
+            };
+
+            graph.merge(&row_graph);
+        }
+
+        graph
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parsers::OSQueryEvent;
+
+    #[test]
+    fn parse_pack_grapl_listening_ports_json() {
+        let test_json = std::fs::read_to_string("sample_data/unit/pack_grapl_listening-ports.json")
+            .expect("unable to read test file.");
+
+        let event: OSQueryEvent =
+            serde_json::from_str(&test_json).expect("serde_json::from_str failed.");
+        match event {
+            OSQueryEvent::ListeningPort(_) => {}
+            _ => panic!("expected OSQueryEvent::ListeningPort"),
+        };
+    }
+}
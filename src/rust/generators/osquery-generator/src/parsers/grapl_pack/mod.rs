@@ -8,9 +8,13 @@ use serde::{
     Deserializer,
 };
 
+mod connection;
 pub mod files;
+pub mod listening_ports;
 pub mod process_files;
+pub mod process_open_sockets;
 pub mod processes;
+pub mod socket_events;
 
 fn from_str<'de, D, T>(deserializer: D) -> Result<T, D::Error>
 where
@@ -10,7 +10,10 @@ use rust_proto::graplinc::grapl::api::graph::v1beta1::GraphDescription;
 use serde::Deserialize;
 
 use super::from_str;
-use crate::parsers::OSQueryAction;
+use crate::parsers::{
+    OSQueryAction,
+    OSQueryRows,
+};
 
 #[derive(Deserialize, Debug, Clone, Hash)]
 #[serde(rename_all = "camelCase")]
@@ -18,8 +21,8 @@ pub struct ProcessFileInteractionEvent {
     host_identifier: String,
     calendar_time: String,
     unix_time: u64,
-    action: OSQueryAction,
-    columns: ProcessFileInteractionEventColumns,
+    #[serde(flatten)]
+    rows: OSQueryRows<ProcessFileInteractionEventColumns>,
 }
 
 #[derive(Deserialize, Debug, Clone, Hash)]
@@ -38,70 +41,113 @@ impl From<ProcessFileInteractionEvent> for GraphDescription {
 
         let mut graph = GraphDescription::new();
 
-        let mut asset = AssetNode::new(AssetNode::static_strategy());
-        asset
-            .with_asset_id(process_file_event.host_identifier.clone())
-            .with_hostname(process_file_event.host_identifier.clone());
-
-        let mut process = ProcessNode::new(ProcessNode::session_strategy());
-        process
-            .with_asset_id(process_file_event.host_identifier.clone())
-            .with_last_seen_timestamp(process_file_event.unix_time)
-            .with_process_id(process_file_event.columns.pid);
-
-        let mut file = FileNode::new(FileNode::session_strategy());
-        file.with_asset_id(process_file_event.host_identifier.clone())
-            .with_file_path(process_file_event.columns.path.clone());
-
-        // TODO: maybe we should set deleted time and created time for the file here?
-        match process_file_event.action {
-            OSQueryAction::Added => {
-                file.with_created_timestamp(process_file_event.unix_time);
-
-                graph.add_edge(
-                    "created_files",
-                    process.clone_node_key(),
-                    file.clone_node_key(),
-                );
-            }
-            OSQueryAction::Removed => {
-                file.with_deleted_timestamp(process_file_event.unix_time);
-
-                graph.add_edge(
-                    "deleted_files",
-                    process.clone_node_key(),
-                    file.clone_node_key(),
-                );
-            }
-            _ => {
-                file.with_last_seen_timestamp(process_file_event.unix_time);
+        for (action, columns) in process_file_event.rows {
+            match action {
+                // `process_open_files` is a snapshot of open file descriptors,
+                // so a removed row means the process closed the file, not that
+                // the file was deleted. No schema has an edge for closing a
+                // file, so there's nothing to record.
+                OSQueryAction::Removed => continue,
+                _ => graph.merge(&process_file_graph(
+                    &process_file_event.host_identifier,
+                    process_file_event.unix_time,
+                    action,
+                    &columns,
+                )),
             }
-        };
-
-        graph.add_edge(
-            "files_on_asset",
-            asset.clone_node_key(),
-            file.clone_node_key(),
-        );
-
-        graph.add_edge(
-            "asset_processes",
-            asset.clone_node_key(),
-            process.clone_node_key(),
-        );
-
-        graph.add_node(asset);
-        graph.add_node(file);
-        graph.add_node(process);
+        }
 
         graph
     }
 }
 
+fn process_file_graph(
+    host_identifier: &str,
+    unix_time: u64,
+    action: OSQueryAction,
+    columns: &ProcessFileInteractionEventColumns,
+) -> GraphDescription {
+    let mut graph = GraphDescription::new();
+
+    let mut asset = AssetNode::new(AssetNode::static_strategy());
+    asset
+        .with_asset_id(host_identifier)
+        .with_hostname(host_identifier);
+
+    let mut process = ProcessNode::new(ProcessNode::session_strategy());
+    process
+        .with_asset_id(host_identifier)
+        .with_last_seen_timestamp(unix_time)
+        .with_process_id(columns.pid);
+
+    let mut file = FileNode::new(FileNode::session_strategy());
+    file.with_asset_id(host_identifier)
+        .with_file_path(columns.path.clone());
+
+    match action {
+        OSQueryAction::Added => {
+            file.with_created_timestamp(unix_time);
+
+            graph.add_edge(
+                "created_files",
+                process.clone_node_key(),
+                file.clone_node_key(),
+            );
+        }
+        _ => {
+            file.with_last_seen_timestamp(unix_time);
+        }
+    };
+
+    graph.add_edge(
+        "files_on_asset",
+        asset.clone_node_key(),
+        file.clone_node_key(),
+    );
+
+    graph.add_edge(
+        "asset_processes",
+        asset.clone_node_key(),
+        process.clone_node_key(),
+    );
+
+    graph.add_node(asset);
+    graph.add_node(file);
+    graph.add_node(process);
+
+    graph
+}
+
 #[cfg(test)]
 mod tests {
+    use rust_proto::graplinc::grapl::api::graph::v1beta1::{
+        GraphDescription,
+        NodeDescription,
+    };
+
     use crate::parsers::OSQueryEvent;
 
+    fn find_file<'a>(graph: &'a GraphDescription, path: &str) -> Option<&'a NodeDescription> {
+        graph.nodes.values().find(|n| {
+            n.get_property("file_path")
+                .and_then(|p| p.as_immutable_str())
+                .map(|p| p.as_inner())
+                == Some(path)
+        })
+    }
+
+    fn find_edge_to<'a>(
+        graph: &'a GraphDescription,
+        edge_name: &str,
+        to: &NodeDescription,
+    ) -> bool {
+        graph
+            .edges
+            .values()
+            .flat_map(|edge_list| edge_list.edges.iter())
+            .any(|edge| edge.edge_name == edge_name && edge.to_node_key == to.get_node_key())
+    }
+
     #[test]
     fn parse_pack_grapl_process_files_json() {
         let test_json = std::fs::read_to_string("sample_data/unit/pack_grapl_process-files.json")
@@ -114,4 +160,25 @@ mod tests {
             _ => panic!("expected OSQueryEvent::ProcessFileAction"),
         };
     }
+
+    #[test]
+    fn parse_pack_grapl_process_files_batch_json() {
+        let test_json =
+            std::fs::read_to_string("sample_data/unit/pack_grapl_process-files_batch.json")
+                .expect("unable to read test file.");
+
+        let event: OSQueryEvent =
+            serde_json::from_str(&test_json).expect("serde_json::from_str failed.");
+        let graph: GraphDescription = match event {
+            OSQueryEvent::ProcessFileAction(event) => event.into(),
+            _ => panic!("expected OSQueryEvent::ProcessFileAction"),
+        };
+
+        let added =
+            find_file(&graph, "/var/osquery/osquery.db/000123.log").expect("added file missing");
+        assert!(find_edge_to(&graph, "created_files", added));
+
+        // a removed row is a closed file descriptor, not a deleted file
+        assert!(find_file(&graph, "/var/osquery/osquery.db/LOCK").is_none());
+    }
 }
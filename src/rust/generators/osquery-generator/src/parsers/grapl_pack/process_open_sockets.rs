@@ -0,0 +1,246 @@
+use std::collections::HashSet;
+
+use rust_proto::graplinc::grapl::api::graph::v1beta1::GraphDescription;
+use serde::Deserialize;
+
+use super::{
+    connection::{
+        self,
+        Endpoint,
+    },
+    from_str,
+};
+use crate::parsers::{
+    OSQueryAction,
+    OSQueryRows,
+};
+
+#[derive(Deserialize, Debug, Clone, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessOpenSocketEvent {
+    host_identifier: String,
+    calendar_time: String,
+    unix_time: u64,
+    #[serde(flatten)]
+    rows: OSQueryRows<ProcessOpenSocketEventColumns>,
+}
+
+/// See https://osquery.io/schema/4.5.0/#process_open_sockets
+#[derive(Deserialize, Debug, Clone, Hash)]
+pub struct ProcessOpenSocketEventColumns {
+    #[serde(deserialize_with = "from_str")]
+    pid: i64,
+    #[serde(deserialize_with = "from_str")]
+    family: u64,
+    #[serde(deserialize_with = "from_str")]
+    protocol: u64,
+    local_address: String,
+    remote_address: String,
+    #[serde(deserialize_with = "from_str")]
+    local_port: u64,
+    #[serde(deserialize_with = "from_str")]
+    remote_port: u64,
+    /// The TCP state (e.g. `LISTEN`, `ESTABLISHED`); empty for UDP sockets.
+    #[serde(default)]
+    state: String,
+}
+
+/// The first port of Linux's default ephemeral range
+/// (`net.ipv4.ip_local_port_range`), from which outgoing connections are
+/// given their local port.
+const EPHEMERAL_PORT_START: u64 = 32768;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SocketDirection {
+    /// Bound and waiting for connections, with no peer.
+    Listening,
+    /// A connection accepted on a port the process listens on.
+    Inbound,
+    /// A connection the process opened.
+    Outbound,
+}
+
+impl ProcessOpenSocketEventColumns {
+    fn is_listening(&self) -> bool {
+        self.state == "LISTEN" || self.remote_port == 0
+    }
+
+    /// osquery doesn't record which side initiated a connection, so it's
+    /// inferred from the ports: a connection is inbound when the process also
+    /// listens on its local port, or when the local port is a fixed one and
+    /// the peer's is ephemeral.
+    fn direction(&self, listening_ports: &HashSet<(i64, u64)>) -> SocketDirection {
+        if self.is_listening() {
+            SocketDirection::Listening
+        } else if listening_ports.contains(&(self.pid, self.local_port))
+            || (self.local_port < EPHEMERAL_PORT_START && self.remote_port >= EPHEMERAL_PORT_START)
+        {
+            SocketDirection::Inbound
+        } else {
+            SocketDirection::Outbound
+        }
+    }
+}
+
+impl From<ProcessOpenSocketEvent> for GraphDescription {
+    #[tracing::instrument]
+    fn from(socket_event: ProcessOpenSocketEvent) -> Self {
+        tracing::trace!(message = "Building Graph from ProcessOpenSocketEvent.");
+
+        let mut graph = GraphDescription::new();
+
+        // osquery records -1 for sockets it can't attribute to a process
+        let rows: Vec<_> = socket_event
+            .rows
+            .into_iter()
+            .filter(|(_, columns)| columns.pid >= 0 && connection::is_inet_family(columns.family))
+            .collect();
+
+        let listening_ports: HashSet<(i64, u64)> = rows
+            .iter()
+            .filter(|(_, columns)| columns.is_listening())
+            .map(|(_, columns)| (columns.pid, columns.local_port))
+            .collect();
+
+        for (action, columns) in rows.iter() {
+            let protocol = connection::protocol_name(columns.protocol);
+            let local = Endpoint {
+                address: &columns.local_address,
+                port: columns.local_port,
+            };
+            let remote = Endpoint {
+                address: &columns.remote_address,
+                port: columns.remote_port,
+            };
+
+            let direction = columns.direction(&listening_ports);
+            let row_graph = match (action, direction) {
+                (OSQueryAction::Removed, SocketDirection::Outbound) => {
+                    connection::terminated_outbound_connection_graph(
+                        &socket_event.host_identifier,
+                        socket_event.unix_time,
+                        &protocol,
+                        local,
+                    )
+                }
+                (OSQueryAction::Removed, _) => connection::terminated_inbound_connection_graph(
+                    &socket_event.host_identifier,
+                    socket_event.unix_time,
+                    &protocol,
+                    local,
+                ),
+                (_, SocketDirection::Listening) => connection::inbound_connection_graph(
+                    &socket_event.host_identifier,
+                    columns.pid as u64,
+                    socket_event.unix_time,
+                    &protocol,
+                    local,
+                    None,
+                ),
+                (_, SocketDirection::Inbound) => connection::inbound_connection_graph(
+                    &socket_event.host_identifier,
+                    columns.pid as u64,
+                    socket_event.unix_time,
+                    &protocol,
+                    local,
+                    Some(remote),
+                ),
+                (_, SocketDirection::Outbound) => connection::outbound_connection_graph(
+                    &socket_event.host_identifier,
+                    columns.pid as u64,
+                    socket_event.unix_time,
+                    &protocol,
+                    local,
+                    remote,
+                ),
+            };
+
+            graph.merge(&row_graph);
+        }
+
+        graph
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_proto::graplinc::grapl::api::graph::v1beta1::{
+        GraphDescription,
+        NodeDescription,
+    };
+
+    use crate::parsers::OSQueryEvent;
+
+    fn parse_graph(path: &str) -> GraphDescription {
+        let test_json = std::fs::read_to_string(path).expect("unable to read test file.");
+
+        let event: OSQueryEvent =
+            serde_json::from_str(&test_json).expect("serde_json::from_str failed.");
+        match event {
+            OSQueryEvent::ProcessOpenSocket(event) => event.into(),
+            _ => panic!("expected OSQueryEvent::ProcessOpenSocket"),
+        }
+    }
+
+    fn find_port_node<'a>(
+        graph: &'a GraphDescription,
+        node_type: &str,
+        port: u64,
+    ) -> Option<&'a NodeDescription> {
+        graph.nodes.values().find(|n| {
+            n.node_type == node_type
+                && n.get_property("port")
+                    .and_then(|p| p.as_immutable_uint())
+                    .map(|p| p.as_inner())
+                    == Some(port)
+        })
+    }
+
+    fn find_edge_to(graph: &GraphDescription, edge_name: &str, to: &NodeDescription) -> bool {
+        graph
+            .edges
+            .values()
+            .flat_map(|edge_list| edge_list.edges.iter())
+            .any(|edge| edge.edge_name == edge_name && edge.to_node_key == to.get_node_key())
+    }
+
+    #[test]
+    fn parse_pack_grapl_process_open_sockets_json() {
+        let graph = parse_graph("sample_data/unit/pack_grapl_process-open-sockets.json");
+
+        // sshd's accepted connection: a fixed local port and an ephemeral peer
+        let inbound = find_port_node(&graph, "ProcessInboundConnection", 22)
+            .expect("inbound connection missing");
+        assert!(find_edge_to(&graph, "inbound_connections", inbound));
+        assert!(find_port_node(&graph, "ProcessOutboundConnection", 22).is_none());
+
+        let peer = find_port_node(&graph, "IpPort", 51812).expect("peer port missing");
+        assert!(find_edge_to(&graph, "connected_from", peer));
+        assert!(!find_edge_to(&graph, "connected_to", peer));
+    }
+
+    #[test]
+    fn parse_pack_grapl_process_open_sockets_snapshot_json() {
+        let graph = parse_graph("sample_data/unit/pack_grapl_process-open-sockets_snapshot.json");
+
+        // A TCP listener and an unconnected UDP socket are both bound ports.
+        for port in [22, 68] {
+            let listening = find_port_node(&graph, "ProcessInboundConnection", port)
+                .expect("listening socket missing");
+            assert!(find_edge_to(&graph, "inbound_connections", listening));
+            assert!(find_port_node(&graph, "ProcessOutboundConnection", port).is_none());
+        }
+
+        let bound = find_port_node(&graph, "IpPort", 22).expect("bound port missing");
+        assert!(find_edge_to(&graph, "bound_port", bound));
+
+        let outbound = find_port_node(&graph, "ProcessOutboundConnection", 41234)
+            .expect("outbound connection missing");
+        assert!(find_edge_to(&graph, "created_connections", outbound));
+        assert!(find_port_node(&graph, "ProcessInboundConnection", 41234).is_none());
+
+        let destination = find_port_node(&graph, "IpPort", 443).expect("destination port missing");
+        assert!(find_edge_to(&graph, "connected_to", destination));
+        assert!(!find_edge_to(&graph, "connected_from", destination));
+    }
+}
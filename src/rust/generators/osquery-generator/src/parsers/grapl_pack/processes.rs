@@ -10,7 +10,10 @@ use rust_proto::graplinc::grapl::api::graph::v1beta1::GraphDescription;
 use serde::Deserialize;
 
 use super::from_str;
-use crate::parsers::OSQueryAction;
+use crate::parsers::{
+    OSQueryAction,
+    OSQueryRows,
+};
 
 #[derive(Deserialize, Debug, Clone, Hash)]
 #[serde(rename_all = "camelCase")]
@@ -18,8 +21,8 @@ pub struct ProcessEvent {
     host_identifier: String,
     calendar_time: String,
     unix_time: u64,
-    action: OSQueryAction,
-    columns: ProcessEventColumns,
+    #[serde(flatten)]
+    rows: OSQueryRows<ProcessEventColumns>,
 }
 
 /// See https://osquery.io/schema/4.5.0/#processes
@@ -43,90 +46,170 @@ impl From<ProcessEvent> for GraphDescription {
 
         let mut graph = GraphDescription::new();
 
-        // this field can be -1 in cases of error
-        // https://osquery.io/schema/4.5.1/#processes
-        let process_start_time = if process_event.columns.time == -1 {
-            process_event.unix_time
-        } else {
-            process_event.columns.time as u64
-        };
+        for (action, columns) in process_event.rows {
+            let row_graph = match action {
+                OSQueryAction::Removed => removed_process_graph(
+                    &process_event.host_identifier,
+                    process_event.unix_time,
+                    &columns,
+                ),
+                _ => process_graph(
+                    &process_event.host_identifier,
+                    process_event.unix_time,
+                    &columns,
+                ),
+            };
 
-        let mut asset = AssetNode::new(AssetNode::static_strategy());
-        asset
-            .with_asset_id(process_event.host_identifier.clone())
-            .with_hostname(process_event.host_identifier.clone());
-
-        let mut child = ProcessNode::new(ProcessNode::session_strategy());
-        child
-            .with_asset_id(process_event.host_identifier.clone())
-            .with_created_timestamp(process_start_time)
-            .with_last_seen_timestamp(process_start_time)
-            .with_process_name(process_event.columns.name.clone().unwrap_or("".to_string()))
-            .with_process_id(process_event.columns.pid);
-
-        if !process_event.columns.path.is_empty() {
-            let mut child_exe = FileNode::new(FileNode::session_strategy());
-            child_exe
-                .with_asset_id(process_event.host_identifier.clone())
-                .with_file_path(process_event.columns.path.clone())
-                .with_last_seen_timestamp(process_start_time);
-
-            graph.add_edge(
-                "binary_file",
-                child.clone_node_key(),
-                child_exe.clone_node_key(),
-            );
-
-            graph.add_edge(
-                "files_on_asset",
-                asset.clone_node_key(),
-                child_exe.clone_node_key(),
-            );
-
-            graph.add_node(child_exe);
+            graph.merge(&row_graph);
         }
 
-        // OSQuery can record -1 for ppid if a parent is not able to be determined
-        // https://osquery.io/schema/4.5.1/#process_events
-        if process_event.columns.parent >= 0 {
-            let mut parent_process = ProcessNode::new(ProcessNode::session_strategy());
-            parent_process
-                .with_asset_id(process_event.host_identifier.clone())
-                .with_process_id(process_event.columns.parent as u64)
-                .with_last_seen_timestamp(process_start_time);
-
-            graph.add_edge(
-                "children",
-                parent_process.clone_node_key(),
-                child.clone_node_key(),
-            );
-
-            graph.add_edge(
-                "asset_processes",
-                asset.clone_node_key(),
-                parent_process.clone_node_key(),
-            );
-
-            graph.add_node(parent_process);
-        }
+        graph
+    }
+}
+
+/// A removed row means osquery no longer sees the process running. We only
+/// know when osquery noticed, so the termination is recorded at that time
+/// against the existing process rather than describing a new one.
+fn removed_process_graph(
+    host_identifier: &str,
+    unix_time: u64,
+    columns: &ProcessEventColumns,
+) -> GraphDescription {
+    let mut graph = GraphDescription::new();
+
+    let mut asset = AssetNode::new(AssetNode::static_strategy());
+    asset
+        .with_asset_id(host_identifier)
+        .with_hostname(host_identifier);
+
+    let mut process = ProcessNode::new(ProcessNode::session_strategy());
+    process
+        .with_asset_id(host_identifier)
+        .with_process_id(columns.pid)
+        .with_terminated_timestamp(unix_time);
+
+    graph.add_edge(
+        "asset_processes",
+        asset.clone_node_key(),
+        process.clone_node_key(),
+    );
+
+    graph.add_node(asset);
+    graph.add_node(process);
+
+    graph
+}
+
+fn process_graph(
+    host_identifier: &str,
+    unix_time: u64,
+    columns: &ProcessEventColumns,
+) -> GraphDescription {
+    let mut graph = GraphDescription::new();
+
+    // this field can be -1 in cases of error
+    // https://osquery.io/schema/4.5.1/#processes
+    let process_start_time = if columns.time == -1 {
+        unix_time
+    } else {
+        columns.time as u64
+    };
+
+    let mut asset = AssetNode::new(AssetNode::static_strategy());
+    asset
+        .with_asset_id(host_identifier)
+        .with_hostname(host_identifier);
+
+    let mut child = ProcessNode::new(ProcessNode::session_strategy());
+    child
+        .with_asset_id(host_identifier)
+        .with_created_timestamp(process_start_time)
+        .with_last_seen_timestamp(process_start_time)
+        .with_process_name(columns.name.clone().unwrap_or("".to_string()))
+        .with_process_id(columns.pid);
+
+    if !columns.path.is_empty() {
+        let mut child_exe = FileNode::new(FileNode::session_strategy());
+        child_exe
+            .with_asset_id(host_identifier)
+            .with_file_path(columns.path.clone())
+            .with_last_seen_timestamp(process_start_time);
 
         graph.add_edge(
-            "asset_processes",
+            "binary_file",
+            child.clone_node_key(),
+            child_exe.clone_node_key(),
+        );
+
+        graph.add_edge(
+            "files_on_asset",
             asset.clone_node_key(),
+            child_exe.clone_node_key(),
+        );
+
+        graph.add_node(child_exe);
+    }
+
+    // OSQuery can record -1 for ppid if a parent is not able to be determined
+    // https://osquery.io/schema/4.5.1/#process_events
+    if columns.parent >= 0 {
+        let mut parent_process = ProcessNode::new(ProcessNode::session_strategy());
+        parent_process
+            .with_asset_id(host_identifier)
+            .with_process_id(columns.parent as u64)
+            .with_last_seen_timestamp(process_start_time);
+
+        graph.add_edge(
+            "children",
+            parent_process.clone_node_key(),
             child.clone_node_key(),
         );
 
-        graph.add_node(child);
-        graph.add_node(asset);
+        graph.add_edge(
+            "asset_processes",
+            asset.clone_node_key(),
+            parent_process.clone_node_key(),
+        );
 
-        graph
+        graph.add_node(parent_process);
     }
+
+    graph.add_edge(
+        "asset_processes",
+        asset.clone_node_key(),
+        child.clone_node_key(),
+    );
+
+    graph.add_node(child);
+    graph.add_node(asset);
+
+    graph
 }
 
 #[cfg(test)]
 mod tests {
+    use rust_proto::graplinc::grapl::api::graph::v1beta1::{
+        GraphDescription,
+        NodeDescription,
+    };
+
     use crate::parsers::OSQueryEvent;
 
+    fn find_process(graph: &GraphDescription, pid: u64) -> Option<&NodeDescription> {
+        graph.nodes.values().find(|n| {
+            n.get_property("process_id")
+                .and_then(|p| p.as_immutable_uint())
+                .map(|p| p.as_inner())
+                == Some(pid)
+        })
+    }
+
+    fn uint_property(node: &NodeDescription, name: &str) -> Option<u64> {
+        node.get_property(name)
+            .and_then(|p| p.as_immutable_uint())
+            .map(|p| p.as_inner())
+    }
+
     #[test]
     fn parse_pack_grapl_processes_json() {
         let test_json = std::fs::read_to_string("sample_data/unit/pack_grapl_processes.json")
@@ -139,4 +222,52 @@ mod tests {
             _ => panic!("expected OSQueryEvent::Process"),
         };
     }
+
+    #[test]
+    fn parse_pack_grapl_processes_snapshot_json() {
+        let test_json =
+            std::fs::read_to_string("sample_data/unit/pack_grapl_processes_snapshot.json")
+                .expect("unable to read test file.");
+
+        let event: OSQueryEvent =
+            serde_json::from_str(&test_json).expect("serde_json::from_str failed.");
+        let graph: GraphDescription = match event {
+            OSQueryEvent::Process(event) => event.into(),
+            _ => panic!("expected OSQueryEvent::Process"),
+        };
+
+        let bash = find_process(&graph, 9355).expect("bash process missing");
+        assert_eq!(uint_property(bash, "created_timestamp"), Some(1603399763));
+
+        let osqueryd = find_process(&graph, 9214).expect("osqueryd process missing");
+        assert_eq!(
+            uint_property(osqueryd, "created_timestamp"),
+            Some(1603399120)
+        );
+    }
+
+    #[test]
+    fn parse_pack_grapl_processes_batch_json() {
+        let test_json = std::fs::read_to_string("sample_data/unit/pack_grapl_processes_batch.json")
+            .expect("unable to read test file.");
+
+        let event: OSQueryEvent =
+            serde_json::from_str(&test_json).expect("serde_json::from_str failed.");
+        let graph: GraphDescription = match event {
+            OSQueryEvent::Process(event) => event.into(),
+            _ => panic!("expected OSQueryEvent::Process"),
+        };
+
+        let added = find_process(&graph, 9355).expect("added process missing");
+        assert_eq!(uint_property(added, "created_timestamp"), Some(1603399763));
+
+        // removed rows only terminate the existing process
+        let removed = find_process(&graph, 9301).expect("removed process missing");
+        assert_eq!(
+            uint_property(removed, "terminated_timestamp"),
+            Some(1603400082)
+        );
+        assert_eq!(uint_property(removed, "created_timestamp"), None);
+        assert!(graph.edges.get(removed.get_node_key()).is_none());
+    }
 }
@@ -0,0 +1,168 @@
+use rust_proto::graplinc::grapl::api::graph::v1beta1::GraphDescription;
+use serde::Deserialize;
+
+use super::{
+    connection::{
+        self,
+        Endpoint,
+    },
+    from_str,
+};
+use crate::parsers::{
+    OSQueryAction,
+    OSQueryRows,
+};
+
+#[derive(Deserialize, Debug, Clone, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct SocketEvent {
+    host_identifier: String,
+    calendar_time: String,
+    unix_time: u64,
+    #[serde(flatten)]
+    rows: OSQueryRows<SocketEventColumns>,
+}
+
+/// See https://osquery.io/schema/4.5.0/#socket_events
+#[derive(Deserialize, Debug, Clone, Hash)]
+pub struct SocketEventColumns {
+    action: OSQuerySocketAction,
+    #[serde(deserialize_with = "from_str")]
+    pid: i64,
+    #[serde(deserialize_with = "from_str")]
+    success: i64,
+    #[serde(deserialize_with = "from_str")]
+    family: u64,
+    #[serde(deserialize_with = "from_str")]
+    protocol: u64,
+    local_address: String,
+    remote_address: String,
+    #[serde(deserialize_with = "from_str")]
+    local_port: u64,
+    #[serde(deserialize_with = "from_str")]
+    remote_port: u64,
+    #[serde(deserialize_with = "from_str")]
+    time: u64,
+}
+
+#[derive(Deserialize, Debug, Clone, Hash)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum OSQuerySocketAction {
+    Connect,
+    Bind,
+    Accept,
+    #[serde(other)]
+    Other,
+}
+
+impl From<SocketEvent> for GraphDescription {
+    #[tracing::instrument]
+    fn from(socket_event: SocketEvent) -> Self {
+        tracing::trace!(message = "Building Graph from SocketEvent.");
+
+        let mut graph = GraphDescription::new();
+
+        for (action, columns) in socket_event.rows {
+            // `socket_events` is an evented table, so a removed row only
+            // means the event aged out of the query's window.
+            if let OSQueryAction::Removed = action {
+                continue;
+            }
+
+            if columns.pid < 0 || !connection::is_inet_family(columns.family) {
+                continue;
+            }
+
+            let protocol = connection::protocol_name(columns.protocol);
+            let local = Endpoint {
+                address: &columns.local_address,
+                port: columns.local_port,
+            };
+            let remote = Endpoint {
+                address: &columns.remote_address,
+                port: columns.remote_port,
+            };
+
+            let row_graph = match columns.action {
+                // Non-blocking sockets report an unsuccessful connect while
+                // the connection is still in progress, so those are kept.
+                OSQuerySocketAction::Connect => connection::outbound_connection_graph(
+                    &socket_event.host_identifier,
+                    columns.pid as u64,
+                    columns.time,
+                    &protocol,
+                    local,
+                    remote,
+                ),
+                OSQuerySocketAction::Bind if columns.success != 0 => {
+                    connection::inbound_connection_graph(
+                        &socket_event.host_identifier,
+                        columns.pid as u64,
+                        columns.time,
+                        &protocol,
+                        local,
+                        None,
+                    )
+                }
+                OSQuerySocketAction::Accept if columns.success != 0 => {
+                    connection::inbound_connection_graph(
+                        &socket_event.host_identifier,
+                        columns.pid as u64,
+                        columns.time,
+                        &protocol,
+                        local,
+                        Some(remote),
+                    )
+                }
+                _ => continue,
+            };
+
+            graph.merge(&row_graph);
+        }
+
+        graph
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_proto::graplinc::grapl::api::graph::v1beta1::GraphDescription;
+
+    use crate::parsers::OSQueryEvent;
+
+    #[test]
+    fn parse_pack_grapl_socket_events_json() {
+        let test_json = std::fs::read_to_string("sample_data/unit/pack_grapl_socket-events.json")
+            .expect("unable to read test file.");
+
+        let event: OSQueryEvent =
+            serde_json::from_str(&test_json).expect("serde_json::from_str failed.");
+        let graph: GraphDescription = match event {
+            OSQueryEvent::Socket(event) => event.into(),
+            _ => panic!("expected OSQueryEvent::Socket"),
+        };
+
+        let dst_port = graph
+            .nodes
+            .values()
+            .find(|n| {
+                n.get_property("port")
+                    .and_then(|p| p.as_immutable_uint())
+                    .map(|p| p.as_inner())
+                    == Some(443)
+            })
+            .expect("destination port missing");
+
+        assert_eq!(
+            dst_port
+                .get_property("ip_address")
+                .and_then(|p| p.as_immutable_str())
+                .map(|p| p.as_inner()),
+            Some("93.184.216.34")
+        );
+
+        // the local address is unknown, so there's no IpConnection to link
+        // the remote IpAddress to and it isn't added
+        assert!(graph.nodes.values().all(|n| n.node_type != "IpAddress"));
+    }
+}
@@ -12,6 +12,12 @@ pub enum OSQueryEvent {
     ProcessFileAction(grapl_pack::process_files::ProcessFileInteractionEvent),
     #[serde(rename = "pack_grapl_files")]
     File(grapl_pack::files::FileEvent),
+    #[serde(rename = "pack_grapl_process-open-sockets")]
+    ProcessOpenSocket(grapl_pack::process_open_sockets::ProcessOpenSocketEvent),
+    #[serde(rename = "pack_grapl_listening-ports")]
+    ListeningPort(grapl_pack::listening_ports::ListeningPortEvent),
+    #[serde(rename = "pack_grapl_socket-events")]
+    Socket(grapl_pack::socket_events::SocketEvent),
 }
 
 #[derive(Deserialize, Debug, Clone, Hash)]
@@ -19,15 +25,82 @@ pub enum OSQueryEvent {
 pub(self) enum OSQueryAction {
     Added,
     Removed,
+    Snapshot,
     Other(String),
 }
 
+/// The result rows carried by a single osquery result log.
+///
+/// Depending on the query's `snapshot` setting and the logger's
+/// `--logger_event_type` flag, osquery writes results in one of three shapes:
+/// * differential ("event") logs, with a single row in `columns` and an `action`
+/// * snapshot logs, with every row the query returned in `snapshot`
+/// * batched differential logs, with `added` and `removed` rows in `diffResults`
+///
+/// See https://osquery.readthedocs.io/en/stable/deployment/logging/#results-logs
+#[derive(Deserialize, Debug, Clone, Hash)]
+#[serde(untagged)]
+pub(self) enum OSQueryRows<C> {
+    Differential {
+        action: OSQueryAction,
+        columns: C,
+    },
+    Snapshot {
+        snapshot: Vec<C>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Batch {
+        diff_results: OSQueryDiffResults<C>,
+    },
+}
+
+#[derive(Deserialize, Debug, Clone, Hash)]
+pub(self) struct OSQueryDiffResults<C> {
+    #[serde(default = "Vec::new")]
+    added: Vec<C>,
+    #[serde(default = "Vec::new")]
+    removed: Vec<C>,
+}
+
+impl<C> IntoIterator for OSQueryRows<C> {
+    type Item = (OSQueryAction, C);
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+
+    /// Flattens every shape into `(action, row)` pairs, so that parsers only
+    /// need to know how to handle a single row.
+    fn into_iter(self) -> Self::IntoIter {
+        let rows: Vec<_> = match self {
+            OSQueryRows::Differential { action, columns } => vec![(action, columns)],
+            OSQueryRows::Snapshot { snapshot } => snapshot
+                .into_iter()
+                .map(|row| (OSQueryAction::Snapshot, row))
+                .collect(),
+            OSQueryRows::Batch { diff_results } => diff_results
+                .added
+                .into_iter()
+                .map(|row| (OSQueryAction::Added, row))
+                .chain(
+                    diff_results
+                        .removed
+                        .into_iter()
+                        .map(|row| (OSQueryAction::Removed, row)),
+                )
+                .collect(),
+        };
+
+        rows.into_iter()
+    }
+}
+
 impl From<OSQueryEvent> for GraphDescription {
     fn from(event: OSQueryEvent) -> Self {
         match event {
             OSQueryEvent::File(event) => event.into(),
             OSQueryEvent::Process(event) => event.into(),
             OSQueryEvent::ProcessFileAction(event) => event.into(),
+            OSQueryEvent::ProcessOpenSocket(event) => event.into(),
+            OSQueryEvent::ListeningPort(event) => event.into(),
+            OSQueryEvent::Socket(event) => event.into(),
         }
     }
 }
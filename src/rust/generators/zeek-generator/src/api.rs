@@ -0,0 +1,42 @@
+use rust_proto::graplinc::grapl::api::{
+    graph::v1beta1::GraphDescription,
+    plugin_sdk::generators::v1beta1::{
+        server::GeneratorApi,
+        GeneratedGraph,
+        RunGeneratorRequest,
+        RunGeneratorResponse,
+    },
+};
+
+use crate::{
+    error::ZeekGeneratorError,
+    models,
+    parser,
+};
+
+pub struct ZeekGenerator {}
+
+#[async_trait::async_trait]
+impl GeneratorApi for ZeekGenerator {
+    type Error = ZeekGeneratorError;
+
+    /// Unlike sysmon-generator, this accepts any number of records per
+    /// request: a TSV record can't be interpreted without the `#fields` and
+    /// `#types` headers that precede it.
+    #[tracing::instrument(skip(self, request), err)]
+    async fn run_generator(
+        &self,
+        request: RunGeneratorRequest,
+    ) -> Result<RunGeneratorResponse, Self::Error> {
+        let input_utf8 = std::str::from_utf8(&request.data)?;
+
+        let mut graph_description = GraphDescription::new();
+        for record in parser::parse_records(input_utf8)? {
+            graph_description.merge(&models::generate_graph_from_record(&record));
+        }
+
+        Ok(RunGeneratorResponse {
+            generated_graph: GeneratedGraph { graph_description },
+        })
+    }
+}
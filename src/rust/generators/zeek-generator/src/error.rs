@@ -0,0 +1,22 @@
+use rust_proto::graplinc::grapl::api::protocol::status::Status;
+use thiserror::Error;
+
+/// This represents all possible errors that can occur in this generator.
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum ZeekGeneratorError {
+    #[error("error converting bytes to utf-8 {0}")]
+    Utf8Error(#[from] std::str::Utf8Error),
+
+    #[error("error parsing zeek JSON record {0}")]
+    JsonError(#[from] serde_json::Error),
+
+    #[error("error configuring tracing {0}")]
+    SetupTracingError(#[from] grapl_tracing::SetupTracingError),
+}
+
+impl From<ZeekGeneratorError> for Status {
+    fn from(e: ZeekGeneratorError) -> Self {
+        Status::unknown(e.to_string())
+    }
+}
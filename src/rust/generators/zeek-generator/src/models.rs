@@ -0,0 +1,22 @@
+use rust_proto::graplinc::grapl::api::graph::v1beta1::GraphDescription;
+
+use crate::parser::ZeekRecord;
+
+mod conn;
+mod dns;
+
+#[tracing::instrument(skip(record))]
+pub(crate) fn generate_graph_from_record(record: &ZeekRecord) -> GraphDescription {
+    let graph = match record {
+        ZeekRecord::Conn(record) => conn::generate_conn_subgraph(record),
+        ZeekRecord::Dns(record) => dns::generate_dns_subgraph(record),
+    };
+
+    tracing::debug!(
+        message = "completed graph generation",
+        node_count = graph.nodes.len(),
+        edge_count = graph.edges.len(),
+    );
+
+    graph
+}
@@ -0,0 +1,121 @@
+use endpoint_plugin::{
+    IIpAddressNode,
+    IIpConnectionNode,
+    IIpPortNode,
+    INetworkConnectionNode,
+    IpAddressNode,
+    IpConnectionNode,
+    IpPortNode,
+    NetworkConnectionNode,
+};
+use rust_proto::graplinc::grapl::api::graph::v1beta1::GraphDescription;
+
+use crate::parser::ConnRecord;
+
+/// Creates a subgraph describing a connection observed on the network.
+///
+/// Subgraph generation for a conn.log record includes the following:
+/// * Source and Destination IP Address and Port nodes
+/// * IP connection and Network connection nodes
+///
+/// These are the same nodes sysmon-generator creates for an outbound
+/// connection, so host-observed and network-observed connections merge.
+#[tracing::instrument]
+pub(crate) fn generate_conn_subgraph(record: &ConnRecord) -> GraphDescription {
+    tracing::trace!("generating graph from record");
+
+    let mut graph = GraphDescription::new();
+
+    let mut src_ip = IpAddressNode::new(IpAddressNode::identity_strategy());
+    src_ip
+        .with_ip_address(&record.orig_h)
+        .with_first_seen_timestamp(record.ts)
+        .with_last_seen_timestamp(record.ts);
+
+    let mut dst_ip = IpAddressNode::new(IpAddressNode::identity_strategy());
+    dst_ip
+        .with_ip_address(&record.resp_h)
+        .with_first_seen_timestamp(record.ts)
+        .with_last_seen_timestamp(record.ts);
+
+    let mut src_port = IpPortNode::new(IpPortNode::identity_strategy());
+    src_port
+        .with_ip_address(&record.orig_h)
+        .with_port(record.orig_p)
+        .with_protocol(&record.proto)
+        .with_first_seen_timestamp(record.ts)
+        .with_last_seen_timestamp(record.ts);
+
+    let mut dst_port = IpPortNode::new(IpPortNode::identity_strategy());
+    dst_port
+        .with_ip_address(&record.resp_h)
+        .with_port(record.resp_p)
+        .with_protocol(&record.proto)
+        .with_first_seen_timestamp(record.ts)
+        .with_last_seen_timestamp(record.ts);
+
+    let mut network_connection =
+        NetworkConnectionNode::new(NetworkConnectionNode::identity_strategy());
+    network_connection
+        .with_src_ip_address(&record.orig_h)
+        .with_src_port(record.orig_p)
+        .with_dst_ip_address(&record.resp_h)
+        .with_dst_port(record.resp_p)
+        .with_protocol(&record.proto)
+        .with_created_timestamp(record.ts)
+        .with_last_seen_timestamp(record.ts);
+
+    let mut ip_connection = IpConnectionNode::new(IpConnectionNode::identity_strategy());
+    ip_connection
+        .with_src_ip_address(&record.orig_h)
+        .with_dst_ip_address(&record.resp_h)
+        .with_protocol(&record.proto)
+        .with_created_timestamp(record.ts)
+        .with_last_seen_timestamp(record.ts);
+
+    // Zeek only logs a connection once it has ended, so its duration tells us
+    // when it was terminated.
+    if let Some(duration) = record.duration {
+        let terminated_timestamp = record.ts + duration;
+
+        network_connection
+            .with_terminated_timestamp(terminated_timestamp)
+            .with_last_seen_timestamp(terminated_timestamp);
+        ip_connection
+            .with_terminated_timestamp(terminated_timestamp)
+            .with_last_seen_timestamp(terminated_timestamp);
+    }
+
+    graph.add_edge(
+        "ip_connections",
+        src_ip.clone_node_key(),
+        ip_connection.clone_node_key(),
+    );
+
+    graph.add_edge(
+        "ip_connections",
+        dst_ip.clone_node_key(),
+        ip_connection.clone_node_key(),
+    );
+
+    graph.add_edge(
+        "network_connections",
+        src_port.clone_node_key(),
+        network_connection.clone_node_key(),
+    );
+
+    graph.add_edge(
+        "network_connections",
+        dst_port.clone_node_key(),
+        network_connection.clone_node_key(),
+    );
+
+    graph.add_node(src_ip);
+    graph.add_node(dst_ip);
+    graph.add_node(src_port);
+    graph.add_node(dst_port);
+    graph.add_node(network_connection);
+    graph.add_node(ip_connection);
+
+    graph
+}
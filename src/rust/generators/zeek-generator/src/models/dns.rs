@@ -0,0 +1,86 @@
+use std::net::IpAddr;
+
+use endpoint_plugin::{
+    DomainNameNode,
+    IDomainNameNode,
+    IIpAddressNode,
+    IpAddressNode,
+};
+use rust_proto::graplinc::grapl::api::graph::v1beta1::GraphDescription;
+
+use crate::parser::DnsRecord;
+
+/// Creates a subgraph describing a DNS lookup observed on the network.
+///
+/// Subgraph generation for a dns.log record includes the following:
+/// * An IP Address node - indicating the client which made the query
+/// * A `DomainName` node - indicating the name which was queried
+/// * IP Address nodes for each address the name resolved to
+/// * `DomainName` nodes for each canonical name the name resolved to
+#[tracing::instrument]
+pub(crate) fn generate_dns_subgraph(record: &DnsRecord) -> GraphDescription {
+    tracing::trace!("generating graph from record");
+
+    let mut graph = GraphDescription::new();
+
+    let query = match &record.query {
+        Some(query) if !query.is_empty() => query,
+        _ => return graph,
+    };
+
+    let mut client_ip = IpAddressNode::new(IpAddressNode::identity_strategy());
+    client_ip
+        .with_ip_address(&record.orig_h)
+        .with_first_seen_timestamp(record.ts)
+        .with_last_seen_timestamp(record.ts);
+
+    let mut domain = DomainNameNode::new(DomainNameNode::identity_strategy());
+    domain
+        .with_domain_name(query)
+        .with_first_seen_timestamp(record.ts)
+        .with_last_seen_timestamp(record.ts);
+
+    graph.add_edge(
+        "queried_domains",
+        client_ip.clone_node_key(),
+        domain.clone_node_key(),
+    );
+
+    for answer in &record.answers {
+        // Answers are either addresses (A/AAAA) or names (e.g. CNAME)
+        if answer.parse::<IpAddr>().is_ok() {
+            let mut resolved_ip = IpAddressNode::new(IpAddressNode::identity_strategy());
+            resolved_ip
+                .with_ip_address(answer)
+                .with_first_seen_timestamp(record.ts)
+                .with_last_seen_timestamp(record.ts);
+
+            graph.add_edge(
+                "resolved_addresses",
+                domain.clone_node_key(),
+                resolved_ip.clone_node_key(),
+            );
+
+            graph.add_node(resolved_ip);
+        } else if answer != query {
+            let mut canonical_name = DomainNameNode::new(DomainNameNode::identity_strategy());
+            canonical_name
+                .with_domain_name(answer)
+                .with_first_seen_timestamp(record.ts)
+                .with_last_seen_timestamp(record.ts);
+
+            graph.add_edge(
+                "canonical_names",
+                domain.clone_node_key(),
+                canonical_name.clone_node_key(),
+            );
+
+            graph.add_node(canonical_name);
+        }
+    }
+
+    graph.add_node(client_ip);
+    graph.add_node(domain);
+
+    graph
+}
@@ -0,0 +1,469 @@
+use chrono::DateTime;
+use serde::{
+    Deserialize,
+    Deserializer,
+};
+use serde_json::{
+    Map,
+    Value,
+};
+
+use crate::error::ZeekGeneratorError;
+
+/// A Zeek log record this generator knows how to build a graph from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ZeekRecord {
+    Conn(ConnRecord),
+    Dns(DnsRecord),
+}
+
+/// See https://docs.zeek.org/en/master/scripts/base/protocols/conn/main.zeek.html#type-Conn::Info
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct ConnRecord {
+    #[serde(deserialize_with = "zeek_time")]
+    pub ts: u64,
+    pub uid: String,
+    #[serde(rename = "id.orig_h")]
+    pub orig_h: String,
+    #[serde(rename = "id.orig_p")]
+    pub orig_p: u64,
+    #[serde(rename = "id.resp_h")]
+    pub resp_h: String,
+    #[serde(rename = "id.resp_p")]
+    pub resp_p: u64,
+    pub proto: String,
+    #[serde(default, deserialize_with = "zeek_interval")]
+    pub duration: Option<u64>,
+    #[serde(default)]
+    pub conn_state: Option<String>,
+}
+
+/// See https://docs.zeek.org/en/master/scripts/base/protocols/dns/main.zeek.html#type-DNS::Info
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct DnsRecord {
+    #[serde(deserialize_with = "zeek_time")]
+    pub ts: u64,
+    pub uid: String,
+    #[serde(rename = "id.orig_h")]
+    pub orig_h: String,
+    #[serde(rename = "id.orig_p")]
+    pub orig_p: u64,
+    #[serde(rename = "id.resp_h")]
+    pub resp_h: String,
+    #[serde(rename = "id.resp_p")]
+    pub resp_p: u64,
+    pub proto: String,
+    #[serde(default)]
+    pub query: Option<String>,
+    #[serde(default)]
+    pub rcode_name: Option<String>,
+    #[serde(default)]
+    pub answers: Vec<String>,
+}
+
+/// Zeek writes timestamps as fractional epoch seconds, or as ISO 8601 strings
+/// when `LogAscii::json_timestamps` is set to `JSON::TS_ISO8601`. Both are
+/// converted to epoch milliseconds, matching sysmon-generator, so that
+/// network-observed connections line up with host-observed ones.
+fn zeek_time<'de, D>(deserializer: D) -> Result<u64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum ZeekTime {
+        Epoch(f64),
+        Iso8601(String),
+    }
+
+    let millis = match ZeekTime::deserialize(deserializer)? {
+        ZeekTime::Epoch(seconds) => (seconds * 1000.0) as i64,
+        ZeekTime::Iso8601(timestamp) => DateTime::parse_from_rfc3339(&timestamp)
+            .map_err(serde::de::Error::custom)?
+            .timestamp_millis(),
+    };
+
+    u64::try_from(millis)
+        .map_err(|_| serde::de::Error::custom(format!("found negative time value: `{}`", millis)))
+}
+
+/// Converts a Zeek interval, in fractional seconds, to milliseconds.
+fn zeek_interval<'de, D>(deserializer: D) -> Result<Option<u64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let seconds = Option::<f64>::deserialize(deserializer)?;
+    Ok(seconds.map(|seconds| (seconds * 1000.0) as u64))
+}
+
+/// Parses every supported record out of a chunk of a Zeek log.
+///
+/// Zeek writes either tab-separated logs, which start with `#`-prefixed
+/// headers describing their fields, or JSON lines (`LogAscii::use_json=T`).
+/// Records from logs other than conn.log and dns.log are skipped, as are
+/// TSV logs missing their `#fields` header and individual lines that can't be
+/// parsed, so that one bad line doesn't drop the rest of the chunk.
+pub fn parse_records(input: &str) -> Result<Vec<ZeekRecord>, ZeekGeneratorError> {
+    let is_tsv = input
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map_or(false, |line| line.starts_with('#'));
+
+    if is_tsv {
+        parse_tsv(input)
+    } else {
+        parse_json(input)
+    }
+}
+
+fn parse_json(input: &str) -> Result<Vec<ZeekRecord>, ZeekGeneratorError> {
+    let mut records = Vec::new();
+
+    for line in input.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        let object: Map<String, Value> = match serde_json::from_str(line) {
+            Ok(object) => object,
+            Err(e) => {
+                tracing::warn!(message = "skipping malformed zeek json line", error = %e);
+                continue;
+            }
+        };
+
+        // `_path` is only written by some log writers (e.g. json-streaming-logs
+        // or the Kafka writer); otherwise the log is recognised by a field
+        // unique to it.
+        let path = match object.get("_path").and_then(Value::as_str) {
+            Some(path) => path.to_string(),
+            None if object.contains_key("query") => "dns".to_string(),
+            None if object.contains_key("conn_state") => "conn".to_string(),
+            None => "unknown".to_string(),
+        };
+
+        if let Some(record) = record_from_object(&path, object) {
+            records.push(record);
+        }
+    }
+
+    Ok(records)
+}
+
+/// The `#`-prefixed headers of a TSV log, as described in
+/// https://docs.zeek.org/en/master/log-formats.html#zeek-tsv-format-logs
+struct TsvHeader {
+    separator: String,
+    set_separator: String,
+    empty_field: String,
+    unset_field: String,
+    path: Option<String>,
+    fields: Vec<String>,
+    types: Vec<String>,
+}
+
+impl Default for TsvHeader {
+    fn default() -> Self {
+        Self {
+            separator: "\t".to_string(),
+            set_separator: ",".to_string(),
+            empty_field: "(empty)".to_string(),
+            unset_field: "-".to_string(),
+            path: None,
+            fields: Vec::new(),
+            types: Vec::new(),
+        }
+    }
+}
+
+fn parse_tsv(input: &str) -> Result<Vec<ZeekRecord>, ZeekGeneratorError> {
+    let mut header = TsvHeader::default();
+    let mut records = Vec::new();
+    let mut skipped_lines = 0;
+
+    for line in input.lines().filter(|line| !line.trim().is_empty()) {
+        if line.starts_with('#') {
+            report_headerless_lines(skipped_lines);
+            skipped_lines = 0;
+        }
+
+        // Unlike the other headers, `#separator` is itself separated by a
+        // space. It's the first line of every log file, so it also starts a
+        // new set of headers when several files are concatenated.
+        if let Some(separator) = line.strip_prefix("#separator ") {
+            header = TsvHeader {
+                separator: unescape(separator),
+                ..TsvHeader::default()
+            };
+            continue;
+        }
+
+        if let Some(directive) = line.strip_prefix('#') {
+            let mut parts = directive.split(header.separator.as_str());
+            let name = parts.next().unwrap_or_default();
+            let values: Vec<String> = parts.map(str::to_string).collect();
+            let value = values.first().cloned().unwrap_or_default();
+
+            match name {
+                "set_separator" => header.set_separator = unescape(&value),
+                "empty_field" => header.empty_field = value,
+                "unset_field" => header.unset_field = value,
+                "path" => header.path = Some(value),
+                "fields" => header.fields = values,
+                "types" => header.types = values,
+                // `#open` and `#close`
+                _ => {}
+            }
+            continue;
+        }
+
+        if header.fields.is_empty() || header.fields.len() != header.types.len() {
+            skipped_lines += 1;
+            continue;
+        }
+
+        let values: Vec<&str> = line.split(header.separator.as_str()).collect();
+        if values.len() != header.fields.len() {
+            tracing::warn!(
+                message = "skipping malformed zeek tsv line",
+                expected_fields = header.fields.len(),
+                found_fields = values.len(),
+            );
+            continue;
+        }
+
+        let object: Map<String, Value> = header
+            .fields
+            .iter()
+            .zip(header.types.iter())
+            .zip(values)
+            .filter_map(|((field, zeek_type), raw)| {
+                tsv_value(&header, zeek_type, raw).map(|value| (field.clone(), value))
+            })
+            .collect();
+
+        let path = header.path.as_deref().unwrap_or("unknown");
+        if let Some(record) = record_from_object(path, object) {
+            records.push(record);
+        }
+    }
+
+    report_headerless_lines(skipped_lines);
+
+    Ok(records)
+}
+
+/// Records in a TSV log can't be read without its `#fields` and `#types`
+/// headers, so a log missing them is reported once and skipped.
+fn report_headerless_lines(skipped_lines: usize) {
+    if skipped_lines > 0 {
+        tracing::warn!(
+            message = "skipping zeek tsv log without matching #fields and #types headers",
+            skipped_lines = skipped_lines,
+        );
+    }
+}
+
+/// Converts a single TSV column into the JSON value Zeek would have written
+/// for it, so that both formats share one set of record definitions.
+fn tsv_value(header: &TsvHeader, zeek_type: &str, raw: &str) -> Option<Value> {
+    if raw == header.unset_field {
+        return None;
+    }
+
+    let container_type = zeek_type
+        .strip_prefix("set[")
+        .or_else(|| zeek_type.strip_prefix("vector["))
+        .and_then(|inner| inner.strip_suffix(']'));
+
+    if let Some(inner_type) = container_type {
+        if raw == header.empty_field {
+            return Some(Value::Array(Vec::new()));
+        }
+
+        return Some(Value::Array(
+            raw.split(header.set_separator.as_str())
+                .filter_map(|item| tsv_value(header, inner_type, item))
+                .collect(),
+        ));
+    }
+
+    match zeek_type {
+        "time" | "interval" | "double" => raw
+            .parse::<f64>()
+            .ok()
+            .and_then(serde_json::Number::from_f64)
+            .map(Value::Number),
+        "count" | "port" => raw.parse::<u64>().ok().map(Value::from),
+        "int" => raw.parse::<i64>().ok().map(Value::from),
+        "bool" => Some(Value::Bool(raw == "T")),
+        _ if raw == header.empty_field => Some(Value::String(String::new())),
+        _ => Some(Value::String(unescape(raw))),
+    }
+}
+
+/// Zeek escapes separators and non-printable bytes as `\xHH`. Multi-byte UTF-8
+/// characters are escaped a byte at a time, so the bytes are decoded together
+/// and anything that still isn't valid UTF-8 is replaced.
+fn unescape(value: &str) -> String {
+    let mut unescaped = Vec::with_capacity(value.len());
+    let mut rest = value;
+
+    while let Some(index) = rest.find("\\x") {
+        unescaped.extend_from_slice(rest[..index].as_bytes());
+
+        let byte = rest
+            .get(index + 2..index + 4)
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+
+        match byte {
+            Some(byte) => {
+                unescaped.push(byte);
+                rest = &rest[index + 4..];
+            }
+            None => {
+                unescaped.extend_from_slice(b"\\x");
+                rest = &rest[index + 2..];
+            }
+        }
+    }
+
+    unescaped.extend_from_slice(rest.as_bytes());
+    String::from_utf8_lossy(&unescaped).into_owned()
+}
+
+/// Builds a record from a parsed line, or `None` if the line is from a log we
+/// don't support or is missing fields its log requires.
+fn record_from_object(path: &str, object: Map<String, Value>) -> Option<ZeekRecord> {
+    let record = match path {
+        "conn" => serde_json::from_value(Value::Object(object)).map(ZeekRecord::Conn),
+        "dns" => serde_json::from_value(Value::Object(object)).map(ZeekRecord::Dns),
+        _ => {
+            // We do not expect to handle all Zeek logs
+            tracing::debug!(message = "skipping unsupported zeek log", path = path);
+            return None;
+        }
+    };
+
+    match record {
+        Ok(record) => Some(record),
+        Err(e) => {
+            tracing::warn!(message = "skipping malformed zeek record", path = path, error = %e);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONN_TSV: &str = "#separator \\x09
+#set_separator\t,
+#empty_field\t(empty)
+#unset_field\t-
+#path\tconn
+#open\t2020-10-22-20-00-00
+#fields\tts\tuid\tid.orig_h\tid.orig_p\tid.resp_h\tid.resp_p\tproto\tservice\tduration\tconn_state
+#types\ttime\tstring\taddr\tport\taddr\tport\tenum\tstring\tinterval\tstring
+1603400719.250000\tCHhAvVGS1DHFjwGM9\t172.31.46.189\t51812\t93.184.216.34\t443\ttcp\tssl\t1.500000\tSF
+1603400720.000000\tC4J4Th3PJpwUYZZ6gc\t172.31.46.189\t51813\t93.184.216.34\t80\ttcp\t-\t-\tS0
+#close\t2020-10-22-21-00-00
+";
+
+    #[test]
+    fn parse_conn_tsv() {
+        let records = parse_records(CONN_TSV).expect("failed to parse conn.log");
+
+        assert_eq!(records.len(), 2);
+        match &records[0] {
+            ZeekRecord::Conn(conn) => {
+                assert_eq!(conn.ts, 1603400719250);
+                assert_eq!(conn.orig_h, "172.31.46.189");
+                assert_eq!(conn.resp_p, 443);
+                assert_eq!(conn.duration, Some(1500));
+            }
+            _ => panic!("expected ZeekRecord::Conn"),
+        }
+        match &records[1] {
+            ZeekRecord::Conn(conn) => assert_eq!(conn.duration, None),
+            _ => panic!("expected ZeekRecord::Conn"),
+        }
+    }
+
+    #[test]
+    fn parse_dns_json() {
+        let input = r#"{"ts":"2020-10-22T20:58:39.250000Z","uid":"CHhAvVGS1DHFjwGM9","id.orig_h":"172.31.46.189","id.orig_p":39412,"id.resp_h":"172.31.0.2","id.resp_p":53,"proto":"udp","query":"example.com","rcode_name":"NOERROR","answers":["93.184.216.34"]}"#;
+
+        let records = parse_records(input).expect("failed to parse dns.log");
+
+        assert_eq!(records.len(), 1);
+        match &records[0] {
+            ZeekRecord::Dns(dns) => {
+                assert_eq!(dns.ts, 1603400319250);
+                assert_eq!(dns.query.as_deref(), Some("example.com"));
+                assert_eq!(dns.answers, vec!["93.184.216.34".to_string()]);
+            }
+            _ => panic!("expected ZeekRecord::Dns"),
+        }
+    }
+
+    #[test]
+    fn unsupported_logs_are_skipped() {
+        let input =
+            r#"{"_path":"http","ts":1603400719.25,"uid":"CHhAvVGS1DHFjwGM9","method":"GET"}"#;
+
+        let records = parse_records(input).expect("failed to parse http.log");
+
+        assert!(records.is_empty());
+    }
+
+    #[test]
+    fn malformed_lines_are_skipped() {
+        let input = r#"{"_path":"dns","ts":1603400719.25,"uid":"CHhAvVGS1DHFjwGM9","id.orig_h":"172.31.46.189","id.orig_p":39412,"id.resp_h":"172.31.0.2","id.resp_p":53,"proto":"udp","query":"example.com"}
+{"_path":"dns","ts":1603400719.5,"uid":
+{"_path":"conn","ts":1603400720.0,"uid":"C4J4Th3PJpwUYZZ6gc"}
+{"_path":"conn","ts":1603400720.0,"uid":"C4J4Th3PJpwUYZZ6gc","id.orig_h":"172.31.46.189","id.orig_p":51813,"id.resp_h":"93.184.216.34","id.resp_p":80,"proto":"tcp","conn_state":"S0"}"#;
+
+        let records = parse_records(input).expect("failed to parse mixed batch");
+
+        assert_eq!(records.len(), 2);
+        assert!(
+            matches!(&records[0], ZeekRecord::Dns(dns) if dns.query.as_deref() == Some("example.com"))
+        );
+        assert!(matches!(&records[1], ZeekRecord::Conn(conn) if conn.resp_p == 80));
+    }
+
+    #[test]
+    fn malformed_tsv_lines_are_skipped() {
+        let input = CONN_TSV.replacen("\t1.500000\tSF", "", 1);
+
+        let records = parse_records(&input).expect("failed to parse conn.log");
+
+        assert_eq!(records.len(), 1);
+        match &records[0] {
+            ZeekRecord::Conn(conn) => assert_eq!(conn.uid, "C4J4Th3PJpwUYZZ6gc"),
+            _ => panic!("expected ZeekRecord::Conn"),
+        }
+    }
+
+    #[test]
+    fn unescape_decodes_utf8_bytes() {
+        assert_eq!(unescape("caf\\xc3\\xa9\\x09bar"), "caf\u{e9}\tbar");
+        assert_eq!(unescape("\\xff\\xzz"), "\u{fffd}\\xzz");
+    }
+
+    #[test]
+    fn tsv_logs_without_fields_are_skipped() {
+        let headerless = "#separator \\x09
+#path\tconn
+1603400718.000000\tCmES5u32sYpV7JYN\t172.31.46.189\t51811\t93.184.216.34\t443\ttcp\t-\t-\tS0
+";
+        let input = format!("{}{}", headerless, CONN_TSV);
+
+        let records = parse_records(&input).expect("failed to parse conn.log");
+
+        assert_eq!(records.len(), 2);
+        match &records[0] {
+            ZeekRecord::Conn(conn) => assert_eq!(conn.uid, "CHhAvVGS1DHFjwGM9"),
+            _ => panic!("expected ZeekRecord::Conn"),
+        }
+    }
+}
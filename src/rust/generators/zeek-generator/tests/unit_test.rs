@@ -0,0 +1,81 @@
+use bytes::Bytes;
+use generator_sdk::test_utils::test_ctx::GeneratorTestContext;
+use rust_proto::graplinc::grapl::api::{
+    graph::v1beta1::{
+        GraphDescription,
+        ImmutableStrProp,
+        NodeDescription,
+        Property,
+    },
+    plugin_sdk::generators::v1beta1::RunGeneratorRequest,
+};
+use test_context::test_context;
+use zeek_generator::api::ZeekGenerator;
+
+fn find_node<'a>(
+    graph: &'a GraphDescription,
+    o_p_name: &str,
+    o_p_value: Property,
+) -> Option<&'a NodeDescription> {
+    graph.nodes.values().find(|n| {
+        n.properties.iter().any(|(p_name, p_value)| {
+            p_name.as_str() == o_p_name && p_value.property.clone() == o_p_value
+        })
+    })
+}
+
+fn log_bytes() -> Bytes {
+    let log_lines: Bytes = r#"
+{"ts":1603400719.25,"uid":"CHhAvVGS1DHFjwGM9","id.orig_h":"172.31.46.189","id.orig_p":39412,"id.resp_h":"172.31.0.2","id.resp_p":53,"proto":"udp","query":"www.example.com","rcode_name":"NOERROR","answers":["www.example.com.edgesuite.net","93.184.216.34"]}
+{"ts":1603400719.5,"uid":"C4J4Th3PJpwUYZZ6gc","id.orig_h":"172.31.46.189","id.orig_p":51812,"id.resp_h":"93.184.216.34","id.resp_p":443,"proto":"tcp","duration":1.5,"conn_state":"SF"}
+"#
+    .into();
+    log_lines
+}
+
+#[test_context(GeneratorTestContext)]
+#[tokio::test]
+async fn test_zeek_logs_produce_expected_graph(ctx: &mut GeneratorTestContext) -> eyre::Result<()> {
+    let mut client = ctx.get_client(ZeekGenerator {}).await;
+
+    let result = client
+        .run_generator(RunGeneratorRequest { data: log_bytes() })
+        .await?;
+    let generated_graph = result.generated_graph.graph_description;
+
+    let domain = find_node(
+        &generated_graph,
+        "domain_name",
+        ImmutableStrProp {
+            prop: "www.example.com".to_string(),
+        }
+        .into(),
+    )
+    .expect("queried domain missing");
+
+    let resolved_edges: Vec<_> = generated_graph
+        .edges
+        .get(domain.get_node_key())
+        .iter()
+        .flat_map(|edge_list| edge_list.edges.iter())
+        .map(|edge| edge.edge_name.as_str())
+        .collect();
+
+    assert!(resolved_edges.contains(&"resolved_addresses"));
+    assert!(resolved_edges.contains(&"canonical_names"));
+
+    let network_connection = generated_graph
+        .nodes
+        .values()
+        .find(|n| n.node_type == "NetworkConnection")
+        .expect("network connection missing");
+
+    assert_eq!(
+        network_connection
+            .get_property("terminated_timestamp")
+            .and_then(|p| p.as_immutable_uint())
+            .map(|p| p.as_inner()),
+        Some(1603400721000)
+    );
+    Ok(())
+}
@@ -22,9 +22,14 @@ use rust_proto::graplinc::grapl::{
             },
         },
         plugin_sdk::analyzers::v1beta1::messages::{
+            BoolPropertyUpdate,
+            BytesPropertyUpdate,
+            DoublePropertyUpdate,
             EdgeUpdate,
             Int64PropertyUpdate,
             StringPropertyUpdate,
+            StringSetPropertyUpdate,
+            TimestampPropertyUpdate,
             UInt64PropertyUpdate,
             Update,
         },
@@ -236,5 +241,39 @@ fn property_to_update(uid: Uid, property_name: String, property: &Property) -> U
                 value: property_name,
             },
         }),
+        Property::ImmutableBoolProp(_) => Update::BoolProperty(BoolPropertyUpdate {
+            uid,
+            property_name: PropertyName {
+                value: property_name,
+            },
+        }),
+        Property::ImmutableDoubleProp(_) => Update::DoubleProperty(DoublePropertyUpdate {
+            uid,
+            property_name: PropertyName {
+                value: property_name,
+            },
+        }),
+        Property::ImmutableBytesProp(_) => Update::BytesProperty(BytesPropertyUpdate {
+            uid,
+            property_name: PropertyName {
+                value: property_name,
+            },
+        }),
+        Property::ImmutableTimestampProp(_)
+        | Property::IncrementOnlyTimestampProp(_)
+        | Property::DecrementOnlyTimestampProp(_) => {
+            Update::TimestampProperty(TimestampPropertyUpdate {
+                uid,
+                property_name: PropertyName {
+                    value: property_name,
+                },
+            })
+        }
+        Property::StringSetProp(_) => Update::StringSetProperty(StringSetPropertyUpdate {
+            uid,
+            property_name: PropertyName {
+                value: property_name,
+            },
+        }),
     }
 }
@@ -1,6 +1,18 @@
-use std::sync::Arc;
+use std::{
+    collections::BTreeSet,
+    sync::Arc,
+    time::{
+        SystemTime,
+        UNIX_EPOCH,
+    },
+};
 
+use bytes::Bytes;
 use grapl_utils::future_ext::GraplFutureExt;
+use reverse_edge_resolver::{
+    ReverseEdgeResolver,
+    ReverseEdgeResolverError,
+};
 use rust_proto::graplinc::grapl::{
     api::{
         client::ClientError,
@@ -27,6 +39,10 @@ use rust_proto::graplinc::grapl::{
     },
 };
 use scylla::{
+    frame::value::{
+        Timestamp,
+        Value,
+    },
     query::Query,
     CachingSession,
 };
@@ -34,18 +50,24 @@ use tracing::Instrument;
 use uid_allocator::client::CachingUidAllocatorClient as UidAllocatorClient;
 
 use crate::{
-    reverse_edge_resolver::{
-        ReverseEdgeResolver,
-        ReverseEdgeResolverError,
-    },
     table_names::{
+        IMM_BOOL_TABLE_NAME,
+        IMM_BYTES_TABLE_NAME,
+        IMM_F_64_TABLE_NAME,
+        IMM_I_64_INDEX_TABLE_NAME,
         IMM_I_64_TABLE_NAME,
+        IMM_STRING_INDEX_TABLE_NAME,
         IMM_STRING_TABLE_NAME,
+        IMM_TIMESTAMP_TABLE_NAME,
+        IMM_U_64_INDEX_TABLE_NAME,
         IMM_U_64_TABLE_NAME,
         MAX_I_64_TABLE_NAME,
+        MAX_TIMESTAMP_TABLE_NAME,
         MAX_U_64_TABLE_NAME,
         MIN_I_64_TABLE_NAME,
+        MIN_TIMESTAMP_TABLE_NAME,
         MIN_U_64_TABLE_NAME,
+        STRING_SET_TABLE_NAME,
     },
     write_dropper::WriteDropper,
 };
@@ -86,6 +108,15 @@ impl From<GraphMutationManagerError> for Status {
     }
 }
 
+/// Milliseconds since the unix epoch, the resolution of Scylla's `timestamp`
+/// type.
+fn timestamp_millis(timestamp: SystemTime) -> i64 {
+    match timestamp.duration_since(UNIX_EPOCH) {
+        Ok(since_epoch) => since_epoch.as_millis() as i64,
+        Err(e) => -(e.duration().as_millis() as i64),
+    }
+}
+
 pub struct GraphMutationManager {
     scylla_client: Arc<CachingSession>,
     uid_allocator_client: UidAllocatorClient,
@@ -108,6 +139,46 @@ impl GraphMutationManager {
         }
     }
 
+    /// Records `uid` under (node_type, property_name, value) in one of the
+    /// inverted property indexes, so that graph-query can search for nodes by
+    /// property value without already knowing a uid.
+    #[allow(clippy::too_many_arguments)]
+    async fn insert_into_property_index<'a, V: Value + 'a>(
+        &'a self,
+        index_table_name: &'static str,
+        tenant_id: uuid::Uuid,
+        uid: Uid,
+        node_type: &'a NodeType,
+        property_name: &'a PropertyName,
+        property_value: V,
+        insert_type: &'static str,
+    ) -> Result<(), GraphMutationManagerError> {
+        let query = Query::new(format!(
+            "INSERT INTO tenant_graph_ks.{index_table_name} \
+            (tenant_id, node_type, populated_field, value, uid) \
+            VALUES (?, ?, ?, ?, ?)"
+        ));
+
+        self.scylla_client
+            .execute(
+                query,
+                &(
+                    tenant_id,
+                    node_type.value.as_str(),
+                    property_name.value.as_str(),
+                    property_value,
+                    uid.as_i64(),
+                ),
+            )
+            .timeout(std::time::Duration::from_secs(3))
+            .await
+            .map_err(|_| GraphMutationManagerError::ScyllaInsertTimeout {
+                tenant_id,
+                insert_type,
+            })??;
+        Ok(())
+    }
+
     #[tracing::instrument(skip(self), err)]
     async fn upsert_max_u64(
         &self,
@@ -120,6 +191,7 @@ impl GraphMutationManager {
         self.write_dropper
             .check_max_u64(
                 tenant_id,
+                uid,
                 node_type.clone(),
                 property_name.clone(),
                 property_value,
@@ -157,6 +229,7 @@ impl GraphMutationManager {
         self.write_dropper
             .check_min_u64(
                 tenant_id,
+                uid,
                 node_type.clone(),
                 property_name.clone(),
                 property_value,
@@ -201,32 +274,52 @@ impl GraphMutationManager {
         property_value: u64,
     ) -> Result<(), GraphMutationManagerError> {
         self.write_dropper
-            .check_imm_u64(tenant_id, node_type.clone(), property_name.clone(), || {
-                async move {
-                    let property_value = property_value as i64;
-                    let query = Query::new(format!(
-                        r"
+            .check_imm_u64(
+                tenant_id,
+                uid,
+                node_type.clone(),
+                property_name.clone(),
+                || {
+                    async move {
+                        let property_value = property_value as i64;
+                        let query = Query::new(format!(
+                            r"
                         INSERT INTO tenant_graph_ks.{IMM_U_64_TABLE_NAME}
                         (tenant_id, uid, populated_field, value)
                         VALUES (?, ?, ?, ?)
                     "
-                    ));
+                        ));
 
-                    self.scylla_client
-                        .execute(
-                            query,
-                            &(tenant_id, uid.as_i64(), property_name.value, property_value),
+                        self.scylla_client
+                            .execute(
+                                query,
+                                &(
+                                    tenant_id,
+                                    uid.as_i64(),
+                                    property_name.value.as_str(),
+                                    property_value,
+                                ),
+                            )
+                            .timeout(std::time::Duration::from_secs(3))
+                            .await
+                            .map_err(|_| GraphMutationManagerError::ScyllaInsertTimeout {
+                                tenant_id,
+                                insert_type: "MAX_U_64",
+                            })??;
+                        self.insert_into_property_index(
+                            IMM_U_64_INDEX_TABLE_NAME,
+                            tenant_id,
+                            uid,
+                            &node_type,
+                            &property_name,
+                            property_value,
+                            "IMM_U_64_INDEX",
                         )
-                        .timeout(std::time::Duration::from_secs(3))
                         .await
-                        .map_err(|_| GraphMutationManagerError::ScyllaInsertTimeout {
-                            tenant_id,
-                            insert_type: "MAX_U_64",
-                        })??;
-                    Ok(())
-                }
-                .instrument(tracing::info_span!("upsert_max_u64"))
-            })
+                    }
+                    .instrument(tracing::info_span!("upsert_max_u64"))
+                },
+            )
             .await
             .map(|_| ())
     }
@@ -243,6 +336,7 @@ impl GraphMutationManager {
         self.write_dropper
             .check_max_i64(
                 tenant_id,
+                uid,
                 node_type.clone(),
                 property_name.clone(),
                 property_value,
@@ -289,6 +383,7 @@ impl GraphMutationManager {
         self.write_dropper
             .check_min_i64(
                 tenant_id,
+                uid,
                 node_type.clone(),
                 property_name.clone(),
                 property_value,
@@ -338,30 +433,50 @@ impl GraphMutationManager {
         property_value: i64,
     ) -> Result<(), GraphMutationManagerError> {
         self.write_dropper
-            .check_imm_i64(tenant_id, node_type.clone(), property_name.clone(), || {
-                async move {
-                    let query = Query::new(format!(
-                        "INSERT INTO tenant_graph_ks.{IMM_I_64_TABLE_NAME} \
+            .check_imm_i64(
+                tenant_id,
+                uid,
+                node_type.clone(),
+                property_name.clone(),
+                || {
+                    async move {
+                        let query = Query::new(format!(
+                            "INSERT INTO tenant_graph_ks.{IMM_I_64_TABLE_NAME} \
                         (tenant_id, uid, populated_field, value) \
                         VALUES (?, ?, ?, ?)\
                     "
-                    ));
+                        ));
 
-                    self.scylla_client
-                        .execute(
-                            query,
-                            &(tenant_id, uid.as_i64(), property_name.value, property_value),
+                        self.scylla_client
+                            .execute(
+                                query,
+                                &(
+                                    tenant_id,
+                                    uid.as_i64(),
+                                    property_name.value.as_str(),
+                                    property_value,
+                                ),
+                            )
+                            .timeout(std::time::Duration::from_secs(3))
+                            .await
+                            .map_err(|_| GraphMutationManagerError::ScyllaInsertTimeout {
+                                tenant_id,
+                                insert_type: "IMM_I_64",
+                            })??;
+                        self.insert_into_property_index(
+                            IMM_I_64_INDEX_TABLE_NAME,
+                            tenant_id,
+                            uid,
+                            &node_type,
+                            &property_name,
+                            property_value,
+                            "IMM_I_64_INDEX",
                         )
-                        .timeout(std::time::Duration::from_secs(3))
                         .await
-                        .map_err(|_| GraphMutationManagerError::ScyllaInsertTimeout {
-                            tenant_id,
-                            insert_type: "IMM_I_64",
-                        })??;
-                    Ok(())
-                }
-                .instrument(tracing::info_span!("upsert_imm_i64"))
-            })
+                    }
+                    .instrument(tracing::info_span!("upsert_imm_i64"))
+                },
+            )
             .await
             .map(|_| ())
     }
@@ -408,29 +523,382 @@ impl GraphMutationManager {
         property_value: String,
     ) -> Result<(), GraphMutationManagerError> {
         self.write_dropper
-            .check_imm_string(tenant_id, node_type.clone(), property_name.clone(), || {
-                async move {
-                    let query = Query::new(format!(
-                        "INSERT INTO tenant_graph_ks.{IMM_STRING_TABLE_NAME} \
+            .check_imm_string(
+                tenant_id,
+                uid,
+                node_type.clone(),
+                property_name.clone(),
+                || {
+                    async move {
+                        let query = Query::new(format!(
+                            "INSERT INTO tenant_graph_ks.{IMM_STRING_TABLE_NAME} \
                         (tenant_id, uid, populated_field, value) \
                         VALUES (?, ?, ?, ?)"
-                    ));
+                        ));
 
-                    self.scylla_client
-                        .execute(
-                            query,
-                            &(tenant_id, uid.as_i64(), property_name.value, property_value),
+                        self.scylla_client
+                            .execute(
+                                query,
+                                &(
+                                    tenant_id,
+                                    uid.as_i64(),
+                                    property_name.value.as_str(),
+                                    property_value.as_str(),
+                                ),
+                            )
+                            .timeout(std::time::Duration::from_secs(3))
+                            .await
+                            .map_err(|_| GraphMutationManagerError::ScyllaInsertTimeout {
+                                tenant_id,
+                                insert_type: "IMM_STRING",
+                            })??;
+                        self.insert_into_property_index(
+                            IMM_STRING_INDEX_TABLE_NAME,
+                            tenant_id,
+                            uid,
+                            &node_type,
+                            &property_name,
+                            property_value,
+                            "IMM_STRING_INDEX",
                         )
-                        .timeout(std::time::Duration::from_secs(3))
                         .await
-                        .map_err(|_| GraphMutationManagerError::ScyllaInsertTimeout {
-                            tenant_id,
-                            insert_type: "IMM_STRING",
-                        })??;
-                    Ok(())
-                }
-                .instrument(tracing::info_span!("upsert_imm_string"))
-            })
+                    }
+                    .instrument(tracing::info_span!("upsert_imm_string"))
+                },
+            )
+            .await
+            .map(|_| ())
+    }
+
+    #[tracing::instrument(skip(self), err)]
+    async fn upsert_immutable_bool(
+        &self,
+        tenant_id: uuid::Uuid,
+        uid: Uid,
+        node_type: NodeType,
+        property_name: PropertyName,
+        property_value: bool,
+    ) -> Result<(), GraphMutationManagerError> {
+        self.write_dropper
+            .check_imm_bool(
+                tenant_id,
+                uid,
+                node_type.clone(),
+                property_name.clone(),
+                || {
+                    async move {
+                        let query = Query::new(format!(
+                            "INSERT INTO tenant_graph_ks.{IMM_BOOL_TABLE_NAME} \
+                        (tenant_id, uid, populated_field, value) \
+                        VALUES (?, ?, ?, ?)"
+                        ));
+
+                        self.scylla_client
+                            .execute(
+                                query,
+                                &(tenant_id, uid.as_i64(), property_name.value, property_value),
+                            )
+                            .timeout(std::time::Duration::from_secs(3))
+                            .await
+                            .map_err(|_| GraphMutationManagerError::ScyllaInsertTimeout {
+                                tenant_id,
+                                insert_type: "IMM_BOOL",
+                            })??;
+                        Ok(())
+                    }
+                    .instrument(tracing::info_span!("upsert_imm_bool"))
+                },
+            )
+            .await
+            .map(|_| ())
+    }
+
+    #[tracing::instrument(skip(self), err)]
+    async fn upsert_immutable_f64(
+        &self,
+        tenant_id: uuid::Uuid,
+        uid: Uid,
+        node_type: NodeType,
+        property_name: PropertyName,
+        property_value: f64,
+    ) -> Result<(), GraphMutationManagerError> {
+        self.write_dropper
+            .check_imm_f64(
+                tenant_id,
+                uid,
+                node_type.clone(),
+                property_name.clone(),
+                || {
+                    async move {
+                        let query = Query::new(format!(
+                            "INSERT INTO tenant_graph_ks.{IMM_F_64_TABLE_NAME} \
+                        (tenant_id, uid, populated_field, value) \
+                        VALUES (?, ?, ?, ?)"
+                        ));
+
+                        self.scylla_client
+                            .execute(
+                                query,
+                                &(tenant_id, uid.as_i64(), property_name.value, property_value),
+                            )
+                            .timeout(std::time::Duration::from_secs(3))
+                            .await
+                            .map_err(|_| GraphMutationManagerError::ScyllaInsertTimeout {
+                                tenant_id,
+                                insert_type: "IMM_F_64",
+                            })??;
+                        Ok(())
+                    }
+                    .instrument(tracing::info_span!("upsert_imm_f64"))
+                },
+            )
+            .await
+            .map(|_| ())
+    }
+
+    #[tracing::instrument(skip(self), err)]
+    async fn upsert_immutable_bytes(
+        &self,
+        tenant_id: uuid::Uuid,
+        uid: Uid,
+        node_type: NodeType,
+        property_name: PropertyName,
+        property_value: Bytes,
+    ) -> Result<(), GraphMutationManagerError> {
+        self.write_dropper
+            .check_imm_bytes(
+                tenant_id,
+                uid,
+                node_type.clone(),
+                property_name.clone(),
+                || {
+                    async move {
+                        let query = Query::new(format!(
+                            "INSERT INTO tenant_graph_ks.{IMM_BYTES_TABLE_NAME} \
+                        (tenant_id, uid, populated_field, value) \
+                        VALUES (?, ?, ?, ?)"
+                        ));
+
+                        self.scylla_client
+                            .execute(
+                                query,
+                                &(
+                                    tenant_id,
+                                    uid.as_i64(),
+                                    property_name.value,
+                                    property_value.to_vec(),
+                                ),
+                            )
+                            .timeout(std::time::Duration::from_secs(3))
+                            .await
+                            .map_err(|_| GraphMutationManagerError::ScyllaInsertTimeout {
+                                tenant_id,
+                                insert_type: "IMM_BYTES",
+                            })??;
+                        Ok(())
+                    }
+                    .instrument(tracing::info_span!("upsert_imm_bytes"))
+                },
+            )
+            .await
+            .map(|_| ())
+    }
+
+    #[tracing::instrument(skip(self), err)]
+    async fn upsert_immutable_timestamp(
+        &self,
+        tenant_id: uuid::Uuid,
+        uid: Uid,
+        node_type: NodeType,
+        property_name: PropertyName,
+        property_value: SystemTime,
+    ) -> Result<(), GraphMutationManagerError> {
+        self.write_dropper
+            .check_imm_timestamp(
+                tenant_id,
+                uid,
+                node_type.clone(),
+                property_name.clone(),
+                || {
+                    async move {
+                        let property_value =
+                            chrono::Duration::milliseconds(timestamp_millis(property_value));
+                        let query = Query::new(format!(
+                            "INSERT INTO tenant_graph_ks.{IMM_TIMESTAMP_TABLE_NAME} \
+                        (tenant_id, uid, populated_field, value) \
+                        VALUES (?, ?, ?, ?)"
+                        ));
+
+                        self.scylla_client
+                            .execute(
+                                query,
+                                &(
+                                    tenant_id,
+                                    uid.as_i64(),
+                                    property_name.value,
+                                    Timestamp(property_value),
+                                ),
+                            )
+                            .timeout(std::time::Duration::from_secs(3))
+                            .await
+                            .map_err(|_| GraphMutationManagerError::ScyllaInsertTimeout {
+                                tenant_id,
+                                insert_type: "IMM_TIMESTAMP",
+                            })??;
+                        Ok(())
+                    }
+                    .instrument(tracing::info_span!("upsert_imm_timestamp"))
+                },
+            )
+            .await
+            .map(|_| ())
+    }
+
+    #[tracing::instrument(skip(self), err)]
+    async fn upsert_max_timestamp(
+        &self,
+        tenant_id: uuid::Uuid,
+        uid: Uid,
+        node_type: NodeType,
+        property_name: PropertyName,
+        property_value: SystemTime,
+    ) -> Result<(), GraphMutationManagerError> {
+        self.write_dropper
+            .check_max_timestamp(
+                tenant_id,
+                uid,
+                node_type.clone(),
+                property_name.clone(),
+                property_value,
+                || {
+                    async move {
+                        let property_value = timestamp_millis(property_value);
+                        let mut query = Query::new(format!(
+                            "INSERT INTO tenant_graph_ks.{MAX_TIMESTAMP_TABLE_NAME} \
+                            (tenant_id, uid, populated_field, value) \
+                            VALUES (?, ?, ?, ?)"
+                        ));
+                        query.set_timestamp(Some(property_value));
+
+                        self.scylla_client
+                            .execute(
+                                query,
+                                &(
+                                    tenant_id,
+                                    uid.as_i64(),
+                                    property_name.value,
+                                    Timestamp(chrono::Duration::milliseconds(property_value)),
+                                ),
+                            )
+                            .timeout(std::time::Duration::from_secs(3))
+                            .await
+                            .map_err(|_| GraphMutationManagerError::ScyllaInsertTimeout {
+                                tenant_id,
+                                insert_type: "MAX_TIMESTAMP",
+                            })??;
+                        Ok(())
+                    }
+                    .instrument(tracing::info_span!("upsert_max_timestamp"))
+                },
+            )
+            .await
+            .map(|_| ())
+    }
+
+    #[tracing::instrument(skip(self), err)]
+    async fn upsert_min_timestamp(
+        &self,
+        tenant_id: uuid::Uuid,
+        uid: Uid,
+        node_type: NodeType,
+        property_name: PropertyName,
+        property_value: SystemTime,
+    ) -> Result<(), GraphMutationManagerError> {
+        self.write_dropper
+            .check_min_timestamp(
+                tenant_id,
+                uid,
+                node_type.clone(),
+                property_name.clone(),
+                property_value,
+                || {
+                    async move {
+                        let property_value = timestamp_millis(property_value);
+                        let mut query = Query::new(format!(
+                            "INSERT INTO tenant_graph_ks.{MIN_TIMESTAMP_TABLE_NAME} \
+                            (tenant_id, uid, populated_field, value) \
+                            VALUES (?, ?, ?, ?)"
+                        ));
+                        query.set_timestamp(Some(-property_value));
+
+                        self.scylla_client
+                            .execute(
+                                query,
+                                &(
+                                    tenant_id,
+                                    uid.as_i64(),
+                                    property_name.value,
+                                    Timestamp(chrono::Duration::milliseconds(property_value)),
+                                ),
+                            )
+                            .timeout(std::time::Duration::from_secs(3))
+                            .await
+                            .map_err(|_| GraphMutationManagerError::ScyllaInsertTimeout {
+                                tenant_id,
+                                insert_type: "MIN_TIMESTAMP",
+                            })??;
+                        Ok(())
+                    }
+                    .instrument(tracing::info_span!("upsert_min_timestamp"))
+                },
+            )
+            .await
+            .map(|_| ())
+    }
+
+    /// String sets are only ever added to, so concurrent writers can't clobber
+    /// each other's members.
+    #[tracing::instrument(skip(self), err)]
+    async fn upsert_string_set(
+        &self,
+        tenant_id: uuid::Uuid,
+        uid: Uid,
+        node_type: NodeType,
+        property_name: PropertyName,
+        property_value: BTreeSet<String>,
+    ) -> Result<(), GraphMutationManagerError> {
+        self.write_dropper
+            .check_string_set(
+                tenant_id,
+                uid,
+                node_type.clone(),
+                property_name.clone(),
+                property_value.clone(),
+                || {
+                    async move {
+                        let members: Vec<String> = property_value.into_iter().collect();
+                        let query = Query::new(format!(
+                            "UPDATE tenant_graph_ks.{STRING_SET_TABLE_NAME} \
+                            SET value = value + ? \
+                            WHERE tenant_id = ? AND uid = ? AND populated_field = ?"
+                        ));
+
+                        self.scylla_client
+                            .execute(
+                                query,
+                                &(members, tenant_id, uid.as_i64(), property_name.value),
+                            )
+                            .timeout(std::time::Duration::from_secs(3))
+                            .await
+                            .map_err(|_| GraphMutationManagerError::ScyllaInsertTimeout {
+                                tenant_id,
+                                insert_type: "STRING_SET",
+                            })??;
+                        Ok(())
+                    }
+                    .instrument(tracing::info_span!("upsert_string_set"))
+                },
+            )
             .await
             .map(|_| ())
     }
@@ -595,6 +1063,46 @@ impl GraphMutationApi for GraphMutationManager {
                 )
                 .await?;
             }
+            Property::ImmutableBoolProp(property) => {
+                self.upsert_immutable_bool(tenant_id, uid, node_type, property_name, property.prop)
+                    .await?;
+            }
+            Property::ImmutableDoubleProp(property) => {
+                self.upsert_immutable_f64(tenant_id, uid, node_type, property_name, property.prop)
+                    .await?;
+            }
+            Property::ImmutableBytesProp(property) => {
+                self.upsert_immutable_bytes(
+                    tenant_id,
+                    uid,
+                    node_type,
+                    property_name,
+                    property.prop,
+                )
+                .await?;
+            }
+            Property::ImmutableTimestampProp(property) => {
+                self.upsert_immutable_timestamp(
+                    tenant_id,
+                    uid,
+                    node_type,
+                    property_name,
+                    property.prop,
+                )
+                .await?;
+            }
+            Property::IncrementOnlyTimestampProp(property) => {
+                self.upsert_max_timestamp(tenant_id, uid, node_type, property_name, property.prop)
+                    .await?;
+            }
+            Property::DecrementOnlyTimestampProp(property) => {
+                self.upsert_min_timestamp(tenant_id, uid, node_type, property_name, property.prop)
+                    .await?;
+            }
+            Property::StringSetProp(property) => {
+                self.upsert_string_set(tenant_id, uid, node_type, property_name, property.prop)
+                    .await?;
+            }
         };
 
         Ok(SetNodePropertyResponse {
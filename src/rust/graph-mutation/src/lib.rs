@@ -1,5 +1,4 @@
 pub mod config;
 pub mod graph_mutation;
-pub mod reverse_edge_resolver;
 pub mod table_names;
 pub mod write_dropper;
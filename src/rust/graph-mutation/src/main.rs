@@ -12,8 +12,8 @@ use figment::{
 use graph_mutation::{
     config::GraphMutationServiceConfig,
     graph_mutation::GraphMutationManager,
-    reverse_edge_resolver::ReverseEdgeResolver,
 };
+use reverse_edge_resolver::ReverseEdgeResolver;
 use rust_proto::graplinc::grapl::api::{
     client::Connect,
     graph_mutation::v1beta1::server::GraphMutationServer,
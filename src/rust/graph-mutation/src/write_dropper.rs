@@ -1,6 +1,8 @@
 use std::{
+    collections::BTreeSet,
     future::Future,
     hash::Hash,
+    time::SystemTime,
 };
 
 use blake2::{
@@ -24,9 +26,13 @@ struct NodeTypeKey {
     uid: Uid,
 }
 
+/// Identifies one node's property. Writes are only dropped when the same
+/// node has already written the property: keying on the node type alone
+/// would drop a write for every node of a type after the first.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 struct PropertyKey {
     tenant_id: uuid::Uuid,
+    uid: Uid,
     node_type: NodeType,
     property_name: PropertyName,
 }
@@ -46,6 +52,9 @@ pub enum WriteDropStatus {
 /// EXAMPLE 2: Max u64/i64 (aka IncrOnly)
 ///   If you have a property that can only increment, and we've previously
 ///   written 5 to the DB, there's no reason to write a 4 if we encounter it.
+/// EXAMPLE 3: String set
+///   A string set only grows, so writing members we've already written
+///   can't change it.
 #[derive(Clone, Debug)]
 pub struct WriteDropper {
     max_i64: Cache<PropertyKey, i64>,
@@ -55,6 +64,13 @@ pub struct WriteDropper {
     min_u64: Cache<PropertyKey, u64>,
     imm_u64: Cache<PropertyKey, ()>,
     imm_string: Cache<PropertyKey, ()>,
+    imm_bool: Cache<PropertyKey, ()>,
+    imm_f64: Cache<PropertyKey, ()>,
+    imm_bytes: Cache<PropertyKey, ()>,
+    imm_timestamp: Cache<PropertyKey, ()>,
+    max_timestamp: Cache<PropertyKey, SystemTime>,
+    min_timestamp: Cache<PropertyKey, SystemTime>,
+    string_set: Cache<PropertyKey, BTreeSet<String>>,
     node_type: Cache<NodeTypeKey, ()>,
     edges: Cache<[u8; 16], ()>,
 }
@@ -69,6 +85,13 @@ impl WriteDropper {
             min_u64: Cache::new(max_size),
             imm_u64: Cache::new(max_size),
             imm_string: Cache::new(max_size),
+            imm_bool: Cache::new(max_size),
+            imm_f64: Cache::new(max_size),
+            imm_bytes: Cache::new(max_size),
+            imm_timestamp: Cache::new(max_size),
+            max_timestamp: Cache::new(max_size),
+            min_timestamp: Cache::new(max_size),
+            string_set: Cache::new(max_size),
             node_type: Cache::new(max_size),
             edges: Cache::new(max_size),
         }
@@ -78,6 +101,7 @@ impl WriteDropper {
     pub async fn check_max_i64<T, E, Fut>(
         &self,
         tenant_id: uuid::Uuid,
+        uid: Uid,
         node_type: NodeType,
         property_name: PropertyName,
         value: i64,
@@ -89,6 +113,7 @@ impl WriteDropper {
     {
         let key = PropertyKey {
             tenant_id,
+            uid,
             node_type,
             property_name,
         };
@@ -102,6 +127,7 @@ impl WriteDropper {
     pub async fn check_min_i64<T, E, Fut>(
         &self,
         tenant_id: uuid::Uuid,
+        uid: Uid,
         node_type: NodeType,
         property_name: PropertyName,
         value: i64,
@@ -113,6 +139,7 @@ impl WriteDropper {
     {
         let key = PropertyKey {
             tenant_id,
+            uid,
             node_type,
             property_name,
         };
@@ -125,6 +152,7 @@ impl WriteDropper {
     pub async fn check_imm_i64<T, E, Fut>(
         &self,
         tenant_id: uuid::Uuid,
+        uid: Uid,
         node_type: NodeType,
         property_name: PropertyName,
         callback: impl FnOnce() -> Fut,
@@ -135,6 +163,7 @@ impl WriteDropper {
     {
         let key = PropertyKey {
             tenant_id,
+            uid,
             node_type,
             property_name,
         };
@@ -145,6 +174,7 @@ impl WriteDropper {
     pub async fn check_max_u64<T, E, Fut>(
         &self,
         tenant_id: uuid::Uuid,
+        uid: Uid,
         node_type: NodeType,
         property_name: PropertyName,
         value: u64,
@@ -156,6 +186,7 @@ impl WriteDropper {
     {
         let key = PropertyKey {
             tenant_id,
+            uid,
             node_type,
             property_name,
         };
@@ -169,6 +200,7 @@ impl WriteDropper {
     pub async fn check_min_u64<T, E, Fut>(
         &self,
         tenant_id: uuid::Uuid,
+        uid: Uid,
         node_type: NodeType,
         property_name: PropertyName,
         value: u64,
@@ -180,6 +212,7 @@ impl WriteDropper {
     {
         let key = PropertyKey {
             tenant_id,
+            uid,
             node_type,
             property_name,
         };
@@ -193,6 +226,7 @@ impl WriteDropper {
     pub async fn check_imm_u64<T, E, Fut>(
         &self,
         tenant_id: uuid::Uuid,
+        uid: Uid,
         node_type: NodeType,
         property_name: PropertyName,
         callback: impl FnOnce() -> Fut,
@@ -203,6 +237,7 @@ impl WriteDropper {
     {
         let key = PropertyKey {
             tenant_id,
+            uid,
             node_type,
             property_name,
         };
@@ -213,6 +248,7 @@ impl WriteDropper {
     pub async fn check_imm_string<T, E, Fut>(
         &self,
         tenant_id: uuid::Uuid,
+        uid: Uid,
         node_type: NodeType,
         property_name: PropertyName,
         callback: impl FnOnce() -> Fut,
@@ -223,12 +259,187 @@ impl WriteDropper {
     {
         let key = PropertyKey {
             tenant_id,
+            uid,
             node_type,
             property_name,
         };
         get_or_insert_into_cache(self.imm_string.clone(), key, (), callback, |_, _| false).await
     }
 
+    #[tracing::instrument(skip(self, callback), err)]
+    pub async fn check_imm_bool<T, E, Fut>(
+        &self,
+        tenant_id: uuid::Uuid,
+        uid: Uid,
+        node_type: NodeType,
+        property_name: PropertyName,
+        callback: impl FnOnce() -> Fut,
+    ) -> Result<WriteDropStatus, E>
+    where
+        Fut: Future<Output = Result<T, E>>,
+        E: std::error::Error,
+    {
+        let key = PropertyKey {
+            tenant_id,
+            uid,
+            node_type,
+            property_name,
+        };
+        get_or_insert_into_cache(self.imm_bool.clone(), key, (), callback, |_, _| false).await
+    }
+
+    #[tracing::instrument(skip(self, callback), err)]
+    pub async fn check_imm_f64<T, E, Fut>(
+        &self,
+        tenant_id: uuid::Uuid,
+        uid: Uid,
+        node_type: NodeType,
+        property_name: PropertyName,
+        callback: impl FnOnce() -> Fut,
+    ) -> Result<WriteDropStatus, E>
+    where
+        Fut: Future<Output = Result<T, E>>,
+        E: std::error::Error,
+    {
+        let key = PropertyKey {
+            tenant_id,
+            uid,
+            node_type,
+            property_name,
+        };
+        get_or_insert_into_cache(self.imm_f64.clone(), key, (), callback, |_, _| false).await
+    }
+
+    #[tracing::instrument(skip(self, callback), err)]
+    pub async fn check_imm_bytes<T, E, Fut>(
+        &self,
+        tenant_id: uuid::Uuid,
+        uid: Uid,
+        node_type: NodeType,
+        property_name: PropertyName,
+        callback: impl FnOnce() -> Fut,
+    ) -> Result<WriteDropStatus, E>
+    where
+        Fut: Future<Output = Result<T, E>>,
+        E: std::error::Error,
+    {
+        let key = PropertyKey {
+            tenant_id,
+            uid,
+            node_type,
+            property_name,
+        };
+        get_or_insert_into_cache(self.imm_bytes.clone(), key, (), callback, |_, _| false).await
+    }
+
+    #[tracing::instrument(skip(self, callback), err)]
+    pub async fn check_imm_timestamp<T, E, Fut>(
+        &self,
+        tenant_id: uuid::Uuid,
+        uid: Uid,
+        node_type: NodeType,
+        property_name: PropertyName,
+        callback: impl FnOnce() -> Fut,
+    ) -> Result<WriteDropStatus, E>
+    where
+        Fut: Future<Output = Result<T, E>>,
+        E: std::error::Error,
+    {
+        let key = PropertyKey {
+            tenant_id,
+            uid,
+            node_type,
+            property_name,
+        };
+        get_or_insert_into_cache(self.imm_timestamp.clone(), key, (), callback, |_, _| false).await
+    }
+
+    #[tracing::instrument(skip(self, callback), err)]
+    pub async fn check_max_timestamp<T, E, Fut>(
+        &self,
+        tenant_id: uuid::Uuid,
+        uid: Uid,
+        node_type: NodeType,
+        property_name: PropertyName,
+        value: SystemTime,
+        callback: impl FnOnce() -> Fut,
+    ) -> Result<WriteDropStatus, E>
+    where
+        Fut: Future<Output = Result<T, E>>,
+        E: std::error::Error,
+    {
+        let key = PropertyKey {
+            tenant_id,
+            uid,
+            node_type,
+            property_name,
+        };
+        get_or_insert_into_cache(
+            self.max_timestamp.clone(),
+            key,
+            value,
+            callback,
+            |new, old| new > old,
+        )
+        .await
+    }
+
+    #[tracing::instrument(skip(self, callback), err)]
+    pub async fn check_min_timestamp<T, E, Fut>(
+        &self,
+        tenant_id: uuid::Uuid,
+        uid: Uid,
+        node_type: NodeType,
+        property_name: PropertyName,
+        value: SystemTime,
+        callback: impl FnOnce() -> Fut,
+    ) -> Result<WriteDropStatus, E>
+    where
+        Fut: Future<Output = Result<T, E>>,
+        E: std::error::Error,
+    {
+        let key = PropertyKey {
+            tenant_id,
+            uid,
+            node_type,
+            property_name,
+        };
+        get_or_insert_into_cache(
+            self.min_timestamp.clone(),
+            key,
+            value,
+            callback,
+            |new, old| new < old,
+        )
+        .await
+    }
+
+    #[tracing::instrument(skip(self, callback), err)]
+    pub async fn check_string_set<T, E, Fut>(
+        &self,
+        tenant_id: uuid::Uuid,
+        uid: Uid,
+        node_type: NodeType,
+        property_name: PropertyName,
+        value: BTreeSet<String>,
+        callback: impl FnOnce() -> Fut,
+    ) -> Result<WriteDropStatus, E>
+    where
+        Fut: Future<Output = Result<T, E>>,
+        E: std::error::Error,
+    {
+        let key = PropertyKey {
+            tenant_id,
+            uid,
+            node_type,
+            property_name,
+        };
+        get_or_insert_into_cache(self.string_set.clone(), key, value, callback, |new, old| {
+            !new.is_subset(old)
+        })
+        .await
+    }
+
     #[tracing::instrument(skip(self, callback), err)]
     pub async fn check_node_type<T, E, Fut>(
         &self,
@@ -330,9 +541,14 @@ mod tests {
     #[derive(Debug, thiserror::Error)]
     enum CallbackError {}
 
+    fn at(seconds: u64) -> SystemTime {
+        std::time::UNIX_EPOCH + std::time::Duration::from_secs(seconds)
+    }
+
     #[tokio::test]
     async fn test_every_cache_drops_when_expected() -> eyre::Result<()> {
         let tenant_id = uuid::Uuid::new_v4();
+        let uid = Uid::from_u64(1).unwrap();
         let node_type = NodeType {
             value: "arbitrary_node_type".to_string(),
         };
@@ -357,7 +573,7 @@ mod tests {
                 let pn = pn.clone();
                 async move {
                     write_dropper
-                        .check_max_i64(tenant_id, nt, pn, value, callback)
+                        .check_max_i64(tenant_id, uid, nt, pn, value, callback)
                         .await
                 }
             };
@@ -383,7 +599,7 @@ mod tests {
                 let pn = pn.clone();
                 async move {
                     write_dropper
-                        .check_min_i64(tenant_id, nt, pn, value, callback)
+                        .check_min_i64(tenant_id, uid, nt, pn, value, callback)
                         .await
                 }
             };
@@ -409,7 +625,7 @@ mod tests {
                 let pn = pn.clone();
                 async move {
                     write_dropper
-                        .check_imm_i64(tenant_id, nt, pn, callback)
+                        .check_imm_i64(tenant_id, uid, nt, pn, callback)
                         .await
                 }
             };
@@ -431,7 +647,7 @@ mod tests {
                 let pn = pn.clone();
                 async move {
                     write_dropper
-                        .check_max_u64(tenant_id, nt, pn, value, callback)
+                        .check_max_u64(tenant_id, uid, nt, pn, value, callback)
                         .await
                 }
             };
@@ -457,7 +673,7 @@ mod tests {
                 let pn = pn.clone();
                 async move {
                     write_dropper
-                        .check_min_u64(tenant_id, nt, pn, value, callback)
+                        .check_min_u64(tenant_id, uid, nt, pn, value, callback)
                         .await
                 }
             };
@@ -483,7 +699,7 @@ mod tests {
                 let pn = pn.clone();
                 async move {
                     write_dropper
-                        .check_imm_u64(tenant_id, nt, pn, callback)
+                        .check_imm_u64(tenant_id, uid, nt, pn, callback)
                         .await
                 }
             };
@@ -505,7 +721,69 @@ mod tests {
                 let pn = pn.clone();
                 async move {
                     write_dropper
-                        .check_imm_string(tenant_id, nt, pn, callback)
+                        .check_imm_string(tenant_id, uid, nt, pn, callback)
+                        .await
+                }
+            };
+
+            let status = check().await?;
+            eyre::ensure!(status == WriteDropStatus::Stored, "initial always stores");
+            let status = check().await?;
+            eyre::ensure!(status == WriteDropStatus::Dropped, "immutable");
+        }
+
+        // ##### check_imm_string on another node #####
+        {
+            let other_uid = Uid::from_u64(2).unwrap();
+            let status = write_dropper
+                .check_imm_string(
+                    tenant_id,
+                    other_uid,
+                    node_type.clone(),
+                    property_name.clone(),
+                    callback,
+                )
+                .await?;
+            eyre::ensure!(
+                status == WriteDropStatus::Stored,
+                "another node's property always stores"
+            );
+        }
+
+        // ##### check_imm_bool #####
+        {
+            let write_dropper = Arc::clone(&write_dropper);
+            let nt = node_type.clone();
+            let pn = property_name.clone();
+            let check = move || {
+                let write_dropper = Arc::clone(&write_dropper);
+                let nt = nt.clone();
+                let pn = pn.clone();
+                async move {
+                    write_dropper
+                        .check_imm_bool(tenant_id, uid, nt, pn, callback)
+                        .await
+                }
+            };
+
+            let status = check().await?;
+            eyre::ensure!(status == WriteDropStatus::Stored, "initial always stores");
+            let status = check().await?;
+            eyre::ensure!(status == WriteDropStatus::Dropped, "immutable");
+        }
+
+        // ##### check_imm_f64 #####
+        {
+            let write_dropper = Arc::clone(&write_dropper);
+            let nt = node_type.clone();
+            let pn = property_name.clone();
+            let check = move || {
+                let write_dropper = Arc::clone(&write_dropper);
+                let nt = nt.clone();
+                let pn = pn.clone();
+                async move {
+                    write_dropper
+                        .check_imm_f64(tenant_id, uid, nt, pn, callback)
                         .await
                 }
             };
@@ -516,6 +794,129 @@ mod tests {
             eyre::ensure!(status == WriteDropStatus::Dropped, "immutable");
         }
 
+        // ##### check_imm_bytes #####
+        {
+            let write_dropper = Arc::clone(&write_dropper);
+            let nt = node_type.clone();
+            let pn = property_name.clone();
+            let check = move || {
+                let write_dropper = Arc::clone(&write_dropper);
+                let nt = nt.clone();
+                let pn = pn.clone();
+                async move {
+                    write_dropper
+                        .check_imm_bytes(tenant_id, uid, nt, pn, callback)
+                        .await
+                }
+            };
+
+            let status = check().await?;
+            eyre::ensure!(status == WriteDropStatus::Stored, "initial always stores");
+            let status = check().await?;
+            eyre::ensure!(status == WriteDropStatus::Dropped, "immutable");
+        }
+
+        // ##### check_imm_timestamp #####
+        {
+            let write_dropper = Arc::clone(&write_dropper);
+            let nt = node_type.clone();
+            let pn = property_name.clone();
+            let check = move || {
+                let write_dropper = Arc::clone(&write_dropper);
+                let nt = nt.clone();
+                let pn = pn.clone();
+                async move {
+                    write_dropper
+                        .check_imm_timestamp(tenant_id, uid, nt, pn, callback)
+                        .await
+                }
+            };
+
+            let status = check().await?;
+            eyre::ensure!(status == WriteDropStatus::Stored, "initial always stores");
+            let status = check().await?;
+            eyre::ensure!(status == WriteDropStatus::Dropped, "immutable");
+        }
+
+        // ##### check_max_timestamp #####
+        {
+            let write_dropper = Arc::clone(&write_dropper);
+            let nt = node_type.clone();
+            let pn = property_name.clone();
+            let check = move |value| {
+                let write_dropper = Arc::clone(&write_dropper);
+                let nt = nt.clone();
+                let pn = pn.clone();
+                async move {
+                    write_dropper
+                        .check_max_timestamp(tenant_id, uid, nt, pn, value, callback)
+                        .await
+                }
+            };
+
+            let status = check(at(3)).await?;
+            eyre::ensure!(status == WriteDropStatus::Stored, "initial always stores");
+            let status = check(at(3)).await?;
+            eyre::ensure!(status == WriteDropStatus::Dropped, "same value, drop it");
+            let status = check(at(2)).await?;
+            eyre::ensure!(status == WriteDropStatus::Dropped, "earlier value, drop it");
+            let status = check(at(4)).await?;
+            eyre::ensure!(status == WriteDropStatus::Stored, "later value, store it");
+        }
+
+        // ##### check_min_timestamp #####
+        {
+            let write_dropper = Arc::clone(&write_dropper);
+            let nt = node_type.clone();
+            let pn = property_name.clone();
+            let check = move |value| {
+                let write_dropper = Arc::clone(&write_dropper);
+                let nt = nt.clone();
+                let pn = pn.clone();
+                async move {
+                    write_dropper
+                        .check_min_timestamp(tenant_id, uid, nt, pn, value, callback)
+                        .await
+                }
+            };
+
+            let status = check(at(3)).await?;
+            eyre::ensure!(status == WriteDropStatus::Stored, "initial always stores");
+            let status = check(at(3)).await?;
+            eyre::ensure!(status == WriteDropStatus::Dropped, "same value, drop it");
+            let status = check(at(4)).await?;
+            eyre::ensure!(status == WriteDropStatus::Dropped, "later value, drop it");
+            let status = check(at(2)).await?;
+            eyre::ensure!(status == WriteDropStatus::Stored, "earlier value, store it");
+        }
+
+        // ##### check_string_set #####
+        {
+            let write_dropper = Arc::clone(&write_dropper);
+            let nt = node_type.clone();
+            let pn = property_name.clone();
+            let check = move |members: &[&str]| {
+                let write_dropper = Arc::clone(&write_dropper);
+                let nt = nt.clone();
+                let pn = pn.clone();
+                let value = members.iter().map(|member| member.to_string()).collect();
+                async move {
+                    write_dropper
+                        .check_string_set(tenant_id, uid, nt, pn, value, callback)
+                        .await
+                }
+            };
+
+            let status = check(&["a", "b"]).await?;
+            eyre::ensure!(status == WriteDropStatus::Stored, "initial always stores");
+            let status = check(&["a", "b"]).await?;
+            eyre::ensure!(status == WriteDropStatus::Dropped, "same members, drop it");
+            let status = check(&["b"]).await?;
+            eyre::ensure!(status == WriteDropStatus::Dropped, "fewer members, drop it");
+            let status = check(&["b", "c"]).await?;
+            eyre::ensure!(status == WriteDropStatus::Stored, "new member, store it");
+        }
+
         // ##### check_node_type #####
         {
             let write_dropper = Arc::clone(&write_dropper);
@@ -588,4 +989,86 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_same_property_on_two_nodes_is_written_for_both() -> eyre::Result<()> {
+        let tenant_id = uuid::Uuid::new_v4();
+        let node_type = NodeType {
+            value: "arbitrary_node_type".to_string(),
+        };
+        let property_name = PropertyName {
+            value: "arbitrary_prop_name".to_string(),
+        };
+
+        let callback = || async {
+            let res: Result<(), CallbackError> = Ok(());
+            res
+        };
+        let write_dropper = WriteDropper::new(100);
+
+        for uid in [Uid::from_u64(1).unwrap(), Uid::from_u64(2).unwrap()] {
+            let nt = || node_type.clone();
+            let pn = || property_name.clone();
+            let statuses = [
+                write_dropper
+                    .check_max_i64(tenant_id, uid, nt(), pn(), 3, callback)
+                    .await?,
+                write_dropper
+                    .check_min_i64(tenant_id, uid, nt(), pn(), 3, callback)
+                    .await?,
+                write_dropper
+                    .check_imm_i64(tenant_id, uid, nt(), pn(), callback)
+                    .await?,
+                write_dropper
+                    .check_max_u64(tenant_id, uid, nt(), pn(), 3, callback)
+                    .await?,
+                write_dropper
+                    .check_min_u64(tenant_id, uid, nt(), pn(), 3, callback)
+                    .await?,
+                write_dropper
+                    .check_imm_u64(tenant_id, uid, nt(), pn(), callback)
+                    .await?,
+                write_dropper
+                    .check_imm_string(tenant_id, uid, nt(), pn(), callback)
+                    .await?,
+                write_dropper
+                    .check_imm_bool(tenant_id, uid, nt(), pn(), callback)
+                    .await?,
+                write_dropper
+                    .check_imm_f64(tenant_id, uid, nt(), pn(), callback)
+                    .await?,
+                write_dropper
+                    .check_imm_bytes(tenant_id, uid, nt(), pn(), callback)
+                    .await?,
+                write_dropper
+                    .check_imm_timestamp(tenant_id, uid, nt(), pn(), callback)
+                    .await?,
+                write_dropper
+                    .check_max_timestamp(tenant_id, uid, nt(), pn(), at(3), callback)
+                    .await?,
+                write_dropper
+                    .check_min_timestamp(tenant_id, uid, nt(), pn(), at(3), callback)
+                    .await?,
+                write_dropper
+                    .check_string_set(
+                        tenant_id,
+                        uid,
+                        nt(),
+                        pn(),
+                        BTreeSet::from(["a".to_string()]),
+                        callback,
+                    )
+                    .await?,
+            ];
+
+            eyre::ensure!(
+                statuses
+                    .iter()
+                    .all(|status| *status == WriteDropStatus::Stored),
+                "the first write of a property to {uid:?} must be stored, even though another node of the same type wrote it",
+            );
+        }
+
+        Ok(())
+    }
 }
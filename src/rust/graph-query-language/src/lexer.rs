@@ -0,0 +1,149 @@
+use super::{
+    QueryLanguageError,
+    Span,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum Token<'a> {
+    OpenParen,
+    CloseParen,
+    OpenBracket,
+    CloseBracket,
+    OpenBrace,
+    CloseBrace,
+    Colon,
+    Comma,
+    Dot,
+    DotDot,
+    Star,
+    Tilde,
+    Dash,
+    /// `->`
+    RightArrow,
+    /// `<-`
+    LeftArrow,
+    Eq,
+    NotEq,
+    Lt,
+    Lte,
+    Gt,
+    Gte,
+    /// `=~`
+    RegexMatch,
+    Identifier(&'a str),
+    String(String),
+    Integer(u64),
+}
+
+impl<'a> Token<'a> {
+    pub(super) fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self, Token::Identifier(word) if word.eq_ignore_ascii_case(keyword))
+    }
+}
+
+pub(super) fn tokenize(input: &str) -> Result<Vec<(Span, Token<'_>)>, QueryLanguageError> {
+    let mut tokens = Vec::new();
+    let mut chars = input.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        let mut next_is = |expected: char| chars.next_if(|(_, next)| *next == expected).is_some();
+
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '(' => Token::OpenParen,
+            ')' => Token::CloseParen,
+            '[' => Token::OpenBracket,
+            ']' => Token::CloseBracket,
+            '{' => Token::OpenBrace,
+            '}' => Token::CloseBrace,
+            ':' => Token::Colon,
+            ',' => Token::Comma,
+            '*' => Token::Star,
+            '~' => Token::Tilde,
+            '.' if next_is('.') => Token::DotDot,
+            '.' => Token::Dot,
+            '-' if next_is('>') => Token::RightArrow,
+            '-' => Token::Dash,
+            '<' if next_is('-') => Token::LeftArrow,
+            '<' if next_is('>') => Token::NotEq,
+            '<' if next_is('=') => Token::Lte,
+            '<' => Token::Lt,
+            '>' if next_is('=') => Token::Gte,
+            '>' => Token::Gt,
+            '=' if next_is('~') => Token::RegexMatch,
+            '=' => Token::Eq,
+            '"' => {
+                let (end, value) = lex_string(input, start, &mut chars)?;
+                tokens.push((Span::new(start, end), Token::String(value)));
+                continue;
+            }
+            c if c.is_ascii_digit() => {
+                let mut end = start + 1;
+                while let Some((i, _)) = chars.next_if(|(_, c)| c.is_ascii_digit()) {
+                    end = i + 1;
+                }
+                let value = input[start..end].parse().map_err(|_| {
+                    QueryLanguageError::new("integer is too large", Span::new(start, end))
+                })?;
+                tokens.push((Span::new(start, end), Token::Integer(value)));
+                continue;
+            }
+            c if c == '_' || c.is_ascii_alphabetic() => {
+                let mut end = start + 1;
+                while let Some((i, _)) =
+                    chars.next_if(|(_, c)| *c == '_' || c.is_ascii_alphanumeric())
+                {
+                    end = i + 1;
+                }
+                tokens.push((Span::new(start, end), Token::Identifier(&input[start..end])));
+                continue;
+            }
+            c => {
+                return Err(QueryLanguageError::new(
+                    format!("unexpected character {c:?}"),
+                    Span::new(start, start + c.len_utf8()),
+                ))
+            }
+        };
+
+        let end = chars.peek().map_or(input.len(), |(i, _)| *i);
+        tokens.push((Span::new(start, end), token));
+    }
+
+    Ok(tokens)
+}
+
+/// Lexes a double-quoted string starting at `start`, returning the offset
+/// just past its closing quote and its unescaped value.
+fn lex_string(
+    input: &str,
+    start: usize,
+    chars: &mut std::iter::Peekable<std::str::CharIndices<'_>>,
+) -> Result<(usize, String), QueryLanguageError> {
+    let mut value = String::new();
+
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Ok((i + 1, value)),
+            '\\' => match chars.next() {
+                Some((_, '"')) => value.push('"'),
+                Some((_, '\\')) => value.push('\\'),
+                Some((_, 'n')) => value.push('\n'),
+                Some((_, 't')) => value.push('\t'),
+                Some((j, c)) => {
+                    return Err(QueryLanguageError::new(
+                        format!("unknown escape \\{c}"),
+                        Span::new(i, j + c.len_utf8()),
+                    ))
+                }
+                None => break,
+            },
+            c => value.push(c),
+        }
+    }
+
+    Err(QueryLanguageError::new(
+        "string is never closed",
+        Span::new(start, input.len()),
+    ))
+}
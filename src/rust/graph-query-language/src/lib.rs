@@ -0,0 +1,520 @@
+//! A Cypher-like text syntax for graph queries, for analysts to write queries
+//! without assembling them in code:
+//!
+//! ```text
+//! MATCH (p:Process {process_name: ~"powershell"})-[:children]->(c:Process)
+//! WHERE c.process_id > 100 AND NOT c.process_name = "conhost.exe"
+//! RETURN p, c
+//! ```
+//!
+//! Nodes are `(variable:NodeType {property: value, ...})`, where a `"string"`
+//! or integer value matches equal properties and `~"pattern"` matches by
+//! regex. A variable needs its node type the first time it's written, and can
+//! then be used on its own to refer to the same node, e.g. to join several
+//! comma-separated patterns. Edges are `-[:edge_name]->` or
+//! `<-[:edge_name]-`, and `-[:edge_name*1..5]->` matches a path of between 1
+//! and 5 such edges.
+//!
+//! `WHERE` adds `AND`ed comparisons of a node's properties: `=`, `<>`, `<`,
+//! `<=`, `>`, `>=` against strings or integers as the property allows, `=~`
+//! against a regex and `CONTAINS` against a substring, each of which `NOT`
+//! negates.
+//!
+//! The first variable after `RETURN` is the root of the query. The matched
+//! graph holds every node that the query matched, not only the returned ones.
+//!
+//! Keywords are case-insensitive, while names are not.
+mod lexer;
+mod parser;
+
+use std::{
+    collections::HashMap,
+    ops::RangeInclusive,
+};
+
+use rust_proto::graplinc::grapl::{
+    api::graph_query::v1beta1::messages::{
+        GraphQuery,
+        IntCmp,
+        NodePropertyQuery,
+        PathFilter,
+        QueryId,
+        StringCmp,
+    },
+    common::v1beta1::types::{
+        EdgeName,
+        NodeType,
+        PropertyName,
+    },
+};
+
+use self::parser::{
+    Comparison,
+    Parser,
+    Predicate,
+    Spanned,
+};
+
+/// Byte offsets into the query text, `start` inclusive and `end` exclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    fn new(start: usize, end: usize) -> Self {
+        Self { start, end }
+    }
+}
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+#[error("{message} at {}..{}", span.start, span.end)]
+pub struct QueryLanguageError {
+    pub message: String,
+    pub span: Span,
+}
+
+impl QueryLanguageError {
+    fn new(message: impl Into<String>, span: Span) -> Self {
+        Self {
+            message: message.into(),
+            span,
+        }
+    }
+}
+
+/// A parsed text query, whose variables have been checked to be typed and
+/// connected to the root.
+#[derive(Debug, Clone)]
+pub struct TextQuery {
+    variables: Vec<Variable>,
+    edges: Vec<Edge>,
+    root: usize,
+}
+
+#[derive(Debug, Clone)]
+struct Variable {
+    name: String,
+    node_type: NodeType,
+    string_filters: HashMap<PropertyName, Vec<StringCmp>>,
+    int_filters: HashMap<PropertyName, Vec<IntCmp>>,
+}
+
+/// An edge from the variable at `from` to the variable at `to`, whichever
+/// way the arrow was written.
+#[derive(Debug, Clone)]
+struct Edge {
+    from: usize,
+    to: usize,
+    edge_name: EdgeName,
+    hops: Option<RangeInclusive<u32>>,
+    span: Span,
+}
+
+impl TextQuery {
+    pub fn parse(text: &str) -> Result<Self, QueryLanguageError> {
+        let tokens = lexer::tokenize(text)?;
+        let parsed = Parser::new(tokens, text.len()).parse_match()?;
+
+        let mut query = TextQuery {
+            variables: Vec::new(),
+            edges: Vec::new(),
+            root: 0,
+        };
+        // Every occurrence of a variable, to find its node type wherever it
+        // was written before checking anything else about it
+        let mut occurrences: Vec<(Spanned<String>, Option<Spanned<String>>)> = Vec::new();
+        let mut predicates: Vec<Predicate> = Vec::new();
+        let mut chains = Vec::new();
+
+        for pattern in parsed.patterns {
+            let mut chain = vec![(None, pattern.first.variable.clone())];
+            occurrences.push((pattern.first.variable, pattern.first.label));
+            predicates.extend(pattern.first.predicates);
+            for (edge, node) in pattern.rest {
+                chain.push((Some(edge), node.variable.clone()));
+                occurrences.push((node.variable, node.label));
+                predicates.extend(node.predicates);
+            }
+            chains.push(chain);
+        }
+        predicates.extend(parsed.predicates);
+
+        let mut indexes: HashMap<String, usize> = HashMap::new();
+        for (variable, label) in &occurrences {
+            let label = match label {
+                Some(label) => label,
+                None => continue,
+            };
+            match indexes.get(&variable.value) {
+                Some(&index) if query.variables[index].node_type.value != label.value => {
+                    return Err(QueryLanguageError::new(
+                        format!(
+                            "{} is already a {}",
+                            variable.value, query.variables[index].node_type
+                        ),
+                        label.span,
+                    ));
+                }
+                Some(_) => {}
+                None => {
+                    let node_type = NodeType::try_from(label.value.clone()).map_err(|e| {
+                        QueryLanguageError::new(format!("invalid node type: {e}"), label.span)
+                    })?;
+                    indexes.insert(variable.value.clone(), query.variables.len());
+                    query.variables.push(Variable {
+                        name: variable.value.clone(),
+                        node_type,
+                        string_filters: HashMap::new(),
+                        int_filters: HashMap::new(),
+                    });
+                }
+            }
+        }
+
+        let index_of = |variable: &Spanned<String>| {
+            indexes.get(&variable.value).copied().ok_or_else(|| {
+                let declared = occurrences
+                    .iter()
+                    .any(|(occurrence, _)| occurrence.value == variable.value);
+                let message = if declared {
+                    format!("{0} needs a node type, as in ({0}:Process)", variable.value)
+                } else {
+                    format!("{} isn't in the MATCH patterns", variable.value)
+                };
+                QueryLanguageError::new(message, variable.span)
+            })
+        };
+
+        for (variable, _) in &occurrences {
+            index_of(variable)?;
+        }
+
+        for chain in &chains {
+            for pair in chain.windows(2) {
+                let (before, after) = (&pair[0].1, &pair[1].1);
+                let edge = pair[1].0.as_ref().expect("only the first has no edge");
+                let (from, to) = if edge.outgoing {
+                    (index_of(before)?, index_of(after)?)
+                } else {
+                    (index_of(after)?, index_of(before)?)
+                };
+                let edge_name = EdgeName::try_from(edge.edge_name.value.as_str()).map_err(|e| {
+                    QueryLanguageError::new(format!("invalid edge name: {e}"), edge.edge_name.span)
+                })?;
+                query.edges.push(Edge {
+                    from,
+                    to,
+                    edge_name,
+                    hops: edge.hops.clone(),
+                    span: edge.span,
+                });
+            }
+        }
+
+        for predicate in predicates {
+            let variable = &mut query.variables[index_of(&predicate.variable)?];
+            let property_name =
+                PropertyName::try_from(predicate.property_name.value).map_err(|e| {
+                    QueryLanguageError::new(
+                        format!("invalid property name: {e}"),
+                        predicate.property_name.span,
+                    )
+                })?;
+            match predicate.comparison {
+                Comparison::String(cmp) => variable
+                    .string_filters
+                    .entry(property_name)
+                    .or_default()
+                    .push(cmp),
+                Comparison::Int(cmp) => variable
+                    .int_filters
+                    .entry(property_name)
+                    .or_default()
+                    .push(cmp),
+            }
+        }
+
+        query.root = index_of(&parsed.returns[0])?;
+        for returned in &parsed.returns[1..] {
+            index_of(returned)?;
+        }
+
+        // A query is only matched outwards from its root, so a variable with
+        // no way there would be silently ignored
+        let mut reachable = vec![false; query.variables.len()];
+        let mut frontier = vec![query.root];
+        reachable[query.root] = true;
+        while let Some(index) = frontier.pop() {
+            for edge in &query.edges {
+                for (here, there) in [(edge.from, edge.to), (edge.to, edge.from)] {
+                    if here == index && !reachable[there] {
+                        reachable[there] = true;
+                        frontier.push(there);
+                    }
+                }
+            }
+        }
+        if let Some((variable, _)) = occurrences
+            .iter()
+            .find(|(variable, _)| !reachable[indexes[&variable.value]])
+        {
+            return Err(QueryLanguageError::new(
+                format!(
+                    "{} isn't connected to {}, which the query is rooted at",
+                    variable.value, query.variables[query.root].name
+                ),
+                variable.span,
+            ));
+        }
+
+        Ok(query)
+    }
+
+    /// The node type that each edge in the query leaves from, along with the
+    /// edge's name. `compile` needs each of their reverse edge names.
+    pub fn edges(&self) -> impl Iterator<Item = (&NodeType, &EdgeName)> + '_ {
+        self.edges
+            .iter()
+            .map(|edge| (&self.variables[edge.from].node_type, &edge.edge_name))
+    }
+
+    /// Compiles the query, with `reverse_edge_name` giving the name of each
+    /// edge as seen from its other end.
+    pub fn compile(
+        &self,
+        mut reverse_edge_name: impl FnMut(&NodeType, &EdgeName) -> Option<EdgeName>,
+    ) -> Result<GraphQuery, QueryLanguageError> {
+        let query_ids: Vec<QueryId> = self.variables.iter().map(|_| QueryId::default()).collect();
+
+        let mut graph_query = GraphQuery {
+            root_query_id: query_ids[self.root],
+            node_property_queries: Default::default(),
+            edge_filters: Default::default(),
+            edge_map: Default::default(),
+            path_filters: Default::default(),
+            edge_count_filters: Default::default(),
+        };
+
+        for (variable, query_id) in self.variables.iter().zip(&query_ids) {
+            let mut node_query = NodePropertyQuery::new(variable.node_type.clone());
+            node_query.query_id = *query_id;
+            // Every comparison of a property has to hold, so they make up a
+            // single alternative
+            for (property_name, comparisons) in &variable.string_filters {
+                node_query.with_string_filters(property_name.clone(), comparisons.clone());
+            }
+            for (property_name, comparisons) in &variable.int_filters {
+                node_query.with_int_filters(property_name.clone(), comparisons.clone());
+            }
+            graph_query.merge_node(node_query);
+        }
+
+        for edge in &self.edges {
+            let from_type = &self.variables[edge.from].node_type;
+            let reverse = reverse_edge_name(from_type, &edge.edge_name).ok_or_else(|| {
+                QueryLanguageError::new(
+                    format!(
+                        "{} has no {} edge with a known reverse",
+                        from_type, edge.edge_name
+                    ),
+                    edge.span,
+                )
+            })?;
+            let (from_id, to_id) = (query_ids[edge.from], query_ids[edge.to]);
+
+            match &edge.hops {
+                None => {
+                    graph_query
+                        .edge_filters
+                        .entry((from_id, edge.edge_name.clone()))
+                        .or_default()
+                        .insert(to_id);
+                    graph_query
+                        .edge_filters
+                        .entry((to_id, reverse.clone()))
+                        .or_default()
+                        .insert(from_id);
+                }
+                Some(hops) => {
+                    let path_filter = PathFilter {
+                        neighbor_query_id: to_id,
+                        min_hops: *hops.start(),
+                        max_hops: *hops.end(),
+                        intermediate_node_query: None,
+                    };
+                    graph_query
+                        .path_filters
+                        .entry((to_id, reverse.clone()))
+                        .or_default()
+                        .push(PathFilter {
+                            neighbor_query_id: from_id,
+                            ..path_filter.clone()
+                        });
+                    graph_query
+                        .path_filters
+                        .entry((from_id, edge.edge_name.clone()))
+                        .or_default()
+                        .push(path_filter);
+                }
+            }
+            graph_query
+                .edge_map
+                .insert(edge.edge_name.clone(), reverse.clone());
+            graph_query.edge_map.insert(reverse, edge.edge_name.clone());
+        }
+
+        Ok(graph_query)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_proto::graplinc::grapl::api::graph_query::v1beta1::messages::{
+        IntOperation,
+        StringOperation,
+    };
+
+    use super::*;
+
+    fn edge_name(value: &str) -> EdgeName {
+        EdgeName {
+            value: value.to_owned(),
+        }
+    }
+
+    fn process_edges(node_type: &NodeType, edge_name: &EdgeName) -> Option<EdgeName> {
+        let reverse = match (node_type.value.as_str(), edge_name.value.as_str()) {
+            ("Process", "children") => "parent",
+            ("Process", "parent") => "children",
+            ("Process", "binary_file") => "executed_as_processes",
+            _ => return None,
+        };
+        Some(self::edge_name(reverse))
+    }
+
+    fn compile(text: &str) -> Result<GraphQuery, QueryLanguageError> {
+        TextQuery::parse(text)?.compile(process_edges)
+    }
+
+    fn error_at(text: &str) -> (String, &str) {
+        let error = compile(text).expect_err("the query is invalid");
+        let spanned = &text[error.span.start..error.span.end];
+        (error.message, spanned)
+    }
+
+    #[test]
+    fn compiles_patterns_to_edge_filters_both_ways() {
+        let graph_query = compile(
+            r#"MATCH (p:Process {process_name: ~"powershell"})-[:children]->(c:Process)
+               RETURN p, c"#,
+        )
+        .unwrap();
+
+        assert_eq!(graph_query.node_property_queries.len(), 2);
+        let root = &graph_query.node_property_queries[&graph_query.root_query_id];
+        let process_name = &root.string_filters
+            [&PropertyName::new_unchecked("process_name".to_owned())]
+            .and_string_filters;
+        assert_eq!(process_name.len(), 1);
+        assert_eq!(
+            process_name[0].string_filters[0].operation,
+            StringOperation::Regex
+        );
+
+        let children =
+            &graph_query.edge_filters[&(graph_query.root_query_id, edge_name("children"))];
+        assert_eq!(children.len(), 1);
+        let child_id = *children.iter().next().unwrap();
+        assert!(graph_query.edge_filters[&(child_id, edge_name("parent"))]
+            .contains(&graph_query.root_query_id));
+        assert_eq!(
+            graph_query.edge_map[&edge_name("parent")],
+            edge_name("children")
+        );
+    }
+
+    #[test]
+    fn incoming_edges_and_where_clauses() {
+        let graph_query = compile(
+            r#"match (c:Process)<-[:children]-(p:Process), (p)-[:parent*1..3]->(a:Process)
+               where c.process_id >= -1 and not p.process_name contains "explorer"
+                 and c.process_id <> 4
+               return c"#,
+        )
+        .unwrap();
+
+        let root_id = graph_query.root_query_id;
+        let root = &graph_query.node_property_queries[&root_id];
+        // Comparisons of one property are a single alternative
+        let process_id = &root.int_filters[&PropertyName::new_unchecked("process_id".to_owned())];
+        assert_eq!(process_id.and_int_filters.len(), 1);
+        let comparisons = &process_id.and_int_filters[0].int_filters;
+        assert_eq!(comparisons.len(), 2);
+        assert_eq!(comparisons[0].operation, IntOperation::GreaterThanOrEqual);
+        assert_eq!(comparisons[0].value, -1);
+        assert!(comparisons[1].negated);
+
+        // c <-[:children]- p is the same edge as c -[:parent]-> p
+        let parent_id = *graph_query.edge_filters[&(root_id, edge_name("parent"))]
+            .iter()
+            .next()
+            .unwrap();
+        let parent = &graph_query.node_property_queries[&parent_id];
+        assert!(
+            parent.string_filters[&PropertyName::new_unchecked("process_name".to_owned())]
+                .and_string_filters[0]
+                .string_filters[0]
+                .negated
+        );
+
+        let ancestors = &graph_query.path_filters[&(parent_id, edge_name("parent"))];
+        assert_eq!((ancestors[0].min_hops, ancestors[0].max_hops), (1, 3));
+        assert_eq!(graph_query.node_property_queries.len(), 3);
+    }
+
+    #[test]
+    fn reports_the_span_of_errors() {
+        assert_eq!(
+            error_at("MATCH (p:Process)-[:children]->(c:Process RETURN p").1,
+            "RETURN"
+        );
+        assert_eq!(error_at("MATCH (p:Process) RETURN p,").1, "");
+        assert_eq!(
+            error_at(r#"MATCH (p:Process {process_name: "cmd.exe}) RETURN p"#).1,
+            r#""cmd.exe}) RETURN p"#
+        );
+        assert_eq!(
+            error_at("MATCH (p:Process)-[:children]->(c) RETURN p").1,
+            "c"
+        );
+        assert_eq!(error_at("MATCH (p:Process), (f:File) RETURN p").1, "f");
+        assert_eq!(
+            error_at("MATCH (p:Process)-[:children]->(p:File) RETURN p").1,
+            "File"
+        );
+        assert_eq!(
+            error_at("MATCH (p:Process)-[:children*0..2]->(c:Process) RETURN p").1,
+            "0..2"
+        );
+        assert_eq!(error_at("MATCH (p:Process) WHERE x.y = 1 RETURN p").1, "x");
+        assert_eq!(error_at("MATCH (p:Process) WHERE p.y ! 1 RETURN p").1, "!");
+        let long_name = "a".repeat(33);
+        assert_eq!(
+            error_at(&format!("MATCH (p:{long_name}) RETURN p")).1,
+            long_name
+        );
+        assert_eq!(
+            error_at(&format!(
+                "MATCH (p:Process) WHERE p.{long_name} = 1 RETURN p"
+            ))
+            .1,
+            long_name
+        );
+
+        let (message, spanned) = error_at("MATCH (f:File)-[:written_by]->(p:Process) RETURN f");
+        assert_eq!(spanned, "-[:written_by]->");
+        assert!(message.contains("File has no written_by edge"));
+    }
+}
@@ -0,0 +1,436 @@
+use std::ops::RangeInclusive;
+
+use rust_proto::graplinc::grapl::api::graph_query::v1beta1::messages::{
+    IntCmp,
+    StringCmp,
+};
+
+use super::{
+    lexer::Token,
+    QueryLanguageError,
+    Span,
+};
+
+/// A name as it was written, with where it was written.
+#[derive(Debug, Clone)]
+pub(super) struct Spanned<T> {
+    pub value: T,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct Match {
+    pub patterns: Vec<Pattern>,
+    pub predicates: Vec<Predicate>,
+    pub returns: Vec<Spanned<String>>,
+}
+
+/// A chain of nodes joined by edges: `(a)-[:x]->(b)<-[:y]-(c)`.
+#[derive(Debug, Clone)]
+pub(super) struct Pattern {
+    pub first: NodePattern,
+    pub rest: Vec<(EdgePattern, NodePattern)>,
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct NodePattern {
+    pub variable: Spanned<String>,
+    pub label: Option<Spanned<String>>,
+    pub predicates: Vec<Predicate>,
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct EdgePattern {
+    pub edge_name: Spanned<String>,
+    /// Whether the edge points from the node before it to the node after it.
+    pub outgoing: bool,
+    pub hops: Option<RangeInclusive<u32>>,
+    pub span: Span,
+}
+
+/// A comparison on one property of a node, from either a `{...}` map or
+/// `WHERE`.
+#[derive(Debug, Clone)]
+pub(super) struct Predicate {
+    pub variable: Spanned<String>,
+    pub property_name: Spanned<String>,
+    pub comparison: Comparison,
+}
+
+#[derive(Debug, Clone)]
+pub(super) enum Comparison {
+    String(StringCmp),
+    Int(IntCmp),
+}
+
+pub(super) struct Parser<'a> {
+    tokens: Vec<(Span, Token<'a>)>,
+    index: usize,
+    input_len: usize,
+}
+
+impl<'a> Parser<'a> {
+    pub(super) fn new(tokens: Vec<(Span, Token<'a>)>, input_len: usize) -> Self {
+        Self {
+            tokens,
+            index: 0,
+            input_len,
+        }
+    }
+
+    fn peek(&self) -> Option<&(Span, Token<'a>)> {
+        self.tokens.get(self.index)
+    }
+
+    fn next(&mut self) -> Option<(Span, Token<'a>)> {
+        let token = self.tokens.get(self.index).cloned();
+        self.index += 1;
+        token
+    }
+
+    fn peek_is(&self, token: &Token<'_>) -> bool {
+        matches!(self.peek(), Some((_, next)) if next == token)
+    }
+
+    fn peek_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some((_, next)) if next.is_keyword(keyword))
+    }
+
+    /// An error at the next token, or at the end of the input if there are
+    /// no tokens left.
+    fn error_at_next(&self, expected: &str) -> QueryLanguageError {
+        match self.peek() {
+            Some((span, _)) => QueryLanguageError::new(format!("expected {expected}"), *span),
+            None => QueryLanguageError::new(
+                format!("expected {expected}, found the end of the query"),
+                Span::new(self.input_len, self.input_len),
+            ),
+        }
+    }
+
+    fn expect(&mut self, token: Token<'_>, expected: &str) -> Result<Span, QueryLanguageError> {
+        if self.peek_is(&token) {
+            Ok(self.next().expect("peeked").0)
+        } else {
+            Err(self.error_at_next(expected))
+        }
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> Result<Span, QueryLanguageError> {
+        if self.peek_keyword(keyword) {
+            Ok(self.next().expect("peeked").0)
+        } else {
+            Err(self.error_at_next(keyword))
+        }
+    }
+
+    fn expect_identifier(&mut self, expected: &str) -> Result<Spanned<String>, QueryLanguageError> {
+        match self.peek() {
+            Some((span, Token::Identifier(word))) => {
+                let identifier = Spanned {
+                    value: (*word).to_owned(),
+                    span: *span,
+                };
+                self.index += 1;
+                Ok(identifier)
+            }
+            _ => Err(self.error_at_next(expected)),
+        }
+    }
+
+    fn expect_integer(&mut self, expected: &str) -> Result<(Span, u64), QueryLanguageError> {
+        match self.peek() {
+            Some((span, Token::Integer(value))) => {
+                let integer = (*span, *value);
+                self.index += 1;
+                Ok(integer)
+            }
+            _ => Err(self.error_at_next(expected)),
+        }
+    }
+
+    /// `MATCH pattern, ... [WHERE predicate AND ...] RETURN variable, ...`
+    pub(super) fn parse_match(&mut self) -> Result<Match, QueryLanguageError> {
+        self.expect_keyword("MATCH")?;
+
+        let mut patterns = vec![self.parse_pattern()?];
+        while self.peek_is(&Token::Comma) {
+            self.next();
+            patterns.push(self.parse_pattern()?);
+        }
+
+        let mut predicates = Vec::new();
+        if self.peek_keyword("WHERE") {
+            self.next();
+            predicates.push(self.parse_where_predicate()?);
+            while self.peek_keyword("AND") {
+                self.next();
+                predicates.push(self.parse_where_predicate()?);
+            }
+        }
+
+        if !self.peek_keyword("RETURN") {
+            return Err(self.error_at_next(if predicates.is_empty() {
+                "WHERE, RETURN or another pattern"
+            } else {
+                "AND or RETURN"
+            }));
+        }
+        self.next();
+
+        let mut returns = vec![self.expect_identifier("a variable to return")?];
+        while self.peek_is(&Token::Comma) {
+            self.next();
+            returns.push(self.expect_identifier("a variable to return")?);
+        }
+
+        match self.peek() {
+            None => Ok(Match {
+                patterns,
+                predicates,
+                returns,
+            }),
+            Some(_) => Err(self.error_at_next("the end of the query")),
+        }
+    }
+
+    fn parse_pattern(&mut self) -> Result<Pattern, QueryLanguageError> {
+        let first = self.parse_node()?;
+        let mut rest = Vec::new();
+        while self.peek_is(&Token::Dash) || self.peek_is(&Token::LeftArrow) {
+            let edge = self.parse_edge()?;
+            rest.push((edge, self.parse_node()?));
+        }
+        Ok(Pattern { first, rest })
+    }
+
+    /// `(variable[:Label] [{property: value, ...}])`
+    fn parse_node(&mut self) -> Result<NodePattern, QueryLanguageError> {
+        self.expect(Token::OpenParen, "( to start a node")?;
+        let variable = self.expect_identifier("a variable name")?;
+
+        let label = if self.peek_is(&Token::Colon) {
+            self.next();
+            Some(self.expect_identifier("a node type")?)
+        } else {
+            None
+        };
+
+        let mut predicates = Vec::new();
+        if self.peek_is(&Token::OpenBrace) {
+            self.next();
+            loop {
+                let property_name = self.expect_identifier("a property name")?;
+                self.expect(Token::Colon, ":")?;
+                let comparison = self.parse_map_value()?;
+                predicates.push(Predicate {
+                    variable: variable.clone(),
+                    property_name,
+                    comparison,
+                });
+
+                if self.peek_is(&Token::Comma) {
+                    self.next();
+                } else {
+                    self.expect(Token::CloseBrace, ", or }")?;
+                    break;
+                }
+            }
+        }
+
+        self.expect(Token::CloseParen, ") to end the node")?;
+        Ok(NodePattern {
+            variable,
+            label,
+            predicates,
+        })
+    }
+
+    /// `-[:edge_name]->`, `<-[:edge_name]-`, or either with `*min..max`
+    /// after the edge name for a path of several hops.
+    fn parse_edge(&mut self) -> Result<EdgePattern, QueryLanguageError> {
+        let (start, incoming) = match self.next() {
+            Some((span, Token::Dash)) => (span.start, false),
+            Some((span, Token::LeftArrow)) => (span.start, true),
+            _ => unreachable!("only called before - or <-"),
+        };
+
+        self.expect(Token::OpenBracket, "[ to start an edge")?;
+        self.expect(Token::Colon, ": before the edge name")?;
+        let edge_name = self.expect_identifier("an edge name")?;
+
+        let hops = if self.peek_is(&Token::Star) {
+            self.next();
+            Some(self.parse_hops()?)
+        } else {
+            None
+        };
+
+        self.expect(Token::CloseBracket, "] to end the edge")?;
+
+        let end = if incoming {
+            self.expect(Token::Dash, "- to end the edge")?
+        } else {
+            self.expect(Token::RightArrow, "-> to end the edge")?
+        };
+
+        Ok(EdgePattern {
+            edge_name,
+            outgoing: !incoming,
+            hops,
+            span: Span::new(start, end.end),
+        })
+    }
+
+    /// `n` or `min..max`, after a `*`.
+    fn parse_hops(&mut self) -> Result<RangeInclusive<u32>, QueryLanguageError> {
+        let (min_span, min) = self.expect_integer("the number of hops")?;
+        let (max_span, max) = if self.peek_is(&Token::DotDot) {
+            self.next();
+            self.expect_integer("the most hops")?
+        } else {
+            (min_span, min)
+        };
+
+        let span = Span::new(min_span.start, max_span.end);
+        let hops = match (u32::try_from(min), u32::try_from(max)) {
+            (Ok(min), Ok(max)) if min >= 1 && min <= max => min..=max,
+            _ => {
+                return Err(QueryLanguageError::new(
+                    "hops must be at least 1, with the fewest no more than the most",
+                    span,
+                ))
+            }
+        };
+        Ok(hops)
+    }
+
+    /// The value of a `{property: value}` entry: `"string"` for equality,
+    /// `~"pattern"` for a regex, or an integer for equality.
+    fn parse_map_value(&mut self) -> Result<Comparison, QueryLanguageError> {
+        match self.next() {
+            Some((_, Token::String(value))) => Ok(Comparison::String(StringCmp::Eq(value, false))),
+            Some((_, Token::Tilde)) => match self.next() {
+                Some((_, Token::String(pattern))) => {
+                    Ok(Comparison::String(StringCmp::Regex(pattern, false)))
+                }
+                _ => {
+                    self.index -= 1;
+                    Err(self.error_at_next("a regex pattern in quotes after ~"))
+                }
+            },
+            Some((span, Token::Integer(value))) => Ok(Comparison::Int(IntCmp::Eq(
+                to_i64(value, false, span)?,
+                false,
+            ))),
+            Some((span, Token::Dash)) => {
+                let (int_span, value) = self.expect_integer("an integer after -")?;
+                let value = to_i64(value, true, Span::new(span.start, int_span.end))?;
+                Ok(Comparison::Int(IntCmp::Eq(value, false)))
+            }
+            _ => {
+                self.index -= 1;
+                Err(self.error_at_next("a string, ~\"regex\" or integer"))
+            }
+        }
+    }
+
+    /// `[NOT] variable.property <op> value`
+    fn parse_where_predicate(&mut self) -> Result<Predicate, QueryLanguageError> {
+        let negated = if self.peek_keyword("NOT") {
+            self.next();
+            true
+        } else {
+            false
+        };
+
+        let variable = self.expect_identifier("a variable")?;
+        self.expect(Token::Dot, ". and a property name")?;
+        let property_name = self.expect_identifier("a property name")?;
+
+        let comparison = match self.next() {
+            Some((_, Token::Eq)) => self.parse_where_value(|value| match value {
+                Value::String(value) => Comparison::String(StringCmp::Eq(value, negated)),
+                Value::Int(value) => Comparison::Int(IntCmp::Eq(value, negated)),
+            })?,
+            Some((_, Token::NotEq)) => self.parse_where_value(|value| match value {
+                Value::String(value) => Comparison::String(StringCmp::Eq(value, !negated)),
+                Value::Int(value) => Comparison::Int(IntCmp::Eq(value, !negated)),
+            })?,
+            Some((_, Token::RegexMatch)) => Comparison::String(StringCmp::Regex(
+                self.parse_string("a regex pattern")?,
+                negated,
+            )),
+            Some((_, token)) if token.is_keyword("CONTAINS") => {
+                Comparison::String(StringCmp::Contains(self.parse_string("a string")?, negated))
+            }
+            Some((_, Token::Lt)) => Comparison::Int(IntCmp::Lt(self.parse_int()?, negated)),
+            Some((_, Token::Lte)) => Comparison::Int(IntCmp::Lte(self.parse_int()?, negated)),
+            Some((_, Token::Gt)) => Comparison::Int(IntCmp::Gt(self.parse_int()?, negated)),
+            Some((_, Token::Gte)) => Comparison::Int(IntCmp::Gte(self.parse_int()?, negated)),
+            _ => {
+                self.index -= 1;
+                return Err(self.error_at_next("=, <>, <, <=, >, >=, =~ or CONTAINS"));
+            }
+        };
+
+        Ok(Predicate {
+            variable,
+            property_name,
+            comparison,
+        })
+    }
+
+    fn parse_where_value(
+        &mut self,
+        to_comparison: impl FnOnce(Value) -> Comparison,
+    ) -> Result<Comparison, QueryLanguageError> {
+        let value = if matches!(self.peek(), Some((_, Token::String(_)))) {
+            Value::String(self.parse_string("a string")?)
+        } else if self.peek_is(&Token::Dash) || matches!(self.peek(), Some((_, Token::Integer(_))))
+        {
+            Value::Int(self.parse_int()?)
+        } else {
+            return Err(self.error_at_next("a string or integer"));
+        };
+        Ok(to_comparison(value))
+    }
+
+    fn parse_string(&mut self, expected: &str) -> Result<String, QueryLanguageError> {
+        match self.peek() {
+            Some((_, Token::String(value))) => {
+                let value = value.clone();
+                self.index += 1;
+                Ok(value)
+            }
+            _ => Err(self.error_at_next(&format!("{expected} in quotes"))),
+        }
+    }
+
+    fn parse_int(&mut self) -> Result<i64, QueryLanguageError> {
+        let negative_span = if self.peek_is(&Token::Dash) {
+            self.next().map(|(span, _)| span)
+        } else {
+            None
+        };
+        let (span, value) = self.expect_integer("an integer")?;
+        let span = Span::new(negative_span.map_or(span.start, |s| s.start), span.end);
+        to_i64(value, negative_span.is_some(), span)
+    }
+}
+
+enum Value {
+    String(String),
+    Int(i64),
+}
+
+fn to_i64(value: u64, negative: bool, span: Span) -> Result<i64, QueryLanguageError> {
+    let value = if !negative {
+        i64::try_from(value).ok()
+    } else if value <= i64::MIN.unsigned_abs() {
+        Some((value as i64).wrapping_neg())
+    } else {
+        None
+    };
+    value.ok_or_else(|| QueryLanguageError::new("integer doesn't fit in 64 bits", span))
+}
@@ -0,0 +1,155 @@
+//! How graph queries' filters match property values, shared by the
+//! graph-query service and analyzer-sdk's in-memory graph so that a query
+//! matches the same nodes in tests as it does in a deployment. Everything here
+//! is independent of where the values are stored.
+pub mod regex_cache;
+
+use rust_proto::graplinc::grapl::{
+    api::graph_query::v1beta1::messages::{
+        FloatCmp,
+        IntCmp,
+        NodePropertyQuery,
+        OrFloatFilters,
+        OrIntFilters,
+        StrCmp,
+        UidOperation,
+    },
+    common::v1beta1::types::{
+        PropertyName,
+        Uid,
+    },
+};
+
+pub use crate::regex_cache::{
+    RegexCache,
+    RegexCacheError,
+};
+
+/// Counts the filters evaluated while matching, e.g. for query statistics.
+pub trait FilterCounter {
+    fn add_filter(&self);
+}
+
+/// Doesn't count.
+impl FilterCounter for () {
+    fn add_filter(&self) {}
+}
+
+/// Whether `property_value` satisfies all of the string filters on
+/// `property_name` in any one of their groups.
+pub fn match_property(
+    node_properties_query: &NodePropertyQuery,
+    property_name: &PropertyName,
+    property_value: &str,
+    regex_cache: &RegexCache,
+    counters: &impl FilterCounter,
+) -> bool {
+    'outer: for or_filters in
+        &node_properties_query.string_filters[property_name].and_string_filters
+    {
+        for and_filter in &or_filters.string_filters {
+            counters.add_filter();
+            match StrCmp::from(and_filter) {
+                StrCmp::Eq(to, negated) => match (negated, property_value == to) {
+                    (false, false) => continue 'outer,
+                    (true, true) => continue 'outer,
+                    (_, _) => (),
+                },
+                StrCmp::Contains(to, negated) => match (negated, property_value.contains(to)) {
+                    (false, false) => continue 'outer,
+                    (true, true) => continue 'outer,
+                    (_, _) => (),
+                },
+                StrCmp::Regex(pattern, negated) => {
+                    match (negated, regex_cache.is_match(pattern, property_value)) {
+                        (false, false) => continue 'outer,
+                        (true, true) => continue 'outer,
+                        (_, _) => (),
+                    }
+                }
+                StrCmp::Has => (),
+            };
+        }
+        return true;
+    }
+
+    false
+}
+
+/// As `match_property`, for the node's integer filters: a property matches
+/// when every filter in any one of its `AndIntFilters` does.
+pub fn match_int_property(
+    node_properties_query: &NodePropertyQuery,
+    property_name: &PropertyName,
+    property_value: i128,
+    counters: &impl FilterCounter,
+) -> bool {
+    match_int(
+        &node_properties_query.int_filters[property_name],
+        property_value,
+        counters,
+    )
+}
+
+/// Whether `value` satisfies all of the filters in any one of the groups.
+pub fn match_int(or_filters: &OrIntFilters, value: i128, counters: &impl FilterCounter) -> bool {
+    'outer: for and_filters in &or_filters.and_int_filters {
+        for and_filter in &and_filters.int_filters {
+            counters.add_filter();
+            let matched = match IntCmp::from(and_filter) {
+                IntCmp::Eq(to, negated) => (value == i128::from(to)) != negated,
+                IntCmp::Lt(to, negated) => (value < i128::from(to)) != negated,
+                IntCmp::Lte(to, negated) => (value <= i128::from(to)) != negated,
+                IntCmp::Gt(to, negated) => (value > i128::from(to)) != negated,
+                IntCmp::Gte(to, negated) => (value >= i128::from(to)) != negated,
+                IntCmp::Has => true,
+            };
+            if !matched {
+                continue 'outer;
+            }
+        }
+        return true;
+    }
+
+    false
+}
+
+/// As `match_int`, for float properties.
+pub fn match_float(or_filters: &OrFloatFilters, value: f64, counters: &impl FilterCounter) -> bool {
+    'outer: for and_filters in &or_filters.and_float_filters {
+        for and_filter in &and_filters.float_filters {
+            counters.add_filter();
+            let matched = match FloatCmp::from(and_filter) {
+                FloatCmp::Eq(to, negated) => (value == to) != negated,
+                FloatCmp::Lt(to, negated) => (value < to) != negated,
+                FloatCmp::Lte(to, negated) => (value <= to) != negated,
+                FloatCmp::Gt(to, negated) => (value > to) != negated,
+                FloatCmp::Gte(to, negated) => (value >= to) != negated,
+                FloatCmp::Has => true,
+            };
+            if !matched {
+                continue 'outer;
+            }
+        }
+        return true;
+    }
+
+    false
+}
+
+/// Whether `uid` is one of the nodes that the query is pinned to, if it's
+/// pinned to any: a node matches when any one of its uid filters does.
+pub fn match_uid(
+    node_properties_query: &NodePropertyQuery,
+    uid: Uid,
+    counters: &impl FilterCounter,
+) -> bool {
+    let uid_filters = &node_properties_query.uid_filters.uid_filters;
+    uid_filters.is_empty()
+        || uid_filters.iter().any(|uid_filter| {
+            counters.add_filter();
+            match uid_filter.operation {
+                UidOperation::Equal => uid_filter.value == uid,
+            }
+        })
+}
@@ -0,0 +1,250 @@
+use std::sync::Arc;
+
+use regex::{
+    Regex,
+    RegexBuilder,
+};
+use rust_proto::graplinc::grapl::api::graph_query::v1beta1::messages::{
+    GraphQuery,
+    StringOperation,
+};
+use rustc_hash::FxHashMap;
+
+/// The longest pattern a regex filter may have, in bytes.
+pub const MAX_PATTERN_LENGTH: usize = 1024;
+/// The most memory, in bytes, that a single compiled pattern may use. Bounds
+/// patterns like `\w{1000}{1000}` which are short but compile to huge
+/// programs.
+pub const COMPILED_SIZE_LIMIT: usize = 1 << 20;
+/// The most memory, in bytes, that the lazy DFA of a single pattern may use
+/// while matching before falling back to a slower engine.
+pub const DFA_SIZE_LIMIT: usize = 1 << 20;
+/// How deeply groups, repetitions and alternations may be nested.
+pub const NEST_LIMIT: u32 = 32;
+/// The most distinct patterns a single query may use. Each is compiled and
+/// held in memory for as long as the query runs, so the per-pattern limits
+/// alone don't bound a query's memory.
+pub const MAX_PATTERNS: usize = 64;
+
+#[derive(thiserror::Error, Debug)]
+pub enum RegexCacheError {
+    #[error("Regex pattern is {length} bytes long, the limit is {MAX_PATTERN_LENGTH}")]
+    PatternTooLong { length: usize },
+    #[error("Query has more than {MAX_PATTERNS} distinct regex patterns")]
+    TooManyPatterns,
+    #[error("Invalid regex pattern {pattern:?}: {source}")]
+    InvalidPattern {
+        pattern: String,
+        source: regex::Error,
+    },
+}
+
+/// The compiled patterns of every regex filter in a `GraphQuery`.
+///
+/// Patterns are compiled once, before the query is run, so that a node query
+/// that is evaluated for many nodes doesn't recompile its patterns for each of
+/// them, and so that a pattern that fails to compile fails the whole query up
+/// front instead of whichever nodes happen to reach it.
+#[derive(Clone, Debug, Default)]
+pub struct RegexCache {
+    regexes: Arc<FxHashMap<String, Regex>>,
+}
+
+impl RegexCache {
+    pub fn compile(graph_query: &GraphQuery) -> Result<Self, RegexCacheError> {
+        let mut regexes = FxHashMap::default();
+
+        let intermediate_node_queries = graph_query
+            .path_filters
+            .values()
+            .flatten()
+            .filter_map(|path_filter| path_filter.intermediate_node_query.as_ref());
+
+        let string_filters = graph_query
+            .node_property_queries
+            .values()
+            .chain(intermediate_node_queries)
+            .flat_map(|node_query| node_query.string_filters.values())
+            .flat_map(|or_filters| &or_filters.and_string_filters)
+            .flat_map(|and_filters| &and_filters.string_filters)
+            .filter(|string_filter| string_filter.operation == StringOperation::Regex);
+
+        for string_filter in string_filters {
+            let pattern = &string_filter.value;
+            if regexes.contains_key(pattern) {
+                continue;
+            }
+            if regexes.len() == MAX_PATTERNS {
+                return Err(RegexCacheError::TooManyPatterns);
+            }
+            regexes.insert(pattern.clone(), compile_pattern(pattern)?);
+        }
+
+        Ok(Self {
+            regexes: Arc::new(regexes),
+        })
+    }
+
+    /// Whether `value` matches `pattern`. Every pattern in the query was
+    /// compiled by `compile`, so a pattern that wasn't never matches.
+    pub fn is_match(&self, pattern: &str, value: &str) -> bool {
+        match self.regexes.get(pattern) {
+            Some(regex) => regex.is_match(value),
+            None => {
+                tracing::error!(
+                    message = "Regex pattern was not compiled with its query. This is a bug.",
+                    pattern = pattern,
+                );
+                false
+            }
+        }
+    }
+}
+
+fn compile_pattern(pattern: &str) -> Result<Regex, RegexCacheError> {
+    if pattern.len() > MAX_PATTERN_LENGTH {
+        return Err(RegexCacheError::PatternTooLong {
+            length: pattern.len(),
+        });
+    }
+
+    RegexBuilder::new(pattern)
+        .size_limit(COMPILED_SIZE_LIMIT)
+        .dfa_size_limit(DFA_SIZE_LIMIT)
+        .nest_limit(NEST_LIMIT)
+        .build()
+        .map_err(|source| RegexCacheError::InvalidPattern {
+            pattern: pattern.to_owned(),
+            source,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_proto::graplinc::grapl::{
+        api::graph_query::v1beta1::messages::{
+            NodePropertyQuery,
+            StringCmp,
+        },
+        common::v1beta1::types::{
+            NodeType,
+            PropertyName,
+        },
+    };
+
+    use super::*;
+
+    fn graph_query(patterns: impl IntoIterator<Item = String>) -> GraphQuery {
+        let mut node_query = NodePropertyQuery::new(NodeType {
+            value: "Process".to_owned(),
+        });
+        for pattern in patterns {
+            node_query.with_string_filters(
+                PropertyName::new_unchecked("command_line".to_owned()),
+                vec![StringCmp::Regex(pattern, false)],
+            );
+        }
+
+        GraphQuery {
+            root_query_id: node_query.query_id,
+            node_property_queries: [(node_query.query_id, node_query)].into_iter().collect(),
+            edge_filters: Default::default(),
+            edge_map: Default::default(),
+            path_filters: Default::default(),
+            edge_count_filters: Default::default(),
+        }
+    }
+
+    fn compile(pattern: String) -> Result<RegexCache, RegexCacheError> {
+        RegexCache::compile(&graph_query([pattern]))
+    }
+
+    #[test]
+    fn compiles_and_matches_patterns() {
+        let regex_cache = compile("^/usr/bin/.*sh$".to_owned()).unwrap();
+
+        assert!(regex_cache.is_match("^/usr/bin/.*sh$", "/usr/bin/bash"));
+        assert!(!regex_cache.is_match("^/usr/bin/.*sh$", "/usr/bin/python"));
+        // never compiled, so never matches
+        assert!(!regex_cache.is_match("python", "/usr/bin/python"));
+    }
+
+    #[test]
+    fn rejects_long_patterns() {
+        assert!(compile("a".repeat(MAX_PATTERN_LENGTH)).is_ok());
+
+        let result = compile("a".repeat(MAX_PATTERN_LENGTH + 1));
+        assert!(matches!(
+            result,
+            Err(RegexCacheError::PatternTooLong { length }) if length == MAX_PATTERN_LENGTH + 1
+        ));
+    }
+
+    #[test]
+    fn rejects_patterns_over_the_compiled_size_limit() {
+        let result = compile(r"\w{1000}{1000}".to_owned());
+        assert!(matches!(
+            result,
+            Err(RegexCacheError::InvalidPattern {
+                source: regex::Error::CompiledTooBig(_),
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn rejects_patterns_over_the_nest_limit() {
+        let nested = |depth: u32| {
+            let depth = depth as usize;
+            format!("{}a{}", "(".repeat(depth), ")".repeat(depth))
+        };
+
+        assert!(compile(nested(NEST_LIMIT / 2)).is_ok());
+        assert!(matches!(
+            compile(nested(NEST_LIMIT + 1)),
+            Err(RegexCacheError::InvalidPattern { .. })
+        ));
+    }
+
+    #[test]
+    fn patterns_over_the_dfa_size_limit_still_match() {
+        // A lazy DFA for this pattern can need a state for each of the 2^20
+        // combinations of the last 20 characters, far more than fit in
+        // DFA_SIZE_LIMIT. Running out falls back to a slower engine rather
+        // than failing the match.
+        let pattern = "^[ab]*a[ab]{20}$";
+        let regex_cache = compile(pattern.to_owned()).unwrap();
+
+        let mut state: u32 = 1;
+        let mut value: String = (0..100_000)
+            .map(|_| {
+                state = state.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+                if state & (1 << 16) == 0 {
+                    'a'
+                } else {
+                    'b'
+                }
+            })
+            .collect();
+        value.push('a');
+        value.push_str(&"b".repeat(20));
+        assert!(regex_cache.is_match(pattern, &value));
+
+        value.push('b');
+        assert!(!regex_cache.is_match(pattern, &value));
+    }
+
+    #[test]
+    fn limits_the_number_of_distinct_patterns() {
+        let patterns = |count: usize| (0..count).map(|i| format!("^{i}$"));
+
+        // repeated patterns are only compiled, and counted, once
+        let repeated = patterns(MAX_PATTERNS).chain(patterns(MAX_PATTERNS));
+        assert!(RegexCache::compile(&graph_query(repeated)).is_ok());
+
+        assert!(matches!(
+            RegexCache::compile(&graph_query(patterns(MAX_PATTERNS + 1))),
+            Err(RegexCacheError::TooManyPatterns)
+        ));
+    }
+}
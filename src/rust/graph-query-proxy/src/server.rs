@@ -6,6 +6,8 @@ use rust_proto::graplinc::grapl::api::{
     },
     graph_query_proxy::v1beta1::{
         messages::{
+            AggregateGraphFromUidRequest,
+            AggregateGraphFromUidResponse,
             QueryGraphFromUidRequest,
             QueryGraphFromUidResponse,
             QueryGraphWithUidRequest,
@@ -58,6 +60,7 @@ impl GraphQueryProxyApi for GraphQueryProxy {
             tenant_id: self.tenant_id,
             graph_query: request.graph_query,
             node_uid: request.node_uid,
+            explain: false,
         };
         let mut graph_query_client = self.graph_query_client.clone();
         Ok(graph_query_client
@@ -75,6 +78,7 @@ impl GraphQueryProxyApi for GraphQueryProxy {
             tenant_id: self.tenant_id,
             graph_query: request.graph_query,
             node_uid: request.node_uid,
+            explain: false,
         };
         let mut graph_query_client = self.graph_query_client.clone();
         Ok(graph_query_client
@@ -82,4 +86,23 @@ impl GraphQueryProxyApi for GraphQueryProxy {
             .await?
             .into())
     }
+
+    #[tracing::instrument(skip(self), err)]
+    async fn aggregate_graph_from_uid(
+        &self,
+        request: AggregateGraphFromUidRequest,
+    ) -> Result<AggregateGraphFromUidResponse, GraphQueryProxyError> {
+        let request = non_proxy_messages::AggregateGraphFromUidRequest {
+            tenant_id: self.tenant_id,
+            graph_query: request.graph_query,
+            node_uid: request.node_uid,
+            edge_names: request.edge_names,
+            explain: false,
+        };
+        let mut graph_query_client = self.graph_query_client.clone();
+        Ok(graph_query_client
+            .aggregate_graph_from_uid(request)
+            .await?
+            .into())
+    }
 }
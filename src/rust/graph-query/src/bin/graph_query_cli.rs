@@ -0,0 +1,184 @@
+/// Runs a text query against graph-query, e.g.
+///
+/// ```text
+/// graph-query-cli --tenant-id $TENANT --node-uid 42 \
+///     'MATCH (p:Process)-[:children]->(c:Process) RETURN p, c'
+/// ```
+///
+/// Pass `--explain` to also print what each node query cost and where it
+/// short-circuited.
+///
+/// The client is configured by the `GRAPH_QUERY_CLIENT_` environment
+/// variables.
+use std::path::PathBuf;
+
+use clap::Parser;
+use figment::{
+    providers::Env,
+    Figment,
+};
+use graph_query_language::{
+    QueryLanguageError,
+    TextQuery,
+};
+use rust_proto::graplinc::grapl::{
+    api::{
+        client::Connect,
+        graph_query::v1beta1::{
+            client::GraphQueryClient,
+            messages::{
+                MatchedGraphWithUid,
+                MaybeMatchWithUid,
+                QueryExplanation,
+                QueryGraphWithTextRequest,
+            },
+        },
+    },
+    common::v1beta1::types::Uid,
+};
+
+#[derive(clap::Parser, Debug)]
+#[clap(
+    name = "graph-query-cli",
+    about = "Runs a text query against graph-query"
+)]
+struct Args {
+    #[clap(long)]
+    /// The tenant whose graph to query
+    tenant_id: uuid::Uuid,
+    #[clap(long, required_unless_present = "check")]
+    /// The uid of a node to match the query against
+    node_uid: Option<u64>,
+    #[clap(long, conflicts_with = "query")]
+    /// A file to read the query from
+    file: Option<PathBuf>,
+    #[clap(long)]
+    /// Only check that the query parses, without running it
+    check: bool,
+    #[clap(long, conflicts_with = "check")]
+    /// Print how the query was evaluated along with its result
+    explain: bool,
+    #[clap(required_unless_present = "file")]
+    /// The query to run
+    query: Option<String>,
+}
+
+/// Formats `error` with the line of `query` it's on, underlined.
+fn render_error(query: &str, error: &QueryLanguageError) -> String {
+    let line_start = query[..error.span.start]
+        .rfind('\n')
+        .map_or(0, |newline| newline + 1);
+    let line_end = query[error.span.start..]
+        .find('\n')
+        .map_or(query.len(), |newline| error.span.start + newline);
+    let line_number = query[..line_start].matches('\n').count() + 1;
+
+    let indent = query[line_start..error.span.start].chars().count();
+    let width = query[error.span.start..error.span.end.min(line_end)]
+        .chars()
+        .count()
+        .max(1);
+
+    format!(
+        "error: {message}\n{line_number:>4} | {line}\n     | {indent}{carets}",
+        message = error.message,
+        line = &query[line_start..line_end],
+        indent = " ".repeat(indent),
+        carets = "^".repeat(width),
+    )
+}
+
+fn print_match(matched: &MatchedGraphWithUid) {
+    println!("matched with root {}", matched.root_uid.as_u64());
+    let mut nodes: Vec<_> = matched.matched_graph.nodes.values().collect();
+    nodes.sort_by_key(|node| node.uid);
+    for node in nodes {
+        println!("({}:{})", node.uid.as_u64(), node.node_type);
+        let mut properties: Vec<_> = node.string_properties.prop_map.iter().collect();
+        properties.sort_by(|a, b| a.0.value.cmp(&b.0.value));
+        for (property_name, value) in properties {
+            println!("    {property_name}: {value:?}");
+        }
+    }
+    let mut edges: Vec<_> = matched
+        .matched_graph
+        .edges
+        .iter()
+        .flat_map(|((src, edge_name), dsts)| dsts.iter().map(move |dst| (src, edge_name, dst)))
+        .collect();
+    edges.sort_by(|a, b| (a.0, &a.1.value, a.2).cmp(&(b.0, &b.1.value, b.2)));
+    for (src, edge_name, dst) in edges {
+        println!("({})-[:{}]->({})", src.as_u64(), edge_name, dst.as_u64());
+    }
+}
+
+fn print_explanation(explanation: &QueryExplanation) {
+    println!("explain: took {:?}", explanation.elapsed);
+    for node_query in &explanation.node_queries {
+        println!(
+            "  {} query {}: {} of {} nodes matched, {} reads, {} rows, {} filters, {:?}",
+            node_query.node_type,
+            node_query.query_id.value,
+            node_query.nodes_matched,
+            node_query.nodes_visited,
+            node_query.scylla_reads,
+            node_query.rows_fetched,
+            node_query.filters_evaluated,
+            node_query.elapsed,
+        );
+        for short_circuit in &node_query.short_circuits {
+            println!(
+                "    short-circuited at {}: {}",
+                short_circuit.uid.as_u64(),
+                short_circuit.reason,
+            );
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+    let query = match (&args.file, args.query) {
+        (Some(file), _) => std::fs::read_to_string(file)?,
+        (None, Some(query)) => query,
+        (None, None) => unreachable!("clap requires one of them"),
+    };
+
+    if let Err(e) = TextQuery::parse(&query) {
+        eprintln!("{}", render_error(&query, &e));
+        std::process::exit(1);
+    }
+    if args.check {
+        return Ok(());
+    }
+
+    let node_uid = args
+        .node_uid
+        .and_then(Uid::from_u64)
+        .ok_or("--node-uid must be a nonzero uid")?;
+
+    let graph_query_client_config = Figment::new()
+        .merge(Env::prefixed("GRAPH_QUERY_CLIENT_"))
+        .extract()?;
+    let mut graph_query_client = GraphQueryClient::connect(graph_query_client_config).await?;
+
+    let response = graph_query_client
+        .query_graph_with_text(QueryGraphWithTextRequest {
+            tenant_id: args.tenant_id,
+            node_uid,
+            query_text: query,
+            explain: args.explain,
+        })
+        .await?;
+
+    match &response.maybe_match {
+        MaybeMatchWithUid::Matched(matched) => print_match(matched),
+        MaybeMatchWithUid::Missed(_) => println!("no match"),
+    }
+    if let Some(explanation) = &response.explanation {
+        print_explanation(explanation);
+    }
+
+    Ok(())
+}
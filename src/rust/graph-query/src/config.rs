@@ -23,4 +23,40 @@ pub struct GraphQueryServiceConfig {
 
     #[clap(flatten)]
     pub graph_db_config: GraphDbConfig,
+
+    #[clap(flatten)]
+    pub property_cache_config: PropertyCacheConfig,
+
+    #[clap(flatten)]
+    pub query_limits_config: QueryLimitsConfig,
+}
+
+#[derive(clap::Parser, Clone, Debug)]
+pub struct PropertyCacheConfig {
+    #[clap(long, env, default_value = "100000")]
+    /// How many immutable property values to cache
+    pub immutable_property_cache_capacity: u64,
+    #[clap(long, env, default_value = "10000")]
+    /// How many max, min and string set property values to cache
+    pub mutable_property_cache_capacity: u64,
+    #[clap(long, env, default_value = "1000")]
+    /// How long, in milliseconds, a max, min or string set property value is
+    /// cached for
+    pub mutable_property_cache_ttl_ms: u64,
+}
+
+#[derive(clap::Parser, Clone, Debug)]
+pub struct QueryLimitsConfig {
+    #[clap(long, env, default_value = "5000")]
+    /// How long, in milliseconds, a single graph query may run for
+    pub graph_query_deadline_ms: u64,
+    #[clap(long, env, default_value = "10000")]
+    /// How many reads a single graph query may make from the graph database
+    pub graph_query_read_budget: u64,
+    #[clap(long, env, default_value = "100")]
+    /// How many uids a SearchNodes page scans when the request doesn't say
+    pub search_nodes_default_page_size: u32,
+    #[clap(long, env, default_value = "1000")]
+    /// The most uids a single SearchNodes page may scan
+    pub search_nodes_max_page_size: u32,
 }
@@ -1,4 +1,8 @@
-use futures::future::join_all;
+use futures::stream::{
+    FuturesUnordered,
+    StreamExt,
+};
+use graph_query_matching::RegexCache;
 pub use rust_proto::graplinc::grapl::api::graph_query::v1beta1::messages::StringCmp;
 use rust_proto::graplinc::grapl::{
     api::graph_query::v1beta1::messages::{
@@ -24,17 +28,19 @@ pub enum GraphQueryError {
     NodeQueryError { uid: Uid, source: NodeQueryError },
 }
 
-#[tracing::instrument(skip(graph_query, property_query_executor), err)]
+#[tracing::instrument(skip(graph_query, property_query_executor, regex_cache), err)]
 pub async fn query_graph(
     graph_query: &GraphQuery,
     uid: Uid,
     tenant_id: uuid::Uuid,
     property_query_executor: PropertyQueryExecutor,
+    regex_cache: &RegexCache,
 ) -> Result<Option<(GraphView, Uid)>, GraphQueryError> {
-    // We build N futures, one for each Query in the GraphQuery,
-    // and join them all at the end.
+    // We build N futures, one for each Query in the GraphQuery, and take
+    // whichever matches first. Returning drops the rest, cancelling any reads
+    // they have in flight.
 
-    let mut query_handles = Vec::with_capacity(graph_query.node_property_queries.len());
+    let mut query_handles = FuturesUnordered::new();
     let x_query_short_circuiter = ShortCircuit::new();
     for node_query in graph_query.node_property_queries.values() {
         let property_query_executor = property_query_executor.clone();
@@ -49,6 +55,7 @@ pub async fn query_graph(
                 uid,
                 tenant_id,
                 property_query_executor,
+                regex_cache,
                 visited,
                 x_query_short_circuiter.clone(),
                 &mut root_query_uid,
@@ -65,10 +72,7 @@ pub async fn query_graph(
         });
     }
 
-    // todo: We don't need to join_all, we can stop polling the other futures
-    //       once one of them matches
-    //       try_select may work better
-    for graph in join_all(query_handles).await {
+    while let Some(graph) = query_handles.next().await {
         match graph {
             Ok(Some((graph, Some(root_uid)))) => return Ok(Some((graph, root_uid))),
             Ok(Some((_, None))) => {
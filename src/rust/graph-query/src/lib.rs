@@ -1,8 +1,12 @@
 pub mod config;
 pub mod graph_query;
 pub mod node_query;
+pub mod property_cache;
 pub mod property_query;
+pub mod query_stats;
+pub mod read_budget;
 pub mod server;
 pub mod short_circuit;
+pub mod string_set_resolver;
 pub mod table_names;
 pub mod visited;
@@ -4,10 +4,16 @@ use std::{
 };
 
 use clap::Parser;
+use figment::{
+    providers::Env,
+    Figment,
+};
 use graph_query::{
     config,
+    property_cache::PropertyCache,
     server,
 };
+use grapl_metrics::setup_metrics::setup_metrics;
 use grapl_tracing::setup_tracing;
 use rust_proto::graplinc::grapl::api::{
     graph_query::v1beta1::server::GraphQueryServiceServer,
@@ -25,6 +31,7 @@ const SERVICE_NAME: &'static str = "graph-query";
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let _guard = setup_tracing(SERVICE_NAME)?;
+    let _metrics = setup_metrics()?;
     let config = GraphQueryServiceConfig::parse();
     let mut scylla_config = scylla::SessionConfig::new();
     scylla_config.add_known_nodes_addr(&config.graph_db_config.graph_db_addresses[..]);
@@ -41,7 +48,18 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         scylla::Session::connect(scylla_config).await?,
         10_000,
     ));
-    let graph_query_service = GraphQueryService::new(scylla_client);
+    let property_cache = PropertyCache::new(&config.property_cache_config);
+
+    let graph_schema_manager_client_config = Figment::new()
+        .merge(Env::prefixed("GRAPH_SCHEMA_MANAGER_CLIENT_"))
+        .extract()?;
+
+    let graph_query_service = GraphQueryService::new(
+        scylla_client,
+        property_cache,
+        config.query_limits_config.clone(),
+        graph_schema_manager_client_config,
+    );
 
     exec_service(config, graph_query_service).await
 }
@@ -1,20 +1,37 @@
 use std::{
     cell::RefCell,
+    ops::RangeInclusive,
     rc::Rc,
+    time::Instant,
 };
 
 use async_recursion::async_recursion;
+use bytes::Bytes;
+use graph_query_matching::{
+    match_float,
+    match_int,
+    match_int_property,
+    match_property,
+    match_uid,
+    RegexCache,
+};
 use rust_proto::graplinc::grapl::{
     api::graph_query::v1beta1::messages::{
+        AndFloatFilters,
+        AndIntFilters,
         AndStringFilters,
         GraphQuery,
         GraphView,
         NodePropertiesView,
         NodePropertyQuery,
+        OrIntFilters,
         OrStringFilters,
+        PathFilter,
         QueryId,
-        StrCmp,
+        ShortCircuitExplanation,
         StringProperties,
+        UidFilter,
+        UidOperation,
     },
     common::v1beta1::types::{
         EdgeName,
@@ -27,6 +44,7 @@ use rustc_hash::{
     FxHashMap,
     FxHashSet,
 };
+use tracing::Instrument;
 
 use crate::{
     property_query::{
@@ -45,66 +63,210 @@ pub enum NodeQueryError {
     PropertyQueryError(#[from] PropertyQueryError),
     #[error("Invalid edge key: {0:?}")]
     NoSuchEdgeQueryId(QueryId),
+    #[error("The graph query's edge_map has no reverse edge for {0}")]
+    NoSuchReverseEdge(EdgeName),
+}
+
+/// The reverse of `edge_name`, which queries sent over the wire aren't
+/// guaranteed to include.
+fn reverse_edge_name<'a>(
+    graph_query: &'a GraphQuery,
+    edge_name: &EdgeName,
+) -> Result<&'a EdgeName, NodeQueryError> {
+    graph_query
+        .edge_map
+        .get(edge_name)
+        .ok_or_else(|| NodeQueryError::NoSuchReverseEdge(edge_name.clone()))
+}
+
+/// Why a node failed a node query. Outside of paths' intermediate nodes, a
+/// node failing its query short circuits the traversal it's part of.
+#[derive(Debug, Clone)]
+pub enum Miss {
+    MissingProperty(PropertyName),
+    PropertyMismatch(PropertyName),
+    EdgeCountMismatch(EdgeName),
+    MissingEdges(EdgeName),
+    NoMatchingNeighbor(EdgeName),
+    NoMatchingPath(EdgeName),
+}
+
+impl std::fmt::Display for Miss {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Miss::MissingProperty(property_name) => {
+                write!(f, "property {property_name} is missing")
+            }
+            Miss::PropertyMismatch(property_name) => {
+                write!(f, "property {property_name} didn't match")
+            }
+            Miss::EdgeCountMismatch(edge_name) => {
+                write!(f, "count of {edge_name} edges didn't match")
+            }
+            Miss::MissingEdges(edge_name) => write!(f, "no {edge_name} edges"),
+            Miss::NoMatchingNeighbor(edge_name) => {
+                write!(f, "no {edge_name} neighbor matched")
+            }
+            Miss::NoMatchingPath(edge_name) => write!(f, "no {edge_name} path matched"),
+        }
+    }
 }
 
-pub(crate) fn match_property(
+/// Checks the node's edge counts against every edge count filter on its
+/// query. Edges are counted in Scylla rather than read.
+#[tracing::instrument(skip(node_properties_query, graph_query, property_query_executor))]
+pub async fn match_edge_counts(
     node_properties_query: &NodePropertyQuery,
-    property_name: &PropertyName,
-    property_value: &str,
-) -> bool {
-    'outer: for or_filters in
-        &node_properties_query.string_filters[property_name].and_string_filters
-    {
-        for and_filter in &or_filters.string_filters {
-            match StrCmp::from(and_filter) {
-                StrCmp::Eq(to, negated) => match (negated, property_value == to) {
-                    (false, false) => continue 'outer,
-                    (true, true) => continue 'outer,
-                    (_, _) => (),
-                },
-                StrCmp::Contains(to, negated) => match (negated, property_value.contains(to)) {
-                    (false, false) => continue 'outer,
-                    (true, true) => continue 'outer,
-                    (_, _) => (),
-                },
-                StrCmp::Has => (),
-            };
+    uid: Uid,
+    graph_query: &GraphQuery,
+    tenant_id: uuid::Uuid,
+    property_query_executor: PropertyQueryExecutor,
+) -> Result<Result<(), Miss>, NodeQueryError> {
+    for ((src_id, edge_name), count_filters) in graph_query.edge_count_filters.iter() {
+        if *src_id != node_properties_query.query_id {
+            continue;
+        }
+
+        let count = property_query_executor
+            .count_edges(tenant_id, uid, edge_name)
+            .await?;
+        if !match_int(
+            count_filters,
+            i128::from(count),
+            property_query_executor.node_query_counters(),
+        ) {
+            return Ok(Err(Miss::EdgeCountMismatch(edge_name.clone())));
         }
-        return true;
     }
 
-    false
+    Ok(Ok(()))
 }
 
-#[tracing::instrument(skip(node_properties_query, property_query_executor))]
+#[tracing::instrument(skip(node_properties_query, property_query_executor, regex_cache))]
 pub async fn fetch_node_properties(
     node_properties_query: &NodePropertyQuery,
     uid: Uid,
     tenant_id: uuid::Uuid,
     property_query_executor: PropertyQueryExecutor,
-) -> Result<Option<Vec<StringField>>, NodeQueryError> {
+    regex_cache: &RegexCache,
+) -> Result<Result<Vec<StringField>, Miss>, NodeQueryError> {
+    let counters = property_query_executor.node_query_counters();
     let mut fields = vec![];
     if !node_properties_query.string_filters.is_empty() {
         let mut filter_names: FxHashSet<_> = node_properties_query.string_filters.keys().collect();
 
         for prop_name in node_properties_query.string_filters.keys() {
-            let property = property_query_executor
-                .get_immutable_string(tenant_id, uid, prop_name)
-                .await?;
+            let matches = |field: &StringField| {
+                match_property(
+                    node_properties_query,
+                    prop_name,
+                    &field.value,
+                    regex_cache,
+                    counters,
+                )
+            };
+            let property = if property_query_executor
+                .is_string_set(&node_properties_query.node_type, prop_name)
+            {
+                let string_set = match property_query_executor
+                    .get_string_set(tenant_id, uid, prop_name)
+                    .await?
+                {
+                    Some(string_set) => string_set,
+                    None => return Ok(Err(Miss::MissingProperty(prop_name.clone()))),
+                };
+                // A string set matches when any one of its members does, and
+                // that member is the one the node's view holds
+                string_set.into_fields().find(|field| matches(field))
+            } else {
+                let property = match property_query_executor
+                    .get_immutable_string(tenant_id, uid, prop_name)
+                    .await?
+                {
+                    Some(property) => property,
+                    None => return Ok(Err(Miss::MissingProperty(prop_name.clone()))),
+                };
+                Some(property).filter(|property| matches(property))
+            };
             match property {
-                Some(p) => fields.push(p),
-                None => return Ok(None),
+                Some(property) => fields.push(property),
+                None => return Ok(Err(Miss::PropertyMismatch(prop_name.clone()))),
             }
             filter_names.remove(prop_name);
         }
 
-        if !filter_names.is_empty() {
+        if let Some(prop_name) = filter_names.into_iter().next() {
             // some values didn't exist, not a match
-            return Ok(None);
+            return Ok(Err(Miss::MissingProperty(prop_name.clone())));
         }
     }
 
-    Ok(Some(fields))
+    // Only string properties are part of the node's view so far, so the rest
+    // are only filtered on
+    for prop_name in node_properties_query.int_filters.keys() {
+        let property = property_query_executor
+            .get_int(tenant_id, uid, prop_name)
+            .await?;
+        match property {
+            Some(p) if match_int_property(node_properties_query, prop_name, p.value, counters) => {}
+            Some(_) => return Ok(Err(Miss::PropertyMismatch(prop_name.clone()))),
+            None => return Ok(Err(Miss::MissingProperty(prop_name.clone()))),
+        }
+    }
+
+    for (prop_name, filters) in node_properties_query.timestamp_filters.iter() {
+        let property = property_query_executor
+            .get_timestamp(tenant_id, uid, prop_name)
+            .await?;
+        match property {
+            Some(millis) if match_int(filters, i128::from(millis), counters) => {}
+            Some(_) => return Ok(Err(Miss::PropertyMismatch(prop_name.clone()))),
+            None => return Ok(Err(Miss::MissingProperty(prop_name.clone()))),
+        }
+    }
+
+    for (prop_name, filters) in node_properties_query.float_filters.iter() {
+        let property = property_query_executor
+            .get_f64(tenant_id, uid, prop_name)
+            .await?;
+        match property {
+            Some(value) if match_float(filters, value, counters) => {}
+            Some(_) => return Ok(Err(Miss::PropertyMismatch(prop_name.clone()))),
+            None => return Ok(Err(Miss::MissingProperty(prop_name.clone()))),
+        }
+    }
+
+    for (prop_name, expected) in node_properties_query.bool_filters.iter() {
+        let property = property_query_executor
+            .get_bool(tenant_id, uid, prop_name)
+            .await?;
+        match property {
+            Some(value) => {
+                counters.add_filter();
+                if value != *expected {
+                    return Ok(Err(Miss::PropertyMismatch(prop_name.clone())));
+                }
+            }
+            None => return Ok(Err(Miss::MissingProperty(prop_name.clone()))),
+        }
+    }
+
+    for (prop_name, expected) in node_properties_query.bytes_filters.iter() {
+        let property = property_query_executor
+            .get_bytes(tenant_id, uid, prop_name)
+            .await?;
+        match property {
+            Some(value) => {
+                counters.add_filter();
+                if value != *expected {
+                    return Ok(Err(Miss::PropertyMismatch(prop_name.clone())));
+                }
+            }
+            None => return Ok(Err(Miss::MissingProperty(prop_name.clone()))),
+        }
+    }
+
+    Ok(Ok(fields))
 }
 
 #[tracing::instrument(skip(node_properties_query, graph_query, property_query_executor))]
@@ -114,7 +276,7 @@ pub async fn fetch_edges(
     graph_query: &GraphQuery,
     tenant_id: uuid::Uuid,
     property_query_executor: PropertyQueryExecutor,
-) -> Result<Option<FxHashMap<EdgeName, Vec<EdgeRow>>>, NodeQueryError> {
+) -> Result<Result<FxHashMap<EdgeName, Vec<EdgeRow>>, Miss>, NodeQueryError> {
     let mut edge_rows = FxHashMap::default();
     for (src_id, edge_name) in graph_query.edge_filters.keys() {
         if *src_id != node_properties_query.query_id {
@@ -126,18 +288,38 @@ pub async fn fetch_edges(
             .await?;
 
         let rows = match rows {
-            None => return Ok(None),
+            None => return Ok(Err(Miss::MissingEdges(edge_name.clone()))),
             Some(rows) => rows,
         };
         debug_assert!(!rows.is_empty());
 
-        println!("edge name {}, rows {:?}", edge_name, rows);
+        tracing::debug!(
+            message = "Fetched edges",
+            edge_name = %edge_name,
+            count = rows.len(),
+        );
         edge_rows.insert(edge_name.to_owned(), rows);
     }
 
-    Ok(Some(edge_rows))
+    Ok(Ok(edge_rows))
+}
+
+/// The result of evaluating a node against a node query.
+enum Evaluation {
+    Matched(GraphView),
+    /// The node didn't match, but the rest of the traversal may still, or
+    /// the traversal was already over
+    Missed,
+    /// The node didn't match, and neither can the rest of the traversal
+    ShortCircuited(Miss),
 }
 
+/// Matches the node `uid` against `node_properties_query`, and its neighbors
+/// against their node queries, returning the graph that matched.
+///
+/// Each node is evaluated in a `node_query` span, which records what the node
+/// cost and why it short circuited, if it did. The same figures are totalled
+/// up per node query in the executor's QueryStats.
 #[async_recursion]
 pub async fn fetch_node_with_edges(
     node_properties_query: &NodePropertyQuery,
@@ -145,6 +327,7 @@ pub async fn fetch_node_with_edges(
     uid: Uid,
     tenant_id: uuid::Uuid,
     property_query_executor: PropertyQueryExecutor,
+    regex_cache: &RegexCache,
     visited: Visited,
     x_short_circuit: ShortCircuit,
     root_node_uid: &mut Option<Uid>,
@@ -153,6 +336,88 @@ pub async fn fetch_node_with_edges(
         return Ok(None);
     }
 
+    let query_id = node_properties_query.query_id;
+    let span = tracing::info_span!(
+        "node_query",
+        query_id = query_id.value,
+        uid = uid.as_u64(),
+        scylla_reads = tracing::field::Empty,
+        rows_fetched = tracing::field::Empty,
+        filters_evaluated = tracing::field::Empty,
+        short_circuit = tracing::field::Empty,
+        elapsed_ms = tracing::field::Empty,
+    );
+    let property_query_executor = property_query_executor.for_node_query();
+    let started = Instant::now();
+
+    let evaluation = evaluate_node(
+        node_properties_query,
+        graph_query,
+        uid,
+        tenant_id,
+        property_query_executor.clone(),
+        regex_cache,
+        visited.clone(),
+        x_short_circuit,
+        root_node_uid,
+    )
+    .instrument(span.clone())
+    .await?;
+
+    let elapsed = started.elapsed();
+    let counters = property_query_executor.node_query_counters();
+    span.record("scylla_reads", counters.scylla_reads());
+    span.record("rows_fetched", counters.rows_fetched());
+    span.record("filters_evaluated", counters.filters_evaluated());
+    span.record("elapsed_ms", elapsed.as_secs_f64() * 1000.0);
+
+    let (graph, short_circuit) = match evaluation {
+        Evaluation::Matched(graph) => (Some(graph), None),
+        Evaluation::Missed => (None, None),
+        Evaluation::ShortCircuited(miss) => {
+            visited.set_short_circuit();
+            span.record("short_circuit", tracing::field::display(&miss));
+            let short_circuit = ShortCircuitExplanation {
+                uid,
+                reason: miss.to_string(),
+            };
+            (None, Some(short_circuit))
+        }
+    };
+    property_query_executor.query_stats().record(
+        query_id,
+        &node_properties_query.node_type,
+        counters,
+        graph.is_some(),
+        short_circuit,
+        elapsed,
+    );
+
+    Ok(graph)
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn evaluate_node(
+    node_properties_query: &NodePropertyQuery,
+    graph_query: &GraphQuery,
+    uid: Uid,
+    tenant_id: uuid::Uuid,
+    property_query_executor: PropertyQueryExecutor,
+    regex_cache: &RegexCache,
+    visited: Visited,
+    x_short_circuit: ShortCircuit,
+    root_node_uid: &mut Option<Uid>,
+) -> Result<Evaluation, NodeQueryError> {
+    let counters = property_query_executor.node_query_counters();
+
+    // Checked before any properties are fetched, so that the nodes around a
+    // pinned node cost no reads. Not being the pinned node says nothing about
+    // the rest of the traversal, so unlike a property miss this doesn't short
+    // circuit it: a sibling may still be the pinned node.
+    if !match_uid(node_properties_query, uid, counters) {
+        return Ok(Evaluation::Missed);
+    }
+
     let mut node = NodePropertiesView::new(
         uid,
         node_properties_query.node_type.clone(),
@@ -164,31 +429,31 @@ pub async fn fetch_node_with_edges(
         uid,
         tenant_id,
         property_query_executor.clone(),
+        regex_cache,
     )
     .await?;
 
     let node_properties = match node_properties {
-        None => {
-            visited.set_short_circuit();
-            return Ok(None);
-        }
-        Some(node_properties) => node_properties,
+        Err(miss) => return Ok(Evaluation::ShortCircuited(miss)),
+        Ok(node_properties) => node_properties,
     };
 
-    for node_property in node_properties.iter() {
-        if match_property(
-            node_properties_query,
-            &node_property.populated_field,
-            &node_property.value,
-        ) {
-            node.add_string_property(
-                node_property.populated_field.clone(),
-                node_property.value.clone(),
-            );
-        } else {
-            visited.set_short_circuit();
-            return Ok(None);
-        }
+    // Each property has already been matched against its filters
+    for node_property in node_properties {
+        node.add_string_property(node_property.populated_field, node_property.value);
+    }
+
+    // Like a property miss, too few or too many edges fails the whole query
+    if let Err(miss) = match_edge_counts(
+        node_properties_query,
+        uid,
+        graph_query,
+        tenant_id,
+        property_query_executor.clone(),
+    )
+    .await?
+    {
+        return Ok(Evaluation::ShortCircuited(miss));
     }
 
     if node_properties_query.query_id == graph_query.root_query_id {
@@ -204,7 +469,7 @@ pub async fn fetch_node_with_edges(
     );
 
     if x_short_circuit.get_short_circuit() {
-        return Ok(None);
+        return Ok(Evaluation::Missed);
     }
 
     // fetch the edges for the uid
@@ -217,11 +482,8 @@ pub async fn fetch_node_with_edges(
     )
     .await?
     {
-        Some(edges) => edges,
-        None => {
-            visited.set_short_circuit();
-            return Ok(None);
-        }
+        Ok(edges) => edges,
+        Err(miss) => return Ok(Evaluation::ShortCircuited(miss)),
     };
 
     for ((src_id, edge_name), edge_queries) in graph_query.edge_filters.iter() {
@@ -244,9 +506,10 @@ pub async fn fetch_node_with_edges(
                 continue;
             }
 
+            let reverse_edge_name = reverse_edge_name(graph_query, edge_name)?;
             if visited.check_and_add(
                 edge_query.query_id,
-                graph_query.edge_map[edge_name].to_owned(),
+                reverse_edge_name.to_owned(),
                 node_properties_query.query_id,
             ) {
                 continue;
@@ -258,7 +521,7 @@ pub async fn fetch_node_with_edges(
             for edge_row in edge_rows {
                 // we can do this in parallel
                 if x_short_circuit.get_short_circuit() {
-                    return Ok(None);
+                    return Ok(Evaluation::Missed);
                 }
                 let neighbors = match fetch_node_with_edges(
                     edge_query,
@@ -266,6 +529,7 @@ pub async fn fetch_node_with_edges(
                     edge_row.destination_uid,
                     tenant_id,
                     property_query_executor.clone(),
+                    regex_cache,
                     visited.clone(),
                     x_short_circuit.clone(),
                     root_node_uid,
@@ -278,19 +542,242 @@ pub async fn fetch_node_with_edges(
                 any = true;
                 for neighbor in neighbors.nodes.keys() {
                     graph.add_edge(uid, edge_name.to_owned(), *neighbor);
-                    graph.add_edge(*neighbor, graph_query.edge_map[edge_name].to_owned(), uid);
+                    graph.add_edge(*neighbor, reverse_edge_name.to_owned(), uid);
                 }
                 graph.merge(neighbors);
             }
             if !any {
                 // if a given query has no matches, return
-                visited.set_short_circuit();
-                return Ok(None);
+                return Ok(Evaluation::ShortCircuited(Miss::NoMatchingNeighbor(
+                    edge_name.clone(),
+                )));
+            }
+        }
+    }
+
+    for ((src_id, edge_name), path_filters) in graph_query.path_filters.iter() {
+        if *src_id != node_properties_query.query_id {
+            continue;
+        }
+
+        for path_filter in path_filters {
+            let neighbor_query = match graph_query
+                .node_property_queries
+                .get(&path_filter.neighbor_query_id)
+            {
+                Some(neighbor_query) => neighbor_query,
+                None => {
+                    return Err(NodeQueryError::NoSuchEdgeQueryId(
+                        path_filter.neighbor_query_id,
+                    ))
+                }
+            };
+            // as with edges, the path may already have been walked from its
+            // other end
+            if visited.check_and_add(
+                node_properties_query.query_id,
+                edge_name.clone(),
+                neighbor_query.query_id,
+            ) {
+                continue;
+            }
+
+            if visited.check_and_add(
+                neighbor_query.query_id,
+                reverse_edge_name(graph_query, edge_name)?.to_owned(),
+                node_properties_query.query_id,
+            ) {
+                continue;
+            }
+
+            let path = fetch_path(
+                node_properties_query,
+                neighbor_query,
+                path_filter,
+                edge_name,
+                graph_query,
+                uid,
+                tenant_id,
+                property_query_executor.clone(),
+                regex_cache,
+                visited.clone(),
+                x_short_circuit.clone(),
+                root_node_uid,
+            )
+            .await?;
+
+            match path {
+                Some(path) => graph.merge(path),
+                None => {
+                    return Ok(Evaluation::ShortCircuited(Miss::NoMatchingPath(
+                        edge_name.clone(),
+                    )))
+                }
             }
         }
     }
 
-    Ok(Some(graph))
+    Ok(Evaluation::Matched(graph))
+}
+
+/// Searches breadth first along `edge_name` from `uid` for a node that matches
+/// `neighbor_query` and is between `min_hops` and `max_hops` edges away,
+/// returning the path to the first one found merged with the graph that the
+/// neighbor matched.
+///
+/// Each node is searched from at most once, at the fewest hops it can be
+/// reached in, so a cycle ends the search instead of looping it and a node
+/// is only ever as many hops away as its shortest path. Every endpoint
+/// candidate is evaluated against a fork of `visited`, so one that doesn't
+/// match can't short circuit the candidates after it.
+#[allow(clippy::too_many_arguments)]
+async fn fetch_path(
+    node_properties_query: &NodePropertyQuery,
+    neighbor_query: &NodePropertyQuery,
+    path_filter: &PathFilter,
+    edge_name: &EdgeName,
+    graph_query: &GraphQuery,
+    uid: Uid,
+    tenant_id: uuid::Uuid,
+    property_query_executor: PropertyQueryExecutor,
+    regex_cache: &RegexCache,
+    visited: Visited,
+    x_short_circuit: ShortCircuit,
+    root_node_uid: &mut Option<Uid>,
+) -> Result<Option<GraphView>, NodeQueryError> {
+    let reverse_edge_name = reverse_edge_name(graph_query, edge_name)?;
+    let src_id = node_properties_query.query_id;
+    let dst_id = neighbor_query.query_id;
+    visited.check_and_add_path_node(src_id, edge_name.clone(), dst_id, uid);
+
+    // Where each node was reached from, to walk the path back from its end
+    let mut previous: FxHashMap<Uid, Uid> = FxHashMap::default();
+    let mut intermediate_nodes: FxHashMap<Uid, NodePropertiesView> = FxHashMap::default();
+    let mut frontier = vec![uid];
+
+    for hops in 1..=path_filter.max_hops {
+        let mut next_frontier = vec![];
+        for from_uid in frontier {
+            let edge_rows = match property_query_executor
+                .get_edges(tenant_id, from_uid, edge_name)
+                .await?
+            {
+                Some(edge_rows) => edge_rows,
+                None => continue,
+            };
+
+            for edge_row in edge_rows {
+                if x_short_circuit.get_short_circuit() {
+                    return Ok(None);
+                }
+                let to_uid = edge_row.destination_uid;
+                if visited.check_and_add_path_node(src_id, edge_name.clone(), dst_id, to_uid) {
+                    continue;
+                }
+                previous.insert(to_uid, from_uid);
+
+                if hops >= path_filter.min_hops {
+                    let candidate_visited = visited.fork();
+                    let neighbor_graph = fetch_node_with_edges(
+                        neighbor_query,
+                        graph_query,
+                        to_uid,
+                        tenant_id,
+                        property_query_executor.clone(),
+                        regex_cache,
+                        candidate_visited.clone(),
+                        x_short_circuit.clone(),
+                        root_node_uid,
+                    )
+                    .await?;
+
+                    if let Some(mut graph) = neighbor_graph {
+                        visited.join(&candidate_visited);
+                        let mut to_uid = to_uid;
+                        while to_uid != uid {
+                            let from_uid = previous[&to_uid];
+                            if let Some(node) = intermediate_nodes.remove(&from_uid) {
+                                graph.add_node(node);
+                            }
+                            graph.add_edge(from_uid, edge_name.to_owned(), to_uid);
+                            graph.add_edge(to_uid, reverse_edge_name.to_owned(), from_uid);
+                            to_uid = from_uid;
+                        }
+                        return Ok(Some(graph));
+                    }
+                }
+
+                if hops < path_filter.max_hops {
+                    if let Some(node) = fetch_intermediate_node(
+                        neighbor_query,
+                        path_filter,
+                        to_uid,
+                        tenant_id,
+                        property_query_executor.clone(),
+                        regex_cache,
+                    )
+                    .await?
+                    {
+                        intermediate_nodes.insert(to_uid, node);
+                        next_frontier.push(to_uid);
+                    }
+                }
+            }
+        }
+
+        if next_frontier.is_empty() {
+            break;
+        }
+        frontier = next_frontier;
+    }
+
+    Ok(None)
+}
+
+/// The view of a node between the two ends of a path, if it matches the
+/// path's intermediate node query. Every edge along the path has the same
+/// name, so intermediate nodes have the same type as the path's neighbor.
+async fn fetch_intermediate_node(
+    neighbor_query: &NodePropertyQuery,
+    path_filter: &PathFilter,
+    uid: Uid,
+    tenant_id: uuid::Uuid,
+    property_query_executor: PropertyQueryExecutor,
+    regex_cache: &RegexCache,
+) -> Result<Option<NodePropertiesView>, NodeQueryError> {
+    let mut node = NodePropertiesView::new(
+        uid,
+        neighbor_query.node_type.clone(),
+        StringProperties::default(),
+    );
+
+    let intermediate_query = match &path_filter.intermediate_node_query {
+        Some(intermediate_query) => intermediate_query,
+        None => return Ok(Some(node)),
+    };
+    let counters = property_query_executor.node_query_counters();
+    if !match_uid(intermediate_query, uid, counters) {
+        return Ok(None);
+    }
+
+    let node_properties = fetch_node_properties(
+        intermediate_query,
+        uid,
+        tenant_id,
+        property_query_executor.clone(),
+        regex_cache,
+    )
+    .await?;
+    let node_properties = match node_properties {
+        Ok(node_properties) => node_properties,
+        Err(_) => return Ok(None),
+    };
+
+    for node_property in node_properties {
+        node.add_string_property(node_property.populated_field, node_property.value);
+    }
+
+    Ok(Some(node))
 }
 
 // Note: Different from the rust_proto NodeQuery.
@@ -308,6 +795,10 @@ impl NodeQuery {
             int_filters: Default::default(),
             string_filters: Default::default(),
             uid_filters: Default::default(),
+            float_filters: Default::default(),
+            timestamp_filters: Default::default(),
+            bool_filters: Default::default(),
+            bytes_filters: Default::default(),
         };
         let mut node_property_queries = FxHashMap::default();
         node_property_queries.insert(query_id, inner_query);
@@ -317,6 +808,8 @@ impl NodeQuery {
             node_property_queries,
             edge_filters: Default::default(),
             edge_map: Default::default(),
+            path_filters: Default::default(),
+            edge_count_filters: Default::default(),
         };
 
         Self {
@@ -343,6 +836,113 @@ impl NodeQuery {
         self
     }
 
+    pub fn with_int_comparisons(
+        &mut self,
+        property_name: PropertyName,
+        comparisons: impl Into<AndIntFilters>,
+    ) -> &mut Self {
+        let mut inner = self.graph.as_mut().unwrap().borrow_mut();
+        inner
+            .node_property_queries
+            .get_mut(&self.query_id)
+            .unwrap()
+            .int_filters
+            .entry(property_name)
+            .or_insert_with(OrIntFilters::new)
+            .push(comparisons.into());
+        drop(inner);
+        self
+    }
+
+    pub fn with_float_comparisons(
+        &mut self,
+        property_name: PropertyName,
+        comparisons: impl Into<AndFloatFilters>,
+    ) -> &mut Self {
+        let mut inner = self.graph.as_mut().unwrap().borrow_mut();
+        inner
+            .node_property_queries
+            .get_mut(&self.query_id)
+            .unwrap()
+            .with_float_filters(property_name, comparisons);
+        drop(inner);
+        self
+    }
+
+    /// As `with_int_comparisons`, comparing against the timestamp in
+    /// milliseconds since the Unix epoch.
+    pub fn with_timestamp_comparisons(
+        &mut self,
+        property_name: PropertyName,
+        comparisons: impl Into<AndIntFilters>,
+    ) -> &mut Self {
+        let mut inner = self.graph.as_mut().unwrap().borrow_mut();
+        inner
+            .node_property_queries
+            .get_mut(&self.query_id)
+            .unwrap()
+            .with_timestamp_filters(property_name, comparisons);
+        drop(inner);
+        self
+    }
+
+    pub fn with_bool(&mut self, property_name: PropertyName, value: bool) -> &mut Self {
+        let mut inner = self.graph.as_mut().unwrap().borrow_mut();
+        inner
+            .node_property_queries
+            .get_mut(&self.query_id)
+            .unwrap()
+            .with_bool_filter(property_name, value);
+        drop(inner);
+        self
+    }
+
+    pub fn with_bytes(
+        &mut self,
+        property_name: PropertyName,
+        value: impl Into<Bytes>,
+    ) -> &mut Self {
+        let mut inner = self.graph.as_mut().unwrap().borrow_mut();
+        inner
+            .node_property_queries
+            .get_mut(&self.query_id)
+            .unwrap()
+            .with_bytes_filter(property_name, value);
+        drop(inner);
+        self
+    }
+
+    /// Constrains how many `edge_name` edges the node has, whatever their
+    /// neighbors are.
+    pub fn with_edge_count(
+        &mut self,
+        edge_name: EdgeName,
+        comparisons: impl Into<AndIntFilters>,
+    ) -> &mut Self {
+        let mut inner = self.graph.as_mut().unwrap().borrow_mut();
+        inner
+            .edge_count_filters
+            .entry((self.query_id, edge_name))
+            .or_insert_with(OrIntFilters::new)
+            .push(comparisons.into());
+        drop(inner);
+        self
+    }
+
+    pub fn with_uid(&mut self, uid: Uid) -> &mut Self {
+        let mut inner = self.graph.as_mut().unwrap().borrow_mut();
+        inner
+            .node_property_queries
+            .get_mut(&self.query_id)
+            .unwrap()
+            .with_uid_filter(UidFilter {
+                operation: UidOperation::Equal,
+                value: uid,
+            });
+        drop(inner);
+        self
+    }
+
     pub fn overwrite_string_comparisons(
         &mut self,
         property_name: PropertyName,
@@ -441,6 +1041,64 @@ impl NodeQuery {
         self
     }
 
+    /// Requires a path of between `hops.start()` and `hops.end()` edges named
+    /// `edge_name` from this node to a node of `node_type`. Every node in
+    /// between must match `intermediate_node_query`, if there is one.
+    pub fn with_path_to(
+        &mut self,
+        edge_name: EdgeName,
+        reverse_edge_name: EdgeName,
+        node_type: NodeType,
+        hops: RangeInclusive<u32>,
+        intermediate_node_query: Option<NodePropertyQuery>,
+        init_path: impl FnOnce(&mut Self),
+    ) -> &mut Self {
+        let new_neighbor_id = QueryId::default();
+
+        {
+            let graph = self.graph.as_mut().unwrap();
+            let mut graph = graph.borrow_mut();
+            graph.add_node(new_neighbor_id, node_type);
+        }
+
+        let mut neighbor = Self {
+            query_id: new_neighbor_id,
+            graph: self.graph.clone(),
+        };
+
+        init_path(&mut neighbor);
+        neighbor.graph = None;
+        {
+            let graph = self.graph.as_mut().unwrap();
+            let mut graph = graph.borrow_mut();
+            let path_filter = PathFilter {
+                neighbor_query_id: new_neighbor_id,
+                min_hops: *hops.start(),
+                max_hops: *hops.end(),
+                intermediate_node_query,
+            };
+            let reverse_path_filter = PathFilter {
+                neighbor_query_id: self.query_id,
+                ..path_filter.clone()
+            };
+            graph
+                .path_filters
+                .entry((self.query_id, edge_name.clone()))
+                .or_default()
+                .push(path_filter);
+            graph
+                .path_filters
+                .entry((new_neighbor_id, reverse_edge_name.clone()))
+                .or_default()
+                .push(reverse_path_filter);
+            graph
+                .edge_map
+                .insert(edge_name.clone(), reverse_edge_name.clone());
+            graph.edge_map.insert(reverse_edge_name, edge_name);
+        }
+        self
+    }
+
     pub fn build(&mut self) -> GraphQuery {
         // This will panic if you have not attached this node to a graph ie: it must be attached
         // to a root node somewhere
@@ -452,6 +1110,8 @@ impl NodeQuery {
             node_property_queries: Default::default(),
             edge_filters: Default::default(),
             edge_map: Default::default(),
+            path_filters: Default::default(),
+            edge_count_filters: Default::default(),
         })
     }
 }
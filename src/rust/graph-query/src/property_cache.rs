@@ -1,44 +1,397 @@
 use std::{
-    fmt::Debug,
-    hash::Hash,
+    future::Future,
+    time::Duration,
 };
 
-use moka::future::{
-    Cache,
-    CacheBuilder,
+use bytes::Bytes;
+use moka::future::Cache;
+use opentelemetry::{
+    global,
+    metrics::Counter,
+    Context,
+    KeyValue,
+};
+use rust_proto::graplinc::grapl::common::v1beta1::types::{
+    PropertyName,
+    Uid,
 };
-use rust_proto::graplinc::grapl::common::v1beta1::types::Uid;
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
-pub enum CacheMatch {
-    Matched,
-    NotMatched,
-    CouldMatch,
-}
+
+use crate::config::PropertyCacheConfig;
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Key {
+    table_name: &'static str,
     property_name: String,
     uid: Uid,
     tenant_id: uuid::Uuid,
 }
 
+impl Key {
+    pub fn new(
+        table_name: &'static str,
+        tenant_id: uuid::Uuid,
+        uid: Uid,
+        property_name: &PropertyName,
+    ) -> Self {
+        Self {
+            table_name,
+            property_name: property_name.value.clone(),
+            uid,
+            tenant_id,
+        }
+    }
+}
+
+/// Caches property values read from Scylla.
+///
+/// Immutable properties can't change once they're written, so they're cached
+/// until they're evicted. Max, min and string set properties can, so they're
+/// only cached for a short time, which bounds how stale a value a query may
+/// see. An immutable property that isn't set may still be set later, so its
+/// absence is cached for the same short time; mutable properties that aren't
+/// set aren't cached. Timestamps are cached as integers, in milliseconds since
+/// the Unix epoch.
 #[derive(Clone)]
 pub struct PropertyCache {
-    string_cache: Cache<Key, String>,
-    int_cache: Cache<Key, i64>,
-    uint_cache: Cache<Key, u64>,
+    immutable_strings: Cache<Key, String>,
+    immutable_ints: Cache<Key, i64>,
+    mutable_ints: Cache<Key, i64>,
+    string_sets: Cache<Key, Vec<String>>,
+    immutable_bools: Cache<Key, bool>,
+    immutable_f64s: Cache<Key, f64>,
+    immutable_bytes: Cache<Key, Bytes>,
+    /// Immutable properties found not to be set
+    absent_immutables: Cache<Key, ()>,
+    metrics: CacheMetrics,
 }
 
 impl PropertyCache {
-    pub fn new(
-        string_cache: Cache<Key, String>,
-        int_cache: Cache<Key, i64>,
-        uint_cache: Cache<Key, u64>,
-    ) -> Self {
+    pub fn new(config: &PropertyCacheConfig) -> Self {
+        let immutable_capacity = config.immutable_property_cache_capacity;
+        let mutable_capacity = config.mutable_property_cache_capacity;
+        let mutable_ttl = Duration::from_millis(config.mutable_property_cache_ttl_ms);
+
+        Self {
+            immutable_strings: Cache::new(immutable_capacity),
+            immutable_ints: Cache::new(immutable_capacity),
+            mutable_ints: expiring_cache(mutable_capacity, mutable_ttl),
+            string_sets: expiring_cache(mutable_capacity, mutable_ttl),
+            immutable_bools: Cache::new(immutable_capacity),
+            immutable_f64s: Cache::new(immutable_capacity),
+            immutable_bytes: Cache::new(immutable_capacity),
+            absent_immutables: expiring_cache(mutable_capacity, mutable_ttl),
+            metrics: CacheMetrics::new(),
+        }
+    }
+
+    pub async fn get_immutable_string<E>(
+        &self,
+        key: Key,
+        fetch: impl Future<Output = Result<Option<String>, E>>,
+    ) -> Result<Option<String>, E> {
+        self.get_immutable(&self.immutable_strings, key, fetch)
+            .await
+    }
+
+    pub async fn get_int<E>(
+        &self,
+        key: Key,
+        is_immutable: bool,
+        fetch: impl Future<Output = Result<Option<i64>, E>>,
+    ) -> Result<Option<i64>, E> {
+        if is_immutable {
+            self.get_immutable(&self.immutable_ints, key, fetch).await
+        } else {
+            get_or_fetch(&self.mutable_ints, None, &self.metrics, key, fetch).await
+        }
+    }
+
+    pub async fn get_string_set<E>(
+        &self,
+        key: Key,
+        fetch: impl Future<Output = Result<Option<Vec<String>>, E>>,
+    ) -> Result<Option<Vec<String>>, E> {
+        get_or_fetch(&self.string_sets, None, &self.metrics, key, fetch).await
+    }
+
+    pub async fn get_bool<E>(
+        &self,
+        key: Key,
+        fetch: impl Future<Output = Result<Option<bool>, E>>,
+    ) -> Result<Option<bool>, E> {
+        self.get_immutable(&self.immutable_bools, key, fetch).await
+    }
+
+    pub async fn get_f64<E>(
+        &self,
+        key: Key,
+        fetch: impl Future<Output = Result<Option<f64>, E>>,
+    ) -> Result<Option<f64>, E> {
+        self.get_immutable(&self.immutable_f64s, key, fetch).await
+    }
+
+    pub async fn get_bytes<E>(
+        &self,
+        key: Key,
+        fetch: impl Future<Output = Result<Option<Bytes>, E>>,
+    ) -> Result<Option<Bytes>, E> {
+        self.get_immutable(&self.immutable_bytes, key, fetch).await
+    }
+
+    async fn get_immutable<V, E>(
+        &self,
+        cache: &Cache<Key, V>,
+        key: Key,
+        fetch: impl Future<Output = Result<Option<V>, E>>,
+    ) -> Result<Option<V>, E>
+    where
+        V: Clone + Send + Sync + 'static,
+    {
+        get_or_fetch(
+            cache,
+            Some(&self.absent_immutables),
+            &self.metrics,
+            key,
+            fetch,
+        )
+        .await
+    }
+}
+
+fn expiring_cache<V>(capacity: u64, time_to_live: Duration) -> Cache<Key, V>
+where
+    V: Clone + Send + Sync + 'static,
+{
+    Cache::builder()
+        .max_capacity(capacity)
+        .time_to_live(time_to_live)
+        .build()
+}
+
+/// Answers from `cache`, or from `absent` if the property is known not to be
+/// set, before falling back to `fetch`.
+async fn get_or_fetch<V, E>(
+    cache: &Cache<Key, V>,
+    absent: Option<&Cache<Key, ()>>,
+    metrics: &CacheMetrics,
+    key: Key,
+    fetch: impl Future<Output = Result<Option<V>, E>>,
+) -> Result<Option<V>, E>
+where
+    V: Clone + Send + Sync + 'static,
+{
+    if let Some(value) = cache.get(&key) {
+        metrics.hit(key.table_name);
+        return Ok(Some(value));
+    }
+    if absent.map_or(false, |absent| absent.get(&key).is_some()) {
+        metrics.hit(key.table_name);
+        return Ok(None);
+    }
+    metrics.miss(key.table_name);
+
+    let value = fetch.await?;
+    match (&value, absent) {
+        (Some(value), _) => cache.insert(key, value.clone()).await,
+        (None, Some(absent)) => absent.insert(key, ()).await,
+        (None, None) => {}
+    }
+    Ok(value)
+}
+
+#[derive(Clone)]
+struct CacheMetrics {
+    hits: Counter<u64>,
+    misses: Counter<u64>,
+}
+
+impl CacheMetrics {
+    fn new() -> Self {
+        let meter = global::meter("graph-query");
         Self {
-            string_cache,
-            int_cache,
-            uint_cache,
+            hits: meter
+                .u64_counter("property_cache_hits")
+                .with_description("Property reads answered by the property cache")
+                .init(),
+            misses: meter
+                .u64_counter("property_cache_misses")
+                .with_description("Property reads that went to Scylla")
+                .init(),
         }
     }
+
+    fn hit(&self, table_name: &'static str) {
+        self.hits.add(
+            &Context::current(),
+            1,
+            &[KeyValue::new("table_name", table_name)],
+        );
+    }
+
+    fn miss(&self, table_name: &'static str) {
+        self.misses.add(
+            &Context::current(),
+            1,
+            &[KeyValue::new("table_name", table_name)],
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        convert::Infallible,
+        sync::atomic::{
+            AtomicUsize,
+            Ordering,
+        },
+    };
+
+    use super::*;
+
+    const TTL_MS: u64 = 50;
+
+    fn property_cache() -> PropertyCache {
+        PropertyCache::new(&PropertyCacheConfig {
+            immutable_property_cache_capacity: 100,
+            mutable_property_cache_capacity: 100,
+            mutable_property_cache_ttl_ms: TTL_MS,
+        })
+    }
+
+    fn key(table_name: &'static str) -> Key {
+        Key::new(
+            table_name,
+            uuid::Uuid::nil(),
+            Uid::from_u64(1).expect("invalid uid"),
+            &PropertyName::new_unchecked("property".to_owned()),
+        )
+    }
+
+    /// Returns `value`, counting how many times the store was read.
+    async fn fetch<V>(fetches: &AtomicUsize, value: Option<V>) -> Result<Option<V>, Infallible> {
+        fetches.fetch_add(1, Ordering::SeqCst);
+        Ok(value)
+    }
+
+    async fn expire() {
+        tokio::time::sleep(Duration::from_millis(TTL_MS * 2)).await;
+    }
+
+    #[tokio::test]
+    async fn test_hit_skips_the_store() -> Result<(), Infallible> {
+        let cache = property_cache();
+        let fetches = AtomicUsize::new(0);
+
+        for _ in 0..2 {
+            let value = cache
+                .get_immutable_string(key("imm_string"), fetch(&fetches, Some("a".to_owned())))
+                .await?;
+            assert_eq!(value.as_deref(), Some("a"));
+        }
+        assert_eq!(fetches.load(Ordering::SeqCst), 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_miss_reads_the_store() -> Result<(), Infallible> {
+        let cache = property_cache();
+        let fetches = AtomicUsize::new(0);
+
+        cache
+            .get_int(key("imm_i64"), true, fetch(&fetches, Some(1)))
+            .await?;
+        // The same property in another table is a different key.
+        let value = cache
+            .get_int(key("max_i64"), false, fetch(&fetches, Some(2)))
+            .await?;
+        assert_eq!(value, Some(2));
+        assert_eq!(fetches.load(Ordering::SeqCst), 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_mutable_values_expire() -> Result<(), Infallible> {
+        let cache = property_cache();
+        let fetches = AtomicUsize::new(0);
+
+        cache
+            .get_int(key("max_i64"), false, fetch(&fetches, Some(1)))
+            .await?;
+        cache
+            .get_int(key("max_i64"), false, fetch(&fetches, Some(2)))
+            .await?;
+        assert_eq!(fetches.load(Ordering::SeqCst), 1);
+
+        expire().await;
+        let value = cache
+            .get_int(key("max_i64"), false, fetch(&fetches, Some(2)))
+            .await?;
+        assert_eq!(value, Some(2));
+        assert_eq!(fetches.load(Ordering::SeqCst), 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_immutable_values_do_not_expire() -> Result<(), Infallible> {
+        let cache = property_cache();
+        let fetches = AtomicUsize::new(0);
+
+        cache
+            .get_bool(key("imm_bool"), fetch(&fetches, Some(true)))
+            .await?;
+        expire().await;
+        let value = cache
+            .get_bool(key("imm_bool"), fetch(&fetches, Some(false)))
+            .await?;
+        assert_eq!(value, Some(true));
+        assert_eq!(fetches.load(Ordering::SeqCst), 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_absent_immutables_are_cached_until_expiry() -> Result<(), Infallible> {
+        let cache = property_cache();
+        let fetches = AtomicUsize::new(0);
+
+        for _ in 0..2 {
+            let value = cache.get_f64(key("imm_f64"), fetch(&fetches, None)).await?;
+            assert_eq!(value, None);
+        }
+        assert_eq!(fetches.load(Ordering::SeqCst), 1);
+
+        // The property may be set later, so its absence isn't kept forever.
+        expire().await;
+        let value = cache
+            .get_f64(key("imm_f64"), fetch(&fetches, Some(0.5)))
+            .await?;
+        assert_eq!(value, Some(0.5));
+        assert_eq!(fetches.load(Ordering::SeqCst), 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_absent_mutables_are_not_cached() -> Result<(), Infallible> {
+        let cache = property_cache();
+        let fetches = AtomicUsize::new(0);
+
+        cache
+            .get_string_set(key("string_set"), fetch(&fetches, None))
+            .await?;
+        let value = cache
+            .get_string_set(
+                key("string_set"),
+                fetch(&fetches, Some(vec!["a".to_owned()])),
+            )
+            .await?;
+        assert_eq!(value, Some(vec!["a".to_owned()]));
+        assert_eq!(fetches.load(Ordering::SeqCst), 2);
+
+        Ok(())
+    }
 }
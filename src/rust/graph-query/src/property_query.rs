@@ -1,23 +1,126 @@
 use std::sync::Arc;
 
+use bytes::Bytes;
 use rust_proto::{
-    graplinc::grapl::common::v1beta1::types::{
-        EdgeName,
-        PropertyName,
-        Uid,
+    graplinc::grapl::{
+        api::graph_query::v1beta1::messages::SearchValue,
+        common::v1beta1::types::{
+            EdgeName,
+            NodeType,
+            PropertyName,
+            Uid,
+        },
     },
     SerDeError,
 };
 use scylla::{
-    cql_to_rust::FromRowError,
+    cql_to_rust::{
+        FromRow,
+        FromRowError,
+    },
+    frame::value::Value,
     transport::{
         errors::QueryError,
-        query_result::MaybeFirstRowTypedError,
+        query_result::{
+            MaybeFirstRowTypedError,
+            QueryResult,
+        },
     },
     CachingSession,
 };
 
-use crate::table_names::IMM_STRING_TABLE_NAME;
+use crate::{
+    property_cache::{
+        Key,
+        PropertyCache,
+    },
+    query_stats::{
+        NodeQueryCounters,
+        QueryStats,
+    },
+    read_budget::ReadBudget,
+    string_set_resolver::StringSetProperties,
+    table_names::{
+        IMM_BOOL_TABLE_NAME,
+        IMM_BYTES_TABLE_NAME,
+        IMM_F_64_TABLE_NAME,
+        IMM_I_64_INDEX_TABLE_NAME,
+        IMM_I_64_TABLE_NAME,
+        IMM_STRING_INDEX_TABLE_NAME,
+        IMM_STRING_TABLE_NAME,
+        IMM_TIMESTAMP_TABLE_NAME,
+        IMM_U_64_INDEX_TABLE_NAME,
+        IMM_U_64_TABLE_NAME,
+        MAX_I_64_TABLE_NAME,
+        MAX_TIMESTAMP_TABLE_NAME,
+        MAX_U_64_TABLE_NAME,
+        MIN_I_64_TABLE_NAME,
+        MIN_TIMESTAMP_TABLE_NAME,
+        MIN_U_64_TABLE_NAME,
+        STRING_SET_TABLE_NAME,
+    },
+};
+
+/// A table an integer property may be stored in.
+struct IntTable {
+    name: &'static str,
+    /// Unsigned values are stored as the `i64` with the same bits
+    is_unsigned: bool,
+    is_immutable: bool,
+}
+
+const INT_TABLES: [IntTable; 6] = [
+    IntTable {
+        name: IMM_I_64_TABLE_NAME,
+        is_unsigned: false,
+        is_immutable: true,
+    },
+    IntTable {
+        name: MAX_I_64_TABLE_NAME,
+        is_unsigned: false,
+        is_immutable: false,
+    },
+    IntTable {
+        name: MIN_I_64_TABLE_NAME,
+        is_unsigned: false,
+        is_immutable: false,
+    },
+    IntTable {
+        name: IMM_U_64_TABLE_NAME,
+        is_unsigned: true,
+        is_immutable: true,
+    },
+    IntTable {
+        name: MAX_U_64_TABLE_NAME,
+        is_unsigned: true,
+        is_immutable: false,
+    },
+    IntTable {
+        name: MIN_U_64_TABLE_NAME,
+        is_unsigned: true,
+        is_immutable: false,
+    },
+];
+
+/// The tables a timestamp property may be stored in. Timestamps are read as
+/// milliseconds since the Unix epoch.
+const TIMESTAMP_TABLES: [IntTable; 3] = [
+    IntTable {
+        name: IMM_TIMESTAMP_TABLE_NAME,
+        is_unsigned: false,
+        is_immutable: true,
+    },
+    IntTable {
+        name: MAX_TIMESTAMP_TABLE_NAME,
+        is_unsigned: false,
+        is_immutable: false,
+    },
+    IntTable {
+        name: MIN_TIMESTAMP_TABLE_NAME,
+        is_unsigned: false,
+        is_immutable: false,
+    },
+];
 
 #[derive(Debug, thiserror::Error)]
 pub enum PropertyQueryError {
@@ -33,8 +136,15 @@ pub enum PropertyQueryError {
         source_uid: Uid,
         f_edge_name: String,
     },
+    #[error("Invalid uid '{uid}' in property index {index_table_name}")]
+    InvalidIndexedUidInDb {
+        uid: i64,
+        index_table_name: &'static str,
+    },
     #[error("Invalid stored edge name {0}")]
     InvalidStoredEdgeName(#[from] SerDeError),
+    #[error("Query exceeded its budget of {budget} reads")]
+    ReadBudgetExceeded { budget: u64 },
 }
 
 #[derive(Debug, Clone)]
@@ -53,16 +163,127 @@ pub struct StringField {
     pub value: String,
 }
 
+#[derive(Debug, Clone)]
+pub struct StringSetField {
+    pub uid: Uid,
+    pub populated_field: PropertyName,
+    pub value: Vec<String>,
+}
+
+impl StringSetField {
+    /// Each member of the set, as if it were a string property of its own
+    pub fn into_fields(self) -> impl Iterator<Item = StringField> {
+        let Self {
+            uid,
+            populated_field,
+            value,
+        } = self;
+        value.into_iter().map(move |value| StringField {
+            uid,
+            populated_field: populated_field.clone(),
+            value,
+        })
+    }
+}
+
+/// An integer property, widened so that both signed and unsigned properties
+/// can be compared with an `i64` filter value.
+#[derive(Debug, Clone)]
+pub struct IntField {
+    pub uid: Uid,
+    pub populated_field: PropertyName,
+    pub value: i128,
+}
+
 // We should push our filtering logic into here
 
 #[derive(Clone)]
 pub struct PropertyQueryExecutor {
     scylla_client: Arc<CachingSession>,
+    property_cache: PropertyCache,
+    read_budget: ReadBudget,
+    query_stats: QueryStats,
+    node_query_counters: Arc<NodeQueryCounters>,
+    string_set_properties: Arc<StringSetProperties>,
 }
 
 impl PropertyQueryExecutor {
-    pub fn new(scylla_client: Arc<CachingSession>) -> Self {
-        Self { scylla_client }
+    pub fn new(scylla_client: Arc<CachingSession>, property_cache: PropertyCache) -> Self {
+        Self {
+            scylla_client,
+            property_cache,
+            read_budget: ReadBudget::unlimited(),
+            query_stats: QueryStats::new(),
+            node_query_counters: Arc::default(),
+            string_set_properties: Arc::default(),
+        }
+    }
+
+    /// An executor that fails with `ReadBudgetExceeded` once it has made
+    /// `read_budget`'s reads from Scylla. Reads answered by the property cache
+    /// are free.
+    pub fn with_read_budget(&self, read_budget: ReadBudget) -> Self {
+        Self {
+            read_budget,
+            ..self.clone()
+        }
+    }
+
+    /// An executor that shares `query_stats`, so that every node query the
+    /// executor evaluates is totalled up in it.
+    pub fn with_query_stats(&self, query_stats: QueryStats) -> Self {
+        Self {
+            query_stats,
+            ..self.clone()
+        }
+    }
+
+    /// An executor that reads the string set table for `string_set_properties`
+    /// when they're filtered on as strings.
+    pub fn with_string_set_properties(&self, string_set_properties: StringSetProperties) -> Self {
+        Self {
+            string_set_properties: Arc::new(string_set_properties),
+            ..self.clone()
+        }
+    }
+
+    /// An executor whose reads, and the filters that are evaluated alongside
+    /// them, are counted apart from this one's, for evaluating another node.
+    pub fn for_node_query(&self) -> Self {
+        Self {
+            node_query_counters: Arc::default(),
+            ..self.clone()
+        }
+    }
+
+    pub fn query_stats(&self) -> &QueryStats {
+        &self.query_stats
+    }
+
+    pub fn node_query_counters(&self) -> &NodeQueryCounters {
+        &self.node_query_counters
+    }
+
+    /// Whether the schema declares `node_type`'s `property_name` a string set
+    pub fn is_string_set(&self, node_type: &NodeType, property_name: &PropertyName) -> bool {
+        self.string_set_properties
+            .contains(node_type, property_name)
+    }
+
+    fn spend_read(&self) -> Result<(), PropertyQueryError> {
+        if self.read_budget.try_spend() {
+            self.node_query_counters.add_read();
+            Ok(())
+        } else {
+            Err(PropertyQueryError::ReadBudgetExceeded {
+                budget: self.read_budget.budget(),
+            })
+        }
+    }
+
+    fn count_rows(&self, query_result: &QueryResult) {
+        self.node_query_counters
+            .add_rows(query_result.rows.as_ref().map_or(0, Vec::len));
     }
 
     pub async fn get_immutable_string(
@@ -71,6 +292,28 @@ impl PropertyQueryExecutor {
         uid: Uid,
         property_name: &PropertyName,
     ) -> Result<Option<StringField>, PropertyQueryError> {
+        let value = self
+            .property_cache
+            .get_immutable_string(
+                Key::new(IMM_STRING_TABLE_NAME, tenant_id, uid, property_name),
+                self.fetch_immutable_string(tenant_id, uid, property_name),
+            )
+            .await?;
+
+        Ok(value.map(|value| StringField {
+            uid,
+            populated_field: property_name.clone(),
+            value,
+        }))
+    }
+
+    async fn fetch_immutable_string(
+        &self,
+        tenant_id: uuid::Uuid,
+        uid: Uid,
+        property_name: &PropertyName,
+    ) -> Result<Option<String>, PropertyQueryError> {
+        self.spend_read()?;
         let mut query = scylla::query::Query::from(format!(
             r"
             SELECT value
@@ -90,25 +333,240 @@ impl PropertyQueryExecutor {
             .scylla_client
             .execute(query, &(tenant_id, uid.as_i64(), &property_name.value))
             .await?;
+        self.count_rows(&query_result);
 
-        let row = match query_result.maybe_first_row_typed::<(String,)>()? {
-            Some((row,)) => row,
-            None => return Ok(None),
-        };
+        Ok(query_result
+            .maybe_first_row_typed::<(String,)>()?
+            .map(|(value,)| value))
+    }
+
+    pub async fn get_string_set(
+        &self,
+        tenant_id: uuid::Uuid,
+        uid: Uid,
+        property_name: &PropertyName,
+    ) -> Result<Option<StringSetField>, PropertyQueryError> {
+        let value = self
+            .property_cache
+            .get_string_set(
+                Key::new(STRING_SET_TABLE_NAME, tenant_id, uid, property_name),
+                self.fetch_string_set(tenant_id, uid, property_name),
+            )
+            .await?;
 
-        Ok(Some(StringField {
+        Ok(value.map(|value| StringSetField {
             uid,
             populated_field: property_name.clone(),
-            value: row,
+            value,
         }))
     }
 
+    async fn fetch_string_set(
+        &self,
+        tenant_id: uuid::Uuid,
+        uid: Uid,
+        property_name: &PropertyName,
+    ) -> Result<Option<Vec<String>>, PropertyQueryError> {
+        self.spend_read()?;
+        let mut query = scylla::query::Query::from(format!(
+            r"
+            SELECT value
+            FROM tenant_graph_ks.{STRING_SET_TABLE_NAME}
+            WHERE
+                tenant_id = ? AND
+                uid = ? AND
+                populated_field = ?
+            LIMIT 1
+            ALLOW FILTERING;
+            ",
+        ));
+
+        query.set_is_idempotent(true);
+
+        let query_result = self
+            .scylla_client
+            .execute(query, &(tenant_id, uid.as_i64(), &property_name.value))
+            .await?;
+        self.count_rows(&query_result);
+
+        // Scylla doesn't distinguish an empty set from a missing one
+        match query_result.maybe_first_row_typed::<(Option<Vec<String>>,)>()? {
+            Some((Some(row),)) if !row.is_empty() => Ok(Some(row)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Reads an integer property from whichever table holds it. The schema
+    /// isn't consulted, so the tables are read one after another, stopping at
+    /// the first that holds the property. A missing property costs a read of
+    /// each of the 6 tables against the read budget, less any that the
+    /// property cache answers.
+    pub async fn get_int(
+        &self,
+        tenant_id: uuid::Uuid,
+        uid: Uid,
+        property_name: &PropertyName,
+    ) -> Result<Option<IntField>, PropertyQueryError> {
+        // A property lives in exactly one of these tables, so they're read
+        // in turn until it's found rather than all at once.
+        for table in INT_TABLES.iter() {
+            let value = self
+                .property_cache
+                .get_int(
+                    Key::new(table.name, tenant_id, uid, property_name),
+                    table.is_immutable,
+                    self.fetch_stored(table.name, tenant_id, uid, property_name),
+                )
+                .await?;
+            if let Some(value) = value {
+                let value = if table.is_unsigned {
+                    i128::from(value as u64)
+                } else {
+                    i128::from(value)
+                };
+                return Ok(Some(IntField {
+                    uid,
+                    populated_field: property_name.clone(),
+                    value,
+                }));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Reads a timestamp property, in milliseconds since the Unix epoch, from
+    /// whichever table holds it. As with `get_int`, a missing property costs
+    /// up to 3 reads, one of each timestamp table.
+    pub async fn get_timestamp(
+        &self,
+        tenant_id: uuid::Uuid,
+        uid: Uid,
+        property_name: &PropertyName,
+    ) -> Result<Option<i64>, PropertyQueryError> {
+        for table in TIMESTAMP_TABLES.iter() {
+            let value = self
+                .property_cache
+                .get_int(
+                    Key::new(table.name, tenant_id, uid, property_name),
+                    table.is_immutable,
+                    async {
+                        Ok::<_, PropertyQueryError>(
+                            self.fetch_stored::<chrono::Duration>(
+                                table.name,
+                                tenant_id,
+                                uid,
+                                property_name,
+                            )
+                            .await?
+                            .map(|timestamp| timestamp.num_milliseconds()),
+                        )
+                    },
+                )
+                .await?;
+            if value.is_some() {
+                return Ok(value);
+            }
+        }
+        Ok(None)
+    }
+
+    pub async fn get_bool(
+        &self,
+        tenant_id: uuid::Uuid,
+        uid: Uid,
+        property_name: &PropertyName,
+    ) -> Result<Option<bool>, PropertyQueryError> {
+        self.property_cache
+            .get_bool(
+                Key::new(IMM_BOOL_TABLE_NAME, tenant_id, uid, property_name),
+                self.fetch_stored(IMM_BOOL_TABLE_NAME, tenant_id, uid, property_name),
+            )
+            .await
+    }
+
+    pub async fn get_f64(
+        &self,
+        tenant_id: uuid::Uuid,
+        uid: Uid,
+        property_name: &PropertyName,
+    ) -> Result<Option<f64>, PropertyQueryError> {
+        self.property_cache
+            .get_f64(
+                Key::new(IMM_F_64_TABLE_NAME, tenant_id, uid, property_name),
+                self.fetch_stored(IMM_F_64_TABLE_NAME, tenant_id, uid, property_name),
+            )
+            .await
+    }
+
+    pub async fn get_bytes(
+        &self,
+        tenant_id: uuid::Uuid,
+        uid: Uid,
+        property_name: &PropertyName,
+    ) -> Result<Option<Bytes>, PropertyQueryError> {
+        self.property_cache
+            .get_bytes(
+                Key::new(IMM_BYTES_TABLE_NAME, tenant_id, uid, property_name),
+                async {
+                    Ok(self
+                        .fetch_stored::<Vec<u8>>(
+                            IMM_BYTES_TABLE_NAME,
+                            tenant_id,
+                            uid,
+                            property_name,
+                        )
+                        .await?
+                        .map(Bytes::from))
+                },
+            )
+            .await
+    }
+
+    /// Reads the value of a property from `table_name`, whatever its type.
+    async fn fetch_stored<T>(
+        &self,
+        table_name: &str,
+        tenant_id: uuid::Uuid,
+        uid: Uid,
+        property_name: &PropertyName,
+    ) -> Result<Option<T>, PropertyQueryError>
+    where
+        (T,): FromRow,
+    {
+        self.spend_read()?;
+        let mut query = scylla::query::Query::from(format!(
+            r"
+            SELECT value
+            FROM tenant_graph_ks.{table_name}
+            WHERE
+                tenant_id = ? AND
+                uid = ? AND
+                populated_field = ?
+            LIMIT 1
+            ALLOW FILTERING;
+            ",
+        ));
+
+        query.set_is_idempotent(true);
+
+        let query_result = self
+            .scylla_client
+            .execute(query, &(tenant_id, uid.as_i64(), &property_name.value))
+            .await?;
+        self.count_rows(&query_result);
+
+        Ok(query_result
+            .maybe_first_row_typed::<(T,)>()?
+            .map(|(value,)| value))
+    }
+
     pub async fn get_edges(
         &self,
         tenant_id: uuid::Uuid,
         uid: Uid,
         edge_name: &EdgeName,
     ) -> Result<Option<Vec<EdgeRow>>, PropertyQueryError> {
+        self.spend_read()?;
         let mut query = scylla::query::Query::from(
             r"
             SELECT r_edge_name, destination_uid
@@ -127,6 +585,7 @@ impl PropertyQueryExecutor {
             .scylla_client
             .execute(query, &(tenant_id, uid.as_i64(), &edge_name.value))
             .await?;
+        self.count_rows(&query_result);
 
         let rows = query_result.rows_typed_or_empty::<(String, i64)>();
 
@@ -155,4 +614,229 @@ impl PropertyQueryExecutor {
             Ok(Some(edge_rows))
         }
     }
+
+    /// Counts `uid`'s `edge_name` edges in Scylla, without reading them.
+    pub async fn count_edges(
+        &self,
+        tenant_id: uuid::Uuid,
+        uid: Uid,
+        edge_name: &EdgeName,
+    ) -> Result<u64, PropertyQueryError> {
+        self.spend_read()?;
+        let mut query = scylla::query::Query::from(
+            r"
+            SELECT COUNT(*)
+            FROM tenant_graph_ks.edges
+            WHERE
+                tenant_id = ? AND
+                source_uid = ? AND
+                f_edge_name = ?
+            ALLOW FILTERING;
+            ",
+        );
+
+        query.set_is_idempotent(true);
+
+        let query_result = self
+            .scylla_client
+            .execute(query, &(tenant_id, uid.as_i64(), &edge_name.value))
+            .await?;
+        self.count_rows(&query_result);
+
+        Ok(query_result
+            .maybe_first_row_typed::<(i64,)>()?
+            .map(|(count,)| count as u64)
+            .unwrap_or_default())
+    }
+
+    /// Counts every one of `uid`'s edges in Scylla, grouped by edge name.
+    pub async fn count_edges_by_name(
+        &self,
+        tenant_id: uuid::Uuid,
+        uid: Uid,
+    ) -> Result<Vec<(EdgeName, u64)>, PropertyQueryError> {
+        self.spend_read()?;
+        let mut query = scylla::query::Query::from(
+            r"
+            SELECT f_edge_name, COUNT(*)
+            FROM tenant_graph_ks.edges
+            WHERE
+                tenant_id = ? AND
+                source_uid = ?
+            GROUP BY tenant_id, source_uid, f_edge_name
+            ALLOW FILTERING;
+            ",
+        );
+
+        query.set_is_idempotent(true);
+
+        let query_result = self
+            .scylla_client
+            .execute(query, &(tenant_id, uid.as_i64()))
+            .await?;
+        self.count_rows(&query_result);
+
+        let mut counts = Vec::new();
+        for row in query_result.rows_typed_or_empty::<(String, i64)>() {
+            let (edge_name, count) = row?;
+            counts.push((
+                EdgeName::try_from(edge_name).map_err(PropertyQueryError::InvalidStoredEdgeName)?,
+                count as u64,
+            ));
+        }
+        Ok(counts)
+    }
+
+    /// Reads up to `limit` uids of `node_type` nodes whose immutable
+    /// `property_name` is equal to `value`, from the inverted property index.
+    /// Uids come back in the index's order, starting after `after_uid`, so the
+    /// last one returned can be passed back in to read the next page. The
+    /// index may be stale; see `holds_value`.
+    pub async fn search_index(
+        &self,
+        tenant_id: uuid::Uuid,
+        node_type: &NodeType,
+        property_name: &PropertyName,
+        value: &SearchValue,
+        after_uid: Option<Uid>,
+        limit: u32,
+    ) -> Result<Vec<Uid>, PropertyQueryError> {
+        match value {
+            SearchValue::String(value) => {
+                self.fetch_index_page(
+                    IMM_STRING_INDEX_TABLE_NAME,
+                    tenant_id,
+                    node_type,
+                    property_name,
+                    value.as_str(),
+                    after_uid,
+                    limit,
+                )
+                .await
+            }
+            SearchValue::I64(value) => {
+                self.fetch_index_page(
+                    IMM_I_64_INDEX_TABLE_NAME,
+                    tenant_id,
+                    node_type,
+                    property_name,
+                    *value,
+                    after_uid,
+                    limit,
+                )
+                .await
+            }
+            // Unsigned values are stored as the `i64` with the same bits
+            SearchValue::U64(value) => {
+                self.fetch_index_page(
+                    IMM_U_64_INDEX_TABLE_NAME,
+                    tenant_id,
+                    node_type,
+                    property_name,
+                    *value as i64,
+                    after_uid,
+                    limit,
+                )
+                .await
+            }
+        }
+    }
+
+    /// Whether `uid`'s stored `property_name` is currently equal to `value`.
+    /// Writing an immutable property again overwrites its value, but leaves
+    /// the index row for the old value in place, so a uid read from the index
+    /// may no longer hold the value it was found under.
+    pub async fn holds_value(
+        &self,
+        tenant_id: uuid::Uuid,
+        uid: Uid,
+        property_name: &PropertyName,
+        value: &SearchValue,
+    ) -> Result<bool, PropertyQueryError> {
+        match value {
+            SearchValue::String(value) => Ok(self
+                .get_immutable_string(tenant_id, uid, property_name)
+                .await?
+                .map_or(false, |field| &field.value == value)),
+            SearchValue::I64(value) => Ok(self
+                .get_int(tenant_id, uid, property_name)
+                .await?
+                .map_or(false, |field| field.value == i128::from(*value))),
+            SearchValue::U64(value) => Ok(self
+                .get_int(tenant_id, uid, property_name)
+                .await?
+                .map_or(false, |field| field.value == i128::from(*value))),
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn fetch_index_page(
+        &self,
+        index_table_name: &'static str,
+        tenant_id: uuid::Uuid,
+        node_type: &NodeType,
+        property_name: &PropertyName,
+        value: impl Value,
+        after_uid: Option<Uid>,
+        limit: u32,
+    ) -> Result<Vec<Uid>, PropertyQueryError> {
+        self.spend_read()?;
+        let after_uid_clause = match after_uid {
+            Some(_) => "AND uid > ?",
+            None => "",
+        };
+        let mut query = scylla::query::Query::from(format!(
+            r"
+            SELECT uid
+            FROM tenant_graph_ks.{index_table_name}
+            WHERE
+                tenant_id = ? AND
+                node_type = ? AND
+                populated_field = ? AND
+                value = ?
+                {after_uid_clause}
+            LIMIT {limit};
+            ",
+        ));
+
+        query.set_is_idempotent(true);
+
+        let query_result = match after_uid {
+            Some(after_uid) => {
+                self.scylla_client
+                    .execute(
+                        query,
+                        &(
+                            tenant_id,
+                            &node_type.value,
+                            &property_name.value,
+                            value,
+                            after_uid.as_i64(),
+                        ),
+                    )
+                    .await?
+            }
+            None => {
+                self.scylla_client
+                    .execute(
+                        query,
+                        &(tenant_id, &node_type.value, &property_name.value, value),
+                    )
+                    .await?
+            }
+        };
+        self.count_rows(&query_result);
+
+        let mut uids = Vec::new();
+        for row in query_result.rows_typed_or_empty::<(i64,)>() {
+            let (uid,) = row?;
+            uids.push(
+                Uid::from_i64(uid).ok_or(PropertyQueryError::InvalidIndexedUidInDb {
+                    uid,
+                    index_table_name,
+                })?,
+            );
+        }
+        Ok(uids)
+    }
 }
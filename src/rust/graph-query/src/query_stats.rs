@@ -0,0 +1,117 @@
+use std::{
+    sync::{
+        atomic::{
+            AtomicU64,
+            Ordering,
+        },
+        Arc,
+        Mutex,
+    },
+    time::Duration,
+};
+
+use graph_query_matching::FilterCounter;
+use rust_proto::graplinc::grapl::{
+    api::graph_query::v1beta1::messages::{
+        NodeQueryExplanation,
+        QueryExplanation,
+        QueryId,
+        ShortCircuitExplanation,
+    },
+    common::v1beta1::types::NodeType,
+};
+use rustc_hash::FxHashMap;
+
+/// What evaluating a single node against a node query cost. Neighbors are
+/// evaluated against their own counters.
+#[derive(Debug, Default)]
+pub struct NodeQueryCounters {
+    scylla_reads: AtomicU64,
+    rows_fetched: AtomicU64,
+    filters_evaluated: AtomicU64,
+}
+
+impl NodeQueryCounters {
+    pub fn add_read(&self) {
+        self.scylla_reads.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn add_rows(&self, rows: usize) {
+        self.rows_fetched.fetch_add(rows as u64, Ordering::Relaxed);
+    }
+
+    pub fn add_filter(&self) {
+        self.filters_evaluated.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn scylla_reads(&self) -> u64 {
+        self.scylla_reads.load(Ordering::Relaxed)
+    }
+
+    pub fn rows_fetched(&self) -> u64 {
+        self.rows_fetched.load(Ordering::Relaxed)
+    }
+
+    pub fn filters_evaluated(&self) -> u64 {
+        self.filters_evaluated.load(Ordering::Relaxed)
+    }
+}
+
+impl FilterCounter for NodeQueryCounters {
+    fn add_filter(&self) {
+        NodeQueryCounters::add_filter(self)
+    }
+}
+
+/// QueryStats totals up the evaluations of each node query in a graph query,
+/// to `explain` it. Like the ReadBudget it's shared between every task
+/// working on the query.
+#[derive(Clone, Debug, Default)]
+pub struct QueryStats {
+    node_queries: Arc<Mutex<FxHashMap<QueryId, NodeQueryExplanation>>>,
+}
+
+impl QueryStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a node's evaluation against `query_id` to the node query's totals.
+    pub fn record(
+        &self,
+        query_id: QueryId,
+        node_type: &NodeType,
+        counters: &NodeQueryCounters,
+        matched: bool,
+        short_circuit: Option<ShortCircuitExplanation>,
+        elapsed: Duration,
+    ) {
+        let mut node_queries = self.node_queries.lock().unwrap();
+        let node_query = node_queries
+            .entry(query_id)
+            .or_insert_with(|| NodeQueryExplanation::new(query_id, node_type.clone()));
+        node_query.nodes_visited += 1;
+        node_query.nodes_matched += u64::from(matched);
+        node_query.scylla_reads += counters.scylla_reads();
+        node_query.rows_fetched += counters.rows_fetched();
+        node_query.filters_evaluated += counters.filters_evaluated();
+        node_query.short_circuits.extend(short_circuit);
+        node_query.elapsed += elapsed;
+    }
+
+    /// The totals so far, of a query that has taken `elapsed`.
+    pub fn explain(&self, elapsed: Duration) -> QueryExplanation {
+        let mut node_queries: Vec<_> = self
+            .node_queries
+            .lock()
+            .unwrap()
+            .values()
+            .cloned()
+            .collect();
+        node_queries.sort_by_key(|node_query| node_query.query_id.value);
+        QueryExplanation {
+            node_queries,
+            elapsed,
+        }
+    }
+}
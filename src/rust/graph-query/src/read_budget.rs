@@ -0,0 +1,81 @@
+use std::sync::{
+    atomic::{
+        AtomicU64,
+        Ordering,
+    },
+    Arc,
+};
+
+/// ReadBudget limits how many reads a single query may make from Scylla. It's
+/// shared between every task working on the query, so that a query which
+/// matches many nodes can't make up for its budget by fanning out.
+#[derive(Clone, Debug)]
+pub struct ReadBudget {
+    budget: u64,
+    remaining: Arc<AtomicU64>,
+}
+
+impl ReadBudget {
+    pub fn new(budget: u64) -> Self {
+        Self {
+            budget,
+            remaining: Arc::new(AtomicU64::new(budget)),
+        }
+    }
+
+    pub fn unlimited() -> Self {
+        Self::new(u64::MAX)
+    }
+
+    pub fn budget(&self) -> u64 {
+        self.budget
+    }
+
+    /// Spends one read, returning false if the budget is already spent.
+    pub fn try_spend(&self) -> bool {
+        self.remaining
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |remaining| {
+                remaining.checked_sub(1)
+            })
+            .is_ok()
+    }
+}
+
+impl Default for ReadBudget {
+    fn default() -> Self {
+        Self::unlimited()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_budget_is_exhausted() {
+        let read_budget = ReadBudget::new(2);
+
+        assert!(read_budget.try_spend());
+        assert!(read_budget.try_spend());
+        assert!(!read_budget.try_spend());
+        // Failing to spend doesn't wrap the remaining reads around
+        assert!(!read_budget.try_spend());
+        assert_eq!(read_budget.budget(), 2);
+    }
+
+    #[test]
+    fn test_budget_is_shared_between_clones() {
+        let read_budget = ReadBudget::new(2);
+        let clone = read_budget.clone();
+
+        assert!(read_budget.try_spend());
+        assert!(clone.try_spend());
+        assert!(!read_budget.try_spend());
+        assert!(!clone.try_spend());
+    }
+
+    #[test]
+    fn test_zero_budget_allows_no_reads() {
+        assert!(!ReadBudget::new(0).try_spend());
+    }
+}
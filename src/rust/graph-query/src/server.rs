@@ -1,24 +1,64 @@
-use std::sync::Arc;
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::Arc,
+    time::{
+        Duration,
+        Instant,
+    },
+};
 
+use futures::future::try_join_all;
+use graph_query_language::{
+    QueryLanguageError,
+    TextQuery,
+};
+use graph_query_matching::{
+    RegexCache,
+    RegexCacheError,
+};
+use reverse_edge_resolver::{
+    ReverseEdgeResolver,
+    ReverseEdgeResolverError,
+};
 use rust_proto::graplinc::grapl::api::{
+    client::{
+        ClientConfiguration,
+        ClientError,
+        Connect,
+    },
     graph_query::v1beta1::{
         messages::{
+            AggregateGraphFromUidRequest,
+            AggregateGraphFromUidResponse,
+            EdgeCount,
+            GraphEdgeCounts,
             GraphQuery,
             MatchedGraphWithUid,
             MaybeMatchWithUid,
             NoMatchWithUid,
+            NodeEdgeCounts,
             QueryGraphFromUidRequest,
             QueryGraphFromUidResponse,
+            QueryGraphWithTextRequest,
+            QueryGraphWithTextResponse,
             QueryGraphWithUidRequest,
             QueryGraphWithUidResponse,
+            QueryId,
+            SearchNodesMatch,
+            SearchNodesRequest,
+            SearchNodesResponse,
         },
         server::GraphQueryApi,
     },
+    graph_schema_manager::v1beta1::client::GraphSchemaManagerClient,
     protocol::status::Status,
 };
 use scylla::CachingSession;
+use tokio::sync::OnceCell;
 
 use crate::{
+    config::QueryLimitsConfig,
     graph_query::{
         query_graph,
         GraphQueryError,
@@ -27,8 +67,20 @@ use crate::{
         fetch_node_with_edges,
         NodeQueryError,
     },
-    property_query::PropertyQueryExecutor,
+    property_cache::PropertyCache,
+    property_query::{
+        PropertyQueryError,
+        PropertyQueryExecutor,
+    },
+    query_stats::QueryStats,
+    read_budget::ReadBudget,
     short_circuit::ShortCircuit,
+    string_set_resolver::{
+        string_filtered_node_types,
+        StringSetProperties,
+        StringSetResolver,
+        StringSetResolverError,
+    },
     visited::Visited,
 };
 
@@ -38,14 +90,66 @@ pub enum GraphQueryServiceError {
     GraphQueryError(#[from] GraphQueryError),
     #[error("NodeQueryError {0}")]
     NodeQueryError(#[from] NodeQueryError),
+    #[error("RegexCacheError {0}")]
+    RegexCacheError(#[from] RegexCacheError),
+    #[error("PropertyQueryError {0}")]
+    PropertyQueryError(#[from] PropertyQueryError),
+    #[error("The graph query has no node query for its root {0:?}")]
+    NoSuchRootQueryId(QueryId),
+    #[error("Query exceeded its deadline of {deadline:?}")]
+    DeadlineExceeded { deadline: Duration },
+    #[error("QueryLanguageError {0}")]
+    QueryLanguageError(#[from] QueryLanguageError),
+    #[error("ReverseEdgeResolverError {0}")]
+    ReverseEdgeResolverError(#[from] ReverseEdgeResolverError),
+    #[error("StringSetResolverError {0}")]
+    StringSetResolverError(#[from] StringSetResolverError),
+    #[error("Failed to connect to graph-schema-manager {0}")]
+    GraphSchemaManagerConnectError(ClientError),
 }
 
 impl From<GraphQueryServiceError> for Status {
     fn from(gqs_err: GraphQueryServiceError) -> Self {
         type GQSErr = GraphQueryServiceError;
+        // A query that runs out of time or reads would do so again if it were
+        // retried, so these aren't reported as `Unavailable`
         match gqs_err {
+            e @ GQSErr::DeadlineExceeded { .. } => Status::failed_precondition(e.to_string()),
+            GQSErr::GraphQueryError(
+                e @ GraphQueryError::NodeQueryError {
+                    source:
+                        NodeQueryError::PropertyQueryError(PropertyQueryError::ReadBudgetExceeded {
+                            ..
+                        }),
+                    ..
+                },
+            ) => Status::failed_precondition(e.to_string()),
+            GQSErr::NodeQueryError(
+                e @ NodeQueryError::PropertyQueryError(PropertyQueryError::ReadBudgetExceeded {
+                    ..
+                }),
+            ) => Status::failed_precondition(e.to_string()),
+            GQSErr::PropertyQueryError(e @ PropertyQueryError::ReadBudgetExceeded { .. }) => {
+                Status::failed_precondition(e.to_string())
+            }
+            GQSErr::GraphQueryError(
+                e @ GraphQueryError::NodeQueryError {
+                    source: NodeQueryError::NoSuchReverseEdge(_),
+                    ..
+                },
+            ) => Status::invalid_argument(e.to_string()),
+            GQSErr::NodeQueryError(e @ NodeQueryError::NoSuchReverseEdge(_)) => {
+                Status::invalid_argument(e.to_string())
+            }
             GQSErr::GraphQueryError(e) => Status::unknown(e.to_string()),
             GQSErr::NodeQueryError(e) => Status::unknown(e.to_string()),
+            GQSErr::PropertyQueryError(e) => Status::unknown(e.to_string()),
+            GQSErr::RegexCacheError(e) => Status::invalid_argument(e.to_string()),
+            e @ GQSErr::NoSuchRootQueryId(_) => Status::invalid_argument(e.to_string()),
+            GQSErr::QueryLanguageError(e) => Status::invalid_argument(e.to_string()),
+            GQSErr::ReverseEdgeResolverError(e) => Status::unknown(e.to_string()),
+            GQSErr::StringSetResolverError(e) => Status::unknown(e.to_string()),
+            e @ GQSErr::GraphSchemaManagerConnectError(_) => Status::unavailable(e.to_string()),
         }
     }
 }
@@ -53,14 +157,96 @@ impl From<GraphQueryServiceError> for Status {
 #[derive(Clone)]
 pub struct GraphQueryService {
     property_query_executor: PropertyQueryExecutor,
+    query_limits_config: QueryLimitsConfig,
+    graph_schema_manager_client_config: ClientConfiguration,
+    // Only text queries and string filters need graph-schema-manager, so
+    // it's connected to on the first such query rather than at startup.
+    schema_resolvers: Arc<OnceCell<SchemaResolvers>>,
+}
+
+/// Lookups in tenants' schemas, which share a graph-schema-manager client.
+struct SchemaResolvers {
+    reverse_edges: ReverseEdgeResolver,
+    string_sets: StringSetResolver,
 }
 
 impl GraphQueryService {
-    pub fn new(scylla_client: Arc<CachingSession>) -> Self {
+    pub fn new(
+        scylla_client: Arc<CachingSession>,
+        property_cache: PropertyCache,
+        query_limits_config: QueryLimitsConfig,
+        graph_schema_manager_client_config: ClientConfiguration,
+    ) -> Self {
         Self {
-            property_query_executor: PropertyQueryExecutor::new(scylla_client),
+            property_query_executor: PropertyQueryExecutor::new(scylla_client, property_cache),
+            query_limits_config,
+            graph_schema_manager_client_config,
+            schema_resolvers: Arc::new(OnceCell::new()),
         }
     }
+
+    async fn schema_resolvers(&self) -> Result<&SchemaResolvers, GraphQueryServiceError> {
+        self.schema_resolvers
+            .get_or_try_init(|| async {
+                let graph_schema_manager_client = GraphSchemaManagerClient::connect(
+                    self.graph_schema_manager_client_config.clone(),
+                )
+                .await
+                .map_err(GraphQueryServiceError::GraphSchemaManagerConnectError)?;
+                Ok(SchemaResolvers {
+                    reverse_edges: ReverseEdgeResolver::new(
+                        graph_schema_manager_client.clone(),
+                        10_000,
+                    ),
+                    string_sets: StringSetResolver::new(graph_schema_manager_client, 10_000),
+                })
+            })
+            .await
+    }
+
+    /// An executor for a single query, with a read budget of its own.
+    fn query_executor(&self) -> PropertyQueryExecutor {
+        self.property_query_executor
+            .with_read_budget(ReadBudget::new(
+                self.query_limits_config.graph_query_read_budget,
+            ))
+    }
+
+    /// Like `query_executor`, for `graph_query`. It knows which of the
+    /// properties that the query filters on as strings are string sets, so
+    /// that it doesn't have to look for each in both tables.
+    async fn graph_query_executor(
+        &self,
+        tenant_id: uuid::Uuid,
+        graph_query: &GraphQuery,
+    ) -> Result<PropertyQueryExecutor, GraphQueryServiceError> {
+        let node_types = string_filtered_node_types(graph_query);
+        let string_set_properties = if node_types.is_empty() {
+            StringSetProperties::default()
+        } else {
+            self.schema_resolvers()
+                .await?
+                .string_sets
+                .resolve(tenant_id, node_types)
+                .await?
+        };
+
+        Ok(self
+            .query_executor()
+            .with_string_set_properties(string_set_properties))
+    }
+
+    /// Runs `query` until it completes or the query deadline passes, at which
+    /// point it's dropped, cancelling any reads it has in flight.
+    async fn with_deadline<T>(
+        &self,
+        query: impl Future<Output = T>,
+    ) -> Result<T, GraphQueryServiceError> {
+        let deadline = Duration::from_millis(self.query_limits_config.graph_query_deadline_ms);
+        tokio::time::timeout(deadline, query)
+            .await
+            .map_err(|_| GraphQueryServiceError::DeadlineExceeded { deadline })
+    }
 }
 
 #[async_trait::async_trait]
@@ -71,38 +257,44 @@ impl GraphQueryApi for GraphQueryService {
         &self,
         request: QueryGraphWithUidRequest,
     ) -> Result<QueryGraphWithUidResponse, GraphQueryServiceError> {
+        let started = Instant::now();
         let node_uid = request.node_uid;
 
         let graph_query: GraphQuery = request.graph_query;
+        let regex_cache = RegexCache::compile(&graph_query)?;
+        let query_stats = QueryStats::new();
 
         tracing::debug!(mesage = "about to query_graph",
             graph_query =? graph_query,
         );
-        let graph = query_graph(
-            &graph_query,
-            node_uid,
-            request.tenant_id,
-            self.property_query_executor.clone(),
-        )
-        .await?;
+        let graph = self
+            .with_deadline(query_graph(
+                &graph_query,
+                node_uid,
+                request.tenant_id,
+                self.graph_query_executor(request.tenant_id, &graph_query)
+                    .await?
+                    .with_query_stats(query_stats.clone()),
+                &regex_cache,
+            ))
+            .await??;
         tracing::debug!(mesage = "query_graph resulting graph",
             graph =? graph,
         );
 
-        let (matched_graph, root_uid) = match graph {
-            Some((graph, root_uid)) => (graph, root_uid),
-            None => {
-                return Ok(QueryGraphWithUidResponse {
-                    maybe_match: MaybeMatchWithUid::Missed(NoMatchWithUid {}),
-                })
-            }
-        };
-
-        Ok(QueryGraphWithUidResponse {
-            maybe_match: MaybeMatchWithUid::Matched(MatchedGraphWithUid {
+        let maybe_match = match graph {
+            Some((matched_graph, root_uid)) => MaybeMatchWithUid::Matched(MatchedGraphWithUid {
                 matched_graph,
                 root_uid,
             }),
+            None => MaybeMatchWithUid::Missed(NoMatchWithUid {}),
+        };
+
+        Ok(QueryGraphWithUidResponse {
+            maybe_match,
+            explanation: request
+                .explain
+                .then(|| query_stats.explain(started.elapsed())),
         })
     }
 
@@ -110,30 +302,283 @@ impl GraphQueryApi for GraphQueryService {
         &self,
         request: QueryGraphFromUidRequest,
     ) -> Result<QueryGraphFromUidResponse, GraphQueryServiceError> {
+        let started = Instant::now();
         let node_uid = request.node_uid;
 
         let graph_query: GraphQuery = request.graph_query;
-        let node_query = &graph_query
+        let regex_cache = RegexCache::compile(&graph_query)?;
+        let query_stats = QueryStats::new();
+        let node_query = graph_query
             .node_property_queries
             .get(&graph_query.root_query_id)
-            .unwrap();
+            .ok_or(GraphQueryServiceError::NoSuchRootQueryId(
+                graph_query.root_query_id,
+            ))?;
 
         let visited = Visited::new();
         let x_short_circuit = ShortCircuit::new();
-        let graph = fetch_node_with_edges(
-            node_query,
-            &graph_query,
-            node_uid,
-            request.tenant_id,
-            self.property_query_executor.clone(),
-            visited,
-            x_short_circuit,
-            &mut None,
-        )
-        .await?;
+        let graph = self
+            .with_deadline(fetch_node_with_edges(
+                node_query,
+                &graph_query,
+                node_uid,
+                request.tenant_id,
+                self.graph_query_executor(request.tenant_id, &graph_query)
+                    .await?
+                    .with_query_stats(query_stats.clone()),
+                &regex_cache,
+                visited,
+                x_short_circuit,
+                &mut None,
+            ))
+            .await??;
 
         Ok(QueryGraphFromUidResponse {
             matched_graph: graph,
+            explanation: request
+                .explain
+                .then(|| query_stats.explain(started.elapsed())),
+        })
+    }
+
+    async fn aggregate_graph_from_uid(
+        &self,
+        request: AggregateGraphFromUidRequest,
+    ) -> Result<AggregateGraphFromUidResponse, GraphQueryServiceError> {
+        let started = Instant::now();
+        let graph_query: GraphQuery = request.graph_query;
+        let regex_cache = RegexCache::compile(&graph_query)?;
+        let query_stats = QueryStats::new();
+        let node_query = graph_query
+            .node_property_queries
+            .get(&graph_query.root_query_id)
+            .ok_or(GraphQueryServiceError::NoSuchRootQueryId(
+                graph_query.root_query_id,
+            ))?;
+
+        // Matching and counting share one deadline and one read budget
+        let query_executor = self
+            .graph_query_executor(request.tenant_id, &graph_query)
+            .await?
+            .with_query_stats(query_stats.clone());
+        let tenant_id = request.tenant_id;
+        let edge_names = &request.edge_names;
+        let aggregate = async {
+            let graph = fetch_node_with_edges(
+                node_query,
+                &graph_query,
+                request.node_uid,
+                tenant_id,
+                query_executor.clone(),
+                &regex_cache,
+                Visited::new(),
+                ShortCircuit::new(),
+                &mut None,
+            )
+            .await?;
+
+            let graph = match graph {
+                Some(graph) => graph,
+                None => {
+                    return Ok(AggregateGraphFromUidResponse {
+                        edge_counts: None,
+                        explanation: None,
+                    })
+                }
+            };
+
+            // Edges are counted in Scylla, so neighbours are never read
+            let nodes = try_join_all(graph.nodes.into_iter().map(|(uid, node)| {
+                let query_executor = query_executor.clone();
+                async move {
+                    let counts = if edge_names.is_empty() {
+                        query_executor.count_edges_by_name(tenant_id, uid).await?
+                    } else {
+                        let mut counts = Vec::with_capacity(edge_names.len());
+                        for edge_name in edge_names {
+                            let count = query_executor
+                                .count_edges(tenant_id, uid, edge_name)
+                                .await?;
+                            counts.push((edge_name.clone(), count));
+                        }
+                        counts
+                    };
+                    Ok::<_, PropertyQueryError>(NodeEdgeCounts {
+                        uid,
+                        node_type: node.node_type,
+                        edge_counts: counts
+                            .into_iter()
+                            .filter(|(_, count)| *count > 0)
+                            .map(|(edge_name, count)| EdgeCount { edge_name, count })
+                            .collect(),
+                    })
+                }
+            }))
+            .await?;
+
+            Ok::<_, GraphQueryServiceError>(AggregateGraphFromUidResponse {
+                edge_counts: Some(GraphEdgeCounts { nodes }),
+                explanation: None,
+            })
+        };
+        let mut response = self.with_deadline(aggregate).await??;
+
+        response.explanation = request
+            .explain
+            .then(|| query_stats.explain(started.elapsed()));
+        Ok(response)
+    }
+
+    async fn search_nodes(
+        &self,
+        request: SearchNodesRequest,
+    ) -> Result<SearchNodesResponse, GraphQueryServiceError> {
+        let started = Instant::now();
+        let query_stats = QueryStats::new();
+        // A page of 0 would be a `LIMIT 0`, which Scylla rejects
+        let page_size = request
+            .page_size
+            .unwrap_or(self.query_limits_config.search_nodes_default_page_size)
+            .min(self.query_limits_config.search_nodes_max_page_size)
+            .max(1);
+
+        let graph_query = request.graph_query;
+        let expansion = match &graph_query {
+            Some(graph_query) => {
+                let node_query = graph_query
+                    .node_property_queries
+                    .get(&graph_query.root_query_id)
+                    .ok_or(GraphQueryServiceError::NoSuchRootQueryId(
+                        graph_query.root_query_id,
+                    ))?;
+                Some((graph_query, node_query, RegexCache::compile(graph_query)?))
+            }
+            None => None,
+        };
+
+        // The whole page shares one deadline and one read budget
+        let tenant_id = request.tenant_id;
+        let query_executor = match &graph_query {
+            Some(graph_query) => self.graph_query_executor(tenant_id, graph_query).await?,
+            None => self.query_executor(),
+        }
+        .with_query_stats(query_stats.clone());
+        let search = async {
+            let uids = query_executor
+                .search_index(
+                    tenant_id,
+                    &request.node_type,
+                    &request.property_name,
+                    &request.value,
+                    request.page_token,
+                    page_size,
+                )
+                .await?;
+
+            // A short page means the index has nothing left past it
+            let next_page_token = if uids.len() == page_size as usize {
+                uids.last().copied()
+            } else {
+                None
+            };
+
+            // The index isn't updated when a value is overwritten, so each
+            // entry is checked to still be current before it's matched
+            let holds_value = try_join_all(uids.iter().map(|uid| {
+                query_executor.holds_value(tenant_id, *uid, &request.property_name, &request.value)
+            }))
+            .await?;
+            let uids = uids
+                .into_iter()
+                .zip(holds_value)
+                .filter(|(_, holds_value)| *holds_value)
+                .map(|(uid, _)| uid);
+
+            let matches = match &expansion {
+                None => uids
+                    .map(|uid| SearchNodesMatch {
+                        uid,
+                        matched_graph: None,
+                    })
+                    .collect(),
+                Some((graph_query, node_query, regex_cache)) => {
+                    let graphs = try_join_all(uids.map(|uid| {
+                        let query_executor = query_executor.clone();
+                        async move {
+                            let graph = fetch_node_with_edges(
+                                node_query,
+                                graph_query,
+                                uid,
+                                tenant_id,
+                                query_executor,
+                                regex_cache,
+                                Visited::new(),
+                                ShortCircuit::new(),
+                                &mut None,
+                            )
+                            .await?;
+                            Ok::<_, NodeQueryError>(graph.map(|graph| SearchNodesMatch {
+                                uid,
+                                matched_graph: Some(graph),
+                            }))
+                        }
+                    }))
+                    .await?;
+                    graphs.into_iter().flatten().collect()
+                }
+            };
+
+            Ok::<_, GraphQueryServiceError>(SearchNodesResponse {
+                matches,
+                next_page_token,
+                explanation: None,
+            })
+        };
+        let mut response = self.with_deadline(search).await??;
+
+        response.explanation = request
+            .explain
+            .then(|| query_stats.explain(started.elapsed()));
+        Ok(response)
+    }
+
+    async fn query_graph_with_text(
+        &self,
+        request: QueryGraphWithTextRequest,
+    ) -> Result<QueryGraphWithTextResponse, GraphQueryServiceError> {
+        let text_query = TextQuery::parse(&request.query_text)?;
+
+        let reverse_edge_resolver = &self.schema_resolvers().await?.reverse_edges;
+        let mut reverse_edges = HashMap::new();
+        for (node_type, edge_name) in text_query.edges() {
+            let key = (node_type.clone(), edge_name.clone());
+            if reverse_edges.contains_key(&key) {
+                continue;
+            }
+            let reverse_edge = reverse_edge_resolver
+                .try_resolve_reverse_edge(request.tenant_id, key.0.clone(), key.1.clone())
+                .await?;
+            reverse_edges.insert(key, reverse_edge);
+        }
+        let graph_query = text_query.compile(|node_type, edge_name| {
+            reverse_edges
+                .get(&(node_type.clone(), edge_name.clone()))
+                .cloned()
+                .flatten()
+        })?;
+
+        let response = self
+            .query_graph_with_uid(QueryGraphWithUidRequest {
+                tenant_id: request.tenant_id,
+                node_uid: request.node_uid,
+                graph_query,
+                explain: request.explain,
+            })
+            .await?;
+
+        Ok(QueryGraphWithTextResponse {
+            maybe_match: response.maybe_match,
+            explanation: response.explanation,
         })
     }
 }
@@ -0,0 +1,141 @@
+use std::sync::Arc;
+
+use moka::future::Cache;
+use rust_proto::graplinc::grapl::{
+    api::{
+        client::ClientError,
+        graph_query::v1beta1::messages::GraphQuery,
+        graph_schema_manager::v1beta1::{
+            client::GraphSchemaManagerClient,
+            messages::{
+                GetNodeSchemaRequest,
+                PropertyType,
+            },
+        },
+        protocol::status::Code,
+    },
+    common::v1beta1::types::{
+        NodeType,
+        PropertyName,
+    },
+};
+use rustc_hash::{
+    FxHashMap,
+    FxHashSet,
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum StringSetResolverError {
+    #[error("gRPC client error: {0}")]
+    ClientError(#[from] ClientError),
+}
+
+/// The string set properties of each node type that a query filters on
+/// strings. Only these are read from the string set table when a string
+/// filter's property isn't an immutable string.
+#[derive(Debug, Clone, Default)]
+pub struct StringSetProperties {
+    properties: FxHashMap<NodeType, Arc<FxHashSet<PropertyName>>>,
+}
+
+impl StringSetProperties {
+    pub fn contains(&self, node_type: &NodeType, property_name: &PropertyName) -> bool {
+        self.properties
+            .get(node_type)
+            .map_or(false, |properties| properties.contains(property_name))
+    }
+}
+
+/// Every node type in `graph_query` with a string filter, including those of
+/// path filters' intermediate nodes. Only their properties can be string sets
+/// that the query reads.
+pub fn string_filtered_node_types(graph_query: &GraphQuery) -> FxHashSet<&NodeType> {
+    let intermediate_queries = graph_query
+        .path_filters
+        .values()
+        .flatten()
+        .filter_map(|path_filter| path_filter.intermediate_node_query.as_ref());
+    graph_query
+        .node_property_queries
+        .values()
+        .chain(intermediate_queries)
+        .filter(|node_query| !node_query.string_filters.is_empty())
+        .map(|node_query| &node_query.node_type)
+        .collect()
+}
+
+/// Looks up which of a node type's properties are string sets in a tenant's
+/// schema.
+#[derive(Clone)]
+pub struct StringSetResolver {
+    schema_client: GraphSchemaManagerClient,
+    string_set_cache: Cache<(uuid::Uuid, NodeType), Arc<FxHashSet<PropertyName>>>,
+}
+
+impl StringSetResolver {
+    pub fn new(schema_client: GraphSchemaManagerClient, cache_size: u64) -> Self {
+        Self {
+            schema_client,
+            string_set_cache: Cache::new(cache_size),
+        }
+    }
+
+    /// The string set properties of each of `node_types`
+    pub async fn resolve<'a>(
+        &self,
+        tenant_id: uuid::Uuid,
+        node_types: impl IntoIterator<Item = &'a NodeType>,
+    ) -> Result<StringSetProperties, StringSetResolverError> {
+        let mut string_set_properties = StringSetProperties::default();
+        for node_type in node_types {
+            let properties = self
+                .resolve_string_sets(tenant_id, node_type.clone())
+                .await?;
+            string_set_properties
+                .properties
+                .insert(node_type.clone(), properties);
+        }
+        Ok(string_set_properties)
+    }
+
+    /// The string set properties of `node_type`, which has none if the
+    /// tenant's schema doesn't declare it. Schemas can be deployed at any time,
+    /// so undeclared node types aren't cached.
+    #[tracing::instrument(skip(self), err)]
+    pub async fn resolve_string_sets(
+        &self,
+        tenant_id: uuid::Uuid,
+        node_type: NodeType,
+    ) -> Result<Arc<FxHashSet<PropertyName>>, StringSetResolverError> {
+        let key = (tenant_id, node_type.clone());
+        if let Some(properties) = self.string_set_cache.get(&key) {
+            return Ok(properties);
+        }
+
+        let mut schema_client = self.schema_client.clone();
+        let response = match schema_client
+            .get_node_schema(GetNodeSchemaRequest {
+                tenant_id,
+                node_type,
+            })
+            .await
+        {
+            Ok(response) => response,
+            Err(ClientError::Status(status)) if status.code() == Code::NotFound => {
+                return Ok(Arc::default())
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        let properties: Arc<FxHashSet<PropertyName>> = Arc::new(
+            response
+                .properties
+                .into_iter()
+                .filter(|property| property.property_type == PropertyType::StringSet)
+                .map(|property| property.property_name)
+                .collect(),
+        );
+        self.string_set_cache.insert(key, properties.clone()).await;
+        Ok(properties)
+    }
+}
@@ -12,9 +12,16 @@ use std::{
 
 use rust_proto::graplinc::grapl::{
     api::graph_query::v1beta1::messages::QueryId,
-    common::v1beta1::types::EdgeName,
+    common::v1beta1::types::{
+        EdgeName,
+        Uid,
+    },
 };
 
+/// A node that a path filter's search has reached, keyed by the path's source
+/// query, edge name and destination query
+type PathNodeKey = (QueryId, EdgeName, QueryId, Uid);
+
 // We should have the short circuit logic get shared between tasks
 // so that parallel queries in WithUid queries can short circuit
 
@@ -22,6 +29,8 @@ use rust_proto::graplinc::grapl::{
 pub struct Visited {
     short_circuit: Arc<AtomicBool>,
     already_visited: Arc<Mutex<HashSet<(QueryId, EdgeName, QueryId)>>>,
+    // The nodes that each path filter's search has reached
+    already_visited_path_nodes: Arc<Mutex<HashSet<PathNodeKey>>>,
 }
 
 impl Default for Visited {
@@ -35,9 +44,34 @@ impl Visited {
         Self {
             short_circuit: Arc::new(AtomicBool::new(false)),
             already_visited: Arc::new(Mutex::new(HashSet::new())),
+            already_visited_path_nodes: Arc::new(Mutex::new(HashSet::new())),
         }
     }
 
+    /// A copy of what's been visited so far with its own short circuit, so
+    /// that a candidate node which fails to match neither short circuits nor
+    /// marks edges as visited for the candidates after it. `join` the fork
+    /// back once its candidate matches.
+    pub fn fork(&self) -> Self {
+        Self {
+            short_circuit: Arc::new(AtomicBool::new(false)),
+            already_visited: Arc::new(Mutex::new(self.already_visited.lock().unwrap().clone())),
+            already_visited_path_nodes: Arc::new(Mutex::new(
+                self.already_visited_path_nodes.lock().unwrap().clone(),
+            )),
+        }
+    }
+
+    pub fn join(&self, fork: &Self) {
+        let visited = fork.already_visited.lock().unwrap().clone();
+        self.already_visited.lock().unwrap().extend(visited);
+        let visited_path_nodes = fork.already_visited_path_nodes.lock().unwrap().clone();
+        self.already_visited_path_nodes
+            .lock()
+            .unwrap()
+            .extend(visited_path_nodes);
+    }
+
     pub fn get_short_circuit(&self) -> bool {
         self.short_circuit.as_ref().load(Ordering::Acquire)
     }
@@ -60,4 +94,19 @@ impl Visited {
     pub fn add(&self, src: QueryId, edge_name: EdgeName, dst: QueryId) {
         (*self.already_visited.lock().unwrap()).insert((src, edge_name.into(), dst));
     }
+
+    /// Marks `uid` as reached by the search for paths along `edge_name` from
+    /// `src` to `dst`, returning whether it already had been. Reaching a node
+    /// twice means either a cycle or a longer path to a node that's already
+    /// been searched from, so it's never worth searching from again.
+    pub fn check_and_add_path_node(
+        &self,
+        src: QueryId,
+        edge_name: EdgeName,
+        dst: QueryId,
+        uid: Uid,
+    ) -> bool {
+        let path_node: PathNodeKey = (src, edge_name, dst, uid);
+        !(*self.already_visited_path_nodes.lock().unwrap()).insert(path_node)
+    }
 }
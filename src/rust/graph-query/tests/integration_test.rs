@@ -1,18 +1,53 @@
 #![cfg(feature = "integration_tests")]
+use std::{
+    collections::{
+        HashMap,
+        HashSet,
+    },
+    time::{
+        Duration,
+        SystemTime,
+    },
+};
+
 use bytes::Bytes;
+use clap::Parser;
 use figment::{
     providers::Env,
     Figment,
 };
-use graph_query::node_query::NodeQuery;
+use graph_query::{
+    config::{
+        GraphDbConfig,
+        PropertyCacheConfig,
+        QueryLimitsConfig,
+    },
+    node_query::NodeQuery,
+    property_cache::PropertyCache,
+    server::{
+        GraphQueryService,
+        GraphQueryServiceError,
+    },
+};
 use rust_proto::graplinc::grapl::{
     api::{
-        client::Connect,
+        client::{
+            ClientError,
+            Connect,
+        },
         graph::v1beta1::{
+            ImmutableBoolProp,
+            ImmutableBytesProp,
+            ImmutableDoubleProp,
+            ImmutableIntProp,
             ImmutableStrProp,
+            ImmutableTimestampProp,
+            ImmutableUintProp,
+            IncrementOnlyTimestampProp,
             IncrementOnlyUintProp,
             NodeProperty,
             Property,
+            StringSetProp,
         },
         graph_mutation::v1beta1::{
             client::GraphMutationClient,
@@ -21,18 +56,29 @@ use rust_proto::graplinc::grapl::{
         graph_query::v1beta1::{
             client::GraphQueryClient,
             messages::{
+                AggregateGraphFromUidRequest,
+                FloatCmp,
+                IntCmp,
                 MatchedGraphWithUid,
                 MaybeMatchWithUid,
                 NodePropertyQuery,
                 QueryGraphFromUidRequest,
+                QueryGraphWithTextRequest,
                 QueryGraphWithUidRequest,
+                SearchNodesRequest,
+                SearchValue,
                 StringCmp,
             },
+            server::GraphQueryApi,
         },
         graph_schema_manager::v1beta1::{
             client::GraphSchemaManagerClient,
             messages as graph_schema_manager_api,
         },
+        protocol::status::{
+            Code,
+            Status,
+        },
         uid_allocator::v1beta1::{
             client::UidAllocatorClient,
             messages::CreateTenantKeyspaceRequest,
@@ -41,6 +87,8 @@ use rust_proto::graplinc::grapl::{
     common::v1beta1::types::{
         EdgeName,
         NodeType,
+        PropertyName,
+        Uid,
     },
 };
 
@@ -68,6 +116,35 @@ async fn provision_example_graph_schema(tenant_id: uuid::Uuid) -> eyre::Result<(
     Ok(())
 }
 
+/// A graph query service running in the test process, so that its query
+/// limits can be set well below the deployed service's.
+async fn graph_query_service(
+    query_limits_config: QueryLimitsConfig,
+) -> eyre::Result<GraphQueryService> {
+    let graph_db_config = GraphDbConfig::parse_from(["graph-query"]);
+    let mut scylla_config = scylla::SessionConfig::new();
+    scylla_config.add_known_nodes_addr(&graph_db_config.graph_db_addresses[..]);
+    scylla_config.auth_username = Some(graph_db_config.graph_db_auth_username.to_owned());
+    scylla_config.auth_password = Some(
+        secrecy::ExposeSecret::expose_secret(&graph_db_config.graph_db_auth_password).to_owned(),
+    );
+    let scylla_client = std::sync::Arc::new(scylla::CachingSession::from(
+        scylla::Session::connect(scylla_config).await?,
+        10_000,
+    ));
+
+    let graph_schema_manager_client_config = Figment::new()
+        .merge(Env::prefixed("GRAPH_SCHEMA_MANAGER_CLIENT_"))
+        .extract()?;
+
+    Ok(GraphQueryService::new(
+        scylla_client,
+        PropertyCache::new(&PropertyCacheConfig::parse_from(["graph-query"])),
+        query_limits_config,
+        graph_schema_manager_client_config,
+    ))
+}
+
 // This should probably replaced by a refactored E2eTestContext at some point.
 struct GraphQueryIntegTestSetup {
     graph_query_client: GraphQueryClient,
@@ -193,6 +270,7 @@ async fn test_query_two_attached_nodes() -> eyre::Result<()> {
             tenant_id: tenant_id.into(),
             node_uid: first_node_uid,
             graph_query,
+            explain: false,
         })
         .await?;
 
@@ -242,6 +320,7 @@ async fn test_query_two_attached_nodes() -> eyre::Result<()> {
             tenant_id: tenant_id.into(),
             node_uid: first_node_uid,
             graph_query,
+            explain: false,
         })
         .await?;
 
@@ -331,4 +410,1361 @@ async fn test_set_node_property() -> eyre::Result<()> {
     Ok(())
 }
 
+#[test_log::test(tokio::test)]
+async fn test_string_set_matches_any_member() -> eyre::Result<()> {
+    let GraphQueryIntegTestSetup {
+        mut graph_query_client,
+        mut graph_mutation_client,
+        tenant_id,
+        _span,
+    } = GraphQueryIntegTestSetup::setup().await?;
+
+    let process_node_type = NodeType::try_from("Process").unwrap();
+
+    let mutation::CreateNodeResponse { uid } = graph_mutation_client
+        .create_node(mutation::CreateNodeRequest {
+            tenant_id,
+            node_type: process_node_type.clone(),
+        })
+        .await?;
+
+    graph_mutation_client
+        .set_node_property(mutation::SetNodePropertyRequest {
+            tenant_id,
+            uid,
+            node_type: process_node_type.clone(),
+            property_name: "command_line_args".try_into()?,
+            property: NodeProperty {
+                property: Property::StringSetProp(StringSetProp::from_iter([
+                    "-NoProfile",
+                    "-EncodedCommand",
+                ])),
+            },
+        })
+        .await?;
+
+    let query_with_arg = |arg: &str| {
+        NodeQuery::root(process_node_type.clone())
+            .with_string_comparisons(
+                "command_line_args".try_into().unwrap(),
+                vec![StringCmp::Eq(arg.to_owned(), false)],
+            )
+            .build()
+    };
+
+    let response = graph_query_client
+        .query_graph_with_uid(QueryGraphWithUidRequest {
+            tenant_id: tenant_id.into(),
+            node_uid: uid,
+            graph_query: query_with_arg("-EncodedCommand"),
+            explain: false,
+        })
+        .await?;
+
+    let matched_graph = match response.maybe_match {
+        MaybeMatchWithUid::Matched(MatchedGraphWithUid { matched_graph, .. }) => matched_graph,
+        MaybeMatchWithUid::Missed(_) => panic!("Expected a match"),
+    };
+    let (_, returned_node) = matched_graph.nodes.into_iter().next().unwrap();
+    assert_eq!(
+        returned_node
+            .string_properties
+            .prop_map
+            .get(&PropertyName::try_from("command_line_args")?)
+            .map(String::as_str),
+        Some("-EncodedCommand")
+    );
+
+    let response = graph_query_client
+        .query_graph_with_uid(QueryGraphWithUidRequest {
+            tenant_id: tenant_id.into(),
+            node_uid: uid,
+            graph_query: query_with_arg("-Command"),
+            explain: false,
+        })
+        .await?;
+
+    assert!(matches!(response.maybe_match, MaybeMatchWithUid::Missed(_)));
+
+    drop(_span);
+    Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_bool_float_bytes_and_timestamp_filters() -> eyre::Result<()> {
+    let GraphQueryIntegTestSetup {
+        mut graph_query_client,
+        mut graph_mutation_client,
+        tenant_id,
+        _span,
+    } = GraphQueryIntegTestSetup::setup().await?;
+
+    let file_node_type = NodeType::try_from("File").unwrap();
+
+    let mutation::CreateNodeResponse { uid } = graph_mutation_client
+        .create_node(mutation::CreateNodeRequest {
+            tenant_id,
+            node_type: file_node_type.clone(),
+        })
+        .await?;
+
+    let sha256 = Bytes::from_static(&[0xde, 0xad, 0xbe, 0xef, 0x00, 0x01]);
+    let created_at = SystemTime::UNIX_EPOCH + Duration::from_millis(1_666_000_000_123);
+    let last_seen = created_at + Duration::from_secs(60);
+
+    for (property_name, property) in [
+        (
+            "sha256",
+            Property::ImmutableBytesProp(ImmutableBytesProp {
+                prop: sha256.clone(),
+            }),
+        ),
+        (
+            "is_signed",
+            Property::ImmutableBoolProp(ImmutableBoolProp { prop: false }),
+        ),
+        (
+            "entropy",
+            Property::ImmutableDoubleProp(ImmutableDoubleProp { prop: 7.25 }),
+        ),
+        (
+            "created_at",
+            Property::ImmutableTimestampProp(ImmutableTimestampProp { prop: created_at }),
+        ),
+        (
+            "last_seen",
+            Property::IncrementOnlyTimestampProp(IncrementOnlyTimestampProp { prop: last_seen }),
+        ),
+    ] {
+        graph_mutation_client
+            .set_node_property(mutation::SetNodePropertyRequest {
+                tenant_id,
+                uid,
+                node_type: file_node_type.clone(),
+                property_name: property_name.try_into()?,
+                property: NodeProperty { property },
+            })
+            .await?;
+    }
+
+    let query = |init: &dyn Fn(&mut NodeQuery)| {
+        let mut node_query = NodeQuery::root(file_node_type.clone());
+        init(&mut node_query);
+        node_query.build()
+    };
+    let created_at_millis = 1_666_000_000_123;
+
+    let cases = [
+        // The bytes are read back exactly as they were written
+        (
+            "sha256 equal",
+            query(&|q| {
+                q.with_bytes("sha256".try_into().unwrap(), sha256.clone());
+            }),
+            true,
+        ),
+        (
+            "sha256 different",
+            query(&|q| {
+                q.with_bytes("sha256".try_into().unwrap(), vec![0xde, 0xad]);
+            }),
+            false,
+        ),
+        (
+            "is_signed false",
+            query(&|q| {
+                q.with_bool("is_signed".try_into().unwrap(), false);
+            }),
+            true,
+        ),
+        (
+            "is_signed true",
+            query(&|q| {
+                q.with_bool("is_signed".try_into().unwrap(), true);
+            }),
+            false,
+        ),
+        (
+            "entropy between 7 and 8",
+            query(&|q| {
+                q.with_float_comparisons(
+                    "entropy".try_into().unwrap(),
+                    vec![FloatCmp::Gt(7.0, false), FloatCmp::Lt(8.0, false)],
+                );
+            }),
+            true,
+        ),
+        (
+            "entropy above 7.5",
+            query(&|q| {
+                q.with_float_comparisons(
+                    "entropy".try_into().unwrap(),
+                    vec![FloatCmp::Gte(7.5, false)],
+                );
+            }),
+            false,
+        ),
+        (
+            "created_at exactly",
+            query(&|q| {
+                q.with_timestamp_comparisons(
+                    "created_at".try_into().unwrap(),
+                    vec![IntCmp::Eq(created_at_millis, false)],
+                );
+            }),
+            true,
+        ),
+        (
+            "created_at later",
+            query(&|q| {
+                q.with_timestamp_comparisons(
+                    "created_at".try_into().unwrap(),
+                    vec![IntCmp::Gt(created_at_millis, false)],
+                );
+            }),
+            false,
+        ),
+        // Max timestamps are read from their own table
+        (
+            "last_seen after created_at",
+            query(&|q| {
+                q.with_timestamp_comparisons(
+                    "last_seen".try_into().unwrap(),
+                    vec![IntCmp::Eq(created_at_millis + 60_000, false)],
+                );
+            }),
+            true,
+        ),
+        // Missing properties never match
+        (
+            "missing bytes",
+            query(&|q| {
+                q.with_bytes("md5".try_into().unwrap(), sha256.clone());
+            }),
+            false,
+        ),
+        (
+            "missing timestamp",
+            query(&|q| {
+                q.with_timestamp_comparisons("deleted_at".try_into().unwrap(), vec![IntCmp::Has]);
+            }),
+            false,
+        ),
+    ];
+
+    for (description, graph_query, expected) in cases {
+        let response = graph_query_client
+            .query_graph_with_uid(QueryGraphWithUidRequest {
+                tenant_id: tenant_id.into(),
+                node_uid: uid,
+                graph_query,
+                explain: false,
+            })
+            .await?;
+
+        let matched = matches!(response.maybe_match, MaybeMatchWithUid::Matched(_));
+        assert_eq!(matched, expected, "{description} should match: {expected}");
+    }
+
+    drop(_span);
+    Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_int_filters() -> eyre::Result<()> {
+    let GraphQueryIntegTestSetup {
+        mut graph_query_client,
+        mut graph_mutation_client,
+        tenant_id,
+        _span,
+    } = GraphQueryIntegTestSetup::setup().await?;
+
+    let process_node_type = NodeType::try_from("Process").unwrap();
+
+    let mutation::CreateNodeResponse { uid } = graph_mutation_client
+        .create_node(mutation::CreateNodeRequest {
+            tenant_id,
+            node_type: process_node_type.clone(),
+        })
+        .await?;
+
+    graph_mutation_client
+        .set_node_property(mutation::SetNodePropertyRequest {
+            tenant_id,
+            uid,
+            node_type: process_node_type.clone(),
+            property_name: "process_id".try_into()?,
+            property: NodeProperty {
+                property: Property::ImmutableUintProp(ImmutableUintProp { prop: 4 }),
+            },
+        })
+        .await?;
+
+    // Not in the schema, but signed properties are read from their own tables
+    graph_mutation_client
+        .set_node_property(mutation::SetNodePropertyRequest {
+            tenant_id,
+            uid,
+            node_type: process_node_type.clone(),
+            property_name: "exit_code".try_into()?,
+            property: NodeProperty {
+                property: Property::ImmutableIntProp(ImmutableIntProp { prop: -1 }),
+            },
+        })
+        .await?;
+
+    let cases = [
+        ("process_id", vec![vec![IntCmp::Lt(10, false)]], true),
+        ("process_id", vec![vec![IntCmp::Gt(10, false)]], false),
+        (
+            "process_id",
+            vec![vec![IntCmp::Gte(4, false), IntCmp::Lte(4, false)]],
+            true,
+        ),
+        (
+            "process_id",
+            vec![vec![IntCmp::Gt(1, false), IntCmp::Eq(4, true)]],
+            false,
+        ),
+        // Any one of the alternatives may match
+        (
+            "process_id",
+            vec![vec![IntCmp::Gt(100, false)], vec![IntCmp::Eq(4, false)]],
+            true,
+        ),
+        ("exit_code", vec![vec![IntCmp::Lt(0, false)]], true),
+        ("exit_code", vec![vec![IntCmp::Eq(-1, true)]], false),
+        ("exit_code", vec![vec![IntCmp::Has]], true),
+        // Missing properties never match
+        ("created_at", vec![vec![IntCmp::Has]], false),
+        ("created_at", vec![vec![IntCmp::Eq(0, true)]], false),
+    ];
+
+    for (property_name, alternatives, expected) in cases {
+        let mut node_query = NodeQuery::root(process_node_type.clone());
+        for comparisons in alternatives.clone() {
+            node_query.with_int_comparisons(property_name.try_into()?, comparisons);
+        }
+
+        let response = graph_query_client
+            .query_graph_with_uid(QueryGraphWithUidRequest {
+                tenant_id: tenant_id.into(),
+                node_uid: uid,
+                graph_query: node_query.build(),
+                explain: false,
+            })
+            .await?;
+
+        let matched = matches!(response.maybe_match, MaybeMatchWithUid::Matched(_));
+        assert_eq!(
+            matched, expected,
+            "{property_name} with {alternatives:?} should match: {expected}"
+        );
+    }
+
+    drop(_span);
+    Ok(())
+}
+
 // TODO: test `with_edge_to`
+
+#[test_log::test(tokio::test)]
+async fn test_regex_filters() -> eyre::Result<()> {
+    let GraphQueryIntegTestSetup {
+        mut graph_query_client,
+        mut graph_mutation_client,
+        tenant_id,
+        _span,
+    } = GraphQueryIntegTestSetup::setup().await?;
+
+    let process_node_type = NodeType::try_from("Process").unwrap();
+
+    let mutation::CreateNodeResponse { uid } = graph_mutation_client
+        .create_node(mutation::CreateNodeRequest {
+            tenant_id,
+            node_type: process_node_type.clone(),
+        })
+        .await?;
+
+    graph_mutation_client
+        .set_node_property(mutation::SetNodePropertyRequest {
+            tenant_id,
+            uid,
+            node_type: process_node_type.clone(),
+            property_name: "process_name".try_into()?,
+            property: NodeProperty {
+                property: Property::ImmutableStrProp(ImmutableStrProp {
+                    prop: "cmd.exe".to_owned(),
+                }),
+            },
+        })
+        .await?;
+
+    let regex = |pattern: &str, negated: bool| StringCmp::Regex(pattern.to_owned(), negated);
+    let cases = [
+        (vec![regex("^c.d\\.exe$", false)], true),
+        (vec![regex("^powershell", false)], false),
+        (vec![regex("(?i)^CMD", true)], false),
+        (vec![regex("^powershell", true)], true),
+        (
+            vec![
+                regex("\\.exe$", false),
+                StringCmp::Contains("cmd".to_owned(), false),
+            ],
+            true,
+        ),
+    ];
+
+    for (comparisons, expected) in cases {
+        let graph_query = NodeQuery::root(process_node_type.clone())
+            .with_string_comparisons("process_name".try_into()?, comparisons.clone())
+            .build();
+
+        let response = graph_query_client
+            .query_graph_with_uid(QueryGraphWithUidRequest {
+                tenant_id: tenant_id.into(),
+                node_uid: uid,
+                graph_query,
+                explain: false,
+            })
+            .await?;
+
+        let matched = matches!(response.maybe_match, MaybeMatchWithUid::Matched(_));
+        assert_eq!(
+            matched, expected,
+            "{comparisons:?} should match: {expected}"
+        );
+    }
+
+    // Patterns that don't compile, or compile to too large a program, are
+    // rejected before the query runs
+    for pattern in ["(cmd", "\\w{1000}{1000}"] {
+        let graph_query = NodeQuery::root(process_node_type.clone())
+            .with_string_comparisons("process_name".try_into()?, vec![regex(pattern, false)])
+            .build();
+
+        let result = graph_query_client
+            .query_graph_with_uid(QueryGraphWithUidRequest {
+                tenant_id: tenant_id.into(),
+                node_uid: uid,
+                graph_query,
+                explain: false,
+            })
+            .await;
+
+        match result {
+            Err(ClientError::Status(status)) => assert_eq!(status.code(), Code::InvalidArgument),
+            other => panic!("expected {pattern} to be rejected, got {other:?}"),
+        }
+    }
+
+    drop(_span);
+    Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_uid_filters() -> eyre::Result<()> {
+    let GraphQueryIntegTestSetup {
+        mut graph_query_client,
+        mut graph_mutation_client,
+        tenant_id,
+        _span,
+    } = GraphQueryIntegTestSetup::setup().await?;
+
+    let process_node_type = NodeType::try_from("Process").unwrap();
+    let file_node_type = NodeType::try_from("File").unwrap();
+    let forward_edge_name = EdgeName {
+        value: "created_files".to_string(),
+    };
+    let reverse_edge_name = EdgeName {
+        value: "created_by_process".to_string(),
+    };
+
+    let mutation::CreateNodeResponse { uid: process_uid } = graph_mutation_client
+        .create_node(mutation::CreateNodeRequest {
+            tenant_id,
+            node_type: process_node_type.clone(),
+        })
+        .await?;
+
+    let mut file_uids = vec![];
+    for _ in 0..2 {
+        let mutation::CreateNodeResponse { uid } = graph_mutation_client
+            .create_node(mutation::CreateNodeRequest {
+                tenant_id,
+                node_type: file_node_type.clone(),
+            })
+            .await?;
+        graph_mutation_client
+            .create_edge(mutation::CreateEdgeRequest {
+                edge_name: forward_edge_name.clone(),
+                tenant_id,
+                from_uid: process_uid,
+                to_uid: uid,
+                source_node_type: process_node_type.clone(),
+            })
+            .await?;
+        file_uids.push(uid);
+    }
+
+    // Either file may be read first, so pin each of them in turn
+    for (pinned_uid, other_uid) in [(file_uids[0], file_uids[1]), (file_uids[1], file_uids[0])] {
+        let graph_query = NodeQuery::root(process_node_type.clone())
+            .with_uid(process_uid)
+            .with_edge_to(
+                forward_edge_name.clone(),
+                reverse_edge_name.clone(),
+                file_node_type.clone(),
+                |file| {
+                    file.with_uid(pinned_uid);
+                },
+            )
+            .build();
+
+        let response = graph_query_client
+            .query_graph_from_uid(QueryGraphFromUidRequest {
+                tenant_id: tenant_id.into(),
+                node_uid: process_uid,
+                graph_query: graph_query.clone(),
+                explain: false,
+            })
+            .await?;
+
+        let matched_graph = response.matched_graph.expect("Expected a matched graph");
+        assert_eq!(matched_graph.nodes.len(), 2);
+        assert!(matched_graph.nodes.contains_key(&pinned_uid));
+        assert!(!matched_graph.nodes.contains_key(&other_uid));
+
+        let response = graph_query_client
+            .query_graph_with_uid(QueryGraphWithUidRequest {
+                tenant_id: tenant_id.into(),
+                node_uid: other_uid,
+                graph_query,
+                explain: false,
+            })
+            .await?;
+
+        assert!(matches!(response.maybe_match, MaybeMatchWithUid::Missed(_)));
+    }
+
+    drop(_span);
+    Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_path_filters() -> eyre::Result<()> {
+    let GraphQueryIntegTestSetup {
+        mut graph_query_client,
+        mut graph_mutation_client,
+        tenant_id,
+        _span,
+    } = GraphQueryIntegTestSetup::setup().await?;
+
+    let process_node_type = NodeType::try_from("Process").unwrap();
+    let forward_edge_name = EdgeName {
+        value: "children".to_string(),
+    };
+    let reverse_edge_name = EdgeName {
+        value: "parent".to_string(),
+    };
+
+    // WINWORD.EXE -> explorer.exe -> cmd.exe -> powershell.exe
+    let mut process_uids = vec![];
+    for process_name in ["WINWORD.EXE", "explorer.exe", "cmd.exe", "powershell.exe"] {
+        let mutation::CreateNodeResponse { uid } = graph_mutation_client
+            .create_node(mutation::CreateNodeRequest {
+                tenant_id,
+                node_type: process_node_type.clone(),
+            })
+            .await?;
+
+        graph_mutation_client
+            .set_node_property(mutation::SetNodePropertyRequest {
+                tenant_id,
+                uid,
+                node_type: process_node_type.clone(),
+                property_name: "process_name".try_into()?,
+                property: NodeProperty {
+                    property: Property::ImmutableStrProp(ImmutableStrProp {
+                        prop: process_name.to_owned(),
+                    }),
+                },
+            })
+            .await?;
+
+        if let Some(parent_uid) = process_uids.last() {
+            graph_mutation_client
+                .create_edge(mutation::CreateEdgeRequest {
+                    edge_name: forward_edge_name.clone(),
+                    tenant_id,
+                    from_uid: *parent_uid,
+                    to_uid: uid,
+                    source_node_type: process_node_type.clone(),
+                })
+                .await?;
+        }
+        process_uids.push(uid);
+    }
+    let powershell_uid = process_uids[3];
+
+    let not_explorer = {
+        let mut query = NodePropertyQuery::new(process_node_type.clone());
+        query.with_string_filters(
+            "process_name".try_into()?,
+            vec![StringCmp::eq("explorer.exe", true)],
+        );
+        query
+    };
+    let cases = [
+        (1..=5, None, true),
+        (3..=3, None, true),
+        (1..=2, None, false),
+        (1..=5, Some(not_explorer), false),
+    ];
+
+    for (hops, intermediate_node_query, expected) in cases {
+        let graph_query = NodeQuery::root(process_node_type.clone())
+            .with_string_comparisons(
+                "process_name".try_into()?,
+                vec![StringCmp::eq("powershell.exe", false)],
+            )
+            .with_path_to(
+                reverse_edge_name.clone(),
+                forward_edge_name.clone(),
+                process_node_type.clone(),
+                hops.clone(),
+                intermediate_node_query.clone(),
+                |ancestor| {
+                    ancestor.with_string_comparisons(
+                        "process_name".try_into().unwrap(),
+                        vec![StringCmp::eq("WINWORD.EXE", false)],
+                    );
+                },
+            )
+            .build();
+
+        let response = graph_query_client
+            .query_graph_with_uid(QueryGraphWithUidRequest {
+                tenant_id: tenant_id.into(),
+                node_uid: powershell_uid,
+                graph_query,
+                explain: false,
+            })
+            .await?;
+
+        match response.maybe_match {
+            MaybeMatchWithUid::Matched(MatchedGraphWithUid {
+                matched_graph,
+                root_uid,
+            }) => {
+                assert!(expected, "{hops:?} via {intermediate_node_query:?} matched");
+                assert_eq!(root_uid, powershell_uid);
+                // The whole path is returned, not just its ends
+                for uid in &process_uids {
+                    assert!(matched_graph.nodes.contains_key(uid));
+                }
+            }
+            MaybeMatchWithUid::Missed(_) => {
+                assert!(!expected, "{hops:?} via {intermediate_node_query:?} missed")
+            }
+        }
+    }
+
+    // A query sent without the path's reverse edge is rejected rather than
+    // panicking the service
+    let mut graph_query = NodeQuery::root(process_node_type.clone())
+        .with_path_to(
+            reverse_edge_name.clone(),
+            forward_edge_name.clone(),
+            process_node_type.clone(),
+            1..=5,
+            None,
+            |_| {},
+        )
+        .build();
+    graph_query.edge_map.remove(&reverse_edge_name);
+
+    let result = graph_query_client
+        .query_graph_with_uid(QueryGraphWithUidRequest {
+            tenant_id: tenant_id.into(),
+            node_uid: powershell_uid,
+            graph_query,
+            explain: false,
+        })
+        .await;
+
+    match result {
+        Err(ClientError::Status(status)) => assert_eq!(status.code(), Code::InvalidArgument),
+        other => panic!("expected a query without its reverse edge to be rejected, got {other:?}"),
+    }
+
+    drop(_span);
+    Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_search_nodes() -> eyre::Result<()> {
+    let GraphQueryIntegTestSetup {
+        mut graph_query_client,
+        mut graph_mutation_client,
+        tenant_id,
+        _span,
+    } = GraphQueryIntegTestSetup::setup().await?;
+
+    let process_node_type = NodeType::try_from("Process").unwrap();
+    let forward_edge_name = EdgeName {
+        value: "children".to_string(),
+    };
+    let reverse_edge_name = EdgeName {
+        value: "parent".to_string(),
+    };
+
+    // WINWORD.EXE -> cmd.exe, WINWORD.EXE -> cmd.exe, explorer.exe -> cmd.exe
+    let mut process_uids = HashMap::new();
+    for (node_key, process_name, process_id) in [
+        ("winword", "WINWORD.EXE", 1),
+        ("explorer", "explorer.exe", 2),
+        ("cmd_1", "cmd.exe", 3),
+        ("cmd_2", "cmd.exe", 4),
+        ("cmd_3", "cmd.exe", 5),
+    ] {
+        let mutation::CreateNodeResponse { uid } = graph_mutation_client
+            .create_node(mutation::CreateNodeRequest {
+                tenant_id,
+                node_type: process_node_type.clone(),
+            })
+            .await?;
+
+        graph_mutation_client
+            .set_node_property(mutation::SetNodePropertyRequest {
+                tenant_id,
+                uid,
+                node_type: process_node_type.clone(),
+                property_name: "process_name".try_into()?,
+                property: NodeProperty {
+                    property: Property::ImmutableStrProp(ImmutableStrProp {
+                        prop: process_name.to_owned(),
+                    }),
+                },
+            })
+            .await?;
+
+        graph_mutation_client
+            .set_node_property(mutation::SetNodePropertyRequest {
+                tenant_id,
+                uid,
+                node_type: process_node_type.clone(),
+                property_name: "process_id".try_into()?,
+                property: NodeProperty {
+                    property: Property::ImmutableUintProp(ImmutableUintProp { prop: process_id }),
+                },
+            })
+            .await?;
+        process_uids.insert(node_key, uid);
+    }
+    for (parent, child) in [
+        ("winword", "cmd_1"),
+        ("winword", "cmd_2"),
+        ("explorer", "cmd_3"),
+    ] {
+        graph_mutation_client
+            .create_edge(mutation::CreateEdgeRequest {
+                edge_name: forward_edge_name.clone(),
+                tenant_id,
+                from_uid: process_uids[parent],
+                to_uid: process_uids[child],
+                source_node_type: process_node_type.clone(),
+            })
+            .await?;
+    }
+    let cmd_uids: HashSet<Uid> = ["cmd_1", "cmd_2", "cmd_3"]
+        .into_iter()
+        .map(|node_key| process_uids[node_key])
+        .collect();
+
+    // Page through every cmd.exe, two at a time
+    let mut request = SearchNodesRequest {
+        tenant_id,
+        node_type: process_node_type.clone(),
+        property_name: "process_name".try_into()?,
+        value: SearchValue::String("cmd.exe".to_owned()),
+        page_size: Some(2),
+        page_token: None,
+        graph_query: None,
+        explain: false,
+    };
+    let mut found_uids = HashSet::new();
+    let mut pages = 0;
+    loop {
+        let response = graph_query_client.search_nodes(request.clone()).await?;
+        pages += 1;
+        assert!(response.matches.len() <= 2);
+        assert!(response.explanation.is_none());
+        for search_match in response.matches {
+            assert!(search_match.matched_graph.is_none());
+            assert!(found_uids.insert(search_match.uid), "uids are not repeated");
+        }
+        match response.next_page_token {
+            Some(page_token) => request.page_token = Some(page_token),
+            None => break,
+        }
+    }
+    assert_eq!(found_uids, cmd_uids);
+    assert_eq!(pages, 2);
+
+    // Integers are indexed too
+    let response = graph_query_client
+        .search_nodes(SearchNodesRequest {
+            property_name: "process_id".try_into()?,
+            value: SearchValue::U64(4),
+            page_size: None,
+            page_token: None,
+            ..request.clone()
+        })
+        .await?;
+    let found_uids: Vec<Uid> = response.matches.iter().map(|m| m.uid).collect();
+    assert_eq!(found_uids, vec![process_uids["cmd_2"]]);
+
+    // A page size of 0 can't be sent over the wire, but is read as a page of 1
+    let response = graph_query_service(QueryLimitsConfig::parse_from(["graph-query"]))
+        .await?
+        .search_nodes(SearchNodesRequest {
+            page_size: Some(0),
+            page_token: None,
+            ..request.clone()
+        })
+        .await?;
+    assert_eq!(response.matches.len(), 1);
+    assert!(response.next_page_token.is_some());
+
+    // Expanded through a graph query, only WINWORD.EXE's children are left
+    let graph_query = NodeQuery::root(process_node_type.clone())
+        .with_edge_to(
+            reverse_edge_name.clone(),
+            forward_edge_name.clone(),
+            process_node_type.clone(),
+            |parent| {
+                parent.with_string_comparisons(
+                    "process_name".try_into().unwrap(),
+                    vec![StringCmp::eq("WINWORD.EXE", false)],
+                );
+            },
+        )
+        .build();
+    let root_query_id = graph_query.root_query_id;
+    let response = graph_query_client
+        .search_nodes(SearchNodesRequest {
+            page_size: None,
+            page_token: None,
+            graph_query: Some(graph_query),
+            explain: true,
+            ..request.clone()
+        })
+        .await?;
+    let explanation = response.explanation.expect("Expected an explanation");
+    let root = explanation.node_query(root_query_id).unwrap();
+    // every cmd.exe was matched against the root, WINWORD.EXE's children did
+    assert!(root.nodes_visited >= 3);
+    assert!(root.nodes_matched >= 2);
+    assert_eq!(response.next_page_token, None);
+    let found_uids: HashSet<Uid> = response.matches.iter().map(|m| m.uid).collect();
+    assert_eq!(
+        found_uids,
+        HashSet::from([process_uids["cmd_1"], process_uids["cmd_2"]])
+    );
+    for search_match in &response.matches {
+        let matched_graph = search_match
+            .matched_graph
+            .as_ref()
+            .expect("expanded matches carry their graph");
+        assert!(matched_graph.nodes.contains_key(&search_match.uid));
+        assert!(matched_graph.nodes.contains_key(&process_uids["winword"]));
+    }
+
+    drop(_span);
+    Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_edge_counts() -> eyre::Result<()> {
+    let GraphQueryIntegTestSetup {
+        mut graph_query_client,
+        mut graph_mutation_client,
+        tenant_id,
+        _span,
+    } = GraphQueryIntegTestSetup::setup().await?;
+
+    let process_node_type = NodeType::try_from("Process").unwrap();
+    let forward_edge_name = EdgeName {
+        value: "children".to_string(),
+    };
+    let reverse_edge_name = EdgeName {
+        value: "parent".to_string(),
+    };
+
+    // WINWORD.EXE has three children, explorer.exe has one
+    let mut process_uids = HashMap::new();
+    for (node_key, process_name) in [
+        ("winword", "WINWORD.EXE"),
+        ("explorer", "explorer.exe"),
+        ("cmd_1", "cmd.exe"),
+        ("cmd_2", "cmd.exe"),
+        ("cmd_3", "cmd.exe"),
+        ("notepad", "notepad.exe"),
+    ] {
+        let mutation::CreateNodeResponse { uid } = graph_mutation_client
+            .create_node(mutation::CreateNodeRequest {
+                tenant_id,
+                node_type: process_node_type.clone(),
+            })
+            .await?;
+
+        graph_mutation_client
+            .set_node_property(mutation::SetNodePropertyRequest {
+                tenant_id,
+                uid,
+                node_type: process_node_type.clone(),
+                property_name: "process_name".try_into()?,
+                property: NodeProperty {
+                    property: Property::ImmutableStrProp(ImmutableStrProp {
+                        prop: process_name.to_owned(),
+                    }),
+                },
+            })
+            .await?;
+        process_uids.insert(node_key, uid);
+    }
+    for (parent, child) in [
+        ("winword", "cmd_1"),
+        ("winword", "cmd_2"),
+        ("winword", "cmd_3"),
+        ("explorer", "notepad"),
+    ] {
+        graph_mutation_client
+            .create_edge(mutation::CreateEdgeRequest {
+                edge_name: forward_edge_name.clone(),
+                tenant_id,
+                from_uid: process_uids[parent],
+                to_uid: process_uids[child],
+                source_node_type: process_node_type.clone(),
+            })
+            .await?;
+    }
+
+    // Only WINWORD.EXE has more than two children
+    let graph_query = NodeQuery::root(process_node_type.clone())
+        .with_edge_count(forward_edge_name.clone(), vec![IntCmp::Gt(2, false)])
+        .build();
+    for (node_key, expected_match) in [("winword", true), ("explorer", false)] {
+        let response = graph_query_client
+            .query_graph_from_uid(QueryGraphFromUidRequest {
+                tenant_id,
+                node_uid: process_uids[node_key],
+                graph_query: graph_query.clone(),
+                explain: false,
+            })
+            .await?;
+        assert_eq!(response.matched_graph.is_some(), expected_match);
+    }
+
+    // Every matched node is counted, without any constraint on the counts
+    let graph_query = NodeQuery::root(process_node_type.clone())
+        .with_string_comparisons(
+            "process_name".try_into()?,
+            vec![StringCmp::eq("WINWORD.EXE", false)],
+        )
+        .with_edge_to(
+            forward_edge_name.clone(),
+            reverse_edge_name.clone(),
+            process_node_type.clone(),
+            |_| {},
+        )
+        .build();
+    let response = graph_query_client
+        .aggregate_graph_from_uid(AggregateGraphFromUidRequest {
+            tenant_id,
+            node_uid: process_uids["winword"],
+            graph_query: graph_query.clone(),
+            edge_names: vec![],
+            explain: true,
+        })
+        .await?;
+    let explanation = response.explanation.expect("Expected an explanation");
+    let root = explanation.node_query(graph_query.root_query_id).unwrap();
+    assert!(root.nodes_matched >= 1);
+    let edge_counts = response.edge_counts.expect("WINWORD.EXE matched");
+    assert_eq!(edge_counts.nodes.len(), 4);
+    let winword_counts = edge_counts.node(process_uids["winword"]).unwrap();
+    assert_eq!(winword_counts.count(&forward_edge_name), 3);
+    assert_eq!(winword_counts.count(&reverse_edge_name), 0);
+    for node_key in ["cmd_1", "cmd_2", "cmd_3"] {
+        let cmd_counts = edge_counts.node(process_uids[node_key]).unwrap();
+        assert_eq!(cmd_counts.count(&reverse_edge_name), 1);
+        assert_eq!(cmd_counts.count(&forward_edge_name), 0);
+    }
+
+    // explorer.exe doesn't match, so there's nothing to count
+    let response = graph_query_client
+        .aggregate_graph_from_uid(AggregateGraphFromUidRequest {
+            tenant_id,
+            node_uid: process_uids["explorer"],
+            graph_query,
+            edge_names: vec![forward_edge_name.clone()],
+            explain: false,
+        })
+        .await?;
+    assert!(response.edge_counts.is_none());
+    assert!(response.explanation.is_none());
+
+    drop(_span);
+    Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_query_graph_with_text() -> eyre::Result<()> {
+    let GraphQueryIntegTestSetup {
+        mut graph_query_client,
+        mut graph_mutation_client,
+        tenant_id,
+        _span,
+    } = GraphQueryIntegTestSetup::setup().await?;
+
+    let process_node_type = NodeType::try_from("Process").unwrap();
+
+    let mut process_uids = HashMap::new();
+    for process_name in ["explorer.exe", "powershell.exe"] {
+        let mutation::CreateNodeResponse { uid } = graph_mutation_client
+            .create_node(mutation::CreateNodeRequest {
+                tenant_id,
+                node_type: process_node_type.clone(),
+            })
+            .await?;
+        graph_mutation_client
+            .set_node_property(mutation::SetNodePropertyRequest {
+                tenant_id,
+                uid,
+                node_type: process_node_type.clone(),
+                property_name: "process_name".try_into()?,
+                property: NodeProperty {
+                    property: Property::ImmutableStrProp(ImmutableStrProp {
+                        prop: process_name.into(),
+                    }),
+                },
+            })
+            .await?;
+        process_uids.insert(process_name, uid);
+    }
+
+    graph_mutation_client
+        .create_edge(mutation::CreateEdgeRequest {
+            edge_name: EdgeName::try_from("children")?,
+            tenant_id,
+            from_uid: process_uids["explorer.exe"],
+            to_uid: process_uids["powershell.exe"],
+            source_node_type: process_node_type.clone(),
+        })
+        .await?;
+
+    // Matching from the child still returns the parent as the root
+    let response = graph_query_client
+        .query_graph_with_text(QueryGraphWithTextRequest {
+            tenant_id,
+            node_uid: process_uids["powershell.exe"],
+            query_text: r#"
+                MATCH (p:Process {process_name: ~"explorer"})-[:children]->(c:Process)
+                WHERE c.process_name = "powershell.exe"
+                RETURN p, c
+            "#
+            .to_owned(),
+            explain: false,
+        })
+        .await?;
+    let (matched_graph, root_uid) = match response.maybe_match {
+        MaybeMatchWithUid::Matched(MatchedGraphWithUid {
+            matched_graph,
+            root_uid,
+        }) => (matched_graph, root_uid),
+        MaybeMatchWithUid::Missed(_) => panic!("Expected a match"),
+    };
+    assert_eq!(root_uid, process_uids["explorer.exe"]);
+    assert_eq!(matched_graph.nodes.len(), 2);
+    assert_eq!(matched_graph.edges.len(), 2); // forward and reverse edge
+
+    let response = graph_query_client
+        .query_graph_with_text(QueryGraphWithTextRequest {
+            tenant_id,
+            node_uid: process_uids["powershell.exe"],
+            query_text: r#"
+                MATCH (c:Process)<-[:children]-(p:Process)
+                WHERE NOT p.process_name = "explorer.exe"
+                RETURN c
+            "#
+            .to_owned(),
+            explain: false,
+        })
+        .await?;
+    assert!(matches!(response.maybe_match, MaybeMatchWithUid::Missed(_)));
+
+    for query_text in [
+        // doesn't parse
+        "MATCH (p:Process)-[:children]->(c:Process RETURN p",
+        // File has no children edge, so it has no reverse either
+        "MATCH (f:File)-[:children]->(p:Process) RETURN f",
+    ] {
+        let result = graph_query_client
+            .query_graph_with_text(QueryGraphWithTextRequest {
+                tenant_id,
+                node_uid: process_uids["powershell.exe"],
+                query_text: query_text.to_owned(),
+                explain: false,
+            })
+            .await;
+
+        match result {
+            Err(ClientError::Status(status)) => assert_eq!(status.code(), Code::InvalidArgument),
+            other => panic!("expected {query_text} to be rejected, got {other:?}"),
+        }
+    }
+
+    drop(_span);
+    Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_query_graph_explain() -> eyre::Result<()> {
+    let GraphQueryIntegTestSetup {
+        mut graph_query_client,
+        mut graph_mutation_client,
+        tenant_id,
+        _span,
+    } = GraphQueryIntegTestSetup::setup().await?;
+
+    let process_node_type = NodeType::try_from("Process").unwrap();
+    let forward_edge_name = EdgeName::try_from("children")?;
+    let reverse_edge_name = EdgeName::try_from("parent")?;
+
+    let mut process_uids = HashMap::new();
+    for process_name in ["explorer.exe", "powershell.exe"] {
+        let mutation::CreateNodeResponse { uid } = graph_mutation_client
+            .create_node(mutation::CreateNodeRequest {
+                tenant_id,
+                node_type: process_node_type.clone(),
+            })
+            .await?;
+        graph_mutation_client
+            .set_node_property(mutation::SetNodePropertyRequest {
+                tenant_id,
+                uid,
+                node_type: process_node_type.clone(),
+                property_name: "process_name".try_into()?,
+                property: NodeProperty {
+                    property: Property::ImmutableStrProp(ImmutableStrProp {
+                        prop: process_name.into(),
+                    }),
+                },
+            })
+            .await?;
+        process_uids.insert(process_name, uid);
+    }
+
+    graph_mutation_client
+        .create_edge(mutation::CreateEdgeRequest {
+            edge_name: forward_edge_name.clone(),
+            tenant_id,
+            from_uid: process_uids["explorer.exe"],
+            to_uid: process_uids["powershell.exe"],
+            source_node_type: process_node_type.clone(),
+        })
+        .await?;
+
+    // explorer.exe's only child is powershell.exe, so the child misses
+    let graph_query = NodeQuery::root(process_node_type.clone())
+        .with_string_comparisons(
+            "process_name".try_into()?,
+            vec![StringCmp::Eq("explorer.exe".to_owned(), false)],
+        )
+        .with_edge_to(
+            forward_edge_name.clone(),
+            reverse_edge_name.clone(),
+            process_node_type.clone(),
+            |child| {
+                child.with_string_comparisons(
+                    "process_name".try_into().unwrap(),
+                    vec![StringCmp::Eq("cmd.exe".to_owned(), false)],
+                );
+            },
+        )
+        .build();
+    let parent_query_id = graph_query.root_query_id;
+    let child_query_id = *graph_query
+        .node_property_queries
+        .keys()
+        .find(|query_id| **query_id != parent_query_id)
+        .unwrap();
+
+    let response = graph_query_client
+        .query_graph_from_uid(QueryGraphFromUidRequest {
+            tenant_id,
+            node_uid: process_uids["explorer.exe"],
+            graph_query: graph_query.clone(),
+            explain: true,
+        })
+        .await?;
+    assert!(response.matched_graph.is_none());
+    let explanation = response.explanation.expect("Expected an explanation");
+
+    let parent = explanation.node_query(parent_query_id).unwrap();
+    assert_eq!(parent.node_type, process_node_type);
+    assert_eq!(parent.nodes_visited, 1);
+    assert_eq!(parent.nodes_matched, 0);
+    // the process name and the children edges
+    assert!(parent.scylla_reads >= 2);
+    assert_eq!(parent.filters_evaluated, 1);
+    assert_eq!(parent.short_circuits.len(), 1);
+    assert_eq!(parent.short_circuits[0].uid, process_uids["explorer.exe"]);
+    assert_eq!(
+        parent.short_circuits[0].reason,
+        "no children neighbor matched"
+    );
+
+    let child = explanation.node_query(child_query_id).unwrap();
+    assert_eq!(child.nodes_visited, 1);
+    assert_eq!(child.nodes_matched, 0);
+    assert_eq!(child.filters_evaluated, 1);
+    assert_eq!(child.short_circuits.len(), 1);
+    assert_eq!(child.short_circuits[0].uid, process_uids["powershell.exe"]);
+    assert_eq!(
+        child.short_circuits[0].reason,
+        "property process_name didn't match"
+    );
+    // the parent's time includes the child's
+    assert!(parent.elapsed >= child.elapsed);
+    assert!(explanation.elapsed >= parent.elapsed);
+
+    // Without `explain` there's nothing to explain
+    let response = graph_query_client
+        .query_graph_from_uid(QueryGraphFromUidRequest {
+            tenant_id,
+            node_uid: process_uids["explorer.exe"],
+            graph_query,
+            explain: false,
+        })
+        .await?;
+    assert!(response.explanation.is_none());
+
+    drop(_span);
+    Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_query_limits() -> eyre::Result<()> {
+    let GraphQueryIntegTestSetup {
+        graph_query_client: _,
+        mut graph_mutation_client,
+        tenant_id,
+        _span,
+    } = GraphQueryIntegTestSetup::setup().await?;
+
+    let process_node_type = NodeType::try_from("Process").unwrap();
+    let file_node_type = NodeType::try_from("File").unwrap();
+
+    let mutation::CreateNodeResponse { uid: process_uid } = graph_mutation_client
+        .create_node(mutation::CreateNodeRequest {
+            tenant_id,
+            node_type: process_node_type.clone(),
+        })
+        .await?;
+    graph_mutation_client
+        .set_node_property(mutation::SetNodePropertyRequest {
+            tenant_id,
+            uid: process_uid,
+            node_type: process_node_type.clone(),
+            property_name: "process_name".try_into()?,
+            property: NodeProperty {
+                property: Property::ImmutableStrProp(ImmutableStrProp {
+                    prop: "chrome.exe".into(),
+                }),
+            },
+        })
+        .await?;
+
+    let mutation::CreateNodeResponse { uid: file_uid } = graph_mutation_client
+        .create_node(mutation::CreateNodeRequest {
+            tenant_id,
+            node_type: file_node_type.clone(),
+        })
+        .await?;
+    graph_mutation_client
+        .create_edge(mutation::CreateEdgeRequest {
+            edge_name: "binary_file".try_into()?,
+            tenant_id,
+            from_uid: process_uid,
+            to_uid: file_uid,
+            source_node_type: process_node_type.clone(),
+        })
+        .await?;
+
+    // Reads the root's process_name, then its binary_file edges
+    let request = QueryGraphWithUidRequest {
+        tenant_id,
+        node_uid: process_uid,
+        graph_query: NodeQuery::root(process_node_type.clone())
+            .with_string_comparisons(
+                "process_name".try_into()?,
+                vec![StringCmp::Eq("chrome.exe".to_owned(), false)],
+            )
+            .with_shared_edge(
+                "binary_file".try_into()?,
+                "executed_as_processes".try_into()?,
+                NodePropertyQuery::new(file_node_type.clone()),
+                |_| {},
+            )
+            .build(),
+        explain: false,
+    };
+    let default_limits = QueryLimitsConfig::parse_from(["graph-query"]);
+
+    // Within the default limits, the query matches
+    let response = graph_query_service(default_limits.clone())
+        .await?
+        .query_graph_with_uid(request.clone())
+        .await?;
+    assert!(matches!(
+        response.maybe_match,
+        MaybeMatchWithUid::Matched(_)
+    ));
+
+    // With too few reads it fails outright, rather than matching the root alone
+    let result = graph_query_service(QueryLimitsConfig {
+        graph_query_read_budget: 1,
+        ..default_limits.clone()
+    })
+    .await?
+    .query_graph_with_uid(request.clone())
+    .await;
+    match result {
+        Ok(response) => panic!("expected the read budget to be exceeded, got {response:?}"),
+        Err(e) => {
+            assert!(e.to_string().contains("budget of 1 reads"), "{e}");
+            assert_eq!(Status::from(e).code(), Code::FailedPrecondition);
+        }
+    }
+
+    // And likewise with too little time
+    let result = graph_query_service(QueryLimitsConfig {
+        graph_query_deadline_ms: 0,
+        ..default_limits
+    })
+    .await?
+    .query_graph_with_uid(request)
+    .await;
+    match result {
+        Ok(response) => panic!("expected the deadline to be exceeded, got {response:?}"),
+        Err(e) => {
+            assert!(matches!(e, GraphQueryServiceError::DeadlineExceeded { .. }));
+            assert_eq!(Status::from(e).code(), Code::FailedPrecondition);
+        }
+    }
+
+    Ok(())
+}
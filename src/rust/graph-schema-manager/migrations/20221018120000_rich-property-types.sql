@@ -0,0 +1,7 @@
+ALTER TYPE graph_schema_manager.property_type ADD VALUE IF NOT EXISTS 'ImmutableBool';
+ALTER TYPE graph_schema_manager.property_type ADD VALUE IF NOT EXISTS 'ImmutableF64';
+ALTER TYPE graph_schema_manager.property_type ADD VALUE IF NOT EXISTS 'ImmutableBytes';
+ALTER TYPE graph_schema_manager.property_type ADD VALUE IF NOT EXISTS 'ImmutableTimestamp';
+ALTER TYPE graph_schema_manager.property_type ADD VALUE IF NOT EXISTS 'MaxTimestamp';
+ALTER TYPE graph_schema_manager.property_type ADD VALUE IF NOT EXISTS 'MinTimestamp';
+ALTER TYPE graph_schema_manager.property_type ADD VALUE IF NOT EXISTS 'StringSet';
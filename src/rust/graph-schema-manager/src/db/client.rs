@@ -13,6 +13,10 @@ use crate::{
     config::SchemaDbConfig,
     db::models::{
         GetEdgeSchemaRequestRow,
+        GetNodeSchemaRow,
+        GetPropertySchemaRow,
+        GetSessionIdentityRow,
+        GetStaticIdentityRow,
         StoredEdgeCardinality,
     },
 };
@@ -73,6 +77,110 @@ impl SchemaDbClient {
         .await
     }
 
+    /// Returns the most recently deployed version of a node type, and the
+    /// identity algorithm it was deployed with.
+    pub async fn get_node_schema(
+        &self,
+        tenant_id: uuid::Uuid,
+        node_type: &NodeType,
+    ) -> Result<GetNodeSchemaRow, sqlx::Error> {
+        sqlx::query_as!(
+            GetNodeSchemaRow,
+            r#"select
+                schema_version,
+                identity_algorithm
+             FROM graph_schema_manager.node_schemas
+             WHERE
+                 tenant_id = $1 AND
+                 node_type = $2
+             ORDER BY schema_version DESC
+             LIMIT 1;
+                 "#,
+            tenant_id,
+            node_type.value,
+        )
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    pub async fn get_static_identity_args(
+        &self,
+        tenant_id: uuid::Uuid,
+        node_type: &NodeType,
+        schema_version: i16,
+    ) -> Result<GetStaticIdentityRow, sqlx::Error> {
+        sqlx::query_as!(
+            GetStaticIdentityRow,
+            r#"select
+                static_key_properties
+             FROM graph_schema_manager.static_identity_arguments
+             WHERE
+                 tenant_id = $1 AND
+                 node_type = $2 AND
+                 schema_version = $3;
+                 "#,
+            tenant_id,
+            node_type.value,
+            schema_version,
+        )
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    pub async fn get_session_identity_args(
+        &self,
+        tenant_id: uuid::Uuid,
+        node_type: &NodeType,
+        schema_version: i16,
+    ) -> Result<GetSessionIdentityRow, sqlx::Error> {
+        sqlx::query_as!(
+            GetSessionIdentityRow,
+            r#"select
+                pseudo_key_properties,
+                creation_timestamp_property,
+                last_seen_timestamp_property,
+                termination_timestamp_property
+             FROM graph_schema_manager.session_identity_arguments
+             WHERE
+                 tenant_id = $1 AND
+                 node_type = $2 AND
+                 schema_version = $3;
+                 "#,
+            tenant_id,
+            node_type.value,
+            schema_version,
+        )
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    pub async fn get_property_schemas(
+        &self,
+        tenant_id: uuid::Uuid,
+        node_type: &NodeType,
+        schema_version: i16,
+    ) -> Result<Vec<GetPropertySchemaRow>, sqlx::Error> {
+        sqlx::query_as!(
+            GetPropertySchemaRow,
+            r#"select
+                property_name,
+                property_type as "property_type: StoredPropertyType",
+                identity_only
+             FROM graph_schema_manager.property_schemas
+             WHERE
+                 tenant_id = $1 AND
+                 node_type = $2 AND
+                 schema_version = $3
+             ORDER BY property_name;
+                 "#,
+            tenant_id,
+            node_type.value,
+            schema_version,
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
     pub async fn insert_node_identity_algorithm(
         &self,
         txn: &mut Txn<'_>,
@@ -1,4 +1,7 @@
-use rust_proto::graplinc::grapl::api::graph_schema_manager::v1beta1::messages::EdgeCardinality;
+use rust_proto::graplinc::grapl::api::graph_schema_manager::v1beta1::messages::{
+    EdgeCardinality,
+    PropertyType,
+};
 
 #[derive(sqlx::Type, Copy, Clone, Debug)]
 #[sqlx(type_name = "edge_cardinality")]
@@ -33,6 +36,60 @@ pub enum StoredPropertyType {
     ImmutableU64,
     MaxU64,
     MinU64,
+    ImmutableBool,
+    ImmutableF64,
+    ImmutableBytes,
+    ImmutableTimestamp,
+    MaxTimestamp,
+    MinTimestamp,
+    StringSet,
+}
+
+impl From<StoredPropertyType> for PropertyType {
+    fn from(t: StoredPropertyType) -> Self {
+        match t {
+            StoredPropertyType::ImmutableString => PropertyType::ImmutableString,
+            StoredPropertyType::ImmutableI64 => PropertyType::ImmutableI64,
+            StoredPropertyType::MaxI64 => PropertyType::MaxI64,
+            StoredPropertyType::MinI64 => PropertyType::MinI64,
+            StoredPropertyType::ImmutableU64 => PropertyType::ImmutableU64,
+            StoredPropertyType::MaxU64 => PropertyType::MaxU64,
+            StoredPropertyType::MinU64 => PropertyType::MinU64,
+            StoredPropertyType::ImmutableBool => PropertyType::ImmutableBool,
+            StoredPropertyType::ImmutableF64 => PropertyType::ImmutableF64,
+            StoredPropertyType::ImmutableBytes => PropertyType::ImmutableBytes,
+            StoredPropertyType::ImmutableTimestamp => PropertyType::ImmutableTimestamp,
+            StoredPropertyType::MaxTimestamp => PropertyType::MaxTimestamp,
+            StoredPropertyType::MinTimestamp => PropertyType::MinTimestamp,
+            StoredPropertyType::StringSet => PropertyType::StringSet,
+        }
+    }
+}
+
+#[derive(sqlx::Type, Clone, Debug)]
+pub struct GetNodeSchemaRow {
+    pub schema_version: i16,
+    pub identity_algorithm: String,
+}
+
+#[derive(sqlx::Type, Clone, Debug)]
+pub struct GetStaticIdentityRow {
+    pub static_key_properties: Vec<String>,
+}
+
+#[derive(sqlx::Type, Clone, Debug)]
+pub struct GetSessionIdentityRow {
+    pub pseudo_key_properties: Vec<String>,
+    pub creation_timestamp_property: String,
+    pub last_seen_timestamp_property: String,
+    pub termination_timestamp_property: String,
+}
+
+#[derive(sqlx::Type, Clone, Debug)]
+pub struct GetPropertySchemaRow {
+    pub property_name: String,
+    pub property_type: StoredPropertyType,
+    pub identity_only: bool,
 }
 
 #[derive(sqlx::Type, Clone, Debug)]
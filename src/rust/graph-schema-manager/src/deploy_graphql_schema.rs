@@ -358,6 +358,20 @@ fn get_predicate_type_name(
         (PredicateType::U64, ConflictResolution::Immutable) => StoredPropertyType::ImmutableU64,
         (PredicateType::U64, ConflictResolution::IncrementOnly) => StoredPropertyType::MaxU64,
         (PredicateType::U64, ConflictResolution::DecrementOnly) => StoredPropertyType::MinU64,
+        (PredicateType::Bool, ConflictResolution::Immutable) => StoredPropertyType::ImmutableBool,
+        (PredicateType::F64, ConflictResolution::Immutable) => StoredPropertyType::ImmutableF64,
+        (PredicateType::Bytes, ConflictResolution::Immutable) => StoredPropertyType::ImmutableBytes,
+        (PredicateType::Timestamp, ConflictResolution::Immutable) => {
+            StoredPropertyType::ImmutableTimestamp
+        }
+        (PredicateType::Timestamp, ConflictResolution::IncrementOnly) => {
+            StoredPropertyType::MaxTimestamp
+        }
+        (PredicateType::Timestamp, ConflictResolution::DecrementOnly) => {
+            StoredPropertyType::MinTimestamp
+        }
+        // String sets only ever grow, whatever the schema says
+        (PredicateType::StringSet, _) => StoredPropertyType::StringSet,
         (PredicateType::String, ConflictResolution::IncrementOnly) => {
             return Err(DeployGraphqlError::InvalidSchema(
                 "String can only be ImmutableString. Got IncrementOnly",
@@ -368,6 +382,11 @@ fn get_predicate_type_name(
                 "String can only be ImmutableString. Got DecrementOnly",
             ));
         }
+        (PredicateType::Bool | PredicateType::F64 | PredicateType::Bytes, _) => {
+            return Err(DeployGraphqlError::InvalidSchema(
+                "Boolean, Float and Bytes can only be Immutable",
+            ));
+        }
     };
 
     Ok(type_name)
@@ -7,13 +7,22 @@ use rust_proto::{
                     DeploySchemaResponse,
                     GetEdgeSchemaRequest,
                     GetEdgeSchemaResponse,
+                    GetNodeSchemaRequest,
+                    GetNodeSchemaResponse,
+                    NodeIdentity,
+                    PropertySchema,
                     SchemaType,
+                    SessionIdentity,
+                    StaticIdentity,
                 },
                 server::GraphSchemaManagerApi,
             },
             protocol::status::Status,
         },
-        common::v1beta1::types::EdgeName,
+        common::v1beta1::types::{
+            EdgeName,
+            PropertyName,
+        },
     },
     SerDeError,
 };
@@ -42,6 +51,17 @@ pub enum GraphSchemaManagerServiceError {
         node_type: String,
         edge_name: String,
     },
+    #[error("GetNodeSchema sqlx error: '{0}'")]
+    GetNodeSchemaSqlxError(sqlx::Error),
+    #[error("GetNodeSchema: Node type not found for tenant_id={tenant_id}, node_type={node_type}")]
+    NodeSchemaNotFound {
+        tenant_id: uuid::Uuid,
+        node_type: String,
+    },
+    #[error("GetNodeSchema: Unknown identity algorithm '{0}'")]
+    UnknownIdentityAlgorithm(String),
+    #[error("Invalid PropertyName: '{0}'")]
+    InvalidPropertyName(SerDeError),
 }
 
 impl From<GraphSchemaManagerServiceError> for Status {
@@ -55,9 +75,13 @@ impl From<GraphSchemaManagerServiceError> for Status {
                 _,
             )) => Status::internal(msg),
             GraphSchemaManagerServiceError::DeployGraphqlError(_) => Status::invalid_argument(msg),
-            GraphSchemaManagerServiceError::EdgeSchemaNotFound { .. } => Status::internal(msg),
+            GraphSchemaManagerServiceError::EdgeSchemaNotFound { .. } => Status::not_found(msg),
             GraphSchemaManagerServiceError::GetEdgeSchemaSqlxError(_) => Status::internal(msg),
             GraphSchemaManagerServiceError::InvalidReverseEdgeName(_) => Status::internal(msg),
+            GraphSchemaManagerServiceError::NodeSchemaNotFound { .. } => Status::not_found(msg),
+            GraphSchemaManagerServiceError::GetNodeSchemaSqlxError(_) => Status::internal(msg),
+            GraphSchemaManagerServiceError::UnknownIdentityAlgorithm(_) => Status::internal(msg),
+            GraphSchemaManagerServiceError::InvalidPropertyName(_) => Status::internal(msg),
         }
     }
 }
@@ -123,4 +147,88 @@ impl GraphSchemaManagerApi for GraphSchemaManager {
             reverse_cardinality: response.reverse_edge_cardinality.into(),
         })
     }
+
+    #[tracing::instrument(skip(self), err)]
+    async fn get_node_schema(
+        &self,
+        request: GetNodeSchemaRequest,
+    ) -> Result<GetNodeSchemaResponse, Self::Error> {
+        let GetNodeSchemaRequest {
+            tenant_id,
+            node_type,
+        } = request;
+
+        let node_schema = self
+            .db_client
+            .get_node_schema(tenant_id, &node_type)
+            .await
+            .map_err(|e| match e {
+                sqlx::Error::RowNotFound => Self::Error::NodeSchemaNotFound {
+                    tenant_id,
+                    node_type: node_type.value.clone(),
+                },
+                _ => Self::Error::GetNodeSchemaSqlxError(e),
+            })?;
+        let schema_version = node_schema.schema_version;
+
+        let identity = match node_schema.identity_algorithm.as_str() {
+            "static" => {
+                let args = self
+                    .db_client
+                    .get_static_identity_args(tenant_id, &node_type, schema_version)
+                    .await
+                    .map_err(Self::Error::GetNodeSchemaSqlxError)?;
+
+                NodeIdentity::Static(StaticIdentity {
+                    static_key_properties: property_names(args.static_key_properties)?,
+                })
+            }
+            "session" => {
+                let args = self
+                    .db_client
+                    .get_session_identity_args(tenant_id, &node_type, schema_version)
+                    .await
+                    .map_err(Self::Error::GetNodeSchemaSqlxError)?;
+
+                NodeIdentity::Session(SessionIdentity {
+                    pseudo_key_properties: property_names(args.pseudo_key_properties)?,
+                    creation_timestamp_property: property_name(args.creation_timestamp_property)?,
+                    last_seen_timestamp_property: property_name(args.last_seen_timestamp_property)?,
+                    termination_timestamp_property: property_name(
+                        args.termination_timestamp_property,
+                    )?,
+                })
+            }
+            other => return Err(Self::Error::UnknownIdentityAlgorithm(other.to_owned())),
+        };
+
+        let properties = self
+            .db_client
+            .get_property_schemas(tenant_id, &node_type, schema_version)
+            .await
+            .map_err(Self::Error::GetNodeSchemaSqlxError)?
+            .into_iter()
+            .map(|row| {
+                Ok(PropertySchema {
+                    property_name: property_name(row.property_name)?,
+                    property_type: row.property_type.into(),
+                    identity_only: row.identity_only,
+                })
+            })
+            .collect::<Result<Vec<_>, Self::Error>>()?;
+
+        Ok(GetNodeSchemaResponse {
+            schema_version: schema_version as u32,
+            identity,
+            properties,
+        })
+    }
+}
+
+fn property_name(name: String) -> Result<PropertyName, GraphSchemaManagerServiceError> {
+    PropertyName::try_from(name).map_err(GraphSchemaManagerServiceError::InvalidPropertyName)
+}
+
+fn property_names(names: Vec<String>) -> Result<Vec<PropertyName>, GraphSchemaManagerServiceError> {
+    names.into_iter().map(property_name).collect()
 }
@@ -6,11 +6,15 @@ use figment::{
 };
 use rust_proto::graplinc::grapl::{
     api::{
-        client::Connect,
+        client::{
+            ClientError,
+            Connect,
+        },
         graph_schema_manager::v1beta1::{
             client::GraphSchemaManagerClient,
             messages as sm_api,
         },
+        protocol::status::Code,
     },
     common::v1beta1::types as common_api,
 };
@@ -54,3 +58,144 @@ async fn test_deploy_schema() -> eyre::Result<()> {
     assert_eq!(edge_schema.reverse_edge_name.value, "executed_as_processes");
     Ok(())
 }
+
+#[tokio::test]
+async fn test_get_edge_schema_not_found() -> eyre::Result<()> {
+    let client_config = Figment::new()
+        .merge(Env::prefixed("GRAPH_SCHEMA_MANAGER_CLIENT_"))
+        .extract()?;
+    let mut client = GraphSchemaManagerClient::connect(client_config).await?;
+
+    let tenant_id = uuid::Uuid::new_v4();
+
+    client
+        .deploy_schema(sm_api::DeploySchemaRequest {
+            tenant_id,
+            schema: get_example_graphql_schema()?,
+            schema_type: sm_api::SchemaType::GraphqlV0,
+            schema_version: 0,
+        })
+        .await?;
+
+    let missing = client
+        .get_edge_schema(sm_api::GetEdgeSchemaRequest {
+            tenant_id,
+            node_type: common_api::NodeType {
+                value: "Process".to_string(),
+            },
+            edge_name: common_api::EdgeName {
+                value: "not_deployed".to_string(),
+            },
+        })
+        .await;
+
+    match missing {
+        Err(ClientError::Status(status)) => assert_eq!(status.code(), Code::NotFound),
+        other => panic!("expected a NotFound status, got {other:?}"),
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_node_schema() -> eyre::Result<()> {
+    let client_config = Figment::new()
+        .merge(Env::prefixed("GRAPH_SCHEMA_MANAGER_CLIENT_"))
+        .extract()?;
+    let mut client = GraphSchemaManagerClient::connect(client_config).await?;
+
+    let tenant_id = uuid::Uuid::new_v4();
+
+    client
+        .deploy_schema(sm_api::DeploySchemaRequest {
+            tenant_id,
+            schema: get_example_graphql_schema()?,
+            schema_type: sm_api::SchemaType::GraphqlV0,
+            schema_version: 0,
+        })
+        .await?;
+
+    let process_schema = client
+        .get_node_schema(sm_api::GetNodeSchemaRequest {
+            tenant_id,
+            node_type: common_api::NodeType {
+                value: "Process".to_string(),
+            },
+        })
+        .await?;
+
+    assert_eq!(process_schema.schema_version, 0);
+    match process_schema.identity {
+        sm_api::NodeIdentity::Session(identity) => {
+            assert_eq!(
+                identity.pseudo_key_properties,
+                vec![common_api::PropertyName {
+                    value: "process_id".to_string()
+                }]
+            );
+            assert_eq!(identity.creation_timestamp_property.value, "created_at");
+            assert_eq!(identity.last_seen_timestamp_property.value, "last_seen_at");
+            assert_eq!(
+                identity.termination_timestamp_property.value,
+                "terminated_at"
+            );
+        }
+        other => panic!("expected session identity, got {other:?}"),
+    }
+
+    let process_name = process_schema
+        .properties
+        .iter()
+        .find(|property| property.property_name.value == "process_name")
+        .expect("process_name missing");
+    assert_eq!(
+        process_name.property_type,
+        sm_api::PropertyType::ImmutableString
+    );
+
+    let command_line_args = process_schema
+        .properties
+        .iter()
+        .find(|property| property.property_name.value == "command_line_args")
+        .expect("command_line_args missing");
+    assert_eq!(
+        command_line_args.property_type,
+        sm_api::PropertyType::StringSet
+    );
+
+    let asset_schema = client
+        .get_node_schema(sm_api::GetNodeSchemaRequest {
+            tenant_id,
+            node_type: common_api::NodeType {
+                value: "Asset".to_string(),
+            },
+        })
+        .await?;
+
+    assert_eq!(
+        asset_schema.identity,
+        sm_api::NodeIdentity::Static(sm_api::StaticIdentity {
+            static_key_properties: vec![common_api::PropertyName {
+                value: "asset_id".to_string()
+            }]
+        })
+    );
+
+    let last_seen_time = asset_schema
+        .properties
+        .iter()
+        .find(|property| property.property_name.value == "last_seen_time")
+        .expect("last_seen_time missing");
+    assert_eq!(last_seen_time.property_type, sm_api::PropertyType::MaxU64);
+
+    let missing = client
+        .get_node_schema(sm_api::GetNodeSchemaRequest {
+            tenant_id,
+            node_type: common_api::NodeType {
+                value: "NotDeployed".to_string(),
+            },
+        })
+        .await;
+    assert!(missing.is_err());
+
+    Ok(())
+}
@@ -12,6 +12,10 @@ pub const DECREMENT_ONLY: &'static str = "decrement_only";
 pub const STRING: &'static str = "String";
 pub const INT: &'static str = "Int";
 pub const UINT: &'static str = "UInt";
+pub const BOOLEAN: &'static str = "Boolean";
+pub const FLOAT: &'static str = "Float";
+pub const BYTES: &'static str = "Bytes";
+pub const TIMESTAMP: &'static str = "Timestamp";
 
 pub const SESSION_ALGORITHM: &'static str = "session";
 pub const STATIC_ALGORITHM: &'static str = "static";
@@ -1,6 +1,9 @@
 use graphql_parser::schema::Directive;
 
-use crate::node_type::MergeFailure;
+use crate::{
+    node_type::MergeFailure,
+    predicate_type::PredicateType,
+};
 
 #[derive(Debug, thiserror::Error)]
 pub enum CodeGenError {
@@ -24,4 +27,10 @@ pub enum CodeGenError {
     },
     #[error("Failed to extend node schema")]
     MergeFailure(#[from] MergeFailure),
+    #[error("Unsupported type: {0}")]
+    UnsupportedType(String),
+    #[error("Unsupported set type: {0:?}")]
+    UnsupportedSetType(PredicateType),
+    #[error("grapl_analyzerlib has no Python type for {0:?}")]
+    UnsupportedPythonType(PredicateType),
 }
@@ -111,7 +111,7 @@ Please read the documentation on
     let mut all_code = String::with_capacity(1024 * node_types.len());
     all_code.push_str(&standin_imports());
     for node_type in node_types {
-        let pycode = node_type.generate_python_code();
+        let pycode = node_type.generate_python_code()?;
         all_code.push_str(&pycode);
     }
 
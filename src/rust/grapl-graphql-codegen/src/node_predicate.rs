@@ -72,29 +72,34 @@ impl NodePredicate {
         comparisons
     }
 
-    pub fn generate_python_query_comparisons(&self) -> String {
+    pub fn generate_python_query_comparisons(&self) -> Result<String, CodeGenError> {
         match self.predicate_type {
-            PredicateType::String => self.generate_python_str_comparisons(),
-            PredicateType::I64 => self.generate_python_int_comparisons(),
-            PredicateType::U64 => self.generate_python_int_comparisons(),
+            PredicateType::String => Ok(self.generate_python_str_comparisons()),
+            PredicateType::StringSet => Ok(self.generate_python_str_comparisons()),
+            PredicateType::I64 => Ok(self.generate_python_int_comparisons()),
+            PredicateType::U64 => Ok(self.generate_python_int_comparisons()),
+            PredicateType::Timestamp => Ok(self.generate_python_int_comparisons()),
+            PredicateType::Bool | PredicateType::F64 | PredicateType::Bytes => {
+                Err(CodeGenError::UnsupportedPythonType(self.predicate_type))
+            }
         }
     }
 
-    pub fn generate_python_query_def(&self) -> String {
+    pub fn generate_python_query_def(&self) -> Result<String, CodeGenError> {
         let mut query_def = String::with_capacity(256);
-        let python_ty = self.predicate_type.into_python_primitive_type();
+        let python_ty = self.predicate_type.into_python_primitive_type()?;
 
         query_def = query_def + &format!("    def with_{}(", self.predicate_name) + "\n";
         query_def = query_def + "        self," + "\n";
         query_def = query_def + "        *," + "\n";
-        query_def = query_def + &self.generate_python_query_comparisons() + "\n";
+        query_def = query_def + &self.generate_python_query_comparisons()? + "\n";
         query_def = query_def + "    ):" + "\n";
         query_def += "        (\n";
         query_def = query_def + "            self.with_" + &python_ty + "_property(\n";
         query_def = query_def + "                \"" + &self.predicate_name + "\",\n";
 
         match self.predicate_type {
-            PredicateType::String => {
+            PredicateType::String | PredicateType::StringSet => {
                 query_def += "                eq=eq,\n";
                 query_def += "                contains=contains,\n";
                 query_def += "                starts_with=starts_with,\n";
@@ -102,25 +107,28 @@ impl NodePredicate {
                 query_def += "                regexp=regexp,\n";
                 query_def += "                distance_lt=distance_lt\n";
             }
-            PredicateType::U64 | PredicateType::I64 => {
+            PredicateType::U64 | PredicateType::I64 | PredicateType::Timestamp => {
                 query_def += "                eq=eq,\n";
                 query_def += "                gt=gt,\n";
                 query_def += "                ge=ge,\n";
                 query_def += "                lt=lt,\n";
                 query_def += "                le=le,\n";
             }
+            PredicateType::Bool | PredicateType::F64 | PredicateType::Bytes => {
+                return Err(CodeGenError::UnsupportedPythonType(self.predicate_type))
+            }
         }
         query_def += "            )\n";
         query_def += "        )\n";
         query_def = query_def + "        return self" + "\n";
-        query_def
+        Ok(query_def)
     }
 
-    pub fn generate_viewable_get_predicate_method(&self) -> String {
+    pub fn generate_viewable_get_predicate_method(&self) -> Result<String, CodeGenError> {
         let mut get_method = String::with_capacity(512);
 
         let predicate_name = self.predicate_name.as_str();
-        let py_ty = self.predicate_type.into_python_primitive_type();
+        let py_ty = self.predicate_type.into_python_primitive_type()?;
         let cached = self
             .conflict_resolution
             .implies_cacheable()
@@ -138,7 +146,7 @@ impl NodePredicate {
             )
             + "\n\n";
 
-        get_method
+        Ok(get_method)
     }
 }
 
@@ -172,7 +180,7 @@ pub fn is_nullable(field_type: &Type<'static, String>) -> bool {
     match field_type {
         Type::NonNullType(_) => false,
         Type::NamedType(_) => true,
-        Type::ListType(_) => panic!("ListType not supported"),
+        Type::ListType(_) => true,
     }
 }
 
@@ -192,7 +200,9 @@ mod tests {
             nullable: true,
         };
         assert_eq!(
-            node_predicate.generate_viewable_get_predicate_method(),
+            node_predicate
+                .generate_viewable_get_predicate_method()
+                .unwrap(),
             expected_str
         );
     }
@@ -21,13 +21,15 @@ use crate::{
 };
 
 // Python Queryable generation
-pub fn generate_parameter_from_predicate(predicate: &NodePredicate) -> String {
+pub fn generate_parameter_from_predicate(
+    predicate: &NodePredicate,
+) -> Result<String, CodeGenError> {
     let parameter_name = predicate.predicate_name.as_str();
-    let parameter_ty = predicate.predicate_type.into_python_primitive_type();
-    format!(
+    let parameter_ty = predicate.predicate_type.into_python_primitive_type()?;
+    Ok(format!(
         r#"{}: Optional["{}"] = None,"#,
         parameter_name, parameter_ty
-    )
+    ))
 }
 
 pub fn generate_parameter_from_edge(edge: &Edge) -> String {
@@ -119,19 +121,19 @@ impl NodeType {
         Ok(())
     }
 
-    pub fn generate_python_code(&self) -> String {
+    pub fn generate_python_code(&self) -> Result<String, CodeGenError> {
         let mut pycode = String::with_capacity(256);
 
-        pycode += &self.generate_python_schema();
+        pycode += &self.generate_python_schema()?;
         pycode += "\n";
 
-        pycode += &self.generate_python_queryable();
+        pycode += &self.generate_python_queryable()?;
         pycode += "\n";
 
-        pycode += &self.generate_python_viewable();
+        pycode += &self.generate_python_viewable()?;
         pycode += "\n";
 
-        pycode
+        Ok(pycode)
     }
 
     fn get_query_name(&self) -> String {
@@ -156,7 +158,7 @@ impl NodeType {
     }
 
     #[tracing::instrument(skip(self))]
-    pub fn generate_python_queryable(&self) -> String {
+    pub fn generate_python_queryable(&self) -> Result<String, CodeGenError> {
         let mut queryable = String::with_capacity(256);
 
         let query_name = format!("{}Query", self.type_name);
@@ -179,7 +181,7 @@ impl NodeType {
             );
 
         for predicate in self.predicates.iter() {
-            queryable.push_str(&predicate.generate_python_query_def());
+            queryable.push_str(&predicate.generate_python_query_def()?);
             queryable.push('\n');
         }
 
@@ -190,11 +192,11 @@ impl NodeType {
 
         queryable += &self.generate_queryable_node_schema_method();
 
-        queryable
+        Ok(queryable)
     }
 
     #[tracing::instrument(skip(self))]
-    pub fn generate_python_viewable(&self) -> String {
+    pub fn generate_python_viewable(&self) -> Result<String, CodeGenError> {
         let mut viewable = String::with_capacity(512);
 
         let (q, v) = (self.get_query_name(), self.get_view_name());
@@ -223,7 +225,7 @@ impl NodeType {
         viewable += "        node_types: Set[str],\n";
 
         for predicate in self.predicates.iter() {
-            let parameter = generate_parameter_from_predicate(predicate);
+            let parameter = generate_parameter_from_predicate(predicate)?;
             viewable = viewable + "        " + &parameter + "\n";
         }
 
@@ -246,15 +248,15 @@ impl NodeType {
             viewable = viewable + "        " + &predicate + "\n";
         }
         viewable.push('\n');
-        viewable += &self.generate_viewable_get_methods();
+        viewable += &self.generate_viewable_get_methods()?;
 
         viewable.push('\n');
         viewable += &self.generate_queryable_node_schema_method();
 
-        viewable
+        Ok(viewable)
     }
 
-    pub fn generate_viewable_get_methods(&self) -> String {
+    pub fn generate_viewable_get_methods(&self) -> Result<String, CodeGenError> {
         let mut get_methods = String::with_capacity(512);
 
         for predicate in self.predicates.iter() {
@@ -267,7 +269,7 @@ impl NodeType {
                 view_name=?self.get_view_name(),
             );
 
-            let get_method = predicate.generate_viewable_get_predicate_method();
+            let get_method = predicate.generate_viewable_get_predicate_method()?;
             get_methods += &get_method;
         }
 
@@ -288,18 +290,18 @@ impl NodeType {
             get_methods += &get_method;
         }
 
-        get_methods
+        Ok(get_methods)
     }
 
     // Python Schema generation
-    pub fn generate_python_schema(&self) -> String {
+    pub fn generate_python_schema(&self) -> Result<String, CodeGenError> {
         let mut schema_str = String::with_capacity(256);
         tracing::trace!(
             message="Generating Python Schema",
             node_type=?self.type_name,
         );
 
-        schema_str += &self.generate_python_default_schema_properties();
+        schema_str += &self.generate_python_default_schema_properties()?;
         schema_str += "\n";
         schema_str += &self.generate_python_default_schema_edges();
         schema_str += "\n";
@@ -308,7 +310,7 @@ impl NodeType {
         schema_str += &self.generate_python_schema_self_type();
         schema_str += "\n";
 
-        schema_str
+        Ok(schema_str)
     }
 
     pub fn generate_python_schema_self_type(&self) -> String {
@@ -346,7 +348,7 @@ impl NodeType {
         schema_str
     }
 
-    pub fn generate_python_default_schema_properties(&self) -> String {
+    pub fn generate_python_default_schema_properties(&self) -> Result<String, CodeGenError> {
         let mut def = String::with_capacity(256);
 
         let lower_node_name = self.type_name.to_lowercase();
@@ -359,12 +361,12 @@ impl NodeType {
         def = def + r#"    return {"# + "\n";
         for predicate in self.predicates.iter() {
             let predicate_name = format!(r#""{}""#, &predicate.predicate_name);
-            let prop_primitive_t = predicate.predicate_type.into_python_prop_primitive();
+            let prop_primitive_t = predicate.predicate_type.into_python_prop_primitive()?;
             def = def + &format!("        {}: {},\n", predicate_name, prop_primitive_t);
         }
 
         def += r#"    }"#;
-        def
+        Ok(def)
     }
 
     pub fn generate_python_default_schema_edges(&self) -> String {
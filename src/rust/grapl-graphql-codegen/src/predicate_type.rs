@@ -7,8 +7,12 @@ use graphql_parser::schema::Type;
 
 use crate::{
     constants::{
+        BOOLEAN,
+        BYTES,
+        FLOAT,
         INT,
         STRING,
+        TIMESTAMP,
         UINT,
     },
     errors::CodeGenError,
@@ -20,34 +24,60 @@ pub enum PredicateType {
     String,
     I64,
     U64,
+    Bool,
+    F64,
+    Bytes,
+    /// Milliseconds since the unix epoch
+    Timestamp,
+    /// A `[String]`, whose values are merged by union
+    StringSet,
 }
 
 // Python code generation
+//
+// grapl_analyzerlib only has Python types for strings and integers, so the
+// rest of the types can't be generated for.
 impl PredicateType {
-    pub fn into_python_prop_primitive(self) -> String {
-        match self {
+    pub fn into_python_prop_primitive(self) -> Result<String, CodeGenError> {
+        let prop_primitive = match self {
             PredicateType::String => "grapl_analyzerlib.node_types.PropType(grapl_analyzerlib.node_types.PropPrimitive.Str, False)",
             PredicateType::I64 => "grapl_analyzerlib.node_types.PropType(grapl_analyzerlib.node_types.PropPrimitive.Int, False)",
             PredicateType::U64 => "grapl_analyzerlib.node_types.PropType(grapl_analyzerlib.node_types.PropPrimitive.Int, False)",
-        }.to_string()
+            PredicateType::Timestamp => "grapl_analyzerlib.node_types.PropType(grapl_analyzerlib.node_types.PropPrimitive.Int, False)",
+            PredicateType::StringSet => "grapl_analyzerlib.node_types.PropType(grapl_analyzerlib.node_types.PropPrimitive.Str, True)",
+            PredicateType::Bool | PredicateType::F64 | PredicateType::Bytes => {
+                return Err(CodeGenError::UnsupportedPythonType(self))
+            }
+        };
+        Ok(prop_primitive.to_string())
     }
 
-    pub fn into_python_primitive_type(self) -> String {
-        match self {
+    pub fn into_python_primitive_type(self) -> Result<String, CodeGenError> {
+        let primitive_type = match self {
             PredicateType::String => "str",
             PredicateType::I64 => "int",
             PredicateType::U64 => "int",
-        }
-        .to_string()
+            PredicateType::Timestamp => "int",
+            PredicateType::StringSet => "str",
+            PredicateType::Bool | PredicateType::F64 | PredicateType::Bytes => {
+                return Err(CodeGenError::UnsupportedPythonType(self))
+            }
+        };
+        Ok(primitive_type.to_string())
     }
 
-    pub fn into_python_primitive_type_or_not(self) -> String {
-        match self {
+    pub fn into_python_primitive_type_or_not(self) -> Result<String, CodeGenError> {
+        let primitive_type_or_not = match self {
             PredicateType::String => "StrOrNot",
             PredicateType::I64 => "IntOrNot",
             PredicateType::U64 => "IntOrNot",
-        }
-        .to_string()
+            PredicateType::Timestamp => "IntOrNot",
+            PredicateType::StringSet => "StrOrNot",
+            PredicateType::Bool | PredicateType::F64 | PredicateType::Bytes => {
+                return Err(CodeGenError::UnsupportedPythonType(self))
+            }
+        };
+        Ok(primitive_type_or_not.to_string())
     }
 }
 
@@ -57,17 +87,21 @@ impl TryFrom<&Type<'static, String>> for PredicateType {
     #[tracing::instrument]
     fn try_from(value: &Type<'static, String>) -> Result<Self, Self::Error> {
         match value {
-            Type::NamedType(value) => {
-                match value.as_str() {
-                    STRING => Ok(PredicateType::String),
-                    INT => Ok(PredicateType::I64),
-                    UINT => Ok(PredicateType::U64),
-                    // todo: error
-                    unsupported => panic!("Unsupported type: {}", unsupported),
-                }
-            }
+            Type::NamedType(value) => match value.as_str() {
+                STRING => Ok(PredicateType::String),
+                INT => Ok(PredicateType::I64),
+                UINT => Ok(PredicateType::U64),
+                BOOLEAN => Ok(PredicateType::Bool),
+                FLOAT => Ok(PredicateType::F64),
+                BYTES => Ok(PredicateType::Bytes),
+                TIMESTAMP => Ok(PredicateType::Timestamp),
+                unsupported => Err(CodeGenError::UnsupportedType(unsupported.to_owned())),
+            },
             Type::NonNullType(ref value) => value.as_ref().try_into(),
-            Type::ListType(_) => todo!("We don't currently support sets of values in Grapl"),
+            Type::ListType(ref value) => match PredicateType::try_from(value.as_ref())? {
+                PredicateType::String => Ok(PredicateType::StringSet),
+                unsupported => Err(CodeGenError::UnsupportedSetType(unsupported)),
+            },
         }
     }
 }
@@ -79,16 +113,67 @@ mod tests {
     #[test]
     fn test_predicate_type() {
         assert_eq!(
-            PredicateType::String.into_python_primitive_type_or_not(),
+            PredicateType::String
+                .into_python_primitive_type_or_not()
+                .unwrap(),
             "StrOrNot"
         );
         assert_eq!(
-            PredicateType::I64.into_python_primitive_type_or_not(),
+            PredicateType::I64
+                .into_python_primitive_type_or_not()
+                .unwrap(),
             "IntOrNot"
         );
         assert_eq!(
-            PredicateType::U64.into_python_primitive_type_or_not(),
+            PredicateType::U64
+                .into_python_primitive_type_or_not()
+                .unwrap(),
             "IntOrNot"
         );
+        assert!(matches!(
+            PredicateType::Bool.into_python_primitive_type_or_not(),
+            Err(CodeGenError::UnsupportedPythonType(PredicateType::Bool))
+        ));
+    }
+
+    fn parse_field_type(schema: &str) -> Result<PredicateType, CodeGenError> {
+        let document = graphql_parser::parse_schema::<String>(schema)
+            .expect("invalid schema")
+            .into_static();
+        let field_type = document
+            .definitions
+            .iter()
+            .find_map(|definition| match definition {
+                graphql_parser::schema::Definition::TypeDefinition(
+                    graphql_parser::schema::TypeDefinition::Object(object),
+                ) => object.fields.first().map(|field| field.field_type.clone()),
+                _ => None,
+            })
+            .expect("missing field");
+        PredicateType::try_from(&field_type)
+    }
+
+    #[test]
+    fn test_string_list_is_string_set() {
+        assert!(matches!(
+            parse_field_type("type Process { command_line_args: [String!]! }"),
+            Ok(PredicateType::StringSet)
+        ));
+        assert!(matches!(
+            parse_field_type("type Process { started_at: Timestamp! }"),
+            Ok(PredicateType::Timestamp)
+        ));
+    }
+
+    #[test]
+    fn test_unsupported_types_are_errors() {
+        assert!(matches!(
+            parse_field_type("type Process { exit_codes: [Int] }"),
+            Err(CodeGenError::UnsupportedSetType(PredicateType::I64))
+        ));
+        assert!(matches!(
+            parse_field_type("type Process { started_at: Date! }"),
+            Err(CodeGenError::UnsupportedType(name)) if name == "Date"
+        ));
     }
 }
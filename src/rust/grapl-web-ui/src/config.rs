@@ -12,6 +12,7 @@ use rust_proto::graplinc::grapl::api::{
         ClientError,
         Connect,
     },
+    graph_query::v1beta1::client::GraphQueryClient,
     pipeline_ingress::v1beta1::client::PipelineIngressClient,
     plugin_registry::v1beta1::PluginRegistryClient,
 };
@@ -42,6 +43,7 @@ pub struct Config {
     pub user_session_table_name: String,
     pub plugin_registry_client: PluginRegistryClient,
     pub pipeline_ingress_client: PipelineIngressClient,
+    pub graph_query_client: GraphQueryClient,
     pub google_client_id: String,
 }
 
@@ -64,6 +66,12 @@ impl Config {
         let pipeline_ingress_client =
             PipelineIngressClient::connect(pipeline_ingress_config).await?;
 
+        let graph_query_config: ClientConfiguration = Figment::new()
+            .merge(Env::prefixed("GRAPH_QUERY_CLIENT_"))
+            .extract()?;
+
+        let graph_query_client = GraphQueryClient::connect(graph_query_config).await?;
+
         let dynamodb_client = DynamoDbClient::from_env();
 
         // generate a random key for encrypting user state.
@@ -77,6 +85,7 @@ impl Config {
             user_session_table_name: builder.user_session_table_name,
             plugin_registry_client,
             pipeline_ingress_client,
+            graph_query_client,
             google_client_id: builder.google_client_id,
         };
 
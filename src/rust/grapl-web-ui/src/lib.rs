@@ -26,6 +26,7 @@ pub fn run(config: config::Config) -> Result<Server, std::io::Error> {
         ));
         let plugin_registry_client = Data::new(config.plugin_registry_client.clone());
         let pipeline_ingress_client = Data::new(config.pipeline_ingress_client.clone());
+        let graph_query_client = Data::new(config.graph_query_client.clone());
 
         App::new()
             .wrap(actix_web::middleware::Logger::default())
@@ -49,6 +50,7 @@ pub fn run(config: config::Config) -> Result<Server, std::io::Error> {
             .app_data(web_client)
             .app_data(plugin_registry_client)
             .app_data(pipeline_ingress_client)
+            .app_data(graph_query_client)
             .app_data(web_authenticator)
             .configure(routes::config)
     })
@@ -1,4 +1,5 @@
 mod auth;
+pub mod graph_query;
 mod health;
 pub mod ingress;
 pub mod plugin;
@@ -7,6 +8,7 @@ use actix_web::web;
 
 pub(super) fn config(cfg: &mut web::ServiceConfig) {
     cfg.service(web::scope("/auth").configure(auth::config));
+    cfg.service(web::scope("/graph_query").configure(graph_query::config));
     cfg.service(web::scope("/ingress").configure(ingress::config));
     cfg.service(web::scope("/plugin").configure(plugin::config));
     cfg.route("/health", web::get().to(health::health));
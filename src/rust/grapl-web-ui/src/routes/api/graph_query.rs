@@ -0,0 +1,9 @@
+mod error;
+pub mod query;
+
+use actix_web::web;
+pub use error::GraphQueryError;
+
+pub(super) fn config(cfg: &mut web::ServiceConfig) {
+    cfg.route("/query", web::post().to(query::query));
+}
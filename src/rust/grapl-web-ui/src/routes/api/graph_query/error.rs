@@ -0,0 +1,58 @@
+use graph_query_language::QueryLanguageError;
+use rust_proto::graplinc::grapl::api::{
+    client::ClientError,
+    protocol::status::Code,
+};
+
+#[derive(thiserror::Error, Debug)]
+#[non_exhaustive]
+pub enum GraphQueryError {
+    #[error(transparent)]
+    GrpcClient(#[from] ClientError),
+    #[error(transparent)]
+    QueryLanguage(#[from] QueryLanguageError),
+    #[error("{message}")]
+    BadRequest { message: String },
+    #[error("gRPC client timeout: {0}")]
+    RcpTimeout(#[from] tokio::time::error::Elapsed),
+}
+
+impl actix_web::error::ResponseError for GraphQueryError {
+    fn error_response(&self) -> actix_web::HttpResponse<actix_web::body::BoxBody> {
+        match self {
+            // The span lets the query editor point at where the error is
+            GraphQueryError::QueryLanguage(e) => {
+                actix_web::HttpResponse::BadRequest().json(serde_json::json!({
+                    "message": e.message,
+                    "span": {
+                        "start": e.span.start,
+                        "end": e.span.end,
+                    },
+                }))
+            }
+            GraphQueryError::BadRequest { message } => actix_web::HttpResponse::BadRequest()
+                .json(serde_json::json!({ "message": message })),
+            GraphQueryError::GrpcClient(ClientError::Status(status))
+                if status.code() == Code::InvalidArgument =>
+            {
+                actix_web::HttpResponse::BadRequest()
+                    .json(serde_json::json!({ "message": status.message() }))
+            }
+            _ => actix_web::HttpResponse::InternalServerError().finish(),
+        }
+    }
+
+    fn status_code(&self) -> actix_web::http::StatusCode {
+        match self {
+            GraphQueryError::QueryLanguage(_) | GraphQueryError::BadRequest { .. } => {
+                actix_web::http::StatusCode::BAD_REQUEST
+            }
+            GraphQueryError::GrpcClient(ClientError::Status(status))
+                if status.code() == Code::InvalidArgument =>
+            {
+                actix_web::http::StatusCode::BAD_REQUEST
+            }
+            _ => actix_web::http::StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use actix_web::{
+    web,
+    HttpResponse,
+};
+use graph_query_language::TextQuery;
+use grapl_utils::future_ext::GraplFutureExt;
+use rust_proto::graplinc::grapl::{
+    api::graph_query::v1beta1::{
+        client::GraphQueryClient,
+        messages::{
+            GraphView,
+            MaybeMatchWithUid,
+            QueryGraphWithTextRequest,
+        },
+    },
+    common::v1beta1::types::Uid,
+};
+
+use super::GraphQueryError;
+
+#[derive(serde::Deserialize)]
+pub(super) struct QueryParameters {
+    node_uid: u64,
+    query: String,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq, Eq)]
+#[serde(tag = "result", rename_all = "snake_case")]
+pub enum QueryResponse {
+    Matched {
+        root_uid: u64,
+        nodes: Vec<MatchedNode>,
+        edges: Vec<MatchedEdge>,
+    },
+    Missed,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq, Eq)]
+pub struct MatchedNode {
+    pub uid: u64,
+    pub node_type: String,
+    pub properties: std::collections::BTreeMap<String, String>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq, Eq)]
+pub struct MatchedEdge {
+    pub source_uid: u64,
+    pub edge_name: String,
+    pub destination_uid: u64,
+}
+
+impl QueryResponse {
+    fn matched(root_uid: Uid, graph: GraphView) -> Self {
+        let nodes = graph
+            .nodes
+            .into_values()
+            .map(|node| MatchedNode {
+                uid: node.uid.as_u64(),
+                node_type: node.node_type.value,
+                properties: node
+                    .string_properties
+                    .prop_map
+                    .into_iter()
+                    .map(|(name, value)| (name.value, value))
+                    .collect(),
+            })
+            .collect();
+        let edges = graph
+            .edges
+            .into_iter()
+            .flat_map(|((source_uid, edge_name), destination_uids)| {
+                destination_uids
+                    .into_iter()
+                    .map(move |destination_uid| MatchedEdge {
+                        source_uid: source_uid.as_u64(),
+                        edge_name: edge_name.value.clone(),
+                        destination_uid: destination_uid.as_u64(),
+                    })
+            })
+            .collect();
+
+        QueryResponse::Matched {
+            root_uid: root_uid.as_u64(),
+            nodes,
+            edges,
+        }
+    }
+}
+
+#[tracing::instrument(skip(graph_query_client, data))]
+pub(super) async fn query(
+    graph_query_client: web::Data<GraphQueryClient>,
+    user: crate::authn::AuthenticatedUser,
+    data: web::Json<QueryParameters>,
+) -> Result<impl actix_web::Responder, GraphQueryError> {
+    let QueryParameters { node_uid, query } = data.into_inner();
+
+    // graph-query parses the query too, but only this error carries a span
+    TextQuery::parse(&query)?;
+
+    let node_uid = Uid::from_u64(node_uid).ok_or_else(|| GraphQueryError::BadRequest {
+        message: "node_uid can't be 0".to_owned(),
+    })?;
+    let request = QueryGraphWithTextRequest {
+        tenant_id: user.get_organization_id().to_owned(),
+        node_uid,
+        query_text: query,
+        explain: false,
+    };
+
+    tracing::debug!(message = "querying graph", ?request);
+
+    let mut graph_query_client = graph_query_client.get_ref().clone();
+    let response = graph_query_client
+        .query_graph_with_text(request)
+        .timeout(std::time::Duration::from_secs(10))
+        .await??;
+
+    let response = match response.maybe_match {
+        MaybeMatchWithUid::Matched(matched) => {
+            QueryResponse::matched(matched.root_uid, matched.matched_graph)
+        }
+        MaybeMatchWithUid::Missed(_) => QueryResponse::Missed,
+    };
+
+    Ok(HttpResponse::Ok().json(response))
+}
@@ -0,0 +1,82 @@
+/// This Analyzer shows a very basic way to build an Analyzer: it reports a hit
+/// whenever a process spawns a child process, without querying the graph.
+use analyzer_sdk::server::{
+    self,
+    AnalyzerServiceConfig,
+};
+use grapl_tracing::setup_tracing;
+use rust_proto::graplinc::grapl::api::{
+    graph_query::v1beta1::messages::GraphView,
+    plugin_sdk::analyzers::v1beta1::{
+        messages::{
+            AnalyzerName,
+            ExecutionHit,
+            ExecutionMiss,
+            ExecutionResult,
+            RunAnalyzerRequest,
+            RunAnalyzerResponse,
+            Update,
+        },
+        server::AnalyzerApi,
+    },
+    protocol::status::Status,
+};
+const SERVICE_NAME: &'static str = "example-analyzer";
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let _guard = setup_tracing(SERVICE_NAME)?;
+    let config = AnalyzerServiceConfig::from_env_vars();
+    let analyzer = ExampleAnalyzer {};
+    server::exec_service(analyzer, config).await
+}
+
+/// An example, silly error class
+#[derive(thiserror::Error, Debug)]
+pub enum ExampleAnalyzerError {
+    #[error("SelfParent")]
+    SelfParent,
+}
+
+impl From<ExampleAnalyzerError> for Status {
+    fn from(e: ExampleAnalyzerError) -> Self {
+        Status::unknown(e.to_string())
+    }
+}
+
+pub struct ExampleAnalyzer {}
+
+#[async_trait::async_trait]
+impl AnalyzerApi for ExampleAnalyzer {
+    type Error = ExampleAnalyzerError;
+
+    #[tracing::instrument(skip(self, request), err)]
+    async fn run_analyzer(
+        &self,
+        request: RunAnalyzerRequest,
+    ) -> Result<RunAnalyzerResponse, Self::Error> {
+        let execution_result = match request.update() {
+            Update::Edge(edge) if edge.forward_edge_name.value == "children" => {
+                if edge.src_uid == edge.dst_uid {
+                    return Err(ExampleAnalyzerError::SelfParent);
+                }
+
+                let mut graph_view = GraphView::default();
+                graph_view.add_edge(edge.src_uid, edge.forward_edge_name, edge.dst_uid);
+
+                ExecutionResult::ExecutionHit(ExecutionHit {
+                    graph_view,
+                    analyzer_name: AnalyzerName {
+                        value: SERVICE_NAME.to_owned(),
+                    },
+                    idempotency_key: edge.dst_uid.as_u64(),
+                    score: 1,
+                    ..Default::default()
+                })
+            }
+            _ => ExecutionResult::ExecutionMiss(ExecutionMiss {}),
+        };
+
+        Ok(RunAnalyzerResponse { execution_result })
+    }
+}
@@ -0,0 +1,8 @@
+pub mod server;
+
+#[cfg(feature = "test_utils")]
+pub mod test_utils {
+    pub mod graph_query_proxy;
+    pub mod in_memory_graph;
+    pub mod test_ctx;
+}
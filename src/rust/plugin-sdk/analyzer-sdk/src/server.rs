@@ -0,0 +1,80 @@
+use std::{
+    sync::Arc,
+    time::Duration,
+};
+
+use clap::Parser;
+use rust_proto::graplinc::grapl::api::{
+    plugin_sdk::analyzers::v1beta1::server::{
+        AnalyzerApi,
+        AnalyzerServer,
+    },
+    protocol::shutdown::{
+        serve_until_signal,
+        shutdown_signal,
+    },
+};
+use tokio::net::TcpListener;
+
+#[derive(clap::Parser, Debug)]
+pub struct AnalyzerServiceConfig {
+    #[clap(long, env = "PLUGIN_BIND_ADDRESS")]
+    pub bind_address: std::net::SocketAddr,
+
+    /// How often the analyzer's health is polled
+    #[clap(
+        long,
+        env = "PLUGIN_HEALTHCHECK_POLLING_INTERVAL_MS",
+        default_value = "5000"
+    )]
+    pub healthcheck_polling_interval_ms: u64,
+
+    /// How long in-flight requests are given to finish once a SIGTERM or
+    /// SIGINT is received, before the service exits regardless
+    #[clap(long, env = "PLUGIN_SHUTDOWN_TIMEOUT_MS", default_value = "30000")]
+    pub shutdown_timeout_ms: u64,
+}
+impl AnalyzerServiceConfig {
+    /// An alias for clap::parse, so that consumers don't need to
+    /// declare a dependency on clap
+    pub fn from_env_vars() -> Self {
+        Self::parse()
+    }
+}
+
+/// Serves `analyzer` until the server fails or the process receives a
+/// SIGTERM or SIGINT. On a signal the server stops accepting connections and
+/// waits up to `shutdown_timeout_ms` for in-flight requests to finish.
+///
+/// The healthcheck service reports the result of `AnalyzerApi::health`.
+pub async fn exec_service(
+    analyzer: impl AnalyzerApi + Send + Sync + 'static,
+    config: AnalyzerServiceConfig,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let analyzer = Arc::new(analyzer);
+    let healthcheck_analyzer = analyzer.clone();
+
+    let (server, shutdown_tx) = AnalyzerServer::new(
+        analyzer,
+        TcpListener::bind(config.bind_address).await?,
+        move || {
+            let healthcheck_analyzer = healthcheck_analyzer.clone();
+            async move { healthcheck_analyzer.health().await }
+        },
+        Duration::from_millis(config.healthcheck_polling_interval_ms),
+    );
+    tracing::info!(
+        message = "starting gRPC server",
+        socket_address = %config.bind_address,
+    );
+
+    serve_until_signal(
+        server.serve(),
+        shutdown_tx,
+        shutdown_signal(),
+        Duration::from_millis(config.shutdown_timeout_ms),
+    )
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,181 @@
+use std::{
+    sync::{
+        Arc,
+        RwLock,
+        RwLockWriteGuard,
+    },
+    time::Duration,
+};
+
+use rust_proto::graplinc::grapl::api::{
+    client::{
+        ClientConfiguration,
+        Connect,
+    },
+    graph_query::v1beta1::messages::{
+        MatchedGraphWithUid,
+        MaybeMatchWithUid,
+        NoMatchWithUid,
+    },
+    graph_query_proxy::v1beta1::{
+        client::GraphQueryProxyClient,
+        messages::{
+            AggregateGraphFromUidRequest,
+            AggregateGraphFromUidResponse,
+            QueryGraphFromUidRequest,
+            QueryGraphFromUidResponse,
+            QueryGraphWithUidRequest,
+            QueryGraphWithUidResponse,
+        },
+        server::{
+            GraphQueryProxyApi,
+            GraphQueryProxyServer,
+        },
+    },
+    protocol::{
+        error::ServeError,
+        healthcheck::HealthcheckStatus,
+    },
+};
+use test_context::futures::channel::oneshot::Sender;
+use tokio::{
+    net::TcpListener,
+    task::JoinHandle,
+};
+
+use crate::test_utils::in_memory_graph::{
+    InMemoryGraph,
+    InMemoryGraphError,
+};
+
+/// Serves graph queries from an `InMemoryGraph`, so that an analyzer can be
+/// given a `GraphQueryProxyClient` in tests just as it would be in a
+/// deployment.
+pub struct InMemoryGraphQueryProxy {
+    graph: Arc<RwLock<InMemoryGraph>>,
+    client: GraphQueryProxyClient,
+    server_handle: JoinHandle<Result<(), ServeError>>,
+    shutdown_tx: Sender<()>,
+}
+
+impl InMemoryGraphQueryProxy {
+    pub async fn serve(graph: InMemoryGraph) -> Self {
+        // binding the tcp listener on port 0 tells the operating system to
+        // reserve an unused, ephemeral port
+        let tcp_listener = TcpListener::bind("0.0.0.0:0")
+            .await
+            .expect("failed to bind tcp listener");
+
+        let socket_address = tcp_listener
+            .local_addr()
+            .expect("failed to obtain socket address");
+        let address = format!("http://{}:{}", socket_address.ip(), socket_address.port());
+
+        let graph = Arc::new(RwLock::new(graph));
+        let (server, shutdown_tx) = GraphQueryProxyServer::new(
+            InMemoryGraphQueryApi {
+                graph: graph.clone(),
+            },
+            tcp_listener,
+            || async { Ok(HealthcheckStatus::Serving) },
+            Duration::from_millis(50),
+        );
+
+        let server_handle = tokio::task::spawn(server.serve());
+
+        let client_config = ClientConfiguration::new(
+            address,
+            Duration::from_millis(500),
+            Duration::from_millis(500),
+            2,
+            Duration::from_millis(1),
+            Duration::from_millis(100),
+            Duration::from_millis(10),
+            20,
+            Duration::from_millis(10),
+            Duration::from_millis(250),
+        );
+
+        let client = GraphQueryProxyClient::connect(client_config)
+            .await
+            .expect("graph query proxy never reported healthy");
+
+        Self {
+            graph,
+            client,
+            server_handle,
+            shutdown_tx,
+        }
+    }
+
+    pub fn client(&self) -> GraphQueryProxyClient {
+        self.client.clone()
+    }
+
+    /// The graph being served, e.g. to merge more of it in between requests.
+    /// Don't hold the guard across an `.await`: it blocks the server.
+    pub fn graph(&self) -> RwLockWriteGuard<'_, InMemoryGraph> {
+        self.graph.write().expect("graph lock poisoned")
+    }
+
+    pub async fn shutdown(self) {
+        self.shutdown_tx
+            .send(())
+            .expect("failed to shutdown server");
+        self.server_handle
+            .await
+            .expect("failed to join server task")
+            .expect("server configuration failed");
+    }
+}
+
+struct InMemoryGraphQueryApi {
+    graph: Arc<RwLock<InMemoryGraph>>,
+}
+
+#[async_trait::async_trait]
+impl GraphQueryProxyApi for InMemoryGraphQueryApi {
+    type Error = InMemoryGraphError;
+
+    async fn query_graph_with_uid(
+        &self,
+        request: QueryGraphWithUidRequest,
+    ) -> Result<QueryGraphWithUidResponse, InMemoryGraphError> {
+        let graph = self.graph.read().expect("graph lock poisoned");
+        let maybe_match = match graph
+            .query_graph_with_uid(&request.graph_query, request.node_uid)?
+        {
+            Some((matched_graph, root_uid)) => MaybeMatchWithUid::Matched(MatchedGraphWithUid {
+                matched_graph,
+                root_uid,
+            }),
+            None => MaybeMatchWithUid::Missed(NoMatchWithUid {}),
+        };
+
+        Ok(QueryGraphWithUidResponse { maybe_match })
+    }
+
+    async fn query_graph_from_uid(
+        &self,
+        request: QueryGraphFromUidRequest,
+    ) -> Result<QueryGraphFromUidResponse, InMemoryGraphError> {
+        let graph = self.graph.read().expect("graph lock poisoned");
+        let matched_graph = graph.query_graph_from_uid(&request.graph_query, request.node_uid)?;
+
+        Ok(QueryGraphFromUidResponse { matched_graph })
+    }
+
+    async fn aggregate_graph_from_uid(
+        &self,
+        request: AggregateGraphFromUidRequest,
+    ) -> Result<AggregateGraphFromUidResponse, InMemoryGraphError> {
+        let graph = self.graph.read().expect("graph lock poisoned");
+        let edge_counts = graph.aggregate_graph_from_uid(
+            &request.graph_query,
+            request.node_uid,
+            &request.edge_names,
+        )?;
+
+        Ok(AggregateGraphFromUidResponse { edge_counts })
+    }
+}
@@ -0,0 +1,962 @@
+use std::{
+    collections::{
+        BTreeSet,
+        HashMap,
+        HashSet,
+    },
+    time::UNIX_EPOCH,
+};
+
+use graph_query_matching::{
+    match_float,
+    match_int,
+    match_int_property,
+    match_property,
+    match_uid,
+    RegexCache,
+    RegexCacheError,
+};
+use rust_proto::graplinc::grapl::{
+    api::{
+        graph::v1beta1::{
+            GraphDescription,
+            IdentifiedGraph,
+            IdentifiedNode,
+            Property,
+        },
+        graph_query::v1beta1::messages::{
+            EdgeCount,
+            GraphEdgeCounts,
+            GraphQuery,
+            GraphView,
+            NodeEdgeCounts,
+            NodePropertiesView,
+            NodePropertyQuery,
+            PathFilter,
+            QueryId,
+            SearchNodesMatch,
+            SearchNodesRequest,
+            SearchNodesResponse,
+            SearchValue,
+            StringProperties,
+        },
+        plugin_sdk::analyzers::v1beta1::messages::{
+            BoolPropertyUpdate,
+            BytesPropertyUpdate,
+            DoublePropertyUpdate,
+            EdgeUpdate,
+            Int64PropertyUpdate,
+            StringPropertyUpdate,
+            StringSetPropertyUpdate,
+            TimestampPropertyUpdate,
+            UInt64PropertyUpdate,
+            Update,
+        },
+        protocol::status::Status,
+    },
+    common::v1beta1::types::{
+        EdgeName,
+        PropertyName,
+        Uid,
+    },
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum InMemoryGraphError {
+    #[error("Invalid edge key: {0:?}")]
+    NoSuchEdgeQueryId(QueryId),
+    #[error("Invalid root query id: {0:?}")]
+    NoSuchRootQueryId(QueryId),
+    #[error("RegexCacheError {0}")]
+    RegexCacheError(#[from] RegexCacheError),
+}
+
+impl From<InMemoryGraphError> for Status {
+    fn from(e: InMemoryGraphError) -> Self {
+        match e {
+            InMemoryGraphError::RegexCacheError(_) => Status::invalid_argument(e.to_string()),
+            _ => Status::unknown(e.to_string()),
+        }
+    }
+}
+
+/// A graph held in memory, which answers graph queries with the same
+/// semantics as the graph-query service answers them from Scylla: filters are
+/// matched by `graph_query_matching`, which the service shares, and the
+/// traversal below follows `graph_query::query_graph` and
+/// `node_query::fetch_node_with_edges` step by step, so a query matches here
+/// exactly when it would match in a deployment.
+///
+/// Graphs are seeded the way the pipeline writes them: `identify` stands in
+/// for node-identifier and `merge` for graph-merger and graph-mutation,
+/// returning the updates that analyzers would be run on.
+#[derive(Debug, Default)]
+pub struct InMemoryGraph {
+    nodes: HashMap<Uid, IdentifiedNode>,
+    edges: HashMap<(Uid, EdgeName), Vec<Uid>>,
+    reverse_edge_names: HashMap<String, String>,
+    node_keys: HashMap<String, Uid>,
+    next_uid: u64,
+}
+
+impl InMemoryGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the reverse of an edge, which graph-mutation would otherwise
+    /// have looked up in the plugin's schema. Every edge merged into the
+    /// graph must have one.
+    pub fn with_reverse_edge_name(
+        mut self,
+        edge_name: impl Into<String>,
+        reverse_edge_name: impl Into<String>,
+    ) -> Self {
+        let edge_name = edge_name.into();
+        let reverse_edge_name = reverse_edge_name.into();
+        self.reverse_edge_names
+            .insert(edge_name.clone(), reverse_edge_name.clone());
+        self.reverse_edge_names.insert(reverse_edge_name, edge_name);
+        self
+    }
+
+    /// The uid that `identify` assigned to the node with `node_key`.
+    pub fn node_uid(&self, node_key: &str) -> Option<Uid> {
+        self.node_keys.get(node_key).copied()
+    }
+
+    pub fn node(&self, uid: Uid) -> Option<&IdentifiedNode> {
+        self.nodes.get(&uid)
+    }
+
+    /// Assigns each node key its own uid, reusing the uid of a node key that
+    /// an earlier call has already identified.
+    pub fn identify(&mut self, graph: GraphDescription) -> IdentifiedGraph {
+        let mut identified_graph = IdentifiedGraph::new();
+
+        for node in graph.nodes.into_values() {
+            let uid = self.uid_for(node.node_key);
+            identified_graph.add_node(IdentifiedNode {
+                properties: node.properties,
+                uid,
+                node_type: node.node_type,
+            });
+        }
+
+        for edge in graph
+            .edges
+            .into_values()
+            .flat_map(|edge_list| edge_list.edges)
+        {
+            let from_uid = self.uid_for(edge.from_node_key);
+            let to_uid = self.uid_for(edge.to_node_key);
+            identified_graph.add_edge(edge.edge_name, from_uid, to_uid);
+        }
+
+        identified_graph
+    }
+
+    /// Merges `graph` into this graph and returns the updates that
+    /// graph-merger would emit for it: one for each property and edge that
+    /// changed the graph, properties first.
+    ///
+    /// # Panics
+    ///
+    /// If an edge's reverse hasn't been registered with
+    /// `with_reverse_edge_name`.
+    pub fn merge(&mut self, graph: IdentifiedGraph) -> Vec<Update> {
+        let mut updates = Vec::new();
+
+        for node in graph.nodes.into_values() {
+            let stored_node = self
+                .nodes
+                .entry(node.uid)
+                .or_insert_with(|| IdentifiedNode {
+                    properties: HashMap::new(),
+                    uid: node.uid,
+                    node_type: node.node_type.clone(),
+                });
+
+            for (property_name, property) in node.properties {
+                let changed = match stored_node.properties.get_mut(&property_name) {
+                    Some(stored_property) => {
+                        let before = stored_property.clone();
+                        stored_property.merge(&property);
+                        *stored_property != before
+                    }
+                    None => {
+                        stored_node
+                            .properties
+                            .insert(property_name.clone(), property.clone());
+                        true
+                    }
+                };
+
+                if changed {
+                    updates.push(property_to_update(
+                        node.uid,
+                        property_name,
+                        &property.property,
+                    ));
+                }
+            }
+        }
+
+        for edge in graph
+            .edges
+            .into_values()
+            .flat_map(|edge_list| edge_list.edges)
+        {
+            let reverse_edge_name = match self.reverse_edge_names.get(&edge.edge_name) {
+                Some(reverse_edge_name) => EdgeName {
+                    value: reverse_edge_name.clone(),
+                },
+                None => panic!(
+                    "no reverse edge name for {}, register it with with_reverse_edge_name",
+                    edge.edge_name
+                ),
+            };
+            let forward_edge_name = EdgeName {
+                value: edge.edge_name,
+            };
+
+            if self.add_edge(edge.from_uid, forward_edge_name.clone(), edge.to_uid) {
+                self.add_edge(edge.to_uid, reverse_edge_name.clone(), edge.from_uid);
+                updates.push(Update::Edge(EdgeUpdate {
+                    src_uid: edge.from_uid,
+                    dst_uid: edge.to_uid,
+                    forward_edge_name,
+                    reverse_edge_name,
+                }));
+            }
+        }
+
+        updates
+    }
+
+    /// Matches `graph_query` against the graph around `uid`, which may match
+    /// any node in the query. Mirrors `graph_query::query_graph`.
+    pub fn query_graph_with_uid(
+        &self,
+        graph_query: &GraphQuery,
+        uid: Uid,
+    ) -> Result<Option<(GraphView, Uid)>, InMemoryGraphError> {
+        let regex_cache = RegexCache::compile(graph_query)?;
+        for node_query in graph_query.node_property_queries.values() {
+            let mut traversal = Traversal::new(self, graph_query, &regex_cache);
+            match (traversal.match_node(node_query, uid)?, traversal.root_uid) {
+                (Some(graph), Some(root_uid)) => return Ok(Some((graph, root_uid))),
+                (Some(_), None) => {
+                    tracing::error!(
+                        message = "Graph query matched without finding root_uid. This is a bug.",
+                    )
+                }
+                (None, _) => continue,
+            }
+        }
+        Ok(None)
+    }
+
+    /// Matches `graph_query` against the graph with `uid` as its root.
+    pub fn query_graph_from_uid(
+        &self,
+        graph_query: &GraphQuery,
+        uid: Uid,
+    ) -> Result<Option<GraphView>, InMemoryGraphError> {
+        let regex_cache = RegexCache::compile(graph_query)?;
+        let node_query = graph_query
+            .node_property_queries
+            .get(&graph_query.root_query_id)
+            .ok_or(InMemoryGraphError::NoSuchRootQueryId(
+                graph_query.root_query_id,
+            ))?;
+
+        Traversal::new(self, graph_query, &regex_cache).match_node(node_query, uid)
+    }
+
+    /// Matches `graph_query` against the graph with `uid` as its root and
+    /// counts the edges of every matched node, by name, leaving out names
+    /// with no edges. Every edge name is counted if `edge_names` is empty.
+    /// Mirrors `GraphQueryService::aggregate_graph_from_uid`.
+    pub fn aggregate_graph_from_uid(
+        &self,
+        graph_query: &GraphQuery,
+        uid: Uid,
+        edge_names: &[EdgeName],
+    ) -> Result<Option<GraphEdgeCounts>, InMemoryGraphError> {
+        let graph = match self.query_graph_from_uid(graph_query, uid)? {
+            Some(graph) => graph,
+            None => return Ok(None),
+        };
+
+        let nodes = graph
+            .nodes
+            .into_iter()
+            .map(|(uid, node)| {
+                // Scylla groups edges in clustering order, i.e. by name
+                let counted_edge_names: Vec<EdgeName> = if edge_names.is_empty() {
+                    let mut counted_edge_names: Vec<EdgeName> = self
+                        .edges
+                        .keys()
+                        .filter(|(src_uid, _)| *src_uid == uid)
+                        .map(|(_, edge_name)| edge_name.clone())
+                        .collect();
+                    counted_edge_names.sort_by(|a, b| a.value.cmp(&b.value));
+                    counted_edge_names
+                } else {
+                    edge_names.to_vec()
+                };
+                let edge_counts = counted_edge_names
+                    .into_iter()
+                    .filter_map(|edge_name| {
+                        let count = self.get_edges(uid, &edge_name)?.len() as u64;
+                        Some(EdgeCount { edge_name, count })
+                    })
+                    .collect();
+                NodeEdgeCounts {
+                    uid,
+                    node_type: node.node_type,
+                    edge_counts,
+                }
+            })
+            .collect();
+
+        Ok(Some(GraphEdgeCounts { nodes }))
+    }
+
+    /// Finds the nodes that graph-mutation would have written to the inverted
+    /// property index under the request's node type, property and value, and
+    /// pages through them in the index's order. Mirrors
+    /// `GraphQueryService::search_nodes`, except that a request without a
+    /// `page_size` gets every node in one page, and that requests to `explain`
+    /// get no explanation.
+    pub fn search_nodes(
+        &self,
+        request: &SearchNodesRequest,
+    ) -> Result<SearchNodesResponse, InMemoryGraphError> {
+        let mut uids: Vec<Uid> = self
+            .nodes
+            .values()
+            .filter(|node| {
+                node.node_type == request.node_type.value
+                    && self.is_indexed_under(node.uid, &request.property_name, &request.value)
+            })
+            .map(|node| node.uid)
+            .collect();
+        // The index is clustered by uid as Scylla stores it, a signed bigint
+        uids.sort_by_key(|uid| uid.as_i64());
+        if let Some(page_token) = request.page_token {
+            uids.retain(|uid| uid.as_i64() > page_token.as_i64());
+        }
+        let next_page_token = match request.page_size {
+            Some(page_size) if uids.len() >= page_size as usize => {
+                uids.truncate(page_size as usize);
+                uids.last().copied()
+            }
+            _ => None,
+        };
+
+        let graph_query = match &request.graph_query {
+            Some(graph_query) => graph_query,
+            None => {
+                return Ok(SearchNodesResponse {
+                    matches: uids
+                        .into_iter()
+                        .map(|uid| SearchNodesMatch {
+                            uid,
+                            matched_graph: None,
+                        })
+                        .collect(),
+                    next_page_token,
+                    explanation: None,
+                })
+            }
+        };
+
+        let regex_cache = RegexCache::compile(graph_query)?;
+        let node_query = graph_query
+            .node_property_queries
+            .get(&graph_query.root_query_id)
+            .ok_or(InMemoryGraphError::NoSuchRootQueryId(
+                graph_query.root_query_id,
+            ))?;
+
+        let mut matches = Vec::new();
+        for uid in uids {
+            if let Some(graph) =
+                Traversal::new(self, graph_query, &regex_cache).match_node(node_query, uid)?
+            {
+                matches.push(SearchNodesMatch {
+                    uid,
+                    matched_graph: Some(graph),
+                });
+            }
+        }
+        Ok(SearchNodesResponse {
+            matches,
+            next_page_token,
+            explanation: None,
+        })
+    }
+
+    fn uid_for(&mut self, node_key: String) -> Uid {
+        if let Some(uid) = self.node_keys.get(&node_key) {
+            return *uid;
+        }
+
+        let uid = loop {
+            self.next_uid += 1;
+            let uid = Uid::from_u64(self.next_uid).expect("uids start at 1");
+            if !self.nodes.contains_key(&uid) {
+                break uid;
+            }
+        };
+        self.node_keys.insert(node_key, uid);
+        uid
+    }
+
+    /// Returns whether the edge is new.
+    fn add_edge(&mut self, from_uid: Uid, edge_name: EdgeName, to_uid: Uid) -> bool {
+        let destinations = self.edges.entry((from_uid, edge_name)).or_default();
+        if destinations.contains(&to_uid) {
+            false
+        } else {
+            destinations.push(to_uid);
+            true
+        }
+    }
+
+    /// As by `PropertyQueryExecutor::get_immutable_string`.
+    fn get_immutable_string(&self, uid: Uid, property_name: &PropertyName) -> Option<&str> {
+        let node = self.nodes.get(&uid)?;
+        match &node.properties.get(&property_name.value)?.property {
+            Property::ImmutableStrProp(property) => Some(&property.prop),
+            _ => None,
+        }
+    }
+
+    /// As by `PropertyQueryExecutor::get_string_set`, which the string
+    /// filters fall back to when there is no immutable string.
+    fn get_string_set(&self, uid: Uid, property_name: &PropertyName) -> Option<&BTreeSet<String>> {
+        let node = self.nodes.get(&uid)?;
+        match &node.properties.get(&property_name.value)?.property {
+            Property::StringSetProp(property) if !property.prop.is_empty() => Some(&property.prop),
+            _ => None,
+        }
+    }
+
+    /// As by `PropertyQueryExecutor::get_int`.
+    fn get_int(&self, uid: Uid, property_name: &PropertyName) -> Option<i128> {
+        let node = self.nodes.get(&uid)?;
+        match &node.properties.get(&property_name.value)?.property {
+            Property::ImmutableIntProp(property) => Some(property.prop.into()),
+            Property::IncrementOnlyIntProp(property) => Some(property.prop.into()),
+            Property::DecrementOnlyIntProp(property) => Some(property.prop.into()),
+            Property::ImmutableUintProp(property) => Some(property.prop.into()),
+            Property::IncrementOnlyUintProp(property) => Some(property.prop.into()),
+            Property::DecrementOnlyUintProp(property) => Some(property.prop.into()),
+            _ => None,
+        }
+    }
+
+    /// As by `PropertyQueryExecutor::get_timestamp`, in milliseconds since the
+    /// Unix epoch.
+    fn get_timestamp(&self, uid: Uid, property_name: &PropertyName) -> Option<i64> {
+        let node = self.nodes.get(&uid)?;
+        let timestamp = match &node.properties.get(&property_name.value)?.property {
+            Property::ImmutableTimestampProp(property) => property.prop,
+            Property::IncrementOnlyTimestampProp(property) => property.prop,
+            Property::DecrementOnlyTimestampProp(property) => property.prop,
+            _ => return None,
+        };
+        // As graph-mutation stores it
+        Some(match timestamp.duration_since(UNIX_EPOCH) {
+            Ok(since_epoch) => since_epoch.as_millis() as i64,
+            Err(e) => -(e.duration().as_millis() as i64),
+        })
+    }
+
+    /// As by `PropertyQueryExecutor::get_f64`.
+    fn get_f64(&self, uid: Uid, property_name: &PropertyName) -> Option<f64> {
+        let node = self.nodes.get(&uid)?;
+        match &node.properties.get(&property_name.value)?.property {
+            Property::ImmutableDoubleProp(property) => Some(property.prop),
+            _ => None,
+        }
+    }
+
+    /// As by `PropertyQueryExecutor::get_bool`.
+    fn get_bool(&self, uid: Uid, property_name: &PropertyName) -> Option<bool> {
+        let node = self.nodes.get(&uid)?;
+        match &node.properties.get(&property_name.value)?.property {
+            Property::ImmutableBoolProp(property) => Some(property.prop),
+            _ => None,
+        }
+    }
+
+    /// As by `PropertyQueryExecutor::get_bytes`.
+    fn get_bytes(&self, uid: Uid, property_name: &PropertyName) -> Option<&[u8]> {
+        let node = self.nodes.get(&uid)?;
+        match &node.properties.get(&property_name.value)?.property {
+            Property::ImmutableBytesProp(property) => Some(&property.prop[..]),
+            _ => None,
+        }
+    }
+
+    /// Whether graph-mutation would have indexed the node's property under
+    /// `value`. Only immutable strings and integers are indexed.
+    fn is_indexed_under(
+        &self,
+        uid: Uid,
+        property_name: &PropertyName,
+        value: &SearchValue,
+    ) -> bool {
+        let property = self
+            .nodes
+            .get(&uid)
+            .and_then(|node| node.properties.get(&property_name.value));
+        match (property.map(|property| &property.property), value) {
+            (Some(Property::ImmutableStrProp(property)), SearchValue::String(value)) => {
+                &property.prop == value
+            }
+            (Some(Property::ImmutableIntProp(property)), SearchValue::I64(value)) => {
+                property.prop == *value
+            }
+            (Some(Property::ImmutableUintProp(property)), SearchValue::U64(value)) => {
+                property.prop == *value
+            }
+            _ => false,
+        }
+    }
+
+    fn get_edges(&self, uid: Uid, edge_name: &EdgeName) -> Option<&[Uid]> {
+        self.edges
+            .get(&(uid, edge_name.clone()))
+            .map(Vec::as_slice)
+            .filter(|destinations| !destinations.is_empty())
+    }
+}
+
+/// The state of matching one node query of a `GraphQuery` from one node,
+/// which `Visited` and the root uid out-parameter hold in graph-query.
+struct Traversal<'a> {
+    graph: &'a InMemoryGraph,
+    graph_query: &'a GraphQuery,
+    regex_cache: &'a RegexCache,
+    visited: HashSet<(QueryId, EdgeName, QueryId)>,
+    visited_path_nodes: HashSet<(QueryId, EdgeName, QueryId, Uid)>,
+    short_circuit: bool,
+    root_uid: Option<Uid>,
+}
+
+impl<'a> Traversal<'a> {
+    fn new(
+        graph: &'a InMemoryGraph,
+        graph_query: &'a GraphQuery,
+        regex_cache: &'a RegexCache,
+    ) -> Self {
+        Self {
+            graph,
+            graph_query,
+            regex_cache,
+            visited: HashSet::new(),
+            visited_path_nodes: HashSet::new(),
+            short_circuit: false,
+            root_uid: None,
+        }
+    }
+
+    /// Mirrors `Visited::fork`.
+    fn fork(&self) -> Self {
+        Self {
+            graph: self.graph,
+            graph_query: self.graph_query,
+            regex_cache: self.regex_cache,
+            visited: self.visited.clone(),
+            visited_path_nodes: self.visited_path_nodes.clone(),
+            short_circuit: false,
+            root_uid: self.root_uid,
+        }
+    }
+
+    /// Mirrors `Visited::join`.
+    fn join(&mut self, fork: Self) {
+        self.visited.extend(fork.visited);
+        self.visited_path_nodes.extend(fork.visited_path_nodes);
+    }
+
+    fn check_and_add(&mut self, src: QueryId, edge_name: EdgeName, dst: QueryId) -> bool {
+        !self.visited.insert((src, edge_name, dst))
+    }
+
+    fn check_and_add_path_node(
+        &mut self,
+        src: QueryId,
+        edge_name: EdgeName,
+        dst: QueryId,
+        uid: Uid,
+    ) -> bool {
+        !self.visited_path_nodes.insert((src, edge_name, dst, uid))
+    }
+
+    /// Adds the string properties that `node_query` filters on to `node`,
+    /// returning whether every string and int filter matched. Mirrors
+    /// `node_query::fetch_node_properties`.
+    fn match_properties(
+        &self,
+        node_query: &NodePropertyQuery,
+        uid: Uid,
+        node: &mut NodePropertiesView,
+    ) -> bool {
+        let regex_cache = self.regex_cache;
+
+        for property_name in node_query.string_filters.keys() {
+            let value = match self.graph.get_immutable_string(uid, property_name) {
+                Some(value) => Some(value),
+                None => self
+                    .graph
+                    .get_string_set(uid, property_name)
+                    .and_then(|string_set| {
+                        string_set.iter().map(String::as_str).find(|value| {
+                            match_property(node_query, property_name, value, regex_cache, &())
+                        })
+                    }),
+            };
+            match value {
+                Some(value)
+                    if match_property(node_query, property_name, value, regex_cache, &()) =>
+                {
+                    node.add_string_property(property_name.clone(), value.to_owned());
+                }
+                _ => return false,
+            }
+        }
+
+        node_query.int_filters.keys().all(|property_name| {
+            match self.graph.get_int(uid, property_name) {
+                Some(value) => match_int_property(node_query, property_name, value, &()),
+                None => false,
+            }
+        }) && node_query
+            .timestamp_filters
+            .iter()
+            .all(
+                |(property_name, filters)| match self.graph.get_timestamp(uid, property_name) {
+                    Some(millis) => match_int(filters, i128::from(millis), &()),
+                    None => false,
+                },
+            )
+            && node_query
+                .float_filters
+                .iter()
+                .all(
+                    |(property_name, filters)| match self.graph.get_f64(uid, property_name) {
+                        Some(value) => match_float(filters, value, &()),
+                        None => false,
+                    },
+                )
+            && node_query
+                .bool_filters
+                .iter()
+                .all(|(property_name, expected)| {
+                    self.graph.get_bool(uid, property_name) == Some(*expected)
+                })
+            && node_query
+                .bytes_filters
+                .iter()
+                .all(|(property_name, expected)| {
+                    self.graph.get_bytes(uid, property_name) == Some(&expected[..])
+                })
+    }
+
+    /// Mirrors `node_query::match_edge_counts`.
+    fn match_edge_counts(&self, node_query: &NodePropertyQuery, uid: Uid) -> bool {
+        self.graph_query
+            .edge_count_filters
+            .iter()
+            .filter(|((src_id, _), _)| *src_id == node_query.query_id)
+            .all(|((_, edge_name), count_filters)| {
+                let count = self
+                    .graph
+                    .get_edges(uid, edge_name)
+                    .map_or(0, |destinations| destinations.len());
+                match_int(count_filters, count as i128, &())
+            })
+    }
+
+    /// Mirrors `node_query::fetch_node_with_edges`.
+    fn match_node(
+        &mut self,
+        node_query: &NodePropertyQuery,
+        uid: Uid,
+    ) -> Result<Option<GraphView>, InMemoryGraphError> {
+        if self.short_circuit {
+            return Ok(None);
+        }
+        let graph_query = self.graph_query;
+
+        if !match_uid(node_query, uid, &()) {
+            return Ok(None);
+        }
+
+        let mut node = NodePropertiesView::new(
+            uid,
+            node_query.node_type.clone(),
+            StringProperties::default(),
+        );
+
+        if !self.match_properties(node_query, uid, &mut node) {
+            self.short_circuit = true;
+            return Ok(None);
+        }
+
+        if !self.match_edge_counts(node_query, uid) {
+            self.short_circuit = true;
+            return Ok(None);
+        }
+
+        if node_query.query_id == graph_query.root_query_id {
+            self.root_uid = Some(uid);
+        }
+
+        let mut graph = GraphView::default();
+        graph.add_node(node);
+
+        let mut edges = HashMap::new();
+        for (src_id, edge_name) in graph_query.edge_filters.keys() {
+            if *src_id != node_query.query_id {
+                continue;
+            }
+            match self.graph.get_edges(uid, edge_name) {
+                Some(destinations) => edges.insert(edge_name, destinations),
+                None => {
+                    self.short_circuit = true;
+                    return Ok(None);
+                }
+            };
+        }
+
+        for ((src_id, edge_name), edge_query_ids) in graph_query.edge_filters.iter() {
+            if *src_id != node_query.query_id {
+                continue;
+            }
+            let reverse_edge_name = &graph_query.edge_map[edge_name];
+
+            for edge_query_id in edge_query_ids {
+                let edge_query = graph_query
+                    .node_property_queries
+                    .get(edge_query_id)
+                    .ok_or(InMemoryGraphError::NoSuchEdgeQueryId(*edge_query_id))?;
+
+                // we have to check the reverse edge as well
+                if self.check_and_add(node_query.query_id, edge_name.clone(), edge_query.query_id) {
+                    continue;
+                }
+                if self.check_and_add(
+                    edge_query.query_id,
+                    reverse_edge_name.clone(),
+                    node_query.query_id,
+                ) {
+                    continue;
+                }
+
+                let mut any = false;
+                for destination_uid in edges[edge_name] {
+                    let neighbors = match self.match_node(edge_query, *destination_uid)? {
+                        Some(neighbors) => neighbors,
+                        None => continue,
+                    };
+                    any = true;
+                    for neighbor in neighbors.nodes.keys() {
+                        graph.add_edge(uid, edge_name.clone(), *neighbor);
+                        graph.add_edge(*neighbor, reverse_edge_name.clone(), uid);
+                    }
+                    graph.merge(neighbors);
+                }
+                if !any {
+                    self.short_circuit = true;
+                    return Ok(None);
+                }
+            }
+        }
+
+        for ((src_id, edge_name), path_filters) in graph_query.path_filters.iter() {
+            if *src_id != node_query.query_id {
+                continue;
+            }
+
+            for path_filter in path_filters {
+                let neighbor_query = graph_query
+                    .node_property_queries
+                    .get(&path_filter.neighbor_query_id)
+                    .ok_or(InMemoryGraphError::NoSuchEdgeQueryId(
+                        path_filter.neighbor_query_id,
+                    ))?;
+
+                if self.check_and_add(
+                    node_query.query_id,
+                    edge_name.clone(),
+                    neighbor_query.query_id,
+                ) {
+                    continue;
+                }
+                if self.check_and_add(
+                    neighbor_query.query_id,
+                    graph_query.edge_map[edge_name].clone(),
+                    node_query.query_id,
+                ) {
+                    continue;
+                }
+
+                match self.match_path(node_query, neighbor_query, path_filter, edge_name, uid)? {
+                    Some(path) => graph.merge(path),
+                    None => {
+                        self.short_circuit = true;
+                        return Ok(None);
+                    }
+                }
+            }
+        }
+
+        Ok(Some(graph))
+    }
+
+    /// Mirrors `node_query::fetch_path`.
+    fn match_path(
+        &mut self,
+        node_query: &NodePropertyQuery,
+        neighbor_query: &NodePropertyQuery,
+        path_filter: &PathFilter,
+        edge_name: &EdgeName,
+        uid: Uid,
+    ) -> Result<Option<GraphView>, InMemoryGraphError> {
+        let src_id = node_query.query_id;
+        let dst_id = neighbor_query.query_id;
+        self.check_and_add_path_node(src_id, edge_name.clone(), dst_id, uid);
+
+        let mut previous: HashMap<Uid, Uid> = HashMap::new();
+        let mut intermediate_nodes: HashMap<Uid, NodePropertiesView> = HashMap::new();
+        let mut frontier = vec![uid];
+
+        for hops in 1..=path_filter.max_hops {
+            let mut next_frontier = vec![];
+            for from_uid in frontier {
+                let destinations = match self.graph.get_edges(from_uid, edge_name) {
+                    Some(destinations) => destinations,
+                    None => continue,
+                };
+
+                for to_uid in destinations.iter().copied() {
+                    if self.check_and_add_path_node(src_id, edge_name.clone(), dst_id, to_uid) {
+                        continue;
+                    }
+                    previous.insert(to_uid, from_uid);
+
+                    if hops >= path_filter.min_hops {
+                        let mut candidate = self.fork();
+                        let neighbor_graph = candidate.match_node(neighbor_query, to_uid)?;
+                        self.root_uid = candidate.root_uid;
+
+                        if let Some(mut graph) = neighbor_graph {
+                            self.join(candidate);
+                            let reverse_edge_name = &self.graph_query.edge_map[edge_name];
+                            let mut to_uid = to_uid;
+                            while to_uid != uid {
+                                let from_uid = previous[&to_uid];
+                                if let Some(node) = intermediate_nodes.remove(&from_uid) {
+                                    graph.add_node(node);
+                                }
+                                graph.add_edge(from_uid, edge_name.clone(), to_uid);
+                                graph.add_edge(to_uid, reverse_edge_name.clone(), from_uid);
+                                to_uid = from_uid;
+                            }
+                            return Ok(Some(graph));
+                        }
+                    }
+
+                    if hops < path_filter.max_hops {
+                        if let Some(node) =
+                            self.match_intermediate_node(neighbor_query, path_filter, to_uid)
+                        {
+                            intermediate_nodes.insert(to_uid, node);
+                            next_frontier.push(to_uid);
+                        }
+                    }
+                }
+            }
+
+            if next_frontier.is_empty() {
+                break;
+            }
+            frontier = next_frontier;
+        }
+
+        Ok(None)
+    }
+
+    /// Mirrors `node_query::fetch_intermediate_node`.
+    fn match_intermediate_node(
+        &self,
+        neighbor_query: &NodePropertyQuery,
+        path_filter: &PathFilter,
+        uid: Uid,
+    ) -> Option<NodePropertiesView> {
+        let mut node = NodePropertiesView::new(
+            uid,
+            neighbor_query.node_type.clone(),
+            StringProperties::default(),
+        );
+
+        match &path_filter.intermediate_node_query {
+            Some(intermediate_query) => {
+                if match_uid(intermediate_query, uid, &())
+                    && self.match_properties(intermediate_query, uid, &mut node)
+                {
+                    Some(node)
+                } else {
+                    None
+                }
+            }
+            None => Some(node),
+        }
+    }
+}
+
+/// Mirrors graph-merger's `property_to_update`.
+fn property_to_update(uid: Uid, property_name: String, property: &Property) -> Update {
+    let property_name = PropertyName {
+        value: property_name,
+    };
+    match property {
+        Property::IncrementOnlyUintProp(_)
+        | Property::DecrementOnlyUintProp(_)
+        | Property::ImmutableUintProp(_) => {
+            Update::Uint64Property(UInt64PropertyUpdate { uid, property_name })
+        }
+        Property::IncrementOnlyIntProp(_)
+        | Property::DecrementOnlyIntProp(_)
+        | Property::ImmutableIntProp(_) => {
+            Update::Int64Property(Int64PropertyUpdate { uid, property_name })
+        }
+        Property::ImmutableStrProp(_) => {
+            Update::StringProperty(StringPropertyUpdate { uid, property_name })
+        }
+        Property::ImmutableBoolProp(_) => {
+            Update::BoolProperty(BoolPropertyUpdate { uid, property_name })
+        }
+        Property::ImmutableDoubleProp(_) => {
+            Update::DoubleProperty(DoublePropertyUpdate { uid, property_name })
+        }
+        Property::ImmutableBytesProp(_) => {
+            Update::BytesProperty(BytesPropertyUpdate { uid, property_name })
+        }
+        Property::ImmutableTimestampProp(_)
+        | Property::IncrementOnlyTimestampProp(_)
+        | Property::DecrementOnlyTimestampProp(_) => {
+            Update::TimestampProperty(TimestampPropertyUpdate { uid, property_name })
+        }
+        Property::StringSetProp(_) => {
+            Update::StringSetProperty(StringSetPropertyUpdate { uid, property_name })
+        }
+    }
+}
@@ -0,0 +1,165 @@
+use std::{
+    sync::Arc,
+    time::Duration as StdDuration,
+};
+
+use rust_proto::graplinc::{
+    common::v1beta1::Duration,
+    grapl::api::{
+        client::{
+            ClientConfiguration,
+            Connect,
+        },
+        plugin_sdk::analyzers::v1beta1::{
+            client::AnalyzerClient,
+            server::{
+                AnalyzerApi,
+                AnalyzerServer,
+            },
+        },
+        protocol::{
+            error::ServeError,
+            shutdown::serve_until_signal,
+        },
+    },
+};
+use test_context::{
+    futures::{
+        channel::oneshot::{
+            self,
+            Sender,
+        },
+        FutureExt,
+    },
+    AsyncTestContext,
+};
+use tokio::{
+    net::TcpListener,
+    task::JoinHandle,
+};
+
+/// How long in-flight requests are given to finish once the test context
+/// shuts the server down.
+const SHUTDOWN_TIMEOUT: StdDuration = StdDuration::from_secs(5);
+
+// Mirrors generator-sdk's GeneratorTestContext: the internals are created
+// lazily by .get_client(), since TestContext can't be parameterized by the
+// AnalyzerApi under test.
+struct AnalyzerTestContextInternals {
+    client: AnalyzerClient,
+    server_handle: JoinHandle<Result<(), ServeError>>,
+    signal_tx: Sender<()>,
+}
+
+impl AnalyzerTestContextInternals {
+    async fn new(analyzer_api: impl AnalyzerApi + Send + Sync + 'static) -> Self {
+        // binding the tcp listener on port 0 tells the operating system to
+        // reserve an unused, ephemeral port
+        let tcp_listener = TcpListener::bind("0.0.0.0:0")
+            .await
+            .expect("failed to bind tcp listener");
+
+        // determine the actual port which was bound
+        let socket_address = tcp_listener
+            .local_addr()
+            .expect("failed to obtain socket address");
+
+        // construct an http URI clients can use to connect to server bound to
+        // the port.
+        let address = format!("http://{}:{}", socket_address.ip(), socket_address.port());
+
+        let analyzer_api = Arc::new(analyzer_api);
+        let healthcheck_api = analyzer_api.clone();
+
+        let (server, shutdown_tx) = AnalyzerServer::new(
+            analyzer_api,
+            tcp_listener,
+            move || {
+                let healthcheck_api = healthcheck_api.clone();
+                async move { healthcheck_api.health().await }
+            },
+            Duration::from_millis(50),
+        );
+
+        // The server is run the way exec_service runs it, with this channel
+        // standing in for a SIGTERM, so that tests exercise the same drain.
+        let (signal_tx, signal_rx) = oneshot::channel::<()>();
+        let server_handle = tokio::task::spawn(serve_until_signal(
+            server.serve(),
+            shutdown_tx,
+            signal_rx.map(|_| Ok("SIGTERM")),
+            SHUTDOWN_TIMEOUT,
+        ));
+
+        let client_config = ClientConfiguration::new(
+            address,
+            Duration::from_millis(500),
+            Duration::from_millis(500),
+            2,
+            Duration::from_millis(1),
+            Duration::from_millis(100),
+            Duration::from_millis(10),
+            20,
+            Duration::from_millis(10),
+            Duration::from_millis(250),
+        );
+
+        let client = AnalyzerClient::connect(client_config)
+            .await
+            .expect("analyzer never reported healthy");
+
+        AnalyzerTestContextInternals {
+            client,
+            server_handle,
+            signal_tx,
+        }
+    }
+
+    async fn shutdown(self) -> Result<(), ServeError> {
+        // Sending only fails if the server has already stopped, in which case
+        // its result is collected below.
+        let _ = self.signal_tx.send(());
+        self.server_handle
+            .await
+            .expect("failed to join server task")
+    }
+}
+
+pub struct AnalyzerTestContext {
+    internals: Option<AnalyzerTestContextInternals>,
+}
+
+#[async_trait::async_trait]
+impl AsyncTestContext for AnalyzerTestContext {
+    async fn setup() -> Self {
+        Self { internals: None }
+    }
+
+    async fn teardown(self) {
+        if let Some(i) = self.internals {
+            i.shutdown().await.expect("server configuration failed");
+        }
+    }
+}
+
+impl AnalyzerTestContext {
+    pub async fn get_client(
+        &mut self,
+        analyzer_api: impl AnalyzerApi + Send + Sync + 'static,
+    ) -> AnalyzerClient {
+        if let None = self.internals {
+            self.internals = Some(AnalyzerTestContextInternals::new(analyzer_api).await)
+        }
+
+        self.internals.as_ref().expect("internals").client.clone()
+    }
+
+    /// Signals the server to shut down, as exec_service does on a SIGTERM, and
+    /// waits for it to drain in-flight requests and stop.
+    pub async fn shutdown(&mut self) -> Result<(), ServeError> {
+        match self.internals.take() {
+            Some(i) => i.shutdown().await,
+            None => Ok(()),
+        }
+    }
+}
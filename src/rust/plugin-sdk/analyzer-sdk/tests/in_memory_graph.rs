@@ -0,0 +1,695 @@
+use std::time::{
+    Duration,
+    UNIX_EPOCH,
+};
+
+use analyzer_sdk::test_utils::{
+    graph_query_proxy::InMemoryGraphQueryProxy,
+    in_memory_graph::InMemoryGraph,
+    test_ctx::AnalyzerTestContext,
+};
+use graph_query_builder::{
+    node_types::{
+        ProcessQuery,
+        ProcessView,
+    },
+    NodeQueryBuilder,
+};
+use rust_proto::graplinc::grapl::{
+    api::{
+        client::ClientError,
+        graph::v1beta1::{
+            GraphDescription,
+            ImmutableBoolProp,
+            ImmutableBytesProp,
+            ImmutableDoubleProp,
+            ImmutableStrProp,
+            ImmutableTimestampProp,
+            ImmutableUintProp,
+            NodeDescription,
+        },
+        graph_query::v1beta1::messages::{
+            FloatCmp,
+            IntCmp,
+            SearchNodesRequest,
+            SearchValue,
+            StringCmp,
+        },
+        graph_query_proxy::v1beta1::client::GraphQueryProxyClient,
+        plugin_sdk::analyzers::v1beta1::{
+            messages::{
+                AnalyzerName,
+                ExecutionHit,
+                ExecutionMiss,
+                ExecutionResult,
+                RunAnalyzerRequest,
+                RunAnalyzerResponse,
+                Update,
+            },
+            server::AnalyzerApi,
+        },
+        protocol::status::Status,
+    },
+    common::v1beta1::types::{
+        EdgeName,
+        NodeType,
+        PropertyName,
+        Uid,
+    },
+};
+use test_context::test_context;
+
+/// Hits when cmd.exe is a child of WINWORD.EXE.
+struct WordSpawnsCmdAnalyzer {
+    graph_query_client: GraphQueryProxyClient,
+}
+
+#[async_trait::async_trait]
+impl AnalyzerApi for WordSpawnsCmdAnalyzer {
+    type Error = Status;
+
+    async fn run_analyzer(
+        &self,
+        request: RunAnalyzerRequest,
+    ) -> Result<RunAnalyzerResponse, Self::Error> {
+        let uid = match request.update() {
+            Update::StringProperty(update) => update.uid,
+            Update::Edge(update) => update.src_uid,
+            _ => {
+                return Ok(RunAnalyzerResponse {
+                    execution_result: ExecutionResult::ExecutionMiss(ExecutionMiss {}),
+                })
+            }
+        };
+
+        let mut query = ProcessQuery::new();
+        query
+            .with_process_name(vec![StringCmp::eq("cmd.exe", false)])
+            .with_parent(|parent| {
+                parent.with_process_name(vec![StringCmp::eq("WINWORD.EXE", false)]);
+            });
+
+        let matched = NodeQueryBuilder::from(query)
+            .query_graph_with_uid(&mut self.graph_query_client.clone(), uid)
+            .await
+            .map_err(|e: ClientError| Status::unknown(e.to_string()))?;
+
+        let execution_result = match matched {
+            Some(matched) => ExecutionResult::ExecutionHit(ExecutionHit {
+                idempotency_key: matched.root_uid.as_u64(),
+                graph_view: matched.graph,
+                analyzer_name: AnalyzerName {
+                    value: "word-spawns-cmd".to_owned(),
+                },
+                score: 75,
+                ..Default::default()
+            }),
+            None => ExecutionResult::ExecutionMiss(ExecutionMiss {}),
+        };
+
+        Ok(RunAnalyzerResponse { execution_result })
+    }
+}
+
+fn process(node_key: &str, process_name: &str) -> NodeDescription {
+    let mut node = NodeDescription {
+        properties: Default::default(),
+        node_key: node_key.to_owned(),
+        node_type: "Process".to_owned(),
+        id_strategy: vec![],
+    };
+    node.set_property("process_name", ImmutableStrProp::from(process_name));
+    node
+}
+
+fn process_tree(parent_name: &str) -> GraphDescription {
+    let mut graph = GraphDescription::new();
+    graph.add_node(process("parent", parent_name));
+    graph.add_node(process("child", "cmd.exe"));
+    graph.add_edge("children", "parent", "child");
+    graph
+}
+
+#[test_context(AnalyzerTestContext)]
+#[tokio::test]
+async fn test_analyzer_hits_on_matching_graph(ctx: &mut AnalyzerTestContext) -> eyre::Result<()> {
+    let mut graph = InMemoryGraph::new().with_reverse_edge_name("children", "parent");
+    let identified_graph = graph.identify(process_tree("WINWORD.EXE"));
+    let updates = graph.merge(identified_graph);
+    let child_uid = graph.node_uid("child").expect("child was identified");
+
+    // Two process names and the edge between them
+    assert_eq!(updates.len(), 3);
+
+    let proxy = InMemoryGraphQueryProxy::serve(graph).await;
+    let mut client = ctx
+        .get_client(WordSpawnsCmdAnalyzer {
+            graph_query_client: proxy.client(),
+        })
+        .await;
+
+    for update in updates {
+        let response = client
+            .run_analyzer(RunAnalyzerRequest::new(update), None)
+            .await?;
+        let hit = match response.execution_result {
+            ExecutionResult::ExecutionHit(hit) => hit,
+            ExecutionResult::ExecutionMiss(_) => panic!("expected a hit"),
+        };
+
+        assert_eq!(hit.analyzer_name.value, "word-spawns-cmd");
+        assert_eq!(hit.idempotency_key, child_uid.as_u64());
+        let matched = graph_query_builder::MatchedGraph {
+            graph: hit.graph_view,
+            root_uid: child_uid,
+        };
+        let child: ProcessView = matched.root().expect("child in matched graph");
+        assert_eq!(child.get_process_name(), Some("cmd.exe"));
+        assert_eq!(
+            child
+                .get_parent()
+                .map(|parent| parent.get_process_name())
+                .collect::<Vec<_>>(),
+            vec![Some("WINWORD.EXE")]
+        );
+    }
+
+    proxy.shutdown().await;
+    Ok(())
+}
+
+#[test_context(AnalyzerTestContext)]
+#[tokio::test]
+async fn test_analyzer_misses_on_other_graphs(ctx: &mut AnalyzerTestContext) -> eyre::Result<()> {
+    let mut graph = InMemoryGraph::new().with_reverse_edge_name("children", "parent");
+    let identified_graph = graph.identify(process_tree("explorer.exe"));
+    let updates = graph.merge(identified_graph);
+
+    let proxy = InMemoryGraphQueryProxy::serve(graph).await;
+    let mut client = ctx
+        .get_client(WordSpawnsCmdAnalyzer {
+            graph_query_client: proxy.client(),
+        })
+        .await;
+
+    for update in updates {
+        let response = client
+            .run_analyzer(RunAnalyzerRequest::new(update), None)
+            .await?;
+        assert!(matches!(
+            response.execution_result,
+            ExecutionResult::ExecutionMiss(_)
+        ));
+    }
+
+    // Merging an identical graph changes nothing
+    let identified_graph = proxy.graph().identify(process_tree("explorer.exe"));
+    assert!(proxy.graph().merge(identified_graph).is_empty());
+
+    proxy.shutdown().await;
+    Ok(())
+}
+
+#[test]
+fn test_query_graph_from_uid_matches_from_the_root() {
+    let mut graph = InMemoryGraph::new().with_reverse_edge_name("children", "parent");
+    let identified_graph = graph.identify(process_tree("WINWORD.EXE"));
+    graph.merge(identified_graph);
+    let parent_uid = graph.node_uid("parent").expect("parent was identified");
+    let child_uid = graph.node_uid("child").expect("child was identified");
+
+    let mut query = ProcessQuery::new();
+    query.with_process_name(vec![StringCmp::eq("cmd.exe", false)]);
+    let graph_query = query.build();
+
+    assert!(graph
+        .query_graph_from_uid(&graph_query, child_uid)
+        .unwrap()
+        .is_some());
+    assert!(graph
+        .query_graph_from_uid(&graph_query, parent_uid)
+        .unwrap()
+        .is_none());
+    assert!(graph
+        .query_graph_from_uid(&graph_query, Uid::from_u64(1000).unwrap())
+        .unwrap()
+        .is_none());
+}
+
+#[test]
+fn test_int_filters_match_any_alternative() {
+    let mut graph = InMemoryGraph::new().with_reverse_edge_name("children", "parent");
+    let mut process_graph = process_tree("WINWORD.EXE");
+    for node in process_graph.nodes.values_mut() {
+        if node.node_key == "child" {
+            node.set_property("process_id", ImmutableUintProp { prop: 4 });
+        }
+    }
+    let identified_graph = graph.identify(process_graph);
+    graph.merge(identified_graph);
+    let parent_uid = graph.node_uid("parent").expect("parent was identified");
+    let child_uid = graph.node_uid("child").expect("child was identified");
+
+    let matches = |uid: Uid, init_query: fn(&mut ProcessQuery)| {
+        let mut query = ProcessQuery::new();
+        init_query(&mut query);
+        graph
+            .query_graph_from_uid(&query.build(), uid)
+            .unwrap()
+            .is_some()
+    };
+
+    assert!(matches(child_uid, |query| {
+        query.with_process_id(vec![IntCmp::Lt(10, false)]);
+    }));
+    assert!(!matches(child_uid, |query| {
+        query.with_process_id(vec![IntCmp::Gt(1, false), IntCmp::Eq(4, true)]);
+    }));
+    assert!(matches(child_uid, |query| {
+        query
+            .with_process_id(vec![IntCmp::Gt(100, false)])
+            .with_process_id(vec![IntCmp::Eq(4, false)]);
+    }));
+    // A property that isn't set matches nothing, not even a negated filter
+    assert!(!matches(parent_uid, |query| {
+        query.with_process_id(vec![IntCmp::Eq(4, true)]);
+    }));
+}
+
+#[test]
+fn test_bool_float_bytes_and_timestamp_filters() {
+    let mut graph = InMemoryGraph::new();
+    let mut file_graph = GraphDescription::new();
+    let mut file = NodeDescription {
+        properties: Default::default(),
+        node_key: "file".to_owned(),
+        node_type: "File".to_owned(),
+        id_strategy: vec![],
+    };
+    file.set_property(
+        "sha256",
+        ImmutableBytesProp {
+            prop: vec![0xde, 0xad, 0xbe, 0xef].into(),
+        },
+    );
+    file.set_property("is_signed", ImmutableBoolProp { prop: true });
+    file.set_property("entropy", ImmutableDoubleProp { prop: 7.25 });
+    file.set_property(
+        "created_at",
+        ImmutableTimestampProp {
+            prop: UNIX_EPOCH + Duration::from_millis(1_500),
+        },
+    );
+    file_graph.add_node(file);
+    let identified_graph = graph.identify(file_graph);
+    graph.merge(identified_graph);
+    let file_uid = graph.node_uid("file").expect("file was identified");
+
+    let matches = |init_query: fn(&mut NodeQueryBuilder)| {
+        let mut query = NodeQueryBuilder::new(NodeType::try_from("File").unwrap());
+        init_query(&mut query);
+        graph
+            .query_graph_from_uid(&query.build(), file_uid)
+            .unwrap()
+            .is_some()
+    };
+    fn property_name(name: &'static str) -> PropertyName {
+        PropertyName::try_from(name).unwrap()
+    }
+
+    assert!(matches(|query| {
+        query.with_bytes(property_name("sha256"), vec![0xde, 0xad, 0xbe, 0xef]);
+    }));
+    assert!(!matches(|query| {
+        query.with_bytes(property_name("sha256"), vec![0xde, 0xad]);
+    }));
+    assert!(matches(|query| {
+        query.with_bool(property_name("is_signed"), true);
+    }));
+    assert!(!matches(|query| {
+        query.with_bool(property_name("is_signed"), false);
+    }));
+    assert!(matches(|query| {
+        query.with_float_filters(
+            property_name("entropy"),
+            vec![FloatCmp::Gt(7.0, false), FloatCmp::Lt(8.0, false)],
+        );
+    }));
+    assert!(!matches(|query| {
+        query.with_float_filters(property_name("entropy"), vec![FloatCmp::Eq(7.25, true)]);
+    }));
+    // Timestamps are compared in milliseconds since the Unix epoch
+    assert!(matches(|query| {
+        query.with_timestamp_filters(property_name("created_at"), vec![IntCmp::Eq(1_500, false)]);
+    }));
+    assert!(!matches(|query| {
+        query.with_timestamp_filters(property_name("created_at"), vec![IntCmp::Lt(1_000, false)]);
+    }));
+    // A property that isn't set matches nothing
+    assert!(!matches(|query| {
+        query.with_bool(property_name("is_hidden"), false);
+    }));
+}
+
+#[test]
+fn test_regex_filters() {
+    let mut graph = InMemoryGraph::new().with_reverse_edge_name("children", "parent");
+    let identified_graph = graph.identify(process_tree("WINWORD.EXE"));
+    graph.merge(identified_graph);
+    let child_uid = graph.node_uid("child").expect("child was identified");
+
+    let query_graph = |comparisons: Vec<StringCmp>| {
+        let mut query = ProcessQuery::new();
+        query.with_process_name(comparisons);
+        graph.query_graph_from_uid(&query.build(), child_uid)
+    };
+
+    assert!(
+        query_graph(vec![StringCmp::Regex("^c.d\\.exe$".to_owned(), false)])
+            .unwrap()
+            .is_some()
+    );
+    assert!(
+        query_graph(vec![StringCmp::Regex("(?i)^CMD".to_owned(), true)])
+            .unwrap()
+            .is_none()
+    );
+    assert!(query_graph(vec![
+        StringCmp::Regex("\\.exe$".to_owned(), false),
+        StringCmp::Regex("^powershell".to_owned(), true),
+    ])
+    .unwrap()
+    .is_some());
+
+    // Patterns that don't compile, or that compile to too large a program,
+    // fail the query rather than matching nothing
+    assert!(query_graph(vec![StringCmp::Regex("(cmd".to_owned(), false)]).is_err());
+    assert!(query_graph(vec![StringCmp::Regex("\\w{1000}{1000}".to_owned(), false)]).is_err());
+}
+
+#[test]
+fn test_uid_filters_pin_nodes() {
+    let mut graph = InMemoryGraph::new().with_reverse_edge_name("children", "parent");
+    let mut process_graph = process_tree("WINWORD.EXE");
+    process_graph.add_node(process("sibling", "cmd.exe"));
+    process_graph.add_edge("children", "parent", "sibling");
+    let identified_graph = graph.identify(process_graph);
+    graph.merge(identified_graph);
+    let parent_uid = graph.node_uid("parent").expect("parent was identified");
+    let child_uid = graph.node_uid("child").expect("child was identified");
+    let sibling_uid = graph.node_uid("sibling").expect("sibling was identified");
+
+    // This exact parent, with this exact child named cmd.exe. The other child
+    // comes first among the parent's children, and is skipped over
+    let mut query = ProcessQuery::new();
+    query.with_uid(parent_uid).with_children(|child| {
+        child
+            .with_uid(sibling_uid)
+            .with_process_name(vec![StringCmp::eq("cmd.exe", false)]);
+    });
+    let graph_query = query.build();
+
+    let (matched_graph, root_uid) = graph
+        .query_graph_with_uid(&graph_query, parent_uid)
+        .unwrap()
+        .expect("the pinned child is among the parent's children");
+    assert_eq!(root_uid, parent_uid);
+    assert!(matched_graph.nodes.contains_key(&sibling_uid));
+    assert!(!matched_graph.nodes.contains_key(&child_uid));
+
+    assert!(graph
+        .query_graph_with_uid(&graph_query, sibling_uid)
+        .unwrap()
+        .is_some());
+    assert!(graph
+        .query_graph_with_uid(&graph_query, child_uid)
+        .unwrap()
+        .is_none());
+
+    let mut query = ProcessQuery::new();
+    query
+        .with_uid(sibling_uid)
+        .with_process_name(vec![StringCmp::eq("cmd.exe", false)]);
+    assert!(graph
+        .query_graph_from_uid(&query.build(), child_uid)
+        .unwrap()
+        .is_none());
+}
+
+#[test]
+fn test_path_filters_follow_edges_for_several_hops() {
+    let mut graph = InMemoryGraph::new().with_reverse_edge_name("children", "parent");
+    let mut process_graph = GraphDescription::new();
+    process_graph.add_node(process("winword", "WINWORD.EXE"));
+    process_graph.add_node(process("explorer", "explorer.exe"));
+    process_graph.add_node(process("cmd", "cmd.exe"));
+    process_graph.add_node(process("powershell", "powershell.exe"));
+    process_graph.add_edge("children", "winword", "explorer");
+    process_graph.add_edge("children", "explorer", "cmd");
+    process_graph.add_edge("children", "cmd", "powershell");
+    // A cycle, which the search has to stop at
+    process_graph.add_edge("children", "powershell", "winword");
+    let identified_graph = graph.identify(process_graph);
+    graph.merge(identified_graph);
+    let winword_uid = graph.node_uid("winword").expect("winword was identified");
+    let explorer_uid = graph.node_uid("explorer").expect("explorer was identified");
+    let cmd_uid = graph.node_uid("cmd").expect("cmd was identified");
+    let powershell_uid = graph
+        .node_uid("powershell")
+        .expect("powershell was identified");
+
+    // Any descendant named powershell.exe. Its ancestors that aren't are
+    // passed through rather than short circuiting the search
+    let mut query = ProcessQuery::new();
+    query
+        .with_process_name(vec![StringCmp::eq("WINWORD.EXE", false)])
+        .with_children_path(
+            1..=5,
+            |_| {},
+            |descendant| {
+                descendant.with_process_name(vec![StringCmp::eq("powershell.exe", false)]);
+            },
+        );
+    let graph_query = query.build();
+
+    let (matched_graph, root_uid) = graph
+        .query_graph_with_uid(&graph_query, winword_uid)
+        .unwrap()
+        .expect("powershell.exe is three hops down");
+    assert_eq!(root_uid, winword_uid);
+    for uid in [winword_uid, explorer_uid, cmd_uid, powershell_uid] {
+        assert!(matched_graph.nodes.contains_key(&uid));
+    }
+    let children = EdgeName {
+        value: "children".to_owned(),
+    };
+    assert!(matched_graph.edges[&(explorer_uid, children.clone())].contains(&cmd_uid));
+    assert!(matched_graph.edges[&(cmd_uid, children)].contains(&powershell_uid));
+
+    // The same path, walked up from its other end
+    let (_, root_uid) = graph
+        .query_graph_with_uid(&graph_query, powershell_uid)
+        .unwrap()
+        .expect("WINWORD.EXE is three hops up");
+    assert_eq!(root_uid, winword_uid);
+
+    // Searches the whole cycle, and stops back at WINWORD.EXE
+    let mut query = ProcessQuery::new();
+    query.with_children_path(
+        1..=10,
+        |_| {},
+        |descendant| {
+            descendant.with_process_name(vec![StringCmp::eq("notepad.exe", false)]);
+        },
+    );
+    assert!(graph
+        .query_graph_with_uid(&query.build(), winword_uid)
+        .unwrap()
+        .is_none());
+
+    let mut query = ProcessQuery::new();
+    query
+        .with_process_name(vec![StringCmp::eq("WINWORD.EXE", false)])
+        .with_children_path(
+            1..=2,
+            |_| {},
+            |descendant| {
+                descendant.with_process_name(vec![StringCmp::eq("powershell.exe", false)]);
+            },
+        );
+    assert!(graph
+        .query_graph_with_uid(&query.build(), winword_uid)
+        .unwrap()
+        .is_none());
+
+    let mut query = ProcessQuery::new();
+    query
+        .with_process_name(vec![StringCmp::eq("powershell.exe", false)])
+        .with_parent_path(
+            2..=5,
+            |ancestor| {
+                ancestor.with_process_name(vec![StringCmp::eq("explorer.exe", true)]);
+            },
+            |ancestor| {
+                ancestor.with_process_name(vec![StringCmp::eq("WINWORD.EXE", false)]);
+            },
+        );
+    assert!(
+        graph
+            .query_graph_with_uid(&query.build(), powershell_uid)
+            .unwrap()
+            .is_none(),
+        "the path to WINWORD.EXE passes through explorer.exe"
+    );
+}
+
+#[test]
+fn test_search_nodes_pages_through_indexed_nodes() {
+    let mut graph = InMemoryGraph::new().with_reverse_edge_name("children", "parent");
+    let mut process_graph = GraphDescription::new();
+    process_graph.add_node(process("winword", "WINWORD.EXE"));
+    process_graph.add_node(process("explorer", "explorer.exe"));
+    process_graph.add_node(process("cmd_1", "cmd.exe"));
+    process_graph.add_node(process("cmd_2", "cmd.exe"));
+    process_graph.add_node(process("cmd_3", "cmd.exe"));
+    process_graph.add_edge("children", "winword", "cmd_1");
+    process_graph.add_edge("children", "explorer", "cmd_2");
+    process_graph.add_edge("children", "winword", "cmd_3");
+    let identified_graph = graph.identify(process_graph);
+    graph.merge(identified_graph);
+    let mut cmd_uids: Vec<Uid> = ["cmd_1", "cmd_2", "cmd_3"]
+        .into_iter()
+        .map(|node_key| graph.node_uid(node_key).expect("cmd.exe was identified"))
+        .collect();
+    cmd_uids.sort_by_key(|uid| uid.as_i64());
+
+    let mut request = SearchNodesRequest {
+        tenant_id: uuid::Uuid::new_v4(),
+        node_type: NodeType::try_from("Process").unwrap(),
+        property_name: PropertyName::try_from("process_name").unwrap(),
+        value: SearchValue::String("cmd.exe".to_owned()),
+        page_size: Some(2),
+        page_token: None,
+        graph_query: None,
+        explain: false,
+    };
+
+    let first_page = graph.search_nodes(&request).unwrap();
+    let first_uids: Vec<Uid> = first_page.matches.iter().map(|m| m.uid).collect();
+    assert_eq!(first_uids, cmd_uids[..2]);
+    assert_eq!(first_page.next_page_token, Some(cmd_uids[1]));
+
+    request.page_token = first_page.next_page_token;
+    let second_page = graph.search_nodes(&request).unwrap();
+    let second_uids: Vec<Uid> = second_page.matches.iter().map(|m| m.uid).collect();
+    assert_eq!(second_uids, cmd_uids[2..]);
+    assert_eq!(second_page.next_page_token, None);
+
+    // Expanded through a query, only the children of WINWORD.EXE are left
+    let mut query = ProcessQuery::new();
+    query
+        .with_process_name(vec![StringCmp::eq("cmd.exe", false)])
+        .with_parent(|parent| {
+            parent.with_process_name(vec![StringCmp::eq("WINWORD.EXE", false)]);
+        });
+    request.page_size = None;
+    request.page_token = None;
+    request.graph_query = Some(query.build());
+    let expanded = graph.search_nodes(&request).unwrap();
+    let mut expanded_uids: Vec<Uid> = expanded.matches.iter().map(|m| m.uid).collect();
+    expanded_uids.sort_by_key(|uid| uid.as_i64());
+    let mut winword_children = vec![
+        graph.node_uid("cmd_1").unwrap(),
+        graph.node_uid("cmd_3").unwrap(),
+    ];
+    winword_children.sort_by_key(|uid| uid.as_i64());
+    assert_eq!(expanded_uids, winword_children);
+    assert!(expanded
+        .matches
+        .iter()
+        .all(|m| m.matched_graph.as_ref().unwrap().nodes.len() == 2));
+
+    // Only immutable properties of the requested node type are indexed
+    request.node_type = NodeType::try_from("File").unwrap();
+    request.graph_query = None;
+    assert!(graph.search_nodes(&request).unwrap().matches.is_empty());
+}
+
+#[test]
+fn test_edge_count_filters_and_aggregation() {
+    let mut graph = InMemoryGraph::new().with_reverse_edge_name("children", "parent");
+    let mut process_graph = GraphDescription::new();
+    process_graph.add_node(process("winword", "WINWORD.EXE"));
+    process_graph.add_node(process("explorer", "explorer.exe"));
+    for i in 0..3 {
+        let node_key = format!("cmd_{i}");
+        process_graph.add_node(process(&node_key, "cmd.exe"));
+        process_graph.add_edge("children", "winword", node_key);
+    }
+    process_graph.add_node(process("notepad", "notepad.exe"));
+    process_graph.add_edge("children", "explorer", "notepad");
+    let identified_graph = graph.identify(process_graph);
+    graph.merge(identified_graph);
+    let winword_uid = graph.node_uid("winword").expect("winword was identified");
+    let explorer_uid = graph.node_uid("explorer").expect("explorer was identified");
+    let cmd_uid = graph.node_uid("cmd_0").expect("cmd.exe was identified");
+
+    let matches = |uid: Uid, init_query: fn(&mut ProcessQuery)| {
+        let mut query = ProcessQuery::new();
+        init_query(&mut query);
+        graph
+            .query_graph_from_uid(&query.build(), uid)
+            .unwrap()
+            .is_some()
+    };
+
+    assert!(matches(winword_uid, |query| {
+        query.with_children_count(vec![IntCmp::Gt(2, false)]);
+    }));
+    assert!(!matches(explorer_uid, |query| {
+        query.with_children_count(vec![IntCmp::Gt(2, false)]);
+    }));
+    // A node without any of the edges has a count of 0
+    assert!(matches(cmd_uid, |query| {
+        query.with_children_count(vec![IntCmp::Eq(0, false)]);
+    }));
+    // A count constraint on a neighbor fails the whole query
+    assert!(!matches(cmd_uid, |query| {
+        query.with_parent(|parent| {
+            parent.with_children_count(vec![IntCmp::Lt(3, false)]);
+        });
+    }));
+
+    let mut query = ProcessQuery::new();
+    query.with_process_name(vec![StringCmp::eq("WINWORD.EXE", false)]);
+    let children = EdgeName::try_from("children").unwrap();
+    let parent = EdgeName::try_from("parent").unwrap();
+
+    let edge_counts = graph
+        .aggregate_graph_from_uid(&query.build(), winword_uid, &[])
+        .unwrap()
+        .expect("WINWORD.EXE matched");
+    assert_eq!(edge_counts.nodes.len(), 1);
+    let winword_counts = edge_counts.node(winword_uid).unwrap();
+    assert_eq!(winword_counts.count(&children), 3);
+    assert_eq!(winword_counts.count(&parent), 0);
+
+    // With a neighbor in the query, every matched node is counted
+    query.with_children(|_| {});
+    let edge_counts = graph
+        .aggregate_graph_from_uid(&query.build(), winword_uid, &[parent.clone()])
+        .unwrap()
+        .expect("WINWORD.EXE matched");
+    assert_eq!(edge_counts.nodes.len(), 4);
+    assert!(edge_counts
+        .node(winword_uid)
+        .unwrap()
+        .edge_counts
+        .is_empty());
+    assert_eq!(edge_counts.node(cmd_uid).unwrap().count(&parent), 1);
+
+    assert!(graph
+        .aggregate_graph_from_uid(&query.build(), explorer_uid, &[])
+        .unwrap()
+        .is_none());
+}
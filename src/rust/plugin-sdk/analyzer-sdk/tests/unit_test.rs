@@ -0,0 +1,184 @@
+use std::time::Duration;
+
+use analyzer_sdk::test_utils::test_ctx::AnalyzerTestContext;
+use rust_proto::graplinc::grapl::{
+    api::{
+        plugin_sdk::analyzers::v1beta1::{
+            messages::{
+                AnalyzerName,
+                ExecutionHit,
+                ExecutionMiss,
+                ExecutionResult,
+                RunAnalyzerRequest,
+                RunAnalyzerResponse,
+                StringPropertyUpdate,
+                Update,
+            },
+            server::AnalyzerApi,
+        },
+        protocol::{
+            healthcheck::{
+                HealthcheckError,
+                HealthcheckStatus,
+            },
+            status::Status,
+            test_utils::ServerProbe,
+        },
+    },
+    common::v1beta1::types::{
+        PropertyName,
+        Uid,
+    },
+};
+use test_context::test_context;
+
+/// Hits on every update to a `command_line` property.
+struct CommandLineAnalyzer {}
+
+#[async_trait::async_trait]
+impl AnalyzerApi for CommandLineAnalyzer {
+    type Error = Status;
+
+    async fn run_analyzer(
+        &self,
+        request: RunAnalyzerRequest,
+    ) -> Result<RunAnalyzerResponse, Self::Error> {
+        let execution_result = match request.update() {
+            Update::StringProperty(update) if update.property_name.value == "command_line" => {
+                ExecutionResult::ExecutionHit(ExecutionHit {
+                    analyzer_name: AnalyzerName {
+                        value: "command-line-analyzer".to_owned(),
+                    },
+                    idempotency_key: update.uid.as_u64(),
+                    ..Default::default()
+                })
+            }
+            _ => ExecutionResult::ExecutionMiss(ExecutionMiss {}),
+        };
+
+        Ok(RunAnalyzerResponse { execution_result })
+    }
+}
+
+fn string_property_update(property_name: &str) -> RunAnalyzerRequest {
+    RunAnalyzerRequest::new(Update::StringProperty(StringPropertyUpdate {
+        uid: Uid::from_u64(42).expect("invalid uid"),
+        property_name: PropertyName::new_unchecked(property_name.to_owned()),
+    }))
+}
+
+#[test_context(AnalyzerTestContext)]
+#[tokio::test]
+async fn test_analyzer_hits_and_misses(ctx: &mut AnalyzerTestContext) -> eyre::Result<()> {
+    let mut client = ctx.get_client(CommandLineAnalyzer {}).await;
+
+    let response = client
+        .run_analyzer(string_property_update("command_line"), None)
+        .await?;
+    match response.execution_result {
+        ExecutionResult::ExecutionHit(hit) => {
+            assert_eq!(hit.analyzer_name.value, "command-line-analyzer");
+            assert_eq!(hit.idempotency_key, 42);
+        }
+        ExecutionResult::ExecutionMiss(_) => panic!("expected a hit"),
+    }
+
+    let response = client
+        .run_analyzer(string_property_update("process_name"), None)
+        .await?;
+    assert!(matches!(
+        response.execution_result,
+        ExecutionResult::ExecutionMiss(_)
+    ));
+
+    Ok(())
+}
+
+/// Answers like `CommandLineAnalyzer`, but reports its health and holds its
+/// requests through a `ServerProbe`.
+struct ProbedAnalyzer {
+    probe: ServerProbe,
+}
+
+#[async_trait::async_trait]
+impl AnalyzerApi for ProbedAnalyzer {
+    type Error = Status;
+
+    async fn run_analyzer(
+        &self,
+        request: RunAnalyzerRequest,
+    ) -> Result<RunAnalyzerResponse, Self::Error> {
+        self.probe.handle_request().await;
+        CommandLineAnalyzer {}.run_analyzer(request).await
+    }
+
+    async fn health(&self) -> Result<HealthcheckStatus, HealthcheckError> {
+        Ok(self.probe.health())
+    }
+}
+
+#[test_context(AnalyzerTestContext)]
+#[tokio::test]
+async fn test_health_hook_is_polled(ctx: &mut AnalyzerTestContext) -> eyre::Result<()> {
+    let probe = ServerProbe::new(2);
+    probe.release_requests();
+    let mut client = ctx
+        .get_client(ProbedAnalyzer {
+            probe: probe.clone(),
+        })
+        .await;
+
+    // The server keeps polling health after the analyzer first reports "not
+    // serving".
+    probe.wait_for_health_checks(3).await;
+
+    let response = client
+        .run_analyzer(string_property_update("command_line"), None)
+        .await?;
+    assert!(matches!(
+        response.execution_result,
+        ExecutionResult::ExecutionHit(_)
+    ));
+
+    Ok(())
+}
+
+#[test_context(AnalyzerTestContext)]
+#[tokio::test]
+async fn test_shutdown_drains_in_flight_requests(
+    ctx: &mut AnalyzerTestContext,
+) -> eyre::Result<()> {
+    let probe = ServerProbe::new(0);
+    let mut client = ctx
+        .get_client(ProbedAnalyzer {
+            probe: probe.clone(),
+        })
+        .await;
+
+    let in_flight = tokio::task::spawn(async move {
+        client
+            .run_analyzer(string_property_update("command_line"), None)
+            .await
+    });
+    probe.wait_for_requests(1).await;
+
+    // The server can't stop while the request is held in flight.
+    let shutdown = ctx.shutdown();
+    tokio::pin!(shutdown);
+    assert!(
+        tokio::time::timeout(Duration::from_millis(100), &mut shutdown)
+            .await
+            .is_err()
+    );
+
+    probe.release_requests();
+    shutdown.await?;
+
+    let response = in_flight.await??;
+    assert!(matches!(
+        response.execution_result,
+        ExecutionResult::ExecutionHit(_)
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,132 @@
+//! Describes generated graphs independently of their node keys, which are
+//! random, so that the output of two runs of a generator can be compared.
+//! Shared by the local runner's JSON output and the snapshot tests.
+use std::{
+    collections::{
+        BTreeMap,
+        HashMap,
+    },
+    fmt::Write,
+};
+
+use rust_proto::graplinc::grapl::api::graph::v1beta1::{
+    GraphDescription,
+    NodeDescription,
+    Property,
+};
+
+/// Labels every node of a graph `<node type>#<n>`, returning `(node key,
+/// label)` pairs in label order. Nodes are ordered by their type, identity and
+/// properties, and nodes which are otherwise identical by the nodes they're
+/// connected to, so that the labels don't depend on the node keys.
+pub(crate) fn label_nodes(graph: &GraphDescription) -> Vec<(&str, String)> {
+    let nodes: HashMap<&str, String> = graph
+        .nodes
+        .iter()
+        .map(|(node_key, node)| (node_key.as_str(), describe_node(node)))
+        .collect();
+
+    let mut connections: HashMap<&str, Vec<String>> = HashMap::new();
+    for edge in graph.edges.values().flat_map(|edge_list| &edge_list.edges) {
+        let describe = |node_key: &str| nodes.get(node_key).cloned().unwrap_or_default();
+        connections
+            .entry(edge.from_node_key.as_str())
+            .or_default()
+            .push(format!(
+                "-{}-> {}",
+                edge.edge_name,
+                describe(&edge.to_node_key)
+            ));
+        connections
+            .entry(edge.to_node_key.as_str())
+            .or_default()
+            .push(format!(
+                "<-{}- {}",
+                edge.edge_name,
+                describe(&edge.from_node_key)
+            ));
+    }
+
+    let mut ordered: Vec<(&str, &String, Vec<String>)> = nodes
+        .iter()
+        .map(|(node_key, description)| {
+            let mut connections = connections.remove(node_key).unwrap_or_default();
+            connections.sort();
+            (*node_key, description, connections)
+        })
+        .collect();
+    ordered.sort_by(|a, b| (a.1, &a.2).cmp(&(b.1, &b.2)));
+
+    let mut counters: BTreeMap<&str, usize> = BTreeMap::new();
+    ordered
+        .into_iter()
+        .map(|(node_key, _, _)| {
+            let node_type = graph.nodes[node_key].node_type.as_str();
+            let counter = counters.entry(node_type).or_default();
+            let label = format!("{node_type}#{counter}");
+            *counter += 1;
+            (node_key, label)
+        })
+        .collect()
+}
+
+/// The node's type, followed by a line per identity strategy and property.
+pub(crate) fn describe_node(node: &NodeDescription) -> String {
+    let mut description = node.node_type.clone();
+
+    for id_strategy in &node.id_strategy {
+        write!(description, "\nid_strategy: {:?}", id_strategy.strategy).expect("write to string");
+    }
+
+    let properties: BTreeMap<&str, String> = node
+        .properties
+        .iter()
+        .map(|(name, property)| (name.as_str(), describe_property(&property.property)))
+        .collect();
+    for (name, property) in properties {
+        write!(description, "\n{name}: {property}").expect("write to string");
+    }
+
+    description
+}
+
+/// A property as `<type> <value>`, e.g. `immutable_str "/etc/passwd"`.
+pub(crate) fn describe_property(property: &Property) -> String {
+    let (property_type, value) = typed_value(property);
+    format!("{property_type} {value}")
+}
+
+/// A property as `{"type": <type>, "value": <value>}`.
+pub(crate) fn property_to_json(property: &Property) -> serde_json::Value {
+    let (property_type, value) = typed_value(property);
+    serde_json::json!({
+        "type": property_type,
+        "value": value,
+    })
+}
+
+fn typed_value(property: &Property) -> (&'static str, serde_json::Value) {
+    match property {
+        Property::IncrementOnlyUintProp(p) => ("increment_only_uint", p.as_inner().into()),
+        Property::DecrementOnlyUintProp(p) => ("decrement_only_uint", p.as_inner().into()),
+        Property::ImmutableUintProp(p) => ("immutable_uint", p.as_inner().into()),
+        Property::IncrementOnlyIntProp(p) => ("increment_only_int", p.as_inner().into()),
+        Property::DecrementOnlyIntProp(p) => ("decrement_only_int", p.as_inner().into()),
+        Property::ImmutableIntProp(p) => ("immutable_int", p.as_inner().into()),
+        Property::ImmutableStrProp(p) => ("immutable_str", p.as_inner().into()),
+        Property::ImmutableBoolProp(p) => ("immutable_bool", p.as_inner().into()),
+        Property::ImmutableDoubleProp(p) => ("immutable_double", p.as_inner().into()),
+        Property::ImmutableBytesProp(p) => ("immutable_bytes", p.to_string().into()),
+        Property::ImmutableTimestampProp(p) => ("immutable_timestamp", p.to_string().into()),
+        Property::IncrementOnlyTimestampProp(p) => {
+            ("increment_only_timestamp", p.to_string().into())
+        }
+        Property::DecrementOnlyTimestampProp(p) => {
+            ("decrement_only_timestamp", p.to_string().into())
+        }
+        Property::StringSetProp(p) => (
+            "string_set",
+            p.as_inner().iter().map(String::as_str).collect(),
+        ),
+    }
+}
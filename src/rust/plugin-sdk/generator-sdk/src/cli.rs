@@ -0,0 +1,398 @@
+//! Runs a generator in-process over local files, without the gRPC server or
+//! any of the pipeline around it, so that plugin authors can iterate on a
+//! generator with nothing but `cargo run`.
+//!
+//! A generator crate can expose this with a second binary:
+//!
+//! ```ignore
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     generator_sdk::cli::run_local(SysmonGenerator {}).await?;
+//!     Ok(())
+//! }
+//! ```
+//!
+//! and be run with e.g.
+//! `cargo run --bin sysmon-generator-local -- --output json events.xml`.
+use std::{
+    collections::{
+        BTreeMap,
+        HashMap,
+    },
+    fmt,
+    io::{
+        Read,
+        Write,
+    },
+    path::PathBuf,
+    str::FromStr,
+};
+
+use clap::Parser;
+use rust_proto::graplinc::grapl::api::{
+    graph::v1beta1::{
+        GraphDescription,
+        Strategy,
+    },
+    plugin_sdk::generators::v1beta1::{
+        server::GeneratorApi,
+        RunGeneratorRequest,
+    },
+    protocol::status::Status,
+};
+
+use crate::canonical;
+
+#[derive(clap::Parser, Debug)]
+#[clap(about = "Runs a generator over local files and prints the graph it generates")]
+pub struct LocalRunConfig {
+    /// Files of raw logs. Each file is passed to the generator as a single
+    /// request. Standard input is read when no files, or `-`, are given.
+    pub inputs: Vec<PathBuf>,
+
+    /// `summary` to print the number of nodes and edges of each type, or
+    /// `json` to print the generated graph
+    #[clap(long, default_value = "summary")]
+    pub output: OutputFormat,
+
+    /// Pass each non-empty line of input to the generator as a separate
+    /// request, for line-delimited logs
+    #[clap(long)]
+    pub per_line: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Summary,
+    Json,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "summary" => Ok(OutputFormat::Summary),
+            "json" => Ok(OutputFormat::Json),
+            other => Err(format!(
+                "unknown output format {other}, expected summary or json"
+            )),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum LocalRunError {
+    #[error("failed to read {input}: {source}")]
+    ReadInput {
+        input: String,
+        source: std::io::Error,
+    },
+
+    #[error("generator failed on {input}: {status}")]
+    Generator { input: String, status: Status },
+
+    #[error("failed to write output: {0}")]
+    WriteOutput(#[from] std::io::Error),
+
+    #[error("failed to serialize graph: {0}")]
+    Serialize(#[from] serde_json::Error),
+}
+
+/// Parses a `LocalRunConfig` from the command line, runs `generator` over
+/// its inputs and prints the merged result to stdout.
+pub async fn run_local(generator: impl GeneratorApi) -> Result<(), LocalRunError> {
+    let config = LocalRunConfig::parse();
+    let graph = generate_local(&generator, &config).await?;
+
+    let mut stdout = std::io::stdout().lock();
+    match config.output {
+        OutputFormat::Summary => write!(stdout, "{}", GraphSummary::from(&graph))?,
+        OutputFormat::Json => {
+            serde_json::to_writer_pretty(&mut stdout, &graph_to_json(&graph))?;
+            writeln!(stdout)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Runs `generator` over the inputs `config` names, merging the graphs it
+/// generates for each request.
+pub async fn generate_local(
+    generator: &impl GeneratorApi,
+    config: &LocalRunConfig,
+) -> Result<GraphDescription, LocalRunError> {
+    let mut graph = GraphDescription::new();
+
+    for (input, data) in read_inputs(&config.inputs)? {
+        let requests = if config.per_line {
+            data.split(|byte| *byte == b'\n')
+                .filter(|line| !line.iter().all(u8::is_ascii_whitespace))
+                .map(|line| line.to_vec())
+                .collect()
+        } else {
+            vec![data]
+        };
+
+        tracing::debug!(
+            message = "running generator",
+            input = %input,
+            request_count = requests.len(),
+        );
+
+        for data in requests {
+            let response = generator
+                .run_generator(RunGeneratorRequest { data: data.into() })
+                .await
+                .map_err(|e| LocalRunError::Generator {
+                    input: input.clone(),
+                    status: e.into(),
+                })?;
+
+            graph.merge(&response.generated_graph.graph_description);
+        }
+    }
+
+    Ok(graph)
+}
+
+/// Reads each input in full, returning it alongside a name for error messages.
+fn read_inputs(inputs: &[PathBuf]) -> Result<Vec<(String, Vec<u8>)>, LocalRunError> {
+    if inputs.is_empty() {
+        return Ok(vec![read_stdin()?]);
+    }
+
+    inputs
+        .iter()
+        .map(|path| {
+            if path.as_os_str() == "-" {
+                return read_stdin();
+            }
+
+            let input = path.display().to_string();
+            match std::fs::read(path) {
+                Ok(data) => Ok((input, data)),
+                Err(source) => Err(LocalRunError::ReadInput { input, source }),
+            }
+        })
+        .collect()
+}
+
+fn read_stdin() -> Result<(String, Vec<u8>), LocalRunError> {
+    let input = "<stdin>".to_owned();
+    let mut data = Vec::new();
+
+    match std::io::stdin().read_to_end(&mut data) {
+        Ok(_) => Ok((input, data)),
+        Err(source) => Err(LocalRunError::ReadInput { input, source }),
+    }
+}
+
+/// The number of nodes of each type and edges of each name in a graph.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct GraphSummary {
+    pub node_counts: BTreeMap<String, usize>,
+    pub edge_counts: BTreeMap<String, usize>,
+}
+
+impl From<&GraphDescription> for GraphSummary {
+    fn from(graph: &GraphDescription) -> Self {
+        let mut summary = GraphSummary::default();
+
+        for node in graph.nodes.values() {
+            *summary
+                .node_counts
+                .entry(node.node_type.clone())
+                .or_default() += 1;
+        }
+
+        for edge in graph.edges.values().flat_map(|edge_list| &edge_list.edges) {
+            *summary
+                .edge_counts
+                .entry(edge.edge_name.clone())
+                .or_default() += 1;
+        }
+
+        summary
+    }
+}
+
+impl fmt::Display for GraphSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let node_count: usize = self.node_counts.values().sum();
+        let edge_count: usize = self.edge_counts.values().sum();
+
+        writeln!(f, "{node_count} nodes")?;
+        for (node_type, count) in &self.node_counts {
+            writeln!(f, "  {node_type}: {count}")?;
+        }
+
+        writeln!(f, "{edge_count} edges")?;
+        for (edge_name, count) in &self.edge_counts {
+            writeln!(f, "  {edge_name}: {count}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Converts a graph to JSON, with nodes and edges sorted so that the output
+/// of two runs can be diffed. Node keys are random, so nodes are keyed by the
+/// same labels snapshot tests use, like `Process#0`.
+pub fn graph_to_json(graph: &GraphDescription) -> serde_json::Value {
+    let labels: HashMap<&str, String> = canonical::label_nodes(graph).into_iter().collect();
+    let label = |node_key: &str| {
+        labels
+            .get(node_key)
+            .cloned()
+            .unwrap_or_else(|| format!("<missing node {node_key}>"))
+    };
+
+    let nodes: BTreeMap<String, serde_json::Value> = graph
+        .nodes
+        .iter()
+        .map(|(node_key, node)| {
+            let properties: BTreeMap<&str, serde_json::Value> = node
+                .properties
+                .iter()
+                .map(|(name, property)| {
+                    (
+                        name.as_str(),
+                        canonical::property_to_json(&property.property),
+                    )
+                })
+                .collect();
+
+            let strategies: Vec<&str> = node
+                .id_strategy
+                .iter()
+                .map(|id_strategy| match id_strategy.strategy {
+                    Strategy::Session(_) => "session",
+                    Strategy::Static(_) => "static",
+                })
+                .collect();
+
+            let node = serde_json::json!({
+                "node_type": node.node_type,
+                "id_strategy": strategies,
+                "properties": properties,
+            });
+
+            (label(node_key), node)
+        })
+        .collect();
+
+    let mut edges: Vec<_> = graph
+        .edges
+        .values()
+        .flat_map(|edge_list| &edge_list.edges)
+        .map(|edge| {
+            (
+                label(&edge.from_node_key),
+                edge.edge_name.as_str(),
+                label(&edge.to_node_key),
+            )
+        })
+        .collect();
+    edges.sort_unstable();
+
+    let edges: Vec<serde_json::Value> = edges
+        .into_iter()
+        .map(|(from, edge_name, to)| {
+            serde_json::json!({
+                "from": from,
+                "edge_name": edge_name,
+                "to": to,
+            })
+        })
+        .collect();
+
+    serde_json::json!({
+        "nodes": nodes,
+        "edges": edges,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_proto::graplinc::grapl::api::{
+        graph::v1beta1::{
+            IdStrategy,
+            ImmutableStrProp,
+            NodeDescription,
+            Static,
+        },
+        plugin_sdk::generators::v1beta1::{
+            GeneratedGraph,
+            RunGeneratorResponse,
+        },
+    };
+
+    use super::*;
+
+    /// Generates a `Line` node, keyed by its contents, for each request.
+    struct LineGenerator {}
+
+    #[async_trait::async_trait]
+    impl GeneratorApi for LineGenerator {
+        type Error = Status;
+
+        async fn run_generator(
+            &self,
+            request: RunGeneratorRequest,
+        ) -> Result<RunGeneratorResponse, Self::Error> {
+            let line = String::from_utf8(request.data.to_vec())
+                .map_err(|e| Status::invalid_argument(e.to_string()))?;
+
+            let mut node = NodeDescription {
+                properties: Default::default(),
+                node_key: line.clone(),
+                node_type: "Line".to_owned(),
+                id_strategy: vec![IdStrategy::from(Static {
+                    primary_key_properties: vec!["line".to_owned()],
+                    primary_key_requires_asset_id: false,
+                })],
+            };
+            node.set_property("line", ImmutableStrProp::from(line.as_str()));
+
+            let mut graph_description = GraphDescription::new();
+            graph_description.add_node(node);
+
+            Ok(RunGeneratorResponse {
+                generated_graph: GeneratedGraph { graph_description },
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn runs_the_generator_per_line() -> Result<(), Box<dyn std::error::Error>> {
+        let mut input = tempfile::NamedTempFile::new()?;
+        input.write_all(b"first\n\nsecond\nfirst\n")?;
+
+        let config = LocalRunConfig::try_parse_from([
+            "generator",
+            "--per-line",
+            "--output",
+            "json",
+            input.path().to_str().expect("non-utf8 path"),
+        ])?;
+        let graph = generate_local(&LineGenerator {}, &config).await?;
+
+        assert_eq!(config.output, OutputFormat::Json);
+        assert_eq!(graph.nodes.len(), 2);
+        assert_eq!(
+            GraphSummary::from(&graph).to_string(),
+            "2 nodes\n  Line: 2\n0 edges\n"
+        );
+
+        // Nodes are ordered by their properties, so "second" is the second
+        // Line regardless of its node key
+        assert_eq!(
+            graph_to_json(&graph)["nodes"]["Line#1"]["properties"]["line"],
+            serde_json::json!({"type": "immutable_str", "value": "second"})
+        );
+
+        Ok(())
+    }
+}
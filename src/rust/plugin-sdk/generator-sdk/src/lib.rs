@@ -1,6 +1,9 @@
+mod canonical;
+pub mod cli;
 pub mod server;
 
 #[cfg(feature = "test_utils")]
 pub mod test_utils {
+    pub mod snapshot;
     pub mod test_ctx;
 }
@@ -1,4 +1,7 @@
-use std::time::Duration;
+use std::{
+    sync::Arc,
+    time::Duration,
+};
 
 use clap::Parser;
 use rust_proto::graplinc::grapl::api::{
@@ -6,7 +9,10 @@ use rust_proto::graplinc::grapl::api::{
         GeneratorApi,
         GeneratorServer,
     },
-    protocol::healthcheck::HealthcheckStatus,
+    protocol::shutdown::{
+        serve_until_signal,
+        shutdown_signal,
+    },
 };
 use tokio::net::TcpListener;
 
@@ -14,6 +20,19 @@ use tokio::net::TcpListener;
 pub struct GeneratorServiceConfig {
     #[clap(long, env = "PLUGIN_BIND_ADDRESS")]
     pub bind_address: std::net::SocketAddr,
+
+    /// How often the generator's health is polled
+    #[clap(
+        long,
+        env = "PLUGIN_HEALTHCHECK_POLLING_INTERVAL_MS",
+        default_value = "5000"
+    )]
+    pub healthcheck_polling_interval_ms: u64,
+
+    /// How long in-flight requests are given to finish once a SIGTERM or
+    /// SIGINT is received, before the service exits regardless
+    #[clap(long, env = "PLUGIN_SHUTDOWN_TIMEOUT_MS", default_value = "30000")]
+    pub shutdown_timeout_ms: u64,
 }
 impl GeneratorServiceConfig {
     /// An alias for clap::parse, so that consumers don't need to
@@ -23,21 +42,39 @@ impl GeneratorServiceConfig {
     }
 }
 
+/// Serves `graph_generator` until the server fails or the process receives a
+/// SIGTERM or SIGINT. On a signal the server stops accepting connections and
+/// waits up to `shutdown_timeout_ms` for in-flight requests to finish.
+///
+/// The healthcheck service reports the result of `GeneratorApi::health`.
 pub async fn exec_service(
     graph_generator: impl GeneratorApi + Send + Sync + 'static,
     config: GeneratorServiceConfig,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let healthcheck_polling_interval_ms = 5000; // TODO: un-hardcode
-    let (server, _shutdown_tx) = GeneratorServer::new(
+    let graph_generator = Arc::new(graph_generator);
+    let healthcheck_generator = graph_generator.clone();
+
+    let (server, shutdown_tx) = GeneratorServer::new(
         graph_generator,
-        TcpListener::bind(config.bind_address.clone()).await?,
-        || async { Ok(HealthcheckStatus::Serving) }, // FIXME: this is garbage
-        Duration::from_millis(healthcheck_polling_interval_ms),
+        TcpListener::bind(config.bind_address).await?,
+        move || {
+            let healthcheck_generator = healthcheck_generator.clone();
+            async move { healthcheck_generator.health().await }
+        },
+        Duration::from_millis(config.healthcheck_polling_interval_ms),
     );
     tracing::info!(
         message = "starting gRPC server",
         socket_address = %config.bind_address,
     );
 
-    Ok(server.serve().await?)
+    serve_until_signal(
+        server.serve(),
+        shutdown_tx,
+        shutdown_signal(),
+        Duration::from_millis(config.shutdown_timeout_ms),
+    )
+    .await?;
+
+    Ok(())
 }
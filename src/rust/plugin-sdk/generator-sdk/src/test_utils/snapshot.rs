@@ -0,0 +1,412 @@
+//! Golden-file tests for generators: each file in a directory of input
+//! fixtures is run through a generator, and the graph it generates is compared
+//! to a checked-in `<fixture name>.snap` file.
+//!
+//! Node keys are random, so snapshots refer to nodes by the labels
+//! `generator_sdk::cli` also uses, like `Process#0`, assigned in the order of
+//! each node's type, identity and properties. Everything else in a snapshot is
+//! sorted, so a snapshot changes only when the generated graph does. A graph
+//! without nodes or edges is written as `(empty graph)`, so that an empty
+//! snapshot file never passes for one.
+//!
+//! Run the tests with `BLESS_GENERATOR_SNAPSHOTS=1` to write the current
+//! output to the snapshot files instead of comparing against them, then review
+//! the changes with `git diff`.
+use std::{
+    collections::HashMap,
+    fmt::Write,
+    path::{
+        Path,
+        PathBuf,
+    },
+};
+
+use rust_proto::graplinc::grapl::api::{
+    graph::v1beta1::GraphDescription,
+    plugin_sdk::generators::v1beta1::{
+        server::GeneratorApi,
+        RunGeneratorRequest,
+    },
+    protocol::status::Status,
+};
+
+use crate::canonical::{
+    describe_node,
+    label_nodes,
+};
+
+pub const BLESS_ENV_VAR: &str = "BLESS_GENERATOR_SNAPSHOTS";
+
+/// The snapshot of a graph without any nodes or edges.
+pub const EMPTY_GRAPH: &str = "(empty graph)\n";
+
+const SNAPSHOT_EXTENSION: &str = "snap";
+
+/// Runs `generator` over every file in `fixtures_dir` and compares the
+/// canonical form of each generated graph to the corresponding snapshot in
+/// `snapshots_dir`. Panics describing every mismatched or missing snapshot,
+/// unless `BLESS_GENERATOR_SNAPSHOTS` is set, in which case the snapshots are
+/// (over)written instead.
+pub async fn assert_snapshots(
+    generator: &impl GeneratorApi,
+    fixtures_dir: impl AsRef<Path>,
+    snapshots_dir: impl AsRef<Path>,
+) {
+    let bless = std::env::var_os(BLESS_ENV_VAR).is_some();
+    let failures = check_snapshots(
+        generator,
+        fixtures_dir.as_ref(),
+        snapshots_dir.as_ref(),
+        bless,
+    )
+    .await;
+
+    assert!(
+        failures.is_empty(),
+        "{}\n\nIf these changes are expected, re-run with {BLESS_ENV_VAR}=1 to update the snapshots.",
+        failures.join("\n\n"),
+    );
+}
+
+/// Compares, or with `bless` writes, the snapshot of each fixture, returning a
+/// description of each mismatched or missing snapshot.
+async fn check_snapshots(
+    generator: &impl GeneratorApi,
+    fixtures_dir: &Path,
+    snapshots_dir: &Path,
+    bless: bool,
+) -> Vec<String> {
+    let mut fixtures: Vec<PathBuf> = std::fs::read_dir(fixtures_dir)
+        .unwrap_or_else(|e| panic!("failed to read {}: {e}", fixtures_dir.display()))
+        .map(|entry| entry.expect("failed to read fixture").path())
+        .filter(|path| path.is_file())
+        .collect();
+    fixtures.sort();
+
+    assert!(
+        !fixtures.is_empty(),
+        "no fixtures in {}",
+        fixtures_dir.display()
+    );
+
+    if bless {
+        std::fs::create_dir_all(snapshots_dir)
+            .unwrap_or_else(|e| panic!("failed to create {}: {e}", snapshots_dir.display()));
+    }
+
+    let mut failures = Vec::new();
+
+    for fixture in fixtures {
+        let data = std::fs::read(&fixture)
+            .unwrap_or_else(|e| panic!("failed to read {}: {e}", fixture.display()));
+        let actual = match generate_snapshot(generator, data).await {
+            Ok(actual) => actual,
+            Err(status) => {
+                failures.push(format!("{}: generator failed: {status}", fixture.display()));
+                continue;
+            }
+        };
+
+        let file_name = fixture.file_name().expect("fixture has no file name");
+        let snapshot_path = snapshots_dir.join(format!(
+            "{}.{SNAPSHOT_EXTENSION}",
+            file_name.to_string_lossy()
+        ));
+
+        if bless {
+            std::fs::write(&snapshot_path, &actual)
+                .unwrap_or_else(|e| panic!("failed to write {}: {e}", snapshot_path.display()));
+            continue;
+        }
+
+        match std::fs::read_to_string(&snapshot_path) {
+            Ok(expected) if expected == actual => {}
+            Ok(expected) => failures.push(format!(
+                "{} does not match:\n{}",
+                snapshot_path.display(),
+                describe_difference(&expected, &actual),
+            )),
+            Err(e) => failures.push(format!(
+                "{} could not be read ({e}); the generated graph was:\n{actual}",
+                snapshot_path.display(),
+            )),
+        }
+    }
+
+    failures
+}
+
+/// Runs `generator` on `data` and returns the canonical form of the generated
+/// graph.
+pub async fn generate_snapshot(
+    generator: &impl GeneratorApi,
+    data: Vec<u8>,
+) -> Result<String, Status> {
+    let response = generator
+        .run_generator(RunGeneratorRequest { data: data.into() })
+        .await
+        .map_err(Into::into)?;
+
+    Ok(canonical_graph(&response.generated_graph.graph_description))
+}
+
+/// Serializes a graph independently of its node keys and of the iteration
+/// order of its maps.
+pub fn canonical_graph(graph: &GraphDescription) -> String {
+    if graph.nodes.is_empty() && graph.edges.is_empty() {
+        return EMPTY_GRAPH.to_owned();
+    }
+
+    let labels = label_nodes(graph);
+    let mut output = String::new();
+
+    for (node_key, label) in &labels {
+        writeln!(output, "{label}").expect("write to string");
+        for line in describe_node(&graph.nodes[*node_key]).lines().skip(1) {
+            writeln!(output, "  {line}").expect("write to string");
+        }
+        output.push('\n');
+    }
+
+    let labels: HashMap<&str, String> = labels.into_iter().collect();
+    let mut edges: Vec<String> = graph
+        .edges
+        .values()
+        .flat_map(|edge_list| &edge_list.edges)
+        .map(|edge| {
+            let label = |node_key: &str| {
+                labels
+                    .get(node_key)
+                    .cloned()
+                    .unwrap_or_else(|| format!("<missing node {node_key}>"))
+            };
+            format!(
+                "{} -[{}]-> {}",
+                label(&edge.from_node_key),
+                edge.edge_name,
+                label(&edge.to_node_key)
+            )
+        })
+        .collect();
+    edges.sort();
+
+    for edge in edges {
+        writeln!(output, "{edge}").expect("write to string");
+    }
+
+    output
+}
+
+/// Points out the first line at which two snapshots differ.
+fn describe_difference(expected: &str, actual: &str) -> String {
+    let mut expected_lines = expected.lines();
+    let mut actual_lines = actual.lines();
+    let mut line_number = 1;
+
+    loop {
+        match (expected_lines.next(), actual_lines.next()) {
+            (Some(e), Some(a)) if e == a => line_number += 1,
+            (None, None) => return "(the snapshots differ only in line endings)".to_owned(),
+            (e, a) => {
+                return format!(
+                    "first difference at line {line_number}\n  expected: {}\n  actual:   {}\n\nthe generated graph was:\n{actual}",
+                    e.unwrap_or("<end of snapshot>"),
+                    a.unwrap_or("<end of output>"),
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_proto::graplinc::grapl::api::{
+        graph::v1beta1::{
+            IdStrategy,
+            ImmutableStrProp,
+            ImmutableUintProp,
+            NodeDescription,
+            Static,
+        },
+        plugin_sdk::generators::v1beta1::{
+            GeneratedGraph,
+            RunGeneratorResponse,
+        },
+    };
+
+    use super::*;
+
+    /// Generates a `Line` node for each line of the request.
+    struct LineGenerator {}
+
+    #[async_trait::async_trait]
+    impl GeneratorApi for LineGenerator {
+        type Error = Status;
+
+        async fn run_generator(
+            &self,
+            request: RunGeneratorRequest,
+        ) -> Result<RunGeneratorResponse, Self::Error> {
+            let data = String::from_utf8(request.data.to_vec())
+                .map_err(|e| Status::invalid_argument(e.to_string()))?;
+
+            let mut graph_description = GraphDescription::new();
+            for (index, line) in data.lines().enumerate() {
+                let mut node = NodeDescription {
+                    properties: Default::default(),
+                    node_key: index.to_string(),
+                    node_type: "Line".to_owned(),
+                    id_strategy: vec![],
+                };
+                node.set_property("line", ImmutableStrProp::from(line));
+                graph_description.add_node(node);
+            }
+
+            Ok(RunGeneratorResponse {
+                generated_graph: GeneratedGraph { graph_description },
+            })
+        }
+    }
+
+    fn fixtures_dir() -> tempfile::TempDir {
+        let fixtures_dir = tempfile::tempdir().expect("failed to create fixtures dir");
+        std::fs::write(fixtures_dir.path().join("lines.log"), "first\nsecond\n")
+            .expect("failed to write fixture");
+        fixtures_dir
+    }
+
+    #[tokio::test]
+    async fn blessing_writes_snapshots_which_then_match() {
+        let fixtures_dir = fixtures_dir();
+        let root = tempfile::tempdir().expect("failed to create snapshots dir");
+        let snapshots_dir = root.path().join("snapshots");
+
+        let failures =
+            check_snapshots(&LineGenerator {}, fixtures_dir.path(), &snapshots_dir, true).await;
+        assert!(failures.is_empty(), "{failures:?}");
+
+        let snapshot = std::fs::read_to_string(snapshots_dir.join("lines.log.snap"))
+            .expect("snapshot was not written");
+        assert_eq!(
+            snapshot,
+            "Line#0\n  line: immutable_str \"first\"\n\nLine#1\n  line: immutable_str \"second\"\n\n"
+        );
+
+        let failures = check_snapshots(
+            &LineGenerator {},
+            fixtures_dir.path(),
+            &snapshots_dir,
+            false,
+        )
+        .await;
+        assert!(failures.is_empty(), "{failures:?}");
+
+        std::fs::write(fixtures_dir.path().join("lines.log"), "first\nthird\n")
+            .expect("failed to write fixture");
+        let failures = check_snapshots(
+            &LineGenerator {},
+            fixtures_dir.path(),
+            &snapshots_dir,
+            false,
+        )
+        .await;
+        assert_eq!(failures.len(), 1);
+        assert!(failures[0].contains("lines.log.snap does not match"));
+    }
+
+    #[tokio::test]
+    async fn missing_snapshots_are_reported() {
+        let fixtures_dir = fixtures_dir();
+        let snapshots_dir = tempfile::tempdir().expect("failed to create snapshots dir");
+
+        let failures = check_snapshots(
+            &LineGenerator {},
+            fixtures_dir.path(),
+            snapshots_dir.path(),
+            false,
+        )
+        .await;
+
+        assert_eq!(failures.len(), 1);
+        assert!(failures[0].contains("lines.log.snap could not be read"));
+        assert!(failures[0].ends_with("line: immutable_str \"second\"\n\n"));
+        assert!(!snapshots_dir.path().join("lines.log.snap").exists());
+    }
+
+    fn file_node(node_key: &str, path: &str) -> NodeDescription {
+        let mut node = NodeDescription {
+            properties: Default::default(),
+            node_key: node_key.to_owned(),
+            node_type: "File".to_owned(),
+            id_strategy: vec![IdStrategy::from(Static {
+                primary_key_properties: vec!["path".to_owned()],
+                primary_key_requires_asset_id: false,
+            })],
+        };
+        node.set_property("path", ImmutableStrProp::from(path));
+        node.set_property("size", ImmutableUintProp { prop: 42 });
+        node
+    }
+
+    fn graph(keys: [&str; 3]) -> GraphDescription {
+        let mut graph = GraphDescription::new();
+        graph.add_node(file_node(keys[0], "/etc/passwd"));
+        graph.add_node(file_node(keys[1], "/etc/shadow"));
+        graph.add_node(file_node(keys[2], "/etc/shadow"));
+        graph.add_edge("copied_to", keys[1], keys[0]);
+        graph
+    }
+
+    #[test]
+    fn canonical_graph_is_independent_of_node_keys() {
+        let canonical = canonical_graph(&graph(["a", "b", "c"]));
+
+        assert_eq!(canonical, canonical_graph(&graph(["z", "y", "x"])));
+        assert_eq!(canonical, canonical_graph(&graph(["m", "c", "b"])));
+        assert!(canonical.starts_with("File#0\n"));
+        assert!(canonical.contains("  path: immutable_str \"/etc/shadow\"\n"));
+        assert!(canonical.contains("  size: immutable_uint 42\n"));
+        assert!(canonical.ends_with("File#2 -[copied_to]-> File#0\n"));
+    }
+
+    #[tokio::test]
+    async fn empty_graphs_do_not_match_empty_snapshots() {
+        let fixtures_dir = tempfile::tempdir().expect("failed to create fixtures dir");
+        std::fs::write(fixtures_dir.path().join("empty.log"), "").expect("failed to write fixture");
+        let snapshots_dir = tempfile::tempdir().expect("failed to create snapshots dir");
+        let snapshot_path = snapshots_dir.path().join("empty.log.snap");
+
+        std::fs::write(&snapshot_path, "").expect("failed to write snapshot");
+        let failures = check_snapshots(
+            &LineGenerator {},
+            fixtures_dir.path(),
+            snapshots_dir.path(),
+            false,
+        )
+        .await;
+        assert_eq!(failures.len(), 1);
+        assert!(failures[0].contains("empty.log.snap does not match"));
+
+        let failures = check_snapshots(
+            &LineGenerator {},
+            fixtures_dir.path(),
+            snapshots_dir.path(),
+            true,
+        )
+        .await;
+        assert!(failures.is_empty(), "{failures:?}");
+        assert_eq!(
+            std::fs::read_to_string(&snapshot_path).expect("snapshot was not written"),
+            EMPTY_GRAPH
+        );
+    }
+
+    #[test]
+    fn describes_the_first_difference() {
+        let description = describe_difference("a\nb\nc\n", "a\nx\n");
+
+        assert!(
+            description.starts_with("first difference at line 2\n  expected: b\n  actual:   x\n")
+        );
+    }
+}
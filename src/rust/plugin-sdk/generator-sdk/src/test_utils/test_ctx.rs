@@ -1,3 +1,8 @@
+use std::{
+    sync::Arc,
+    time::Duration as StdDuration,
+};
+
 use rust_proto::graplinc::{
     common::v1beta1::Duration,
     grapl::api::{
@@ -14,12 +19,18 @@ use rust_proto::graplinc::{
         },
         protocol::{
             error::ServeError,
-            healthcheck::HealthcheckStatus,
+            shutdown::serve_until_signal,
         },
     },
 };
 use test_context::{
-    futures::channel::oneshot::Sender,
+    futures::{
+        channel::oneshot::{
+            self,
+            Sender,
+        },
+        FutureExt,
+    },
     AsyncTestContext,
 };
 use tokio::{
@@ -41,10 +52,14 @@ We have to do this silly Internals song-and-dance for a couple reasons:
 After experimenting a bit, this seemed the most ergonomic solution.
 */
 
+/// How long in-flight requests are given to finish once the test context
+/// shuts the server down.
+const SHUTDOWN_TIMEOUT: StdDuration = StdDuration::from_secs(5);
+
 struct GeneratorTestContextInternals {
     client: GeneratorClient,
     server_handle: JoinHandle<Result<(), ServeError>>,
-    shutdown_tx: Sender<()>,
+    signal_tx: Sender<()>,
 }
 
 impl GeneratorTestContextInternals {
@@ -64,14 +79,28 @@ impl GeneratorTestContextInternals {
         // the port.
         let address = format!("http://{}:{}", socket_address.ip(), socket_address.port());
 
+        let generator_api = Arc::new(generator_api);
+        let healthcheck_api = generator_api.clone();
+
         let (server, shutdown_tx) = GeneratorServer::new(
             generator_api,
             tcp_listener,
-            || async { Ok(HealthcheckStatus::Serving) },
+            move || {
+                let healthcheck_api = healthcheck_api.clone();
+                async move { healthcheck_api.health().await }
+            },
             Duration::from_millis(50),
         );
 
-        let server_handle = tokio::task::spawn(server.serve());
+        // The server is run the way exec_service runs it, with this channel
+        // standing in for a SIGTERM, so that tests exercise the same drain.
+        let (signal_tx, signal_rx) = oneshot::channel::<()>();
+        let server_handle = tokio::task::spawn(serve_until_signal(
+            server.serve(),
+            shutdown_tx,
+            signal_rx.map(|_| Ok("SIGTERM")),
+            SHUTDOWN_TIMEOUT,
+        ));
 
         let client_config = ClientConfiguration::new(
             address,
@@ -93,18 +122,17 @@ impl GeneratorTestContextInternals {
         GeneratorTestContextInternals {
             client,
             server_handle,
-            shutdown_tx,
+            signal_tx,
         }
     }
 
-    async fn teardown(self) {
-        self.shutdown_tx
-            .send(())
-            .expect("failed to shutdown server");
+    async fn shutdown(self) -> Result<(), ServeError> {
+        // Sending only fails if the server has already stopped, in which case
+        // its result is collected below.
+        let _ = self.signal_tx.send(());
         self.server_handle
             .await
             .expect("failed to join server task")
-            .expect("server configuration failed");
     }
 }
 
@@ -120,7 +148,7 @@ impl AsyncTestContext for GeneratorTestContext {
 
     async fn teardown(self) {
         if let Some(i) = self.internals {
-            i.teardown().await;
+            i.shutdown().await.expect("server configuration failed");
         }
     }
 }
@@ -136,4 +164,13 @@ impl GeneratorTestContext {
 
         self.internals.as_ref().expect("internals").client.clone()
     }
+
+    /// Signals the server to shut down, as exec_service does on a SIGTERM, and
+    /// waits for it to drain in-flight requests and stop.
+    pub async fn shutdown(&mut self) -> Result<(), ServeError> {
+        match self.internals.take() {
+            Some(i) => i.shutdown().await,
+            None => Ok(()),
+        }
+    }
 }
@@ -0,0 +1,115 @@
+use std::{
+    collections::HashMap,
+    time::Duration,
+};
+
+use bytes::Bytes;
+use generator_sdk::test_utils::test_ctx::GeneratorTestContext;
+use rust_proto::graplinc::grapl::api::{
+    graph::v1beta1::GraphDescription,
+    plugin_sdk::generators::v1beta1::{
+        server::GeneratorApi,
+        GeneratedGraph,
+        RunGeneratorRequest,
+        RunGeneratorResponse,
+    },
+    protocol::{
+        healthcheck::{
+            HealthcheckError,
+            HealthcheckStatus,
+        },
+        status::Status,
+        test_utils::ServerProbe,
+    },
+};
+use test_context::test_context;
+
+/// Reports its health and holds its requests through a `ServerProbe`, and
+/// generates an empty graph.
+struct ProbedGenerator {
+    probe: ServerProbe,
+}
+
+#[async_trait::async_trait]
+impl GeneratorApi for ProbedGenerator {
+    type Error = Status;
+
+    async fn run_generator(
+        &self,
+        _request: RunGeneratorRequest,
+    ) -> Result<RunGeneratorResponse, Self::Error> {
+        self.probe.handle_request().await;
+        Ok(RunGeneratorResponse {
+            generated_graph: GeneratedGraph {
+                graph_description: GraphDescription {
+                    nodes: HashMap::new(),
+                    edges: HashMap::new(),
+                },
+            },
+        })
+    }
+
+    async fn health(&self) -> Result<HealthcheckStatus, HealthcheckError> {
+        Ok(self.probe.health())
+    }
+}
+
+fn request() -> RunGeneratorRequest {
+    RunGeneratorRequest {
+        data: Bytes::from_static(b"event"),
+    }
+}
+
+#[test_context(GeneratorTestContext)]
+#[tokio::test]
+async fn test_health_hook_is_polled(ctx: &mut GeneratorTestContext) -> eyre::Result<()> {
+    let probe = ServerProbe::new(2);
+    probe.release_requests();
+    let mut client = ctx
+        .get_client(ProbedGenerator {
+            probe: probe.clone(),
+        })
+        .await;
+
+    // The server keeps polling health after the generator first reports "not
+    // serving".
+    probe.wait_for_health_checks(3).await;
+
+    let response = client.run_generator(request()).await?;
+    assert!(response.generated_graph.graph_description.nodes.is_empty());
+
+    Ok(())
+}
+
+#[test_context(GeneratorTestContext)]
+#[tokio::test]
+async fn test_shutdown_drains_in_flight_requests(
+    ctx: &mut GeneratorTestContext,
+) -> eyre::Result<()> {
+    let probe = ServerProbe::new(0);
+    let mut client = ctx
+        .get_client(ProbedGenerator {
+            probe: probe.clone(),
+        })
+        .await;
+
+    let in_flight = tokio::task::spawn(async move { client.run_generator(request()).await });
+    probe.wait_for_requests(1).await;
+
+    // The server can't stop while the request is held in flight.
+    let shutdown = ctx.shutdown();
+    tokio::pin!(shutdown);
+    assert!(
+        tokio::time::timeout(Duration::from_millis(100), &mut shutdown)
+            .await
+            .is_err()
+    );
+
+    probe.release_requests();
+    shutdown.await?;
+
+    let response = in_flight.await??;
+    assert!(response.generated_graph.graph_description.nodes.is_empty());
+
+    Ok(())
+}
@@ -0,0 +1,143 @@
+//! Generates a `node_schema!` invocation for each node type in
+//! `etc/example_schemas/example.graphql`, so that the typed queries and views
+//! in `node_types` can't drift from the schema. Set `GRAPL_EXAMPLE_SCHEMA` to
+//! read the schema from elsewhere, e.g. when building outside the repository.
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    path::PathBuf,
+};
+
+use grapl_graphql_codegen::{
+    node_type::parse_into_node_types,
+    parse_schema,
+    predicate_type::PredicateType,
+};
+
+const SCHEMA_ENV_VAR: &str = "GRAPL_EXAMPLE_SCHEMA";
+
+/// The properties and edges of a node type, as `node_schema!` takes them.
+#[derive(Default)]
+struct NodeSchema {
+    string_properties: Vec<String>,
+    int_properties: Vec<String>,
+    float_properties: Vec<String>,
+    timestamp_properties: Vec<String>,
+    bool_properties: Vec<String>,
+    bytes_properties: Vec<String>,
+    /// `(edge name, neighbor type, reverse edge name)`
+    edges: Vec<(String, String, String)>,
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed={SCHEMA_ENV_VAR}");
+
+    let schema_path = match std::env::var_os(SCHEMA_ENV_VAR) {
+        Some(schema_path) => PathBuf::from(schema_path),
+        None => PathBuf::from(std::env::var("CARGO_MANIFEST_DIR")?)
+            .join("../../../../etc/example_schemas/example.graphql"),
+    };
+    println!("cargo:rerun-if-changed={}", schema_path.display());
+
+    let raw_schema = std::fs::read_to_string(&schema_path)
+        .map_err(|e| format!("failed to read {}: {e}", schema_path.display()))?;
+    let document = parse_schema::<String>(&raw_schema)?.into_static();
+    let node_types = parse_into_node_types(document)?;
+
+    let mut schemas: BTreeMap<String, NodeSchema> = BTreeMap::new();
+    for node_type in &node_types {
+        let schema = schemas.entry(node_type.type_name.clone()).or_default();
+
+        for predicate in &node_type.predicates {
+            let name = predicate.predicate_name.clone();
+            match predicate.predicate_type {
+                // A string filter matches a set of strings if any of its
+                // members match
+                PredicateType::String | PredicateType::StringSet => {
+                    schema.string_properties.push(name)
+                }
+                PredicateType::I64 | PredicateType::U64 => schema.int_properties.push(name),
+                PredicateType::F64 => schema.float_properties.push(name),
+                PredicateType::Timestamp => schema.timestamp_properties.push(name),
+                PredicateType::Bool => schema.bool_properties.push(name),
+                PredicateType::Bytes => schema.bytes_properties.push(name),
+            }
+        }
+    }
+
+    // Schemas only declare each edge on one side, so the reverse edge is
+    // added to the neighbor type
+    for edge in node_types.iter().flat_map(|node_type| &node_type.edges) {
+        schemas
+            .entry(edge.source_type_name.clone())
+            .or_default()
+            .edges
+            .push((
+                edge.edge_name.clone(),
+                edge.target_type_name.clone(),
+                edge.reverse_edge_name.clone(),
+            ));
+        schemas
+            .entry(edge.target_type_name.clone())
+            .or_default()
+            .edges
+            .push((
+                edge.reverse_edge_name.clone(),
+                edge.source_type_name.clone(),
+                edge.edge_name.clone(),
+            ));
+    }
+
+    let mut output = String::new();
+    for (type_name, schema) in &schemas {
+        writeln!(output, "node_schema! {{")?;
+        writeln!(output, "    {type_name} {{")?;
+        writeln!(
+            output,
+            "        string_properties: [{}],",
+            schema.string_properties.join(", ")
+        )?;
+        writeln!(
+            output,
+            "        int_properties: [{}],",
+            schema.int_properties.join(", ")
+        )?;
+        writeln!(
+            output,
+            "        float_properties: [{}],",
+            schema.float_properties.join(", ")
+        )?;
+        writeln!(
+            output,
+            "        timestamp_properties: [{}],",
+            schema.timestamp_properties.join(", ")
+        )?;
+        writeln!(
+            output,
+            "        bool_properties: [{}],",
+            schema.bool_properties.join(", ")
+        )?;
+        writeln!(
+            output,
+            "        bytes_properties: [{}],",
+            schema.bytes_properties.join(", ")
+        )?;
+        writeln!(output, "        edges: [")?;
+        for (edge_name, neighbor, reverse_edge_name) in &schema.edges {
+            writeln!(
+                output,
+                "            {edge_name}: {neighbor} @reverse({reverse_edge_name}),"
+            )?;
+        }
+        writeln!(output, "        ],")?;
+        writeln!(output, "    }}")?;
+        writeln!(output, "}}")?;
+        writeln!(output)?;
+    }
+
+    let out_dir = PathBuf::from(std::env::var("OUT_DIR")?);
+    std::fs::write(out_dir.join("node_types.rs"), output)?;
+
+    Ok(())
+}
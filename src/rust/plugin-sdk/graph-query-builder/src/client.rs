@@ -0,0 +1,96 @@
+use rust_proto::graplinc::grapl::{
+    api::{
+        client::ClientError,
+        graph_query::v1beta1::messages::{
+            GraphEdgeCounts,
+            MatchedGraphWithUid,
+            MaybeMatchWithUid,
+        },
+        graph_query_proxy::v1beta1::{
+            client::GraphQueryProxyClient,
+            messages::{
+                AggregateGraphFromUidRequest,
+                QueryGraphFromUidRequest,
+                QueryGraphWithUidRequest,
+            },
+        },
+    },
+    common::v1beta1::types::{
+        EdgeName,
+        Uid,
+    },
+};
+
+use crate::{
+    node_query::NodeQueryBuilder,
+    node_view::MatchedGraph,
+};
+
+impl NodeQueryBuilder {
+    /// Matches the query against the graph around `node_uid`, which may match
+    /// any node in the query, and returns the matched graph along with the
+    /// uid of the node that matched the root of the query.
+    pub async fn query_graph_with_uid(
+        &self,
+        client: &mut GraphQueryProxyClient,
+        node_uid: Uid,
+    ) -> Result<Option<MatchedGraph>, ClientError> {
+        let response = client
+            .query_graph_with_uid(QueryGraphWithUidRequest {
+                node_uid,
+                graph_query: self.build(),
+            })
+            .await?;
+
+        match response.maybe_match {
+            MaybeMatchWithUid::Matched(MatchedGraphWithUid {
+                matched_graph,
+                root_uid,
+            }) => Ok(Some(MatchedGraph {
+                graph: matched_graph,
+                root_uid,
+            })),
+            MaybeMatchWithUid::Missed(_) => Ok(None),
+        }
+    }
+
+    /// Matches the query against the graph with `node_uid` as its root.
+    pub async fn query_graph_from_uid(
+        &self,
+        client: &mut GraphQueryProxyClient,
+        node_uid: Uid,
+    ) -> Result<Option<MatchedGraph>, ClientError> {
+        let response = client
+            .query_graph_from_uid(QueryGraphFromUidRequest {
+                node_uid,
+                graph_query: self.build(),
+            })
+            .await?;
+
+        Ok(response.matched_graph.map(|graph| MatchedGraph {
+            graph,
+            root_uid: node_uid,
+        }))
+    }
+
+    /// Matches the query against the graph with `node_uid` as its root and
+    /// counts the `edge_names` edges of every node in the matched graph, or
+    /// all of their edges if `edge_names` is empty. The neighbors themselves
+    /// aren't read.
+    pub async fn aggregate_graph_from_uid(
+        &self,
+        client: &mut GraphQueryProxyClient,
+        node_uid: Uid,
+        edge_names: Vec<EdgeName>,
+    ) -> Result<Option<GraphEdgeCounts>, ClientError> {
+        let response = client
+            .aggregate_graph_from_uid(AggregateGraphFromUidRequest {
+                node_uid,
+                graph_query: self.build(),
+                edge_names,
+            })
+            .await?;
+
+        Ok(response.edge_counts)
+    }
+}
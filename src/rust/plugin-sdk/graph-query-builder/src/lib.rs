@@ -0,0 +1,63 @@
+//! A builder for graph queries, for analyzers to use instead of assembling
+//! `GraphQuery`s by hand.
+//!
+//! `NodeQueryBuilder` builds a query for any node type, while the
+//! `node_schema!` macro generates typed queries and views for a node type from
+//! its schema:
+//!
+//! ```ignore
+//! let mut query = ProcessQuery::new();
+//! query
+//!     .with_process_name(vec![StringCmp::eq("cmd.exe", false)])
+//!     .with_binary_file(|file| {
+//!         file.with_file_path(vec![StringCmp::Contains("Temp".to_owned(), false)]);
+//!     });
+//!
+//! if let Some(matched) = query.as_ref().query_graph_with_uid(&mut client, uid).await? {
+//!     let process: ProcessView = matched.root().expect("root is a Process");
+//!     for file in process.get_binary_file() {
+//!         tracing::info!(file_path = ?file.get_file_path());
+//!     }
+//! }
+//! ```
+mod client;
+mod node_query;
+pub mod node_types;
+mod node_view;
+mod schema;
+
+pub use graph_query_language as query_language;
+
+pub use crate::{
+    node_query::NodeQueryBuilder,
+    node_view::{
+        FromGraphView,
+        MatchedGraph,
+        NodeView,
+    },
+    query_language::{
+        QueryLanguageError,
+        TextQuery,
+    },
+};
+
+#[doc(hidden)]
+pub mod __private {
+    pub use bytes::Bytes;
+    pub use paste;
+    pub use rust_proto::graplinc::grapl::{
+        api::graph_query::v1beta1::messages::{
+            AndFloatFilters,
+            AndIntFilters,
+            AndStringFilters,
+            GraphQuery,
+            GraphView,
+        },
+        common::v1beta1::types::{
+            EdgeName,
+            NodeType,
+            PropertyName,
+            Uid,
+        },
+    };
+}
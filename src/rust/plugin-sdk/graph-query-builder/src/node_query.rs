@@ -0,0 +1,468 @@
+use std::ops::RangeInclusive;
+
+use bytes::Bytes;
+use rust_proto::graplinc::grapl::{
+    api::graph_query::v1beta1::messages::{
+        AndFloatFilters,
+        AndIntFilters,
+        AndStringFilters,
+        GraphQuery,
+        NodePropertyQuery,
+        OrIntFilters,
+        PathFilter,
+        QueryId,
+        UidFilter,
+        UidOperation,
+    },
+    common::v1beta1::types::{
+        EdgeName,
+        NodeType,
+        PropertyName,
+        Uid,
+    },
+};
+
+/// Builds a `GraphQuery` rooted at a single node.
+///
+/// Neighbors are owned by the node that they're attached to, so a query is a
+/// tree of `NodeQueryBuilder`s until `build` flattens it into the `QueryId`
+/// keyed maps that `GraphQuery` expects, adding each edge and path in both
+/// directions.
+#[derive(Debug, Clone)]
+pub struct NodeQueryBuilder {
+    query: NodePropertyQuery,
+    edges: Vec<EdgeQuery>,
+    paths: Vec<PathQuery>,
+    edge_counts: Vec<(EdgeName, AndIntFilters)>,
+}
+
+#[derive(Debug, Clone)]
+struct EdgeQuery {
+    edge_name: EdgeName,
+    reverse_edge_name: EdgeName,
+    neighbor: NodeQueryBuilder,
+}
+
+#[derive(Debug, Clone)]
+struct PathQuery {
+    edge_name: EdgeName,
+    reverse_edge_name: EdgeName,
+    hops: RangeInclusive<u32>,
+    intermediate_node_query: Option<NodePropertyQuery>,
+    neighbor: NodeQueryBuilder,
+}
+
+impl NodeQueryBuilder {
+    pub fn new(node_type: NodeType) -> Self {
+        Self {
+            query: NodePropertyQuery::new(node_type),
+            edges: Vec::new(),
+            paths: Vec::new(),
+            edge_counts: Vec::new(),
+        }
+    }
+
+    pub fn query_id(&self) -> QueryId {
+        self.query.query_id
+    }
+
+    pub fn node_type(&self) -> &NodeType {
+        &self.query.node_type
+    }
+
+    /// Each call adds an alternative: the node matches if the property
+    /// matches every comparison in any one of the calls.
+    pub fn with_string_filters(
+        &mut self,
+        property_name: PropertyName,
+        filters: impl Into<AndStringFilters>,
+    ) -> &mut Self {
+        self.query.with_string_filters(property_name, filters);
+        self
+    }
+
+    /// Each call adds an alternative: the node matches if the property
+    /// matches every comparison in any one of the calls.
+    pub fn with_int_filters(
+        &mut self,
+        property_name: PropertyName,
+        filters: impl Into<AndIntFilters>,
+    ) -> &mut Self {
+        self.query.with_int_filters(property_name, filters);
+        self
+    }
+
+    /// Each call adds an alternative, as with `with_int_filters`.
+    pub fn with_float_filters(
+        &mut self,
+        property_name: PropertyName,
+        filters: impl Into<AndFloatFilters>,
+    ) -> &mut Self {
+        self.query.with_float_filters(property_name, filters);
+        self
+    }
+
+    /// As `with_int_filters`, comparing against the timestamp in milliseconds
+    /// since the Unix epoch.
+    pub fn with_timestamp_filters(
+        &mut self,
+        property_name: PropertyName,
+        filters: impl Into<AndIntFilters>,
+    ) -> &mut Self {
+        self.query.with_timestamp_filters(property_name, filters);
+        self
+    }
+
+    pub fn with_bool(&mut self, property_name: PropertyName, value: bool) -> &mut Self {
+        self.query.with_bool_filter(property_name, value);
+        self
+    }
+
+    pub fn with_bytes(
+        &mut self,
+        property_name: PropertyName,
+        value: impl Into<Bytes>,
+    ) -> &mut Self {
+        self.query.with_bytes_filter(property_name, value);
+        self
+    }
+
+    /// Constrains how many edges named `edge_name` the node has, whatever
+    /// their neighbors are. Each call adds an alternative, as with
+    /// `with_int_filters`.
+    pub fn with_edge_count(
+        &mut self,
+        edge_name: EdgeName,
+        filters: impl Into<AndIntFilters>,
+    ) -> &mut Self {
+        self.edge_counts.push((edge_name, filters.into()));
+        self
+    }
+
+    pub fn with_uid(&mut self, uid: Uid) -> &mut Self {
+        self.query.with_uid_filter(UidFilter {
+            operation: UidOperation::Equal,
+            value: uid,
+        });
+        self
+    }
+
+    /// Requires an edge named `edge_name` from this node to a node of
+    /// `node_type`, which `init_edge` can add its own filters and edges to.
+    /// `reverse_edge_name` is the name of the same edge as seen from the
+    /// neighbor.
+    pub fn with_edge_to(
+        &mut self,
+        edge_name: EdgeName,
+        reverse_edge_name: EdgeName,
+        node_type: NodeType,
+        init_edge: impl FnOnce(&mut Self),
+    ) -> &mut Self {
+        let mut neighbor = Self::new(node_type);
+        init_edge(&mut neighbor);
+        self.with_edge(edge_name, reverse_edge_name, neighbor)
+    }
+
+    pub fn with_edge(
+        &mut self,
+        edge_name: EdgeName,
+        reverse_edge_name: EdgeName,
+        neighbor: NodeQueryBuilder,
+    ) -> &mut Self {
+        self.edges.push(EdgeQuery {
+            edge_name,
+            reverse_edge_name,
+            neighbor,
+        });
+        self
+    }
+
+    /// Requires a path of between `hops.start()` and `hops.end()` edges named
+    /// `edge_name` from this node to `neighbor`. Every node in between must
+    /// match the filters of `intermediate`, if there is one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `intermediate` has edges, paths or edge counts, which aren't
+    /// supported on the nodes in between.
+    pub fn with_path(
+        &mut self,
+        edge_name: EdgeName,
+        reverse_edge_name: EdgeName,
+        hops: RangeInclusive<u32>,
+        intermediate: Option<NodeQueryBuilder>,
+        neighbor: NodeQueryBuilder,
+    ) -> &mut Self {
+        let intermediate_node_query = intermediate.map(|intermediate| {
+            assert!(
+                intermediate.edges.is_empty()
+                    && intermediate.paths.is_empty()
+                    && intermediate.edge_counts.is_empty(),
+                "a path's intermediate nodes can only be filtered by their properties, not by edges, paths or edge counts",
+            );
+            intermediate.query
+        });
+        self.paths.push(PathQuery {
+            edge_name,
+            reverse_edge_name,
+            hops,
+            intermediate_node_query,
+            neighbor,
+        });
+        self
+    }
+
+    pub fn build(&self) -> GraphQuery {
+        let mut graph_query = GraphQuery {
+            root_query_id: self.query_id(),
+            node_property_queries: Default::default(),
+            edge_filters: Default::default(),
+            edge_map: Default::default(),
+            path_filters: Default::default(),
+            edge_count_filters: Default::default(),
+        };
+        self.add_to(&mut graph_query);
+        graph_query
+    }
+
+    fn add_to(&self, graph_query: &mut GraphQuery) {
+        graph_query.merge_node(self.query.clone());
+
+        for (edge_name, filters) in &self.edge_counts {
+            graph_query
+                .edge_count_filters
+                .entry((self.query_id(), edge_name.clone()))
+                .or_insert_with(OrIntFilters::new)
+                .push(filters.clone());
+        }
+
+        for edge in &self.edges {
+            let neighbor_query_id = edge.neighbor.query_id();
+            graph_query
+                .edge_filters
+                .entry((self.query_id(), edge.edge_name.clone()))
+                .or_default()
+                .insert(neighbor_query_id);
+            graph_query
+                .edge_filters
+                .entry((neighbor_query_id, edge.reverse_edge_name.clone()))
+                .or_default()
+                .insert(self.query_id());
+            graph_query
+                .edge_map
+                .insert(edge.edge_name.clone(), edge.reverse_edge_name.clone());
+            graph_query
+                .edge_map
+                .insert(edge.reverse_edge_name.clone(), edge.edge_name.clone());
+
+            edge.neighbor.add_to(graph_query);
+        }
+
+        for path in &self.paths {
+            let neighbor_query_id = path.neighbor.query_id();
+            let path_filter = PathFilter {
+                neighbor_query_id,
+                min_hops: *path.hops.start(),
+                max_hops: *path.hops.end(),
+                intermediate_node_query: path.intermediate_node_query.clone(),
+            };
+            graph_query
+                .path_filters
+                .entry((neighbor_query_id, path.reverse_edge_name.clone()))
+                .or_default()
+                .push(PathFilter {
+                    neighbor_query_id: self.query_id(),
+                    ..path_filter.clone()
+                });
+            graph_query
+                .path_filters
+                .entry((self.query_id(), path.edge_name.clone()))
+                .or_default()
+                .push(path_filter);
+            graph_query
+                .edge_map
+                .insert(path.edge_name.clone(), path.reverse_edge_name.clone());
+            graph_query
+                .edge_map
+                .insert(path.reverse_edge_name.clone(), path.edge_name.clone());
+
+            path.neighbor.add_to(graph_query);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_proto::graplinc::grapl::api::graph_query::v1beta1::messages::{
+        IntCmp,
+        IntOperation,
+        StringCmp,
+    };
+
+    use super::*;
+
+    fn node_type(value: &str) -> NodeType {
+        NodeType {
+            value: value.to_owned(),
+        }
+    }
+
+    fn edge_name(value: &str) -> EdgeName {
+        EdgeName {
+            value: value.to_owned(),
+        }
+    }
+
+    #[test]
+    fn build_flattens_edges_in_both_directions() {
+        let mut file_query_id = None;
+        let mut process = NodeQueryBuilder::new(node_type("Process"));
+        process
+            .with_string_filters(
+                PropertyName::new_unchecked("process_name".to_owned()),
+                vec![StringCmp::eq("chrome.exe", false)],
+            )
+            .with_int_filters(
+                PropertyName::new_unchecked("process_id".to_owned()),
+                vec![IntCmp::Gt(100, false), IntCmp::Lte(200, false)],
+            )
+            .with_uid(Uid::from_u64(7).unwrap())
+            .with_edge_to(
+                edge_name("binary_file"),
+                edge_name("executed_as_processes"),
+                node_type("File"),
+                |file| {
+                    file_query_id = Some(file.query_id());
+                },
+            );
+        let file_query_id = file_query_id.unwrap();
+
+        let graph_query = process.build();
+
+        assert_eq!(graph_query.root_query_id, process.query_id());
+        assert_eq!(graph_query.node_property_queries.len(), 2);
+        assert_eq!(
+            graph_query.edge_filters[&(process.query_id(), edge_name("binary_file"))],
+            [file_query_id].into_iter().collect()
+        );
+        assert_eq!(
+            graph_query.edge_filters[&(file_query_id, edge_name("executed_as_processes"))],
+            [process.query_id()].into_iter().collect()
+        );
+        assert_eq!(
+            graph_query.edge_map[&edge_name("executed_as_processes")],
+            edge_name("binary_file")
+        );
+
+        let root = &graph_query.node_property_queries[&process.query_id()];
+        let process_id_filters = &root.int_filters
+            [&PropertyName::new_unchecked("process_id".to_owned())]
+            .and_int_filters;
+        assert_eq!(process_id_filters.len(), 1);
+        assert_eq!(
+            process_id_filters[0].int_filters[0].operation,
+            IntOperation::GreaterThan
+        );
+        assert_eq!(root.uid_filters.uid_filters[0].value.as_u64(), 7);
+        assert_eq!(
+            graph_query.node_property_queries[&file_query_id].node_type,
+            node_type("File")
+        );
+    }
+
+    #[test]
+    fn build_flattens_paths_in_both_directions() {
+        let mut intermediate = NodeQueryBuilder::new(node_type("Process"));
+        intermediate.with_string_filters(
+            PropertyName::new_unchecked("process_name".to_owned()),
+            vec![StringCmp::eq("explorer.exe", true)],
+        );
+        let ancestor = NodeQueryBuilder::new(node_type("Process"));
+        let ancestor_query_id = ancestor.query_id();
+
+        let mut process = NodeQueryBuilder::new(node_type("Process"));
+        process.with_path(
+            edge_name("parent"),
+            edge_name("children"),
+            1..=5,
+            Some(intermediate),
+            ancestor,
+        );
+
+        let graph_query = process.build();
+
+        assert_eq!(graph_query.node_property_queries.len(), 2);
+        assert!(graph_query.edge_filters.is_empty());
+
+        let ancestors = &graph_query.path_filters[&(process.query_id(), edge_name("parent"))];
+        assert_eq!(ancestors.len(), 1);
+        assert_eq!(ancestors[0].neighbor_query_id, ancestor_query_id);
+        assert_eq!((ancestors[0].min_hops, ancestors[0].max_hops), (1, 5));
+        assert!(ancestors[0]
+            .intermediate_node_query
+            .as_ref()
+            .unwrap()
+            .string_filters
+            .contains_key(&PropertyName::new_unchecked("process_name".to_owned())));
+
+        let descendants = &graph_query.path_filters[&(ancestor_query_id, edge_name("children"))];
+        assert_eq!(descendants.len(), 1);
+        assert_eq!(descendants[0].neighbor_query_id, process.query_id());
+        assert_eq!(
+            graph_query.edge_map[&edge_name("children")],
+            edge_name("parent")
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "a path's intermediate nodes can only be filtered by their properties"
+    )]
+    fn with_path_rejects_intermediate_edges() {
+        let mut intermediate = NodeQueryBuilder::new(node_type("Process"));
+        intermediate.with_edge_count(edge_name("children"), vec![IntCmp::Gt(20, false)]);
+
+        NodeQueryBuilder::new(node_type("Process")).with_path(
+            edge_name("parent"),
+            edge_name("children"),
+            1..=5,
+            Some(intermediate),
+            NodeQueryBuilder::new(node_type("Process")),
+        );
+    }
+
+    #[test]
+    fn build_collects_edge_counts_by_query_id() {
+        let mut process = NodeQueryBuilder::new(node_type("Process"));
+        process
+            .with_edge_count(edge_name("children"), vec![IntCmp::Gt(20, false)])
+            .with_edge_count(edge_name("children"), vec![IntCmp::Eq(0, false)])
+            .with_edge_to(
+                edge_name("binary_file"),
+                edge_name("executed_as_processes"),
+                node_type("File"),
+                |file| {
+                    file.with_edge_count(
+                        edge_name("written_by_processes"),
+                        vec![IntCmp::Gt(5, false)],
+                    );
+                },
+            );
+
+        let graph_query = process.build();
+
+        assert_eq!(graph_query.edge_count_filters.len(), 2);
+        let children =
+            &graph_query.edge_count_filters[&(process.query_id(), edge_name("children"))];
+        assert_eq!(children.and_int_filters.len(), 2);
+        assert_eq!(
+            children.and_int_filters[1].int_filters[0].operation,
+            IntOperation::Equal
+        );
+        assert!(graph_query
+            .edge_count_filters
+            .keys()
+            .any(|(query_id, name)| *query_id != process.query_id()
+                && *name == edge_name("written_by_processes")));
+    }
+}
@@ -0,0 +1,120 @@
+//! Queries and views for the node types in
+//! `etc/example_schemas/example.graphql`.
+//!
+//! The `node_schema!` invocations are generated from the schema by `build.rs`.
+use crate::node_schema;
+
+include!(concat!(env!("OUT_DIR"), "/node_types.rs"));
+
+#[cfg(test)]
+mod tests {
+    use rust_proto::graplinc::grapl::{
+        api::graph_query::v1beta1::messages::{
+            GraphView,
+            IntCmp,
+            StringCmp,
+        },
+        common::v1beta1::types::{
+            EdgeName,
+            NodeType,
+            PropertyName,
+            Uid,
+        },
+    };
+
+    use super::*;
+    use crate::MatchedGraph;
+
+    fn uid(value: u64) -> Uid {
+        Uid::from_u64(value).unwrap()
+    }
+
+    #[test]
+    fn typed_query_uses_schema_names() {
+        let mut query = ProcessQuery::new();
+        query
+            .with_process_name(vec![StringCmp::eq("cmd.exe", false)])
+            .with_process_id(vec![IntCmp::Eq(4, true)])
+            .with_binary_file(|file| {
+                file.with_file_path(vec![StringCmp::Contains("Temp".to_owned(), false)]);
+            });
+
+        let graph_query = query.build();
+        let root = &graph_query.node_property_queries[&graph_query.root_query_id];
+
+        assert_eq!(root.node_type.value, "Process");
+        assert!(root
+            .string_filters
+            .contains_key(&PropertyName::new_unchecked("process_name".to_owned())));
+        assert!(root
+            .int_filters
+            .contains_key(&PropertyName::new_unchecked("process_id".to_owned())));
+        assert_eq!(
+            graph_query.edge_map[&EdgeName {
+                value: "binary_file".to_owned()
+            }]
+                .value,
+            "executed_as_processes"
+        );
+    }
+
+    #[test]
+    fn string_sets_take_string_filters() {
+        let mut query = ProcessQuery::new();
+        query.with_command_line_args(vec![StringCmp::eq("/c", false)]);
+
+        let graph_query = query.build();
+        let root = &graph_query.node_property_queries[&graph_query.root_query_id];
+
+        assert!(root
+            .string_filters
+            .contains_key(&PropertyName::new_unchecked("command_line_args".to_owned())));
+    }
+
+    #[test]
+    fn typed_views_follow_edges() {
+        let mut graph = GraphView::default();
+        graph
+            .new_node(
+                uid(1),
+                NodeType {
+                    value: "Process".to_owned(),
+                },
+            )
+            .add_string_property(
+                PropertyName::new_unchecked("process_name".to_owned()),
+                "cmd.exe".to_owned(),
+            );
+        graph
+            .new_node(
+                uid(2),
+                NodeType {
+                    value: "File".to_owned(),
+                },
+            )
+            .add_string_property(
+                PropertyName::new_unchecked("file_path".to_owned()),
+                "C:\\Temp\\cmd.exe".to_owned(),
+            );
+        let binary_file = EdgeName {
+            value: "binary_file".to_owned(),
+        };
+        graph.add_edge(uid(1), binary_file, uid(2));
+
+        let matched = MatchedGraph {
+            graph,
+            root_uid: uid(1),
+        };
+
+        assert!(matched.root::<FileView>().is_none());
+        let process: ProcessView = matched.root().unwrap();
+        assert_eq!(process.get_process_name(), Some("cmd.exe"));
+
+        let files: Vec<FileView> = process.get_binary_file().collect();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].uid(), uid(2));
+        assert_eq!(files[0].get_file_path(), Some("C:\\Temp\\cmd.exe"));
+        assert_eq!(process.get_children().count(), 0);
+        assert_eq!(matched.nodes::<FileView>().count(), 1);
+    }
+}
@@ -0,0 +1,91 @@
+use rust_proto::graplinc::grapl::{
+    api::graph_query::v1beta1::messages::{
+        GraphView,
+        NodePropertiesView,
+    },
+    common::v1beta1::types::{
+        EdgeName,
+        NodeType,
+        PropertyName,
+        Uid,
+    },
+};
+
+/// Implemented by views that can be looked up by uid in a matched graph.
+pub trait FromGraphView<'a>: Sized {
+    fn from_graph_view(graph: &'a GraphView, uid: Uid) -> Option<Self>;
+}
+
+/// A node in a `GraphView`, which can follow the edges the query matched.
+#[derive(Debug, Clone, Copy)]
+pub struct NodeView<'a> {
+    graph: &'a GraphView,
+    node: &'a NodePropertiesView,
+}
+
+impl<'a> NodeView<'a> {
+    pub fn new(graph: &'a GraphView, uid: Uid) -> Option<Self> {
+        graph.get_node(uid).map(|node| Self { graph, node })
+    }
+
+    pub fn uid(&self) -> Uid {
+        self.node.uid
+    }
+
+    pub fn node_type(&self) -> &'a NodeType {
+        &self.node.node_type
+    }
+
+    /// Only the properties that the query filtered on are returned by
+    /// graph-query, so this is `None` for any other property.
+    pub fn get_string(&self, property_name: &str) -> Option<&'a str> {
+        self.node
+            .string_properties
+            .prop_map
+            .get(&PropertyName::new_unchecked(property_name.to_owned()))
+            .map(String::as_str)
+    }
+
+    pub fn neighbors(&self, edge_name: &str) -> impl Iterator<Item = NodeView<'a>> + 'a {
+        let graph = self.graph;
+        let edge_name = EdgeName {
+            value: edge_name.to_owned(),
+        };
+        graph
+            .edges
+            .get(&(self.uid(), edge_name))
+            .into_iter()
+            .flatten()
+            .filter_map(move |uid| NodeView::new(graph, *uid))
+    }
+}
+
+impl<'a> FromGraphView<'a> for NodeView<'a> {
+    fn from_graph_view(graph: &'a GraphView, uid: Uid) -> Option<Self> {
+        NodeView::new(graph, uid)
+    }
+}
+
+/// A graph returned by graph-query-proxy, along with the uid of the node that
+/// matched the root of the query.
+#[derive(Debug, Clone)]
+pub struct MatchedGraph {
+    pub graph: GraphView,
+    pub root_uid: Uid,
+}
+
+impl MatchedGraph {
+    /// Returns `None` if the root is not a `V`, e.g. if it is a `FileView`
+    /// and the root is a `Process`.
+    pub fn root<'a, V: FromGraphView<'a>>(&'a self) -> Option<V> {
+        V::from_graph_view(&self.graph, self.root_uid)
+    }
+
+    /// Every node in the graph that is a `V`.
+    pub fn nodes<'a, V: FromGraphView<'a> + 'a>(&'a self) -> impl Iterator<Item = V> + 'a {
+        self.graph
+            .nodes
+            .keys()
+            .filter_map(|uid| V::from_graph_view(&self.graph, *uid))
+    }
+}
@@ -0,0 +1,301 @@
+/// Generates a typed query builder and view for a node type from a
+/// description of its schema. For
+///
+/// ```ignore
+/// node_schema! {
+///     Process {
+///         string_properties: [process_name],
+///         int_properties: [process_id],
+///         float_properties: [],
+///         timestamp_properties: [],
+///         bool_properties: [],
+///         bytes_properties: [],
+///         edges: [binary_file: File @reverse(executed_as_processes)],
+///     }
+/// }
+/// ```
+///
+/// this generates a `ProcessQuery`, with `with_process_name`,
+/// `with_process_id` and `with_binary_file` predicates, and a `ProcessView`,
+/// with `get_process_name` and `get_binary_file` accessors. The neighbor type,
+/// here `File`, must be declared with `node_schema!` as well.
+///
+/// Each edge also gets a `with_<edge>_count` predicate, such as
+/// `with_children_count(vec![IntCmp::Gt(20, false)])`, which constrains how
+/// many of the edges the node has without matching its neighbors.
+///
+/// Each edge also gets a `with_<edge>_path` predicate, such as
+/// `with_parent_path(1..=5, |_| {}, |ancestor| ...)`, for paths of several
+/// hops along it. Only edges from a node type to itself can be followed for
+/// more than one hop.
+///
+/// Timestamp properties take the same comparisons as int properties, in
+/// milliseconds since the Unix epoch, while bool and bytes properties can only
+/// be compared for equality. A set of strings goes in `string_properties`, as
+/// a string filter matches a set if any of its members match.
+///
+/// Views only have accessors for string properties, as `GraphView` only holds
+/// string properties.
+#[macro_export]
+macro_rules! node_schema {
+    (
+        $(#[$meta:meta])*
+        $node_type:ident {
+            string_properties: [$($string_property:ident),* $(,)?],
+            int_properties: [$($int_property:ident),* $(,)?],
+            float_properties: [$($float_property:ident),* $(,)?],
+            timestamp_properties: [$($timestamp_property:ident),* $(,)?],
+            bool_properties: [$($bool_property:ident),* $(,)?],
+            bytes_properties: [$($bytes_property:ident),* $(,)?],
+            edges: [$($edge:ident: $neighbor:ident @reverse($reverse_edge:ident)),* $(,)?] $(,)?
+        }
+    ) => {
+        $crate::__private::paste::paste! {
+            $(#[$meta])*
+            #[derive(Debug, Clone)]
+            pub struct [<$node_type Query>] {
+                inner: $crate::NodeQueryBuilder,
+            }
+
+            impl [<$node_type Query>] {
+                pub const NODE_TYPE: &'static str = stringify!($node_type);
+
+                pub fn new() -> Self {
+                    Self {
+                        inner: $crate::NodeQueryBuilder::new($crate::__private::NodeType {
+                            value: Self::NODE_TYPE.to_owned(),
+                        }),
+                    }
+                }
+
+                pub fn with_uid(&mut self, uid: $crate::__private::Uid) -> &mut Self {
+                    self.inner.with_uid(uid);
+                    self
+                }
+
+                $(
+                    pub fn [<with_ $string_property>](
+                        &mut self,
+                        filters: impl Into<$crate::__private::AndStringFilters>,
+                    ) -> &mut Self {
+                        self.inner.with_string_filters(
+                            $crate::__private::PropertyName::new_unchecked(
+                                stringify!($string_property).to_owned(),
+                            ),
+                            filters,
+                        );
+                        self
+                    }
+                )*
+
+                $(
+                    pub fn [<with_ $int_property>](
+                        &mut self,
+                        filters: impl Into<$crate::__private::AndIntFilters>,
+                    ) -> &mut Self {
+                        self.inner.with_int_filters(
+                            $crate::__private::PropertyName::new_unchecked(
+                                stringify!($int_property).to_owned(),
+                            ),
+                            filters,
+                        );
+                        self
+                    }
+                )*
+
+                $(
+                    pub fn [<with_ $float_property>](
+                        &mut self,
+                        filters: impl Into<$crate::__private::AndFloatFilters>,
+                    ) -> &mut Self {
+                        self.inner.with_float_filters(
+                            $crate::__private::PropertyName::new_unchecked(
+                                stringify!($float_property).to_owned(),
+                            ),
+                            filters,
+                        );
+                        self
+                    }
+                )*
+
+                $(
+                    pub fn [<with_ $timestamp_property>](
+                        &mut self,
+                        filters: impl Into<$crate::__private::AndIntFilters>,
+                    ) -> &mut Self {
+                        self.inner.with_timestamp_filters(
+                            $crate::__private::PropertyName::new_unchecked(
+                                stringify!($timestamp_property).to_owned(),
+                            ),
+                            filters,
+                        );
+                        self
+                    }
+                )*
+
+                $(
+                    pub fn [<with_ $bool_property>](&mut self, value: bool) -> &mut Self {
+                        self.inner.with_bool(
+                            $crate::__private::PropertyName::new_unchecked(
+                                stringify!($bool_property).to_owned(),
+                            ),
+                            value,
+                        );
+                        self
+                    }
+                )*
+
+                $(
+                    pub fn [<with_ $bytes_property>](
+                        &mut self,
+                        value: impl Into<$crate::__private::Bytes>,
+                    ) -> &mut Self {
+                        self.inner.with_bytes(
+                            $crate::__private::PropertyName::new_unchecked(
+                                stringify!($bytes_property).to_owned(),
+                            ),
+                            value,
+                        );
+                        self
+                    }
+                )*
+
+                $(
+                    pub fn [<with_ $edge>](
+                        &mut self,
+                        init_edge: impl FnOnce(&mut [<$neighbor Query>]),
+                    ) -> &mut Self {
+                        let mut neighbor = [<$neighbor Query>]::new();
+                        init_edge(&mut neighbor);
+                        self.inner.with_edge(
+                            $crate::__private::EdgeName {
+                                value: stringify!($edge).to_owned(),
+                            },
+                            $crate::__private::EdgeName {
+                                value: stringify!($reverse_edge).to_owned(),
+                            },
+                            neighbor.into(),
+                        );
+                        self
+                    }
+                )*
+
+                $(
+                    pub fn [<with_ $edge _count>](
+                        &mut self,
+                        filters: impl Into<$crate::__private::AndIntFilters>,
+                    ) -> &mut Self {
+                        self.inner.with_edge_count(
+                            $crate::__private::EdgeName {
+                                value: stringify!($edge).to_owned(),
+                            },
+                            filters,
+                        );
+                        self
+                    }
+                )*
+
+                $(
+                    pub fn [<with_ $edge _path>](
+                        &mut self,
+                        hops: ::std::ops::RangeInclusive<u32>,
+                        init_intermediate: impl FnOnce(&mut [<$neighbor Query>]),
+                        init_neighbor: impl FnOnce(&mut [<$neighbor Query>]),
+                    ) -> &mut Self {
+                        let mut intermediate = [<$neighbor Query>]::new();
+                        init_intermediate(&mut intermediate);
+                        let mut neighbor = [<$neighbor Query>]::new();
+                        init_neighbor(&mut neighbor);
+                        self.inner.with_path(
+                            $crate::__private::EdgeName {
+                                value: stringify!($edge).to_owned(),
+                            },
+                            $crate::__private::EdgeName {
+                                value: stringify!($reverse_edge).to_owned(),
+                            },
+                            hops,
+                            Some(intermediate.into()),
+                            neighbor.into(),
+                        );
+                        self
+                    }
+                )*
+
+                pub fn build(&self) -> $crate::__private::GraphQuery {
+                    self.inner.build()
+                }
+            }
+
+            impl Default for [<$node_type Query>] {
+                fn default() -> Self {
+                    Self::new()
+                }
+            }
+
+            impl From<[<$node_type Query>]> for $crate::NodeQueryBuilder {
+                fn from(query: [<$node_type Query>]) -> Self {
+                    query.inner
+                }
+            }
+
+            impl AsRef<$crate::NodeQueryBuilder> for [<$node_type Query>] {
+                fn as_ref(&self) -> &$crate::NodeQueryBuilder {
+                    &self.inner
+                }
+            }
+
+            $(#[$meta])*
+            #[derive(Debug, Clone, Copy)]
+            pub struct [<$node_type View>]<'a> {
+                inner: $crate::NodeView<'a>,
+            }
+
+            impl<'a> [<$node_type View>]<'a> {
+                pub fn uid(&self) -> $crate::__private::Uid {
+                    self.inner.uid()
+                }
+
+                $(
+                    pub fn [<get_ $string_property>](&self) -> Option<&'a str> {
+                        self.inner.get_string(stringify!($string_property))
+                    }
+                )*
+
+                $(
+                    pub fn [<get_ $edge>](
+                        &self,
+                    ) -> impl Iterator<Item = [<$neighbor View>]<'a>> + 'a {
+                        self.inner
+                            .neighbors(stringify!($edge))
+                            .filter_map(|node| [<$neighbor View>]::try_from(node).ok())
+                    }
+                )*
+
+                pub fn as_node_view(&self) -> $crate::NodeView<'a> {
+                    self.inner
+                }
+            }
+
+            impl<'a> TryFrom<$crate::NodeView<'a>> for [<$node_type View>]<'a> {
+                type Error = $crate::NodeView<'a>;
+
+                fn try_from(node: $crate::NodeView<'a>) -> Result<Self, Self::Error> {
+                    if node.node_type().value == [<$node_type Query>]::NODE_TYPE {
+                        Ok(Self { inner: node })
+                    } else {
+                        Err(node)
+                    }
+                }
+            }
+
+            impl<'a> $crate::FromGraphView<'a> for [<$node_type View>]<'a> {
+                fn from_graph_view(
+                    graph: &'a $crate::__private::GraphView,
+                    uid: $crate::__private::Uid,
+                ) -> Option<Self> {
+                    $crate::NodeView::new(graph, uid).and_then(|node| Self::try_from(node).ok())
+                }
+            }
+        }
+    };
+}
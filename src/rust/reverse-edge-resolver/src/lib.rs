@@ -1,3 +1,5 @@
+//! Resolves edges to their reverse through graph-schema-manager, shared by
+//! graph-mutation and graph-query so that neither service depends on the other.
 use moka::future::Cache;
 use rust_proto::graplinc::grapl::{
     api::{
@@ -6,6 +8,7 @@ use rust_proto::graplinc::grapl::{
             client::GraphSchemaManagerClient,
             messages::GetEdgeSchemaRequest,
         },
+        protocol::status::Code,
     },
     common::v1beta1::types::{
         EdgeName,
@@ -19,6 +22,9 @@ pub enum ReverseEdgeResolverError {
     ClientError(#[from] ClientError),
 }
 
+/// Looks up the reverse of an edge in a tenant's schema, for graph-mutation to
+/// write both directions of an edge and for graph-query to compile text
+/// queries, which only name one direction of each edge.
 #[derive(Clone)]
 pub struct ReverseEdgeResolver {
     schema_client: GraphSchemaManagerClient,
@@ -62,4 +68,27 @@ impl ReverseEdgeResolver {
             }
         }
     }
+
+    /// Like `resolve_reverse_edge`, but returns `None` if `node_type` has no
+    /// `edge_name` edge in the tenant's schema. Schemas can be deployed at any
+    /// time, so misses aren't cached.
+    pub async fn try_resolve_reverse_edge(
+        &self,
+        tenant_id: uuid::Uuid,
+        node_type: NodeType,
+        edge_name: EdgeName,
+    ) -> Result<Option<EdgeName>, ReverseEdgeResolverError> {
+        match self
+            .resolve_reverse_edge(tenant_id, node_type, edge_name)
+            .await
+        {
+            Ok(r_edge_name) => Ok(Some(r_edge_name)),
+            Err(ReverseEdgeResolverError::ClientError(ClientError::Status(status)))
+                if status.code() == Code::NotFound =>
+            {
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
 }
@@ -1,9 +1,18 @@
-use std::collections::HashMap;
+use std::collections::{
+    BTreeSet,
+    HashMap,
+};
+
+use bytes::Bytes;
 
 use crate::{
-    graplinc::grapl::common::v1beta1::types::Uid,
+    graplinc::{
+        common::v1beta1::SystemTime,
+        grapl::common::v1beta1::types::Uid,
+    },
     protobufs::graplinc::grapl::api::graph::v1beta1::{
         DecrementOnlyIntProp as DecrementOnlyIntPropProto,
+        DecrementOnlyTimestampProp as DecrementOnlyTimestampPropProto,
         DecrementOnlyUintProp as DecrementOnlyUintPropProto,
         Edge as EdgeProto,
         EdgeList as EdgeListProto,
@@ -13,16 +22,22 @@ use crate::{
         IdentifiedEdgeList as IdentifiedEdgeListProto,
         IdentifiedGraph as IdentifiedGraphProto,
         IdentifiedNode as IdentifiedNodeProto,
+        ImmutableBoolProp as ImmutableBoolPropProto,
+        ImmutableBytesProp as ImmutableBytesPropProto,
+        ImmutableDoubleProp as ImmutableDoublePropProto,
         ImmutableIntProp as ImmutableIntPropProto,
         ImmutableStrProp as ImmutableStrPropProto,
+        ImmutableTimestampProp as ImmutableTimestampPropProto,
         ImmutableUintProp as ImmutableUintPropProto,
         IncrementOnlyIntProp as IncrementOnlyIntPropProto,
+        IncrementOnlyTimestampProp as IncrementOnlyTimestampPropProto,
         IncrementOnlyUintProp as IncrementOnlyUintPropProto,
         Lens as LensProto,
         NodeDescription as NodeDescriptionProto,
         NodeProperty as NodePropertyProto,
         Session as SessionProto,
         Static as StaticProto,
+        StringSetProp as StringSetPropProto,
     },
     serde_impl,
     type_url,
@@ -656,6 +671,424 @@ impl_from_for_unit!(
     &std::borrow::Cow<'_, str>
 );
 
+//
+// ImmutableBoolProp
+//
+
+#[derive(Copy, Debug, PartialOrd, Ord, PartialEq, Eq, Clone, Hash)]
+pub struct ImmutableBoolProp {
+    pub prop: bool,
+}
+
+impl ImmutableBoolProp {
+    pub fn as_inner(&self) -> bool {
+        self.prop
+    }
+
+    pub fn merge_property(&mut self, other_prop: &Self) {
+        tracing::trace!(message="ImmutableBoolProp merge", self_prop=?self, other_prop=?other_prop);
+    }
+}
+
+impl From<ImmutableBoolPropProto> for ImmutableBoolProp {
+    fn from(immutable_bool_prop_proto: ImmutableBoolPropProto) -> Self {
+        ImmutableBoolProp {
+            prop: immutable_bool_prop_proto.prop,
+        }
+    }
+}
+
+impl From<ImmutableBoolProp> for ImmutableBoolPropProto {
+    fn from(immutable_bool_prop: ImmutableBoolProp) -> Self {
+        ImmutableBoolPropProto {
+            prop: immutable_bool_prop.prop,
+        }
+    }
+}
+
+impl type_url::TypeUrl for ImmutableBoolProp {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph.v1beta1.ImmutableBoolProp";
+}
+
+impl serde_impl::ProtobufSerializable for ImmutableBoolProp {
+    type ProtobufMessage = ImmutableBoolPropProto;
+}
+
+impl std::string::ToString for ImmutableBoolProp {
+    fn to_string(&self) -> String {
+        self.prop.to_string()
+    }
+}
+
+impl_from_for_unit!(ImmutableBoolProp, prop, bool, &bool);
+
+//
+// ImmutableDoubleProp
+//
+
+/// Equality is bitwise, so that `Property` can stay `Eq`: NaN equals itself.
+/// The exception is -0.0, which equals 0.0, since protobuf encodes both as
+/// the default value and so decodes -0.0 as 0.0.
+#[derive(Copy, Debug, Clone)]
+pub struct ImmutableDoubleProp {
+    pub prop: f64,
+}
+
+impl ImmutableDoubleProp {
+    pub fn as_inner(&self) -> f64 {
+        self.prop
+    }
+
+    pub fn merge_property(&mut self, other_prop: &Self) {
+        tracing::trace!(message="ImmutableDoubleProp merge", self_prop=?self, other_prop=?other_prop);
+    }
+}
+
+impl PartialEq for ImmutableDoubleProp {
+    fn eq(&self, other: &Self) -> bool {
+        let bits = |prop: f64| if prop == 0.0 { 0 } else { prop.to_bits() };
+        bits(self.prop) == bits(other.prop)
+    }
+}
+
+impl Eq for ImmutableDoubleProp {}
+
+impl From<ImmutableDoublePropProto> for ImmutableDoubleProp {
+    fn from(immutable_double_prop_proto: ImmutableDoublePropProto) -> Self {
+        ImmutableDoubleProp {
+            prop: immutable_double_prop_proto.prop,
+        }
+    }
+}
+
+impl From<ImmutableDoubleProp> for ImmutableDoublePropProto {
+    fn from(immutable_double_prop: ImmutableDoubleProp) -> Self {
+        ImmutableDoublePropProto {
+            prop: immutable_double_prop.prop,
+        }
+    }
+}
+
+impl type_url::TypeUrl for ImmutableDoubleProp {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph.v1beta1.ImmutableDoubleProp";
+}
+
+impl serde_impl::ProtobufSerializable for ImmutableDoubleProp {
+    type ProtobufMessage = ImmutableDoublePropProto;
+}
+
+impl std::string::ToString for ImmutableDoubleProp {
+    fn to_string(&self) -> String {
+        self.prop.to_string()
+    }
+}
+
+impl_from_for_unit!(ImmutableDoubleProp, prop, f64, f32, &f64, &f32);
+
+//
+// ImmutableBytesProp
+//
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct ImmutableBytesProp {
+    pub prop: Bytes,
+}
+
+impl ImmutableBytesProp {
+    pub fn as_inner(&self) -> &[u8] {
+        self.prop.as_ref()
+    }
+
+    pub fn merge_property(&mut self, other_prop: &Self) {
+        tracing::trace!(message="ImmutableBytesProp merge", self_prop=?self, other_prop=?other_prop);
+    }
+}
+
+impl From<ImmutableBytesPropProto> for ImmutableBytesProp {
+    fn from(immutable_bytes_prop_proto: ImmutableBytesPropProto) -> Self {
+        ImmutableBytesProp {
+            prop: immutable_bytes_prop_proto.prop,
+        }
+    }
+}
+
+impl From<ImmutableBytesProp> for ImmutableBytesPropProto {
+    fn from(immutable_bytes_prop: ImmutableBytesProp) -> Self {
+        ImmutableBytesPropProto {
+            prop: immutable_bytes_prop.prop,
+        }
+    }
+}
+
+impl type_url::TypeUrl for ImmutableBytesProp {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph.v1beta1.ImmutableBytesProp";
+}
+
+impl serde_impl::ProtobufSerializable for ImmutableBytesProp {
+    type ProtobufMessage = ImmutableBytesPropProto;
+}
+
+impl std::string::ToString for ImmutableBytesProp {
+    /// Lowercase hex, which is how hashes are usually written
+    fn to_string(&self) -> String {
+        self.prop.iter().map(|byte| format!("{byte:02x}")).collect()
+    }
+}
+
+impl_from_for_unit!(ImmutableBytesProp, prop, Bytes, Vec<u8>, &[u8]);
+
+//
+// ImmutableTimestampProp
+//
+
+#[derive(Copy, Debug, PartialOrd, Ord, PartialEq, Eq, Clone, Hash)]
+pub struct ImmutableTimestampProp {
+    pub prop: SystemTime,
+}
+
+impl ImmutableTimestampProp {
+    pub fn as_inner(&self) -> SystemTime {
+        self.prop
+    }
+
+    pub fn merge_property(&mut self, other_prop: &Self) {
+        tracing::trace!(message="ImmutableTimestampProp merge", self_prop=?self, other_prop=?other_prop);
+    }
+}
+
+impl TryFrom<ImmutableTimestampPropProto> for ImmutableTimestampProp {
+    type Error = SerDeError;
+
+    fn try_from(
+        immutable_timestamp_prop_proto: ImmutableTimestampPropProto,
+    ) -> Result<Self, Self::Error> {
+        Ok(ImmutableTimestampProp {
+            prop: immutable_timestamp_prop_proto
+                .prop
+                .ok_or(SerDeError::MissingField("ImmutableTimestampProp.prop"))?
+                .try_into()?,
+        })
+    }
+}
+
+impl From<ImmutableTimestampProp> for ImmutableTimestampPropProto {
+    fn from(immutable_timestamp_prop: ImmutableTimestampProp) -> Self {
+        ImmutableTimestampPropProto {
+            prop: Some(immutable_timestamp_prop.prop.try_into().unwrap()), // this can never actually fail
+        }
+    }
+}
+
+impl type_url::TypeUrl for ImmutableTimestampProp {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph.v1beta1.ImmutableTimestampProp";
+}
+
+impl serde_impl::ProtobufSerializable for ImmutableTimestampProp {
+    type ProtobufMessage = ImmutableTimestampPropProto;
+}
+
+impl std::string::ToString for ImmutableTimestampProp {
+    fn to_string(&self) -> String {
+        timestamp_to_string(self.prop)
+    }
+}
+
+impl_from_for_unit!(ImmutableTimestampProp, prop, SystemTime, &SystemTime);
+
+//
+// IncrementOnlyTimestampProp
+//
+
+#[derive(Copy, Debug, PartialOrd, Ord, PartialEq, Eq, Clone, Hash)]
+pub struct IncrementOnlyTimestampProp {
+    pub prop: SystemTime,
+}
+
+impl IncrementOnlyTimestampProp {
+    pub fn as_inner(&self) -> SystemTime {
+        self.prop
+    }
+
+    pub fn merge_property(&mut self, other_prop: &Self) {
+        tracing::trace!(message="IncrementOnlyTimestampProp merge", self_prop=?self, other_prop=?other_prop);
+        self.prop = std::cmp::max(self.prop, other_prop.prop);
+    }
+}
+
+impl TryFrom<IncrementOnlyTimestampPropProto> for IncrementOnlyTimestampProp {
+    type Error = SerDeError;
+
+    fn try_from(
+        increment_only_timestamp_prop_proto: IncrementOnlyTimestampPropProto,
+    ) -> Result<Self, Self::Error> {
+        Ok(IncrementOnlyTimestampProp {
+            prop: increment_only_timestamp_prop_proto
+                .prop
+                .ok_or(SerDeError::MissingField("IncrementOnlyTimestampProp.prop"))?
+                .try_into()?,
+        })
+    }
+}
+
+impl From<IncrementOnlyTimestampProp> for IncrementOnlyTimestampPropProto {
+    fn from(increment_only_timestamp_prop: IncrementOnlyTimestampProp) -> Self {
+        IncrementOnlyTimestampPropProto {
+            prop: Some(increment_only_timestamp_prop.prop.try_into().unwrap()), // this can never actually fail
+        }
+    }
+}
+
+impl type_url::TypeUrl for IncrementOnlyTimestampProp {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph.v1beta1.IncrementOnlyTimestampProp";
+}
+
+impl serde_impl::ProtobufSerializable for IncrementOnlyTimestampProp {
+    type ProtobufMessage = IncrementOnlyTimestampPropProto;
+}
+
+impl std::string::ToString for IncrementOnlyTimestampProp {
+    fn to_string(&self) -> String {
+        timestamp_to_string(self.prop)
+    }
+}
+
+impl_from_for_unit!(IncrementOnlyTimestampProp, prop, SystemTime, &SystemTime);
+
+//
+// DecrementOnlyTimestampProp
+//
+
+#[derive(Copy, Debug, PartialOrd, Ord, PartialEq, Eq, Clone, Hash)]
+pub struct DecrementOnlyTimestampProp {
+    pub prop: SystemTime,
+}
+
+impl DecrementOnlyTimestampProp {
+    pub fn as_inner(&self) -> SystemTime {
+        self.prop
+    }
+
+    pub fn merge_property(&mut self, other_prop: &Self) {
+        tracing::trace!(message="DecrementOnlyTimestampProp merge", self_prop=?self, other_prop=?other_prop);
+        self.prop = std::cmp::min(self.prop, other_prop.prop);
+    }
+}
+
+impl TryFrom<DecrementOnlyTimestampPropProto> for DecrementOnlyTimestampProp {
+    type Error = SerDeError;
+
+    fn try_from(
+        decrement_only_timestamp_prop_proto: DecrementOnlyTimestampPropProto,
+    ) -> Result<Self, Self::Error> {
+        Ok(DecrementOnlyTimestampProp {
+            prop: decrement_only_timestamp_prop_proto
+                .prop
+                .ok_or(SerDeError::MissingField("DecrementOnlyTimestampProp.prop"))?
+                .try_into()?,
+        })
+    }
+}
+
+impl From<DecrementOnlyTimestampProp> for DecrementOnlyTimestampPropProto {
+    fn from(decrement_only_timestamp_prop: DecrementOnlyTimestampProp) -> Self {
+        DecrementOnlyTimestampPropProto {
+            prop: Some(decrement_only_timestamp_prop.prop.try_into().unwrap()), // this can never actually fail
+        }
+    }
+}
+
+impl type_url::TypeUrl for DecrementOnlyTimestampProp {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph.v1beta1.DecrementOnlyTimestampProp";
+}
+
+impl serde_impl::ProtobufSerializable for DecrementOnlyTimestampProp {
+    type ProtobufMessage = DecrementOnlyTimestampPropProto;
+}
+
+impl std::string::ToString for DecrementOnlyTimestampProp {
+    fn to_string(&self) -> String {
+        timestamp_to_string(self.prop)
+    }
+}
+
+impl_from_for_unit!(DecrementOnlyTimestampProp, prop, SystemTime, &SystemTime);
+
+/// Milliseconds since (or, if negative, before) unix epoch
+fn timestamp_to_string(timestamp: SystemTime) -> String {
+    match timestamp.duration_since(std::time::UNIX_EPOCH) {
+        Ok(since_epoch) => since_epoch.as_millis().to_string(),
+        Err(e) => format!("-{}", e.duration().as_millis()),
+    }
+}
+
+//
+// StringSetProp
+//
+
+#[derive(Debug, Default, PartialEq, Eq, Clone, Hash)]
+pub struct StringSetProp {
+    pub prop: BTreeSet<String>,
+}
+
+impl StringSetProp {
+    pub fn as_inner(&self) -> &BTreeSet<String> {
+        &self.prop
+    }
+
+    pub fn merge_property(&mut self, other_prop: &Self) {
+        tracing::trace!(message="StringSetProp merge", self_prop=?self, other_prop=?other_prop);
+        self.prop.extend(other_prop.prop.iter().cloned());
+    }
+}
+
+impl From<StringSetPropProto> for StringSetProp {
+    fn from(string_set_prop_proto: StringSetPropProto) -> Self {
+        StringSetProp {
+            prop: string_set_prop_proto.prop.into_iter().collect(),
+        }
+    }
+}
+
+impl From<StringSetProp> for StringSetPropProto {
+    fn from(string_set_prop: StringSetProp) -> Self {
+        StringSetPropProto {
+            prop: string_set_prop.prop.into_iter().collect(),
+        }
+    }
+}
+
+impl type_url::TypeUrl for StringSetProp {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph.v1beta1.StringSetProp";
+}
+
+impl serde_impl::ProtobufSerializable for StringSetProp {
+    type ProtobufMessage = StringSetPropProto;
+}
+
+impl std::string::ToString for StringSetProp {
+    fn to_string(&self) -> String {
+        format!("{:?}", self.prop)
+    }
+}
+
+impl<S> FromIterator<S> for StringSetProp
+where
+    S: Into<String>,
+{
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self {
+        StringSetProp {
+            prop: iter.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
 //
 // NodeProperty
 //
@@ -669,6 +1102,13 @@ pub enum Property {
     DecrementOnlyIntProp(DecrementOnlyIntProp),
     ImmutableIntProp(ImmutableIntProp),
     ImmutableStrProp(ImmutableStrProp),
+    ImmutableBoolProp(ImmutableBoolProp),
+    ImmutableDoubleProp(ImmutableDoubleProp),
+    ImmutableBytesProp(ImmutableBytesProp),
+    ImmutableTimestampProp(ImmutableTimestampProp),
+    IncrementOnlyTimestampProp(IncrementOnlyTimestampProp),
+    DecrementOnlyTimestampProp(DecrementOnlyTimestampProp),
+    StringSetProp(StringSetProp),
 }
 
 impl Property {
@@ -702,6 +1142,34 @@ impl Property {
                 Property::ImmutableStrProp(ref mut self_prop),
                 Property::ImmutableStrProp(ref other_prop),
             ) => self_prop.merge_property(other_prop),
+            (
+                Property::ImmutableBoolProp(ref mut self_prop),
+                Property::ImmutableBoolProp(ref other_prop),
+            ) => self_prop.merge_property(other_prop),
+            (
+                Property::ImmutableDoubleProp(ref mut self_prop),
+                Property::ImmutableDoubleProp(ref other_prop),
+            ) => self_prop.merge_property(other_prop),
+            (
+                Property::ImmutableBytesProp(ref mut self_prop),
+                Property::ImmutableBytesProp(ref other_prop),
+            ) => self_prop.merge_property(other_prop),
+            (
+                Property::ImmutableTimestampProp(ref mut self_prop),
+                Property::ImmutableTimestampProp(ref other_prop),
+            ) => self_prop.merge_property(other_prop),
+            (
+                Property::IncrementOnlyTimestampProp(ref mut self_prop),
+                Property::IncrementOnlyTimestampProp(ref other_prop),
+            ) => self_prop.merge_property(other_prop),
+            (
+                Property::DecrementOnlyTimestampProp(ref mut self_prop),
+                Property::DecrementOnlyTimestampProp(ref other_prop),
+            ) => self_prop.merge_property(other_prop),
+            (
+                Property::StringSetProp(ref mut self_prop),
+                Property::StringSetProp(ref other_prop),
+            ) => self_prop.merge_property(other_prop),
             // technically we could improve type safety here by exhausting the combinations,
             // but I'm not going to type that all out right now
             // TODO: figure out what this comment means ^^
@@ -758,6 +1226,48 @@ impl From<ImmutableStrProp> for Property {
     }
 }
 
+impl From<ImmutableBoolProp> for Property {
+    fn from(p: ImmutableBoolProp) -> Self {
+        Self::ImmutableBoolProp(p)
+    }
+}
+
+impl From<ImmutableDoubleProp> for Property {
+    fn from(p: ImmutableDoubleProp) -> Self {
+        Self::ImmutableDoubleProp(p)
+    }
+}
+
+impl From<ImmutableBytesProp> for Property {
+    fn from(p: ImmutableBytesProp) -> Self {
+        Self::ImmutableBytesProp(p)
+    }
+}
+
+impl From<ImmutableTimestampProp> for Property {
+    fn from(p: ImmutableTimestampProp) -> Self {
+        Self::ImmutableTimestampProp(p)
+    }
+}
+
+impl From<IncrementOnlyTimestampProp> for Property {
+    fn from(p: IncrementOnlyTimestampProp) -> Self {
+        Self::IncrementOnlyTimestampProp(p)
+    }
+}
+
+impl From<DecrementOnlyTimestampProp> for Property {
+    fn from(p: DecrementOnlyTimestampProp) -> Self {
+        Self::DecrementOnlyTimestampProp(p)
+    }
+}
+
+impl From<StringSetProp> for Property {
+    fn from(p: StringSetProp) -> Self {
+        Self::StringSetProp(p)
+    }
+}
+
 impl std::string::ToString for Property {
     fn to_string(&self) -> String {
         match self {
@@ -776,6 +1286,21 @@ impl std::string::ToString for Property {
             }
             Property::ImmutableIntProp(immutable_int_prop) => immutable_int_prop.to_string(),
             Property::ImmutableStrProp(immutable_str_prop) => immutable_str_prop.to_string(),
+            Property::ImmutableBoolProp(immutable_bool_prop) => immutable_bool_prop.to_string(),
+            Property::ImmutableDoubleProp(immutable_double_prop) => {
+                immutable_double_prop.to_string()
+            }
+            Property::ImmutableBytesProp(immutable_bytes_prop) => immutable_bytes_prop.to_string(),
+            Property::ImmutableTimestampProp(immutable_timestamp_prop) => {
+                immutable_timestamp_prop.to_string()
+            }
+            Property::IncrementOnlyTimestampProp(increment_only_timestamp_prop) => {
+                increment_only_timestamp_prop.to_string()
+            }
+            Property::DecrementOnlyTimestampProp(decrement_only_timestamp_prop) => {
+                decrement_only_timestamp_prop.to_string()
+            }
+            Property::StringSetProp(string_set_prop) => string_set_prop.to_string(),
         }
     }
 }
@@ -838,6 +1363,55 @@ impl NodeProperty {
             _ => None,
         }
     }
+
+    pub fn as_immutable_bool(&self) -> Option<ImmutableBoolProp> {
+        match self.property {
+            Property::ImmutableBoolProp(ref prop) => Some(*prop),
+            _ => None,
+        }
+    }
+
+    pub fn as_immutable_double(&self) -> Option<ImmutableDoubleProp> {
+        match self.property {
+            Property::ImmutableDoubleProp(ref prop) => Some(*prop),
+            _ => None,
+        }
+    }
+
+    pub fn as_immutable_bytes(&self) -> Option<&ImmutableBytesProp> {
+        match self.property {
+            Property::ImmutableBytesProp(ref prop) => Some(prop),
+            _ => None,
+        }
+    }
+
+    pub fn as_immutable_timestamp(&self) -> Option<ImmutableTimestampProp> {
+        match self.property {
+            Property::ImmutableTimestampProp(ref prop) => Some(*prop),
+            _ => None,
+        }
+    }
+
+    pub fn as_increment_only_timestamp(&self) -> Option<IncrementOnlyTimestampProp> {
+        match self.property {
+            Property::IncrementOnlyTimestampProp(ref prop) => Some(*prop),
+            _ => None,
+        }
+    }
+
+    pub fn as_decrement_only_timestamp(&self) -> Option<DecrementOnlyTimestampProp> {
+        match self.property {
+            Property::DecrementOnlyTimestampProp(ref prop) => Some(*prop),
+            _ => None,
+        }
+    }
+
+    pub fn as_string_set(&self) -> Option<&StringSetProp> {
+        match self.property {
+            Property::StringSetProp(ref prop) => Some(prop),
+            _ => None,
+        }
+    }
 }
 
 impl TryFrom<NodePropertyProto> for NodeProperty {
@@ -908,6 +1482,69 @@ impl TryFrom<NodePropertyProto> for NodeProperty {
                     immutable_str_prop_proto.into()
                 )
             }),
+            Some(
+                crate::protobufs::graplinc::grapl::api::graph::v1beta1::node_property::Property::ImmutableBool(
+                    immutable_bool_prop_proto
+                )
+            ) => Ok(NodeProperty {
+                property: Property::ImmutableBoolProp(
+                    immutable_bool_prop_proto.into()
+                )
+            }),
+            Some(
+                crate::protobufs::graplinc::grapl::api::graph::v1beta1::node_property::Property::ImmutableDouble(
+                    immutable_double_prop_proto
+                )
+            ) => Ok(NodeProperty {
+                property: Property::ImmutableDoubleProp(
+                    immutable_double_prop_proto.into()
+                )
+            }),
+            Some(
+                crate::protobufs::graplinc::grapl::api::graph::v1beta1::node_property::Property::ImmutableBytes(
+                    immutable_bytes_prop_proto
+                )
+            ) => Ok(NodeProperty {
+                property: Property::ImmutableBytesProp(
+                    immutable_bytes_prop_proto.into()
+                )
+            }),
+            Some(
+                crate::protobufs::graplinc::grapl::api::graph::v1beta1::node_property::Property::ImmutableTimestamp(
+                    immutable_timestamp_prop_proto
+                )
+            ) => Ok(NodeProperty {
+                property: Property::ImmutableTimestampProp(
+                    immutable_timestamp_prop_proto.try_into()?
+                )
+            }),
+            Some(
+                crate::protobufs::graplinc::grapl::api::graph::v1beta1::node_property::Property::IncrementOnlyTimestamp(
+                    increment_only_timestamp_prop_proto
+                )
+            ) => Ok(NodeProperty {
+                property: Property::IncrementOnlyTimestampProp(
+                    increment_only_timestamp_prop_proto.try_into()?
+                )
+            }),
+            Some(
+                crate::protobufs::graplinc::grapl::api::graph::v1beta1::node_property::Property::DecrementOnlyTimestamp(
+                    decrement_only_timestamp_prop_proto
+                )
+            ) => Ok(NodeProperty {
+                property: Property::DecrementOnlyTimestampProp(
+                    decrement_only_timestamp_prop_proto.try_into()?
+                )
+            }),
+            Some(
+                crate::protobufs::graplinc::grapl::api::graph::v1beta1::node_property::Property::StringSet(
+                    string_set_prop_proto
+                )
+            ) => Ok(NodeProperty {
+                property: Property::StringSetProp(
+                    string_set_prop_proto.into()
+                )
+            }),
             None => Err(SerDeError::MissingField("property")),
         }
     }
@@ -965,6 +1602,55 @@ impl From<NodeProperty> for NodePropertyProto {
                     )
                 )
             },
+            Property::ImmutableBoolProp(immutable_bool_prop) => NodePropertyProto {
+                property: Some(
+                    crate::protobufs::graplinc::grapl::api::graph::v1beta1::node_property::Property::ImmutableBool(
+                        immutable_bool_prop.into()
+                    )
+                )
+            },
+            Property::ImmutableDoubleProp(immutable_double_prop) => NodePropertyProto {
+                property: Some(
+                    crate::protobufs::graplinc::grapl::api::graph::v1beta1::node_property::Property::ImmutableDouble(
+                        immutable_double_prop.into()
+                    )
+                )
+            },
+            Property::ImmutableBytesProp(immutable_bytes_prop) => NodePropertyProto {
+                property: Some(
+                    crate::protobufs::graplinc::grapl::api::graph::v1beta1::node_property::Property::ImmutableBytes(
+                        immutable_bytes_prop.into()
+                    )
+                )
+            },
+            Property::ImmutableTimestampProp(immutable_timestamp_prop) => NodePropertyProto {
+                property: Some(
+                    crate::protobufs::graplinc::grapl::api::graph::v1beta1::node_property::Property::ImmutableTimestamp(
+                        immutable_timestamp_prop.into()
+                    )
+                )
+            },
+            Property::IncrementOnlyTimestampProp(increment_only_timestamp_prop) => NodePropertyProto {
+                property: Some(
+                    crate::protobufs::graplinc::grapl::api::graph::v1beta1::node_property::Property::IncrementOnlyTimestamp(
+                        increment_only_timestamp_prop.into()
+                    )
+                )
+            },
+            Property::DecrementOnlyTimestampProp(decrement_only_timestamp_prop) => NodePropertyProto {
+                property: Some(
+                    crate::protobufs::graplinc::grapl::api::graph::v1beta1::node_property::Property::DecrementOnlyTimestamp(
+                        decrement_only_timestamp_prop.into()
+                    )
+                )
+            },
+            Property::StringSetProp(string_set_prop) => NodePropertyProto {
+                property: Some(
+                    crate::protobufs::graplinc::grapl::api::graph::v1beta1::node_property::Property::StringSet(
+                        string_set_prop.into()
+                    )
+                )
+            },
         }
     }
 }
@@ -996,6 +1682,21 @@ impl std::string::ToString for NodeProperty {
             }
             Property::ImmutableIntProp(immutable_int_prop) => immutable_int_prop.to_string(),
             Property::ImmutableStrProp(immutable_str_prop) => immutable_str_prop.to_string(),
+            Property::ImmutableBoolProp(immutable_bool_prop) => immutable_bool_prop.to_string(),
+            Property::ImmutableDoubleProp(immutable_double_prop) => {
+                immutable_double_prop.to_string()
+            }
+            Property::ImmutableBytesProp(immutable_bytes_prop) => immutable_bytes_prop.to_string(),
+            Property::ImmutableTimestampProp(immutable_timestamp_prop) => {
+                immutable_timestamp_prop.to_string()
+            }
+            Property::IncrementOnlyTimestampProp(increment_only_timestamp_prop) => {
+                increment_only_timestamp_prop.to_string()
+            }
+            Property::DecrementOnlyTimestampProp(decrement_only_timestamp_prop) => {
+                decrement_only_timestamp_prop.to_string()
+            }
+            Property::StringSetProp(string_set_prop) => string_set_prop.to_string(),
         }
     }
 }
@@ -1722,6 +2423,66 @@ pub mod test {
         }
     }
 
+    impl Arbitrary for ImmutableBoolProp {
+        fn arbitrary(g: &mut Gen) -> Self {
+            Self {
+                prop: bool::arbitrary(g),
+            }
+        }
+    }
+
+    impl Arbitrary for ImmutableDoubleProp {
+        fn arbitrary(g: &mut Gen) -> Self {
+            Self {
+                prop: f64::arbitrary(g),
+            }
+        }
+    }
+
+    impl Arbitrary for ImmutableBytesProp {
+        fn arbitrary(g: &mut Gen) -> Self {
+            Self {
+                prop: Vec::<u8>::arbitrary(g).into(),
+            }
+        }
+    }
+
+    fn arbitrary_timestamp(g: &mut Gen) -> SystemTime {
+        std::time::UNIX_EPOCH + std::time::Duration::from_millis(u32::arbitrary(g).into())
+    }
+
+    impl Arbitrary for ImmutableTimestampProp {
+        fn arbitrary(g: &mut Gen) -> Self {
+            Self {
+                prop: arbitrary_timestamp(g),
+            }
+        }
+    }
+
+    impl Arbitrary for IncrementOnlyTimestampProp {
+        fn arbitrary(g: &mut Gen) -> Self {
+            Self {
+                prop: arbitrary_timestamp(g),
+            }
+        }
+    }
+
+    impl Arbitrary for DecrementOnlyTimestampProp {
+        fn arbitrary(g: &mut Gen) -> Self {
+            Self {
+                prop: arbitrary_timestamp(g),
+            }
+        }
+    }
+
+    impl Arbitrary for StringSetProp {
+        fn arbitrary(g: &mut Gen) -> Self {
+            Self {
+                prop: BTreeSet::arbitrary(g),
+            }
+        }
+    }
+
     impl Arbitrary for Property {
         fn arbitrary(g: &mut Gen) -> Self {
             let props = &[
@@ -1732,6 +2493,13 @@ pub mod test {
                 Property::DecrementOnlyUintProp(DecrementOnlyUintProp::arbitrary(g)),
                 Property::ImmutableUintProp(ImmutableUintProp::arbitrary(g)),
                 Property::ImmutableStrProp(ImmutableStrProp::arbitrary(g)),
+                Property::ImmutableBoolProp(ImmutableBoolProp::arbitrary(g)),
+                Property::ImmutableDoubleProp(ImmutableDoubleProp::arbitrary(g)),
+                Property::ImmutableBytesProp(ImmutableBytesProp::arbitrary(g)),
+                Property::ImmutableTimestampProp(ImmutableTimestampProp::arbitrary(g)),
+                Property::IncrementOnlyTimestampProp(IncrementOnlyTimestampProp::arbitrary(g)),
+                Property::DecrementOnlyTimestampProp(DecrementOnlyTimestampProp::arbitrary(g)),
+                Property::StringSetProp(StringSetProp::arbitrary(g)),
             ];
             g.choose(props).unwrap().clone()
         }
@@ -1868,6 +2636,41 @@ pub mod test {
         assert_eq!(x.clone(), std::cmp::min(x, y));
     }
 
+    #[quickcheck]
+    fn test_merge_immutable_double(mut x: ImmutableDoubleProp, y: ImmutableDoubleProp) {
+        init_test_env();
+        let original = x;
+        x.merge_property(&y);
+        assert_eq!(x, original);
+    }
+
+    #[quickcheck]
+    fn test_merge_timestamp_max(mut x: IncrementOnlyTimestampProp, y: IncrementOnlyTimestampProp) {
+        init_test_env();
+        x.merge_property(&y);
+        assert_eq!(x, std::cmp::max(x, y));
+    }
+
+    #[quickcheck]
+    fn test_merge_timestamp_min(mut x: DecrementOnlyTimestampProp, y: DecrementOnlyTimestampProp) {
+        init_test_env();
+        x.merge_property(&y);
+        assert_eq!(x, std::cmp::min(x, y));
+    }
+
+    #[quickcheck]
+    fn test_merge_string_set_union(x: StringSetProp, y: StringSetProp) {
+        init_test_env();
+        let mut x_then_y = x.clone();
+        x_then_y.merge_property(&y);
+        let mut y_then_x = y.clone();
+        y_then_x.merge_property(&x);
+
+        assert_eq!(x_then_y, y_then_x);
+        assert!(x.prop.is_subset(&x_then_y.prop));
+        assert!(y.prop.is_subset(&x_then_y.prop));
+    }
+
     #[quickcheck]
     fn test_merge_incr_uint_commutative(mut properties: Vec<IncrementOnlyUintProp>) {
         init_test_env();
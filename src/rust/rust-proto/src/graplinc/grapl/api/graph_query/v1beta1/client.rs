@@ -61,4 +61,49 @@ impl GraphQueryClient {
             )
             .await
     }
+
+    pub async fn search_nodes(
+        &mut self,
+        request: native::SearchNodesRequest,
+    ) -> Result<native::SearchNodesResponse, ClientError> {
+        self.client
+            .execute(
+                request,
+                None,
+                |status| status.code() == tonic::Code::Unavailable,
+                10,
+                |mut client, request| async move { client.search_nodes(request).await },
+            )
+            .await
+    }
+
+    pub async fn aggregate_graph_from_uid(
+        &mut self,
+        request: native::AggregateGraphFromUidRequest,
+    ) -> Result<native::AggregateGraphFromUidResponse, ClientError> {
+        self.client
+            .execute(
+                request,
+                None,
+                |status| status.code() == tonic::Code::Unavailable,
+                10,
+                |mut client, request| async move { client.aggregate_graph_from_uid(request).await },
+            )
+            .await
+    }
+
+    pub async fn query_graph_with_text(
+        &mut self,
+        request: native::QueryGraphWithTextRequest,
+    ) -> Result<native::QueryGraphWithTextResponse, ClientError> {
+        self.client
+            .execute(
+                request,
+                None,
+                |status| status.code() == tonic::Code::Unavailable,
+                10,
+                |mut client, request| async move { client.query_graph_with_text(request).await },
+            )
+            .await
+    }
 }
@@ -1,5 +1,9 @@
-use std::collections::hash_map::Entry;
+use std::{
+    collections::hash_map::Entry,
+    time::Duration,
+};
 
+use bytes::Bytes;
 use rustc_hash::{
     FxHashMap,
     FxHashSet,
@@ -189,11 +193,37 @@ impl serde_impl::ProtobufSerializable for AndIntFilters {
     type ProtobufMessage = proto::AndIntFilters;
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+impl From<Vec<IntCmp>> for AndIntFilters {
+    fn from(cmps: Vec<IntCmp>) -> AndIntFilters {
+        AndIntFilters {
+            int_filters: cmps.into_iter().map(IntFilter::from).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
 pub struct OrIntFilters {
     pub and_int_filters: Vec<AndIntFilters>,
 }
 
+impl OrIntFilters {
+    pub fn new() -> Self {
+        Self {
+            and_int_filters: Vec::new(),
+        }
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            and_int_filters: Vec::with_capacity(capacity),
+        }
+    }
+
+    pub fn push(&mut self, filters: AndIntFilters) {
+        self.and_int_filters.push(filters);
+    }
+}
+
 impl TryFrom<proto::OrIntFilters> for OrIntFilters {
     type Error = SerDeError;
     fn try_from(value: proto::OrIntFilters) -> Result<Self, Self::Error> {
@@ -226,11 +256,283 @@ impl serde_impl::ProtobufSerializable for OrIntFilters {
     type ProtobufMessage = proto::OrIntFilters;
 }
 
+// Higher level helper
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IntCmp {
+    Eq(i64, bool),
+    Lt(i64, bool),
+    Lte(i64, bool),
+    Gt(i64, bool),
+    Gte(i64, bool),
+    Has,
+}
+
+impl From<&IntFilter> for IntCmp {
+    fn from(int_filter: &IntFilter) -> IntCmp {
+        let (value, negated) = (int_filter.value, int_filter.negated);
+        match int_filter.operation {
+            IntOperation::Has => IntCmp::Has,
+            IntOperation::Equal => IntCmp::Eq(value, negated),
+            IntOperation::LessThan => IntCmp::Lt(value, negated),
+            IntOperation::LessThanOrEqual => IntCmp::Lte(value, negated),
+            IntOperation::GreaterThan => IntCmp::Gt(value, negated),
+            IntOperation::GreaterThanOrEqual => IntCmp::Gte(value, negated),
+        }
+    }
+}
+
+impl From<IntCmp> for IntFilter {
+    fn from(int_cmp: IntCmp) -> IntFilter {
+        let (operation, value, negated) = match int_cmp {
+            IntCmp::Has => (IntOperation::Has, 0, false),
+            IntCmp::Eq(value, negated) => (IntOperation::Equal, value, negated),
+            IntCmp::Lt(value, negated) => (IntOperation::LessThan, value, negated),
+            IntCmp::Lte(value, negated) => (IntOperation::LessThanOrEqual, value, negated),
+            IntCmp::Gt(value, negated) => (IntOperation::GreaterThan, value, negated),
+            IntCmp::Gte(value, negated) => (IntOperation::GreaterThanOrEqual, value, negated),
+        };
+        IntFilter {
+            operation,
+            value,
+            negated,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum FloatOperation {
+    Has,
+    Equal,
+    LessThan,
+    LessThanOrEqual,
+    GreaterThan,
+    GreaterThanOrEqual,
+}
+
+impl TryFrom<proto::float_filter::Operation> for FloatOperation {
+    type Error = SerDeError;
+    fn try_from(value_proto: proto::float_filter::Operation) -> Result<Self, Self::Error> {
+        match value_proto {
+            proto::float_filter::Operation::Unspecified => {
+                Err(SerDeError::UnknownVariant("FloatOperation"))
+            }
+            proto::float_filter::Operation::Has => Ok(Self::Has),
+            proto::float_filter::Operation::Equal => Ok(Self::Equal),
+            proto::float_filter::Operation::LessThan => Ok(Self::LessThan),
+            proto::float_filter::Operation::LessThanOrEqual => Ok(Self::LessThanOrEqual),
+            proto::float_filter::Operation::GreaterThan => Ok(Self::GreaterThan),
+            proto::float_filter::Operation::GreaterThanOrEqual => Ok(Self::GreaterThanOrEqual),
+        }
+    }
+}
+
+impl From<FloatOperation> for proto::float_filter::Operation {
+    fn from(value: FloatOperation) -> Self {
+        match value {
+            FloatOperation::Has => proto::float_filter::Operation::Has,
+            FloatOperation::Equal => proto::float_filter::Operation::Equal,
+            FloatOperation::LessThan => proto::float_filter::Operation::LessThan,
+            FloatOperation::LessThanOrEqual => proto::float_filter::Operation::LessThanOrEqual,
+            FloatOperation::GreaterThan => proto::float_filter::Operation::GreaterThan,
+            FloatOperation::GreaterThanOrEqual => {
+                proto::float_filter::Operation::GreaterThanOrEqual
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FloatFilter {
+    pub operation: FloatOperation,
+    pub value: f64,
+    pub negated: bool,
+}
+
+impl TryFrom<proto::FloatFilter> for FloatFilter {
+    type Error = SerDeError;
+
+    fn try_from(value_proto: proto::FloatFilter) -> Result<Self, Self::Error> {
+        let operation = value_proto.operation().try_into()?;
+        let value = value_proto.value;
+        let negated = value_proto.negated;
+        Ok(Self {
+            operation,
+            value,
+            negated,
+        })
+    }
+}
+
+impl From<FloatFilter> for proto::FloatFilter {
+    fn from(value: FloatFilter) -> proto::FloatFilter {
+        let operation: proto::float_filter::Operation = value.operation.into();
+        proto::FloatFilter {
+            operation: operation as i32,
+            value: value.value,
+            negated: value.negated,
+        }
+    }
+}
+
+impl type_url::TypeUrl for FloatFilter {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph_query_service.v1beta1.FloatFilter";
+}
+
+impl serde_impl::ProtobufSerializable for FloatFilter {
+    type ProtobufMessage = proto::FloatFilter;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AndFloatFilters {
+    pub float_filters: Vec<FloatFilter>,
+}
+
+impl TryFrom<proto::AndFloatFilters> for AndFloatFilters {
+    type Error = SerDeError;
+    fn try_from(value: proto::AndFloatFilters) -> Result<Self, Self::Error> {
+        let float_filters = value
+            .float_filters
+            .into_iter()
+            .map(FloatFilter::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { float_filters })
+    }
+}
+
+impl From<AndFloatFilters> for proto::AndFloatFilters {
+    fn from(value: AndFloatFilters) -> Self {
+        Self {
+            float_filters: value
+                .float_filters
+                .into_iter()
+                .map(proto::FloatFilter::from)
+                .collect(),
+        }
+    }
+}
+
+impl type_url::TypeUrl for AndFloatFilters {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph_query_service.v1beta1.AndFloatFilters";
+}
+
+impl serde_impl::ProtobufSerializable for AndFloatFilters {
+    type ProtobufMessage = proto::AndFloatFilters;
+}
+
+impl From<Vec<FloatCmp>> for AndFloatFilters {
+    fn from(cmps: Vec<FloatCmp>) -> AndFloatFilters {
+        AndFloatFilters {
+            float_filters: cmps.into_iter().map(FloatFilter::from).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OrFloatFilters {
+    pub and_float_filters: Vec<AndFloatFilters>,
+}
+
+impl OrFloatFilters {
+    pub fn new() -> Self {
+        Self {
+            and_float_filters: Vec::new(),
+        }
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            and_float_filters: Vec::with_capacity(capacity),
+        }
+    }
+
+    pub fn push(&mut self, filters: AndFloatFilters) {
+        self.and_float_filters.push(filters);
+    }
+}
+
+impl TryFrom<proto::OrFloatFilters> for OrFloatFilters {
+    type Error = SerDeError;
+    fn try_from(value: proto::OrFloatFilters) -> Result<Self, Self::Error> {
+        let and_float_filters = value
+            .and_float_filters
+            .into_iter()
+            .map(AndFloatFilters::try_from)
+            .collect::<Result<_, SerDeError>>()?;
+        Ok(Self { and_float_filters })
+    }
+}
+
+impl From<OrFloatFilters> for proto::OrFloatFilters {
+    fn from(value: OrFloatFilters) -> Self {
+        let and_float_filters = value
+            .and_float_filters
+            .into_iter()
+            .map(proto::AndFloatFilters::from)
+            .collect();
+        Self { and_float_filters }
+    }
+}
+
+impl type_url::TypeUrl for OrFloatFilters {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph_query_service.v1beta1.OrFloatFilters";
+}
+
+impl serde_impl::ProtobufSerializable for OrFloatFilters {
+    type ProtobufMessage = proto::OrFloatFilters;
+}
+
+// Higher level helper
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FloatCmp {
+    Eq(f64, bool),
+    Lt(f64, bool),
+    Lte(f64, bool),
+    Gt(f64, bool),
+    Gte(f64, bool),
+    Has,
+}
+
+impl From<&FloatFilter> for FloatCmp {
+    fn from(float_filter: &FloatFilter) -> FloatCmp {
+        let (value, negated) = (float_filter.value, float_filter.negated);
+        match float_filter.operation {
+            FloatOperation::Has => FloatCmp::Has,
+            FloatOperation::Equal => FloatCmp::Eq(value, negated),
+            FloatOperation::LessThan => FloatCmp::Lt(value, negated),
+            FloatOperation::LessThanOrEqual => FloatCmp::Lte(value, negated),
+            FloatOperation::GreaterThan => FloatCmp::Gt(value, negated),
+            FloatOperation::GreaterThanOrEqual => FloatCmp::Gte(value, negated),
+        }
+    }
+}
+
+impl From<FloatCmp> for FloatFilter {
+    fn from(float_cmp: FloatCmp) -> FloatFilter {
+        let (operation, value, negated) = match float_cmp {
+            FloatCmp::Has => (FloatOperation::Has, 0.0, false),
+            FloatCmp::Eq(value, negated) => (FloatOperation::Equal, value, negated),
+            FloatCmp::Lt(value, negated) => (FloatOperation::LessThan, value, negated),
+            FloatCmp::Lte(value, negated) => (FloatOperation::LessThanOrEqual, value, negated),
+            FloatCmp::Gt(value, negated) => (FloatOperation::GreaterThan, value, negated),
+            FloatCmp::Gte(value, negated) => (FloatOperation::GreaterThanOrEqual, value, negated),
+        };
+        FloatFilter {
+            operation,
+            value,
+            negated,
+        }
+    }
+}
+
 // Higher level helper
 #[derive(Clone, Debug)]
 pub enum StrCmp<'a> {
     Eq(&'a str, bool),
     Contains(&'a str, bool),
+    Regex(&'a str, bool),
     Has,
 }
 
@@ -251,8 +553,7 @@ impl<'a> From<&'a StringFilter> for StrCmp<'a> {
                 StrCmp::Contains(string_filter.value.as_str(), string_filter.negated)
             }
             StringOperation::Regex => {
-                // todo: We don't currently support Regex, but we should
-                unimplemented!()
+                StrCmp::Regex(string_filter.value.as_str(), string_filter.negated)
             }
         }
     }
@@ -263,6 +564,7 @@ impl<'a> From<&'a StringFilter> for StrCmp<'a> {
 pub enum StringCmp {
     Eq(String, bool),
     Contains(String, bool),
+    Regex(String, bool),
     Has,
 }
 
@@ -280,10 +582,7 @@ impl From<StringFilter> for StringCmp {
             StringOperation::Contains => {
                 StringCmp::Contains(string_filter.value, string_filter.negated)
             }
-            StringOperation::Regex => {
-                // todo: We don't currently support Regex, but we should
-                unimplemented!()
-            }
+            StringOperation::Regex => StringCmp::Regex(string_filter.value, string_filter.negated),
         }
     }
 }
@@ -306,6 +605,11 @@ impl From<StringCmp> for StringFilter {
                 value,
                 negated,
             },
+            StringCmp::Regex(value, negated) => StringFilter {
+                operation: StringOperation::Regex,
+                value,
+                negated,
+            },
         }
     }
 }
@@ -612,6 +916,11 @@ pub struct NodePropertyQuery {
     pub int_filters: FxHashMap<PropertyName, OrIntFilters>,
     pub string_filters: FxHashMap<PropertyName, OrStringFilters>,
     pub uid_filters: UidFilters,
+    pub float_filters: FxHashMap<PropertyName, OrFloatFilters>,
+    /// Compared against the timestamp in milliseconds since the Unix epoch
+    pub timestamp_filters: FxHashMap<PropertyName, OrIntFilters>,
+    pub bool_filters: FxHashMap<PropertyName, bool>,
+    pub bytes_filters: FxHashMap<PropertyName, Bytes>,
 }
 
 impl NodePropertyQuery {
@@ -622,6 +931,10 @@ impl NodePropertyQuery {
             int_filters: Default::default(),
             string_filters: Default::default(),
             uid_filters: Default::default(),
+            float_filters: Default::default(),
+            timestamp_filters: Default::default(),
+            bool_filters: Default::default(),
+            bytes_filters: Default::default(),
         }
     }
 
@@ -629,6 +942,14 @@ impl NodePropertyQuery {
         debug_assert_eq!(self.query_id, other.query_id);
         debug_assert_eq!(self.node_type, other.node_type);
         self.string_filters.extend(other.string_filters);
+        self.int_filters.extend(other.int_filters);
+        self.uid_filters
+            .uid_filters
+            .extend(other.uid_filters.uid_filters);
+        self.float_filters.extend(other.float_filters);
+        self.timestamp_filters.extend(other.timestamp_filters);
+        self.bool_filters.extend(other.bool_filters);
+        self.bytes_filters.extend(other.bytes_filters);
     }
 
     pub fn with_string_filters(
@@ -643,6 +964,86 @@ impl NodePropertyQuery {
             .push(filters);
         self
     }
+
+    pub fn with_int_filters(
+        &mut self,
+        property_name: PropertyName,
+        filters: impl Into<AndIntFilters>,
+    ) -> &mut Self {
+        let filters = filters.into();
+        self.int_filters
+            .entry(property_name)
+            .or_insert_with(|| OrIntFilters::with_capacity(1))
+            .push(filters);
+        self
+    }
+
+    pub fn with_uid_filter(&mut self, uid_filter: UidFilter) -> &mut Self {
+        self.uid_filters.uid_filters.push(uid_filter);
+        self
+    }
+
+    pub fn with_float_filters(
+        &mut self,
+        property_name: PropertyName,
+        filters: impl Into<AndFloatFilters>,
+    ) -> &mut Self {
+        let filters = filters.into();
+        self.float_filters
+            .entry(property_name)
+            .or_insert_with(|| OrFloatFilters::with_capacity(1))
+            .push(filters);
+        self
+    }
+
+    /// As `with_int_filters`, comparing against the timestamp in milliseconds
+    /// since the Unix epoch.
+    pub fn with_timestamp_filters(
+        &mut self,
+        property_name: PropertyName,
+        filters: impl Into<AndIntFilters>,
+    ) -> &mut Self {
+        let filters = filters.into();
+        self.timestamp_filters
+            .entry(property_name)
+            .or_insert_with(|| OrIntFilters::with_capacity(1))
+            .push(filters);
+        self
+    }
+
+    pub fn with_bool_filter(&mut self, property_name: PropertyName, value: bool) -> &mut Self {
+        self.bool_filters.insert(property_name, value);
+        self
+    }
+
+    pub fn with_bytes_filter(
+        &mut self,
+        property_name: PropertyName,
+        value: impl Into<Bytes>,
+    ) -> &mut Self {
+        self.bytes_filters.insert(property_name, value.into());
+        self
+    }
+}
+
+/// Converts a protobuf map keyed by property name, such as
+/// `NodePropertyQuery`'s filters.
+fn property_map_try_from<P, V>(
+    map: std::collections::HashMap<String, P>,
+    field_name: &'static str,
+    convert: impl Fn(P) -> Result<V, SerDeError>,
+) -> Result<FxHashMap<PropertyName, V>, SerDeError> {
+    map.into_iter()
+        .map(|(k, v)| {
+            Ok((
+                PropertyName::try_from(k).map_err(|e| SerDeError::InvalidField {
+                    field_name,
+                    assertion: e.to_string(),
+                })?,
+                convert(v)?,
+            ))
+        })
+        .collect()
 }
 
 impl TryFrom<proto::NodePropertyQuery> for NodePropertyQuery {
@@ -686,6 +1087,19 @@ impl TryFrom<proto::NodePropertyQuery> for NodePropertyQuery {
             .ok_or(SerDeError::MissingField("uid_filters"))?
             .try_into()?;
 
+        let float_filters = property_map_try_from(
+            value.float_filters,
+            "float_filters",
+            OrFloatFilters::try_from,
+        )?;
+        let timestamp_filters = property_map_try_from(
+            value.timestamp_filters,
+            "timestamp_filters",
+            OrIntFilters::try_from,
+        )?;
+        let bool_filters = property_map_try_from(value.bool_filters, "bool_filters", Ok)?;
+        let bytes_filters = property_map_try_from(value.bytes_filters, "bytes_filters", Ok)?;
+
         let query_id = value
             .query_id
             .ok_or(SerDeError::MissingField("query_id"))?
@@ -696,6 +1110,10 @@ impl TryFrom<proto::NodePropertyQuery> for NodePropertyQuery {
             int_filters,
             string_filters,
             uid_filters,
+            float_filters,
+            timestamp_filters,
+            bool_filters,
+            bytes_filters,
         })
     }
 }
@@ -718,12 +1136,40 @@ impl From<NodePropertyQuery> for proto::NodePropertyQuery {
 
         let uid_filters = value.uid_filters.into();
 
+        let float_filters = value
+            .float_filters
+            .into_iter()
+            .map(|(k, v)| (k.value, v.into()))
+            .collect();
+
+        let timestamp_filters = value
+            .timestamp_filters
+            .into_iter()
+            .map(|(k, v)| (k.value, v.into()))
+            .collect();
+
+        let bool_filters = value
+            .bool_filters
+            .into_iter()
+            .map(|(k, v)| (k.value, v))
+            .collect();
+
+        let bytes_filters = value
+            .bytes_filters
+            .into_iter()
+            .map(|(k, v)| (k.value, v))
+            .collect();
+
         Self {
             query_id,
             node_type,
             int_filters,
             string_filters,
             uid_filters: Some(uid_filters),
+            float_filters,
+            timestamp_filters,
+            bool_filters,
+            bytes_filters,
         }
     }
 }
@@ -737,12 +1183,80 @@ impl serde_impl::ProtobufSerializable for NodePropertyQuery {
     type ProtobufMessage = proto::NodePropertyQuery;
 }
 
+/// A path of between `min_hops` and `max_hops` edges, all with the same name,
+/// to a node matching `neighbor_query_id`. Every node between the two ends of
+/// the path must match `intermediate_node_query`, if there is one.
+#[derive(Debug, Clone)]
+pub struct PathFilter {
+    pub neighbor_query_id: QueryId,
+    pub min_hops: u32,
+    pub max_hops: u32,
+    pub intermediate_node_query: Option<NodePropertyQuery>,
+}
+
+impl TryFrom<proto::PathFilter> for PathFilter {
+    type Error = SerDeError;
+    fn try_from(value: proto::PathFilter) -> Result<Self, Self::Error> {
+        let neighbor_query_id = value
+            .neighbor_query_id
+            .ok_or(SerDeError::MissingField("neighbor_query_id"))?
+            .try_into()?;
+
+        if value.min_hops == 0 {
+            return Err(SerDeError::InvalidField {
+                field_name: "min_hops",
+                assertion: "must be at least 1".to_owned(),
+            });
+        }
+        if value.max_hops < value.min_hops {
+            return Err(SerDeError::InvalidField {
+                field_name: "max_hops",
+                assertion: format!("must be at least min_hops ({})", value.min_hops),
+            });
+        }
+
+        let intermediate_node_query = value
+            .intermediate_node_query
+            .map(NodePropertyQuery::try_from)
+            .transpose()?;
+
+        Ok(Self {
+            neighbor_query_id,
+            min_hops: value.min_hops,
+            max_hops: value.max_hops,
+            intermediate_node_query,
+        })
+    }
+}
+
+impl From<PathFilter> for proto::PathFilter {
+    fn from(value: PathFilter) -> Self {
+        Self {
+            neighbor_query_id: Some(value.neighbor_query_id.into()),
+            min_hops: value.min_hops,
+            max_hops: value.max_hops,
+            intermediate_node_query: value.intermediate_node_query.map(Into::into),
+        }
+    }
+}
+
+impl type_url::TypeUrl for PathFilter {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph_query_service.v1beta1.PathFilter";
+}
+
+impl serde_impl::ProtobufSerializable for PathFilter {
+    type ProtobufMessage = proto::PathFilter;
+}
+
 #[derive(Debug, Clone)]
 pub struct GraphQuery {
     pub root_query_id: QueryId,
     pub node_property_queries: FxHashMap<QueryId, NodePropertyQuery>,
     pub edge_filters: FxHashMap<(QueryId, EdgeName), FxHashSet<QueryId>>,
     pub edge_map: FxHashMap<EdgeName, EdgeName>,
+    pub path_filters: FxHashMap<(QueryId, EdgeName), Vec<PathFilter>>,
+    pub edge_count_filters: FxHashMap<(QueryId, EdgeName), OrIntFilters>,
 }
 
 impl GraphQuery {
@@ -755,6 +1269,10 @@ impl GraphQuery {
                 int_filters: Default::default(),
                 string_filters: Default::default(),
                 uid_filters: Default::default(),
+                float_filters: Default::default(),
+                timestamp_filters: Default::default(),
+                bool_filters: Default::default(),
+                bytes_filters: Default::default(),
             },
         );
     }
@@ -841,14 +1359,68 @@ impl TryFrom<proto::GraphQuery> for GraphQuery {
             edge_map.insert(forward_edge_name, reverse_edge_name);
         }
 
-        Ok(Self {
-            root_query_id,
-            node_property_queries,
-            edge_filters,
-            edge_map,
-        })
-    }
-}
+        // Older clients don't know about path filters, so they're optional
+        let path_filters_proto = value.path_filters.unwrap_or_default();
+        let mut path_filters: FxHashMap<(QueryId, EdgeName), Vec<PathFilter>> =
+            FxHashMap::default();
+        path_filters.reserve(path_filters_proto.entries.len());
+
+        for path_entry in path_filters_proto.entries {
+            let query_id = path_entry
+                .query_id
+                .ok_or_else(|| SerDeError::MissingField("query_id"))?
+                .try_into()?;
+            let edge_name = path_entry
+                .edge_name
+                .ok_or_else(|| SerDeError::MissingField("edge_name"))?
+                .try_into()?;
+            let entry_filters = path_entry
+                .path_filters
+                .into_iter()
+                .map(PathFilter::try_from)
+                .collect::<Result<Vec<_>, _>>()?;
+            path_filters
+                .entry((query_id, edge_name))
+                .or_default()
+                .extend(entry_filters);
+        }
+
+        // As are edge count filters
+        let edge_count_filters_proto = value.edge_count_filters.unwrap_or_default();
+        let mut edge_count_filters: FxHashMap<(QueryId, EdgeName), OrIntFilters> =
+            FxHashMap::default();
+        edge_count_filters.reserve(edge_count_filters_proto.entries.len());
+
+        for edge_count_entry in edge_count_filters_proto.entries {
+            let query_id = edge_count_entry
+                .query_id
+                .ok_or_else(|| SerDeError::MissingField("query_id"))?
+                .try_into()?;
+            let edge_name = edge_count_entry
+                .edge_name
+                .ok_or_else(|| SerDeError::MissingField("edge_name"))?
+                .try_into()?;
+            let count_filters: OrIntFilters = edge_count_entry
+                .count_filters
+                .ok_or_else(|| SerDeError::MissingField("count_filters"))?
+                .try_into()?;
+            edge_count_filters
+                .entry((query_id, edge_name))
+                .or_default()
+                .and_int_filters
+                .extend(count_filters.and_int_filters);
+        }
+
+        Ok(Self {
+            root_query_id,
+            node_property_queries,
+            edge_filters,
+            edge_map,
+            path_filters,
+            edge_count_filters,
+        })
+    }
+}
 
 impl From<GraphQuery> for proto::GraphQuery {
     fn from(value: GraphQuery) -> Self {
@@ -885,11 +1457,35 @@ impl From<GraphQuery> for proto::GraphQuery {
                 })
                 .collect(),
         });
+        let path_filters = Some(proto::PathQueryMap {
+            entries: value
+                .path_filters
+                .into_iter()
+                .map(|((k0, k1), v)| proto::PathQueryEntry {
+                    query_id: Some(k0.into()),
+                    edge_name: Some(k1.into()),
+                    path_filters: v.into_iter().map(proto::PathFilter::from).collect(),
+                })
+                .collect(),
+        });
+        let edge_count_filters = Some(proto::EdgeCountQueryMap {
+            entries: value
+                .edge_count_filters
+                .into_iter()
+                .map(|((k0, k1), v)| proto::EdgeCountQueryEntry {
+                    query_id: Some(k0.into()),
+                    edge_name: Some(k1.into()),
+                    count_filters: Some(v.into()),
+                })
+                .collect(),
+        });
         Self {
             root_query_id,
             node_property_queries,
             edge_filters,
             edge_map,
+            path_filters,
+            edge_count_filters,
         }
     }
 }
@@ -1350,6 +1946,7 @@ pub struct QueryGraphWithUidRequest {
     pub tenant_id: uuid::Uuid,
     pub node_uid: Uid,
     pub graph_query: GraphQuery,
+    pub explain: bool,
 }
 
 impl TryFrom<proto::QueryGraphWithUidRequest> for QueryGraphWithUidRequest {
@@ -1369,6 +1966,7 @@ impl TryFrom<proto::QueryGraphWithUidRequest> for QueryGraphWithUidRequest {
                 .graph_query
                 .ok_or(SerDeError::MissingField("graph_query"))?
                 .try_into()?,
+            explain: value.explain,
         })
     }
 }
@@ -1379,6 +1977,7 @@ impl From<QueryGraphWithUidRequest> for proto::QueryGraphWithUidRequest {
             tenant_id: Some(value.tenant_id.into()),
             node_uid: Some(value.node_uid.into()),
             graph_query: Some(value.graph_query.into()),
+            explain: value.explain,
         }
     }
 }
@@ -1505,6 +2104,7 @@ impl serde_impl::ProtobufSerializable for MaybeMatchWithUid {
 #[derive(Debug, Clone)]
 pub struct QueryGraphWithUidResponse {
     pub maybe_match: MaybeMatchWithUid,
+    pub explanation: Option<QueryExplanation>,
 }
 
 impl TryFrom<proto::QueryGraphWithUidResponse> for QueryGraphWithUidResponse {
@@ -1515,6 +2115,7 @@ impl TryFrom<proto::QueryGraphWithUidResponse> for QueryGraphWithUidResponse {
                 .maybe_match
                 .ok_or(SerDeError::MissingField("maybe_match"))?
                 .try_into()?,
+            explanation: value.explanation.map(|e| e.try_into()).transpose()?,
         })
     }
 }
@@ -1523,6 +2124,7 @@ impl From<QueryGraphWithUidResponse> for proto::QueryGraphWithUidResponse {
     fn from(value: QueryGraphWithUidResponse) -> Self {
         Self {
             maybe_match: Some(value.maybe_match.into()),
+            explanation: value.explanation.map(Into::into),
         }
     }
 }
@@ -1536,11 +2138,184 @@ impl serde_impl::ProtobufSerializable for QueryGraphWithUidResponse {
     type ProtobufMessage = proto::QueryGraphWithUidResponse;
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShortCircuitExplanation {
+    pub uid: Uid,
+    pub reason: String,
+}
+
+impl TryFrom<proto::ShortCircuitExplanation> for ShortCircuitExplanation {
+    type Error = SerDeError;
+    fn try_from(value: proto::ShortCircuitExplanation) -> Result<Self, Self::Error> {
+        Ok(Self {
+            uid: value
+                .uid
+                .ok_or(SerDeError::MissingField("uid"))?
+                .try_into()?,
+            reason: value.reason,
+        })
+    }
+}
+
+impl From<ShortCircuitExplanation> for proto::ShortCircuitExplanation {
+    fn from(value: ShortCircuitExplanation) -> Self {
+        Self {
+            uid: Some(value.uid.into()),
+            reason: value.reason,
+        }
+    }
+}
+
+impl type_url::TypeUrl for ShortCircuitExplanation {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph_query_service.v1beta1.ShortCircuitExplanation";
+}
+
+impl serde_impl::ProtobufSerializable for ShortCircuitExplanation {
+    type ProtobufMessage = proto::ShortCircuitExplanation;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeQueryExplanation {
+    pub query_id: QueryId,
+    pub node_type: NodeType,
+    pub nodes_visited: u64,
+    pub nodes_matched: u64,
+    pub scylla_reads: u64,
+    pub rows_fetched: u64,
+    pub filters_evaluated: u64,
+    pub short_circuits: Vec<ShortCircuitExplanation>,
+    pub elapsed: Duration,
+}
+
+impl NodeQueryExplanation {
+    pub fn new(query_id: QueryId, node_type: NodeType) -> Self {
+        Self {
+            query_id,
+            node_type,
+            nodes_visited: 0,
+            nodes_matched: 0,
+            scylla_reads: 0,
+            rows_fetched: 0,
+            filters_evaluated: 0,
+            short_circuits: vec![],
+            elapsed: Duration::ZERO,
+        }
+    }
+}
+
+impl TryFrom<proto::NodeQueryExplanation> for NodeQueryExplanation {
+    type Error = SerDeError;
+    fn try_from(value: proto::NodeQueryExplanation) -> Result<Self, Self::Error> {
+        Ok(Self {
+            query_id: value
+                .query_id
+                .ok_or(SerDeError::MissingField("query_id"))?
+                .try_into()?,
+            node_type: value
+                .node_type
+                .ok_or(SerDeError::MissingField("node_type"))?
+                .try_into()?,
+            nodes_visited: value.nodes_visited,
+            nodes_matched: value.nodes_matched,
+            scylla_reads: value.scylla_reads,
+            rows_fetched: value.rows_fetched,
+            filters_evaluated: value.filters_evaluated,
+            short_circuits: value
+                .short_circuits
+                .into_iter()
+                .map(ShortCircuitExplanation::try_from)
+                .collect::<Result<_, _>>()?,
+            elapsed: value
+                .elapsed
+                .ok_or(SerDeError::MissingField("elapsed"))?
+                .into(),
+        })
+    }
+}
+
+impl From<NodeQueryExplanation> for proto::NodeQueryExplanation {
+    fn from(value: NodeQueryExplanation) -> Self {
+        Self {
+            query_id: Some(value.query_id.into()),
+            node_type: Some(value.node_type.into()),
+            nodes_visited: value.nodes_visited,
+            nodes_matched: value.nodes_matched,
+            scylla_reads: value.scylla_reads,
+            rows_fetched: value.rows_fetched,
+            filters_evaluated: value.filters_evaluated,
+            short_circuits: value.short_circuits.into_iter().map(Into::into).collect(),
+            elapsed: Some(value.elapsed.into()),
+        }
+    }
+}
+
+impl type_url::TypeUrl for NodeQueryExplanation {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph_query_service.v1beta1.NodeQueryExplanation";
+}
+
+impl serde_impl::ProtobufSerializable for NodeQueryExplanation {
+    type ProtobufMessage = proto::NodeQueryExplanation;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryExplanation {
+    pub node_queries: Vec<NodeQueryExplanation>,
+    pub elapsed: Duration,
+}
+
+impl QueryExplanation {
+    /// The explanation of the node query identified by `query_id`, if it was
+    /// evaluated at all
+    pub fn node_query(&self, query_id: QueryId) -> Option<&NodeQueryExplanation> {
+        self.node_queries
+            .iter()
+            .find(|node_query| node_query.query_id == query_id)
+    }
+}
+
+impl TryFrom<proto::QueryExplanation> for QueryExplanation {
+    type Error = SerDeError;
+    fn try_from(value: proto::QueryExplanation) -> Result<Self, Self::Error> {
+        Ok(Self {
+            node_queries: value
+                .node_queries
+                .into_iter()
+                .map(NodeQueryExplanation::try_from)
+                .collect::<Result<_, _>>()?,
+            elapsed: value
+                .elapsed
+                .ok_or(SerDeError::MissingField("elapsed"))?
+                .into(),
+        })
+    }
+}
+
+impl From<QueryExplanation> for proto::QueryExplanation {
+    fn from(value: QueryExplanation) -> Self {
+        Self {
+            node_queries: value.node_queries.into_iter().map(Into::into).collect(),
+            elapsed: Some(value.elapsed.into()),
+        }
+    }
+}
+
+impl type_url::TypeUrl for QueryExplanation {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph_query_service.v1beta1.QueryExplanation";
+}
+
+impl serde_impl::ProtobufSerializable for QueryExplanation {
+    type ProtobufMessage = proto::QueryExplanation;
+}
+
 #[derive(Debug, Clone)]
 pub struct QueryGraphFromUidRequest {
     pub tenant_id: uuid::Uuid,
     pub node_uid: Uid,
     pub graph_query: GraphQuery,
+    pub explain: bool,
 }
 
 impl TryFrom<proto::QueryGraphFromUidRequest> for QueryGraphFromUidRequest {
@@ -1560,6 +2335,7 @@ impl TryFrom<proto::QueryGraphFromUidRequest> for QueryGraphFromUidRequest {
                 .graph_query
                 .ok_or(SerDeError::MissingField("graph_query"))?
                 .try_into()?,
+            explain: value.explain,
         })
     }
 }
@@ -1570,6 +2346,7 @@ impl From<QueryGraphFromUidRequest> for proto::QueryGraphFromUidRequest {
             tenant_id: Some(value.tenant_id.into()),
             node_uid: Some(value.node_uid.into()),
             graph_query: Some(value.graph_query.into()),
+            explain: value.explain,
         }
     }
 }
@@ -1586,6 +2363,7 @@ impl serde_impl::ProtobufSerializable for QueryGraphFromUidRequest {
 #[derive(Debug, Clone)]
 pub struct QueryGraphFromUidResponse {
     pub matched_graph: Option<GraphView>,
+    pub explanation: Option<QueryExplanation>,
 }
 
 impl TryFrom<proto::QueryGraphFromUidResponse> for QueryGraphFromUidResponse {
@@ -1593,6 +2371,7 @@ impl TryFrom<proto::QueryGraphFromUidResponse> for QueryGraphFromUidResponse {
     fn try_from(value: proto::QueryGraphFromUidResponse) -> Result<Self, Self::Error> {
         Ok(Self {
             matched_graph: value.matched_graph.map(|g| g.try_into()).transpose()?,
+            explanation: value.explanation.map(|e| e.try_into()).transpose()?,
         })
     }
 }
@@ -1601,6 +2380,7 @@ impl From<QueryGraphFromUidResponse> for proto::QueryGraphFromUidResponse {
     fn from(value: QueryGraphFromUidResponse) -> Self {
         Self {
             matched_graph: value.matched_graph.map(Into::into),
+            explanation: value.explanation.map(Into::into),
         }
     }
 }
@@ -1613,3 +2393,505 @@ impl type_url::TypeUrl for QueryGraphFromUidResponse {
 impl serde_impl::ProtobufSerializable for QueryGraphFromUidResponse {
     type ProtobufMessage = proto::QueryGraphFromUidResponse;
 }
+
+/// A value to look up in the inverted property index
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SearchValue {
+    String(String),
+    I64(i64),
+    U64(u64),
+}
+
+impl TryFrom<proto::SearchValue> for SearchValue {
+    type Error = SerDeError;
+    fn try_from(value_proto: proto::SearchValue) -> Result<Self, Self::Error> {
+        match value_proto.value {
+            Some(proto::search_value::Value::String(value)) => Ok(SearchValue::String(value)),
+            Some(proto::search_value::Value::Int64(value)) => Ok(SearchValue::I64(value)),
+            Some(proto::search_value::Value::Uint64(value)) => Ok(SearchValue::U64(value)),
+            None => Err(SerDeError::UnknownVariant("SearchValue")),
+        }
+    }
+}
+
+impl From<SearchValue> for proto::SearchValue {
+    fn from(value: SearchValue) -> Self {
+        let value = match value {
+            SearchValue::String(value) => proto::search_value::Value::String(value),
+            SearchValue::I64(value) => proto::search_value::Value::Int64(value),
+            SearchValue::U64(value) => proto::search_value::Value::Uint64(value),
+        };
+        proto::SearchValue { value: Some(value) }
+    }
+}
+
+impl type_url::TypeUrl for SearchValue {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph_query_service.v1beta1.SearchValue";
+}
+
+impl serde_impl::ProtobufSerializable for SearchValue {
+    type ProtobufMessage = proto::SearchValue;
+}
+
+#[derive(Debug, Clone)]
+pub struct SearchNodesRequest {
+    pub tenant_id: uuid::Uuid,
+    pub node_type: NodeType,
+    pub property_name: PropertyName,
+    pub value: SearchValue,
+    /// The maximum number of uids to scan for this page, or `None` for the
+    /// server's default
+    pub page_size: Option<u32>,
+    pub page_token: Option<Uid>,
+    pub graph_query: Option<GraphQuery>,
+    pub explain: bool,
+}
+
+impl TryFrom<proto::SearchNodesRequest> for SearchNodesRequest {
+    type Error = SerDeError;
+
+    fn try_from(value: proto::SearchNodesRequest) -> Result<Self, Self::Error> {
+        Ok(Self {
+            tenant_id: value
+                .tenant_id
+                .ok_or(SerDeError::MissingField("tenant_id"))?
+                .into(),
+            node_type: value
+                .node_type
+                .ok_or(SerDeError::MissingField("node_type"))?
+                .try_into()?,
+            property_name: value
+                .property_name
+                .ok_or(SerDeError::MissingField("property_name"))?
+                .try_into()?,
+            value: value
+                .value
+                .ok_or(SerDeError::MissingField("value"))?
+                .try_into()?,
+            page_size: match value.page_size {
+                0 => None,
+                page_size => Some(page_size),
+            },
+            page_token: value.page_token.map(Uid::try_from).transpose()?,
+            graph_query: value.graph_query.map(GraphQuery::try_from).transpose()?,
+            explain: value.explain,
+        })
+    }
+}
+
+impl From<SearchNodesRequest> for proto::SearchNodesRequest {
+    fn from(value: SearchNodesRequest) -> Self {
+        Self {
+            tenant_id: Some(value.tenant_id.into()),
+            node_type: Some(value.node_type.into()),
+            property_name: Some(value.property_name.into()),
+            value: Some(value.value.into()),
+            page_size: value.page_size.unwrap_or_default(),
+            page_token: value.page_token.map(Into::into),
+            graph_query: value.graph_query.map(Into::into),
+            explain: value.explain,
+        }
+    }
+}
+
+impl type_url::TypeUrl for SearchNodesRequest {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph_query_service.v1beta1.SearchNodesRequest";
+}
+
+impl serde_impl::ProtobufSerializable for SearchNodesRequest {
+    type ProtobufMessage = proto::SearchNodesRequest;
+}
+
+#[derive(Debug, Clone)]
+pub struct SearchNodesMatch {
+    pub uid: Uid,
+    /// Only set if the request had a `graph_query`
+    pub matched_graph: Option<GraphView>,
+}
+
+impl TryFrom<proto::SearchNodesMatch> for SearchNodesMatch {
+    type Error = SerDeError;
+    fn try_from(value: proto::SearchNodesMatch) -> Result<Self, Self::Error> {
+        Ok(Self {
+            uid: value
+                .uid
+                .ok_or(SerDeError::MissingField("uid"))?
+                .try_into()?,
+            matched_graph: value.matched_graph.map(|g| g.try_into()).transpose()?,
+        })
+    }
+}
+
+impl From<SearchNodesMatch> for proto::SearchNodesMatch {
+    fn from(value: SearchNodesMatch) -> Self {
+        Self {
+            uid: Some(value.uid.into()),
+            matched_graph: value.matched_graph.map(Into::into),
+        }
+    }
+}
+
+impl type_url::TypeUrl for SearchNodesMatch {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph_query_service.v1beta1.SearchNodesMatch";
+}
+
+impl serde_impl::ProtobufSerializable for SearchNodesMatch {
+    type ProtobufMessage = proto::SearchNodesMatch;
+}
+
+#[derive(Debug, Clone)]
+pub struct SearchNodesResponse {
+    pub matches: Vec<SearchNodesMatch>,
+    /// `None` once there are no more nodes to scan
+    pub next_page_token: Option<Uid>,
+    pub explanation: Option<QueryExplanation>,
+}
+
+impl TryFrom<proto::SearchNodesResponse> for SearchNodesResponse {
+    type Error = SerDeError;
+    fn try_from(value: proto::SearchNodesResponse) -> Result<Self, Self::Error> {
+        Ok(Self {
+            matches: value
+                .matches
+                .into_iter()
+                .map(SearchNodesMatch::try_from)
+                .collect::<Result<_, _>>()?,
+            next_page_token: value.next_page_token.map(Uid::try_from).transpose()?,
+            explanation: value.explanation.map(|e| e.try_into()).transpose()?,
+        })
+    }
+}
+
+impl From<SearchNodesResponse> for proto::SearchNodesResponse {
+    fn from(value: SearchNodesResponse) -> Self {
+        Self {
+            matches: value.matches.into_iter().map(Into::into).collect(),
+            next_page_token: value.next_page_token.map(Into::into),
+            explanation: value.explanation.map(Into::into),
+        }
+    }
+}
+
+impl type_url::TypeUrl for SearchNodesResponse {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph_query_service.v1beta1.SearchNodesResponse";
+}
+
+impl serde_impl::ProtobufSerializable for SearchNodesResponse {
+    type ProtobufMessage = proto::SearchNodesResponse;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EdgeCount {
+    pub edge_name: EdgeName,
+    pub count: u64,
+}
+
+impl TryFrom<proto::EdgeCount> for EdgeCount {
+    type Error = SerDeError;
+    fn try_from(value: proto::EdgeCount) -> Result<Self, Self::Error> {
+        Ok(Self {
+            edge_name: value
+                .edge_name
+                .ok_or(SerDeError::MissingField("edge_name"))?
+                .try_into()?,
+            count: value.count,
+        })
+    }
+}
+
+impl From<EdgeCount> for proto::EdgeCount {
+    fn from(value: EdgeCount) -> Self {
+        Self {
+            edge_name: Some(value.edge_name.into()),
+            count: value.count,
+        }
+    }
+}
+
+impl type_url::TypeUrl for EdgeCount {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph_query_service.v1beta1.EdgeCount";
+}
+
+impl serde_impl::ProtobufSerializable for EdgeCount {
+    type ProtobufMessage = proto::EdgeCount;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeEdgeCounts {
+    pub uid: Uid,
+    pub node_type: NodeType,
+    pub edge_counts: Vec<EdgeCount>,
+}
+
+impl NodeEdgeCounts {
+    /// The number of `edge_name` edges the node has
+    pub fn count(&self, edge_name: &EdgeName) -> u64 {
+        self.edge_counts
+            .iter()
+            .find(|edge_count| &edge_count.edge_name == edge_name)
+            .map(|edge_count| edge_count.count)
+            .unwrap_or_default()
+    }
+}
+
+impl TryFrom<proto::NodeEdgeCounts> for NodeEdgeCounts {
+    type Error = SerDeError;
+    fn try_from(value: proto::NodeEdgeCounts) -> Result<Self, Self::Error> {
+        Ok(Self {
+            uid: value
+                .uid
+                .ok_or(SerDeError::MissingField("uid"))?
+                .try_into()?,
+            node_type: value
+                .node_type
+                .ok_or(SerDeError::MissingField("node_type"))?
+                .try_into()?,
+            edge_counts: value
+                .edge_counts
+                .into_iter()
+                .map(EdgeCount::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<NodeEdgeCounts> for proto::NodeEdgeCounts {
+    fn from(value: NodeEdgeCounts) -> Self {
+        Self {
+            uid: Some(value.uid.into()),
+            node_type: Some(value.node_type.into()),
+            edge_counts: value.edge_counts.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl type_url::TypeUrl for NodeEdgeCounts {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph_query_service.v1beta1.NodeEdgeCounts";
+}
+
+impl serde_impl::ProtobufSerializable for NodeEdgeCounts {
+    type ProtobufMessage = proto::NodeEdgeCounts;
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GraphEdgeCounts {
+    pub nodes: Vec<NodeEdgeCounts>,
+}
+
+impl GraphEdgeCounts {
+    pub fn node(&self, uid: Uid) -> Option<&NodeEdgeCounts> {
+        self.nodes.iter().find(|node| node.uid == uid)
+    }
+}
+
+impl TryFrom<proto::GraphEdgeCounts> for GraphEdgeCounts {
+    type Error = SerDeError;
+    fn try_from(value: proto::GraphEdgeCounts) -> Result<Self, Self::Error> {
+        Ok(Self {
+            nodes: value
+                .nodes
+                .into_iter()
+                .map(NodeEdgeCounts::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<GraphEdgeCounts> for proto::GraphEdgeCounts {
+    fn from(value: GraphEdgeCounts) -> Self {
+        Self {
+            nodes: value.nodes.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl type_url::TypeUrl for GraphEdgeCounts {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph_query_service.v1beta1.GraphEdgeCounts";
+}
+
+impl serde_impl::ProtobufSerializable for GraphEdgeCounts {
+    type ProtobufMessage = proto::GraphEdgeCounts;
+}
+
+#[derive(Debug, Clone)]
+pub struct AggregateGraphFromUidRequest {
+    pub tenant_id: uuid::Uuid,
+    pub node_uid: Uid,
+    pub graph_query: GraphQuery,
+    /// If empty, every edge is counted
+    pub edge_names: Vec<EdgeName>,
+    pub explain: bool,
+}
+
+impl TryFrom<proto::AggregateGraphFromUidRequest> for AggregateGraphFromUidRequest {
+    type Error = SerDeError;
+
+    fn try_from(value: proto::AggregateGraphFromUidRequest) -> Result<Self, Self::Error> {
+        Ok(Self {
+            tenant_id: value
+                .tenant_id
+                .ok_or(SerDeError::MissingField("tenant_id"))?
+                .into(),
+            node_uid: value
+                .node_uid
+                .ok_or(SerDeError::MissingField("node_uid"))?
+                .try_into()?,
+            graph_query: value
+                .graph_query
+                .ok_or(SerDeError::MissingField("graph_query"))?
+                .try_into()?,
+            edge_names: value
+                .edge_names
+                .into_iter()
+                .map(EdgeName::try_from)
+                .collect::<Result<_, _>>()?,
+            explain: value.explain,
+        })
+    }
+}
+
+impl From<AggregateGraphFromUidRequest> for proto::AggregateGraphFromUidRequest {
+    fn from(value: AggregateGraphFromUidRequest) -> Self {
+        Self {
+            tenant_id: Some(value.tenant_id.into()),
+            node_uid: Some(value.node_uid.into()),
+            graph_query: Some(value.graph_query.into()),
+            edge_names: value.edge_names.into_iter().map(Into::into).collect(),
+            explain: value.explain,
+        }
+    }
+}
+
+impl type_url::TypeUrl for AggregateGraphFromUidRequest {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph_query_service.v1beta1.AggregateGraphFromUidRequest";
+}
+
+impl serde_impl::ProtobufSerializable for AggregateGraphFromUidRequest {
+    type ProtobufMessage = proto::AggregateGraphFromUidRequest;
+}
+
+#[derive(Debug, Clone)]
+pub struct AggregateGraphFromUidResponse {
+    pub edge_counts: Option<GraphEdgeCounts>,
+    pub explanation: Option<QueryExplanation>,
+}
+
+impl TryFrom<proto::AggregateGraphFromUidResponse> for AggregateGraphFromUidResponse {
+    type Error = SerDeError;
+    fn try_from(value: proto::AggregateGraphFromUidResponse) -> Result<Self, Self::Error> {
+        Ok(Self {
+            edge_counts: value.edge_counts.map(|c| c.try_into()).transpose()?,
+            explanation: value.explanation.map(|e| e.try_into()).transpose()?,
+        })
+    }
+}
+
+impl From<AggregateGraphFromUidResponse> for proto::AggregateGraphFromUidResponse {
+    fn from(value: AggregateGraphFromUidResponse) -> Self {
+        Self {
+            edge_counts: value.edge_counts.map(Into::into),
+            explanation: value.explanation.map(Into::into),
+        }
+    }
+}
+
+impl type_url::TypeUrl for AggregateGraphFromUidResponse {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph_query_service.v1beta1.AggregateGraphFromUidResponse";
+}
+
+impl serde_impl::ProtobufSerializable for AggregateGraphFromUidResponse {
+    type ProtobufMessage = proto::AggregateGraphFromUidResponse;
+}
+
+#[derive(Debug, Clone)]
+pub struct QueryGraphWithTextRequest {
+    pub tenant_id: uuid::Uuid,
+    pub node_uid: Uid,
+    pub query_text: String,
+    pub explain: bool,
+}
+
+impl TryFrom<proto::QueryGraphWithTextRequest> for QueryGraphWithTextRequest {
+    type Error = SerDeError;
+
+    fn try_from(value: proto::QueryGraphWithTextRequest) -> Result<Self, Self::Error> {
+        Ok(Self {
+            tenant_id: value
+                .tenant_id
+                .ok_or(SerDeError::MissingField("tenant_id"))?
+                .into(),
+            node_uid: value
+                .node_uid
+                .ok_or(SerDeError::MissingField("node_uid"))?
+                .try_into()?,
+            query_text: value.query_text,
+            explain: value.explain,
+        })
+    }
+}
+
+impl From<QueryGraphWithTextRequest> for proto::QueryGraphWithTextRequest {
+    fn from(value: QueryGraphWithTextRequest) -> Self {
+        Self {
+            tenant_id: Some(value.tenant_id.into()),
+            node_uid: Some(value.node_uid.into()),
+            query_text: value.query_text,
+            explain: value.explain,
+        }
+    }
+}
+
+impl type_url::TypeUrl for QueryGraphWithTextRequest {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph_query_service.v1beta1.QueryGraphWithTextRequest";
+}
+
+impl serde_impl::ProtobufSerializable for QueryGraphWithTextRequest {
+    type ProtobufMessage = proto::QueryGraphWithTextRequest;
+}
+
+#[derive(Debug, Clone)]
+pub struct QueryGraphWithTextResponse {
+    pub maybe_match: MaybeMatchWithUid,
+    pub explanation: Option<QueryExplanation>,
+}
+
+impl TryFrom<proto::QueryGraphWithTextResponse> for QueryGraphWithTextResponse {
+    type Error = SerDeError;
+    fn try_from(value: proto::QueryGraphWithTextResponse) -> Result<Self, Self::Error> {
+        Ok(Self {
+            maybe_match: value
+                .maybe_match
+                .ok_or(SerDeError::MissingField("maybe_match"))?
+                .try_into()?,
+            explanation: value.explanation.map(|e| e.try_into()).transpose()?,
+        })
+    }
+}
+
+impl From<QueryGraphWithTextResponse> for proto::QueryGraphWithTextResponse {
+    fn from(value: QueryGraphWithTextResponse) -> Self {
+        Self {
+            maybe_match: Some(value.maybe_match.into()),
+            explanation: value.explanation.map(Into::into),
+        }
+    }
+}
+
+impl type_url::TypeUrl for QueryGraphWithTextResponse {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph_query_service.v1beta1.QueryGraphWithTextResponse";
+}
+
+impl serde_impl::ProtobufSerializable for QueryGraphWithTextResponse {
+    type ProtobufMessage = proto::QueryGraphWithTextResponse;
+}
@@ -23,10 +23,16 @@ use crate::{
     execute_rpc,
     graplinc::grapl::api::{
         graph_query::v1beta1::messages::{
+            AggregateGraphFromUidRequest,
+            AggregateGraphFromUidResponse,
             QueryGraphFromUidRequest,
             QueryGraphFromUidResponse,
+            QueryGraphWithTextRequest,
+            QueryGraphWithTextResponse,
             QueryGraphWithUidRequest,
             QueryGraphWithUidResponse,
+            SearchNodesRequest,
+            SearchNodesResponse,
         },
         protocol::{
             error::ServeError,
@@ -44,10 +50,16 @@ use crate::{
             GraphQueryService as GraphQueryServiceProto,
             GraphQueryServiceServer as GraphQueryServiceServerProto,
         },
+        AggregateGraphFromUidRequest as AggregateGraphFromUidRequestProto,
+        AggregateGraphFromUidResponse as AggregateGraphFromUidResponseProto,
         QueryGraphFromUidRequest as QueryGraphFromUidRequestProto,
         QueryGraphFromUidResponse as QueryGraphFromUidResponseProto,
+        QueryGraphWithTextRequest as QueryGraphWithTextRequestProto,
+        QueryGraphWithTextResponse as QueryGraphWithTextResponseProto,
         QueryGraphWithUidRequest as QueryGraphWithUidRequestProto,
         QueryGraphWithUidResponse as QueryGraphWithUidResponseProto,
+        SearchNodesRequest as SearchNodesRequestProto,
+        SearchNodesResponse as SearchNodesResponseProto,
     },
 };
 
@@ -70,6 +82,18 @@ pub trait GraphQueryApi {
         &self,
         request: QueryGraphFromUidRequest,
     ) -> Result<QueryGraphFromUidResponse, Self::Error>;
+    async fn search_nodes(
+        &self,
+        request: SearchNodesRequest,
+    ) -> Result<SearchNodesResponse, Self::Error>;
+    async fn aggregate_graph_from_uid(
+        &self,
+        request: AggregateGraphFromUidRequest,
+    ) -> Result<AggregateGraphFromUidResponse, Self::Error>;
+    async fn query_graph_with_text(
+        &self,
+        request: QueryGraphWithTextRequest,
+    ) -> Result<QueryGraphWithTextResponse, Self::Error>;
 }
 
 #[tonic::async_trait]
@@ -90,6 +114,27 @@ where
     ) -> Result<tonic::Response<QueryGraphFromUidResponseProto>, tonic::Status> {
         execute_rpc!(self, request, query_graph_from_uid)
     }
+
+    async fn search_nodes(
+        &self,
+        request: tonic::Request<SearchNodesRequestProto>,
+    ) -> Result<tonic::Response<SearchNodesResponseProto>, tonic::Status> {
+        execute_rpc!(self, request, search_nodes)
+    }
+
+    async fn aggregate_graph_from_uid(
+        &self,
+        request: tonic::Request<AggregateGraphFromUidRequestProto>,
+    ) -> Result<tonic::Response<AggregateGraphFromUidResponseProto>, tonic::Status> {
+        execute_rpc!(self, request, aggregate_graph_from_uid)
+    }
+
+    async fn query_graph_with_text(
+        &self,
+        request: tonic::Request<QueryGraphWithTextRequestProto>,
+    ) -> Result<tonic::Response<QueryGraphWithTextResponseProto>, tonic::Status> {
+        execute_rpc!(self, request, query_graph_with_text)
+    }
 }
 
 /**
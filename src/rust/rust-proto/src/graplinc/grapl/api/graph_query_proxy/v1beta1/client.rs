@@ -63,4 +63,19 @@ impl GraphQueryProxyClient {
             )
             .await
     }
+
+    pub async fn aggregate_graph_from_uid(
+        &mut self,
+        request: native::AggregateGraphFromUidRequest,
+    ) -> Result<native::AggregateGraphFromUidResponse, ClientError> {
+        self.client
+            .execute(
+                request,
+                None,
+                |status| status.code() == tonic::Code::Unavailable,
+                10,
+                |mut client, request| async move { client.aggregate_graph_from_uid(request).await },
+            )
+            .await
+    }
 }
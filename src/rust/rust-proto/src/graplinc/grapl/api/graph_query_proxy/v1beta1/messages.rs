@@ -1,7 +1,10 @@
 use crate::{
     graplinc::grapl::{
         api::graph_query::v1beta1::messages as graph_query_messages,
-        common::v1beta1::types::Uid,
+        common::v1beta1::types::{
+            EdgeName,
+            Uid,
+        },
     },
     protobufs::graplinc::grapl::api::graph_query_proxy::v1beta1 as proto,
     serde_impl,
@@ -173,3 +176,92 @@ impl type_url::TypeUrl for QueryGraphFromUidResponse {
 impl serde_impl::ProtobufSerializable for QueryGraphFromUidResponse {
     type ProtobufMessage = proto::QueryGraphFromUidResponse;
 }
+
+#[derive(Debug, Clone)]
+pub struct AggregateGraphFromUidRequest {
+    pub node_uid: Uid,
+    pub graph_query: graph_query_messages::GraphQuery,
+    /// If empty, every edge is counted
+    pub edge_names: Vec<EdgeName>,
+}
+
+impl TryFrom<proto::AggregateGraphFromUidRequest> for AggregateGraphFromUidRequest {
+    type Error = SerDeError;
+
+    fn try_from(value: proto::AggregateGraphFromUidRequest) -> Result<Self, Self::Error> {
+        Ok(Self {
+            node_uid: value
+                .node_uid
+                .ok_or(SerDeError::MissingField("node_uid"))?
+                .try_into()?,
+            graph_query: value
+                .graph_query
+                .ok_or(SerDeError::MissingField("graph_query"))?
+                .try_into()?,
+            edge_names: value
+                .edge_names
+                .into_iter()
+                .map(EdgeName::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<AggregateGraphFromUidRequest> for proto::AggregateGraphFromUidRequest {
+    fn from(value: AggregateGraphFromUidRequest) -> Self {
+        Self {
+            node_uid: Some(value.node_uid.into()),
+            graph_query: Some(value.graph_query.into()),
+            edge_names: value.edge_names.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl type_url::TypeUrl for AggregateGraphFromUidRequest {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph_query_proxy.v1beta1.AggregateGraphFromUidRequest";
+}
+
+impl serde_impl::ProtobufSerializable for AggregateGraphFromUidRequest {
+    type ProtobufMessage = proto::AggregateGraphFromUidRequest;
+}
+
+#[derive(Debug, Clone)]
+pub struct AggregateGraphFromUidResponse {
+    pub edge_counts: Option<graph_query_messages::GraphEdgeCounts>,
+}
+
+impl TryFrom<proto::AggregateGraphFromUidResponse> for AggregateGraphFromUidResponse {
+    type Error = SerDeError;
+    fn try_from(value: proto::AggregateGraphFromUidResponse) -> Result<Self, Self::Error> {
+        Ok(Self {
+            edge_counts: value.edge_counts.map(|c| c.try_into()).transpose()?,
+        })
+    }
+}
+
+impl From<AggregateGraphFromUidResponse> for proto::AggregateGraphFromUidResponse {
+    fn from(value: AggregateGraphFromUidResponse) -> Self {
+        Self {
+            edge_counts: value.edge_counts.map(Into::into),
+        }
+    }
+}
+
+// Convert from a Graph Query response to a Graph Query Proxy response.
+impl From<graph_query_messages::AggregateGraphFromUidResponse> for AggregateGraphFromUidResponse {
+    fn from(other: graph_query_messages::AggregateGraphFromUidResponse) -> Self {
+        Self {
+            edge_counts: other.edge_counts,
+        }
+    }
+}
+
+impl type_url::TypeUrl for AggregateGraphFromUidResponse {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph_query_proxy.v1beta1.AggregateGraphFromUidResponse";
+}
+
+impl serde_impl::ProtobufSerializable for AggregateGraphFromUidResponse {
+    type ProtobufMessage = proto::AggregateGraphFromUidResponse;
+}
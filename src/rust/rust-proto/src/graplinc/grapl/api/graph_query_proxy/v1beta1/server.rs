@@ -23,6 +23,8 @@ use crate::{
     execute_rpc,
     graplinc::grapl::api::{
         graph_query_proxy::v1beta1::messages::{
+            AggregateGraphFromUidRequest,
+            AggregateGraphFromUidResponse,
             QueryGraphFromUidRequest,
             QueryGraphFromUidResponse,
             QueryGraphWithUidRequest,
@@ -59,6 +61,10 @@ pub trait GraphQueryProxyApi {
         &self,
         request: QueryGraphFromUidRequest,
     ) -> Result<QueryGraphFromUidResponse, Self::Error>;
+    async fn aggregate_graph_from_uid(
+        &self,
+        request: AggregateGraphFromUidRequest,
+    ) -> Result<AggregateGraphFromUidResponse, Self::Error>;
 }
 
 #[tonic::async_trait]
@@ -79,6 +85,13 @@ where
     ) -> Result<tonic::Response<proto::QueryGraphFromUidResponse>, tonic::Status> {
         execute_rpc!(self, request, query_graph_from_uid)
     }
+
+    async fn aggregate_graph_from_uid(
+        &self,
+        request: tonic::Request<proto::AggregateGraphFromUidRequest>,
+    ) -> Result<tonic::Response<proto::AggregateGraphFromUidResponse>, tonic::Status> {
+        execute_rpc!(self, request, aggregate_graph_from_uid)
+    }
 }
 
 /**
@@ -65,4 +65,19 @@ impl GraphSchemaManagerClient {
             )
             .await
     }
+
+    pub async fn get_node_schema(
+        &mut self,
+        request: native::GetNodeSchemaRequest,
+    ) -> Result<native::GetNodeSchemaResponse, ClientError> {
+        self.client
+            .execute(
+                request,
+                None,
+                |status| status.code() == tonic::Code::Unavailable,
+                10,
+                |mut client, request| async move { client.get_node_schema(request).await },
+            )
+            .await
+    }
 }
@@ -4,14 +4,22 @@ use crate::{
     graplinc::grapl::common::v1beta1::types::{
         EdgeName,
         NodeType,
+        PropertyName,
     },
     protobufs::graplinc::grapl::api::graph_schema_manager::v1beta1::{
+        get_node_schema_response::Identity as NodeIdentityProto,
         DeploySchemaRequest as DeploySchemaRequestProto,
         DeploySchemaResponse as DeploySchemaResponseProto,
         EdgeCardinality as EdgeCardinalityProto,
         GetEdgeSchemaRequest as GetEdgeSchemaRequestProto,
         GetEdgeSchemaResponse as GetEdgeSchemaResponseProto,
+        GetNodeSchemaRequest as GetNodeSchemaRequestProto,
+        GetNodeSchemaResponse as GetNodeSchemaResponseProto,
+        PropertySchema as PropertySchemaProto,
+        PropertyType as PropertyTypeProto,
         SchemaType as SchemaTypeProto,
+        SessionIdentity as SessionIdentityProto,
+        StaticIdentity as StaticIdentityProto,
     },
     serde_impl,
     type_url,
@@ -265,3 +273,342 @@ impl type_url::TypeUrl for EdgeCardinality {
     const TYPE_URL: &'static str =
         "graplsecurity.com/graplinc.grapl.api.graph_schema_manager.v1beta1.EdgeCardinality";
 }
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GetNodeSchemaRequest {
+    pub tenant_id: uuid::Uuid,
+    pub node_type: NodeType,
+}
+
+impl TryFrom<GetNodeSchemaRequestProto> for GetNodeSchemaRequest {
+    type Error = SerDeError;
+
+    fn try_from(request_proto: GetNodeSchemaRequestProto) -> Result<Self, Self::Error> {
+        let tenant_id = request_proto
+            .tenant_id
+            .ok_or(SerDeError::MissingField("GetNodeSchemaRequest.tenant_id"))?
+            .into();
+
+        let node_type = request_proto
+            .node_type
+            .ok_or(SerDeError::MissingField("GetNodeSchemaRequest.node_type"))?
+            .try_into()?;
+
+        Ok(GetNodeSchemaRequest {
+            tenant_id,
+            node_type,
+        })
+    }
+}
+
+impl From<GetNodeSchemaRequest> for GetNodeSchemaRequestProto {
+    fn from(request: GetNodeSchemaRequest) -> Self {
+        GetNodeSchemaRequestProto {
+            tenant_id: Some(request.tenant_id.into()),
+            node_type: Some(request.node_type.into()),
+        }
+    }
+}
+
+impl type_url::TypeUrl for GetNodeSchemaRequest {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph_schema_manager.v1beta1.GetNodeSchemaRequest";
+}
+
+impl serde_impl::ProtobufSerializable for GetNodeSchemaRequest {
+    type ProtobufMessage = GetNodeSchemaRequestProto;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GetNodeSchemaResponse {
+    pub schema_version: u32,
+    pub identity: NodeIdentity,
+    pub properties: Vec<PropertySchema>,
+}
+
+impl TryFrom<GetNodeSchemaResponseProto> for GetNodeSchemaResponse {
+    type Error = SerDeError;
+
+    fn try_from(response_proto: GetNodeSchemaResponseProto) -> Result<Self, Self::Error> {
+        let identity = match response_proto.identity {
+            Some(NodeIdentityProto::StaticIdentity(identity)) => {
+                NodeIdentity::Static(identity.try_into()?)
+            }
+            Some(NodeIdentityProto::SessionIdentity(identity)) => {
+                NodeIdentity::Session(identity.try_into()?)
+            }
+            None => return Err(SerDeError::MissingField("GetNodeSchemaResponse.identity")),
+        };
+
+        let properties = response_proto
+            .properties
+            .into_iter()
+            .map(PropertySchema::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(GetNodeSchemaResponse {
+            schema_version: response_proto.schema_version,
+            identity,
+            properties,
+        })
+    }
+}
+
+impl From<GetNodeSchemaResponse> for GetNodeSchemaResponseProto {
+    fn from(response: GetNodeSchemaResponse) -> Self {
+        let identity = match response.identity {
+            NodeIdentity::Static(identity) => NodeIdentityProto::StaticIdentity(identity.into()),
+            NodeIdentity::Session(identity) => NodeIdentityProto::SessionIdentity(identity.into()),
+        };
+
+        GetNodeSchemaResponseProto {
+            schema_version: response.schema_version,
+            identity: Some(identity),
+            properties: response.properties.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl type_url::TypeUrl for GetNodeSchemaResponse {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph_schema_manager.v1beta1.GetNodeSchemaResponse";
+}
+
+impl serde_impl::ProtobufSerializable for GetNodeSchemaResponse {
+    type ProtobufMessage = GetNodeSchemaResponseProto;
+}
+
+/// How nodes of a given type are identified
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NodeIdentity {
+    Static(StaticIdentity),
+    Session(SessionIdentity),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StaticIdentity {
+    pub static_key_properties: Vec<PropertyName>,
+}
+
+impl TryFrom<StaticIdentityProto> for StaticIdentity {
+    type Error = SerDeError;
+
+    fn try_from(identity_proto: StaticIdentityProto) -> Result<Self, Self::Error> {
+        let static_key_properties = identity_proto
+            .static_key_properties
+            .into_iter()
+            .map(PropertyName::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(StaticIdentity {
+            static_key_properties,
+        })
+    }
+}
+
+impl From<StaticIdentity> for StaticIdentityProto {
+    fn from(identity: StaticIdentity) -> Self {
+        StaticIdentityProto {
+            static_key_properties: identity
+                .static_key_properties
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+        }
+    }
+}
+
+impl type_url::TypeUrl for StaticIdentity {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph_schema_manager.v1beta1.StaticIdentity";
+}
+
+impl serde_impl::ProtobufSerializable for StaticIdentity {
+    type ProtobufMessage = StaticIdentityProto;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionIdentity {
+    pub pseudo_key_properties: Vec<PropertyName>,
+    pub creation_timestamp_property: PropertyName,
+    pub last_seen_timestamp_property: PropertyName,
+    pub termination_timestamp_property: PropertyName,
+}
+
+impl TryFrom<SessionIdentityProto> for SessionIdentity {
+    type Error = SerDeError;
+
+    fn try_from(identity_proto: SessionIdentityProto) -> Result<Self, Self::Error> {
+        let pseudo_key_properties = identity_proto
+            .pseudo_key_properties
+            .into_iter()
+            .map(PropertyName::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let creation_timestamp_property = identity_proto
+            .creation_timestamp_property
+            .ok_or(SerDeError::MissingField(
+                "SessionIdentity.creation_timestamp_property",
+            ))?
+            .try_into()?;
+
+        let last_seen_timestamp_property = identity_proto
+            .last_seen_timestamp_property
+            .ok_or(SerDeError::MissingField(
+                "SessionIdentity.last_seen_timestamp_property",
+            ))?
+            .try_into()?;
+
+        let termination_timestamp_property = identity_proto
+            .termination_timestamp_property
+            .ok_or(SerDeError::MissingField(
+                "SessionIdentity.termination_timestamp_property",
+            ))?
+            .try_into()?;
+
+        Ok(SessionIdentity {
+            pseudo_key_properties,
+            creation_timestamp_property,
+            last_seen_timestamp_property,
+            termination_timestamp_property,
+        })
+    }
+}
+
+impl From<SessionIdentity> for SessionIdentityProto {
+    fn from(identity: SessionIdentity) -> Self {
+        SessionIdentityProto {
+            pseudo_key_properties: identity
+                .pseudo_key_properties
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            creation_timestamp_property: Some(identity.creation_timestamp_property.into()),
+            last_seen_timestamp_property: Some(identity.last_seen_timestamp_property.into()),
+            termination_timestamp_property: Some(identity.termination_timestamp_property.into()),
+        }
+    }
+}
+
+impl type_url::TypeUrl for SessionIdentity {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph_schema_manager.v1beta1.SessionIdentity";
+}
+
+impl serde_impl::ProtobufSerializable for SessionIdentity {
+    type ProtobufMessage = SessionIdentityProto;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PropertySchema {
+    pub property_name: PropertyName,
+    pub property_type: PropertyType,
+    pub identity_only: bool,
+}
+
+impl TryFrom<PropertySchemaProto> for PropertySchema {
+    type Error = SerDeError;
+
+    fn try_from(property_proto: PropertySchemaProto) -> Result<Self, Self::Error> {
+        let property_type = property_proto.property_type().try_into()?;
+
+        let property_name = property_proto
+            .property_name
+            .ok_or(SerDeError::MissingField("PropertySchema.property_name"))?
+            .try_into()?;
+
+        Ok(PropertySchema {
+            property_name,
+            property_type,
+            identity_only: property_proto.identity_only,
+        })
+    }
+}
+
+impl From<PropertySchema> for PropertySchemaProto {
+    fn from(property: PropertySchema) -> Self {
+        let property_type: PropertyTypeProto = property.property_type.into();
+        PropertySchemaProto {
+            property_name: Some(property.property_name.into()),
+            property_type: property_type as i32,
+            identity_only: property.identity_only,
+        }
+    }
+}
+
+impl type_url::TypeUrl for PropertySchema {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph_schema_manager.v1beta1.PropertySchema";
+}
+
+impl serde_impl::ProtobufSerializable for PropertySchema {
+    type ProtobufMessage = PropertySchemaProto;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PropertyType {
+    ImmutableString,
+    ImmutableI64,
+    MaxI64,
+    MinI64,
+    ImmutableU64,
+    MaxU64,
+    MinU64,
+    ImmutableBool,
+    ImmutableF64,
+    ImmutableBytes,
+    ImmutableTimestamp,
+    MaxTimestamp,
+    MinTimestamp,
+    StringSet,
+}
+
+impl TryFrom<PropertyTypeProto> for PropertyType {
+    type Error = SerDeError;
+
+    fn try_from(property_type_proto: PropertyTypeProto) -> Result<Self, Self::Error> {
+        match property_type_proto {
+            PropertyTypeProto::ImmutableString => Ok(PropertyType::ImmutableString),
+            PropertyTypeProto::ImmutableI64 => Ok(PropertyType::ImmutableI64),
+            PropertyTypeProto::MaxI64 => Ok(PropertyType::MaxI64),
+            PropertyTypeProto::MinI64 => Ok(PropertyType::MinI64),
+            PropertyTypeProto::ImmutableU64 => Ok(PropertyType::ImmutableU64),
+            PropertyTypeProto::MaxU64 => Ok(PropertyType::MaxU64),
+            PropertyTypeProto::MinU64 => Ok(PropertyType::MinU64),
+            PropertyTypeProto::ImmutableBool => Ok(PropertyType::ImmutableBool),
+            PropertyTypeProto::ImmutableF64 => Ok(PropertyType::ImmutableF64),
+            PropertyTypeProto::ImmutableBytes => Ok(PropertyType::ImmutableBytes),
+            PropertyTypeProto::ImmutableTimestamp => Ok(PropertyType::ImmutableTimestamp),
+            PropertyTypeProto::MaxTimestamp => Ok(PropertyType::MaxTimestamp),
+            PropertyTypeProto::MinTimestamp => Ok(PropertyType::MinTimestamp),
+            PropertyTypeProto::StringSet => Ok(PropertyType::StringSet),
+            PropertyTypeProto::Unspecified => Err(SerDeError::UnknownVariant("PropertyType")),
+        }
+    }
+}
+
+impl From<PropertyType> for PropertyTypeProto {
+    fn from(property_type: PropertyType) -> Self {
+        match property_type {
+            PropertyType::ImmutableString => PropertyTypeProto::ImmutableString,
+            PropertyType::ImmutableI64 => PropertyTypeProto::ImmutableI64,
+            PropertyType::MaxI64 => PropertyTypeProto::MaxI64,
+            PropertyType::MinI64 => PropertyTypeProto::MinI64,
+            PropertyType::ImmutableU64 => PropertyTypeProto::ImmutableU64,
+            PropertyType::MaxU64 => PropertyTypeProto::MaxU64,
+            PropertyType::MinU64 => PropertyTypeProto::MinU64,
+            PropertyType::ImmutableBool => PropertyTypeProto::ImmutableBool,
+            PropertyType::ImmutableF64 => PropertyTypeProto::ImmutableF64,
+            PropertyType::ImmutableBytes => PropertyTypeProto::ImmutableBytes,
+            PropertyType::ImmutableTimestamp => PropertyTypeProto::ImmutableTimestamp,
+            PropertyType::MaxTimestamp => PropertyTypeProto::MaxTimestamp,
+            PropertyType::MinTimestamp => PropertyTypeProto::MinTimestamp,
+            PropertyType::StringSet => PropertyTypeProto::StringSet,
+        }
+    }
+}
+
+impl type_url::TypeUrl for PropertyType {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph_schema_manager.v1beta1.PropertyType";
+}
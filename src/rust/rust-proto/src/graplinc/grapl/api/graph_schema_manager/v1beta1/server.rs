@@ -25,6 +25,8 @@ use crate::{
             DeploySchemaResponse,
             GetEdgeSchemaRequest,
             GetEdgeSchemaResponse,
+            GetNodeSchemaRequest,
+            GetNodeSchemaResponse,
         },
         protocol::{
             error::ServeError,
@@ -66,6 +68,11 @@ pub trait GraphSchemaManagerApi {
         &self,
         request: GetEdgeSchemaRequest,
     ) -> Result<GetEdgeSchemaResponse, Self::Error>;
+
+    async fn get_node_schema(
+        &self,
+        request: GetNodeSchemaRequest,
+    ) -> Result<GetNodeSchemaResponse, Self::Error>;
 }
 
 #[tonic::async_trait]
@@ -86,6 +93,13 @@ where
     ) -> Result<tonic::Response<proto::GetEdgeSchemaResponse>, tonic::Status> {
         execute_rpc!(self, request, get_edge_schema)
     }
+
+    async fn get_node_schema(
+        &self,
+        request: tonic::Request<proto::GetNodeSchemaRequest>,
+    ) -> Result<tonic::Response<proto::GetNodeSchemaResponse>, tonic::Status> {
+        execute_rpc!(self, request, get_node_schema)
+    }
 }
 
 /**
@@ -13,6 +13,9 @@ use crate::{
         execution_result::Inner as ExecutionResultInnerProto,
         update::Inner as UpdateInnerProto,
         AnalyzerName as AnalyzerNameProto,
+        BoolPropertyUpdate as BoolPropertyUpdateProto,
+        BytesPropertyUpdate as BytesPropertyUpdateProto,
+        DoublePropertyUpdate as DoublePropertyUpdateProto,
         EdgeUpdate as EdgeUpdateProto,
         ExecutionHit as ExecutionHitProto,
         ExecutionMiss as ExecutionMissProto,
@@ -22,6 +25,8 @@ use crate::{
         RunAnalyzerRequest as RunAnalyzerRequestProto,
         RunAnalyzerResponse as RunAnalyzerResponseProto,
         StringPropertyUpdate as StringPropertyUpdateProto,
+        StringSetPropertyUpdate as StringSetPropertyUpdateProto,
+        TimestampPropertyUpdate as TimestampPropertyUpdateProto,
         UInt64PropertyUpdate as UInt64PropertyUpdateProto,
         Update as UpdateProto,
     },
@@ -126,6 +131,161 @@ impl From<Int64PropertyUpdate> for Int64PropertyUpdateProto {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BoolPropertyUpdate {
+    pub uid: Uid,
+    pub property_name: PropertyName,
+}
+
+impl TryFrom<BoolPropertyUpdateProto> for BoolPropertyUpdate {
+    type Error = SerDeError;
+    fn try_from(value: BoolPropertyUpdateProto) -> Result<Self, Self::Error> {
+        Ok(Self {
+            uid: value
+                .uid
+                .ok_or(SerDeError::MissingField("uid"))?
+                .try_into()?,
+            property_name: value
+                .property_name
+                .ok_or(SerDeError::MissingField("property_name"))?
+                .try_into()?,
+        })
+    }
+}
+
+impl From<BoolPropertyUpdate> for BoolPropertyUpdateProto {
+    fn from(value: BoolPropertyUpdate) -> Self {
+        Self {
+            uid: Some(value.uid.into()),
+            property_name: Some(value.property_name.into()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DoublePropertyUpdate {
+    pub uid: Uid,
+    pub property_name: PropertyName,
+}
+
+impl TryFrom<DoublePropertyUpdateProto> for DoublePropertyUpdate {
+    type Error = SerDeError;
+    fn try_from(value: DoublePropertyUpdateProto) -> Result<Self, Self::Error> {
+        Ok(Self {
+            uid: value
+                .uid
+                .ok_or(SerDeError::MissingField("uid"))?
+                .try_into()?,
+            property_name: value
+                .property_name
+                .ok_or(SerDeError::MissingField("property_name"))?
+                .try_into()?,
+        })
+    }
+}
+
+impl From<DoublePropertyUpdate> for DoublePropertyUpdateProto {
+    fn from(value: DoublePropertyUpdate) -> Self {
+        Self {
+            uid: Some(value.uid.into()),
+            property_name: Some(value.property_name.into()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BytesPropertyUpdate {
+    pub uid: Uid,
+    pub property_name: PropertyName,
+}
+
+impl TryFrom<BytesPropertyUpdateProto> for BytesPropertyUpdate {
+    type Error = SerDeError;
+    fn try_from(value: BytesPropertyUpdateProto) -> Result<Self, Self::Error> {
+        Ok(Self {
+            uid: value
+                .uid
+                .ok_or(SerDeError::MissingField("uid"))?
+                .try_into()?,
+            property_name: value
+                .property_name
+                .ok_or(SerDeError::MissingField("property_name"))?
+                .try_into()?,
+        })
+    }
+}
+
+impl From<BytesPropertyUpdate> for BytesPropertyUpdateProto {
+    fn from(value: BytesPropertyUpdate) -> Self {
+        Self {
+            uid: Some(value.uid.into()),
+            property_name: Some(value.property_name.into()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TimestampPropertyUpdate {
+    pub uid: Uid,
+    pub property_name: PropertyName,
+}
+
+impl TryFrom<TimestampPropertyUpdateProto> for TimestampPropertyUpdate {
+    type Error = SerDeError;
+    fn try_from(value: TimestampPropertyUpdateProto) -> Result<Self, Self::Error> {
+        Ok(Self {
+            uid: value
+                .uid
+                .ok_or(SerDeError::MissingField("uid"))?
+                .try_into()?,
+            property_name: value
+                .property_name
+                .ok_or(SerDeError::MissingField("property_name"))?
+                .try_into()?,
+        })
+    }
+}
+
+impl From<TimestampPropertyUpdate> for TimestampPropertyUpdateProto {
+    fn from(value: TimestampPropertyUpdate) -> Self {
+        Self {
+            uid: Some(value.uid.into()),
+            property_name: Some(value.property_name.into()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StringSetPropertyUpdate {
+    pub uid: Uid,
+    pub property_name: PropertyName,
+}
+
+impl TryFrom<StringSetPropertyUpdateProto> for StringSetPropertyUpdate {
+    type Error = SerDeError;
+    fn try_from(value: StringSetPropertyUpdateProto) -> Result<Self, Self::Error> {
+        Ok(Self {
+            uid: value
+                .uid
+                .ok_or(SerDeError::MissingField("uid"))?
+                .try_into()?,
+            property_name: value
+                .property_name
+                .ok_or(SerDeError::MissingField("property_name"))?
+                .try_into()?,
+        })
+    }
+}
+
+impl From<StringSetPropertyUpdate> for StringSetPropertyUpdateProto {
+    fn from(value: StringSetPropertyUpdate) -> Self {
+        Self {
+            uid: Some(value.uid.into()),
+            property_name: Some(value.property_name.into()),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct EdgeUpdate {
     pub src_uid: Uid,
@@ -175,6 +335,11 @@ pub enum Update {
     Uint64Property(UInt64PropertyUpdate),
     Int64Property(Int64PropertyUpdate),
     Edge(EdgeUpdate),
+    BoolProperty(BoolPropertyUpdate),
+    DoubleProperty(DoublePropertyUpdate),
+    BytesProperty(BytesPropertyUpdate),
+    TimestampProperty(TimestampPropertyUpdate),
+    StringSetProperty(StringSetPropertyUpdate),
 }
 
 impl TryFrom<UpdateProto> for Update {
@@ -191,6 +356,21 @@ impl TryFrom<UpdateProto> for Update {
                 Ok(Update::Int64Property(update.try_into()?))
             }
             Some(UpdateInnerProto::Edge(update)) => Ok(Update::Edge(update.try_into()?)),
+            Some(UpdateInnerProto::BoolProperty(update)) => {
+                Ok(Update::BoolProperty(update.try_into()?))
+            }
+            Some(UpdateInnerProto::DoubleProperty(update)) => {
+                Ok(Update::DoubleProperty(update.try_into()?))
+            }
+            Some(UpdateInnerProto::BytesProperty(update)) => {
+                Ok(Update::BytesProperty(update.try_into()?))
+            }
+            Some(UpdateInnerProto::TimestampProperty(update)) => {
+                Ok(Update::TimestampProperty(update.try_into()?))
+            }
+            Some(UpdateInnerProto::StringSetProperty(update)) => {
+                Ok(Update::StringSetProperty(update.try_into()?))
+            }
             None => Err(SerDeError::UnknownVariant("Update")),
         }
     }
@@ -211,6 +391,21 @@ impl From<Update> for UpdateProto {
             Update::Edge(update) => UpdateProto {
                 inner: Some(UpdateInnerProto::Edge(update.into())),
             },
+            Update::BoolProperty(update) => UpdateProto {
+                inner: Some(UpdateInnerProto::BoolProperty(update.into())),
+            },
+            Update::DoubleProperty(update) => UpdateProto {
+                inner: Some(UpdateInnerProto::DoubleProperty(update.into())),
+            },
+            Update::BytesProperty(update) => UpdateProto {
+                inner: Some(UpdateInnerProto::BytesProperty(update.into())),
+            },
+            Update::TimestampProperty(update) => UpdateProto {
+                inner: Some(UpdateInnerProto::TimestampProperty(update.into())),
+            },
+            Update::StringSetProperty(update) => UpdateProto {
+                inner: Some(UpdateInnerProto::StringSetProperty(update.into())),
+            },
         }
     }
 }
@@ -1,5 +1,6 @@
 use std::{
     marker::PhantomData,
+    sync::Arc,
     time::Duration,
 };
 
@@ -56,6 +57,33 @@ pub trait GeneratorApi {
         &self,
         request: native::RunGeneratorRequest,
     ) -> Result<native::RunGeneratorResponse, Self::Error>;
+
+    /// Reports whether this generator is able to serve requests. The server's
+    /// healthcheck service polls this; generators which depend on another
+    /// service should override it to check that service.
+    async fn health(&self) -> Result<HealthcheckStatus, HealthcheckError> {
+        Ok(HealthcheckStatus::Serving)
+    }
+}
+
+/// Lets a generator be shared between the server and its healthcheck.
+#[tonic::async_trait]
+impl<T> GeneratorApi for Arc<T>
+where
+    T: GeneratorApi + Send + Sync,
+{
+    type Error = T::Error;
+
+    async fn run_generator(
+        &self,
+        request: native::RunGeneratorRequest,
+    ) -> Result<native::RunGeneratorResponse, Self::Error> {
+        self.as_ref().run_generator(request).await
+    }
+
+    async fn health(&self) -> Result<HealthcheckStatus, HealthcheckError> {
+        self.as_ref().health().await
+    }
 }
 
 #[tonic::async_trait]
@@ -1,3 +1,6 @@
 pub mod error;
 pub mod healthcheck;
+pub mod shutdown;
 pub mod status;
+#[cfg(feature = "test_utils")]
+pub mod test_utils;
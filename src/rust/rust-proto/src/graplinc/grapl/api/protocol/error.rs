@@ -5,4 +5,13 @@ use thiserror::Error;
 pub enum ServeError {
     #[error("encountered tonic error {0}")]
     TransportError(#[from] tonic::transport::Error),
+
+    #[error("failed to listen for shutdown signals {0}")]
+    SignalError(#[from] std::io::Error),
+
+    #[error("server task failed {0}")]
+    JoinError(#[from] tokio::task::JoinError),
+
+    #[error("in-flight requests did not finish within {drain_timeout_ms}ms of shutdown and were dropped")]
+    DrainTimeout { drain_timeout_ms: u64 },
 }
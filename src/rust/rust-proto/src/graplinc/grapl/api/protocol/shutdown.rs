@@ -0,0 +1,149 @@
+use std::{
+    future::Future,
+    time::Duration,
+};
+
+use futures::channel::oneshot::Sender;
+use tokio::signal::unix::{
+    signal,
+    SignalKind,
+};
+
+use crate::graplinc::grapl::api::protocol::error::ServeError;
+
+/// Resolves to the name of the first SIGTERM or SIGINT the process receives.
+pub async fn shutdown_signal() -> Result<&'static str, std::io::Error> {
+    let mut sigterm = signal(SignalKind::terminate())?;
+    let mut sigint = signal(SignalKind::interrupt())?;
+
+    tokio::select! {
+        _ = sigterm.recv() => Ok("SIGTERM"),
+        _ = sigint.recv() => Ok("SIGINT"),
+    }
+}
+
+/// Runs a server's `serve()` future until it stops on its own or
+/// `shutdown_signal` resolves. On a signal, `shutdown_tx` (the sender returned
+/// alongside the server) stops it accepting connections, and in-flight
+/// requests are given up to `drain_timeout` to finish. If they don't, the
+/// server task is aborted, dropping them, and `ServeError::DrainTimeout` is
+/// returned.
+pub async fn serve_until_signal<S>(
+    serve: impl Future<Output = Result<(), ServeError>> + Send + 'static,
+    shutdown_tx: Sender<()>,
+    shutdown_signal: S,
+    drain_timeout: Duration,
+) -> Result<(), ServeError>
+where
+    S: Future<Output = Result<&'static str, std::io::Error>>,
+{
+    let mut server_handle = tokio::task::spawn(serve);
+
+    let signal_name = tokio::select! {
+        result = &mut server_handle => return result?,
+        signal_name = shutdown_signal => signal_name?,
+    };
+
+    tracing::info!(
+        message = "shutting down gRPC server",
+        signal = signal_name,
+        drain_timeout_ms = drain_timeout.as_millis() as u64,
+    );
+
+    // Sending only fails if the server has already stopped, in which case its
+    // result is collected below.
+    let _ = shutdown_tx.send(());
+
+    match tokio::time::timeout(drain_timeout, &mut server_handle).await {
+        Ok(result) => result?,
+        Err(_) => {
+            server_handle.abort();
+            tracing::warn!(
+                message = "in-flight requests did not finish before the shutdown timeout, aborting",
+                drain_timeout_ms = drain_timeout.as_millis() as u64,
+            );
+            Err(ServeError::DrainTimeout {
+                drain_timeout_ms: drain_timeout.as_millis() as u64,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{
+            AtomicBool,
+            Ordering,
+        },
+        Arc,
+    };
+
+    use futures::channel::oneshot;
+
+    use super::*;
+
+    /// Sets its flag when dropped, i.e. when the task holding it is aborted.
+    struct DropFlag(Arc<AtomicBool>);
+
+    impl Drop for DropFlag {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    #[tokio::test]
+    async fn drain_timeout_aborts_the_server() {
+        let dropped = Arc::new(AtomicBool::new(false));
+        let drop_flag = DropFlag(dropped.clone());
+        // A server with a request that never finishes
+        let serve = async move {
+            let _drop_flag = drop_flag;
+            futures::future::pending::<Result<(), ServeError>>().await
+        };
+        let (shutdown_tx, _shutdown_rx) = oneshot::channel();
+
+        let result = serve_until_signal(
+            serve,
+            shutdown_tx,
+            async { Ok("SIGTERM") },
+            Duration::from_millis(10),
+        )
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(ServeError::DrainTimeout {
+                drain_timeout_ms: 10
+            })
+        ));
+
+        // Aborting only takes effect once the runtime next gets to the task.
+        for _ in 0..100 {
+            if dropped.load(Ordering::SeqCst) {
+                break;
+            }
+            tokio::task::yield_now().await;
+        }
+        assert!(dropped.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn drained_servers_stop_cleanly() {
+        let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+        let serve = async move {
+            let _ = shutdown_rx.await;
+            Ok(())
+        };
+
+        let result = serve_until_signal(
+            serve,
+            shutdown_tx,
+            async { Ok("SIGTERM") },
+            Duration::from_secs(10),
+        )
+        .await;
+
+        assert!(result.is_ok());
+    }
+}
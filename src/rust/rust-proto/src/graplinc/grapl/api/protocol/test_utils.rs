@@ -0,0 +1,107 @@
+use std::sync::{
+    atomic::{
+        AtomicBool,
+        AtomicUsize,
+        Ordering,
+    },
+    Arc,
+};
+
+use tokio::sync::Notify;
+
+use crate::graplinc::grapl::api::protocol::healthcheck::HealthcheckStatus;
+
+/// Lets plugin server tests observe the server's health checks and hold
+/// requests in flight, so that they wait on what the server has done instead
+/// of sleeping for long enough that it has probably done it.
+///
+/// A test's plugin calls `health` from its health hook and `handle_request`
+/// from each of its RPCs, and the test holds a clone of the probe.
+#[derive(Clone, Debug, Default)]
+pub struct ServerProbe {
+    inner: Arc<ServerProbeInner>,
+}
+
+#[derive(Debug, Default)]
+struct ServerProbeInner {
+    not_serving_for: usize,
+    health_checks: AtomicUsize,
+    health_checked: Notify,
+    requests: AtomicUsize,
+    request_started: Notify,
+    released: AtomicBool,
+    requests_released: Notify,
+}
+
+impl ServerProbe {
+    /// A probe which reports "not serving" for the first `not_serving_for`
+    /// health checks, like a plugin waiting on a dependency, and holds every
+    /// request until `release_requests` is called.
+    pub fn new(not_serving_for: usize) -> Self {
+        Self {
+            inner: Arc::new(ServerProbeInner {
+                not_serving_for,
+                ..Default::default()
+            }),
+        }
+    }
+
+    /// Counts a health check, and reports the plugin's health.
+    pub fn health(&self) -> HealthcheckStatus {
+        let health_checks = self.inner.health_checks.fetch_add(1, Ordering::SeqCst);
+        self.inner.health_checked.notify_waiters();
+
+        if health_checks < self.inner.not_serving_for {
+            HealthcheckStatus::NotServing
+        } else {
+            HealthcheckStatus::Serving
+        }
+    }
+
+    /// Counts a request, and waits until requests are released.
+    pub async fn handle_request(&self) {
+        self.inner.requests.fetch_add(1, Ordering::SeqCst);
+        self.inner.request_started.notify_waiters();
+
+        wait_until(&self.inner.requests_released, || {
+            self.inner.released.load(Ordering::SeqCst)
+        })
+        .await;
+    }
+
+    /// Lets every held request, and every later one, finish.
+    pub fn release_requests(&self) {
+        self.inner.released.store(true, Ordering::SeqCst);
+        self.inner.requests_released.notify_waiters();
+    }
+
+    /// Waits until the server has checked the plugin's health `count` times.
+    pub async fn wait_for_health_checks(&self, count: usize) {
+        wait_until(&self.inner.health_checked, || {
+            self.inner.health_checks.load(Ordering::SeqCst) >= count
+        })
+        .await;
+    }
+
+    /// Waits until `count` requests have reached the plugin.
+    pub async fn wait_for_requests(&self, count: usize) {
+        wait_until(&self.inner.request_started, || {
+            self.inner.requests.load(Ordering::SeqCst) >= count
+        })
+        .await;
+    }
+}
+
+/// Waits for `condition` to hold, re-checking it whenever `notify` wakes its
+/// waiters.
+async fn wait_until(notify: &Notify, condition: impl Fn() -> bool) {
+    loop {
+        // A `Notified` receives every `notify_waiters` from when it's created,
+        // so a notification between checking and awaiting isn't missed.
+        let notified = notify.notified();
+        if condition() {
+            return;
+        }
+        notified.await;
+    }
+}
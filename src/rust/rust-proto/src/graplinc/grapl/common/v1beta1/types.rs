@@ -31,30 +31,26 @@ impl PropertyName {
 impl TryFrom<&'static str> for PropertyName {
     type Error = SerDeError;
     fn try_from(raw: &'static str) -> Result<Self, Self::Error> {
-        if raw.is_empty() {
+        PropertyName::try_from(raw.to_owned())
+    }
+}
+
+impl TryFrom<String> for PropertyName {
+    type Error = SerDeError;
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        if value.is_empty() {
             return Err(SerDeError::InvalidField {
                 field_name: "PropertyName",
                 assertion: "can not be empty".to_owned(),
             });
         }
-        if raw.len() > 32 {
+        if value.len() > 32 {
             return Err(SerDeError::InvalidField {
                 field_name: "PropertyName",
                 assertion: "can not be more than 32 characters".to_owned(),
             });
         }
 
-        Ok(Self {
-            value: raw.to_owned(),
-        })
-    }
-}
-
-impl TryFrom<String> for PropertyName {
-    type Error = SerDeError;
-    fn try_from(value: String) -> Result<Self, Self::Error> {
-        // While this is currently infallible, we may add validation later,
-        // hence the TryFrom.
         Ok(PropertyName { value })
     }
 }
@@ -155,22 +151,27 @@ impl std::fmt::Display for NodeType {
 impl TryFrom<&'static str> for NodeType {
     type Error = SerDeError;
     fn try_from(raw: &'static str) -> Result<Self, Self::Error> {
-        if raw.is_empty() {
+        NodeType::try_from(raw.to_owned())
+    }
+}
+
+impl TryFrom<String> for NodeType {
+    type Error = SerDeError;
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        if value.is_empty() {
             return Err(SerDeError::InvalidField {
-                field_name: "EdgeName",
+                field_name: "NodeType",
                 assertion: "can not be empty".to_owned(),
             });
         }
-        if raw.len() > 32 {
+        if value.len() > 32 {
             return Err(SerDeError::InvalidField {
-                field_name: "EdgeName",
+                field_name: "NodeType",
                 assertion: "can not be more than 32 characters".to_owned(),
             });
         }
 
-        Ok(Self {
-            value: raw.to_owned(),
-        })
+        Ok(Self { value })
     }
 }
 
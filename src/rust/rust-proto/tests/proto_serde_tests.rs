@@ -146,6 +146,55 @@ mod graph {
             check_encode_decode_invariant(increment_only_uint_prop)
         }
 
+        #[test]
+        fn test_immutable_bool_prop_encode_decode(
+            immutable_bool_prop in st::immutable_bool_props()
+        ) {
+            check_encode_decode_invariant(immutable_bool_prop)
+        }
+
+        #[test]
+        fn test_immutable_double_prop_encode_decode(
+            immutable_double_prop in st::immutable_double_props()
+        ) {
+            check_encode_decode_invariant(immutable_double_prop)
+        }
+
+        #[test]
+        fn test_immutable_bytes_prop_encode_decode(
+            immutable_bytes_prop in st::immutable_bytes_props()
+        ) {
+            check_encode_decode_invariant(immutable_bytes_prop)
+        }
+
+        #[test]
+        fn test_immutable_timestamp_prop_encode_decode(
+            immutable_timestamp_prop in st::immutable_timestamp_props()
+        ) {
+            check_encode_decode_invariant(immutable_timestamp_prop)
+        }
+
+        #[test]
+        fn test_increment_only_timestamp_prop_encode_decode(
+            increment_only_timestamp_prop in st::increment_only_timestamp_props()
+        ) {
+            check_encode_decode_invariant(increment_only_timestamp_prop)
+        }
+
+        #[test]
+        fn test_decrement_only_timestamp_prop_encode_decode(
+            decrement_only_timestamp_prop in st::decrement_only_timestamp_props()
+        ) {
+            check_encode_decode_invariant(decrement_only_timestamp_prop)
+        }
+
+        #[test]
+        fn test_string_set_prop_encode_decode(
+            string_set_prop in st::string_set_props()
+        ) {
+            check_encode_decode_invariant(string_set_prop)
+        }
+
         #[test]
         fn test_edge_encode_decode(edge in st::edges()) {
             check_encode_decode_invariant(edge)
@@ -519,6 +568,16 @@ mod graph_schema_manager {
         fn test_get_edge_schema_responses(value in sm_strats::get_edge_schema_responses()) {
             check_encode_decode_invariant(value)
         }
+
+        #[test]
+        fn test_get_node_schema_requests(value in sm_strats::get_node_schema_requests()) {
+            check_encode_decode_invariant(value)
+        }
+
+        #[test]
+        fn test_get_node_schema_responses(value in sm_strats::get_node_schema_responses()) {
+            check_encode_decode_invariant(value)
+        }
     }
 }
 
@@ -549,6 +608,11 @@ mod analyzer_sdk {
             check_encode_decode_invariant(value)
         }
 
+        #[test]
+        fn test_float_filters(value in as_strats::float_filters()) {
+            check_encode_decode_invariant(value)
+        }
+
         #[test]
         fn test_uid_filters(value in as_strats::uid_filters()) {
             check_encode_decode_invariant(value)
@@ -77,7 +77,9 @@ pub mod pipeline {
 
 pub mod common {
 
-    use rust_proto::graplinc::grapl::common::v1beta1::types::{self as native,};
+    use rust_proto::graplinc::grapl::common::v1beta1::types::{
+        self as native,
+    };
 
     use super::*;
     prop_compose! {
@@ -126,6 +128,7 @@ pub mod graph {
     use proptest::collection;
     use rust_proto::graplinc::grapl::api::graph::v1beta1::{
         DecrementOnlyIntProp,
+        DecrementOnlyTimestampProp,
         DecrementOnlyUintProp,
         Edge,
         EdgeList,
@@ -135,10 +138,15 @@ pub mod graph {
         IdentifiedEdgeList,
         IdentifiedGraph,
         IdentifiedNode,
+        ImmutableBoolProp,
+        ImmutableBytesProp,
+        ImmutableDoubleProp,
         ImmutableIntProp,
         ImmutableStrProp,
+        ImmutableTimestampProp,
         ImmutableUintProp,
         IncrementOnlyIntProp,
+        IncrementOnlyTimestampProp,
         IncrementOnlyUintProp,
         Lens,
         NodeDescription,
@@ -147,6 +155,7 @@ pub mod graph {
         Session,
         Static,
         Strategy as GraphStrategy,
+        StringSetProp,
     };
 
     use super::*;
@@ -249,6 +258,104 @@ pub mod graph {
         }
     }
 
+    //
+    // ImmutableBoolProp
+    //
+
+    prop_compose! {
+        pub fn immutable_bool_props()(
+            prop in any::<bool>(),
+        ) -> ImmutableBoolProp {
+            ImmutableBoolProp {
+                prop
+            }
+        }
+    }
+
+    //
+    // ImmutableDoubleProp
+    //
+
+    prop_compose! {
+        pub fn immutable_double_props()(
+            prop in any::<f64>(),
+        ) -> ImmutableDoubleProp {
+            ImmutableDoubleProp {
+                prop
+            }
+        }
+    }
+
+    //
+    // ImmutableBytesProp
+    //
+
+    prop_compose! {
+        pub fn immutable_bytes_props()(
+            prop in bytes(32),
+        ) -> ImmutableBytesProp {
+            ImmutableBytesProp {
+                prop
+            }
+        }
+    }
+
+    //
+    // ImmutableTimestampProp
+    //
+
+    prop_compose! {
+        pub fn immutable_timestamp_props()(
+            prop in any::<SystemTime>(),
+        ) -> ImmutableTimestampProp {
+            ImmutableTimestampProp {
+                prop
+            }
+        }
+    }
+
+    //
+    // IncrementOnlyTimestampProp
+    //
+
+    prop_compose! {
+        pub fn increment_only_timestamp_props()(
+            prop in any::<SystemTime>(),
+        ) -> IncrementOnlyTimestampProp {
+            IncrementOnlyTimestampProp {
+                prop
+            }
+        }
+    }
+
+    //
+    // DecrementOnlyTimestampProp
+    //
+
+    prop_compose! {
+        pub fn decrement_only_timestamp_props()(
+            prop in any::<SystemTime>(),
+        ) -> DecrementOnlyTimestampProp {
+            DecrementOnlyTimestampProp {
+                prop
+            }
+        }
+    }
+
+    //
+    // StringSetProp
+    //
+
+    prop_compose! {
+        pub fn string_set_props()(
+            prop in collection::btree_set(any::<String>(), 0..10),
+        ) -> StringSetProp {
+            StringSetProp {
+                prop
+            }
+        }
+    }
+
     //
     // Lens
     //
@@ -375,6 +482,13 @@ pub mod graph {
             immutable_uint_props().prop_map(Property::ImmutableUintProp),
             increment_only_int_props().prop_map(Property::IncrementOnlyIntProp),
             increment_only_uint_props().prop_map(Property::IncrementOnlyUintProp),
+            immutable_bool_props().prop_map(Property::ImmutableBoolProp),
+            immutable_double_props().prop_map(Property::ImmutableDoubleProp),
+            immutable_bytes_props().prop_map(Property::ImmutableBytesProp),
+            immutable_timestamp_props().prop_map(Property::ImmutableTimestampProp),
+            increment_only_timestamp_props().prop_map(Property::IncrementOnlyTimestampProp),
+            decrement_only_timestamp_props().prop_map(Property::DecrementOnlyTimestampProp),
+            string_set_props().prop_map(Property::StringSetProp),
         ]
     }
 
@@ -1092,6 +1206,7 @@ pub mod plugin_work_queue {
 }
 
 pub mod graph_schema_manager {
+    use proptest::collection;
     use rust_proto::graplinc::grapl::api::graph_schema_manager::v1beta1::messages::{
         self as native,
     };
@@ -1144,11 +1259,107 @@ pub mod graph_schema_manager {
             }
         }
     }
+
+    pub fn property_types() -> BoxedStrategy<native::PropertyType> {
+        prop_oneof![
+            Just(native::PropertyType::ImmutableString),
+            Just(native::PropertyType::ImmutableI64),
+            Just(native::PropertyType::MaxI64),
+            Just(native::PropertyType::MinI64),
+            Just(native::PropertyType::ImmutableU64),
+            Just(native::PropertyType::MaxU64),
+            Just(native::PropertyType::MinU64),
+            Just(native::PropertyType::ImmutableBool),
+            Just(native::PropertyType::ImmutableF64),
+            Just(native::PropertyType::ImmutableBytes),
+            Just(native::PropertyType::ImmutableTimestamp),
+            Just(native::PropertyType::MaxTimestamp),
+            Just(native::PropertyType::MinTimestamp),
+            Just(native::PropertyType::StringSet),
+        ]
+        .boxed()
+    }
+
+    prop_compose! {
+        pub fn property_schemas()(
+            property_name in common::property_names(),
+            property_type in property_types(),
+            identity_only in any::<bool>(),
+        ) -> native::PropertySchema {
+            native::PropertySchema {
+                property_name,
+                property_type,
+                identity_only,
+            }
+        }
+    }
+
+    prop_compose! {
+        pub fn static_identities()(
+            static_key_properties in collection::vec(common::property_names(), 1..4),
+        ) -> native::StaticIdentity {
+            native::StaticIdentity {
+                static_key_properties,
+            }
+        }
+    }
+
+    prop_compose! {
+        pub fn session_identities()(
+            pseudo_key_properties in collection::vec(common::property_names(), 1..4),
+            creation_timestamp_property in common::property_names(),
+            last_seen_timestamp_property in common::property_names(),
+            termination_timestamp_property in common::property_names(),
+        ) -> native::SessionIdentity {
+            native::SessionIdentity {
+                pseudo_key_properties,
+                creation_timestamp_property,
+                last_seen_timestamp_property,
+                termination_timestamp_property,
+            }
+        }
+    }
+
+    pub fn node_identities() -> BoxedStrategy<native::NodeIdentity> {
+        prop_oneof![
+            static_identities().prop_map(native::NodeIdentity::Static),
+            session_identities().prop_map(native::NodeIdentity::Session),
+        ]
+        .boxed()
+    }
+
+    prop_compose! {
+        pub fn get_node_schema_requests()(
+            tenant_id in uuids(),
+            node_type in common::node_types(),
+        ) -> native::GetNodeSchemaRequest {
+            native::GetNodeSchemaRequest {
+                tenant_id,
+                node_type,
+            }
+        }
+    }
+
+    prop_compose! {
+        pub fn get_node_schema_responses()(
+            schema_version in any::<u32>(),
+            identity in node_identities(),
+            properties in collection::vec(property_schemas(), 10),
+        ) -> native::GetNodeSchemaResponse {
+            native::GetNodeSchemaResponse {
+                schema_version,
+                identity,
+                properties,
+            }
+        }
+    }
 }
 
 pub mod analyzer_sdk {
     use rust_proto::graplinc::grapl::api::{
         graph_query::v1beta1::messages::{
+            FloatFilter,
+            FloatOperation,
             IntFilter,
             IntOperation,
             StringFilter,
@@ -1156,7 +1367,9 @@ pub mod analyzer_sdk {
             UidFilter,
             UidOperation,
         },
-        plugin_sdk::analyzers::v1beta1::messages::{self as native,},
+        plugin_sdk::analyzers::v1beta1::messages::{
+            self as native,
+        },
     };
 
     use super::{
@@ -1201,6 +1414,61 @@ pub mod analyzer_sdk {
         }
     }
 
+    prop_compose! {
+        pub fn bool_property_updates()(
+            uid in uids(),
+            property_name in property_names(),
+        ) -> native::BoolPropertyUpdate {
+            native::BoolPropertyUpdate {
+                uid, property_name,
+            }
+        }
+    }
+
+    prop_compose! {
+        pub fn double_property_updates()(
+            uid in uids(),
+            property_name in property_names(),
+        ) -> native::DoublePropertyUpdate {
+            native::DoublePropertyUpdate {
+                uid, property_name,
+            }
+        }
+    }
+
+    prop_compose! {
+        pub fn bytes_property_updates()(
+            uid in uids(),
+            property_name in property_names(),
+        ) -> native::BytesPropertyUpdate {
+            native::BytesPropertyUpdate {
+                uid, property_name,
+            }
+        }
+    }
+
+    prop_compose! {
+        pub fn timestamp_property_updates()(
+            uid in uids(),
+            property_name in property_names(),
+        ) -> native::TimestampPropertyUpdate {
+            native::TimestampPropertyUpdate {
+                uid, property_name,
+            }
+        }
+    }
+
+    prop_compose! {
+        pub fn string_set_property_updates()(
+            uid in uids(),
+            property_name in property_names(),
+        ) -> native::StringSetPropertyUpdate {
+            native::StringSetPropertyUpdate {
+                uid, property_name,
+            }
+        }
+    }
+
     prop_compose! {
         pub fn edge_updates()(
             src_uid in uids(),
@@ -1223,6 +1491,11 @@ pub mod analyzer_sdk {
             uint_64_property_updates().prop_map(native::Update::Uint64Property),
             int_64_property_updates().prop_map(native::Update::Int64Property),
             edge_updates().prop_map(native::Update::Edge),
+            bool_property_updates().prop_map(native::Update::BoolProperty),
+            double_property_updates().prop_map(native::Update::DoubleProperty),
+            bytes_property_updates().prop_map(native::Update::BytesProperty),
+            timestamp_property_updates().prop_map(native::Update::TimestampProperty),
+            string_set_property_updates().prop_map(native::Update::StringSetProperty),
         ]
     }
 
@@ -1274,6 +1547,31 @@ pub mod analyzer_sdk {
         }
     }
 
+    pub fn float_operations() -> impl Strategy<Value = FloatOperation> {
+        prop_oneof![
+            Just(FloatOperation::Has),
+            Just(FloatOperation::Equal),
+            Just(FloatOperation::LessThan),
+            Just(FloatOperation::LessThanOrEqual),
+            Just(FloatOperation::GreaterThan),
+            Just(FloatOperation::GreaterThanOrEqual),
+        ]
+    }
+
+    prop_compose! {
+        pub fn float_filters()(
+            operation in float_operations(),
+            value in any::<f64>(),
+            negated in any::<bool>(),
+        ) -> FloatFilter {
+            FloatFilter {
+                operation,
+                value,
+                negated,
+            }
+        }
+    }
+
     pub fn uid_operations() -> impl Strategy<Value = UidOperation> {
         prop_oneof![Just(UidOperation::Equal),]
     }
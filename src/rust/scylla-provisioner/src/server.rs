@@ -29,13 +29,23 @@ use tokio::net::TcpListener;
 use crate::{
     config::ScyllaProvisionerServiceConfig,
     table_names::{
+        IMM_BOOL_TABLE_NAME,
+        IMM_BYTES_TABLE_NAME,
+        IMM_F_64_TABLE_NAME,
+        IMM_I_64_INDEX_TABLE_NAME,
         IMM_I_64_TABLE_NAME,
+        IMM_STRING_INDEX_TABLE_NAME,
         IMM_STRING_TABLE_NAME,
+        IMM_TIMESTAMP_TABLE_NAME,
+        IMM_U_64_INDEX_TABLE_NAME,
         IMM_U_64_TABLE_NAME,
         MAX_I_64_TABLE_NAME,
+        MAX_TIMESTAMP_TABLE_NAME,
         MAX_U_64_TABLE_NAME,
         MIN_I_64_TABLE_NAME,
+        MIN_TIMESTAMP_TABLE_NAME,
         MIN_U_64_TABLE_NAME,
+        STRING_SET_TABLE_NAME,
     },
 };
 
@@ -89,6 +99,13 @@ impl ScyllaProvisionerApi for ScyllaProvisioner {
             (MAX_U_64_TABLE_NAME, "bigint"),
             (MIN_U_64_TABLE_NAME, "bigint"),
             (IMM_U_64_TABLE_NAME, "bigint"),
+            (IMM_BOOL_TABLE_NAME, "boolean"),
+            (IMM_F_64_TABLE_NAME, "double"),
+            (IMM_BYTES_TABLE_NAME, "blob"),
+            (IMM_TIMESTAMP_TABLE_NAME, "timestamp"),
+            (MAX_TIMESTAMP_TABLE_NAME, "timestamp"),
+            (MIN_TIMESTAMP_TABLE_NAME, "timestamp"),
+            (STRING_SET_TABLE_NAME, "set<text>"),
         ];
 
         for (table_name, value_type) in property_table_names.into_iter() {
@@ -108,6 +125,33 @@ impl ScyllaProvisionerApi for ScyllaProvisioner {
                 .await?;
         }
 
+        // Inverted indexes over the immutable properties, keyed by value, so that
+        // nodes can be searched for without knowing their uid up front. Rows are
+        // clustered by uid so that a search can be paged through.
+        let property_index_table_names = [
+            (IMM_STRING_INDEX_TABLE_NAME, "text"),
+            (IMM_I_64_INDEX_TABLE_NAME, "bigint"),
+            (IMM_U_64_INDEX_TABLE_NAME, "bigint"),
+        ];
+
+        for (table_name, value_type) in property_index_table_names.into_iter() {
+            session
+                .query(
+                    format!(
+                        r"CREATE TABLE IF NOT EXISTS tenant_graph_ks.{table_name} (
+                            tenant_id uuid,
+                            node_type text,
+                            populated_field text,
+                            value {value_type},
+                            uid bigint,
+                            PRIMARY KEY ((tenant_id, node_type, populated_field, value), uid)
+                        )"
+                    ),
+                    &(),
+                )
+                .await?;
+        }
+
         session
             .query(
                 "CREATE TABLE IF NOT EXISTS tenant_graph_ks.node_type (
@@ -7,3 +7,13 @@ pub const MAX_U_64_TABLE_NAME: &str = "max_u64";
 pub const MIN_U_64_TABLE_NAME: &str = "min_u64";
 pub const IMM_U_64_TABLE_NAME: &str = "imm_u64";
 pub const IMM_STRING_TABLE_NAME: &str = "imm_string";
+pub const IMM_BOOL_TABLE_NAME: &str = "imm_bool";
+pub const IMM_F_64_TABLE_NAME: &str = "imm_f64";
+pub const IMM_BYTES_TABLE_NAME: &str = "imm_bytes";
+pub const IMM_TIMESTAMP_TABLE_NAME: &str = "imm_timestamp";
+pub const MAX_TIMESTAMP_TABLE_NAME: &str = "max_timestamp";
+pub const MIN_TIMESTAMP_TABLE_NAME: &str = "min_timestamp";
+pub const STRING_SET_TABLE_NAME: &str = "string_set";
+pub const IMM_I_64_INDEX_TABLE_NAME: &str = "imm_i64_index";
+pub const IMM_U_64_INDEX_TABLE_NAME: &str = "imm_u64_index";
+pub const IMM_STRING_INDEX_TABLE_NAME: &str = "imm_string_index";
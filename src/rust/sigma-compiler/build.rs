@@ -0,0 +1,40 @@
+//! Bundles the rules that the sigma-analyzer binary runs into a single YAML
+//! stream, so that the analyzer plugin is a self-contained binary. Rules are
+//! read from the directory named by `SIGMA_RULES_DIR`, or `rules/` by default.
+use std::{
+    fs,
+    path::PathBuf,
+};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("cargo:rerun-if-env-changed=SIGMA_RULES_DIR");
+
+    let rules_dir = std::env::var_os("SIGMA_RULES_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("rules"));
+    println!("cargo:rerun-if-changed={}", rules_dir.display());
+
+    let mut rule_paths: Vec<PathBuf> = fs::read_dir(&rules_dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<_, _>>()?;
+    rule_paths.retain(|path| {
+        matches!(
+            path.extension().and_then(|extension| extension.to_str()),
+            Some("yml" | "yaml")
+        )
+    });
+    rule_paths.sort();
+
+    let mut bundle = String::new();
+    for path in rule_paths {
+        println!("cargo:rerun-if-changed={}", path.display());
+        bundle.push_str("---\n");
+        bundle.push_str(&fs::read_to_string(&path)?);
+        bundle.push('\n');
+    }
+
+    let out_dir = PathBuf::from(std::env::var("OUT_DIR")?);
+    fs::write(out_dir.join("rules.yml"), bundle)?;
+
+    Ok(())
+}
@@ -0,0 +1,162 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{
+        Hash,
+        Hasher,
+    },
+    time::SystemTime,
+};
+
+use rust_proto::graplinc::grapl::{
+    api::{
+        client::ClientError,
+        graph_query_proxy::v1beta1::client::GraphQueryProxyClient,
+        plugin_sdk::analyzers::v1beta1::{
+            messages::{
+                AnalyzerName,
+                ExecutionHit,
+                ExecutionMiss,
+                ExecutionResult,
+                RunAnalyzerRequest,
+                RunAnalyzerResponse,
+                Update,
+            },
+            server::AnalyzerApi,
+        },
+        protocol::status::Status,
+    },
+    common::v1beta1::types::Uid,
+};
+
+use crate::{
+    compile::{
+        CompiledQuery,
+        CompiledRule,
+    },
+    rule::Level,
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum SigmaAnalyzerError {
+    #[error("graph query failed: {0}")]
+    GraphQuery(#[from] ClientError),
+}
+
+impl From<SigmaAnalyzerError> for Status {
+    fn from(e: SigmaAnalyzerError) -> Self {
+        match e {
+            SigmaAnalyzerError::GraphQuery(e) => Status::unavailable(e.to_string()),
+        }
+    }
+}
+
+/// Runs a set of compiled Sigma rules against each update to the graph,
+/// reporting a hit for the first rule that matches.
+pub struct SigmaAnalyzer {
+    rules: Vec<CompiledRule>,
+    graph_query_client: GraphQueryProxyClient,
+}
+
+impl SigmaAnalyzer {
+    pub fn new(rules: Vec<CompiledRule>, graph_query_client: GraphQueryProxyClient) -> Self {
+        Self {
+            rules,
+            graph_query_client,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl AnalyzerApi for SigmaAnalyzer {
+    type Error = SigmaAnalyzerError;
+
+    #[tracing::instrument(skip(self, request), err)]
+    async fn run_analyzer(
+        &self,
+        request: RunAnalyzerRequest,
+    ) -> Result<RunAnalyzerResponse, Self::Error> {
+        let update = request.update();
+        let uid = updated_uid(&update);
+        let mut graph_query_client = self.graph_query_client.clone();
+
+        for rule in &self.rules {
+            for query in rule
+                .queries
+                .iter()
+                .filter(|query| is_affected_by(query, &update))
+            {
+                let matched = match query
+                    .query
+                    .query_graph_with_uid(&mut graph_query_client, uid)
+                    .await?
+                {
+                    Some(matched) => matched,
+                    None => continue,
+                };
+
+                tracing::debug!(
+                    message = "sigma rule matched",
+                    title = %rule.title,
+                    root_uid = ?matched.root_uid,
+                );
+
+                let execution_result = ExecutionResult::ExecutionHit(ExecutionHit {
+                    graph_view: matched.graph,
+                    lens_refs: vec![],
+                    analyzer_name: AnalyzerName {
+                        value: rule.title.clone(),
+                    },
+                    time_of_match: SystemTime::now(),
+                    idempotency_key: idempotency_key(rule, matched.root_uid),
+                    score: rule.level.unwrap_or(Level::Medium).score(),
+                });
+                return Ok(RunAnalyzerResponse { execution_result });
+            }
+        }
+
+        Ok(RunAnalyzerResponse {
+            execution_result: ExecutionResult::ExecutionMiss(ExecutionMiss {}),
+        })
+    }
+}
+
+fn updated_uid(update: &Update) -> Uid {
+    match update {
+        Update::StringProperty(update) => update.uid,
+        Update::Uint64Property(update) => update.uid,
+        Update::Int64Property(update) => update.uid,
+        Update::BoolProperty(update) => update.uid,
+        Update::DoubleProperty(update) => update.uid,
+        Update::BytesProperty(update) => update.uid,
+        Update::TimestampProperty(update) => update.uid,
+        Update::StringSetProperty(update) => update.uid,
+        Update::Edge(update) => update.src_uid,
+    }
+}
+
+fn is_affected_by(query: &CompiledQuery, update: &Update) -> bool {
+    let property_name = match update {
+        Update::StringProperty(update) => &update.property_name,
+        Update::Uint64Property(update) => &update.property_name,
+        Update::Int64Property(update) => &update.property_name,
+        Update::BoolProperty(update) => &update.property_name,
+        Update::DoubleProperty(update) => &update.property_name,
+        Update::BytesProperty(update) => &update.property_name,
+        Update::TimestampProperty(update) => &update.property_name,
+        Update::StringSetProperty(update) => &update.property_name,
+        Update::Edge(update) => {
+            return query
+                .edge_names
+                .contains(update.forward_edge_name.value.as_str())
+        }
+    };
+    query.property_names.contains(property_name.value.as_str())
+}
+
+/// The same rule matching the same root node is reported once.
+fn idempotency_key(rule: &CompiledRule, root_uid: Uid) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    rule.id.as_ref().unwrap_or(&rule.title).hash(&mut hasher);
+    root_uid.hash(&mut hasher);
+    hasher.finish()
+}
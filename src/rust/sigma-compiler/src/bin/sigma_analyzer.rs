@@ -0,0 +1,41 @@
+/// An analyzer plugin running the Sigma rules bundled into it at build time.
+/// Build it with `SIGMA_RULES_DIR=path/to/rules cargo build --bin
+/// sigma-analyzer` to package a different rule set.
+use analyzer_sdk::server::{
+    self,
+    AnalyzerServiceConfig,
+};
+use figment::{
+    providers::Env,
+    Figment,
+};
+use grapl_tracing::setup_tracing;
+use rust_proto::graplinc::grapl::api::{
+    client::Connect,
+    graph_query_proxy::v1beta1::client::GraphQueryProxyClient,
+};
+use sigma_compiler::{
+    compile_rules,
+    SigmaAnalyzer,
+};
+
+const SERVICE_NAME: &str = "sigma-analyzer";
+
+const RULES: &str = include_str!(concat!(env!("OUT_DIR"), "/rules.yml"));
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let _guard = setup_tracing(SERVICE_NAME)?;
+    let config = AnalyzerServiceConfig::from_env_vars();
+
+    let rules = compile_rules(RULES)?;
+    tracing::info!(message = "compiled sigma rules", rule_count = rules.len());
+
+    let graph_query_client_config = Figment::new()
+        .merge(Env::prefixed("GRAPH_QUERY_PROXY_CLIENT_"))
+        .extract()?;
+    let graph_query_client = GraphQueryProxyClient::connect(graph_query_client_config).await?;
+
+    let analyzer = SigmaAnalyzer::new(rules, graph_query_client);
+    server::exec_service(analyzer, config).await
+}
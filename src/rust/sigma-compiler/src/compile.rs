@@ -0,0 +1,712 @@
+use std::collections::{
+    BTreeMap,
+    BTreeSet,
+};
+
+use graph_query_builder::NodeQueryBuilder;
+use rust_proto::graplinc::grapl::{
+    api::graph_query::v1beta1::messages::{
+        AndIntFilters,
+        AndStringFilters,
+        IntFilter,
+        IntOperation,
+        StringFilter,
+        StringOperation,
+    },
+    common::v1beta1::types::{
+        EdgeName,
+        NodeType,
+        PropertyName,
+    },
+};
+use serde_yaml::Value;
+
+use crate::{
+    condition::{
+        parse_condition,
+        ConditionExpr,
+        Selector,
+    },
+    error::SigmaError,
+    logsource::{
+        FieldMapping,
+        Hop,
+        LogSourceMapping,
+        PropertyKind,
+    },
+    rule::{
+        Level,
+        SigmaRule,
+    },
+};
+
+/// Rules are compiled to a query per conjunction of their condition in
+/// disjunctive normal form, which grows exponentially with e.g. negated
+/// selections of several fields, so this bounds the number of queries a
+/// single rule may need.
+pub const MAX_QUERIES_PER_RULE: usize = 64;
+
+#[derive(Debug, Clone)]
+pub struct CompiledRule {
+    pub title: String,
+    pub id: Option<String>,
+    pub level: Option<Level>,
+    pub category: &'static str,
+    /// The rule matches if any of these queries does.
+    pub queries: Vec<CompiledQuery>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CompiledQuery {
+    pub query: NodeQueryBuilder,
+    /// The properties and edges the query filters on. A query can only start
+    /// matching after an update to one of them.
+    pub property_names: BTreeSet<&'static str>,
+    pub edge_names: BTreeSet<&'static str>,
+}
+
+pub fn compile_rule(rule: &SigmaRule) -> Result<CompiledRule, SigmaError> {
+    let mapping = LogSourceMapping::for_category(rule.logsource.category.as_deref())?;
+
+    if rule.detection.timeframe.is_some() {
+        return Err(SigmaError::Unsupported("timeframes"));
+    }
+
+    let search_identifiers = rule
+        .detection
+        .search_identifiers
+        .iter()
+        .map(|(name, value)| Ok((name.as_str(), search_identifier_expr(name, value, mapping)?)))
+        .collect::<Result<BTreeMap<_, _>, SigmaError>>()?;
+
+    let conditions = rule
+        .detection
+        .condition
+        .as_slice()
+        .iter()
+        .map(|condition| condition_expr(&parse_condition(condition)?, &search_identifiers))
+        .collect::<Result<Vec<_>, SigmaError>>()?;
+
+    let conjunctions = to_dnf(&Expr::Or(conditions), false)?;
+    if conjunctions.is_empty() {
+        return Err(SigmaError::Unsupported("conditions that never match"));
+    }
+    if conjunctions.iter().any(Vec::is_empty) {
+        return Err(SigmaError::Unsupported("conditions that match every event"));
+    }
+
+    let queries = conjunctions
+        .into_iter()
+        .map(|conjunction| compile_conjunction(mapping, conjunction))
+        .collect();
+
+    Ok(CompiledRule {
+        title: rule.title.clone(),
+        id: rule.id.clone(),
+        level: rule.level,
+        category: mapping.category,
+        queries,
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Expr {
+    Constant(bool),
+    Atom(Atom),
+    And(Vec<Expr>),
+    Or(Vec<Expr>),
+    Not(Box<Expr>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Atom {
+    path: &'static [Hop],
+    property_name: &'static str,
+    filter: Filter,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Filter {
+    String {
+        operation: StringOperation,
+        value: String,
+    },
+    Int {
+        operation: IntOperation,
+        value: i64,
+    },
+}
+
+/// An atom which may be negated.
+type Literal = (Atom, bool);
+
+fn condition_expr(
+    condition: &ConditionExpr,
+    search_identifiers: &BTreeMap<&str, Expr>,
+) -> Result<Expr, SigmaError> {
+    let quantified = |selector: &Selector| {
+        let exprs: Vec<Expr> = search_identifiers
+            .iter()
+            .filter(|(name, _)| selector.matches(name))
+            .map(|(_, expr)| expr.clone())
+            .collect();
+        match (exprs.is_empty(), selector) {
+            (true, Selector::Pattern(pattern)) => {
+                Err(SigmaError::UnknownSearchIdentifier(pattern.clone()))
+            }
+            (true, Selector::Them) => Err(SigmaError::UnknownSearchIdentifier("them".to_owned())),
+            (false, _) => Ok(exprs),
+        }
+    };
+
+    Ok(match condition {
+        ConditionExpr::Identifier(name) => search_identifiers
+            .get(name.as_str())
+            .cloned()
+            .ok_or_else(|| SigmaError::UnknownSearchIdentifier(name.clone()))?,
+        ConditionExpr::OneOf(selector) => Expr::Or(quantified(selector)?),
+        ConditionExpr::AllOf(selector) => Expr::And(quantified(selector)?),
+        ConditionExpr::Not(inner) => {
+            Expr::Not(Box::new(condition_expr(inner, search_identifiers)?))
+        }
+        ConditionExpr::And(left, right) => Expr::And(vec![
+            condition_expr(left, search_identifiers)?,
+            condition_expr(right, search_identifiers)?,
+        ]),
+        ConditionExpr::Or(left, right) => Expr::Or(vec![
+            condition_expr(left, search_identifiers)?,
+            condition_expr(right, search_identifiers)?,
+        ]),
+    })
+}
+
+/// A map of fields matches if every field does, and a list of maps matches
+/// if any map does.
+fn search_identifier_expr(
+    name: &str,
+    value: &Value,
+    mapping: &LogSourceMapping,
+) -> Result<Expr, SigmaError> {
+    match value {
+        Value::Mapping(fields) => fields
+            .iter()
+            .map(|(field, values)| match field {
+                Value::String(field) => field_expr(field, values, mapping),
+                _ => Err(SigmaError::InvalidSearchIdentifier {
+                    name: name.to_owned(),
+                    reason: "field names must be strings".to_owned(),
+                }),
+            })
+            .collect::<Result<_, _>>()
+            .map(Expr::And),
+        Value::Sequence(items) if items.iter().all(Value::is_mapping) => items
+            .iter()
+            .map(|item| search_identifier_expr(name, item, mapping))
+            .collect::<Result<_, _>>()
+            .map(Expr::Or),
+        Value::Sequence(_) => Err(SigmaError::Unsupported("keyword searches")),
+        _ => Err(SigmaError::InvalidSearchIdentifier {
+            name: name.to_owned(),
+            reason: "expected a map of fields or a list of maps".to_owned(),
+        }),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Position {
+    Anywhere,
+    Start,
+    End,
+    Whole,
+}
+
+/// Compiles e.g. `CommandLine|contains|all: [a, b]`. A list of values
+/// matches if any value does, or with `|all` if every value does.
+fn field_expr(
+    field_spec: &str,
+    values: &Value,
+    mapping: &LogSourceMapping,
+) -> Result<Expr, SigmaError> {
+    let mut parts = field_spec.split('|');
+    let field = parts.next().unwrap_or_default();
+    let field_mapping = mapping.field(field)?;
+
+    let mut position = Position::Whole;
+    let mut regex = false;
+    let mut all = false;
+    let mut int_operation = None;
+    for modifier in parts {
+        match modifier {
+            "contains" => position = Position::Anywhere,
+            "startswith" => position = Position::Start,
+            "endswith" => position = Position::End,
+            "re" => regex = true,
+            "all" => all = true,
+            "lt" => int_operation = Some(IntOperation::LessThan),
+            "lte" => int_operation = Some(IntOperation::LessThanOrEqual),
+            "gt" => int_operation = Some(IntOperation::GreaterThan),
+            "gte" => int_operation = Some(IntOperation::GreaterThanOrEqual),
+            other => return Err(SigmaError::UnsupportedModifier(other.to_owned())),
+        }
+    }
+
+    let values = match values {
+        Value::Sequence(values) => values.iter().collect(),
+        value => vec![value],
+    };
+
+    let invalid = |reason: &str| SigmaError::InvalidValue {
+        field: field.to_owned(),
+        reason: reason.to_owned(),
+    };
+
+    let exprs = values
+        .into_iter()
+        .map(|value| {
+            let value = scalar_to_string(value).ok_or_else(|| invalid("expected a scalar"))?;
+
+            let (path, property_name, kind) = match field_mapping {
+                FieldMapping::Constant(constant) => {
+                    if position != Position::Whole || regex || int_operation.is_some() {
+                        return Err(invalid("only plain values can be matched"));
+                    }
+                    return Ok(Expr::Constant(value.eq_ignore_ascii_case(constant)));
+                }
+                FieldMapping::Property {
+                    path,
+                    property_name,
+                    kind,
+                } => (path, property_name, kind),
+            };
+
+            let filter = match kind {
+                PropertyKind::String if int_operation.is_some() => {
+                    return Err(invalid("lt, lte, gt and gte apply only to numbers"))
+                }
+                PropertyKind::String if regex => Filter::String {
+                    operation: StringOperation::Regex,
+                    value,
+                },
+                PropertyKind::String => string_filter(&value, position),
+                PropertyKind::Int if regex || position != Position::Whole => {
+                    return Err(invalid("string modifiers don't apply to numbers"))
+                }
+                PropertyKind::Int => Filter::Int {
+                    operation: int_operation.clone().unwrap_or(IntOperation::Equal),
+                    value: value.parse().map_err(|_| invalid("expected an integer"))?,
+                },
+            };
+
+            Ok(Expr::Atom(Atom {
+                path,
+                property_name,
+                filter,
+            }))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(if all {
+        Expr::And(exprs)
+    } else {
+        Expr::Or(exprs)
+    })
+}
+
+fn scalar_to_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(value) => Some(value.clone()),
+        Value::Number(value) => Some(value.to_string()),
+        Value::Bool(value) => Some(value.to_string()),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Piece {
+    Char(char),
+    /// `*`, any sequence of characters
+    Any,
+    /// `?`, any single character
+    One,
+}
+
+/// Converts a value with Sigma's `*` and `?` wildcards, which `\` escapes, to
+/// an equivalent filter. Sigma matches strings case-insensitively, which
+/// Grapl's other string operations don't, so anything other than a lone `*`
+/// compiles to a `(?i)` regex.
+fn string_filter(value: &str, position: Position) -> Filter {
+    let mut pieces = Vec::new();
+    if matches!(position, Position::Anywhere | Position::End) {
+        pieces.push(Piece::Any);
+    }
+
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if matches!(chars.peek(), Some('*' | '?' | '\\')) => {
+                pieces.push(Piece::Char(chars.next().unwrap_or(c)))
+            }
+            '*' if pieces.last() != Some(&Piece::Any) => pieces.push(Piece::Any),
+            '*' => {}
+            '?' => pieces.push(Piece::One),
+            c => pieces.push(Piece::Char(c)),
+        }
+    }
+
+    if matches!(position, Position::Anywhere | Position::Start)
+        && pieces.last() != Some(&Piece::Any)
+    {
+        pieces.push(Piece::Any);
+    }
+
+    let (operation, value) = match pieces.as_slice() {
+        [Piece::Any] => (StringOperation::Has, String::new()),
+        pieces => (StringOperation::Regex, to_regex(pieces)),
+    };
+
+    Filter::String { operation, value }
+}
+
+fn to_regex(pieces: &[Piece]) -> String {
+    let mut regex = String::from("(?i)");
+
+    let (pieces, anchored_start) = match pieces {
+        [Piece::Any, rest @ ..] => (rest, false),
+        pieces => (pieces, true),
+    };
+    let (pieces, anchored_end) = match pieces {
+        [rest @ .., Piece::Any] => (rest, false),
+        pieces => (pieces, true),
+    };
+
+    if anchored_start {
+        regex.push('^');
+    }
+    for piece in pieces {
+        match piece {
+            Piece::Char(c) => {
+                if "\\.+*?()|[]{}^$#&-~".contains(*c) {
+                    regex.push('\\');
+                }
+                regex.push(*c);
+            }
+            Piece::Any => regex.push_str(".*"),
+            Piece::One => regex.push('.'),
+        }
+    }
+    if anchored_end {
+        regex.push('$');
+    }
+
+    regex
+}
+
+/// Pushes negation down to the atoms and distributes `and` over `or`,
+/// returning a list of conjunctions of which any must match.
+fn to_dnf(expr: &Expr, negated: bool) -> Result<Vec<Vec<Literal>>, SigmaError> {
+    match (expr, negated) {
+        (Expr::Constant(value), negated) => Ok(if *value != negated {
+            vec![vec![]]
+        } else {
+            vec![]
+        }),
+        (Expr::Atom(atom), negated) => Ok(vec![vec![(atom.clone(), negated)]]),
+        (Expr::Not(inner), negated) => to_dnf(inner, !negated),
+        (Expr::Or(exprs), false) | (Expr::And(exprs), true) => {
+            let mut conjunctions = Vec::new();
+            for expr in exprs {
+                conjunctions.extend(to_dnf(expr, negated)?);
+                check_query_count(conjunctions.len())?;
+            }
+            Ok(conjunctions)
+        }
+        (Expr::And(exprs), false) | (Expr::Or(exprs), true) => {
+            let mut conjunctions = vec![vec![]];
+            for expr in exprs {
+                let terms = to_dnf(expr, negated)?;
+                check_query_count(conjunctions.len() * terms.len())?;
+                conjunctions = conjunctions
+                    .iter()
+                    .flat_map(|conjunction| {
+                        terms.iter().map(move |term| {
+                            let mut conjunction: Vec<Literal> = conjunction.clone();
+                            conjunction.extend(term.iter().cloned());
+                            conjunction
+                        })
+                    })
+                    .collect();
+            }
+            Ok(conjunctions)
+        }
+    }
+}
+
+fn check_query_count(count: usize) -> Result<(), SigmaError> {
+    if count > MAX_QUERIES_PER_RULE {
+        Err(SigmaError::TooComplex(MAX_QUERIES_PER_RULE))
+    } else {
+        Ok(())
+    }
+}
+
+/// The filters of a node in a query, and the nodes connected to it.
+#[derive(Default)]
+struct QueryTree {
+    string_filters: BTreeMap<&'static str, Vec<StringFilter>>,
+    int_filters: BTreeMap<&'static str, Vec<IntFilter>>,
+    neighbors: BTreeMap<&'static str, (Hop, QueryTree)>,
+}
+
+impl QueryTree {
+    fn insert(
+        &mut self,
+        path: &'static [Hop],
+        property_name: &'static str,
+        filter: Filter,
+        negated: bool,
+    ) {
+        match path.split_first() {
+            Some((hop, rest)) => self
+                .neighbors
+                .entry(hop.edge_name)
+                .or_insert_with(|| (*hop, QueryTree::default()))
+                .1
+                .insert(rest, property_name, filter, negated),
+            None => match filter {
+                Filter::String { operation, value } => self
+                    .string_filters
+                    .entry(property_name)
+                    .or_default()
+                    .push(StringFilter {
+                        operation,
+                        value,
+                        negated,
+                    }),
+                Filter::Int { operation, value } => self
+                    .int_filters
+                    .entry(property_name)
+                    .or_default()
+                    .push(IntFilter {
+                        operation,
+                        value,
+                        negated,
+                    }),
+            },
+        }
+    }
+
+    fn into_query(self, node_type: &str) -> NodeQueryBuilder {
+        let mut query = NodeQueryBuilder::new(NodeType {
+            value: node_type.to_owned(),
+        });
+
+        for (property_name, string_filters) in self.string_filters {
+            query.with_string_filters(
+                PropertyName::new_unchecked(property_name.to_owned()),
+                AndStringFilters { string_filters },
+            );
+        }
+        for (property_name, int_filters) in self.int_filters {
+            query.with_int_filters(
+                PropertyName::new_unchecked(property_name.to_owned()),
+                AndIntFilters { int_filters },
+            );
+        }
+        for (hop, neighbor) in self.neighbors.into_values() {
+            query.with_edge(
+                EdgeName {
+                    value: hop.edge_name.to_owned(),
+                },
+                EdgeName {
+                    value: hop.reverse_edge_name.to_owned(),
+                },
+                neighbor.into_query(hop.node_type),
+            );
+        }
+
+        query
+    }
+}
+
+fn compile_conjunction(mapping: &LogSourceMapping, conjunction: Vec<Literal>) -> CompiledQuery {
+    let mut tree = QueryTree::default();
+    let mut property_names = BTreeSet::new();
+    let mut edge_names = BTreeSet::new();
+
+    for (atom, negated) in conjunction {
+        property_names.insert(atom.property_name);
+        for hop in atom.path {
+            edge_names.insert(hop.edge_name);
+            edge_names.insert(hop.reverse_edge_name);
+        }
+        tree.insert(atom.path, atom.property_name, atom.filter, negated);
+    }
+
+    CompiledQuery {
+        query: tree.into_query(mapping.root_node_type),
+        property_names,
+        edge_names,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compile(yaml: &str) -> Result<CompiledRule, SigmaError> {
+        compile_rule(&SigmaRule::from_yaml(yaml)?)
+    }
+
+    fn string(operation: StringOperation, value: &str) -> Filter {
+        Filter::String {
+            operation,
+            value: value.to_owned(),
+        }
+    }
+
+    #[test]
+    fn converts_wildcards_to_case_insensitive_regexes() {
+        use StringOperation::*;
+
+        assert_eq!(
+            string_filter("cmd.exe", Position::Whole),
+            string(Regex, "(?i)^cmd\\.exe$")
+        );
+        assert_eq!(string_filter("*", Position::Whole), string(Has, ""));
+        assert_eq!(
+            string_filter("/c", Position::Anywhere),
+            string(Regex, "(?i)/c")
+        );
+        assert_eq!(
+            string_filter("*/c*", Position::Whole),
+            string(Regex, "(?i)/c")
+        );
+        assert_eq!(
+            string_filter("\\cmd.exe", Position::End),
+            string(Regex, "(?i)\\\\cmd\\.exe$")
+        );
+        assert_eq!(
+            string_filter("C:\\Users\\\\*\\AppData?", Position::Start),
+            string(Regex, "(?i)^C:\\\\Users\\\\.*\\\\AppData.")
+        );
+        assert_eq!(
+            string_filter("a\\*b", Position::Whole),
+            string(Regex, "(?i)^a\\*b$")
+        );
+    }
+
+    #[test]
+    fn compiles_process_creation_to_a_query_per_conjunction() -> Result<(), SigmaError> {
+        let rule = compile(
+            r#"
+title: Whoami Execution From Office
+id: 502b42de-4306-40b4-9596-6f590c81f073
+logsource:
+    category: process_creation
+    product: windows
+detection:
+    selection_img:
+        Image|endswith: '\whoami.exe'
+    selection_parent:
+        ParentImage|endswith:
+            - '\winword.exe'
+            - '\excel.exe'
+    filter:
+        CommandLine|contains: '/help'
+    condition: all of selection_* and not filter
+level: high
+"#,
+        )?;
+
+        assert_eq!(rule.category, "process_creation");
+        assert_eq!(rule.level.map(Level::score), Some(75));
+        assert_eq!(rule.queries.len(), 2);
+
+        let query = &rule.queries[0];
+        assert_eq!(
+            query.property_names,
+            ["file_path", "process_command_line"].into_iter().collect()
+        );
+        assert!(query.edge_names.contains("parent"));
+
+        let graph_query = query.query.build();
+        assert_eq!(graph_query.node_property_queries.len(), 4);
+        let root = &graph_query.node_property_queries[&graph_query.root_query_id];
+        assert_eq!(root.node_type.value, "Process");
+        let command_line = &root.string_filters
+            [&PropertyName::new_unchecked("process_command_line".to_owned())]
+            .and_string_filters[0]
+            .string_filters[0];
+        assert_eq!(command_line.operation, StringOperation::Regex);
+        assert_eq!(command_line.value, "(?i)/help");
+        assert!(command_line.negated);
+
+        Ok(())
+    }
+
+    #[test]
+    fn compiles_network_connection_int_fields_and_constants() -> Result<(), SigmaError> {
+        let rule = compile(
+            r#"
+title: Outbound RDP
+logsource:
+    category: network_connection
+detection:
+    selection:
+        Initiated: 'true'
+        DestinationPort:
+            - 3389
+            - '5900'
+        SourcePort|gte: 1024
+    condition: selection
+"#,
+        )?;
+
+        assert_eq!(rule.queries.len(), 2);
+        let graph_query = rule.queries[1].query.build();
+        let port = graph_query
+            .node_property_queries
+            .values()
+            .find(|query| query.node_type.value == "IpPort")
+            .expect("no IpPort node");
+        let filter = &port.int_filters[&PropertyName::new_unchecked("port".to_owned())]
+            .and_int_filters[0]
+            .int_filters[0];
+        assert_eq!(
+            (filter.operation.clone(), filter.value),
+            (IntOperation::Equal, 5900)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_what_cannot_be_queried() {
+        let rule = |detection: &str| {
+            compile(&format!(
+                "title: t\nlogsource:\n    category: file_event\ndetection:\n{detection}"
+            ))
+        };
+
+        assert!(matches!(
+            rule("    selection:\n        User: SYSTEM\n    condition: selection"),
+            Err(SigmaError::UnsupportedField { .. })
+        ));
+        assert!(matches!(
+            rule("    selection:\n        TargetFilename|base64: x\n    condition: selection"),
+            Err(SigmaError::UnsupportedModifier(_))
+        ));
+        assert!(matches!(
+            rule("    keywords:\n        - mimikatz\n    condition: keywords"),
+            Err(SigmaError::Unsupported(_))
+        ));
+        assert!(matches!(
+            rule("    selection:\n        ProcessId: abc\n    condition: selection"),
+            Err(SigmaError::InvalidValue { .. })
+        ));
+        assert!(matches!(
+            rule("    selection:\n        TargetFilename: x\n    condition: selektion"),
+            Err(SigmaError::UnknownSearchIdentifier(_))
+        ));
+    }
+}
@@ -0,0 +1,265 @@
+use crate::error::SigmaError;
+
+/// A parsed `detection.condition`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConditionExpr {
+    Identifier(String),
+    OneOf(Selector),
+    AllOf(Selector),
+    Not(Box<ConditionExpr>),
+    And(Box<ConditionExpr>, Box<ConditionExpr>),
+    Or(Box<ConditionExpr>, Box<ConditionExpr>),
+}
+
+/// What an `1 of`/`all of` quantifier ranges over.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Selector {
+    Them,
+    /// A search identifier name, where `*` matches any sequence of characters.
+    Pattern(String),
+}
+
+impl Selector {
+    pub fn matches(&self, identifier: &str) -> bool {
+        match self {
+            Selector::Them => !identifier.starts_with('_'),
+            Selector::Pattern(pattern) => glob_matches(pattern, identifier),
+        }
+    }
+}
+
+fn glob_matches(pattern: &str, identifier: &str) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == identifier,
+        Some((prefix, rest)) => {
+            let identifier = match identifier.strip_prefix(prefix) {
+                Some(identifier) => identifier,
+                None => return false,
+            };
+            (0..=identifier.len())
+                .filter(|i| identifier.is_char_boundary(*i))
+                .any(|i| glob_matches(rest, &identifier[i..]))
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token<'a> {
+    OpenParen,
+    CloseParen,
+    Pipe,
+    Word(&'a str),
+}
+
+/// Parses a condition, where `not` binds tighter than `and`, which binds
+/// tighter than `or`. Aggregations (`| count() > 5` etc.) aren't supported.
+pub fn parse_condition(input: &str) -> Result<ConditionExpr, SigmaError> {
+    let mut parser = Parser {
+        tokens: tokenize(input),
+        index: 0,
+        input_len: input.len(),
+    };
+    let expr = parser.parse_or()?;
+
+    match parser.peek() {
+        None => Ok(expr),
+        Some((_, Token::Pipe)) => Err(SigmaError::Unsupported("aggregation conditions")),
+        Some((position, _)) => Err(SigmaError::InvalidCondition {
+            position,
+            reason: "expected and, or or the end of the condition".to_owned(),
+        }),
+    }
+}
+
+fn tokenize(input: &str) -> Vec<(usize, Token<'_>)> {
+    let mut tokens = Vec::new();
+    let mut word_start = None;
+
+    for (i, c) in input.char_indices() {
+        let token = match c {
+            '(' => Some(Token::OpenParen),
+            ')' => Some(Token::CloseParen),
+            '|' => Some(Token::Pipe),
+            c if c.is_whitespace() => None,
+            _ => {
+                word_start.get_or_insert(i);
+                continue;
+            }
+        };
+
+        if let Some(start) = word_start.take() {
+            tokens.push((start, Token::Word(&input[start..i])));
+        }
+        if let Some(token) = token {
+            tokens.push((i, token));
+        }
+    }
+
+    if let Some(start) = word_start {
+        tokens.push((start, Token::Word(&input[start..])));
+    }
+
+    tokens
+}
+
+struct Parser<'a> {
+    tokens: Vec<(usize, Token<'a>)>,
+    index: usize,
+    input_len: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<(usize, Token<'a>)> {
+        self.tokens.get(self.index).cloned()
+    }
+
+    fn next(&mut self) -> Option<(usize, Token<'a>)> {
+        let token = self.peek();
+        self.index += 1;
+        token
+    }
+
+    fn peek_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some((_, Token::Word(word))) if word.eq_ignore_ascii_case(keyword))
+    }
+
+    fn error(&self, position: Option<usize>, reason: &str) -> SigmaError {
+        SigmaError::InvalidCondition {
+            position: position.unwrap_or(self.input_len),
+            reason: reason.to_owned(),
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<ConditionExpr, SigmaError> {
+        let mut expr = self.parse_and()?;
+        while self.peek_keyword("or") {
+            self.next();
+            expr = ConditionExpr::Or(Box::new(expr), Box::new(self.parse_and()?));
+        }
+        Ok(expr)
+    }
+
+    fn parse_and(&mut self) -> Result<ConditionExpr, SigmaError> {
+        let mut expr = self.parse_not()?;
+        while self.peek_keyword("and") {
+            self.next();
+            expr = ConditionExpr::And(Box::new(expr), Box::new(self.parse_not()?));
+        }
+        Ok(expr)
+    }
+
+    fn parse_not(&mut self) -> Result<ConditionExpr, SigmaError> {
+        if self.peek_keyword("not") {
+            self.next();
+            return Ok(ConditionExpr::Not(Box::new(self.parse_not()?)));
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<ConditionExpr, SigmaError> {
+        match self.next() {
+            Some((_, Token::OpenParen)) => {
+                let expr = self.parse_or()?;
+                match self.next() {
+                    Some((_, Token::CloseParen)) => Ok(expr),
+                    other => Err(self.error(other.map(|(position, _)| position), "expected )")),
+                }
+            }
+            Some((position, Token::Word(word))) => {
+                let is_keyword = |keyword: &str| word.eq_ignore_ascii_case(keyword);
+                if is_keyword("1") || is_keyword("any") || is_keyword("all") {
+                    let selector = self.parse_selector()?;
+                    if is_keyword("all") {
+                        Ok(ConditionExpr::AllOf(selector))
+                    } else {
+                        Ok(ConditionExpr::OneOf(selector))
+                    }
+                } else if ["and", "or", "not", "of", "them"]
+                    .iter()
+                    .any(|k| is_keyword(k))
+                {
+                    Err(self.error(Some(position), "expected a search identifier"))
+                } else {
+                    Ok(ConditionExpr::Identifier(word.to_owned()))
+                }
+            }
+            other => Err(self.error(
+                other.map(|(position, _)| position),
+                "expected a search identifier",
+            )),
+        }
+    }
+
+    fn parse_selector(&mut self) -> Result<Selector, SigmaError> {
+        match self.next() {
+            Some((_, Token::Word(word))) if word.eq_ignore_ascii_case("of") => {}
+            other => return Err(self.error(other.map(|(position, _)| position), "expected of")),
+        }
+
+        match self.next() {
+            Some((_, Token::Word(word))) if word.eq_ignore_ascii_case("them") => Ok(Selector::Them),
+            Some((_, Token::Word(word))) => Ok(Selector::Pattern(word.to_owned())),
+            other => Err(self.error(
+                other.map(|(position, _)| position),
+                "expected them or a search identifier pattern",
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn identifier(name: &str) -> Box<ConditionExpr> {
+        Box::new(ConditionExpr::Identifier(name.to_owned()))
+    }
+
+    #[test]
+    fn not_binds_tighter_than_and_than_or() {
+        let expr = parse_condition("a or b and not c").unwrap();
+
+        assert_eq!(
+            expr,
+            ConditionExpr::Or(
+                identifier("a"),
+                Box::new(ConditionExpr::And(
+                    identifier("b"),
+                    Box::new(ConditionExpr::Not(identifier("c")))
+                ))
+            )
+        );
+    }
+
+    #[test]
+    fn parses_quantifiers_and_parentheses() {
+        let expr = parse_condition("(1 of selection_*) and not all of them").unwrap();
+
+        assert_eq!(
+            expr,
+            ConditionExpr::And(
+                Box::new(ConditionExpr::OneOf(Selector::Pattern(
+                    "selection_*".to_owned()
+                ))),
+                Box::new(ConditionExpr::Not(Box::new(ConditionExpr::AllOf(
+                    Selector::Them
+                ))))
+            )
+        );
+        assert!(Selector::Pattern("selection_*".to_owned()).matches("selection_img"));
+        assert!(!Selector::Pattern("selection_*".to_owned()).matches("filter"));
+    }
+
+    #[test]
+    fn reports_the_position_of_errors() {
+        match parse_condition("selection and (filter or") {
+            Err(SigmaError::InvalidCondition { position, .. }) => assert_eq!(position, 24),
+            other => panic!("expected an invalid condition, got {other:?}"),
+        }
+
+        assert!(matches!(
+            parse_condition("selection | count() > 5"),
+            Err(SigmaError::Unsupported(_))
+        ));
+    }
+}
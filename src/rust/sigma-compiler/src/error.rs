@@ -0,0 +1,35 @@
+#[derive(thiserror::Error, Debug)]
+pub enum SigmaError {
+    #[error("failed to parse rule: {0}")]
+    Parse(#[from] serde_yaml::Error),
+
+    #[error("unsupported logsource {0:?}, expected one of process_creation, network_connection or file_event")]
+    UnsupportedLogSource(Option<String>),
+
+    #[error("field {field} is not supported for {category}")]
+    UnsupportedField {
+        category: &'static str,
+        field: String,
+    },
+
+    #[error("modifier {0} is not supported")]
+    UnsupportedModifier(String),
+
+    #[error("invalid value for {field}: {reason}")]
+    InvalidValue { field: String, reason: String },
+
+    #[error("search identifier {name} is malformed: {reason}")]
+    InvalidSearchIdentifier { name: String, reason: String },
+
+    #[error("condition refers to unknown search identifier {0}")]
+    UnknownSearchIdentifier(String),
+
+    #[error("invalid condition at position {position}: {reason}")]
+    InvalidCondition { position: usize, reason: String },
+
+    #[error("{0} are not supported")]
+    Unsupported(&'static str),
+
+    #[error("rule expands to more than {0} queries")]
+    TooComplex(usize),
+}
@@ -0,0 +1,38 @@
+//! Compiles Sigma rules to graph queries, so that community rule sets can be
+//! run as analyzers against the Grapl graph rather than raw logs.
+//!
+//! Rules over the `process_creation`, `network_connection` and `file_event`
+//! logsource categories are supported, with their fields mapped to
+//! endpoint-plugin node properties by [`logsource`]. Each conjunction of a
+//! rule's condition becomes one `GraphQuery`; the rule matches if any of them
+//! does.
+//!
+//! Sigma matches values case-insensitively, which Grapl's equality and
+//! substring comparisons don't, so string values, with or without
+//! `contains`, `startswith`, `endswith` or wildcards, compile to `(?i)` regex
+//! filters. Values with the `re` modifier are used as written.
+pub mod analyzer;
+pub mod compile;
+pub mod condition;
+pub mod error;
+pub mod logsource;
+pub mod rule;
+
+pub use crate::{
+    analyzer::SigmaAnalyzer,
+    compile::{
+        compile_rule,
+        CompiledQuery,
+        CompiledRule,
+    },
+    error::SigmaError,
+    rule::SigmaRule,
+};
+
+/// Parses and compiles every rule in a multi-document YAML stream.
+pub fn compile_rules(yaml: &str) -> Result<Vec<CompiledRule>, SigmaError> {
+    SigmaRule::from_yaml_documents(yaml)?
+        .iter()
+        .map(compile_rule)
+        .collect()
+}
@@ -0,0 +1,179 @@
+//! Maps the fields of each supported Sigma logsource category to the
+//! endpoint-plugin node properties that the Sysmon and osquery generators
+//! populate.
+//!
+//! Each category is rooted at the node the events are about, and fields of
+//! related nodes are reached by following edges from it: `ParentImage` in
+//! `process_creation` is the `file_path` of the `binary_file` of the
+//! `parent` of the root `Process`.
+use crate::error::SigmaError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PropertyKind {
+    String,
+    Int,
+}
+
+/// An edge to follow from one node to another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hop {
+    pub edge_name: &'static str,
+    pub reverse_edge_name: &'static str,
+    pub node_type: &'static str,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldMapping {
+    Property {
+        path: &'static [Hop],
+        property_name: &'static str,
+        kind: PropertyKind,
+    },
+    /// A field that always has this value for the events in the graph, e.g.
+    /// `Initiated` is always `true` because only outbound connections are
+    /// recorded.
+    Constant(&'static str),
+}
+
+#[derive(Debug)]
+pub struct LogSourceMapping {
+    pub category: &'static str,
+    pub root_node_type: &'static str,
+    pub fields: &'static [(&'static str, FieldMapping)],
+}
+
+impl LogSourceMapping {
+    pub fn for_category(category: Option<&str>) -> Result<&'static Self, SigmaError> {
+        [&PROCESS_CREATION, &NETWORK_CONNECTION, &FILE_EVENT]
+            .into_iter()
+            .find(|mapping| Some(mapping.category) == category)
+            .ok_or_else(|| SigmaError::UnsupportedLogSource(category.map(str::to_owned)))
+    }
+
+    pub fn field(&self, field: &str) -> Result<FieldMapping, SigmaError> {
+        self.fields
+            .iter()
+            .find(|(name, _)| *name == field)
+            .map(|(_, mapping)| *mapping)
+            .ok_or_else(|| SigmaError::UnsupportedField {
+                category: self.category,
+                field: field.to_owned(),
+            })
+    }
+}
+
+const BINARY_FILE: Hop = Hop {
+    edge_name: "binary_file",
+    reverse_edge_name: "executed_as_processes",
+    node_type: "File",
+};
+
+const PARENT: Hop = Hop {
+    edge_name: "parent",
+    reverse_edge_name: "children",
+    node_type: "Process",
+};
+
+const PROCESS_ASSET: Hop = Hop {
+    edge_name: "process_asset",
+    reverse_edge_name: "asset_processes",
+    node_type: "Asset",
+};
+
+const CREATED_CONNECTIONS: Hop = Hop {
+    edge_name: "created_connections",
+    reverse_edge_name: "creator_process",
+    node_type: "ProcessOutboundConnection",
+};
+
+const CONNECTED_TO: Hop = Hop {
+    edge_name: "connected_to",
+    reverse_edge_name: "connected_from",
+    node_type: "IpPort",
+};
+
+const CREATED_BY_PROCESS: Hop = Hop {
+    edge_name: "created_by_process",
+    reverse_edge_name: "created_files",
+    node_type: "Process",
+};
+
+const FILE_ASSET: Hop = Hop {
+    edge_name: "file_asset",
+    reverse_edge_name: "files_on_asset",
+    node_type: "Asset",
+};
+
+const fn string(path: &'static [Hop], property_name: &'static str) -> FieldMapping {
+    FieldMapping::Property {
+        path,
+        property_name,
+        kind: PropertyKind::String,
+    }
+}
+
+const fn int(path: &'static [Hop], property_name: &'static str) -> FieldMapping {
+    FieldMapping::Property {
+        path,
+        property_name,
+        kind: PropertyKind::Int,
+    }
+}
+
+pub static PROCESS_CREATION: LogSourceMapping = LogSourceMapping {
+    category: "process_creation",
+    root_node_type: "Process",
+    fields: &[
+        ("Image", string(&[BINARY_FILE], "file_path")),
+        ("Description", string(&[BINARY_FILE], "file_description")),
+        ("Product", string(&[BINARY_FILE], "file_product")),
+        ("Company", string(&[BINARY_FILE], "file_company")),
+        ("CommandLine", string(&[], "process_command_line")),
+        ("ProcessId", int(&[], "process_id")),
+        ("ProcessGuid", string(&[], "process_guid")),
+        ("ParentImage", string(&[PARENT, BINARY_FILE], "file_path")),
+        (
+            "ParentCommandLine",
+            string(&[PARENT], "process_command_line"),
+        ),
+        ("ParentProcessId", int(&[PARENT], "process_id")),
+        ("ParentProcessGuid", string(&[PARENT], "process_guid")),
+        ("Computer", string(&[PROCESS_ASSET], "hostname")),
+    ],
+};
+
+pub static NETWORK_CONNECTION: LogSourceMapping = LogSourceMapping {
+    category: "network_connection",
+    root_node_type: "Process",
+    fields: &[
+        ("Image", string(&[BINARY_FILE], "file_path")),
+        ("ProcessId", int(&[], "process_id")),
+        ("Initiated", FieldMapping::Constant("true")),
+        ("Protocol", string(&[CREATED_CONNECTIONS], "protocol")),
+        ("SourceIp", string(&[CREATED_CONNECTIONS], "ip_address")),
+        ("SourcePort", int(&[CREATED_CONNECTIONS], "port")),
+        (
+            "DestinationIp",
+            string(&[CREATED_CONNECTIONS, CONNECTED_TO], "ip_address"),
+        ),
+        (
+            "DestinationPort",
+            int(&[CREATED_CONNECTIONS, CONNECTED_TO], "port"),
+        ),
+        ("Computer", string(&[PROCESS_ASSET], "hostname")),
+    ],
+};
+
+pub static FILE_EVENT: LogSourceMapping = LogSourceMapping {
+    category: "file_event",
+    root_node_type: "File",
+    fields: &[
+        ("TargetFilename", string(&[], "file_path")),
+        (
+            "Image",
+            string(&[CREATED_BY_PROCESS, BINARY_FILE], "file_path"),
+        ),
+        ("ProcessId", int(&[CREATED_BY_PROCESS], "process_id")),
+        ("Computer", string(&[FILE_ASSET], "hostname")),
+    ],
+};
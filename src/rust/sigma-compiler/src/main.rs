@@ -0,0 +1,63 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+use sigma_compiler::{
+    compile_rule,
+    SigmaRule,
+};
+
+#[derive(clap::Parser, Debug)]
+#[clap(
+    name = "sigma-compiler",
+    about = "Checks that Sigma rules compile to graph queries"
+)]
+struct SigmaCompilerConfig {
+    /// Sigma rule files
+    rules: Vec<PathBuf>,
+
+    /// Print the compiled queries of each rule
+    #[clap(long)]
+    verbose: bool,
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let config = SigmaCompilerConfig::parse();
+    let mut failures = 0;
+
+    for path in &config.rules {
+        let compiled = std::fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|yaml| SigmaRule::from_yaml(&yaml).map_err(|e| e.to_string()))
+            .and_then(|rule| compile_rule(&rule).map_err(|e| e.to_string()));
+
+        match compiled {
+            Ok(rule) => {
+                println!(
+                    "{}: {} ({} queries)",
+                    path.display(),
+                    rule.title,
+                    rule.queries.len()
+                );
+                if config.verbose {
+                    for query in &rule.queries {
+                        println!("{:#?}", query.query.build());
+                    }
+                }
+            }
+            Err(e) => {
+                failures += 1;
+                println!("{}: {e}", path.display());
+            }
+        }
+    }
+
+    if failures > 0 {
+        return Err(format!(
+            "{failures} of {} rules failed to compile",
+            config.rules.len()
+        )
+        .into());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,95 @@
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+
+use crate::error::SigmaError;
+
+/// The parts of a Sigma rule that the compiler reads. See
+/// https://github.com/SigmaHQ/sigma-specification for the full format.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SigmaRule {
+    pub title: String,
+    #[serde(default)]
+    pub id: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub logsource: LogSource,
+    pub detection: Detection,
+    #[serde(default)]
+    pub level: Option<Level>,
+}
+
+impl SigmaRule {
+    pub fn from_yaml(yaml: &str) -> Result<Self, SigmaError> {
+        Ok(serde_yaml::from_str(yaml)?)
+    }
+
+    /// Parses every rule in a multi-document YAML stream, i.e. rules
+    /// separated by `---` lines.
+    pub fn from_yaml_documents(yaml: &str) -> Result<Vec<Self>, SigmaError> {
+        serde_yaml::Deserializer::from_str(yaml)
+            .map(|document| Ok(SigmaRule::deserialize(document)?))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LogSource {
+    #[serde(default)]
+    pub category: Option<String>,
+    #[serde(default)]
+    pub product: Option<String>,
+    #[serde(default)]
+    pub service: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Detection {
+    pub condition: Condition,
+    #[serde(default)]
+    pub timeframe: Option<String>,
+    /// Selections and filters, keyed by the names the condition refers to
+    /// them by.
+    #[serde(flatten)]
+    pub search_identifiers: BTreeMap<String, serde_yaml::Value>,
+}
+
+/// Rules may list several conditions, any of which matches.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum Condition {
+    One(String),
+    Any(Vec<String>),
+}
+
+impl Condition {
+    pub fn as_slice(&self) -> &[String] {
+        match self {
+            Condition::One(condition) => std::slice::from_ref(condition),
+            Condition::Any(conditions) => conditions,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Level {
+    Informational,
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl Level {
+    /// The score reported with an analyzer hit for this level.
+    pub fn score(self) -> i32 {
+        match self {
+            Level::Informational => 10,
+            Level::Low => 25,
+            Level::Medium => 50,
+            Level::High => 75,
+            Level::Critical => 100,
+        }
+    }
+}
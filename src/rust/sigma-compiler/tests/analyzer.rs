@@ -0,0 +1,126 @@
+use analyzer_sdk::test_utils::{
+    graph_query_proxy::InMemoryGraphQueryProxy,
+    in_memory_graph::InMemoryGraph,
+    test_ctx::AnalyzerTestContext,
+};
+use rust_proto::graplinc::grapl::api::{
+    graph::v1beta1::{
+        GraphDescription,
+        ImmutableStrProp,
+        NodeDescription,
+    },
+    plugin_sdk::analyzers::v1beta1::messages::{
+        ExecutionHit,
+        ExecutionResult,
+        RunAnalyzerRequest,
+    },
+};
+use sigma_compiler::{
+    compile_rules,
+    SigmaAnalyzer,
+};
+use test_context::test_context;
+
+const WHOAMI_FROM_OFFICE: &str = include_str!("../rules/proc_creation_whoami_from_office.yml");
+
+fn node(node_key: &str, node_type: &str, property_name: &str, value: &str) -> NodeDescription {
+    let mut node = NodeDescription {
+        properties: Default::default(),
+        node_key: node_key.to_owned(),
+        node_type: node_type.to_owned(),
+        id_strategy: vec![],
+    };
+    node.set_property(property_name, ImmutableStrProp::from(value));
+    node
+}
+
+/// A process running `image`, whose parent runs `parent_image`.
+fn process_tree(parent_image: &str, image: &str) -> GraphDescription {
+    let mut graph = GraphDescription::new();
+    graph.add_node(node("parent", "Process", "process_name", "parent"));
+    graph.add_node(node("child", "Process", "process_name", "child"));
+    graph.add_node(node("parent_bin", "File", "file_path", parent_image));
+    graph.add_node(node("child_bin", "File", "file_path", image));
+    graph.add_edge("children", "parent", "child");
+    graph.add_edge("binary_file", "parent", "parent_bin");
+    graph.add_edge("binary_file", "child", "child_bin");
+    graph
+}
+
+/// Merges `graph` into an in-memory graph and runs the bundled whoami rule
+/// against every update the merge produced, returning the hits.
+async fn run_whoami_rule(
+    ctx: &mut AnalyzerTestContext,
+    graph: GraphDescription,
+) -> eyre::Result<Vec<ExecutionHit>> {
+    let mut in_memory_graph = InMemoryGraph::new()
+        .with_reverse_edge_name("children", "parent")
+        .with_reverse_edge_name("binary_file", "executed_as_processes");
+    let identified_graph = in_memory_graph.identify(graph);
+    let updates = in_memory_graph.merge(identified_graph);
+
+    let proxy = InMemoryGraphQueryProxy::serve(in_memory_graph).await;
+    let mut client = ctx
+        .get_client(SigmaAnalyzer::new(
+            compile_rules(WHOAMI_FROM_OFFICE)?,
+            proxy.client(),
+        ))
+        .await;
+
+    let mut hits = vec![];
+    for update in updates {
+        let response = client
+            .run_analyzer(RunAnalyzerRequest::new(update), None)
+            .await?;
+        if let ExecutionResult::ExecutionHit(hit) = response.execution_result {
+            hits.push(hit);
+        }
+    }
+
+    proxy.shutdown().await;
+    Ok(hits)
+}
+
+#[test_context(AnalyzerTestContext)]
+#[tokio::test]
+async fn test_rule_hits_on_whoami_spawned_by_word(
+    ctx: &mut AnalyzerTestContext,
+) -> eyre::Result<()> {
+    let hits = run_whoami_rule(
+        ctx,
+        process_tree(
+            r"C:\Program Files\Microsoft Office\root\Office16\winword.exe",
+            r"C:\Windows\System32\whoami.exe",
+        ),
+    )
+    .await?;
+
+    assert!(!hits.is_empty());
+    for hit in &hits {
+        assert_eq!(
+            hit.analyzer_name.value,
+            "Whoami Spawned By An Office Application"
+        );
+        assert_eq!(hit.score, hits[0].score);
+        assert_eq!(hit.idempotency_key, hits[0].idempotency_key);
+    }
+    Ok(())
+}
+
+#[test_context(AnalyzerTestContext)]
+#[tokio::test]
+async fn test_rule_misses_on_whoami_spawned_by_explorer(
+    ctx: &mut AnalyzerTestContext,
+) -> eyre::Result<()> {
+    let hits = run_whoami_rule(
+        ctx,
+        process_tree(
+            r"C:\Windows\explorer.exe",
+            r"C:\Windows\System32\whoami.exe",
+        ),
+    )
+    .await?;
+
+    assert!(hits.is_empty());
+    Ok(())
+}